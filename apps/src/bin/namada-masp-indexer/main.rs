@@ -0,0 +1,17 @@
+use color_eyre::eyre::Result;
+use namada_apps::{cli, logging, masp_indexer};
+use tracing_subscriber::filter::LevelFilter;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // init error reporting
+    color_eyre::install()?;
+
+    // init logging
+    logging::init_from_env_or(LevelFilter::INFO)?;
+
+    let args = cli::namada_masp_indexer_cli();
+    // run the indexer
+    masp_indexer::run(args).await?;
+    Ok(())
+}
@@ -833,6 +833,7 @@ impl BenchShieldedCtx {
         async_runtime
             .block_on(self.shielded.fetch(
                 &self.shell,
+                &StdIo,
                 &[spending_key.into()],
                 &[],
             ))
@@ -853,6 +854,7 @@ impl BenchShieldedCtx {
                     &target,
                     &address::nam(),
                     denominated_amount,
+                    None,
                 ),
             )
             .unwrap()
@@ -162,19 +162,22 @@ pub mod cmds {
     pub enum NamadaRelayer {
         EthBridgePool(EthBridgePool),
         ValidatorSet(ValidatorSet),
+        StateProof(StateProof),
     }
 
     impl Cmd for NamadaRelayer {
         fn add_sub(app: App) -> App {
             app.subcommand(EthBridgePool::def())
                 .subcommand(ValidatorSet::def())
+                .subcommand(StateProof::def())
         }
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             let eth_bridge_pool =
                 SubCmd::parse(matches).map(Self::EthBridgePool);
             let validator_set = SubCmd::parse(matches).map(Self::ValidatorSet);
-            eth_bridge_pool.or(validator_set)
+            let state_proof = SubCmd::parse(matches).map(Self::StateProof);
+            eth_bridge_pool.or(validator_set).or(state_proof)
         }
     }
 
@@ -238,6 +241,7 @@ pub mod cmds {
                 .subcommand(TxMetadataChange::def().display_order(2))
                 // Ethereum bridge transactions
                 .subcommand(AddToEthBridgePool::def().display_order(3))
+                .subcommand(BumpEthBridgePoolFee::def().display_order(3))
                 // PGF transactions
                 .subcommand(TxUpdateStewardCommission::def().display_order(4))
                 .subcommand(TxResignSteward::def().display_order(4))
@@ -246,9 +250,13 @@ pub mod cmds {
                 .subcommand(QueryAccount::def().display_order(5))
                 .subcommand(QueryTransfers::def().display_order(5))
                 .subcommand(QueryConversions::def().display_order(5))
+                .subcommand(QueryShieldedNotes::def().display_order(5))
+                .subcommand(QueryShieldedHistory::def().display_order(5))
+                .subcommand(ShieldedSync::def().display_order(5))
                 .subcommand(QueryBlock::def().display_order(5))
                 .subcommand(QueryBalance::def().display_order(5))
                 .subcommand(QueryBonds::def().display_order(5))
+                .subcommand(QueryWatched::def().display_order(5))
                 .subcommand(QueryBondedStake::def().display_order(5))
                 .subcommand(QuerySlashes::def().display_order(5))
                 .subcommand(QueryDelegations::def().display_order(5))
@@ -266,6 +274,11 @@ pub mod cmds {
                 // Actions
                 .subcommand(SignTx::def().display_order(6))
                 .subcommand(GenIbcShieldedTransafer::def().display_order(6))
+                .subcommand(DiscoverAccounts::def().display_order(6))
+                .subcommand(BundleCreate::def().display_order(6))
+                .subcommand(BundleSign::def().display_order(6))
+                .subcommand(BundleMerge::def().display_order(6))
+                .subcommand(BundleSubmit::def().display_order(6))
                 // Utils
                 .subcommand(Utils::def().display_order(7))
         }
@@ -313,9 +326,15 @@ pub mod cmds {
             let query_transfers = Self::parse_with_ctx(matches, QueryTransfers);
             let query_conversions =
                 Self::parse_with_ctx(matches, QueryConversions);
+            let query_shielded_notes =
+                Self::parse_with_ctx(matches, QueryShieldedNotes);
+            let query_shielded_history =
+                Self::parse_with_ctx(matches, QueryShieldedHistory);
+            let shielded_sync = Self::parse_with_ctx(matches, ShieldedSync);
             let query_block = Self::parse_with_ctx(matches, QueryBlock);
             let query_balance = Self::parse_with_ctx(matches, QueryBalance);
             let query_bonds = Self::parse_with_ctx(matches, QueryBonds);
+            let query_watched = Self::parse_with_ctx(matches, QueryWatched);
             let query_bonded_stake =
                 Self::parse_with_ctx(matches, QueryBondedStake);
             let query_slashes = Self::parse_with_ctx(matches, QuerySlashes);
@@ -339,9 +358,17 @@ pub mod cmds {
             let query_metadata = Self::parse_with_ctx(matches, QueryMetaData);
             let add_to_eth_bridge_pool =
                 Self::parse_with_ctx(matches, AddToEthBridgePool);
+            let bump_eth_bridge_pool_fee =
+                Self::parse_with_ctx(matches, BumpEthBridgePoolFee);
             let sign_tx = Self::parse_with_ctx(matches, SignTx);
             let gen_ibc_shielded =
                 Self::parse_with_ctx(matches, GenIbcShieldedTransafer);
+            let discover_accounts =
+                Self::parse_with_ctx(matches, DiscoverAccounts);
+            let bundle_create = Self::parse_with_ctx(matches, BundleCreate);
+            let bundle_sign = Self::parse_with_ctx(matches, BundleSign);
+            let bundle_merge = Self::parse_with_ctx(matches, BundleMerge);
+            let bundle_submit = Self::parse_with_ctx(matches, BundleSubmit);
             let utils = SubCmd::parse(matches).map(Self::WithoutContext);
             tx_custom
                 .or(tx_transfer)
@@ -365,14 +392,19 @@ pub mod cmds {
                 .or(redelegate)
                 .or(claim_rewards)
                 .or(add_to_eth_bridge_pool)
+                .or(bump_eth_bridge_pool_fee)
                 .or(tx_update_steward_commission)
                 .or(tx_resign_steward)
                 .or(query_epoch)
                 .or(query_transfers)
                 .or(query_conversions)
+                .or(query_shielded_notes)
+                .or(query_shielded_history)
+                .or(shielded_sync)
                 .or(query_block)
                 .or(query_balance)
                 .or(query_bonds)
+                .or(query_watched)
                 .or(query_bonded_stake)
                 .or(query_slashes)
                 .or(query_rewards)
@@ -390,6 +422,11 @@ pub mod cmds {
                 .or(query_account)
                 .or(sign_tx)
                 .or(gen_ibc_shielded)
+                .or(discover_accounts)
+                .or(bundle_create)
+                .or(bundle_sign)
+                .or(bundle_merge)
+                .or(bundle_submit)
                 .or(utils)
         }
     }
@@ -450,15 +487,20 @@ pub mod cmds {
         ClaimRewards(ClaimRewards),
         Redelegate(Redelegate),
         AddToEthBridgePool(AddToEthBridgePool),
+        BumpEthBridgePoolFee(BumpEthBridgePoolFee),
         TxUpdateStewardCommission(TxUpdateStewardCommission),
         TxResignSteward(TxResignSteward),
         QueryEpoch(QueryEpoch),
         QueryAccount(QueryAccount),
         QueryTransfers(QueryTransfers),
         QueryConversions(QueryConversions),
+        QueryShieldedNotes(QueryShieldedNotes),
+        QueryShieldedHistory(QueryShieldedHistory),
+        ShieldedSync(ShieldedSync),
         QueryBlock(QueryBlock),
         QueryBalance(QueryBalance),
         QueryBonds(QueryBonds),
+        QueryWatched(QueryWatched),
         QueryBondedStake(QueryBondedStake),
         QueryCommissionRate(QueryCommissionRate),
         QueryMetaData(QueryMetaData),
@@ -474,6 +516,11 @@ pub mod cmds {
         QueryRewards(QueryRewards),
         SignTx(SignTx),
         GenIbcShieldedTransafer(GenIbcShieldedTransafer),
+        DiscoverAccounts(DiscoverAccounts),
+        BundleCreate(BundleCreate),
+        BundleSign(BundleSign),
+        BundleMerge(BundleMerge),
+        BundleSubmit(BundleSubmit),
     }
 
     #[allow(clippy::large_enum_variant)]
@@ -485,6 +532,10 @@ pub mod cmds {
         Address(WalletAddress),
         /// MASP key, address management commands
         Masp(WalletMasp),
+        /// Wallet store encryption commands
+        Store(WalletStore),
+        /// Address book management commands
+        Book(WalletBook),
     }
 
     impl Cmd for NamadaWallet {
@@ -492,13 +543,17 @@ pub mod cmds {
             app.subcommand(WalletKey::def())
                 .subcommand(WalletAddress::def())
                 .subcommand(WalletMasp::def())
+                .subcommand(WalletStore::def())
+                .subcommand(WalletBook::def())
         }
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             let key = SubCmd::parse(matches).map(Self::Key);
             let address = SubCmd::parse(matches).map(Self::Address);
             let masp = SubCmd::parse(matches).map(Self::Masp);
-            key.or(address).or(masp)
+            let store = SubCmd::parse(matches).map(Self::Store);
+            let book = SubCmd::parse(matches).map(Self::Book);
+            key.or(address).or(masp).or(store).or(book)
         }
     }
 
@@ -529,6 +584,7 @@ pub mod cmds {
         Find(KeyFind),
         List(KeyList),
         Export(Export),
+        Serve(KeyServe),
     }
 
     impl SubCmd for WalletKey {
@@ -541,7 +597,13 @@ pub mod cmds {
                 let lookup = SubCmd::parse(matches).map(Self::Find);
                 let list = SubCmd::parse(matches).map(Self::List);
                 let export = SubCmd::parse(matches).map(Self::Export);
-                generate.or(restore).or(lookup).or(list).or(export)
+                let serve = SubCmd::parse(matches).map(Self::Serve);
+                generate
+                    .or(restore)
+                    .or(lookup)
+                    .or(list)
+                    .or(export)
+                    .or(serve)
             })
         }
 
@@ -558,6 +620,7 @@ pub mod cmds {
                 .subcommand(KeyFind::def())
                 .subcommand(KeyList::def())
                 .subcommand(Export::def())
+                .subcommand(KeyServe::def())
         }
     }
 
@@ -668,6 +731,305 @@ pub mod cmds {
         }
     }
 
+    /// Serve the keys of the wallet to remote signer clients
+    #[derive(Clone, Debug)]
+    pub struct KeyServe(pub args::KeyServe);
+
+    impl SubCmd for KeyServe {
+        const CMD: &'static str = "serve";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::KeyServe::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Runs a signing daemon on a local socket, which signs \
+                     with the keys of the wallet on behalf of the clients \
+                     given `--remote-signer`. Only the processes of the \
+                     current user may connect to it.",
+                )
+                .add_args::<args::KeyServe>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub enum WalletBook {
+        AddContact(BookAddContact),
+        Watch(BookWatch),
+        List(BookList),
+        Remove(BookRemove),
+        Export(BookExport),
+        Import(BookImport),
+    }
+
+    impl SubCmd for WalletBook {
+        const CMD: &'static str = "book";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).and_then(|matches| {
+                let add_contact = SubCmd::parse(matches).map(Self::AddContact);
+                let watch = SubCmd::parse(matches).map(Self::Watch);
+                let list = SubCmd::parse(matches).map(Self::List);
+                let remove = SubCmd::parse(matches).map(Self::Remove);
+                let export = SubCmd::parse(matches).map(Self::Export);
+                let import = SubCmd::parse(matches).map(Self::Import);
+                add_contact
+                    .or(watch)
+                    .or(list)
+                    .or(remove)
+                    .or(export)
+                    .or(import)
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Address book management, including methods to label \
+                     counterparties and to watch accounts without their keys.",
+                )
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(BookAddContact::def())
+                .subcommand(BookWatch::def())
+                .subcommand(BookList::def())
+                .subcommand(BookRemove::def())
+                .subcommand(BookExport::def())
+                .subcommand(BookImport::def())
+        }
+    }
+
+    /// Add a contact to the address book
+    #[derive(Clone, Debug)]
+    pub struct BookAddContact(pub args::ContactAdd);
+
+    impl SubCmd for BookAddContact {
+        const CMD: &'static str = "add";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::ContactAdd::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Adds a counterparty with a transparent and/or a payment \
+                     address to the address book.",
+                )
+                .add_args::<args::ContactAdd>()
+        }
+    }
+
+    /// Watch an account without its keys
+    #[derive(Clone, Debug)]
+    pub struct BookWatch(pub args::WatchOnlyAdd);
+
+    impl SubCmd for BookWatch {
+        const CMD: &'static str = "watch";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::WatchOnlyAdd::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Adds a watch-only account to the address book. Its \
+                     balances and bonds are queried with `namadac watched`.",
+                )
+                .add_args::<args::WatchOnlyAdd>()
+        }
+    }
+
+    /// List the address book
+    #[derive(Clone, Debug)]
+    pub struct BookList(pub args::AddressBookList);
+
+    impl SubCmd for BookList {
+        const CMD: &'static str = "list";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::AddressBookList::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Lists the contacts and watch-only accounts.")
+                .add_args::<args::AddressBookList>()
+        }
+    }
+
+    /// Remove an address book entry
+    #[derive(Clone, Debug)]
+    pub struct BookRemove(pub args::AddressBookRemove);
+
+    impl SubCmd for BookRemove {
+        const CMD: &'static str = "remove";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::AddressBookRemove::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Removes a contact or a watch-only account.")
+                .add_args::<args::AddressBookRemove>()
+        }
+    }
+
+    /// Export the address book to a JSON file
+    #[derive(Clone, Debug)]
+    pub struct BookExport(pub args::AddressBookExport);
+
+    impl SubCmd for BookExport {
+        const CMD: &'static str = "export";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::AddressBookExport::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Exports the address book to a JSON file.")
+                .add_args::<args::AddressBookExport>()
+        }
+    }
+
+    /// Import an address book from a JSON file
+    #[derive(Clone, Debug)]
+    pub struct BookImport(pub args::AddressBookImport);
+
+    impl SubCmd for BookImport {
+        const CMD: &'static str = "import";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::AddressBookImport::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Imports the contacts and watch-only accounts of an \
+                     address book exported to a JSON file.",
+                )
+                .add_args::<args::AddressBookImport>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub enum WalletStore {
+        Encrypt,
+        Decrypt,
+        ChangePassword,
+    }
+
+    impl SubCmd for WalletStore {
+        const CMD: &'static str = "store";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).and_then(|matches| {
+                let encrypt = <StoreEncrypt as SubCmd>::parse(matches)
+                    .map(|_| Self::Encrypt);
+                let decrypt = <StoreDecrypt as SubCmd>::parse(matches)
+                    .map(|_| Self::Decrypt);
+                let change_password =
+                    <StoreChangePassword as SubCmd>::parse(matches)
+                        .map(|_| Self::ChangePassword);
+                encrypt.or(decrypt).or(change_password)
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Wallet store management, including methods to encrypt \
+                     the whole store with a master password.",
+                )
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(StoreEncrypt::def())
+                .subcommand(StoreDecrypt::def())
+                .subcommand(StoreChangePassword::def())
+        }
+    }
+
+    /// Convert the wallet store to the encrypted format
+    #[derive(Clone, Debug)]
+    pub struct StoreEncrypt;
+
+    impl SubCmd for StoreEncrypt {
+        const CMD: &'static str = "encrypt";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|_| Self)
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD).about(
+                "Converts the wallet store to the encrypted format, hiding \
+                 all its keys, aliases and addresses behind a master password \
+                 that is asked for once per command. The keys encrypted with \
+                 the same password are then only protected by the master \
+                 password.",
+            )
+        }
+    }
+
+    /// Convert the wallet store to the plaintext format
+    #[derive(Clone, Debug)]
+    pub struct StoreDecrypt;
+
+    impl SubCmd for StoreDecrypt {
+        const CMD: &'static str = "decrypt";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|_| Self)
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD).about(
+                "Converts an encrypted wallet store back to the plaintext \
+                 format. Its unencrypted keys are then encrypted with the \
+                 former master password.",
+            )
+        }
+    }
+
+    /// Change the master password of the wallet store
+    #[derive(Clone, Debug)]
+    pub struct StoreChangePassword;
+
+    impl SubCmd for StoreChangePassword {
+        const CMD: &'static str = "change-password";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|_| Self)
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD).about(
+                "Changes the master password of an encrypted wallet store and \
+                 encrypts it again with a fresh key.",
+            )
+        }
+    }
+
     #[allow(clippy::large_enum_variant)]
     #[derive(Clone, Debug)]
     pub enum WalletMasp {
@@ -1670,6 +2032,74 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct ShieldedSync(pub args::ShieldedSync<args::CliTypes>);
+
+    impl SubCmd for ShieldedSync {
+        const CMD: &'static str = "shielded-sync";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| ShieldedSync(args::ShieldedSync::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Sync the local shielded context with the ledger, or with \
+                     a MASP indexer.",
+                )
+                .add_args::<args::ShieldedSync<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryShieldedNotes(pub args::QueryShieldedNotes<args::CliTypes>);
+
+    impl SubCmd for QueryShieldedNotes {
+        const CMD: &'static str = "shielded-notes";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryShieldedNotes(args::QueryShieldedNotes::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the notes received by shielded viewing keys, along \
+                     with their memos.",
+                )
+                .add_args::<args::QueryShieldedNotes<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryShieldedHistory(
+        pub args::QueryShieldedHistory<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryShieldedHistory {
+        const CMD: &'static str = "shielded-history";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryShieldedHistory(args::QueryShieldedHistory::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the notes received and spent by shielded viewing \
+                     keys, transaction by transaction.",
+                )
+                .add_args::<args::QueryShieldedHistory<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryBlock(pub args::Query<args::CliTypes>);
 
@@ -1708,6 +2138,52 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct DiscoverAccounts(pub args::DiscoverAccounts<args::CliTypes>);
+
+    impl SubCmd for DiscoverAccounts {
+        const CMD: &'static str = "discover-accounts";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                DiscoverAccounts(args::DiscoverAccounts::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Restore all the used accounts of a mnemonic code. The \
+                     keys derived from the mnemonic are inserted into the \
+                     wallet until a number of consecutive accounts are found \
+                     unused on chain.",
+                )
+                .add_args::<args::DiscoverAccounts<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryWatched(pub args::Query<args::CliTypes>);
+
+    impl SubCmd for QueryWatched {
+        const CMD: &'static str = "watched";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryWatched(args::Query::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the balances and bonds of the watch-only accounts \
+                     of the address book.",
+                )
+                .add_args::<args::Query<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryBonds(pub args::QueryBonds<args::CliTypes>);
 
@@ -1765,6 +2241,96 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct BundleCreate(pub args::BundleCreate<args::CliTypes>);
+
+    impl SubCmd for BundleCreate {
+        const CMD: &'static str = "bundle-create";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| BundleCreate(args::BundleCreate::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Create a partially signed bundle of a dumped \
+                     transaction, with the public keys and threshold of its \
+                     owner.",
+                )
+                .add_args::<args::BundleCreate<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct BundleSign(pub args::BundleSign);
+
+    impl SubCmd for BundleSign {
+        const CMD: &'static str = "bundle-sign";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| BundleSign(args::BundleSign::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Sign a transaction bundle with the keys of the wallet, \
+                     without connecting to a node. The bundle is sealed with \
+                     the fee payer signature once its threshold is met.",
+                )
+                .add_args::<args::BundleSign>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct BundleMerge(pub args::BundleMerge);
+
+    impl SubCmd for BundleMerge {
+        const CMD: &'static str = "bundle-merge";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| BundleMerge(args::BundleMerge::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Merge the signatures of bundles of the same transaction \
+                     signed separately.",
+                )
+                .add_args::<args::BundleMerge>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct BundleSubmit(pub args::BundleSubmit<args::CliTypes>);
+
+    impl SubCmd for BundleSubmit {
+        const CMD: &'static str = "bundle-submit";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| BundleSubmit(args::BundleSubmit::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Submit the transaction of a bundle whose threshold is \
+                     met.",
+                )
+                .add_args::<args::BundleSubmit<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryValidatorState(
         pub args::QueryValidatorState<args::CliTypes>,
@@ -2496,6 +3062,9 @@ pub mod cmds {
         /// Get a recommendation on a batch of transfers
         /// to relay.
         RecommendBatch(RecommendBatch),
+        /// Continuously relay profitable batches of transfers
+        /// to Ethereum.
+        RelayDaemon(RelayDaemon),
     }
 
     /// Ethereum Bridge pool commands not requiring [`super::Context`].
@@ -2512,6 +3081,8 @@ pub mod cmds {
         QueryPool(QueryEthBridgePool),
         /// Query to provable contents of the pool.
         QuerySigned(QuerySignedBridgePool),
+        /// Query statistics of the gas fees paid in the pool.
+        QueryFeeStats(QueryBridgePoolFeeStats),
         /// Check the confirmation status of `TransferToEthereum`
         /// events.
         QueryRelays(QueryRelayProgress),
@@ -2522,8 +3093,10 @@ pub mod cmds {
             app.subcommand(RecommendBatch::def().display_order(1))
                 .subcommand(ConstructProof::def().display_order(1))
                 .subcommand(RelayProof::def().display_order(1))
+                .subcommand(RelayDaemon::def().display_order(1))
                 .subcommand(QueryEthBridgePool::def().display_order(1))
                 .subcommand(QuerySignedBridgePool::def().display_order(1))
+                .subcommand(QueryBridgePoolFeeStats::def().display_order(1))
                 .subcommand(QueryRelayProgress::def().display_order(1))
         }
 
@@ -2535,15 +3108,20 @@ pub mod cmds {
             let construct_proof =
                 Self::parse_without_ctx(matches, ConstructProof);
             let relay_proof = Self::parse_without_ctx(matches, RelayProof);
+            let relay_daemon = Self::parse_with_ctx(matches, RelayDaemon);
             let query_pool = Self::parse_without_ctx(matches, QueryPool);
             let query_signed = Self::parse_without_ctx(matches, QuerySigned);
+            let query_fee_stats =
+                Self::parse_without_ctx(matches, QueryFeeStats);
             let query_relays = Self::parse_without_ctx(matches, QueryRelays);
 
             construct_proof
                 .or(recommend)
                 .or(relay_proof)
+                .or(relay_daemon)
                 .or(query_pool)
                 .or(query_signed)
+                .or(query_fee_stats)
                 .or(query_relays)
         }
     }
@@ -2584,8 +3162,10 @@ pub mod cmds {
                 .subcommand(ConstructProof::def().display_order(1))
                 .subcommand(RecommendBatch::def().display_order(1))
                 .subcommand(RelayProof::def().display_order(1))
+                .subcommand(RelayDaemon::def().display_order(1))
                 .subcommand(QueryEthBridgePool::def().display_order(1))
                 .subcommand(QuerySignedBridgePool::def().display_order(1))
+                .subcommand(QueryBridgePoolFeeStats::def().display_order(1))
                 .subcommand(QueryRelayProgress::def().display_order(1))
         }
     }
@@ -2610,6 +3190,31 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct BumpEthBridgePoolFee(
+        pub args::BumpBridgePoolFee<args::CliTypes>,
+    );
+
+    impl SubCmd for BumpEthBridgePoolFee {
+        const CMD: &'static str = "bump-erc20-transfer-fee";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::BumpBridgePoolFee::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Replace a transfer in the Ethereum Bridge pool with one \
+                     paying a higher gas fee.",
+                )
+                .arg_required_else_help(true)
+                .add_args::<args::BumpBridgePoolFee<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct ConstructProof(pub args::BridgePoolProof<args::CliTypes>);
 
@@ -2656,6 +3261,29 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct RelayDaemon(pub args::BridgePoolRelayDaemon<args::CliTypes>);
+
+    impl SubCmd for RelayDaemon {
+        const CMD: &'static str = "relay-daemon";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                Self(args::BridgePoolRelayDaemon::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Run a daemon that continuously relays the most \
+                     profitable batches of transfers in the pool to Ethereum.",
+                )
+                .arg_required_else_help(true)
+                .add_args::<args::BridgePoolRelayDaemon<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct RecommendBatch(pub args::RecommendBatch<args::CliTypes>);
 
@@ -2698,6 +3326,28 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryBridgePoolFeeStats(pub args::Query<args::CliTypes>);
+
+    impl SubCmd for QueryBridgePoolFeeStats {
+        const CMD: &'static str = "fee-stats";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::Query::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Get statistics of the gas fees paid by transfers in the \
+                     Ethereum Bridge pool, per fee token.",
+                )
+                .add_args::<args::Query<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QuerySignedBridgePool(pub args::Query<args::CliTypes>);
 
@@ -2886,6 +3536,88 @@ pub mod cmds {
         }
     }
 
+    /// Proofs of Namada's state, consumable by Ethereum contracts.
+    #[derive(Clone, Debug)]
+    pub enum StateProof {
+        /// Query an Ethereum ABI encoding of a proof of the value
+        /// of some storage key in Namada.
+        ConstructStateProof(ConstructStateProof),
+        /// Relay a proof of the value of some storage key in
+        /// Namada to a verifier contract on Ethereum.
+        RelayStateProof(RelayStateProof),
+    }
+
+    impl SubCmd for StateProof {
+        const CMD: &'static str = "state-proof";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).and_then(|matches| {
+                let construct = ConstructStateProof::parse(matches)
+                    .map(Self::ConstructStateProof);
+                let relay =
+                    RelayStateProof::parse(matches).map(Self::RelayStateProof);
+                construct.or(relay)
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Proofs of the values of storage keys in Namada, \
+                     verifiable by Ethereum smart contracts against state \
+                     roots signed by Namada's validators.",
+                )
+                .subcommand_required(true)
+                .subcommand(ConstructStateProof::def().display_order(1))
+                .subcommand(RelayStateProof::def().display_order(1))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ConstructStateProof(pub args::StateProof<args::CliTypes>);
+
+    impl SubCmd for ConstructStateProof {
+        const CMD: &'static str = "construct";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::StateProof::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query an Ethereum ABI encoding of a proof of the value \
+                     of a storage key, against the latest state root signed \
+                     by Namada's validators.",
+                )
+                .add_args::<args::StateProof<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct RelayStateProof(pub args::RelayStateProof<args::CliTypes>);
+
+    impl SubCmd for RelayStateProof {
+        const CMD: &'static str = "relay";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::RelayStateProof::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Relay a proof of the value of a storage key to a \
+                     verifier contract on Ethereum.",
+                )
+                .add_args::<args::RelayStateProof<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct PkToTmAddress(pub args::PkToTmAddress);
 
@@ -2941,13 +3673,13 @@ pub mod args {
     use std::str::FromStr;
 
     use namada::ibc::core::host::types::identifiers::{ChannelId, PortId};
-    use namada::types::address::{Address, EstablishedAddress};
+    use namada::types::address::{Address, EstablishedAddress, MASP};
     use namada::types::chain::{ChainId, ChainIdPrefix};
     use namada::types::dec::Dec;
     use namada::types::ethereum_events::EthAddress;
     use namada::types::keccak::KeccakHash;
     use namada::types::key::*;
-    use namada::types::masp::MaspValue;
+    use namada::types::masp::{MaspValue, PaymentAddress, TransferSource};
     use namada::types::storage::{self, BlockHeight, Epoch};
     use namada::types::time::DateTimeUtc;
     use namada::types::token;
@@ -2955,16 +3687,17 @@ pub mod args {
     use namada::types::transaction::GasLimit;
     pub use namada_sdk::args::*;
     pub use namada_sdk::tx::{
-        TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
-        TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
-        TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
-        TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
-        TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-        TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
-        TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
+        TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_BUMP_FEE_WASM,
+        TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
+        TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
+        TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
+        TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
+        TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
+        TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
         TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
         VP_USER_WASM,
     };
+    use namada_sdk::wallet::alias::Alias;
 
     use super::context::*;
     use super::utils::*;
@@ -2980,9 +3713,14 @@ pub mod args {
     pub const ALIAS: Arg<String> = arg("alias");
     pub const ALIAS_FORCE: ArgFlag = flag("alias-force");
     pub const ALIAS_MANY: ArgMulti<String, GlobPlus> = arg_multi("aliases");
+    pub const ALIAS_PREFIX: ArgDefault<String> =
+        arg_default("alias-prefix", DefaultFn(|| "account".to_string()));
     pub const ALLOW_DUPLICATE_IP: ArgFlag = flag("allow-duplicate-ip");
+    pub const ALLOWED_TOKENS: ArgMulti<EthAddress, GlobStar> =
+        arg_multi("allowed-tokens");
     pub const AMOUNT: Arg<token::DenominatedAmount> = arg("amount");
     pub const ARCHIVE_DIR: ArgOpt<PathBuf> = arg_opt("archive-dir");
+    pub const AUTO_FEE: ArgFlag = flag("auto-fee");
     pub const BALANCE_OWNER: ArgOpt<WalletBalanceOwner> = arg_opt("owner");
     pub const BASE_DIR: ArgDefault<PathBuf> = arg_default(
         "base-dir",
@@ -2991,6 +3729,7 @@ pub mod args {
             Err(_) => config::get_default_namada_folder(),
         }),
     );
+    pub const BIRTHDAY: ArgOpt<BlockHeight> = arg_opt("birthday");
     pub const BLOCK_HEIGHT: Arg<BlockHeight> = arg("block-height");
     pub const BLOCK_HEIGHT_OPT: ArgOpt<BlockHeight> = arg_opt("height");
     pub const BRIDGE_POOL_GAS_AMOUNT: ArgDefault<token::DenominatedAmount> =
@@ -3001,6 +3740,8 @@ pub mod args {
                 denom: NATIVE_MAX_DECIMAL_PLACES.into(),
             }),
         );
+    pub const BRIDGE_POOL_GAS_AMOUNT_OPT: ArgOpt<token::DenominatedAmount> =
+        arg_opt("pool-gas-amount");
     pub const BRIDGE_POOL_GAS_PAYER: ArgOpt<WalletAddress> =
         arg_opt("pool-gas-payer");
     pub const BRIDGE_POOL_GAS_TOKEN: ArgDefaultFromCtx<WalletAddress> =
@@ -3010,6 +3751,8 @@ pub mod args {
         );
     pub const BRIDGE_POOL_TARGET: Arg<EthAddress> = arg("target");
     pub const BROADCAST_ONLY: ArgFlag = flag("broadcast-only");
+    pub const BUNDLE: Arg<PathBuf> = arg("bundle");
+    pub const BUNDLES: ArgMulti<PathBuf, GlobPlus> = arg_multi("bundles");
     pub const CHAIN_ID: Arg<ChainId> = arg("chain-id");
     pub const CHAIN_ID_OPT: ArgOpt<ChainId> = CHAIN_ID.opt();
     pub const CHAIN_ID_PREFIX: Arg<ChainIdPrefix> = arg("chain-prefix");
@@ -3042,6 +3785,7 @@ pub mod args {
     pub const DUMP_TX: ArgFlag = flag("dump-tx");
     pub const EPOCH: ArgOpt<Epoch> = arg_opt("epoch");
     pub const ERC20: Arg<EthAddress> = arg("erc20");
+    pub const ESTIMATE_GAS: ArgFlag = flag("estimate-gas");
     pub const ETH_CONFIRMATIONS: Arg<u64> = arg("confirmations");
     pub const ETH_GAS: ArgOpt<u64> = arg_opt("eth-gas");
     pub const ETH_GAS_PRICE: ArgOpt<u64> = arg_opt("eth-gas-price");
@@ -3053,6 +3797,7 @@ pub mod args {
     );
     pub const ETH_SYNC: ArgFlag = flag("sync");
     pub const EXPIRATION_OPT: ArgOpt<DateTimeUtc> = arg_opt("expiration");
+    pub const EXPORT_PATH: ArgOpt<PathBuf> = arg_opt("export");
     pub const EMAIL: Arg<String> = arg("email");
     pub const EMAIL_OPT: ArgOpt<String> = EMAIL.opt();
     pub const FEE_UNSHIELD_SPENDING_KEY: ArgOpt<WalletTransferSource> =
@@ -3073,6 +3818,10 @@ pub mod args {
             denom: NATIVE_MAX_DECIMAL_PLACES.into(),
         }),
     );
+    pub const GATEWAY_LISTEN_ADDRESS: ArgDefault<SocketAddr> = arg_default(
+        "listen-address",
+        DefaultFn(|| SocketAddr::from(([127, 0, 0, 1], 26690))),
+    );
     pub const GENESIS_BOND_SOURCE: ArgOpt<GenesisAddress> = arg_opt("source");
     pub const GENESIS_PATH: Arg<PathBuf> = arg("genesis-path");
     pub const GENESIS_TIME: Arg<DateTimeUtc> = arg("genesis-time");
@@ -3080,11 +3829,17 @@ pub mod args {
         arg("genesis-validator").opt();
     pub const GENESIS_VALIDATOR_ADDRESS: Arg<EstablishedAddress> =
         arg("validator");
+    pub const GAP_LIMIT: ArgDefault<u32> = arg_default(
+        "gap-limit",
+        DefaultFn(|| namada_sdk::discovery::DEFAULT_GAP_LIMIT),
+    );
     pub const HALT_ACTION: ArgFlag = flag("halt");
     pub const HASH_LIST: Arg<String> = arg("hash-list");
     pub const HD_WALLET_DERIVATION_PATH: ArgDefault<String> =
         arg_default("hd-path", DefaultFn(|| "default".to_string()));
     pub const HISTORIC: ArgFlag = flag("historic");
+    pub const HISTORY_FORMAT: ArgDefault<HistoryFormat> =
+        arg_default("format", DefaultFn(|| HistoryFormat::Csv));
     pub const IBC_TRANSFER_MEMO_PATH: ArgOpt<PathBuf> = arg_opt("memo-path");
     pub const INPUT_OPT: ArgOpt<PathBuf> = arg_opt("input");
     pub const LEDGER_ADDRESS_ABOUT: &str =
@@ -3097,24 +3852,41 @@ pub mod args {
         }));
 
     pub const LEDGER_ADDRESS: Arg<TendermintAddress> = arg("node");
+    pub const LABEL: ArgOpt<String> = arg_opt("label");
+    pub const LISTEN_ADDRESS: ArgDefault<SocketAddr> = arg_default(
+        "listen-address",
+        DefaultFn(|| SocketAddr::from(([127, 0, 0, 1], 26680))),
+    );
     pub const LOCALHOST: ArgFlag = flag("localhost");
+    pub const MASP_INDEXER: ArgOpt<String> = arg_opt("masp-indexer");
     pub const MASP_VALUE: Arg<MaspValue> = arg("value");
     pub const MAX_COMMISSION_RATE_CHANGE: Arg<Dec> =
         arg("max-commission-rate-change");
     pub const MAX_ETH_GAS: ArgOpt<u64> = arg_opt("max_eth-gas");
+    pub const MEMO: ArgOpt<String> = arg_opt("memo");
+    pub const MEMO_TEMPLATE: ArgOpt<String> = arg_opt("memo-template");
+    pub const MIN_PROFIT: ArgOpt<u64> = arg_opt("min-profit");
     pub const MODE: ArgOpt<String> = arg_opt("mode");
     pub const NET_ADDRESS: Arg<SocketAddr> = arg("net-address");
     pub const NAMADA_START_TIME: ArgOpt<DateTimeUtc> = arg_opt("time");
+    pub const NO_CONFIRM: ArgFlag = flag("no-confirm");
     pub const NO_CONVERSIONS: ArgFlag = flag("no-conversions");
     pub const NUT: ArgFlag = flag("nut");
     pub const OUT_FILE_PATH_OPT: ArgOpt<PathBuf> = arg_opt("out-file-path");
     pub const OUTPUT: ArgOpt<PathBuf> = arg_opt("output");
+    pub const OUTPUT_PATH: Arg<PathBuf> = arg("output");
     pub const OUTPUT_FOLDER_PATH: ArgOpt<PathBuf> =
         arg_opt("output-folder-path");
+    pub const OUTPUT_FORMAT: ArgDefault<OutputFormat> =
+        arg_default("output", DefaultFn(|| OutputFormat::Text));
     pub const OWNER: Arg<WalletAddress> = arg("owner");
     pub const OWNER_OPT: ArgOpt<WalletAddress> = OWNER.opt();
     pub const PATH: Arg<PathBuf> = arg("path");
     pub const PIN: ArgFlag = flag("pin");
+    pub const POLL_INTERVAL: ArgDefault<Duration> = arg_default(
+        "poll-interval",
+        DefaultFn(|| Duration::from_str("5s").unwrap()),
+    );
     pub const PORT_ID: ArgDefault<PortId> = arg_default(
         "port-id",
         DefaultFn(|| PortId::from_str("transfer").unwrap()),
@@ -3137,33 +3909,44 @@ pub mod args {
     pub const RAW_ADDRESS: Arg<Address> = arg("address");
     pub const RAW_ADDRESS_ESTABLISHED: Arg<EstablishedAddress> = arg("address");
     pub const RAW_ADDRESS_OPT: ArgOpt<Address> = RAW_ADDRESS.opt();
+    pub const RAW_PAYMENT_ADDRESS_OPT: ArgOpt<PaymentAddress> =
+        arg_opt("payment-address");
     pub const RAW_PUBLIC_KEY: Arg<common::PublicKey> = arg("public-key");
     pub const RAW_PUBLIC_KEY_OPT: ArgOpt<common::PublicKey> =
         arg_opt("public-key");
     pub const RECEIVER: Arg<String> = arg("receiver");
     pub const RELAYER: Arg<Address> = arg("relayer");
+    pub const RELAYER_STATE_FILE: Arg<PathBuf> = arg("state-file");
+    pub const RELAY_VALSET: ArgFlag = flag("relay-valset");
+    pub const REMOTE_SIGNER: ArgOpt<PathBuf> = arg_opt("remote-signer");
     pub const SAFE_MODE: ArgFlag = flag("safe-mode");
     pub const SCHEME: ArgDefault<SchemeType> =
         arg_default("scheme", DefaultFn(|| SchemeType::Ed25519));
+    pub const SCHEMES: ArgMulti<SchemeType, GlobStar> = arg_multi("schemes");
     pub const SELF_BOND_AMOUNT: Arg<token::DenominatedAmount> =
         arg("self-bond-amount");
     pub const SENDER: Arg<String> = arg("sender");
+    pub const SHIELDED: ArgFlag = flag("shielded");
     pub const SIGNER: ArgOpt<WalletAddress> = arg_opt("signer");
     pub const SIGNING_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
         arg_multi("signing-keys");
     pub const SIGNATURES: ArgMulti<PathBuf, GlobStar> = arg_multi("signatures");
+    pub const SIGNER_SOCKET: Arg<PathBuf> = arg("socket");
     pub const SOURCE: Arg<WalletAddress> = arg("source");
     pub const SOURCE_OPT: ArgOpt<WalletAddress> = SOURCE.opt();
     pub const STEWARD: Arg<WalletAddress> = arg("steward");
     pub const SOURCE_VALIDATOR: Arg<WalletAddress> = arg("source-validator");
     pub const STORAGE_KEY: Arg<storage::Key> = arg("storage-key");
     pub const SUSPEND_ACTION: ArgFlag = flag("suspend");
+    pub const TAG_OPT: ArgOpt<String> = arg_opt("tag");
+    pub const TAGS: ArgMulti<String, GlobStar> = arg_multi("tags");
     pub const TEMPLATES_PATH: Arg<PathBuf> = arg("templates-path");
     pub const TIMEOUT_HEIGHT: ArgOpt<u64> = arg_opt("timeout-height");
     pub const TIMEOUT_SEC_OFFSET: ArgOpt<u64> = arg_opt("timeout-sec-offset");
     pub const TM_ADDRESS: Arg<String> = arg("tm-address");
     pub const TOKEN_OPT: ArgOpt<WalletAddress> = TOKEN.opt();
     pub const TOKEN: Arg<WalletAddress> = arg("token");
+    pub const TRANSFER_HASH: Arg<String> = arg("transfer-hash");
     pub const TRANSFER_SOURCE: Arg<WalletTransferSource> = arg("source");
     pub const TRANSFER_TARGET: Arg<WalletTransferTarget> = arg("target");
     pub const TX_HASH: Arg<String> = arg("tx-hash");
@@ -3186,7 +3969,9 @@ pub mod args {
     pub const VALIDATOR_ETH_HOT_KEY: ArgOpt<WalletPublicKey> =
         arg_opt("eth-hot-key");
     pub const VALUE: ArgOpt<String> = arg_opt("value");
+    pub const VERIFIER_CONTRACT: Arg<EthAddress> = arg("verifier");
     pub const VIEWING_KEY: Arg<WalletViewingKey> = arg("key");
+    pub const VIEWING_KEY_OPT: ArgOpt<WalletViewingKey> = VIEWING_KEY.opt();
     pub const VP: ArgOpt<String> = arg_opt("vp");
     pub const WALLET_ALIAS_FORCE: ArgFlag = flag("wallet-alias-force");
     pub const WASM_CHECKSUMS_PATH: Arg<PathBuf> = arg("wasm-checksums-path");
@@ -3270,6 +4055,79 @@ pub mod args {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct MaspIndexer {
+        /// Address of the ledger node to index
+        pub ledger_address: TendermintAddress,
+        /// Address on which the compact blocks are served
+        pub listen_address: SocketAddr,
+        /// Time to wait between two polls of the ledger node
+        pub poll_interval: Duration,
+        /// Base directory in which the indexed blocks are stored
+        pub base_dir: PathBuf,
+    }
+
+    impl Args for MaspIndexer {
+        fn parse(matches: &ArgMatches) -> Self {
+            let ledger_address = LEDGER_ADDRESS_DEFAULT.parse(matches);
+            let listen_address = LISTEN_ADDRESS.parse(matches);
+            let poll_interval = POLL_INTERVAL.parse(matches);
+            let base_dir = BASE_DIR.parse(matches);
+            Self {
+                ledger_address,
+                listen_address,
+                poll_interval,
+                base_dir,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(LEDGER_ADDRESS_DEFAULT.def().help(LEDGER_ADDRESS_ABOUT))
+                .arg(LISTEN_ADDRESS.def().help(
+                    "The address on which the compact blocks are served. \
+                     Defaults to 127.0.0.1:26680.",
+                ))
+                .arg(POLL_INTERVAL.def().help(
+                    "The amount of time to wait between two polls of the \
+                     ledger node for new shielded transactions. Defaults to \
+                     5s.",
+                ))
+                .arg(BASE_DIR.def().help(
+                    "The base directory in whose `masp-indexer` sub-directory \
+                     the indexed blocks are stored. This value can also be \
+                     set via `NAMADA_BASE_DIR` environment variable, but the \
+                     argument takes precedence, if specified.",
+                ))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryGateway {
+        /// Address of the ledger node to which the queries are proxied
+        pub ledger_address: TendermintAddress,
+        /// Address on which the JSON API is served
+        pub listen_address: SocketAddr,
+    }
+
+    impl Args for QueryGateway {
+        fn parse(matches: &ArgMatches) -> Self {
+            let ledger_address = LEDGER_ADDRESS_DEFAULT.parse(matches);
+            let listen_address = GATEWAY_LISTEN_ADDRESS.parse(matches);
+            Self {
+                ledger_address,
+                listen_address,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(LEDGER_ADDRESS_DEFAULT.def().help(LEDGER_ADDRESS_ABOUT))
+                .arg(GATEWAY_LISTEN_ADDRESS.def().help(
+                    "The address on which the JSON API is served. Defaults to \
+                     127.0.0.1:26690.",
+                ))
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct LedgerRunUntil {
         pub time: Option<DateTimeUtc>,
@@ -3435,6 +4293,7 @@ pub mod args {
                     .fee_payer
                     .map(|fee_payer| chain_ctx.get(&fee_payer)),
                 fee_token: chain_ctx.get(&self.fee_token),
+                auto_fee: self.auto_fee,
                 code_path: self.code_path,
             }
         }
@@ -3451,6 +4310,7 @@ pub mod args {
                 InputAmount::Unvalidated(BRIDGE_POOL_GAS_AMOUNT.parse(matches));
             let fee_payer = BRIDGE_POOL_GAS_PAYER.parse(matches);
             let fee_token = BRIDGE_POOL_GAS_TOKEN.parse(matches);
+            let auto_fee = AUTO_FEE.parse(matches);
             let code_path = PathBuf::from(TX_BRIDGE_POOL_WASM);
             let nut = NUT.parse(matches);
             Self {
@@ -3462,6 +4322,7 @@ pub mod args {
                 fee_amount,
                 fee_payer,
                 fee_token,
+                auto_fee,
                 code_path,
                 nut,
             }
@@ -3499,9 +4360,63 @@ pub mod args {
                     "The token for paying the Bridge pool gas fees. Defaults \
                      to NAM.",
                 ))
-                .arg(NUT.def().help(
-                    "Add Non Usable Tokens (NUTs) to the Bridge pool. These \
-                     are usually obtained from invalid transfers to Namada.",
+                .arg(AUTO_FEE.def().help(
+                    "Pay the recommended gas fee of the Bridge pool for the \
+                     given fee token, instead of the amount of \
+                     --pool-gas-amount.",
+                ))
+                .arg(NUT.def().help(
+                    "Add Non Usable Tokens (NUTs) to the Bridge pool. These \
+                     are usually obtained from invalid transfers to Namada.",
+                ))
+        }
+    }
+
+    impl CliToSdk<BumpBridgePoolFee<SdkTypes>> for BumpBridgePoolFee<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> BumpBridgePoolFee<SdkTypes> {
+            BumpBridgePoolFee::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                transfer: self.transfer,
+                fee_amount: self.fee_amount,
+                code_path: self.code_path,
+            }
+        }
+    }
+
+    impl Args for BumpBridgePoolFee<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let hash = TRANSFER_HASH.parse(matches);
+            let transfer =
+                KeccakHash::try_from(hash.as_str()).unwrap_or_else(|_| {
+                    tracing::info!(
+                        "Could not parse '{}' as a Keccak hash.",
+                        hash
+                    );
+                    safe_exit(1)
+                });
+            let fee_amount = BRIDGE_POOL_GAS_AMOUNT_OPT
+                .parse(matches)
+                .map(InputAmount::Unvalidated);
+            let code_path = PathBuf::from(TX_BRIDGE_POOL_BUMP_FEE_WASM);
+            Self {
+                tx,
+                transfer,
+                fee_amount,
+                code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(TRANSFER_HASH.def().help(
+                    "The Keccak hash of the transfer in the Bridge pool to \
+                     replace.",
+                ))
+                .arg(BRIDGE_POOL_GAS_AMOUNT_OPT.def().help(
+                    "The new amount of gas to pay to have the transfer \
+                     relayed to Ethereum. Defaults to the recommended gas fee \
+                     of the Bridge pool.",
                 ))
         }
     }
@@ -3578,6 +4493,121 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<BridgePoolRelayDaemon<SdkTypes>>
+        for BridgePoolRelayDaemon<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> BridgePoolRelayDaemon<SdkTypes> {
+            BridgePoolRelayDaemon::<SdkTypes> {
+                recommend: self.recommend.to_sdk(ctx),
+                min_profit: self.min_profit,
+                allowed_tokens: self.allowed_tokens,
+                relayer: self.relayer,
+                state_file: self.state_file,
+                relay_valset: self.relay_valset,
+                confirmations: self.confirmations,
+                eth_rpc_endpoint: (),
+                gas_price: self.gas_price,
+                eth_addr: self.eth_addr,
+                sync: self.sync,
+                retry_dur: self.retry_dur,
+                success_dur: self.success_dur,
+                safe_mode: self.safe_mode,
+            }
+        }
+    }
+
+    impl Args for BridgePoolRelayDaemon<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let recommend = RecommendBatch::parse(matches);
+            let min_profit = MIN_PROFIT.parse(matches);
+            let allowed_tokens = ALLOWED_TOKENS.parse(matches);
+            let relayer = RELAYER.parse(matches);
+            let state_file = RELAYER_STATE_FILE.parse(matches);
+            let relay_valset = RELAY_VALSET.parse(matches);
+            let safe_mode = SAFE_MODE.parse(matches);
+            let gas_price = ETH_GAS_PRICE.parse(matches);
+            let eth_rpc_endpoint = ETH_RPC_ENDPOINT.parse(matches);
+            let eth_addr = ETH_ADDRESS_OPT.parse(matches);
+            let confirmations = ETH_CONFIRMATIONS.parse(matches);
+            let sync = ETH_SYNC.parse(matches);
+            let retry_dur =
+                DAEMON_MODE_RETRY_DUR.parse(matches).map(|dur| dur.0);
+            let success_dur =
+                DAEMON_MODE_SUCCESS_DUR.parse(matches).map(|dur| dur.0);
+            Self {
+                recommend,
+                min_profit,
+                allowed_tokens,
+                relayer,
+                state_file,
+                relay_valset,
+                confirmations,
+                eth_rpc_endpoint,
+                gas_price,
+                eth_addr,
+                sync,
+                retry_dur,
+                success_dur,
+                safe_mode,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<RecommendBatch<CliTypes>>()
+                .arg(MIN_PROFIT.def().help(
+                    "The minimum net profit, in gwei, that a batch must yield \
+                     to be relayed. Ignored if --eth-gas is provided.",
+                ))
+                .arg(ALLOWED_TOKENS.def().help(
+                    "Only relay transfers of these ERC20 assets. By default, \
+                     transfers of any asset may be relayed.",
+                ))
+                .arg(
+                    RELAYER
+                        .def()
+                        .help("The rewards address for relaying proofs."),
+                )
+                .arg(RELAYER_STATE_FILE.def().help(
+                    "Path to the file where the progress of the daemon is \
+                     persisted across restarts.",
+                ))
+                .arg(RELAY_VALSET.def().help(
+                    "Also relay validator set updates, in the same process.",
+                ))
+                .arg(SAFE_MODE.def().help(
+                    "Safe mode overrides keyboard interrupt signals, to \
+                     ensure Ethereum transfers aren't canceled midway through.",
+                ))
+                .arg(DAEMON_MODE_RETRY_DUR.def().help(
+                    "The amount of time to sleep between failed daemon mode \
+                     relays.",
+                ))
+                .arg(DAEMON_MODE_SUCCESS_DUR.def().help(
+                    "The amount of time to sleep between successful daemon \
+                     mode relays.",
+                ))
+                .arg(ETH_ADDRESS_OPT.def().help(
+                    "The address of the Ethereum wallet to pay the gas fees. \
+                     If unset, the default wallet is used.",
+                ))
+                .arg(
+                    ETH_GAS_PRICE.def().help(
+                        "The price of Ethereum gas, during the relay call.",
+                    ),
+                )
+                .arg(ETH_RPC_ENDPOINT.def().help("The Ethereum RPC endpoint."))
+                .arg(
+                    ETH_CONFIRMATIONS
+                        .def()
+                        .help("The number of block confirmations on Ethereum."),
+                )
+                .arg(ETH_SYNC.def().help(
+                    "Synchronize with the network, or exit immediately, if \
+                     the Ethereum node has fallen behind.",
+                ))
+        }
+    }
+
     impl CliToSdkCtxless<BridgePoolProof<SdkTypes>> for BridgePoolProof<CliTypes> {
         fn to_sdk_ctxless(self) -> BridgePoolProof<SdkTypes> {
             BridgePoolProof::<SdkTypes> {
@@ -3901,6 +4931,114 @@ pub mod args {
         }
     }
 
+    impl CliToSdkCtxless<StateProof<SdkTypes>> for StateProof<CliTypes> {
+        fn to_sdk_ctxless(self) -> StateProof<SdkTypes> {
+            StateProof::<SdkTypes> {
+                query: self.query.to_sdk_ctxless(),
+                key: self.key,
+            }
+        }
+    }
+
+    impl Args for StateProof<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let key = STORAGE_KEY.parse(matches);
+            Self { query, key }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                STORAGE_KEY
+                    .def()
+                    .help("The storage key whose value should be proven."),
+            )
+        }
+    }
+
+    impl CliToSdkCtxless<RelayStateProof<SdkTypes>> for RelayStateProof<CliTypes> {
+        fn to_sdk_ctxless(self) -> RelayStateProof<SdkTypes> {
+            RelayStateProof::<SdkTypes> {
+                query: self.query.to_sdk_ctxless(),
+                key: self.key,
+                verifier: self.verifier,
+                confirmations: self.confirmations,
+                eth_rpc_endpoint: (),
+                gas: self.gas,
+                gas_price: self.gas_price,
+                eth_addr: self.eth_addr,
+                sync: self.sync,
+                safe_mode: self.safe_mode,
+            }
+        }
+    }
+
+    impl Args for RelayStateProof<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let safe_mode = SAFE_MODE.parse(matches);
+            let query = Query::parse(matches);
+            let key = STORAGE_KEY.parse(matches);
+            let verifier = VERIFIER_CONTRACT.parse(matches);
+            let gas = ETH_GAS.parse(matches);
+            let gas_price = ETH_GAS_PRICE.parse(matches);
+            let eth_rpc_endpoint = ETH_RPC_ENDPOINT.parse(matches);
+            let eth_addr = ETH_ADDRESS_OPT.parse(matches);
+            let confirmations = ETH_CONFIRMATIONS.parse(matches);
+            let sync = ETH_SYNC.parse(matches);
+            Self {
+                query,
+                key,
+                verifier,
+                confirmations,
+                eth_rpc_endpoint,
+                gas,
+                gas_price,
+                eth_addr,
+                sync,
+                safe_mode,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(SAFE_MODE.def().help(
+                    "Safe mode overrides keyboard interrupt signals, to \
+                     ensure Ethereum transactions aren't canceled midway \
+                     through.",
+                ))
+                .arg(
+                    STORAGE_KEY
+                        .def()
+                        .help("The storage key whose value should be proven."),
+                )
+                .arg(VERIFIER_CONTRACT.def().help(
+                    "The address of the Ethereum contract verifying the proof.",
+                ))
+                .arg(ETH_ADDRESS_OPT.def().help(
+                    "The address of the Ethereum wallet to pay the gas fees. \
+                     If unset, the default wallet is used.",
+                ))
+                .arg(ETH_GAS.def().help(
+                    "The Ethereum gas that can be spent during the relay call.",
+                ))
+                .arg(
+                    ETH_GAS_PRICE.def().help(
+                        "The price of Ethereum gas, during the relay call.",
+                    ),
+                )
+                .arg(ETH_RPC_ENDPOINT.def().help("The Ethereum RPC endpoint."))
+                .arg(
+                    ETH_CONFIRMATIONS
+                        .def()
+                        .help("The number of block confirmations on Ethereum."),
+                )
+                .arg(ETH_SYNC.def().help(
+                    "Synchronize with the network, or exit immediately, if \
+                     the Ethereum node has fallen behind.",
+                ))
+        }
+    }
+
     impl CliToSdk<TxCustom<SdkTypes>> for TxCustom<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> TxCustom<SdkTypes> {
             TxCustom::<SdkTypes> {
@@ -3973,13 +5111,43 @@ pub mod args {
         fn to_sdk(self, ctx: &mut Context) -> TxTransfer<SdkTypes> {
             let tx = self.tx.to_sdk(ctx);
             let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            let target = chain_ctx.get(&self.target);
+            // Shielded transfers to a contact without a memo get its memo
+            // template
+            let memo = self.memo.or_else(|| {
+                let alias = Alias::from(self.target.raw.as_str());
+                let template = chain_ctx
+                    .wallet
+                    .get_address_book()
+                    .find_memo_template(&alias, &target)?;
+                Some(namada_sdk::masp::encode_memo(template).unwrap_or_else(
+                    |e| {
+                        println!("Could not parse the memo template: {}", e);
+                        safe_exit(1);
+                    },
+                ))
+            });
+            // The spends of a shielded source are authorized by the hardware
+            // wallet holding its spending key, so only its viewing key is
+            // needed
+            let device_source = self
+                .source
+                .to_viewing_key(chain_ctx)
+                .filter(|_| tx.use_device)
+                .map(|vk| chain_ctx.get_cached(&vk));
+            let source = match device_source {
+                Some(_) => TransferSource::Address(MASP),
+                None => chain_ctx.get_cached(&self.source),
+            };
             TxTransfer::<SdkTypes> {
                 tx,
-                source: chain_ctx.get_cached(&self.source),
-                target: chain_ctx.get(&self.target),
+                source,
+                target,
                 token: chain_ctx.get(&self.token),
                 amount: self.amount,
                 native_token: chain_ctx.native_token.clone(),
+                memo,
+                device_source,
                 tx_code_path: self.tx_code_path.to_path_buf(),
             }
         }
@@ -3992,6 +5160,12 @@ pub mod args {
             let target = TRANSFER_TARGET.parse(matches);
             let token = TOKEN.parse(matches);
             let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let memo = MEMO.parse(matches).map(|memo| {
+                namada_sdk::masp::encode_memo(&memo).unwrap_or_else(|e| {
+                    println!("Could not parse the memo: {}", e);
+                    safe_exit(1);
+                })
+            });
             let tx_code_path = PathBuf::from(TX_TRANSFER_WASM);
             Self {
                 tx,
@@ -4001,6 +5175,8 @@ pub mod args {
                 amount,
                 tx_code_path,
                 native_token: (),
+                memo,
+                device_source: None,
             }
         }
 
@@ -4008,7 +5184,9 @@ pub mod args {
             app.add_args::<Tx<CliTypes>>()
                 .arg(TRANSFER_SOURCE.def().help(
                     "The source account address. The source's key may be used \
-                     to produce the signature.",
+                     to produce the signature. With --use-device, a shielded \
+                     source is given by its viewing key and its spends are \
+                     authorized by the hardware wallet.",
                 ))
                 .arg(TRANSFER_TARGET.def().help(
                     "The target account address. The target's key may be used \
@@ -4016,6 +5194,11 @@ pub mod args {
                 ))
                 .arg(TOKEN.def().help("The transfer token."))
                 .arg(AMOUNT.def().help("The amount to transfer in decimal."))
+                .arg(MEMO.def().help(
+                    "An optional memo of at most 512 bytes, encrypted to the \
+                     recipient. Only allowed when transferring to a payment \
+                     address.",
+                ))
         }
     }
 
@@ -5154,20 +6337,130 @@ pub mod args {
         }
     }
 
-    impl Args for QueryAccount<CliTypes> {
+    impl Args for QueryAccount<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let owner = OWNER.parse(matches);
+            Self { query, owner }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                OWNER
+                    .def()
+                    .help("The substorage space address to query.")
+                    .required(true),
+            )
+        }
+    }
+
+    impl CliToSdk<ShieldedSync<SdkTypes>> for ShieldedSync<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> ShieldedSync<SdkTypes> {
+            let query = self.query.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            ShieldedSync::<SdkTypes> {
+                query,
+                viewing_key: self.viewing_key.map(|x| chain_ctx.get_cached(&x)),
+                masp_indexer: self.masp_indexer,
+            }
+        }
+    }
+
+    impl Args for ShieldedSync<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let viewing_key = VIEWING_KEY_OPT.parse(matches);
+            let masp_indexer = MASP_INDEXER.parse(matches);
+            Self {
+                query,
+                viewing_key,
+                masp_indexer,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(VIEWING_KEY_OPT.def().help(
+                    "The viewing key whose notes to sync. Defaults to all the \
+                     viewing keys in the wallet.",
+                ))
+                .arg(MASP_INDEXER.def().help(
+                    "The URL of a MASP indexer from which to download the \
+                     shielded transactions instead of querying the ledger.",
+                ))
+        }
+    }
+
+    impl CliToSdk<QueryShieldedNotes<SdkTypes>> for QueryShieldedNotes<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryShieldedNotes<SdkTypes> {
+            let query = self.query.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            QueryShieldedNotes::<SdkTypes> {
+                query,
+                viewing_key: self.viewing_key.map(|x| chain_ctx.get_cached(&x)),
+            }
+        }
+    }
+
+    impl Args for QueryShieldedNotes<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let viewing_key = VIEWING_KEY_OPT.parse(matches);
+            Self { query, viewing_key }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(VIEWING_KEY_OPT.def().help(
+                    "The viewing key whose notes to query. Defaults to all \
+                     the viewing keys in the wallet.",
+                ))
+        }
+    }
+
+    impl CliToSdk<QueryShieldedHistory<SdkTypes>>
+        for QueryShieldedHistory<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> QueryShieldedHistory<SdkTypes> {
+            let query = self.query.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            QueryShieldedHistory::<SdkTypes> {
+                query,
+                viewing_key: self.viewing_key.map(|x| chain_ctx.get_cached(&x)),
+                export: self.export,
+                format: self.format,
+            }
+        }
+    }
+
+    impl Args for QueryShieldedHistory<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
-            let owner = OWNER.parse(matches);
-            Self { query, owner }
+            let viewing_key = VIEWING_KEY_OPT.parse(matches);
+            let export = EXPORT_PATH.parse(matches);
+            let format = HISTORY_FORMAT.parse(matches);
+            Self {
+                query,
+                viewing_key,
+                export,
+                format,
+            }
         }
 
         fn def(app: App) -> App {
-            app.add_args::<Query<CliTypes>>().arg(
-                OWNER
-                    .def()
-                    .help("The substorage space address to query.")
-                    .required(true),
-            )
+            app.add_args::<Query<CliTypes>>()
+                .arg(VIEWING_KEY_OPT.def().help(
+                    "The viewing key whose history to query. Defaults to all \
+                     the viewing keys in the wallet.",
+                ))
+                .arg(EXPORT_PATH.def().help(
+                    "Write the history to the given file instead of printing \
+                     it.",
+                ))
+                .arg(HISTORY_FORMAT.def().help(
+                    "The format of the exported history, either csv or json. \
+                     Defaults to csv.",
+                ))
         }
     }
 
@@ -5641,6 +6934,95 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<BundleCreate<SdkTypes>> for BundleCreate<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> BundleCreate<SdkTypes> {
+            BundleCreate::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                tx_data: std::fs::read(self.tx_data).expect(""),
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+            }
+        }
+    }
+
+    impl Args for BundleCreate<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let tx_path = TX_PATH.parse(matches);
+            let owner = OWNER.parse(matches);
+            Self {
+                tx,
+                tx_data: tx_path,
+                owner,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(
+                    TX_PATH.def().help(
+                        "The path to the tx file with the serialized tx.",
+                    ),
+                )
+                .arg(OWNER.def().help("The address of the account owner"))
+        }
+    }
+
+    impl Args for BundleSign {
+        fn parse(matches: &ArgMatches) -> Self {
+            let bundle = BUNDLE.parse(matches);
+            Self { bundle }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                BUNDLE
+                    .def()
+                    .help("The path to the bundle, which is updated in place."),
+            )
+        }
+    }
+
+    impl Args for BundleMerge {
+        fn parse(matches: &ArgMatches) -> Self {
+            let bundles = BUNDLES.parse(matches);
+            let output = OUTPUT_PATH.parse(matches);
+            Self { bundles, output }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(BUNDLES.def().help(
+                "Comma-separated paths to the bundles of the same transaction.",
+            ))
+            .arg(
+                OUTPUT_PATH
+                    .def()
+                    .help("The path to write the merged bundle to."),
+            )
+        }
+    }
+
+    impl CliToSdk<BundleSubmit<SdkTypes>> for BundleSubmit<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> BundleSubmit<SdkTypes> {
+            BundleSubmit::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                bundle: self.bundle,
+            }
+        }
+    }
+
+    impl Args for BundleSubmit<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let bundle = BUNDLE.parse(matches);
+            Self { tx, bundle }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(BUNDLE.def().help("The path to the bundle."))
+        }
+    }
+
     impl CliToSdk<GenIbcShieldedTransafer<SdkTypes>>
         for GenIbcShieldedTransafer<CliTypes>
     {
@@ -5875,6 +7257,74 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<DiscoverAccounts<SdkTypes>> for DiscoverAccounts<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> DiscoverAccounts<SdkTypes> {
+            DiscoverAccounts::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                schemes: self.schemes,
+                shielded: self.shielded,
+                gap_limit: self.gap_limit,
+                alias_prefix: self.alias_prefix,
+                alias_force: self.alias_force,
+                unsafe_dont_encrypt: self.unsafe_dont_encrypt,
+            }
+        }
+    }
+
+    impl Args for DiscoverAccounts<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let schemes = SCHEMES.parse(matches);
+            let schemes = if schemes.is_empty() {
+                vec![SchemeType::Ed25519, SchemeType::Secp256k1]
+            } else {
+                schemes
+            };
+            let shielded = SHIELDED.parse(matches);
+            let gap_limit = GAP_LIMIT.parse(matches);
+            let alias_prefix = ALIAS_PREFIX.parse(matches);
+            let alias_force = ALIAS_FORCE.parse(matches);
+            let unsafe_dont_encrypt = UNSAFE_DONT_ENCRYPT.parse(matches);
+            Self {
+                query,
+                schemes,
+                shielded,
+                gap_limit,
+                alias_prefix,
+                alias_force,
+                unsafe_dont_encrypt,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(SCHEMES.def().help(
+                    "Comma-separated signature schemes of the transparent \
+                     accounts to discover. Defaults to ed25519 and secp256k1.",
+                ))
+                .arg(
+                    SHIELDED.def().help("Also discover the shielded accounts."),
+                )
+                .arg(GAP_LIMIT.def().help(
+                    "The number of consecutive unused accounts after which \
+                     the discovery stops. Defaults to 20.",
+                ))
+                .arg(ALIAS_PREFIX.def().help(
+                    "The prefix of the aliases of the discovered accounts, \
+                     which are followed by the kind and the index of the \
+                     account. Defaults to \"account\".",
+                ))
+                .arg(ALIAS_FORCE.def().help(
+                    "Override the aliases without confirmation if they \
+                     already exist.",
+                ))
+                .arg(UNSAFE_DONT_ENCRYPT.def().help(
+                    "UNSAFE: Do not encrypt the keys. Do not use this for \
+                     keys used in a live network.",
+                ))
+        }
+    }
+
     impl CliToSdk<QueryRawBytes<SdkTypes>> for QueryRawBytes<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> QueryRawBytes<SdkTypes> {
             QueryRawBytes::<SdkTypes> {
@@ -5925,6 +7375,7 @@ pub mod args {
                 dump_tx: self.dump_tx,
                 output_folder: self.output_folder,
                 force: self.force,
+                no_confirm: self.no_confirm,
                 broadcast_only: self.broadcast_only,
                 ledger_address: (),
                 initialized_account_alias: self.initialized_account_alias,
@@ -5935,6 +7386,7 @@ pub mod args {
                     .fee_unshield
                     .map(|ref fee_unshield| ctx.get_cached(fee_unshield)),
                 gas_limit: self.gas_limit,
+                estimate_gas: self.estimate_gas,
                 signing_keys: self
                     .signing_keys
                     .iter()
@@ -5954,6 +7406,7 @@ pub mod args {
                     .or_else(|| Some(ctx.config.ledger.chain_id.clone())),
                 wrapper_fee_payer: self.wrapper_fee_payer.map(|x| ctx.get(&x)),
                 use_device: self.use_device,
+                remote_signer: self.remote_signer,
             }
         }
     }
@@ -5979,6 +7432,14 @@ pub mod args {
             .arg(FORCE.def().help(
                 "Submit the transaction even if it doesn't pass client checks.",
             ))
+            .arg(
+                NO_CONFIRM
+                    .def()
+                    .help(
+                        "Sign the transaction without asking for confirmation.",
+                    )
+                    .alias("yes"),
+            )
             .arg(BROADCAST_ONLY.def().help(
                 "Do not wait for the transaction to be applied. This will \
                  return once the transaction is added to the mempool.",
@@ -6009,6 +7470,11 @@ pub mod args {
                 "The multiplier of the gas limit resolution defining the \
                  maximum amount of gas needed to run transaction.",
             ))
+            .arg(ESTIMATE_GAS.def().help(
+                "Estimate the gas limit of the transaction by dry running it \
+                 before signing, instead of using the given gas limit. A \
+                 safety margin is added to the gas used by the dry run.",
+            ))
             .arg(WALLET_ALIAS_FORCE.def().help(
                 "Override the alias without confirmation if it already exists.",
             ))
@@ -6067,6 +7533,16 @@ pub mod args {
                 "Use an attached hardware wallet device to sign the \
                  transaction.",
             ))
+            .arg(
+                REMOTE_SIGNER
+                    .def()
+                    .help(
+                        "The path to the socket of a signing daemon, e.g. \
+                         started with `namadaw key serve`. The daemon signs \
+                         with the keys that are not in the wallet.",
+                    )
+                    .conflicts_with(USE_DEVICE.name),
+            )
         }
 
         fn parse(matches: &ArgMatches) -> Self {
@@ -6074,6 +7550,7 @@ pub mod args {
             let dry_run_wrapper = DRY_RUN_WRAPPER_TX.parse(matches);
             let dump_tx = DUMP_TX.parse(matches);
             let force = FORCE.parse(matches);
+            let no_confirm = NO_CONFIRM.parse(matches);
             let broadcast_only = BROADCAST_ONLY.parse(matches);
             let ledger_address = LEDGER_ADDRESS_DEFAULT.parse(matches);
             let initialized_account_alias = ALIAS_OPT.parse(matches);
@@ -6083,6 +7560,7 @@ pub mod args {
             let fee_unshield = FEE_UNSHIELD_SPENDING_KEY.parse(matches);
             let _wallet_alias_force = WALLET_ALIAS_FORCE.parse(matches);
             let gas_limit = GAS_LIMIT.parse(matches);
+            let estimate_gas = ESTIMATE_GAS.parse(matches);
             let wallet_alias_force = WALLET_ALIAS_FORCE.parse(matches);
             let expiration = EXPIRATION_OPT.parse(matches);
             let disposable_signing_key = DISPOSABLE_SIGNING_KEY.parse(matches);
@@ -6094,11 +7572,13 @@ pub mod args {
             let wrapper_fee_payer = FEE_PAYER_OPT.parse(matches);
             let output_folder = OUTPUT_FOLDER_PATH.parse(matches);
             let use_device = USE_DEVICE.parse(matches);
+            let remote_signer = REMOTE_SIGNER.parse(matches);
             Self {
                 dry_run,
                 dry_run_wrapper,
                 dump_tx,
                 force,
+                no_confirm,
                 broadcast_only,
                 ledger_address,
                 initialized_account_alias,
@@ -6107,6 +7587,7 @@ pub mod args {
                 fee_token,
                 fee_unshield,
                 gas_limit,
+                estimate_gas,
                 expiration,
                 disposable_signing_key,
                 signing_keys,
@@ -6117,13 +7598,17 @@ pub mod args {
                 wrapper_fee_payer,
                 output_folder,
                 use_device,
+                remote_signer,
             }
         }
     }
 
     impl CliToSdkCtxless<Query<SdkTypes>> for Query<CliTypes> {
         fn to_sdk_ctxless(self) -> Query<SdkTypes> {
-            Query::<SdkTypes> { ledger_address: () }
+            Query::<SdkTypes> {
+                ledger_address: (),
+                output: self.output,
+            }
         }
     }
 
@@ -6136,11 +7621,20 @@ pub mod args {
                     // This used to be "ledger-address", alias for compatibility
                     .alias("ledger-address"),
             )
+            .arg(OUTPUT_FORMAT.def().help(
+                "The format of the output, either text or json. The json \
+                 output is a versioned document meant to be read by other \
+                 programs. Defaults to text.",
+            ))
         }
 
         fn parse(matches: &ArgMatches) -> Self {
             let ledger_address = LEDGER_ADDRESS_DEFAULT.parse(matches);
-            Self { ledger_address }
+            let output = OUTPUT_FORMAT.parse(matches);
+            Self {
+                ledger_address,
+                output,
+            }
         }
     }
 
@@ -6149,11 +7643,13 @@ pub mod args {
             let alias = ALIAS.parse(matches);
             let alias_force = ALIAS_FORCE.parse(matches);
             let value = MASP_VALUE.parse(matches);
+            let birthday = BIRTHDAY.parse(matches);
             let unsafe_dont_encrypt = UNSAFE_DONT_ENCRYPT.parse(matches);
             Self {
                 alias,
                 alias_force,
                 value,
+                birthday,
                 unsafe_dont_encrypt,
             }
         }
@@ -6172,6 +7668,11 @@ pub mod args {
                     .def()
                     .help("A spending key, viewing key, or payment address."),
             )
+            .arg(BIRTHDAY.def().help(
+                "The height of the first block that can contain notes for the \
+                 key. Earlier blocks are not scanned for its notes, which \
+                 speeds up its first sync.",
+            ))
             .arg(UNSAFE_DONT_ENCRYPT.def().help(
                 "UNSAFE: Do not encrypt the keypair. Do not use this for keys \
                  used in a live network.",
@@ -6483,6 +7984,159 @@ pub mod args {
         }
     }
 
+    impl Args for ContactAdd {
+        fn parse(matches: &ArgMatches) -> Self {
+            let alias = ALIAS.parse(matches);
+            let alias_force = ALIAS_FORCE.parse(matches);
+            let address = RAW_ADDRESS_OPT.parse(matches);
+            let payment_address = RAW_PAYMENT_ADDRESS_OPT.parse(matches);
+            let label = LABEL.parse(matches);
+            let tags = TAGS.parse(matches);
+            let memo_template = MEMO_TEMPLATE.parse(matches);
+            Self {
+                alias,
+                alias_force,
+                address,
+                payment_address,
+                label,
+                tags,
+                memo_template,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                ALIAS
+                    .def()
+                    .help("An alias to be associated with the contact."),
+            )
+            .arg(ALIAS_FORCE.def().help(
+                "Override the alias without confirmation if it already exists.",
+            ))
+            .arg(
+                RAW_ADDRESS_OPT
+                    .def()
+                    .help("The bech32m encoded transparent address."),
+            )
+            .arg(
+                RAW_PAYMENT_ADDRESS_OPT
+                    .def()
+                    .help("The bech32m encoded payment address."),
+            )
+            .group(
+                ArgGroup::new("contact-addresses")
+                    .args([RAW_ADDRESS_OPT.name, RAW_PAYMENT_ADDRESS_OPT.name])
+                    .required(true)
+                    .multiple(true),
+            )
+            .arg(LABEL.def().help("A human-readable label."))
+            .arg(TAGS.def().help("Comma-separated tags of the contact."))
+            .arg(MEMO_TEMPLATE.def().help(
+                "A memo attached to the transfers to the contact that don't \
+                 have one.",
+            ))
+        }
+    }
+
+    impl Args for WatchOnlyAdd {
+        fn parse(matches: &ArgMatches) -> Self {
+            let alias = ALIAS.parse(matches);
+            let alias_force = ALIAS_FORCE.parse(matches);
+            let address = RAW_ADDRESS.parse(matches);
+            let label = LABEL.parse(matches);
+            let tags = TAGS.parse(matches);
+            Self {
+                alias,
+                alias_force,
+                address,
+                label,
+                tags,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                ALIAS
+                    .def()
+                    .help("An alias to be associated with the account."),
+            )
+            .arg(ALIAS_FORCE.def().help(
+                "Override the alias without confirmation if it already exists.",
+            ))
+            .arg(
+                RAW_ADDRESS
+                    .def()
+                    .help("The bech32m encoded address string."),
+            )
+            .arg(LABEL.def().help("A human-readable label."))
+            .arg(TAGS.def().help("Comma-separated tags of the account."))
+        }
+    }
+
+    impl Args for AddressBookList {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tag = TAG_OPT.parse(matches);
+            Self { tag }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(TAG_OPT.def().help("Only list the entries with this tag."))
+        }
+    }
+
+    impl Args for AddressBookRemove {
+        fn parse(matches: &ArgMatches) -> Self {
+            let alias = ALIAS.parse(matches);
+            Self { alias }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(ALIAS.def().help("The alias of the entry to remove."))
+        }
+    }
+
+    impl Args for AddressBookExport {
+        fn parse(matches: &ArgMatches) -> Self {
+            let path = PATH.parse(matches);
+            Self { path }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(PATH.def().help("The path of the JSON file to write."))
+        }
+    }
+
+    impl Args for AddressBookImport {
+        fn parse(matches: &ArgMatches) -> Self {
+            let path = PATH.parse(matches);
+            let alias_force = ALIAS_FORCE.parse(matches);
+            Self { path, alias_force }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(PATH.def().help("The path of the JSON file to read."))
+                .arg(ALIAS_FORCE.def().help(
+                    "Override the aliases without confirmation if they \
+                     already exist.",
+                ))
+        }
+    }
+
+    impl Args for KeyServe {
+        fn parse(matches: &ArgMatches) -> Self {
+            let socket = SIGNER_SOCKET.parse(matches);
+            Self { socket }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                SIGNER_SOCKET
+                    .def()
+                    .help("The path of the socket to listen on."),
+            )
+        }
+    }
+
     impl Args for AddressOrAliasFind {
         fn parse(matches: &ArgMatches) -> Self {
             let alias = ALIAS_OPT.parse(matches);
@@ -7039,6 +8693,7 @@ pub enum NamadaRelayer {
     EthBridgePoolWithCtx(Box<(cmds::EthBridgePoolWithCtx, Context)>),
     EthBridgePoolWithoutCtx(cmds::EthBridgePoolWithoutCtx),
     ValidatorSet(cmds::ValidatorSet),
+    StateProof(cmds::StateProof),
 }
 
 pub fn namada_relayer_cli() -> Result<NamadaRelayer> {
@@ -7061,6 +8716,9 @@ pub fn namada_relayer_cli() -> Result<NamadaRelayer> {
             cmds::NamadaRelayer::ValidatorSet(sub_cmd) => {
                 Ok(NamadaRelayer::ValidatorSet(sub_cmd))
             }
+            cmds::NamadaRelayer::StateProof(sub_cmd) => {
+                Ok(NamadaRelayer::StateProof(sub_cmd))
+            }
         },
         None => {
             let mut app = app;
@@ -7070,6 +8728,16 @@ pub fn namada_relayer_cli() -> Result<NamadaRelayer> {
     }
 }
 
+pub fn namada_masp_indexer_cli() -> args::MaspIndexer {
+    let matches = namada_masp_indexer_app().get_matches();
+    <args::MaspIndexer as Args>::parse(&matches)
+}
+
+pub fn namada_query_gateway_cli() -> args::QueryGateway {
+    let matches = namada_query_gateway_app().get_matches();
+    <args::QueryGateway as Args>::parse(&matches)
+}
+
 fn namada_app() -> App {
     let app = App::new(APP_NAME)
         .version(namada_version())
@@ -7117,3 +8785,19 @@ fn namada_relayer_app() -> App {
         .subcommand_required(true);
     cmds::NamadaRelayer::add_sub(args::Global::def(app))
 }
+
+fn namada_masp_indexer_app() -> App {
+    let app = App::new(APP_NAME)
+        .version(namada_version())
+        .about("Namada MASP indexer.")
+        .color(ColorChoice::Auto);
+    args::MaspIndexer::def(app)
+}
+
+fn namada_query_gateway_app() -> App {
+    let app = App::new(APP_NAME)
+        .version(namada_version())
+        .about("Namada JSON gateway for the ledger queries.")
+        .color(ColorChoice::Auto);
+    args::QueryGateway::def(app)
+}
@@ -1,4 +1,4 @@
-use namada::tendermint_rpc::HttpClient;
+use namada::tendermint_rpc::{HttpClient, WebSocketClientUrl};
 use namada::types::io::Io;
 use namada_sdk::error::Error;
 use namada_sdk::queries::Client;
@@ -11,6 +11,9 @@ use crate::client::utils;
 #[async_trait::async_trait(?Send)]
 pub trait CliClient: Client + Sync {
     fn from_tendermint_address(address: &mut TendermintAddress) -> Self;
+    /// The websocket URL of the ledger at the given address, to subscribe to
+    /// the events of the submitted transactions, if the client supports it
+    fn events_url(address: &TendermintAddress) -> Option<WebSocketClientUrl>;
     async fn wait_until_node_is_synced(
         &self,
         io: &impl Io,
@@ -23,6 +26,15 @@ impl CliClient for HttpClient {
         HttpClient::new(utils::take_config_address(address)).unwrap()
     }
 
+    fn events_url(address: &TendermintAddress) -> Option<WebSocketClientUrl> {
+        match address {
+            TendermintAddress::Tcp { host, port, .. } => {
+                format!("ws://{host}:{port}/websocket").parse().ok()
+            }
+            TendermintAddress::Unix { .. } => None,
+        }
+    }
+
     async fn wait_until_node_is_synced(
         &self,
         io: &impl Io,
@@ -24,6 +24,7 @@ impl CliApi {
                 match cmd {
                     // Ledger cmds
                     Sub::TxCustom(TxCustom(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -31,7 +32,8 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         let dry_run =
                             args.tx.dry_run || args.tx.dry_run_wrapper;
                         tx::submit_custom(&namada, args).await?;
@@ -49,6 +51,7 @@ impl CliApi {
                         }
                     }
                     Sub::TxTransfer(TxTransfer(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -56,10 +59,12 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_transfer(&namada, args).await?;
                     }
                     Sub::TxIbcTransfer(TxIbcTransfer(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -67,10 +72,12 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_ibc_transfer(&namada, args).await?;
                     }
                     Sub::TxUpdateAccount(TxUpdateAccount(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -78,10 +85,12 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_update_account(&namada, args).await?;
                     }
                     Sub::TxInitAccount(TxInitAccount(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -89,7 +98,8 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         let dry_run =
                             args.tx.dry_run || args.tx.dry_run_wrapper;
                         tx::submit_init_account(&namada, args).await?;
@@ -107,6 +117,7 @@ impl CliApi {
                         }
                     }
                     Sub::TxBecomeValidator(TxBecomeValidator(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -126,11 +137,13 @@ impl CliApi {
                             shielded,
                             io,
                             native_token,
-                        );
+                        )
+                        .with_events_url(events_url);
                         tx::submit_become_validator(&namada, &mut config, args)
                             .await?;
                     }
                     Sub::TxInitValidator(TxInitValidator(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -150,11 +163,13 @@ impl CliApi {
                             shielded,
                             io,
                             native_token,
-                        );
+                        )
+                        .with_events_url(events_url);
                         tx::submit_init_validator(&namada, &mut config, args)
                             .await?;
                     }
                     Sub::TxInitProposal(TxInitProposal(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -162,10 +177,12 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_init_proposal(&namada, args).await?;
                     }
                     Sub::TxVoteProposal(TxVoteProposal(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -173,10 +190,12 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_vote_proposal(&namada, args).await?;
                     }
                     Sub::TxRevealPk(TxRevealPk(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -184,10 +203,12 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_reveal_pk(&namada, args).await?;
                     }
                     Sub::Bond(Bond(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -195,10 +216,12 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_bond(&namada, args).await?;
                     }
                     Sub::Unbond(Unbond(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -206,10 +229,12 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_unbond(&namada, args).await?;
                     }
                     Sub::Withdraw(Withdraw(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -217,10 +242,12 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_withdraw(&namada, args).await?;
                     }
                     Sub::ClaimRewards(ClaimRewards(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -228,10 +255,12 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_claim_rewards(&namada, args).await?;
                     }
                     Sub::Redelegate(Redelegate(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -239,12 +268,14 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_redelegate(&namada, args).await?;
                     }
                     Sub::TxCommissionRateChange(TxCommissionRateChange(
                         mut args,
                     )) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -252,13 +283,15 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_validator_commission_change(&namada, args)
                             .await?;
                     }
                     Sub::TxChangeConsensusKey(TxChangeConsensusKey(
                         mut args,
                     )) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -278,7 +311,8 @@ impl CliApi {
                             shielded,
                             io,
                             native_token,
-                        );
+                        )
+                        .with_events_url(events_url);
                         tx::submit_change_consensus_key(
                             &namada,
                             &mut config,
@@ -287,6 +321,7 @@ impl CliApi {
                         .await?;
                     }
                     Sub::TxMetadataChange(TxMetadataChange(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -294,13 +329,15 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_validator_metadata_change(&namada, args)
                             .await?;
                     }
                     // Eth bridge
                     Sub::AddToEthBridgePool(args) => {
                         let mut args = args.0;
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -308,10 +345,26 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_bridge_pool_tx(&namada, args).await?;
                     }
+                    Sub::BumpEthBridgePoolFee(args) => {
+                        let mut args = args.0;
+                        let events_url = C::events_url(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
+                        tx::submit_bump_bridge_pool_fee(&namada, args).await?;
+                    }
                     Sub::TxUnjailValidator(TxUnjailValidator(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -319,12 +372,14 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_unjail_validator(&namada, args).await?;
                     }
                     Sub::TxDeactivateValidator(TxDeactivateValidator(
                         mut args,
                     )) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -332,12 +387,14 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_deactivate_validator(&namada, args).await?;
                     }
                     Sub::TxReactivateValidator(TxReactivateValidator(
                         mut args,
                     )) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -345,12 +402,14 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_reactivate_validator(&namada, args).await?;
                     }
                     Sub::TxUpdateStewardCommission(
                         TxUpdateStewardCommission(mut args),
                     ) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -358,11 +417,13 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_update_steward_commission(&namada, args)
                             .await?;
                     }
                     Sub::TxResignSteward(TxResignSteward(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -370,7 +431,8 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::submit_resign_steward(&namada, args).await?;
                     }
                     // Ledger queries
@@ -380,7 +442,16 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let namada = ctx.to_sdk(client, io);
-                        rpc::query_and_print_epoch(&namada).await;
+                        rpc::query_and_output_epoch(&namada, args.output).await;
+                    }
+                    Sub::QueryWatched(QueryWatched(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&mut args.ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_watch_only_accounts(&namada, args.output)
+                            .await;
                     }
                     Sub::QueryValidatorState(QueryValidatorState(mut args)) => {
                         let client = client.unwrap_or_else(|| {
@@ -416,13 +487,48 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_conversions(&namada, args).await;
                     }
+                    Sub::ShieldedSync(ShieldedSync(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::shielded_sync(&namada, args).await;
+                    }
+                    Sub::QueryShieldedNotes(QueryShieldedNotes(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_shielded_notes(&namada, args).await;
+                    }
+                    Sub::QueryShieldedHistory(QueryShieldedHistory(
+                        mut args,
+                    )) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_shielded_history(&namada, args).await;
+                    }
                     Sub::QueryBlock(QueryBlock(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(&mut args.ledger_address)
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let namada = ctx.to_sdk(client, io);
-                        rpc::query_block(&namada).await;
+                        rpc::query_block(&namada, args.output).await;
                     }
                     Sub::QueryBalance(QueryBalance(mut args)) => {
                         let client = client.unwrap_or_else(|| {
@@ -606,6 +712,7 @@ impl CliApi {
                         rpc::query_account(&namada, args).await;
                     }
                     Sub::SignTx(SignTx(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
@@ -613,7 +720,8 @@ impl CliApi {
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
-                        let namada = ctx.to_sdk(client, io);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
                         tx::sign_tx(&namada, args).await?;
                     }
                     Sub::GenIbcShieldedTransafer(GenIbcShieldedTransafer(
@@ -629,6 +737,53 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::gen_ibc_shielded_transfer(&namada, args).await?;
                     }
+                    Sub::BundleCreate(BundleCreate(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
+                        tx::bundle_create(&namada, args).await?;
+                    }
+                    Sub::BundleSign(BundleSign(args)) => {
+                        let wallet = &mut ctx.borrow_mut_chain_or_exit().wallet;
+                        tx::bundle_sign(&io, wallet, args);
+                        wallet
+                            .save()
+                            .unwrap_or_else(|err| eprintln!("{}", err));
+                    }
+                    Sub::BundleMerge(BundleMerge(args)) => {
+                        tx::bundle_merge(&io, args);
+                    }
+                    Sub::BundleSubmit(BundleSubmit(mut args)) => {
+                        let events_url = C::events_url(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada =
+                            ctx.to_sdk(client, io).with_events_url(events_url);
+                        tx::bundle_submit(&namada, args).await?;
+                    }
+                    Sub::DiscoverAccounts(DiscoverAccounts(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::discover_accounts(&namada, args).await;
+                    }
                 }
             }
             cli::NamadaClient::WithoutContext(cmd, global_args) => match cmd {
@@ -17,7 +17,7 @@ use namada::types::masp::*;
 use namada_sdk::masp::fs::FsShieldedUtils;
 use namada_sdk::masp::ShieldedContext;
 use namada_sdk::wallet::Wallet;
-use namada_sdk::{Namada, NamadaImpl};
+use namada_sdk::NamadaImpl;
 
 use super::args;
 use crate::cli::utils;
@@ -114,7 +114,14 @@ impl Context {
                         .expect("Missing genesis files");
                 let native_token = genesis.get_native_token().clone();
                 let wallet = if wallet::exists(&chain_dir) {
-                    wallet::load(&chain_dir).unwrap()
+                    wallet::load(&chain_dir).unwrap_or_else(|err| {
+                        eprintln!(
+                            "Unable to load the wallet at {}: {}",
+                            chain_dir.to_string_lossy(),
+                            err
+                        );
+                        utils::safe_exit(1)
+                    })
                 } else {
                     panic!(
                         "Could not find wallet at {}.",
@@ -175,7 +182,11 @@ impl Context {
     }
 
     /// Make an implementation of Namada from this object and parameters.
-    pub fn to_sdk<C, IO>(self, client: C, io: IO) -> impl Namada
+    pub fn to_sdk<C, IO>(
+        self,
+        client: C,
+        io: IO,
+    ) -> NamadaImpl<C, CliWalletUtils, FsShieldedUtils, IO>
     where
         C: namada::ledger::queries::Client + Sync,
         IO: Io,
@@ -304,6 +315,21 @@ impl FromContext<TransferSource> {
             phantom: PhantomData,
         }
     }
+
+    /// Converts this TransferSource argument to an ExtendedViewingKey, unless
+    /// raw represents a transparent address. Used for shielded sources whose
+    /// spending key is held by a hardware wallet.
+    pub fn to_viewing_key(
+        &self,
+        ctx: &ChainContext,
+    ) -> Option<FromContext<ExtendedViewingKey>> {
+        Address::arg_from_ctx(ctx, &self.raw)
+            .is_err()
+            .then(|| FromContext::<ExtendedViewingKey> {
+                raw: self.raw.clone(),
+                phantom: PhantomData,
+            })
+    }
 }
 
 impl FromContext<TransferTarget> {
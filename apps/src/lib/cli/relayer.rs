@@ -1,6 +1,6 @@
 use color_eyre::eyre::Result;
 use namada::types::io::Io;
-use namada_sdk::eth_bridge::{bridge_pool, validator_set};
+use namada_sdk::eth_bridge::{bridge_pool, state_proof, validator_set};
 
 use crate::cli;
 use crate::cli::api::{CliApi, CliClient};
@@ -34,6 +34,24 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         bridge_pool::recommend_batch(&namada, args).await?;
                     }
+                    EthBridgePoolWithCtx::RelayDaemon(RelayDaemon(
+                        mut args,
+                    )) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.recommend.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let eth_client =
+                            get_eth_rpc_client(&args.eth_rpc_endpoint).await;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        bridge_pool::relay_bridge_pool_daemon(
+                            eth_client, &namada, args,
+                        )
+                        .await?;
+                    }
                 }
             }
             cli::NamadaRelayer::EthBridgePoolWithoutCtx(sub) => match sub {
@@ -82,6 +100,16 @@ impl CliApi {
                     client.wait_until_node_is_synced(&io).await?;
                     bridge_pool::query_signed_bridge_pool(&client, &io).await?;
                 }
+                EthBridgePoolWithoutCtx::QueryFeeStats(
+                    QueryBridgePoolFeeStats(mut query),
+                ) => {
+                    let client = client.unwrap_or_else(|| {
+                        C::from_tendermint_address(&mut query.ledger_address)
+                    });
+                    client.wait_until_node_is_synced(&io).await?;
+                    bridge_pool::query_bridge_pool_fee_stats(&client, &io)
+                        .await?;
+                }
                 EthBridgePoolWithoutCtx::QueryRelays(QueryRelayProgress(
                     mut query,
                 )) => {
@@ -156,6 +184,36 @@ impl CliApi {
                     .await?;
                 }
             },
+            cli::NamadaRelayer::StateProof(sub) => match sub {
+                StateProof::ConstructStateProof(ConstructStateProof(
+                    mut args,
+                )) => {
+                    let client = client.unwrap_or_else(|| {
+                        C::from_tendermint_address(
+                            &mut args.query.ledger_address,
+                        )
+                    });
+                    client.wait_until_node_is_synced(&io).await?;
+                    let args = args.to_sdk_ctxless();
+                    state_proof::construct_state_proof(&client, &io, args)
+                        .await?;
+                }
+                StateProof::RelayStateProof(RelayStateProof(mut args)) => {
+                    let client = client.unwrap_or_else(|| {
+                        C::from_tendermint_address(
+                            &mut args.query.ledger_address,
+                        )
+                    });
+                    client.wait_until_node_is_synced(&io).await?;
+                    let eth_client =
+                        get_eth_rpc_client(&args.eth_rpc_endpoint).await;
+                    let args = args.to_sdk_ctxless();
+                    state_proof::relay_state_proof(
+                        eth_client, &client, &io, args,
+                    )
+                    .await?;
+                }
+            },
         }
         Ok(())
     }
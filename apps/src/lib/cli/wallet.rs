@@ -7,7 +7,7 @@ use std::str::FromStr;
 use borsh::BorshDeserialize;
 use borsh_ext::BorshSerializeExt;
 use color_eyre::eyre::Result;
-use itertools::sorted;
+use itertools::{sorted, Itertools};
 use ledger_namada_rs::{BIP44Path, NamadaApp};
 use ledger_transport_hid::hidapi::HidApi;
 use ledger_transport_hid::TransportNativeHID;
@@ -16,12 +16,14 @@ use namada::types::address::Address;
 use namada::types::io::Io;
 use namada::types::key::*;
 use namada::types::masp::{MaspValue, PaymentAddress};
-use namada_sdk::masp::find_valid_diversifier;
+use namada_sdk::masp::{find_valid_diversifier, to_viewing_key};
 use namada_sdk::wallet::{
-    DecryptionError, DerivationPath, DerivationPathError, FindKeyError, Wallet,
+    AddressBook, Contact, DecryptionError, DerivationPath, DerivationPathError,
+    FindKeyError, Wallet, WatchOnlyAccount,
 };
 use namada_sdk::{display, display_line, edisplay_line};
 use rand_core::OsRng;
+use zeroize::Zeroizing;
 
 use crate::cli;
 use crate::cli::api::CliApi;
@@ -55,6 +57,9 @@ impl CliApi {
                 cmds::WalletKey::Export(cmds::Export(args)) => {
                     key_export(ctx, io, args)
                 }
+                cmds::WalletKey::Serve(cmds::KeyServe(args)) => {
+                    key_serve(ctx, io, args).await
+                }
             },
             cmds::NamadaWallet::Address(sub) => match sub {
                 cmds::WalletAddress::Gen(cmds::AddressGen(args)) => {
@@ -80,7 +85,7 @@ impl CliApi {
                     payment_address_gen(ctx, io, args)
                 }
                 cmds::WalletMasp::AddAddrKey(cmds::MaspAddAddrKey(args)) => {
-                    address_key_add(ctx, io, args)
+                    address_key_add(ctx, io, args).await
                 }
                 cmds::WalletMasp::ListPayAddrs => {
                     payment_addresses_list(ctx, io)
@@ -92,6 +97,33 @@ impl CliApi {
                     address_key_find(ctx, io, args)
                 }
             },
+            cmds::NamadaWallet::Store(sub) => match sub {
+                cmds::WalletStore::Encrypt => store_encrypt(ctx, io),
+                cmds::WalletStore::Decrypt => store_decrypt(ctx, io),
+                cmds::WalletStore::ChangePassword => {
+                    store_change_password(ctx, io)
+                }
+            },
+            cmds::NamadaWallet::Book(sub) => match sub {
+                cmds::WalletBook::AddContact(cmds::BookAddContact(args)) => {
+                    contact_add(ctx, io, args)
+                }
+                cmds::WalletBook::Watch(cmds::BookWatch(args)) => {
+                    watch_only_add(ctx, io, args)
+                }
+                cmds::WalletBook::List(cmds::BookList(args)) => {
+                    address_book_list(ctx, io, args)
+                }
+                cmds::WalletBook::Remove(cmds::BookRemove(args)) => {
+                    address_book_remove(ctx, io, args)
+                }
+                cmds::WalletBook::Export(cmds::BookExport(args)) => {
+                    address_book_export(ctx, io, args)
+                }
+                cmds::WalletBook::Import(cmds::BookImport(args)) => {
+                    address_book_import(ctx, io, args)
+                }
+            },
         }
         Ok(())
     }
@@ -244,7 +276,7 @@ fn spending_key_gen(
 ) {
     let mut wallet = load_wallet(ctx);
     let alias = alias.to_lowercase();
-    let password = read_and_confirm_encryption_password(unsafe_dont_encrypt);
+    let password = read_key_encryption_password(&wallet, unsafe_dont_encrypt);
     let (alias, _key) =
         wallet.gen_store_spending_key(alias, password, alias_force, &mut OsRng);
     wallet.save().unwrap_or_else(|err| eprintln!("{}", err));
@@ -293,18 +325,33 @@ fn payment_address_gen(
 }
 
 /// Add a viewing key, spending key, or payment address to wallet.
-fn address_key_add(
+async fn address_key_add(
     ctx: Context,
     io: &impl Io,
     args::MaspAddrKeyAdd {
         alias,
         alias_force,
         value,
+        birthday,
         unsafe_dont_encrypt,
     }: args::MaspAddrKeyAdd,
 ) {
     let alias = alias.to_lowercase();
-    let mut wallet = load_wallet(ctx);
+    // The birthday of a key is recorded in the shielded context of the chain
+    let (mut wallet, shielded) = match (birthday, &value) {
+        (None, _) => (load_wallet(ctx), None),
+        (Some(_), MaspValue::PaymentAddress(_)) => {
+            edisplay_line!(
+                io,
+                "A birthday can only be set for viewing and spending keys"
+            );
+            cli::safe_exit(1)
+        }
+        (Some(height), _) => {
+            let chain_ctx = ctx.take_chain_or_exit();
+            (chain_ctx.wallet, Some((chain_ctx.shielded, height)))
+        }
+    };
     let (alias, typ) = match value {
         MaspValue::FullViewingKey(viewing_key) => {
             let alias = wallet
@@ -317,7 +364,7 @@ fn address_key_add(
         }
         MaspValue::ExtendedSpendingKey(spending_key) => {
             let password =
-                read_and_confirm_encryption_password(unsafe_dont_encrypt);
+                read_key_encryption_password(&wallet, unsafe_dont_encrypt);
             let alias = wallet
                 .insert_spending_key(alias, spending_key, password, alias_force)
                 .unwrap_or_else(|| {
@@ -343,6 +390,39 @@ fn address_key_add(
         typ,
         alias,
     );
+    if let Some((mut shielded, height)) = shielded {
+        let vk = match value {
+            MaspValue::FullViewingKey(viewing_key) => {
+                ExtendedFullViewingKey::from(viewing_key).fvk.vk
+            }
+            MaspValue::ExtendedSpendingKey(spending_key) => {
+                to_viewing_key(&spending_key.into()).vk
+            }
+            MaspValue::PaymentAddress(_) => unreachable!(),
+        };
+        if let Err(err) = shielded.load().await {
+            edisplay_line!(
+                io,
+                "Failed to load the saved shielded context: {}",
+                err
+            );
+            cli::safe_exit(1)
+        }
+        shielded.set_birthday(vk, height);
+        shielded.save().await.unwrap_or_else(|err| {
+            edisplay_line!(
+                io,
+                "Failed to save the birthday of the key: {}",
+                err
+            );
+            cli::safe_exit(1)
+        });
+        display_line!(
+            io,
+            "Notes of the key will be scanned from block height {}",
+            height
+        );
+    }
 }
 
 /// Decode the derivation path from the given string unless it is "default",
@@ -389,7 +469,7 @@ async fn key_and_address_derive(
         });
     let alias = if !use_device {
         let encryption_password =
-            read_and_confirm_encryption_password(unsafe_dont_encrypt);
+            read_key_encryption_password(&wallet, unsafe_dont_encrypt);
         wallet
             .derive_key_from_mnemonic_code(
                 scheme,
@@ -478,7 +558,7 @@ fn key_and_address_gen(
 ) {
     let mut wallet = load_wallet(ctx);
     let encryption_password =
-        read_and_confirm_encryption_password(unsafe_dont_encrypt);
+        read_key_encryption_password(&wallet, unsafe_dont_encrypt);
     let derivation_path = decode_derivation_path(scheme, derivation_path)
         .unwrap_or_else(|err| {
             edisplay_line!(io, "{}", err);
@@ -656,6 +736,354 @@ fn key_export(
         })
 }
 
+/// Read a new master password for the wallet store from the tty.
+fn read_master_password(io: &impl Io) -> Zeroizing<String> {
+    let prompt = "Enter the new master password of the wallet: ";
+    match wallet::read_and_confirm_passphrase_tty(prompt) {
+        Ok(password) if !password.is_empty() => password,
+        Ok(_) => {
+            edisplay_line!(io, "Password cannot be empty");
+            cli::safe_exit(1)
+        }
+        Err(err) => {
+            edisplay_line!(io, "{}", err);
+            cli::safe_exit(1)
+        }
+    }
+}
+
+/// Encrypt the wallet store with a new master password and save it.
+fn encrypt_and_save_store(io: &impl Io, mut wallet: Wallet<CliWalletUtils>) {
+    let password = read_master_password(io);
+    let encrypted_keys = wallet.encrypt_store(password);
+    if !encrypted_keys.is_empty() {
+        display_line!(
+            io,
+            "The following keys are still encrypted with their own password: \
+             {}",
+            encrypted_keys.iter().join(", ")
+        );
+    }
+    wallet.save().unwrap_or_else(|err| {
+        edisplay_line!(io, "{}", err);
+        cli::safe_exit(1)
+    });
+}
+
+/// Convert the wallet store to the encrypted format.
+fn store_encrypt(ctx: Context, io: &impl Io) {
+    let wallet = load_wallet(ctx);
+    if wallet.is_store_encrypted() {
+        edisplay_line!(
+            io,
+            "The wallet store is already encrypted. Use `store \
+             change-password` to change its master password."
+        );
+        cli::safe_exit(1)
+    }
+    encrypt_and_save_store(io, wallet);
+    display_line!(io, "Successfully encrypted the wallet store");
+}
+
+/// Convert the wallet store back to the plaintext format.
+fn store_decrypt(ctx: Context, io: &impl Io) {
+    let mut wallet = load_wallet(ctx);
+    if !wallet.is_store_encrypted() {
+        edisplay_line!(io, "The wallet store is not encrypted.");
+        cli::safe_exit(1)
+    }
+    wallet.decrypt_store();
+    wallet.save().unwrap_or_else(|err| {
+        edisplay_line!(io, "{}", err);
+        cli::safe_exit(1)
+    });
+    display_line!(io, "Successfully decrypted the wallet store");
+}
+
+/// Change the master password of the wallet store.
+fn store_change_password(ctx: Context, io: &impl Io) {
+    let wallet = load_wallet(ctx);
+    if !wallet.is_store_encrypted() {
+        edisplay_line!(
+            io,
+            "The wallet store is not encrypted. Use `store encrypt` to \
+             encrypt it."
+        );
+        cli::safe_exit(1)
+    }
+    encrypt_and_save_store(io, wallet);
+    display_line!(io, "Successfully changed the master password");
+}
+
+/// Add a contact to the address book.
+fn contact_add(
+    ctx: Context,
+    io: &impl Io,
+    args::ContactAdd {
+        alias,
+        alias_force,
+        address,
+        payment_address,
+        label,
+        tags,
+        memo_template,
+    }: args::ContactAdd,
+) {
+    if address.is_none() && payment_address.is_none() {
+        edisplay_line!(
+            io,
+            "A contact needs a transparent or a payment address."
+        );
+        cli::safe_exit(1)
+    }
+    let mut wallet = load_wallet(ctx);
+    let contact = Contact {
+        address,
+        payment_address,
+        label,
+        tags: tags.into_iter().collect(),
+        memo_template,
+    };
+    let alias = wallet
+        .insert_contact(alias.to_lowercase(), contact, alias_force)
+        .unwrap_or_else(|| {
+            edisplay_line!(io, "Contact not added");
+            cli::safe_exit(1);
+        });
+    wallet
+        .save()
+        .unwrap_or_else(|err| edisplay_line!(io, "{}", err));
+    display_line!(
+        io,
+        "Successfully added a contact with the alias \"{}\" to the address \
+         book.",
+        alias
+    );
+}
+
+/// Add a watch-only account to the address book.
+fn watch_only_add(
+    ctx: Context,
+    io: &impl Io,
+    args::WatchOnlyAdd {
+        alias,
+        alias_force,
+        address,
+        label,
+        tags,
+    }: args::WatchOnlyAdd,
+) {
+    let mut wallet = load_wallet(ctx);
+    let account = WatchOnlyAccount {
+        address,
+        label,
+        tags: tags.into_iter().collect(),
+    };
+    let alias = wallet
+        .insert_watch_only(alias.to_lowercase(), account, alias_force)
+        .unwrap_or_else(|| {
+            edisplay_line!(io, "Watch-only account not added");
+            cli::safe_exit(1);
+        });
+    wallet
+        .save()
+        .unwrap_or_else(|err| edisplay_line!(io, "{}", err));
+    display_line!(
+        io,
+        "Successfully added a watch-only account with the alias \"{}\" to the \
+         address book.",
+        alias
+    );
+}
+
+/// List the contacts and watch-only accounts of the address book.
+fn address_book_list(
+    ctx: Context,
+    io: &impl Io,
+    args::AddressBookList { tag }: args::AddressBookList,
+) {
+    let wallet = load_wallet(ctx);
+    let book = wallet.get_address_book();
+    let has_tag = |tags: &std::collections::BTreeSet<String>| {
+        tag.as_ref().map_or(true, |tag| tags.contains(tag))
+    };
+    let contacts = book
+        .contacts()
+        .iter()
+        .filter(|(_, contact)| has_tag(&contact.tags))
+        .collect::<Vec<_>>();
+    let watch_only = book
+        .watch_only_accounts()
+        .iter()
+        .filter(|(_, account)| has_tag(&account.tags))
+        .collect::<Vec<_>>();
+    if contacts.is_empty() && watch_only.is_empty() {
+        display_line!(
+            io,
+            "No address book entries found. Try `book add --alias my-contact \
+             --address ...` to add a contact."
+        );
+        return;
+    }
+    let stdout = io::stdout();
+    let mut w = stdout.lock();
+    if !contacts.is_empty() {
+        display_line!(io, &mut w; "Contacts:").unwrap();
+        for (alias, contact) in contacts {
+            display_line!(io, &mut w; "  Alias \"{}\"", alias).unwrap();
+            if let Some(label) = &contact.label {
+                display_line!(io, &mut w; "    Label: {}", label).unwrap();
+            }
+            if let Some(address) = &contact.address {
+                display_line!(io, &mut w; "    Address: {}", address.to_pretty_string()).unwrap();
+            }
+            if let Some(payment_address) = &contact.payment_address {
+                display_line!(io, &mut w; "    Payment address: {}", payment_address).unwrap();
+            }
+            if !contact.tags.is_empty() {
+                display_line!(io, &mut w; "    Tags: {}", contact.tags.iter().join(", ")).unwrap();
+            }
+            if let Some(memo) = &contact.memo_template {
+                display_line!(io, &mut w; "    Memo template: {}", memo)
+                    .unwrap();
+            }
+        }
+    }
+    if !watch_only.is_empty() {
+        display_line!(io, &mut w; "Watch-only accounts:").unwrap();
+        for (alias, account) in watch_only {
+            display_line!(io, &mut w; "  Alias \"{}\"", alias).unwrap();
+            if let Some(label) = &account.label {
+                display_line!(io, &mut w; "    Label: {}", label).unwrap();
+            }
+            display_line!(io, &mut w; "    Address: {}", account.address.to_pretty_string()).unwrap();
+            if !account.tags.is_empty() {
+                display_line!(io, &mut w; "    Tags: {}", account.tags.iter().join(", ")).unwrap();
+            }
+        }
+    }
+}
+
+/// Remove a contact or a watch-only account from the address book.
+fn address_book_remove(
+    ctx: Context,
+    io: &impl Io,
+    args::AddressBookRemove { alias }: args::AddressBookRemove,
+) {
+    let mut wallet = load_wallet(ctx);
+    let alias = alias.to_lowercase();
+    if !wallet.remove_book_entry(&alias) {
+        edisplay_line!(
+            io,
+            "No address book entry with alias {} found. Use the command `book \
+             list` to see all the entries.",
+            alias
+        );
+        cli::safe_exit(1)
+    }
+    wallet
+        .save()
+        .unwrap_or_else(|err| edisplay_line!(io, "{}", err));
+    display_line!(
+        io,
+        "Successfully removed \"{}\" from the address book.",
+        alias
+    );
+}
+
+/// Export the address book to a JSON file.
+fn address_book_export(
+    ctx: Context,
+    io: &impl Io,
+    args::AddressBookExport { path }: args::AddressBookExport,
+) {
+    let wallet = load_wallet(ctx);
+    let json = wallet.get_address_book().to_json();
+    std::fs::write(&path, json).unwrap_or_else(|err| {
+        edisplay_line!(
+            io,
+            "Failed to write the address book to {}: {}",
+            path.to_string_lossy(),
+            err
+        );
+        cli::safe_exit(1)
+    });
+    display_line!(
+        io,
+        "Exported the address book to {}",
+        path.to_string_lossy()
+    );
+}
+
+/// Import the entries of an address book from a JSON file.
+fn address_book_import(
+    ctx: Context,
+    io: &impl Io,
+    args::AddressBookImport { path, alias_force }: args::AddressBookImport,
+) {
+    let json = std::fs::read_to_string(&path).unwrap_or_else(|err| {
+        edisplay_line!(
+            io,
+            "Failed to read the address book from {}: {}",
+            path.to_string_lossy(),
+            err
+        );
+        cli::safe_exit(1)
+    });
+    let book = AddressBook::from_json(&json).unwrap_or_else(|err| {
+        edisplay_line!(io, "{}", err);
+        cli::safe_exit(1)
+    });
+    let mut wallet = load_wallet(ctx);
+    let aliases = wallet.import_address_book(book, alias_force);
+    wallet
+        .save()
+        .unwrap_or_else(|err| edisplay_line!(io, "{}", err));
+    display_line!(
+        io,
+        "Imported {} address book entries: {}",
+        aliases.len(),
+        aliases.iter().join(", ")
+    );
+}
+
+/// Read the password to encrypt a new key with, unless the whole store is
+/// encrypted, which already protects the key.
+fn read_key_encryption_password(
+    wallet: &Wallet<CliWalletUtils>,
+    unsafe_dont_encrypt: bool,
+) -> Option<Zeroizing<String>> {
+    read_and_confirm_encryption_password(
+        unsafe_dont_encrypt || wallet.is_store_encrypted(),
+    )
+}
+
+/// Sign with the keys of the wallet on behalf of remote signer clients.
+#[cfg(unix)]
+async fn key_serve(
+    ctx: Context,
+    io: &impl Io,
+    args::KeyServe { socket }: args::KeyServe,
+) {
+    use namada_sdk::signer::{self, WalletSigner};
+    use tokio::sync::RwLock;
+
+    let wallet = RwLock::new(load_wallet(ctx));
+    let signer = WalletSigner::new(&wallet, None);
+    display_line!(io, "Serving the keys of the wallet at {}", socket.display());
+    signer::serve(&socket, &signer).await.unwrap_or_else(|err| {
+        edisplay_line!(io, "{}", err);
+        cli::safe_exit(1)
+    })
+}
+
+/// Signing daemons listen on Unix sockets.
+#[cfg(not(unix))]
+async fn key_serve(_ctx: Context, io: &impl Io, _args: args::KeyServe) {
+    edisplay_line!(io, "Signing daemons are only supported on Unix.");
+    cli::safe_exit(1)
+}
+
 /// List all known addresses.
 fn address_list(ctx: Context, io: &impl Io) {
     let wallet = load_wallet(ctx);
@@ -673,7 +1101,10 @@ fn address_list(ctx: Context, io: &impl Io) {
         for (alias, address) in sorted(known_addresses) {
             display_line!(io,
                 &mut w;
-                "  \"{}\": {}", alias, address.to_pretty_string(),
+                "  \"{}\": {} ({})",
+                alias,
+                address.to_pretty_string(),
+                wallet.address_ownership(&address),
             )
             .unwrap();
         }
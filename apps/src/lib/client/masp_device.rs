@@ -0,0 +1,388 @@
+//! Client for the MASP endpoints of a hardware wallet. The device never
+//! reveals the spend authorizing key of a MASP account: it only exports the
+//! proof generation key, with which the host can prove the spends of a
+//! shielded transaction, and authorizes each spend with a signature over the
+//! transaction's sighash. [`MaspDevice::gen_shielded_transfer`] runs the
+//! whole flow, and is used by transfers from a shielded source with
+//! `--use-device`.
+
+use std::fmt::Display;
+
+use ledger_transport::{APDUAnswer, APDUCommand, APDUErrorCode, Exchange};
+use masp_primitives::ff::PrimeField;
+use masp_primitives::group::GroupEncoding;
+use masp_primitives::memo::MemoBytes;
+use masp_primitives::sapling::redjubjub::Signature;
+use masp_primitives::sapling::ProofGenerationKey;
+use masp_primitives::transaction::Transaction;
+use masp_primitives::zip32::ExtendedFullViewingKey;
+use masp_proofs::jubjub;
+use namada::types::address::Address;
+use namada::types::masp::TransferTarget;
+use namada::types::token;
+use namada_sdk::masp::{
+    apply_spend_signatures, shielded_sighash, ShieldedContext, ShieldedTransfer,
+};
+use namada_sdk::Namada;
+use thiserror::Error;
+
+/// The instruction class of the Namada app
+pub const CLA: u8 = 0x57;
+/// Instruction to export the proof generation key of a MASP account
+pub const INS_GET_PROOF_GEN_KEY: u8 = 0x30;
+/// Instruction to sign a spend with the spend authorizing key of a MASP
+/// account
+pub const INS_SIGN_MASP_SPEND: u8 = 0x31;
+/// Request that the user confirms the command on the device
+pub const P1_CONFIRM: u8 = 0x01;
+/// Execute the command without confirmation on the device
+pub const P1_NO_CONFIRM: u8 = 0x00;
+/// The MASP account of the device whose spends the client authorizes
+pub const DEFAULT_ACCOUNT: u32 = 0;
+
+/// The length of the serialized proof generation key
+const PROOF_GEN_KEY_LEN: usize = 64;
+/// The length of a serialized spend authorization signature
+const SIGNATURE_LEN: usize = 64;
+
+/// Errors that can occur when talking to a MASP device
+#[derive(Error, Debug)]
+pub enum DeviceError {
+    #[error("Failed to communicate with the device: {0}")]
+    Transport(String),
+    #[error("The device rejected the command with code {0:#06x}")]
+    Rejected(u16),
+    #[error("The device returned an invalid response: {0}")]
+    InvalidResponse(String),
+    #[error("Failed to authorize the shielded transaction: {0}")]
+    Authorization(namada_sdk::error::Error),
+    #[error("Failed to build the shielded transaction: {0}")]
+    Build(namada_sdk::masp::TransferErr),
+}
+
+/// A hardware wallet holding MASP spending keys
+pub struct MaspDevice<E> {
+    /// The transport to the device
+    transport: E,
+}
+
+impl<E> MaspDevice<E>
+where
+    E: Exchange + Send + Sync,
+    E::Error: Display,
+{
+    /// Talk to a device over the given transport
+    pub fn new(transport: E) -> Self {
+        Self { transport }
+    }
+
+    /// Send a command to the device and return its response data
+    async fn send(
+        &self,
+        ins: u8,
+        p1: u8,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, DeviceError> {
+        let command = APDUCommand {
+            cla: CLA,
+            ins,
+            p1,
+            p2: 0,
+            data,
+        };
+        let answer = self
+            .transport
+            .exchange(&command)
+            .await
+            .map_err(|e| DeviceError::Transport(e.to_string()))?;
+        match answer.error_code() {
+            Ok(APDUErrorCode::NoError) => Ok(answer.data().to_vec()),
+            _ => Err(DeviceError::Rejected(answer.retcode())),
+        }
+    }
+
+    /// Export the proof generation key of the given MASP account, with which
+    /// the spends of its notes can be proven
+    pub async fn proof_generation_key(
+        &self,
+        account: u32,
+        confirm: bool,
+    ) -> Result<ProofGenerationKey, DeviceError> {
+        let p1 = if confirm { P1_CONFIRM } else { P1_NO_CONFIRM };
+        let response = self
+            .send(INS_GET_PROOF_GEN_KEY, p1, account.to_le_bytes().to_vec())
+            .await?;
+        decode_proof_generation_key(&response)
+    }
+
+    /// Have the device authorize a spend of the given MASP account in the
+    /// transaction with the given sighash. The spend is identified by the
+    /// randomizer of its verification key.
+    pub async fn sign_spend(
+        &self,
+        account: u32,
+        sighash: &[u8; 32],
+        alpha: &jubjub::Fr,
+    ) -> Result<Signature, DeviceError> {
+        let mut data = account.to_le_bytes().to_vec();
+        data.extend_from_slice(sighash);
+        data.extend_from_slice(&alpha.to_repr());
+        let response = self.send(INS_SIGN_MASP_SPEND, P1_CONFIRM, data).await?;
+        if response.len() != SIGNATURE_LEN {
+            return Err(DeviceError::InvalidResponse(format!(
+                "expected a signature of {SIGNATURE_LEN} bytes, got {}",
+                response.len()
+            )));
+        }
+        Signature::read(&response[..])
+            .map_err(|e| DeviceError::InvalidResponse(e.to_string()))
+    }
+
+    /// Have the device sign every spend of the given transfer, whose spend
+    /// descriptions were randomized with the given randomizers in that
+    /// order, and apply the resulting signatures to the transfer's MASP
+    /// transaction
+    pub async fn authorize_transfer(
+        &self,
+        account: u32,
+        transfer: &mut ShieldedTransfer,
+        alphas: &[jubjub::Fr],
+    ) -> Result<(), DeviceError> {
+        let sighash = sighash(&transfer.masp_tx)?;
+        let mut signatures = Vec::with_capacity(alphas.len());
+        for alpha in alphas {
+            signatures.push(self.sign_spend(account, &sighash, alpha).await?);
+        }
+        transfer.masp_tx =
+            apply_spend_signatures(&transfer.masp_tx, signatures)
+                .map_err(DeviceError::Authorization)?;
+        Ok(())
+    }
+
+    /// Make the shielded components of a transfer spending the notes of the
+    /// given MASP account of the device, whose viewing key is given. The
+    /// host proves the spends with the account's proof generation key and
+    /// the device authorizes them.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn gen_shielded_transfer<N: Namada>(
+        &self,
+        context: &N,
+        account: u32,
+        xfvk: ExtendedFullViewingKey,
+        target: &TransferTarget,
+        token: &Address,
+        amount: token::DenominatedAmount,
+        memo: Option<MemoBytes>,
+    ) -> Result<ShieldedTransfer, DeviceError> {
+        let proof_generation_key =
+            self.proof_generation_key(account, false).await?;
+        let (mut transfer, alphas) =
+            ShieldedContext::<N::ShieldedUtils>::gen_external_shielded_transfer(
+                context,
+                xfvk,
+                proof_generation_key,
+                target,
+                token,
+                amount,
+                memo,
+            )
+            .await
+            .map_err(DeviceError::Build)?;
+        self.authorize_transfer(account, &mut transfer, &alphas)
+            .await?;
+        Ok(transfer)
+    }
+}
+
+/// Compute the sighash that the spends of the given transaction must sign
+fn sighash(transaction: &Transaction) -> Result<[u8; 32], DeviceError> {
+    shielded_sighash(transaction).ok_or_else(|| {
+        DeviceError::Authorization(namada_sdk::error::Error::Other(
+            "Unable to compute the sighash of the transaction".to_string(),
+        ))
+    })
+}
+
+/// Deserialize a proof generation key sent by the device
+fn decode_proof_generation_key(
+    bytes: &[u8],
+) -> Result<ProofGenerationKey, DeviceError> {
+    if bytes.len() != PROOF_GEN_KEY_LEN {
+        return Err(DeviceError::InvalidResponse(format!(
+            "expected a proof generation key of {PROOF_GEN_KEY_LEN} bytes, \
+             got {}",
+            bytes.len()
+        )));
+    }
+    let mut ak = [0u8; 32];
+    ak.copy_from_slice(&bytes[..32]);
+    let mut nsk = [0u8; 32];
+    nsk.copy_from_slice(&bytes[32..]);
+    let ak = Option::from(jubjub::SubgroupPoint::from_bytes(&ak)).ok_or_else(
+        || DeviceError::InvalidResponse("invalid ak".to_string()),
+    )?;
+    let nsk = Option::from(jubjub::Fr::from_repr(nsk)).ok_or_else(|| {
+        DeviceError::InvalidResponse("invalid nsk".to_string())
+    })?;
+    Ok(ProofGenerationKey { ak, nsk })
+}
+
+/// A software device implementing the same APDU interface as a hardware
+/// wallet, for testing the external signing flow
+#[cfg(any(test, feature = "testing"))]
+pub mod mock {
+    use std::collections::HashMap;
+    use std::convert::Infallible;
+
+    use masp_primitives::sapling::redjubjub::PrivateKey;
+    use masp_primitives::sapling::spend_sig;
+    use masp_primitives::zip32::ExtendedSpendingKey;
+    use rand_core::OsRng;
+
+    use super::*;
+
+    /// Serialize a proof generation key as sent by the device
+    fn encode_proof_generation_key(key: &ProofGenerationKey) -> Vec<u8> {
+        let mut bytes = key.ak.to_bytes().to_vec();
+        bytes.extend_from_slice(&key.nsk.to_repr());
+        bytes
+    }
+
+    /// A device holding the spending keys of some MASP accounts
+    #[derive(Default)]
+    pub struct MockMaspDevice {
+        /// The spending keys, indexed by account
+        keys: HashMap<u32, ExtendedSpendingKey>,
+    }
+
+    impl MockMaspDevice {
+        /// Create a device holding the given spending keys
+        pub fn new(keys: HashMap<u32, ExtendedSpendingKey>) -> Self {
+            Self { keys }
+        }
+
+        /// Execute a command, returning the response data or an error code
+        fn execute(&self, command: &[u8], ins: u8) -> Result<Vec<u8>, u16> {
+            let account = command
+                .get(..4)
+                .map(|bytes| {
+                    u32::from_le_bytes(bytes.try_into().expect("length is 4"))
+                })
+                .ok_or(APDUErrorCode::WrongLength as u16)?;
+            let key = self
+                .keys
+                .get(&account)
+                .ok_or(APDUErrorCode::DataInvalid as u16)?;
+            match ins {
+                INS_GET_PROOF_GEN_KEY if command.len() == 4 => {
+                    Ok(encode_proof_generation_key(
+                        &key.expsk.proof_generation_key(),
+                    ))
+                }
+                INS_SIGN_MASP_SPEND if command.len() == 68 => {
+                    let sighash: [u8; 32] =
+                        command[4..36].try_into().expect("length is 32");
+                    let alpha: [u8; 32] =
+                        command[36..].try_into().expect("length is 32");
+                    let alpha = Option::from(jubjub::Fr::from_repr(alpha))
+                        .ok_or(APDUErrorCode::DataInvalid as u16)?;
+                    let signature = spend_sig(
+                        PrivateKey(key.expsk.ask),
+                        alpha,
+                        &sighash,
+                        &mut OsRng,
+                    );
+                    let mut response = Vec::with_capacity(SIGNATURE_LEN);
+                    signature
+                        .write(&mut response)
+                        .expect("writing to a vector cannot fail");
+                    Ok(response)
+                }
+                INS_GET_PROOF_GEN_KEY | INS_SIGN_MASP_SPEND => {
+                    Err(APDUErrorCode::WrongLength as u16)
+                }
+                _ => Err(APDUErrorCode::InsNotSupported as u16),
+            }
+        }
+    }
+
+    #[ledger_transport::async_trait]
+    impl Exchange for MockMaspDevice {
+        type AnswerType = Vec<u8>;
+        type Error = Infallible;
+
+        async fn exchange<I>(
+            &self,
+            command: &APDUCommand<I>,
+        ) -> Result<APDUAnswer<Self::AnswerType>, Self::Error>
+        where
+            I: std::ops::Deref<Target = [u8]> + Send + Sync,
+        {
+            let result = if command.cla != CLA {
+                Err(APDUErrorCode::ClaNotSupported as u16)
+            } else {
+                self.execute(&command.data, command.ins)
+            };
+            let (mut answer, retcode) = match result {
+                Ok(data) => (data, APDUErrorCode::NoError as u16),
+                Err(retcode) => (vec![], retcode),
+            };
+            answer.extend_from_slice(&retcode.to_be_bytes());
+            Ok(APDUAnswer::from_answer(answer)
+                .expect("answer contains a return code"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use masp_primitives::constants::SPENDING_KEY_GENERATOR;
+    use masp_primitives::ff::Field;
+    use masp_primitives::sapling::redjubjub::PublicKey;
+    use masp_primitives::zip32::ExtendedSpendingKey;
+    use rand_core::OsRng;
+
+    use super::mock::MockMaspDevice;
+    use super::*;
+
+    fn device() -> (ExtendedSpendingKey, MaspDevice<MockMaspDevice>) {
+        let sk = ExtendedSpendingKey::master(&[7; 32]);
+        let device =
+            MaspDevice::new(MockMaspDevice::new(HashMap::from([(0, sk)])));
+        (sk, device)
+    }
+
+    /// Check that the exported proof generation key is the one of the
+    /// account's spending key
+    #[tokio::test]
+    async fn test_proof_generation_key() {
+        let (sk, device) = device();
+        let key = device.proof_generation_key(0, false).await.unwrap();
+        let expected = sk.expsk.proof_generation_key();
+        assert_eq!(key.ak, expected.ak);
+        assert_eq!(key.nsk, expected.nsk);
+        assert!(matches!(
+            device.proof_generation_key(1, false).await,
+            Err(DeviceError::Rejected(_))
+        ));
+    }
+
+    /// Check that the device's spend signatures verify against the
+    /// randomized key derived from the exported proof generation key
+    #[tokio::test]
+    async fn test_sign_spend() {
+        let (_sk, device) = device();
+        let key = device.proof_generation_key(0, false).await.unwrap();
+        let alpha = jubjub::Fr::random(OsRng);
+        let sighash = [42; 32];
+        let signature = device.sign_spend(0, &sighash, &alpha).await.unwrap();
+
+        let rk =
+            PublicKey(key.ak.into()).randomize(alpha, SPENDING_KEY_GENERATOR);
+        let mut message = rk.0.to_bytes().to_vec();
+        message.extend_from_slice(&sighash);
+        assert!(rk.verify(&message, &signature, SPENDING_KEY_GENERATOR));
+    }
+}
@@ -1,3 +1,5 @@
+pub mod masp_device;
+pub mod output;
 pub mod rpc;
 pub mod tx;
 pub mod utils;
@@ -0,0 +1,1544 @@
+//! Machine-readable output of the client queries.
+//!
+//! With `--output json`, a query command prints a single JSON document
+//! instead of text. The document wraps the result of the query with the
+//! version of its schema and the name of the command, e.g.
+//! `{"version": 1, "query": "epoch", "result": {"epoch": 5}}`. The schema of
+//! a result only changes along with [`OUTPUT_VERSION`]. Addresses and keys
+//! are encoded strings, and token amounts are decimal strings with all the
+//! decimal places of their token, except in the entries of the shielded
+//! history which are the ones of its JSON export.
+
+use std::collections::BTreeMap;
+
+use namada::core::ledger::governance::storage::proposal::{
+    ProposalType, StoragePgfFunding, StorageProposal,
+};
+use namada::core::ledger::governance::utils::ProposalResult;
+use namada::core::ledger::pgf::storage::steward::StewardDetail;
+use namada::ledger::pos::types::Slash;
+use namada::ledger::queries::vp::pos::{
+    EnrichedBondsAndUnbondsDetail, EnrichedBondsAndUnbondsDetails,
+};
+use namada::proof_of_stake::types::{BondId, ValidatorState};
+use namada::types::address::Address;
+use namada::types::dec::Dec;
+use namada::types::io::Io;
+use namada::types::storage::{BlockHeight, Epoch};
+use namada::types::token::{Amount, DenominatedAmount};
+use namada_sdk::args::OutputFormat;
+use namada_sdk::proof_of_stake::types::ValidatorMetaData;
+use namada_sdk::rpc::TxResponse;
+use namada_sdk::{display_line, MaybeSend, MaybeSync};
+use serde::Serialize;
+
+/// The version of the schema of the JSON documents
+pub const OUTPUT_VERSION: u32 = 1;
+
+/// The JSON document printed by a query
+#[derive(Clone, Debug, Serialize)]
+pub struct Document<T> {
+    /// The version of the schema of the document
+    pub version: u32,
+    /// The name of the query command
+    pub query: &'static str,
+    /// The result of the query
+    pub result: T,
+}
+
+impl<T: Serialize> Document<T> {
+    /// Wrap the result of the given query command
+    pub fn new(query: &'static str, result: T) -> Self {
+        Self {
+            version: OUTPUT_VERSION,
+            query,
+            result,
+        }
+    }
+}
+
+/// Print the result of the given query command as a JSON document
+pub fn print_json<T: Serialize>(io: &impl Io, query: &'static str, result: T) {
+    let document = Document::new(query, result);
+    display_line!(
+        io,
+        "{}",
+        serde_json::to_string_pretty(&document)
+            .expect("Serializing a query result shouldn't fail")
+    );
+}
+
+/// The [`Io`] of the progress and diagnostics printed while a query runs,
+/// such as the shielded sync. With the JSON output they go to stderr, so that
+/// stdout only carries the document, and otherwise to the wrapped [`Io`].
+pub struct ProgressIo<'io, IO> {
+    io: &'io IO,
+    json: bool,
+}
+
+impl<'io, IO: Io> ProgressIo<'io, IO> {
+    /// Wrap the [`Io`] of a query printed in the given format
+    pub fn new(io: &'io IO, format: OutputFormat) -> Self {
+        Self {
+            io,
+            json: format.is_json(),
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl<'io, IO: Io> Io for ProgressIo<'io, IO> {
+    fn print(&self, output: impl AsRef<str>) {
+        if self.json {
+            eprint!("{}", output.as_ref());
+        } else {
+            self.io.print(output);
+        }
+    }
+
+    fn flush(&self) {
+        if self.json {
+            use std::io::Write;
+            std::io::stderr().flush().unwrap();
+        } else {
+            self.io.flush();
+        }
+    }
+
+    fn println(&self, output: impl AsRef<str>) {
+        if self.json {
+            eprintln!("{}", output.as_ref());
+        } else {
+            self.io.println(output);
+        }
+    }
+
+    fn write<W: std::io::Write>(
+        &self,
+        writer: W,
+        output: impl AsRef<str>,
+    ) -> std::io::Result<()> {
+        if self.json {
+            eprint!("{}", output.as_ref());
+            Ok(())
+        } else {
+            self.io.write(writer, output)
+        }
+    }
+
+    fn writeln<W: std::io::Write>(
+        &self,
+        writer: W,
+        output: impl AsRef<str>,
+    ) -> std::io::Result<()> {
+        if self.json {
+            eprintln!("{}", output.as_ref());
+            Ok(())
+        } else {
+            self.io.writeln(writer, output)
+        }
+    }
+
+    fn eprintln(&self, output: impl AsRef<str>) {
+        self.io.eprintln(output);
+    }
+
+    async fn read(&self) -> std::io::Result<String> {
+        self.io.read().await
+    }
+
+    async fn prompt(
+        &self,
+        question: impl AsRef<str> + MaybeSync + MaybeSend,
+    ) -> String {
+        self.io.prompt(question).await
+    }
+}
+
+/// The epoch of the last committed block
+#[derive(Clone, Debug, Serialize)]
+pub struct EpochOutput {
+    pub epoch: Epoch,
+}
+
+/// The last committed block
+#[derive(Clone, Debug, Serialize)]
+pub struct BlockOutput {
+    pub hash: String,
+    pub height: BlockHeight,
+    pub time: String,
+}
+
+/// The balance of a token
+#[derive(Clone, Debug, Serialize)]
+pub struct TokenBalance {
+    pub token: Address,
+    pub token_alias: String,
+    pub amount: DenominatedAmount,
+}
+
+/// The balance of a token owned by a transparent address
+#[derive(Clone, Debug, Serialize)]
+pub struct TransparentBalance {
+    pub owner: Address,
+    #[serde(flatten)]
+    pub balance: TokenBalance,
+}
+
+/// The balance of a token of a given asset epoch owned by a viewing key
+#[derive(Clone, Debug, Serialize)]
+pub struct ShieldedBalance {
+    pub viewing_key: String,
+    pub asset_epoch: Epoch,
+    #[serde(flatten)]
+    pub balance: TokenBalance,
+}
+
+/// The tokens received by a pinned payment address
+#[derive(Clone, Debug, Serialize)]
+pub struct PinnedBalance {
+    pub payment_address: String,
+    /// The epoch in which the payment address was consumed, if it was
+    pub epoch: Option<Epoch>,
+    pub balances: Vec<TokenBalance>,
+    /// Why the balance couldn't be computed, if it couldn't
+    pub error: Option<String>,
+}
+
+/// The balances matching a balance query
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BalancesOutput {
+    pub transparent: Vec<TransparentBalance>,
+    pub shielded: Vec<ShieldedBalance>,
+    pub pinned: Vec<PinnedBalance>,
+}
+
+/// A bond of a bond ID
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BondOutput {
+    pub start: Epoch,
+    pub amount: DenominatedAmount,
+    pub slashed_amount: Option<DenominatedAmount>,
+}
+
+/// An unbond of a bond ID
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct UnbondOutput {
+    pub start: Epoch,
+    pub withdraw: Epoch,
+    pub amount: DenominatedAmount,
+    pub slashed_amount: Option<DenominatedAmount>,
+}
+
+/// A slash of a validator
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SlashOutput {
+    pub validator: Address,
+    pub epoch: Epoch,
+    pub block_height: u64,
+    pub r#type: String,
+    pub rate: Dec,
+}
+
+impl SlashOutput {
+    /// The slash of the given validator
+    pub fn new(validator: &Address, slash: &Slash) -> Self {
+        Self {
+            validator: validator.clone(),
+            epoch: slash.epoch,
+            block_height: slash.block_height,
+            r#type: slash.r#type.to_string(),
+            rate: slash.rate,
+        }
+    }
+}
+
+/// The bonds, unbonds and slashes of a bond ID, with their totals
+#[derive(Clone, Debug, Serialize)]
+pub struct BondIdOutput {
+    pub source: Address,
+    pub validator: Address,
+    pub bonds: Vec<BondOutput>,
+    pub unbonds: Vec<UnbondOutput>,
+    pub slashes: Vec<SlashOutput>,
+    pub bonds_total: DenominatedAmount,
+    pub bonds_total_active: DenominatedAmount,
+    pub unbonds_total: DenominatedAmount,
+    pub unbonds_total_active: DenominatedAmount,
+    pub total_withdrawable: DenominatedAmount,
+}
+
+impl BondIdOutput {
+    /// The details of the given bond ID
+    pub fn new(
+        bond_id: &BondId,
+        details: &EnrichedBondsAndUnbondsDetail,
+    ) -> Self {
+        Self {
+            source: bond_id.source.clone(),
+            validator: bond_id.validator.clone(),
+            bonds: details
+                .data
+                .bonds
+                .iter()
+                .map(|bond| BondOutput {
+                    start: bond.start,
+                    amount: bond.amount.native_denominated(),
+                    slashed_amount: bond
+                        .slashed_amount
+                        .map(Amount::native_denominated),
+                })
+                .collect(),
+            unbonds: details
+                .data
+                .unbonds
+                .iter()
+                .map(|unbond| UnbondOutput {
+                    start: unbond.start,
+                    withdraw: unbond.withdraw,
+                    amount: unbond.amount.native_denominated(),
+                    slashed_amount: unbond
+                        .slashed_amount
+                        .map(Amount::native_denominated),
+                })
+                .collect(),
+            slashes: details
+                .data
+                .slashes
+                .iter()
+                .map(|slash| SlashOutput::new(&bond_id.validator, slash))
+                .collect(),
+            bonds_total: details.bonds_total.native_denominated(),
+            bonds_total_active: details
+                .bonds_total_active()
+                .native_denominated(),
+            unbonds_total: details.unbonds_total.native_denominated(),
+            unbonds_total_active: details
+                .unbonds_total_active()
+                .native_denominated(),
+            total_withdrawable: details.total_withdrawable.native_denominated(),
+        }
+    }
+}
+
+/// The bonds and unbonds matching a bonds query, ordered by bond ID
+#[derive(Clone, Debug, Serialize)]
+pub struct BondsOutput {
+    pub epoch: Epoch,
+    pub bonds: Vec<BondIdOutput>,
+    pub bonds_total: DenominatedAmount,
+    pub bonds_total_active: DenominatedAmount,
+    pub unbonds_total: DenominatedAmount,
+    pub unbonds_total_active: DenominatedAmount,
+    pub total_withdrawable: DenominatedAmount,
+}
+
+impl BondsOutput {
+    /// The given bonds and unbonds queried at the given epoch
+    pub fn new(epoch: Epoch, details: &EnrichedBondsAndUnbondsDetails) -> Self {
+        let mut bonds = details.data.iter().collect::<Vec<_>>();
+        bonds.sort_by(|(id_a, _), (id_b, _)| id_a.cmp(id_b));
+        Self {
+            epoch,
+            bonds: bonds
+                .into_iter()
+                .map(|(bond_id, details)| BondIdOutput::new(bond_id, details))
+                .collect(),
+            bonds_total: details.bonds_total.native_denominated(),
+            bonds_total_active: details
+                .bonds_total_active()
+                .native_denominated(),
+            unbonds_total: details.unbonds_total.native_denominated(),
+            unbonds_total_active: details
+                .unbonds_total_active()
+                .native_denominated(),
+            total_withdrawable: details.total_withdrawable.native_denominated(),
+        }
+    }
+}
+
+/// A watch-only account with its balances and the totals of its bonds
+#[derive(Clone, Debug, Serialize)]
+pub struct WatchedAccountOutput {
+    pub alias: String,
+    pub label: Option<String>,
+    pub address: Address,
+    pub balances: Vec<TokenBalance>,
+    pub bonds_total: Option<DenominatedAmount>,
+    pub unbonds_total: Option<DenominatedAmount>,
+    pub total_withdrawable: Option<DenominatedAmount>,
+}
+
+/// The watch-only accounts of the wallet
+#[derive(Clone, Debug, Serialize)]
+pub struct WatchedOutput {
+    pub epoch: Epoch,
+    pub accounts: Vec<WatchedAccountOutput>,
+}
+
+/// The name of a validator state
+pub fn validator_state_name(state: &ValidatorState) -> &'static str {
+    match state {
+        ValidatorState::Consensus => "consensus",
+        ValidatorState::BelowCapacity => "below_capacity",
+        ValidatorState::BelowThreshold => "below_threshold",
+        ValidatorState::Inactive => "inactive",
+        ValidatorState::Jailed => "jailed",
+    }
+}
+
+/// The state of a validator, if it's known
+#[derive(Clone, Debug, Serialize)]
+pub struct ValidatorStateOutput {
+    pub validator: Address,
+    pub epoch: Option<Epoch>,
+    pub state: Option<&'static str>,
+}
+
+/// A change of the balance of an owner caused by a transfer
+#[derive(Clone, Debug, Serialize)]
+pub struct BalanceChange {
+    pub owner: String,
+    pub token: Address,
+    pub token_alias: String,
+    /// The signed change, e.g. "-1.500000"
+    pub change: String,
+}
+
+/// An applied transfer
+#[derive(Clone, Debug, Serialize)]
+pub struct TransferOutput {
+    pub height: BlockHeight,
+    pub index: u32,
+    pub epoch: Epoch,
+    pub transparent: Vec<BalanceChange>,
+    pub shielded: Vec<BalanceChange>,
+}
+
+/// An asset of a conversion
+#[derive(Clone, Debug, Serialize)]
+pub struct ConversionAsset {
+    pub token: Address,
+    pub token_alias: String,
+    pub epoch: Epoch,
+}
+
+/// A component of a conversion, with its signed value
+#[derive(Clone, Debug, Serialize)]
+pub struct ConversionComponent {
+    #[serde(flatten)]
+    pub asset: ConversionAsset,
+    pub value: String,
+}
+
+/// An allowed conversion, whose components sum to zero
+#[derive(Clone, Debug, Serialize)]
+pub struct ConversionOutput {
+    #[serde(flatten)]
+    pub asset: ConversionAsset,
+    pub components: Vec<ConversionComponent>,
+}
+
+/// A note received by a viewing key
+#[derive(Clone, Debug, Serialize)]
+pub struct NoteOutput {
+    pub pos: usize,
+    pub token: Address,
+    pub token_alias: String,
+    pub epoch: Epoch,
+    pub amount: DenominatedAmount,
+    pub spent: bool,
+    pub memo: Option<String>,
+}
+
+/// The notes received by a viewing key
+#[derive(Clone, Debug, Serialize)]
+pub struct ViewingKeyNotes {
+    pub viewing_key: String,
+    pub notes: Vec<NoteOutput>,
+}
+
+/// The stake of a validator
+#[derive(Clone, Debug, Serialize)]
+pub struct ValidatorStakeOutput {
+    pub validator: Address,
+    /// The validator set the validator is in, if it was queried
+    pub set: Option<&'static str>,
+    pub stake: Option<DenominatedAmount>,
+}
+
+/// The bonded stake of validators at an epoch
+#[derive(Clone, Debug, Serialize)]
+pub struct BondedStakeOutput {
+    pub epoch: Epoch,
+    pub validators: Vec<ValidatorStakeOutput>,
+    pub total_bonded_stake: DenominatedAmount,
+}
+
+/// The commission rate of a validator
+#[derive(Clone, Debug, Serialize)]
+pub struct CommissionRateOutput {
+    pub validator: Address,
+    pub epoch: Option<Epoch>,
+    pub commission_rate: Option<Dec>,
+    pub max_commission_change_per_epoch: Option<Dec>,
+}
+
+/// The metadata and current commission rate of a validator
+#[derive(Clone, Debug, Serialize)]
+pub struct ValidatorMetadataOutput {
+    pub validator: Address,
+    pub metadata: Option<ValidatorMetaData>,
+    pub commission_rate: Option<Dec>,
+    pub max_commission_change_per_epoch: Option<Dec>,
+}
+
+/// A slash enqueued to be processed in a future epoch
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct EnqueuedSlashOutput {
+    pub processing_epoch: Epoch,
+    #[serde(flatten)]
+    pub slash: SlashOutput,
+}
+
+/// The processed and enqueued slashes of validators
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SlashesOutput {
+    pub processed: Vec<SlashOutput>,
+    pub enqueued: Vec<EnqueuedSlashOutput>,
+}
+
+impl SlashesOutput {
+    /// The given processed slashes and the enqueued slashes to be processed
+    /// by epoch, both by validator. The slashes are ordered by validator.
+    pub fn new<'a>(
+        processed: impl IntoIterator<Item = (&'a Address, &'a Vec<Slash>)>,
+        enqueued: impl IntoIterator<
+            Item = (&'a Address, &'a BTreeMap<Epoch, Vec<Slash>>),
+        >,
+    ) -> Self {
+        let processed = processed
+            .into_iter()
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .flat_map(|(validator, slashes)| {
+                slashes
+                    .iter()
+                    .map(|slash| SlashOutput::new(validator, slash))
+            })
+            .collect();
+        let enqueued = enqueued
+            .into_iter()
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .flat_map(|(validator, slashes_by_epoch)| {
+                slashes_by_epoch.iter().flat_map(|(epoch, slashes)| {
+                    slashes.iter().map(|slash| EnqueuedSlashOutput {
+                        processing_epoch: *epoch,
+                        slash: SlashOutput::new(validator, slash),
+                    })
+                })
+            })
+            .collect();
+        Self {
+            processed,
+            enqueued,
+        }
+    }
+}
+
+/// The rewards available for claim of a bond
+#[derive(Clone, Debug, Serialize)]
+pub struct RewardsOutput {
+    pub source: Option<Address>,
+    pub validator: Address,
+    pub rewards: DenominatedAmount,
+}
+
+/// The validators an owner delegated to
+#[derive(Clone, Debug, Serialize)]
+pub struct DelegationsOutput {
+    pub owner: Address,
+    pub validators: Vec<Address>,
+}
+
+/// The validator with a Tendermint address, if any
+#[derive(Clone, Debug, Serialize)]
+pub struct FindValidatorOutput {
+    pub tm_address: String,
+    pub validator: Option<Address>,
+}
+
+/// The raw bytes of a storage key, hex-encoded, if the key has a value
+#[derive(Clone, Debug, Serialize)]
+pub struct RawBytesOutput {
+    pub storage_key: String,
+    pub data: Option<String>,
+}
+
+/// The result of a transaction, either "applied" or "accepted"
+#[derive(Clone, Debug, Serialize)]
+pub struct TxResultOutput {
+    pub status: &'static str,
+    pub response: TxResponse,
+}
+
+/// A governance proposal
+#[derive(Clone, Debug, Serialize)]
+pub struct ProposalOutput {
+    pub id: u64,
+    /// One of "default", "pgf_steward" or "pgf_payment"
+    pub kind: &'static str,
+    pub author: Address,
+    pub content: BTreeMap<String, String>,
+    pub voting_start_epoch: Epoch,
+    pub voting_end_epoch: Epoch,
+    pub grace_epoch: Epoch,
+    pub status: String,
+    /// The data of the proposal, which depends on its kind
+    pub data: ProposalType,
+}
+
+impl ProposalOutput {
+    /// The given proposal, with its status at the given epoch
+    pub fn new(proposal: &StorageProposal, current_epoch: Epoch) -> Self {
+        Self {
+            id: proposal.id,
+            kind: match proposal.r#type {
+                ProposalType::Default(_) => "default",
+                ProposalType::PGFSteward(_) => "pgf_steward",
+                ProposalType::PGFPayment(_) => "pgf_payment",
+            },
+            author: proposal.author.clone(),
+            content: proposal.content.clone(),
+            voting_start_epoch: proposal.voting_start_epoch,
+            voting_end_epoch: proposal.voting_end_epoch,
+            grace_epoch: proposal.grace_epoch,
+            status: proposal.get_status(current_epoch).to_string(),
+            data: proposal.r#type.clone(),
+        }
+    }
+}
+
+/// Governance proposals
+#[derive(Clone, Debug, Serialize)]
+pub struct ProposalsOutput {
+    pub epoch: Epoch,
+    pub proposals: Vec<ProposalOutput>,
+}
+
+/// The tally of the votes of a proposal
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TallyOutput {
+    /// Either "passed" or "rejected"
+    pub result: String,
+    pub total_voting_power: DenominatedAmount,
+    pub total_yay_power: DenominatedAmount,
+    pub total_nay_power: DenominatedAmount,
+    pub total_abstain_power: DenominatedAmount,
+}
+
+impl From<&ProposalResult> for TallyOutput {
+    fn from(result: &ProposalResult) -> Self {
+        Self {
+            result: result.result.to_string(),
+            total_voting_power: result.total_voting_power.native_denominated(),
+            total_yay_power: result.total_yay_power.native_denominated(),
+            total_nay_power: result.total_nay_power.native_denominated(),
+            total_abstain_power: result
+                .total_abstain_power
+                .native_denominated(),
+        }
+    }
+}
+
+/// The result of an on-chain or offline proposal
+#[derive(Clone, Debug, Serialize)]
+pub struct ProposalResultOutput {
+    pub proposal_id: Option<u64>,
+    pub offline_proposal_hash: Option<String>,
+    /// The number of offline votes
+    pub offline_votes: Option<usize>,
+    pub tally: TallyOutput,
+}
+
+/// A PGF steward with the distribution of its rewards
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StewardOutput {
+    pub address: Address,
+    pub reward_distribution: BTreeMap<Address, Dec>,
+}
+
+/// A continuous PGF funding
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FundingOutput {
+    pub proposal_id: u64,
+    pub target: Address,
+    pub amount: DenominatedAmount,
+}
+
+/// The PGF stewards and continuous fundings
+#[derive(Clone, Debug, Serialize)]
+pub struct PgfOutput {
+    pub stewards: Vec<StewardOutput>,
+    pub fundings: Vec<FundingOutput>,
+}
+
+impl PgfOutput {
+    /// The given stewards and fundings
+    pub fn new(
+        stewards: &[StewardDetail],
+        fundings: &[StoragePgfFunding],
+    ) -> Self {
+        Self {
+            stewards: stewards
+                .iter()
+                .map(|steward| StewardOutput {
+                    address: steward.address.clone(),
+                    reward_distribution: steward
+                        .reward_distribution
+                        .iter()
+                        .map(|(address, share)| (address.clone(), *share))
+                        .collect(),
+                })
+                .collect(),
+            fundings: fundings
+                .iter()
+                .map(|funding| FundingOutput {
+                    proposal_id: funding.id,
+                    target: funding.detail.target.clone(),
+                    amount: funding.detail.amount.native_denominated(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The governance parameters
+#[derive(Clone, Debug, Serialize)]
+pub struct GovernanceParametersOutput {
+    pub min_proposal_fund: DenominatedAmount,
+    pub max_proposal_code_size: u64,
+    pub min_proposal_voting_period: u64,
+    pub max_proposal_period: u64,
+    pub max_proposal_content_size: u64,
+    pub min_proposal_grace_epochs: u64,
+}
+
+/// The PGF parameters
+#[derive(Clone, Debug, Serialize)]
+pub struct PgfParametersOutput {
+    pub pgf_inflation_rate: Dec,
+    pub stewards_inflation_rate: Dec,
+}
+
+/// The protocol parameters
+#[derive(Clone, Debug, Serialize)]
+pub struct ChainParametersOutput {
+    pub min_epoch_duration: u64,
+    pub min_num_of_blocks: u64,
+    pub max_block_duration: u64,
+    pub vp_whitelist: Vec<String>,
+    pub tx_whitelist: Vec<String>,
+    pub max_block_gas: u64,
+    pub fee_unshielding_gas_limit: u64,
+    pub fee_unshielding_descriptions_limit: u64,
+    pub gas_cost_table: BTreeMap<Address, DenominatedAmount>,
+}
+
+/// The PoS parameters
+#[derive(Clone, Debug, Serialize)]
+pub struct PosParametersOutput {
+    pub block_proposer_reward: Dec,
+    pub block_vote_reward: Dec,
+    pub duplicate_vote_min_slash_rate: Dec,
+    pub light_client_attack_min_slash_rate: Dec,
+    pub max_validator_slots: u64,
+    pub pipeline_len: u64,
+    pub unbonding_len: u64,
+    pub tm_votes_per_token: Dec,
+}
+
+/// All the parameters of the chain
+#[derive(Clone, Debug, Serialize)]
+pub struct ProtocolParametersOutput {
+    pub governance: GovernanceParametersOutput,
+    pub pgf: PgfParametersOutput,
+    pub protocol: ChainParametersOutput,
+    pub pos: PosParametersOutput,
+}
+
+/// An account with its public keys
+#[derive(Clone, Debug, Serialize)]
+pub struct AccountOutput {
+    pub address: Address,
+    pub threshold: u8,
+    pub public_keys: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use namada::core::ledger::governance::storage::proposal::{
+        AddRemove, PGFTarget,
+    };
+    use namada::core::ledger::governance::utils::TallyResult;
+    use namada::ledger::pos::types::SlashType;
+    use namada::ledger::queries::vp::pos::Enriched;
+    use namada::proof_of_stake::types::{
+        BondDetails, BondsAndUnbondsDetail, UnbondDetails,
+    };
+    use namada::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    fn to_json(result: impl Serialize) -> serde_json::Value {
+        serde_json::to_value(result).unwrap()
+    }
+
+    fn nam(whole: u64) -> Amount {
+        Amount::native_whole(whole)
+    }
+
+    fn slash() -> Slash {
+        Slash {
+            epoch: Epoch(3),
+            block_height: 42,
+            r#type: SlashType::DuplicateVote,
+            rate: Dec::new(5, 2).unwrap(),
+        }
+    }
+
+    /// An [`Io`] recording the lines it prints
+    #[derive(Default)]
+    struct RecordingIo(std::cell::RefCell<Vec<String>>);
+
+    #[async_trait::async_trait(?Send)]
+    impl Io for RecordingIo {
+        fn println(&self, output: impl AsRef<str>) {
+            self.0.borrow_mut().push(output.as_ref().to_string());
+        }
+    }
+
+    #[test]
+    fn test_progress_io() {
+        let io = RecordingIo::default();
+        // The progress of a text query is printed with the query's Io
+        let progress = ProgressIo::new(&io, OutputFormat::Text);
+        display_line!(&progress, "Scanned {}/{} shielded transactions", 1, 2);
+        assert_eq!(*io.0.borrow(), ["Scanned 1/2 shielded transactions"]);
+        // The progress of a JSON query is kept off stdout
+        let progress = ProgressIo::new(&io, OutputFormat::Json);
+        display_line!(&progress, "Scanned {}/{} shielded transactions", 2, 2);
+        print_json(&io, "epoch", EpochOutput { epoch: Epoch(7) });
+        let lines = io.0.borrow();
+        assert_eq!(lines.len(), 2);
+        let document: serde_json::Value =
+            serde_json::from_str(&lines[1]).unwrap();
+        assert_eq!(document["result"], json!({ "epoch": 7 }));
+    }
+
+    #[test]
+    fn test_document_envelope() {
+        let document = Document::new("epoch", EpochOutput { epoch: Epoch(7) });
+        assert_eq!(
+            to_json(document),
+            json!({
+                "version": OUTPUT_VERSION,
+                "query": "epoch",
+                "result": { "epoch": 7 },
+            })
+        );
+    }
+
+    #[test]
+    fn test_balances_output() {
+        let owner = established_address_1();
+        let token = established_address_2();
+        let balance = TokenBalance {
+            token: token.clone(),
+            token_alias: "btc".to_string(),
+            amount: DenominatedAmount::native(nam(5)),
+        };
+        let output = BalancesOutput {
+            transparent: vec![TransparentBalance {
+                owner: owner.clone(),
+                balance: balance.clone(),
+            }],
+            shielded: vec![ShieldedBalance {
+                viewing_key: "zvknam1".to_string(),
+                asset_epoch: Epoch(2),
+                balance: balance.clone(),
+            }],
+            pinned: vec![PinnedBalance {
+                payment_address: "znam1".to_string(),
+                epoch: None,
+                balances: vec![],
+                error: Some("not consumed".to_string()),
+            }],
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "transparent": [{
+                    "owner": owner.encode(),
+                    "token": token.encode(),
+                    "token_alias": "btc",
+                    "amount": "5.000000",
+                }],
+                "shielded": [{
+                    "viewing_key": "zvknam1",
+                    "asset_epoch": 2,
+                    "token": token.encode(),
+                    "token_alias": "btc",
+                    "amount": "5.000000",
+                }],
+                "pinned": [{
+                    "payment_address": "znam1",
+                    "epoch": null,
+                    "balances": [],
+                    "error": "not consumed",
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_bonds_output() {
+        let source = established_address_1();
+        let validator = established_address_2();
+        let detail = Enriched {
+            data: BondsAndUnbondsDetail {
+                bonds: vec![BondDetails {
+                    start: Epoch(1),
+                    amount: nam(100),
+                    slashed_amount: Some(nam(5)),
+                }],
+                unbonds: vec![UnbondDetails {
+                    start: Epoch(1),
+                    withdraw: Epoch(9),
+                    amount: nam(20),
+                    slashed_amount: None,
+                }],
+                slashes: vec![slash()],
+            },
+            bonds_total: nam(100),
+            bonds_total_slashed: nam(5),
+            unbonds_total: nam(20),
+            unbonds_total_slashed: Amount::zero(),
+            total_withdrawable: Amount::zero(),
+        };
+        let bond_id = BondId {
+            source: source.clone(),
+            validator: validator.clone(),
+        };
+        let details = Enriched {
+            data: HashMap::from([(bond_id, detail)]),
+            bonds_total: nam(100),
+            bonds_total_slashed: nam(5),
+            unbonds_total: nam(20),
+            unbonds_total_slashed: Amount::zero(),
+            total_withdrawable: Amount::zero(),
+        };
+        assert_eq!(
+            to_json(BondsOutput::new(Epoch(4), &details)),
+            json!({
+                "epoch": 4,
+                "bonds": [{
+                    "source": source.encode(),
+                    "validator": validator.encode(),
+                    "bonds": [{
+                        "start": 1,
+                        "amount": "100.000000",
+                        "slashed_amount": "5.000000",
+                    }],
+                    "unbonds": [{
+                        "start": 1,
+                        "withdraw": 9,
+                        "amount": "20.000000",
+                        "slashed_amount": null,
+                    }],
+                    "slashes": [{
+                        "validator": validator.encode(),
+                        "epoch": 3,
+                        "block_height": 42,
+                        "type": SlashType::DuplicateVote.to_string(),
+                        "rate": "0.05",
+                    }],
+                    "bonds_total": "100.000000",
+                    "bonds_total_active": "95.000000",
+                    "unbonds_total": "20.000000",
+                    "unbonds_total_active": "20.000000",
+                    "total_withdrawable": "0.000000",
+                }],
+                "bonds_total": "100.000000",
+                "bonds_total_active": "95.000000",
+                "unbonds_total": "20.000000",
+                "unbonds_total_active": "20.000000",
+                "total_withdrawable": "0.000000",
+            })
+        );
+    }
+
+    #[test]
+    fn test_watched_output() {
+        let address = established_address_1();
+        let output = WatchedOutput {
+            epoch: Epoch(2),
+            accounts: vec![WatchedAccountOutput {
+                alias: "cold".to_string(),
+                label: None,
+                address: address.clone(),
+                balances: vec![],
+                bonds_total: Some(DenominatedAmount::native(nam(1))),
+                unbonds_total: None,
+                total_withdrawable: None,
+            }],
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "epoch": 2,
+                "accounts": [{
+                    "alias": "cold",
+                    "label": null,
+                    "address": address.encode(),
+                    "balances": [],
+                    "bonds_total": "1.000000",
+                    "unbonds_total": null,
+                    "total_withdrawable": null,
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_validator_state_output() {
+        let validator = established_address_1();
+        let output = ValidatorStateOutput {
+            validator: validator.clone(),
+            epoch: Some(Epoch(3)),
+            state: Some(validator_state_name(&ValidatorState::BelowCapacity)),
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "validator": validator.encode(),
+                "epoch": 3,
+                "state": "below_capacity",
+            })
+        );
+    }
+
+    #[test]
+    fn test_transfer_output() {
+        let owner = established_address_1();
+        let token = established_address_2();
+        let output = TransferOutput {
+            height: BlockHeight(10),
+            index: 1,
+            epoch: Epoch(2),
+            transparent: vec![BalanceChange {
+                owner: owner.to_string(),
+                token: token.clone(),
+                token_alias: "btc".to_string(),
+                change: "-1.500000".to_string(),
+            }],
+            shielded: vec![],
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "height": 10,
+                "index": 1,
+                "epoch": 2,
+                "transparent": [{
+                    "owner": owner.to_string(),
+                    "token": token.encode(),
+                    "token_alias": "btc",
+                    "change": "-1.500000",
+                }],
+                "shielded": [],
+            })
+        );
+    }
+
+    #[test]
+    fn test_conversion_output() {
+        let token = established_address_1();
+        let asset = |epoch| ConversionAsset {
+            token: token.clone(),
+            token_alias: "nam".to_string(),
+            epoch: Epoch(epoch),
+        };
+        let output = ConversionOutput {
+            asset: asset(1),
+            components: vec![
+                ConversionComponent {
+                    asset: asset(1),
+                    value: "-10".to_string(),
+                },
+                ConversionComponent {
+                    asset: asset(2),
+                    value: "11".to_string(),
+                },
+            ],
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "token": token.encode(),
+                "token_alias": "nam",
+                "epoch": 1,
+                "components": [
+                    {
+                        "token": token.encode(),
+                        "token_alias": "nam",
+                        "epoch": 1,
+                        "value": "-10",
+                    },
+                    {
+                        "token": token.encode(),
+                        "token_alias": "nam",
+                        "epoch": 2,
+                        "value": "11",
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_notes_output() {
+        let token = established_address_1();
+        let output = ViewingKeyNotes {
+            viewing_key: "zvknam1".to_string(),
+            notes: vec![NoteOutput {
+                pos: 3,
+                token: token.clone(),
+                token_alias: "nam".to_string(),
+                epoch: Epoch(1),
+                amount: DenominatedAmount::native(nam(2)),
+                spent: true,
+                memo: Some("rent".to_string()),
+            }],
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "viewing_key": "zvknam1",
+                "notes": [{
+                    "pos": 3,
+                    "token": token.encode(),
+                    "token_alias": "nam",
+                    "epoch": 1,
+                    "amount": "2.000000",
+                    "spent": true,
+                    "memo": "rent",
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_bonded_stake_output() {
+        let validator = established_address_1();
+        let output = BondedStakeOutput {
+            epoch: Epoch(5),
+            validators: vec![ValidatorStakeOutput {
+                validator: validator.clone(),
+                set: Some("consensus"),
+                stake: Some(DenominatedAmount::native(nam(1000))),
+            }],
+            total_bonded_stake: DenominatedAmount::native(nam(1000)),
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "epoch": 5,
+                "validators": [{
+                    "validator": validator.encode(),
+                    "set": "consensus",
+                    "stake": "1000.000000",
+                }],
+                "total_bonded_stake": "1000.000000",
+            })
+        );
+    }
+
+    #[test]
+    fn test_validator_metadata_output() {
+        let validator = established_address_1();
+        let rate = Dec::new(5, 2).unwrap();
+        let output = ValidatorMetadataOutput {
+            validator: validator.clone(),
+            metadata: Some(ValidatorMetaData {
+                email: "validator@example.com".to_string(),
+                description: None,
+                website: Some("https://example.com".to_string()),
+                discord_handle: None,
+            }),
+            commission_rate: Some(rate),
+            max_commission_change_per_epoch: Some(rate),
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "validator": validator.encode(),
+                "metadata": {
+                    "email": "validator@example.com",
+                    "description": null,
+                    "website": "https://example.com",
+                    "discord_handle": null,
+                },
+                "commission_rate": "0.05",
+                "max_commission_change_per_epoch": "0.05",
+            })
+        );
+        let output = CommissionRateOutput {
+            validator: validator.clone(),
+            epoch: None,
+            commission_rate: None,
+            max_commission_change_per_epoch: None,
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "validator": validator.encode(),
+                "epoch": null,
+                "commission_rate": null,
+                "max_commission_change_per_epoch": null,
+            })
+        );
+    }
+
+    #[test]
+    fn test_slashes_output() {
+        let validator = established_address_1();
+        let processed = HashMap::from([(validator.clone(), vec![slash()])]);
+        let enqueued = HashMap::from([(
+            validator.clone(),
+            BTreeMap::from([(Epoch(6), vec![slash()])]),
+        )]);
+        let slash = json!({
+            "validator": validator.encode(),
+            "epoch": 3,
+            "block_height": 42,
+            "type": SlashType::DuplicateVote.to_string(),
+            "rate": "0.05",
+        });
+        let mut enqueued_slash = slash.clone();
+        enqueued_slash["processing_epoch"] = json!(6);
+        assert_eq!(
+            to_json(SlashesOutput::new(&processed, &enqueued)),
+            json!({ "processed": [slash], "enqueued": [enqueued_slash] })
+        );
+    }
+
+    #[test]
+    fn test_rewards_and_delegations_output() {
+        let source = established_address_1();
+        let validator = established_address_2();
+        let output = RewardsOutput {
+            source: Some(source.clone()),
+            validator: validator.clone(),
+            rewards: DenominatedAmount::native(nam(3)),
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "source": source.encode(),
+                "validator": validator.encode(),
+                "rewards": "3.000000",
+            })
+        );
+        let output = DelegationsOutput {
+            owner: source.clone(),
+            validators: vec![validator.clone()],
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "owner": source.encode(),
+                "validators": [validator.encode()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_find_validator_and_raw_bytes_output() {
+        let output = FindValidatorOutput {
+            tm_address: "A".repeat(40),
+            validator: None,
+        };
+        assert_eq!(
+            to_json(output),
+            json!({ "tm_address": "A".repeat(40), "validator": null })
+        );
+        let output = RawBytesOutput {
+            storage_key: "#tnam1/counter".to_string(),
+            data: Some("0a00".to_string()),
+        };
+        assert_eq!(
+            to_json(output),
+            json!({ "storage_key": "#tnam1/counter", "data": "0a00" })
+        );
+    }
+
+    #[test]
+    fn test_tx_result_output() {
+        let account = established_address_1();
+        let output = TxResultOutput {
+            status: "applied",
+            response: TxResponse {
+                info: "Transaction is valid.".to_string(),
+                log: "".to_string(),
+                height: "12".to_string(),
+                hash: "ABCD".to_string(),
+                code: "0".to_string(),
+                gas_used: "42".to_string(),
+                initialized_accounts: vec![account.clone()],
+            },
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "status": "applied",
+                "response": {
+                    "info": "Transaction is valid.",
+                    "log": "",
+                    "height": "12",
+                    "hash": "ABCD",
+                    "code": "0",
+                    "gas_used": "42",
+                    "initialized_accounts": [account.encode()],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_proposal_output() {
+        let author = established_address_1();
+        let steward = established_address_2();
+        let proposal = StorageProposal {
+            id: 2,
+            content: BTreeMap::from([(
+                "title".to_string(),
+                "Stewards".to_string(),
+            )]),
+            author: author.clone(),
+            r#type: ProposalType::PGFSteward(HashSet::from([AddRemove::Add(
+                steward.clone(),
+            )])),
+            voting_start_epoch: Epoch(3),
+            voting_end_epoch: Epoch(6),
+            grace_epoch: Epoch(9),
+        };
+        let output = ProposalsOutput {
+            epoch: Epoch(4),
+            proposals: vec![ProposalOutput::new(&proposal, Epoch(4))],
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "epoch": 4,
+                "proposals": [{
+                    "id": 2,
+                    "kind": "pgf_steward",
+                    "author": author.encode(),
+                    "content": { "title": "Stewards" },
+                    "voting_start_epoch": 3,
+                    "voting_end_epoch": 6,
+                    "grace_epoch": 9,
+                    "status": "on-going",
+                    "data": { "PGFSteward": [{ "Add": steward.encode() }] },
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_proposal_result_output() {
+        let result = ProposalResult {
+            result: TallyResult::Passed,
+            total_voting_power: nam(90),
+            total_yay_power: nam(70),
+            total_nay_power: nam(10),
+            total_abstain_power: Amount::zero(),
+        };
+        let output = ProposalResultOutput {
+            proposal_id: Some(2),
+            offline_proposal_hash: None,
+            offline_votes: None,
+            tally: TallyOutput::from(&result),
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "proposal_id": 2,
+                "offline_proposal_hash": null,
+                "offline_votes": null,
+                "tally": {
+                    "result": "passed",
+                    "total_voting_power": "90.000000",
+                    "total_yay_power": "70.000000",
+                    "total_nay_power": "10.000000",
+                    "total_abstain_power": "0.000000",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_pgf_output() {
+        let steward = established_address_1();
+        let target = established_address_2();
+        let stewards = [StewardDetail {
+            address: steward.clone(),
+            reward_distribution: HashMap::from([(steward.clone(), Dec::one())]),
+        }];
+        let fundings = [StoragePgfFunding::new(
+            PGFTarget {
+                target: target.clone(),
+                amount: nam(12),
+            },
+            7,
+        )];
+        let mut distribution = serde_json::Map::new();
+        distribution.insert(steward.encode(), json!(Dec::one().to_string()));
+        assert_eq!(
+            to_json(PgfOutput::new(&stewards, &fundings)),
+            json!({
+                "stewards": [{
+                    "address": steward.encode(),
+                    "reward_distribution": distribution,
+                }],
+                "fundings": [{
+                    "proposal_id": 7,
+                    "target": target.encode(),
+                    "amount": "12.000000",
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_protocol_parameters_output() {
+        let token = established_address_1();
+        let rate = Dec::new(1, 1).unwrap();
+        let output = ProtocolParametersOutput {
+            governance: GovernanceParametersOutput {
+                min_proposal_fund: DenominatedAmount::native(nam(500)),
+                max_proposal_code_size: 300_000,
+                min_proposal_voting_period: 3,
+                max_proposal_period: 27,
+                max_proposal_content_size: 10_000,
+                min_proposal_grace_epochs: 6,
+            },
+            pgf: PgfParametersOutput {
+                pgf_inflation_rate: rate,
+                stewards_inflation_rate: rate,
+            },
+            protocol: ChainParametersOutput {
+                min_epoch_duration: 60,
+                min_num_of_blocks: 10,
+                max_block_duration: 30,
+                vp_whitelist: vec![],
+                tx_whitelist: vec!["abcd".to_string()],
+                max_block_gas: 20_000_000,
+                fee_unshielding_gas_limit: 20_000,
+                fee_unshielding_descriptions_limit: 15,
+                gas_cost_table: BTreeMap::from([(
+                    token.clone(),
+                    DenominatedAmount::native(Amount::from_u64(1)),
+                )]),
+            },
+            pos: PosParametersOutput {
+                block_proposer_reward: rate,
+                block_vote_reward: rate,
+                duplicate_vote_min_slash_rate: rate,
+                light_client_attack_min_slash_rate: rate,
+                max_validator_slots: 128,
+                pipeline_len: 2,
+                unbonding_len: 21,
+                tm_votes_per_token: rate,
+            },
+        };
+        let mut gas_cost_table = serde_json::Map::new();
+        gas_cost_table.insert(token.encode(), json!("0.000001"));
+        assert_eq!(
+            to_json(output),
+            json!({
+                "governance": {
+                    "min_proposal_fund": "500.000000",
+                    "max_proposal_code_size": 300_000,
+                    "min_proposal_voting_period": 3,
+                    "max_proposal_period": 27,
+                    "max_proposal_content_size": 10_000,
+                    "min_proposal_grace_epochs": 6,
+                },
+                "pgf": {
+                    "pgf_inflation_rate": "0.1",
+                    "stewards_inflation_rate": "0.1",
+                },
+                "protocol": {
+                    "min_epoch_duration": 60,
+                    "min_num_of_blocks": 10,
+                    "max_block_duration": 30,
+                    "vp_whitelist": [],
+                    "tx_whitelist": ["abcd"],
+                    "max_block_gas": 20_000_000,
+                    "fee_unshielding_gas_limit": 20_000,
+                    "fee_unshielding_descriptions_limit": 15,
+                    "gas_cost_table": gas_cost_table,
+                },
+                "pos": {
+                    "block_proposer_reward": "0.1",
+                    "block_vote_reward": "0.1",
+                    "duplicate_vote_min_slash_rate": "0.1",
+                    "light_client_attack_min_slash_rate": "0.1",
+                    "max_validator_slots": 128,
+                    "pipeline_len": 2,
+                    "unbonding_len": 21,
+                    "tm_votes_per_token": "0.1",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_block_and_account_output() {
+        let address = established_address_1();
+        let output = Some(BlockOutput {
+            hash: "ABCD".to_string(),
+            height: BlockHeight(12),
+            time: "2023-01-01T00:00:00Z".to_string(),
+        });
+        assert_eq!(
+            to_json(output),
+            json!({
+                "hash": "ABCD",
+                "height": 12,
+                "time": "2023-01-01T00:00:00Z",
+            })
+        );
+        let output = AccountOutput {
+            address: address.clone(),
+            threshold: 1,
+            public_keys: vec!["tpknam1".to_string()],
+        };
+        assert_eq!(
+            to_json(output),
+            json!({
+                "address": address.encode(),
+                "threshold": 1,
+                "public_keys": ["tpknam1"],
+            })
+        );
+    }
+}
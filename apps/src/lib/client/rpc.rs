@@ -8,7 +8,6 @@ use std::iter::Iterator;
 use std::str::FromStr;
 
 use borsh::BorshDeserialize;
-use borsh_ext::BorshSerializeExt;
 use data_encoding::HEXLOWER;
 use itertools::Either;
 use masp_primitives::asset_type::AssetType;
@@ -47,19 +46,26 @@ use namada::types::masp::{BalanceOwner, ExtendedViewingKey, PaymentAddress};
 use namada::types::storage::{BlockHeight, BlockResults, Epoch, Key, KeySeg};
 use namada::types::token::{Change, MaspDenom};
 use namada::types::{storage, token};
+use namada_sdk::discovery::AccountKind;
 use namada_sdk::error::{is_pinned_error, Error, PinnedBalanceError};
-use namada_sdk::masp::{Conversions, MaspAmount, MaspChange};
+use namada_sdk::masp::{
+    decode_memo, Conversions, MaspChange, ShieldedContext, TransparentKind,
+    HISTORY_CSV_HEADER,
+};
 use namada_sdk::proof_of_stake::types::ValidatorMetaData;
 use namada_sdk::rpc::{
     self, enriched_bonds_and_unbonds, query_epoch, TxResponse,
 };
-use namada_sdk::wallet::AddressVpType;
+use namada_sdk::wallet::{AddressVpType, WalletIo};
 use namada_sdk::{display, display_line, edisplay_line, error, prompt, Namada};
 use tokio::time::Instant;
 
 use crate::cli::{self, args};
+use crate::client::output;
 use crate::facade::tendermint::merkle::proof::ProofOps;
 use crate::facade::tendermint_rpc::error::Error as TError;
+use crate::masp_indexer::IndexerClient;
+use crate::wallet::read_and_confirm_encryption_password;
 
 /// Query the status of a given transaction.
 ///
@@ -82,11 +88,50 @@ pub async fn query_and_print_epoch(context: &impl Namada) -> Epoch {
     epoch
 }
 
+/// Query the epoch of the last committed block and print it in the given
+/// format
+pub async fn query_and_output_epoch(
+    context: &impl Namada,
+    format: args::OutputFormat,
+) -> Epoch {
+    let epoch = query_and_maybe_print_epoch(context, format).await;
+    if format.is_json() {
+        output::print_json(
+            context.io(),
+            "epoch",
+            output::EpochOutput { epoch },
+        );
+    }
+    epoch
+}
+
+/// Query the epoch of the last committed block, and only print it if the
+/// output is text, where it precedes the result of the query
+async fn query_and_maybe_print_epoch(
+    context: &impl Namada,
+    format: args::OutputFormat,
+) -> Epoch {
+    if format.is_json() {
+        rpc::query_epoch(context.client()).await.unwrap()
+    } else {
+        query_and_print_epoch(context).await
+    }
+}
+
 /// Query the last committed block
-pub async fn query_block(context: &impl Namada) {
+pub async fn query_block(context: &impl Namada, format: args::OutputFormat) {
     let block = namada_sdk::rpc::query_block(context.client())
         .await
         .unwrap();
+    if format.is_json() {
+        let block = block.map(|block| output::BlockOutput {
+            hash: block.hash.to_string(),
+            height: block.height,
+            time: block.time.to_rfc3339(),
+        });
+        output::print_json(context.io(), "block", block);
+        return;
+    }
     match block {
         Some(block) => {
             display_line!(
@@ -113,11 +158,29 @@ pub async fn query_results<C: namada::ledger::queries::Client + Sync>(
     )
 }
 
+/// Load the saved shielded context into the given one, exiting if it cannot
+/// be read
+async fn load_shielded<N: Namada>(
+    context: &N,
+    shielded: &mut ShieldedContext<N::ShieldedUtils>,
+) {
+    if let Err(err) = shielded.load().await {
+        edisplay_line!(
+            context.io(),
+            "Failed to load the saved shielded context: {}",
+            err
+        );
+        cli::safe_exit(1)
+    }
+}
+
 /// Query the specified accepted transfers from the ledger
 pub async fn query_transfers(
     context: &impl Namada,
     args: args::QueryTransfers,
 ) {
+    let format = args.query.output;
+    let io = output::ProgressIo::new(context.io(), format);
     let query_token = args.token;
     let wallet = context.wallet().await;
     let query_owner = args.owner.map_or_else(
@@ -125,11 +188,12 @@ pub async fn query_transfers(
         Either::Left,
     );
     let mut shielded = context.shielded_mut().await;
-    let _ = shielded.load().await;
+    load_shielded(context, &mut shielded).await;
     // Obtain the effects of all shielded and transparent transactions
     let transfers = shielded
         .query_tx_deltas(
             context.client(),
+            &io,
             &query_owner,
             &query_token,
             &wallet.get_viewing_keys(),
@@ -143,6 +207,7 @@ pub async fn query_transfers(
         .values()
         .map(|fvk| (ExtendedFullViewingKey::from(*fvk).fvk.vk, fvk))
         .collect();
+    let mut transfers_output = vec![];
     // Now display historical shielded and transparent transactions
     for ((height, idx), (epoch, tfer_delta, tx_delta)) in transfers {
         // Check if this transfer pertains to the supplied owner
@@ -163,7 +228,7 @@ pub async fn query_transfers(
             let amt = shielded
                 .compute_exchanged_amount(
                     context.client(),
-                    context.io(),
+                    &io,
                     amt,
                     epoch,
                     Conversions::new(),
@@ -194,6 +259,46 @@ pub async fn query_transfers(
         if !relevant {
             continue;
         }
+        if format.is_json() {
+            let mut transparent = vec![];
+            for (account, MaspChange { asset, change }) in tfer_delta {
+                if account != MASP {
+                    let owner = account.to_string();
+                    transparent.push(
+                        balance_change(
+                            context, &io, owner, &asset, change, &account,
+                        )
+                        .await,
+                    );
+                }
+            }
+            let mut shielded_changes = vec![];
+            for (account, masp_change) in shielded_accounts {
+                if let Some(fvk) = fvk_map.get(&account) {
+                    for (token, change) in masp_change {
+                        shielded_changes.push(
+                            balance_change(
+                                context,
+                                &io,
+                                fvk.to_string(),
+                                &token,
+                                change,
+                                &MASP,
+                            )
+                            .await,
+                        );
+                    }
+                }
+            }
+            transfers_output.push(output::TransferOutput {
+                height,
+                index: idx.0,
+                epoch,
+                transparent,
+                shielded: shielded_changes,
+            });
+            continue;
+        }
         display_line!(
             context.io(),
             "Height: {}, Index: {}, Transparent Transfer:",
@@ -247,6 +352,30 @@ pub async fn query_transfers(
             }
         }
     }
+    if format.is_json() {
+        output::print_json(context.io(), "show-transfers", transfers_output);
+    }
+}
+
+/// The signed change of the balance of a token, for the JSON output
+async fn balance_change(
+    context: &impl Namada,
+    io: &impl Io,
+    owner: String,
+    token: &Address,
+    change: Change,
+    token_owner: &Address,
+) -> output::BalanceChange {
+    let sign = if change < Change::zero() { "-" } else { "" };
+    let amount =
+        rpc::denominate_amount(context.client(), io, token, change.into())
+            .await;
+    output::BalanceChange {
+        owner,
+        token: token.clone(),
+        token_alias: lookup_token_alias(context, token, token_owner).await,
+        change: format!("{}{}", sign, amount.to_string_precise()),
+    }
 }
 
 /// Query the raw bytes of given storage key
@@ -265,6 +394,19 @@ pub async fn query_raw_bytes<N: Namada>(
             )
             .await,
     );
+    if args.query.output.is_json() {
+        let data = (!response.data.is_empty())
+            .then(|| HEXLOWER.encode(&response.data));
+        output::print_json(
+            context.io(),
+            "query-bytes",
+            output::RawBytesOutput {
+                storage_key: args.storage_key.to_string(),
+                data,
+            },
+        );
+        return;
+    }
     if !response.data.is_empty() {
         display_line!(
             context.io(),
@@ -282,131 +424,274 @@ pub async fn query_raw_bytes<N: Namada>(
 
 /// Query token balance(s)
 pub async fn query_balance(context: &impl Namada, args: args::QueryBalance) {
-    // Query the balances of shielded or transparent account types depending on
-    // the CLI arguments
-    match &args.owner {
-        Some(BalanceOwner::FullViewingKey(_viewing_key)) => {
-            query_shielded_balance(context, args).await
-        }
-        Some(BalanceOwner::Address(_owner)) => {
-            query_transparent_balance(context, args).await
-        }
-        Some(BalanceOwner::PaymentAddress(_owner)) => {
-            query_pinned_balance(context, args).await
-        }
-        None => {
-            // Print pinned balance
-            query_pinned_balance(context, args.clone()).await;
-            // Print shielded balance
-            query_shielded_balance(context, args.clone()).await;
-            // Then print transparent balance
-            query_transparent_balance(context, args).await;
+    let format = args.query.output;
+    let io = output::ProgressIo::new(context.io(), format);
+    let balances = query_balances(context, &io, &args).await;
+    if format.is_json() {
+        output::print_json(context.io(), "balance", balances.output);
+    } else {
+        print_balances(context, &args, &balances).await;
+    }
+}
+
+/// The balances matching a balance query
+struct Balances {
+    /// The shielded tokens matching the base token of the query, if it has
+    /// one, by alias
+    tokens: Option<BTreeMap<String, Address>>,
+    /// The epoch of the last committed block, if shielded balances were
+    /// queried
+    epoch: Option<Epoch>,
+    /// The balances, as printed with the JSON output
+    output: output::BalancesOutput,
+}
+
+/// Query the balances of the account types matching the owner of the query,
+/// printing the progress to the given [`Io`]
+async fn query_balances(
+    context: &impl Namada,
+    io: &impl Io,
+    args: &args::QueryBalance,
+) -> Balances {
+    let is_shielded = !matches!(args.owner, Some(BalanceOwner::Address(_)));
+    let tokens = match &args.token {
+        Some(base_token) if is_shielded => {
+            Some(query_tokens(context, Some(base_token), Some(&MASP)).await)
         }
+        _ => None,
+    };
+    let mut balances = Balances {
+        tokens,
+        epoch: None,
+        output: output::BalancesOutput::default(),
     };
+    if matches!(args.owner, None | Some(BalanceOwner::PaymentAddress(_))) {
+        balances.output.pinned =
+            pinned_balances(context, io, args, balances.tokens.as_ref()).await;
+    }
+    if matches!(args.owner, None | Some(BalanceOwner::FullViewingKey(_))) {
+        let (epoch, shielded) =
+            shielded_balances(context, io, args, balances.tokens.as_ref())
+                .await;
+        balances.epoch = Some(epoch);
+        balances.output.shielded = shielded;
+    }
+    if matches!(args.owner, None | Some(BalanceOwner::Address(_))) {
+        balances.output.transparent =
+            transparent_balances(context, io, args).await;
+    }
+    balances
 }
 
-/// Query token balance(s)
-pub async fn query_transparent_balance(
+/// The balance of a token
+async fn token_balance(
     context: &impl Namada,
-    args: args::QueryBalance,
-) {
-    let prefix = Key::from(
-        Address::Internal(namada::types::address::InternalAddress::Multitoken)
-            .to_db_key(),
-    );
-    match (args.token, args.owner) {
-        (Some(base_token), Some(owner)) => {
-            let owner = owner.address().unwrap();
-            let tokens =
-                query_tokens(context, Some(&base_token), Some(&owner)).await;
-            for (token_alias, token) in tokens {
-                let balance_key = token::balance_key(&token, &owner);
-                match query_storage_value::<_, token::Amount>(
-                    context.client(),
-                    &balance_key,
-                )
-                .await
-                {
-                    Ok(balance) => {
-                        let balance =
-                            context.format_amount(&token, balance).await;
-                        display_line!(
-                            context.io(),
-                            "{}: {}",
-                            token_alias,
-                            balance
-                        );
-                    }
-                    Err(e) => {
-                        display_line!(context.io(), "Querying error: {e}");
-                        display_line!(
-                            context.io(),
-                            "No {} balance found for {}",
-                            token_alias,
-                            owner
-                        )
-                    }
-                }
-            }
-        }
-        (None, Some(owner)) => {
-            let owner = owner.address().unwrap();
-            let tokens = query_tokens(context, None, Some(&owner)).await;
-            for (token_alias, token) in tokens {
-                let balance =
-                    get_token_balance(context.client(), &token, &owner).await;
-                if !balance.is_zero() {
-                    let balance = context.format_amount(&token, balance).await;
-                    display_line!(context.io(), "{}: {}", token_alias, balance);
-                }
-            }
-        }
-        (Some(base_token), None) => {
-            let tokens = query_tokens(context, Some(&base_token), None).await;
-            for (_, token) in tokens {
-                let prefix = token::balance_prefix(&token);
-                let balances =
-                    query_storage_prefix::<token::Amount>(context, &prefix)
-                        .await;
-                if let Some(balances) = balances {
-                    print_balances(context, balances, Some(&token), None).await;
-                }
+    io: &impl Io,
+    token: Address,
+    token_alias: String,
+    amount: token::Amount,
+) -> output::TokenBalance {
+    output::TokenBalance {
+        amount: rpc::denominate_amount(context.client(), io, &token, amount)
+            .await,
+        token,
+        token_alias,
+    }
+}
+
+/// Query the transparent balances matching the query
+async fn transparent_balances(
+    context: &impl Namada,
+    io: &impl Io,
+    args: &args::QueryBalance,
+) -> Vec<output::TransparentBalance> {
+    let mut balances = vec![];
+    if let Some(owner) = args.owner.as_ref().and_then(|owner| owner.address()) {
+        let tokens =
+            query_tokens(context, args.token.as_ref(), Some(&owner)).await;
+        for (token_alias, token) in tokens {
+            let amount =
+                get_token_balance(context.client(), &token, &owner).await;
+            // Only the balances of the given token are shown when zero
+            if args.token.is_some() || !amount.is_zero() {
+                balances.push(output::TransparentBalance {
+                    owner: owner.clone(),
+                    balance: token_balance(
+                        context,
+                        io,
+                        token,
+                        token_alias,
+                        amount,
+                    )
+                    .await,
+                });
             }
         }
-        (None, None) => {
-            let balances = query_storage_prefix(context, &prefix).await;
-            if let Some(balances) = balances {
-                print_balances(context, balances, None, None).await;
+        return balances;
+    }
+    let prefixes = match &args.token {
+        Some(base_token) => query_tokens(context, Some(base_token), None)
+            .await
+            .into_values()
+            .map(|token| token::balance_prefix(&token))
+            .collect(),
+        None => vec![Key::from(
+            Address::Internal(InternalAddress::Multitoken).to_db_key(),
+        )],
+    };
+    for prefix in prefixes {
+        let Some(entries) =
+            query_storage_prefix::<token::Amount>(context, &prefix).await
+        else {
+            continue;
+        };
+        for (key, amount) in entries {
+            if let Some([token, owner]) = token::is_any_token_balance_key(&key)
+            {
+                let token_alias =
+                    lookup_token_alias(context, token, owner).await;
+                balances.push(output::TransparentBalance {
+                    owner: owner.clone(),
+                    balance: token_balance(
+                        context,
+                        io,
+                        token.clone(),
+                        token_alias,
+                        amount,
+                    )
+                    .await,
+                });
             }
         }
     }
+    balances
+}
+
+/// Check if the given token is one of the tokens matching the base token of
+/// a query, if it has one
+fn is_queried_token(
+    tokens: Option<&BTreeMap<String, Address>>,
+    token: &Address,
+) -> bool {
+    tokens.map_or(true, |tokens| tokens.values().any(|t| t == token))
 }
 
-/// Query the token pinned balance(s)
-pub async fn query_pinned_balance(
+/// Query the shielded balances of the viewing key of the query, or of all the
+/// viewing keys in the wallet, along with the epoch of the last committed
+/// block. Only the balances of this epoch are kept, unless conversions are
+/// disabled.
+async fn shielded_balances(
     context: &impl Namada,
-    args: args::QueryBalance,
-) {
-    // Map addresses to token names
-    let wallet = context.wallet().await;
-    let owners = if let Some(pa) = args.owner.and_then(|x| x.payment_address())
+    io: &impl Io,
+    args: &args::QueryBalance,
+    tokens: Option<&BTreeMap<String, Address>>,
+) -> (Epoch, Vec<output::ShieldedBalance>) {
+    let viewing_keys: Vec<_> = match args
+        .owner
+        .as_ref()
+        .and_then(|owner| owner.full_viewing_key())
     {
-        vec![pa]
-    } else {
-        wallet
-            .get_payment_addrs()
-            .into_values()
-            .filter(PaymentAddress::is_pinned)
-            .collect()
+        Some(viewing_key) => vec![viewing_key],
+        None => context
+            .wallet()
+            .await
+            .get_viewing_keys()
+            .values()
+            .copied()
+            .collect(),
     };
-    // Get the viewing keys with which to try note decryptions
-    let viewing_keys: Vec<ViewingKey> = wallet
-        .get_viewing_keys()
-        .values()
+    let vks: Vec<_> = viewing_keys
+        .iter()
         .map(|fvk| ExtendedFullViewingKey::from(*fvk).fvk.vk)
         .collect();
-    let _ = context.shielded_mut().await.load().await;
-    // Print the token balances by payment address
+    {
+        let mut shielded = context.shielded_mut().await;
+        load_shielded(context, &mut shielded).await;
+        shielded
+            .fetch(context.client(), io, &[], &vks)
+            .await
+            .unwrap();
+        // Save the update state so that future fetches can be short-circuited
+        let _ = shielded.save().await;
+    }
+    // The epoch is required to identify timestamped tokens
+    let epoch = query_epoch(context.client()).await.unwrap();
+    let mut balances = vec![];
+    for (fvk, vk) in viewing_keys.iter().zip(&vks) {
+        let balance = if args.no_conversions {
+            context
+                .shielded_mut()
+                .await
+                .compute_shielded_balance(context.client(), vk)
+                .await
+        } else {
+            context
+                .shielded_mut()
+                .await
+                .compute_exchanged_balance(context.client(), io, vk, epoch)
+                .await
+        }
+        .unwrap()
+        .expect("context should contain viewing key");
+        for ((asset_epoch, token), amount) in balance.iter() {
+            if (!args.no_conversions && *asset_epoch != epoch)
+                || amount.is_zero()
+                || !is_queried_token(tokens, token)
+            {
+                continue;
+            }
+            let token_alias = lookup_token_alias(context, token, &MASP).await;
+            balances.push(output::ShieldedBalance {
+                viewing_key: fvk.to_string(),
+                asset_epoch: *asset_epoch,
+                balance: token_balance(
+                    context,
+                    io,
+                    token.clone(),
+                    token_alias,
+                    (*amount).into(),
+                )
+                .await,
+            });
+        }
+    }
+    (epoch, balances)
+}
+
+/// Query the tokens received by the payment address of the query, or by all
+/// the pinned payment addresses in the wallet. Unless the output is JSON, the
+/// viewing key of a payment address that no viewing key of the wallet can
+/// decode is prompted for.
+async fn pinned_balances(
+    context: &impl Namada,
+    io: &impl Io,
+    args: &args::QueryBalance,
+    tokens: Option<&BTreeMap<String, Address>>,
+) -> Vec<output::PinnedBalance> {
+    let (owners, viewing_keys) = {
+        let wallet = context.wallet().await;
+        let owners: Vec<_> = match args
+            .owner
+            .as_ref()
+            .and_then(|owner| owner.payment_address())
+        {
+            Some(pa) => vec![pa],
+            None => wallet
+                .get_payment_addrs()
+                .into_values()
+                .filter(PaymentAddress::is_pinned)
+                .collect(),
+        };
+        // Get the viewing keys with which to try note decryptions
+        let viewing_keys: Vec<ViewingKey> = wallet
+            .get_viewing_keys()
+            .values()
+            .map(|fvk| ExtendedFullViewingKey::from(*fvk).fvk.vk)
+            .collect();
+        (owners, viewing_keys)
+    };
+    load_shielded(context, &mut *context.shielded_mut().await).await;
+    let mut balances = vec![];
     for owner in owners {
         let mut balance =
             Err(Error::from(PinnedBalanceError::InvalidViewingKey));
@@ -416,14 +701,19 @@ pub async fn query_pinned_balance(
             balance = context
                 .shielded_mut()
                 .await
-                .compute_exchanged_pinned_balance(context, owner, vk)
+                .compute_exchanged_pinned_balance(
+                    context.client(),
+                    io,
+                    owner,
+                    vk,
+                )
                 .await;
             if !is_pinned_error(&balance) {
                 break;
             }
         }
         // If a suitable viewing key was not found, then demand it from the user
-        if is_pinned_error(&balance) {
+        if is_pinned_error(&balance) && !args.query.output.is_json() {
             let vk_str =
                 prompt!(context.io(), "Enter the viewing key for {}: ", owner)
                     .await;
@@ -439,190 +729,319 @@ pub async fn query_pinned_balance(
             balance = context
                 .shielded_mut()
                 .await
-                .compute_exchanged_pinned_balance(context, owner, &vk)
+                .compute_exchanged_pinned_balance(
+                    context.client(),
+                    io,
+                    owner,
+                    &vk,
+                )
                 .await
         }
-
-        // Now print out the received quantities according to CLI arguments
-        match (balance, args.token.as_ref()) {
-            (Err(Error::Pinned(PinnedBalanceError::InvalidViewingKey)), _) => {
-                display_line!(
-                    context.io(),
+        let mut pinned = output::PinnedBalance {
+            payment_address: owner.to_string(),
+            epoch: None,
+            balances: vec![],
+            error: None,
+        };
+        match balance {
+            Ok((balance, epoch)) => {
+                pinned.epoch = Some(epoch);
+                for ((token_epoch, token), value) in balance.iter() {
+                    if *token_epoch != epoch || !is_queried_token(tokens, token)
+                    {
+                        continue;
+                    }
+                    let token_alias =
+                        lookup_token_alias(context, token, &MASP).await;
+                    pinned.balances.push(
+                        token_balance(
+                            context,
+                            io,
+                            token.clone(),
+                            token_alias,
+                            (*value).into(),
+                        )
+                        .await,
+                    );
+                }
+            }
+            Err(Error::Pinned(PinnedBalanceError::InvalidViewingKey)) => {
+                pinned.error = Some(
                     "Supplied viewing key cannot decode transactions to given \
                      payment address."
-                )
+                        .to_string(),
+                );
             }
-            (
-                Err(Error::Pinned(PinnedBalanceError::NoTransactionPinned)),
-                _,
-            ) => {
-                display_line!(
-                    context.io(),
+            Err(Error::Pinned(PinnedBalanceError::NoTransactionPinned)) => {
+                pinned.error = Some(format!(
                     "Payment address {} has not yet been consumed.",
                     owner
-                )
-            }
-            (Err(other), _) => {
-                display_line!(
-                    context.io(),
-                    "Error in Querying Pinned balance {}",
-                    other
-                )
+                ));
             }
-            (Ok((balance, epoch)), Some(base_token)) => {
-                let tokens =
-                    query_tokens(context, Some(base_token), None).await;
-                for (token_alias, token) in &tokens {
-                    let total_balance = balance
-                        .get(&(epoch, token.clone()))
-                        .cloned()
-                        .unwrap_or_default();
-
-                    if total_balance.is_zero() {
-                        display_line!(
-                            context.io(),
-                            "Payment address {} was consumed during epoch {}. \
-                             Received no shielded {}",
-                            owner,
-                            epoch,
-                            token_alias
-                        );
-                    } else {
-                        let formatted = context
-                            .format_amount(token, total_balance.into())
-                            .await;
-                        display_line!(
-                            context.io(),
-                            "Payment address {} was consumed during epoch {}. \
-                             Received {} {}",
-                            owner,
-                            epoch,
-                            formatted,
-                            token_alias,
-                        );
-                    }
-                }
+            Err(other) => {
+                pinned.error =
+                    Some(format!("Error in Querying Pinned balance {}", other));
             }
-            (Ok((balance, epoch)), None) => {
-                let mut found_any = false;
+        }
+        balances.push(pinned);
+    }
+    balances
+}
 
-                for ((_, token_addr), value) in balance
-                    .iter()
-                    .filter(|((token_epoch, _), _)| *token_epoch == epoch)
-                {
-                    if !found_any {
-                        display_line!(
-                            context.io(),
-                            "Payment address {} was consumed during epoch {}. \
-                             Received:",
-                            owner,
-                            epoch
-                        );
-                        found_any = true;
-                    }
-                    let formatted = context
-                        .format_amount(token_addr, (*value).into())
-                        .await;
-                    let token_alias =
-                        lookup_token_alias(context, token_addr, &MASP).await;
-                    display_line!(
+/// Print the balances of a balance query as text
+async fn print_balances(
+    context: &impl Namada,
+    args: &args::QueryBalance,
+    balances: &Balances,
+) {
+    let tokens = balances.tokens.as_ref();
+    for pinned in &balances.output.pinned {
+        print_pinned_balance(context, pinned, tokens);
+    }
+    if let Some(epoch) = balances.epoch {
+        display_line!(context.io(), "Last committed epoch: {}", epoch);
+        print_shielded_balances(
+            context,
+            args,
+            epoch,
+            tokens,
+            &balances.output.shielded,
+        );
+    }
+    if matches!(args.owner, None | Some(BalanceOwner::Address(_))) {
+        print_transparent_balances(context, args, &balances.output.transparent)
+            .await;
+    }
+}
+
+/// Print the tokens received by a pinned payment address, or why they
+/// couldn't be computed
+fn print_pinned_balance(
+    context: &impl Namada,
+    pinned: &output::PinnedBalance,
+    tokens: Option<&BTreeMap<String, Address>>,
+) {
+    let owner = &pinned.payment_address;
+    let Some(epoch) = pinned.epoch else {
+        if let Some(error) = &pinned.error {
+            display_line!(context.io(), "{}", error);
+        }
+        return;
+    };
+    match tokens {
+        Some(tokens) => {
+            for (token_alias, token) in tokens {
+                let balance = pinned.balances.iter().find(|balance| {
+                    balance.token == *token && !balance.amount.amount.is_zero()
+                });
+                match balance {
+                    Some(balance) => display_line!(
                         context.io(),
-                        " {}: {}",
+                        "Payment address {} was consumed during epoch {}. \
+                         Received {} {}",
+                        owner,
+                        epoch,
+                        balance.amount,
                         token_alias,
-                        formatted,
-                    );
-                }
-                if !found_any {
-                    display_line!(
+                    ),
+                    None => display_line!(
                         context.io(),
                         "Payment address {} was consumed during epoch {}. \
-                         Received no shielded assets.",
+                         Received no shielded {}",
                         owner,
-                        epoch
-                    );
+                        epoch,
+                        token_alias
+                    ),
                 }
             }
         }
+        None if pinned.balances.is_empty() => {
+            display_line!(
+                context.io(),
+                "Payment address {} was consumed during epoch {}. Received no \
+                 shielded assets.",
+                owner,
+                epoch
+            );
+        }
+        None => {
+            display_line!(
+                context.io(),
+                "Payment address {} was consumed during epoch {}. Received:",
+                owner,
+                epoch
+            );
+            for balance in &pinned.balances {
+                display_line!(
+                    context.io(),
+                    " {}: {}",
+                    balance.token_alias,
+                    balance.amount,
+                );
+            }
+        }
     }
 }
 
-async fn print_balances(
+/// Print the shielded balances of a balance query
+fn print_shielded_balances(
     context: &impl Namada,
-    balances: impl Iterator<Item = (storage::Key, token::Amount)>,
-    token: Option<&Address>,
-    target: Option<&Address>,
+    args: &args::QueryBalance,
+    epoch: Epoch,
+    tokens: Option<&BTreeMap<String, Address>>,
+    balances: &[output::ShieldedBalance],
 ) {
-    let stdout = io::stdout();
-    let mut w = stdout.lock();
-    let wallet = context.wallet().await;
-
-    let mut print_num = 0;
-    let mut print_token = None;
-    for (key, balance) in balances {
-        // Get the token, the owner, and the balance with the token and the
-        // owner
-        let (t, o, s) = match token::is_any_token_balance_key(&key) {
-            Some([tok, owner]) => (
-                tok.clone(),
-                owner.clone(),
-                format!(
-                    ": {}, owned by {}",
-                    context.format_amount(tok, balance).await,
-                    wallet.lookup_alias(owner)
-                ),
-            ),
-            None => continue,
-        };
-        let token_alias = lookup_token_alias(context, &t, &o).await;
-        // Get the token and the balance
-        let (t, s) = match (token, target) {
-            // the given token and the given target are the same as the
-            // retrieved ones
-            (Some(token), Some(target)) if t == *token && o == *target => {
-                (t, s)
+    let owned_by_key = args
+        .owner
+        .as_ref()
+        .and_then(|owner| owner.full_viewing_key())
+        .is_some();
+    match (tokens, owned_by_key) {
+        // Here the user wants to know the balance for a specific token
+        (Some(tokens), true) => {
+            for (token_alias, token) in tokens {
+                let mut token_balances = balances
+                    .iter()
+                    .filter(|shielded| {
+                        shielded.balance.token == *token
+                            && shielded.asset_epoch == epoch
+                    })
+                    .peekable();
+                if token_balances.peek().is_none() {
+                    display_line!(
+                        context.io(),
+                        "No shielded {} balance found for given key",
+                        token_alias
+                    );
+                }
+                for shielded in token_balances {
+                    display_line!(
+                        context.io(),
+                        "{}: {}",
+                        token_alias,
+                        shielded.balance.amount
+                    );
+                }
             }
-            // the given token is the same as the retrieved one
-            (Some(token), None) if t == *token => (t, s),
-            // the given target is the same as the retrieved one
-            (None, Some(target)) if o == *target => (t, s),
-            // no specified token or target
-            (None, None) => (t, s),
-            // otherwise, this balance will not be printed
-            _ => continue,
-        };
-        // Print the token if it isn't printed yet
-        match &print_token {
-            Some(token) if *token == t => {
-                // the token has been already printed
+        }
+        // Here the user wants to know the balance of all tokens across users
+        (None, false) => {
+            for shielded in balances
+                .iter()
+                .filter(|shielded| shielded.asset_epoch == epoch)
+            {
+                display_line!(
+                    context.io(),
+                    "Shielded Token {}:",
+                    shielded.balance.token_alias
+                );
+                display_line!(
+                    context.io(),
+                    "  {}, owned by {}",
+                    shielded.balance.amount,
+                    shielded.viewing_key
+                );
             }
-            _ => {
-                display_line!(context.io(), &mut w; "Token {}", token_alias)
-                    .unwrap();
-                print_token = Some(t);
+        }
+        // Here the user wants to know the balance for a specific token across
+        // users
+        (Some(tokens), false) => {
+            for (token_alias, token) in tokens {
+                display_line!(context.io(), "Shielded Token {}:", token_alias);
+                let mut token_balances = balances
+                    .iter()
+                    .filter(|shielded| shielded.balance.token == *token)
+                    .peekable();
+                if token_balances.peek().is_none() {
+                    display_line!(
+                        context.io(),
+                        "No shielded {} balance found for any wallet key",
+                        token_alias,
+                    );
+                }
+                for shielded in token_balances {
+                    display_line!(
+                        context.io(),
+                        "  {}, owned by {}",
+                        shielded.balance.amount,
+                        shielded.viewing_key
+                    );
+                }
+            }
+        }
+        // Here the user wants to know all possible token balances for a key
+        (None, true) => {
+            if balances.is_empty() {
+                display_line!(
+                    context.io(),
+                    "No shielded balance found for given key"
+                );
+            }
+            for shielded in balances {
+                if args.no_conversions {
+                    display_line!(
+                        context.io(),
+                        "{} | {} : {}",
+                        shielded.balance.token_alias,
+                        shielded.asset_epoch,
+                        shielded.balance.amount,
+                    );
+                } else {
+                    display_line!(
+                        context.io(),
+                        "{} : {}",
+                        shielded.balance.token_alias,
+                        shielded.balance.amount,
+                    );
+                }
             }
         }
-        // Print the balance
-        display_line!(context.io(), &mut w; "{}", s).unwrap();
-        print_num += 1;
     }
+}
 
-    if print_num == 0 {
-        match (token, target) {
-            (Some(_), Some(target)) | (None, Some(target)) => display_line!(
+/// Print the transparent balances of a balance query
+async fn print_transparent_balances(
+    context: &impl Namada,
+    args: &args::QueryBalance,
+    balances: &[output::TransparentBalance],
+) {
+    // The balances of a given owner are printed by token
+    if args.owner.is_some() {
+        for output::TransparentBalance { balance, .. } in balances {
+            display_line!(
                 context.io(),
-                &mut w;
-                "No balances owned by {}",
-                wallet.lookup_alias(target)
-            )
-            .unwrap(),
-            (Some(token), None) => {
-                let token_alias = wallet.lookup_alias(token);
-                display_line!(context.io(), &mut w; "No balances for token {}", token_alias).unwrap()
-            }
-            (None, None) => {
-                display_line!(context.io(), &mut w; "No balances").unwrap()
-            }
+                "{}: {}",
+                balance.token_alias,
+                balance.amount
+            );
+        }
+        return;
+    }
+    let wallet = context.wallet().await;
+    if balances.is_empty() {
+        match &args.token {
+            Some(token) => display_line!(
+                context.io(),
+                "No balances for token {}",
+                wallet.lookup_alias(token)
+            ),
+            None => display_line!(context.io(), "No balances"),
+        }
+        return;
+    }
+    let mut print_token = None;
+    for output::TransparentBalance { owner, balance } in balances {
+        // Print the token if it isn't printed yet
+        if print_token != Some(&balance.token) {
+            display_line!(context.io(), "Token {}", balance.token_alias);
+            print_token = Some(&balance.token);
         }
+        display_line!(
+            context.io(),
+            ": {}, owned by {}",
+            balance.amount,
+            wallet.lookup_alias(owner)
+        );
     }
 }
 
@@ -682,415 +1101,126 @@ async fn query_tokens(
             // transfers in the same chain
             vec![ibc_denom_key_prefix(None)]
         }
-        (None, None) => vec![ibc_denom_key_prefix(None)],
-    };
-
-    for prefix in prefixes {
-        let ibc_denoms = query_storage_prefix::<String>(context, &prefix).await;
-        if let Some(ibc_denoms) = ibc_denoms {
-            for (key, ibc_denom) in ibc_denoms {
-                if let Some((_, hash)) = is_ibc_denom_key(&key) {
-                    let ibc_denom_alias =
-                        get_ibc_denom_alias(context, ibc_denom).await;
-                    let hash: IbcTokenHash = hash.parse().expect(
-                        "Parsing an IBC token hash from storage shouldn't fail",
-                    );
-                    let ibc_token =
-                        Address::Internal(InternalAddress::IbcToken(hash));
-                    tokens.insert(ibc_denom_alias, ibc_token);
-                }
-            }
-        }
-    }
-    tokens
-}
-
-async fn get_ibc_denom_alias(
-    context: &impl Namada,
-    ibc_denom: impl AsRef<str>,
-) -> String {
-    let wallet = context.wallet().await;
-    is_ibc_denom(&ibc_denom)
-        .map(|(trace_path, base_token)| {
-            let base_token_alias = match Address::decode(&base_token) {
-                Ok(base_token) => wallet.lookup_alias(&base_token),
-                Err(_) => base_token,
-            };
-            if trace_path.is_empty() {
-                base_token_alias
-            } else {
-                format!("{}/{}", trace_path, base_token_alias)
-            }
-        })
-        .unwrap_or(ibc_denom.as_ref().to_string())
-}
-
-/// Query Proposals
-pub async fn query_proposal(context: &impl Namada, args: args::QueryProposal) {
-    let current_epoch = query_and_print_epoch(context).await;
-
-    if let Some(id) = args.proposal_id {
-        let proposal =
-            query_proposal_by_id(context.client(), id).await.unwrap();
-        if let Some(proposal) = proposal {
-            display_line!(
-                context.io(),
-                "{}",
-                proposal.to_string_with_status(current_epoch)
-            );
-        } else {
-            edisplay_line!(context.io(), "No proposal found with id: {}", id);
-        }
-    } else {
-        let last_proposal_id_key = governance_storage::get_counter_key();
-        let last_proposal_id: u64 =
-            query_storage_value(context.client(), &last_proposal_id_key)
-                .await
-                .unwrap();
-
-        let from_id = if last_proposal_id > 10 {
-            last_proposal_id - 10
-        } else {
-            0
-        };
-
-        display_line!(context.io(), "id: {}", last_proposal_id);
-
-        for id in from_id..last_proposal_id {
-            let proposal = query_proposal_by_id(context.client(), id)
-                .await
-                .unwrap()
-                .expect("Proposal should be written to storage.");
-            display_line!(context.io(), "{}", proposal);
-        }
-    }
-}
-
-/// Query proposal by Id
-pub async fn query_proposal_by_id<C: namada::ledger::queries::Client + Sync>(
-    client: &C,
-    proposal_id: u64,
-) -> Result<Option<StorageProposal>, error::Error> {
-    namada_sdk::rpc::query_proposal_by_id(client, proposal_id).await
-}
-
-/// Query token shielded balance(s)
-pub async fn query_shielded_balance(
-    context: &impl Namada,
-    args: args::QueryBalance,
-) {
-    // Used to control whether balances for all keys or a specific key are
-    // printed
-    let owner = args.owner.and_then(|x| x.full_viewing_key());
-    // Used to control whether conversions are automatically performed
-    let no_conversions = args.no_conversions;
-    // Viewing keys are used to query shielded balances. If a spending key is
-    // provided, then convert to a viewing key first.
-    let viewing_keys = match owner {
-        Some(viewing_key) => vec![viewing_key],
-        None => context
-            .wallet()
-            .await
-            .get_viewing_keys()
-            .values()
-            .copied()
-            .collect(),
-    };
-    {
-        let mut shielded = context.shielded_mut().await;
-        let _ = shielded.load().await;
-        let fvks: Vec<_> = viewing_keys
-            .iter()
-            .map(|fvk| ExtendedFullViewingKey::from(*fvk).fvk.vk)
-            .collect();
-        shielded.fetch(context.client(), &[], &fvks).await.unwrap();
-        // Save the update state so that future fetches can be short-circuited
-        let _ = shielded.save().await;
-    }
-    // The epoch is required to identify timestamped tokens
-    let epoch = query_and_print_epoch(context).await;
-    // Map addresses to token names
-    match (args.token, owner.is_some()) {
-        // Here the user wants to know the balance for a specific token
-        (Some(base_token), true) => {
-            let tokens =
-                query_tokens(context, Some(&base_token), Some(&MASP)).await;
-            for (token_alias, token) in tokens {
-                // Query the multi-asset balance at the given spending key
-                let viewing_key =
-                    ExtendedFullViewingKey::from(viewing_keys[0]).fvk.vk;
-                let balance: MaspAmount = if no_conversions {
-                    context
-                        .shielded_mut()
-                        .await
-                        .compute_shielded_balance(
-                            context.client(),
-                            &viewing_key,
-                        )
-                        .await
-                        .unwrap()
-                        .expect("context should contain viewing key")
-                } else {
-                    context
-                        .shielded_mut()
-                        .await
-                        .compute_exchanged_balance(
-                            context.client(),
-                            context.io(),
-                            &viewing_key,
-                            epoch,
-                        )
-                        .await
-                        .unwrap()
-                        .expect("context should contain viewing key")
-                };
-
-                let total_balance = balance
-                    .get(&(epoch, token.clone()))
-                    .cloned()
-                    .unwrap_or_default();
-                if total_balance.is_zero() {
-                    display_line!(
-                        context.io(),
-                        "No shielded {} balance found for given key",
-                        token_alias
-                    );
-                } else {
-                    display_line!(
-                        context.io(),
-                        "{}: {}",
-                        token_alias,
-                        context
-                            .format_amount(
-                                &token,
-                                token::Amount::from(total_balance),
-                            )
-                            .await
-                    );
-                }
-            }
-        }
-        // Here the user wants to know the balance of all tokens across users
-        (None, false) => {
-            // Maps asset types to balances divided by viewing key
-            let mut balances = HashMap::new();
-            for fvk in viewing_keys {
-                // Query the multi-asset balance at the given spending key
-                let viewing_key = ExtendedFullViewingKey::from(fvk).fvk.vk;
-                let balance = if no_conversions {
-                    context
-                        .shielded_mut()
-                        .await
-                        .compute_shielded_balance(
-                            context.client(),
-                            &viewing_key,
-                        )
-                        .await
-                        .unwrap()
-                        .expect("context should contain viewing key")
-                } else {
-                    context
-                        .shielded_mut()
-                        .await
-                        .compute_exchanged_balance(
-                            context.client(),
-                            context.io(),
-                            &viewing_key,
-                            epoch,
-                        )
-                        .await
-                        .unwrap()
-                        .expect("context should contain viewing key")
-                };
-                for (key, value) in balance.iter() {
-                    if !balances.contains_key(key) {
-                        balances.insert(key.clone(), Vec::new());
-                    }
-                    balances.get_mut(key).unwrap().push((fvk, *value));
-                }
-            }
-
-            // Print non-zero balances whose asset types can be decoded
-            // TODO Implement a function for this
-
-            let mut balance_map = HashMap::new();
-            for ((asset_epoch, token_addr), balances) in balances {
-                if asset_epoch == epoch {
-                    // remove this from here, should not be making the
-                    // hashtable creation any uglier
-                    if balances.is_empty() {
-                        display_line!(
-                            context.io(),
-                            "No shielded {} balance found for any wallet key",
-                            &token_addr
-                        );
-                    }
-                    for (fvk, value) in balances {
-                        balance_map.insert((fvk, token_addr.clone()), value);
-                    }
-                }
-            }
-            for ((fvk, token), token_balance) in balance_map {
-                // Only assets with the current timestamp count
-                let alias = lookup_token_alias(context, &token, &MASP).await;
-                display_line!(context.io(), "Shielded Token {}:", alias);
-                let formatted =
-                    context.format_amount(&token, token_balance.into()).await;
-                display_line!(
-                    context.io(),
-                    "  {}, owned by {}",
-                    formatted,
-                    fvk
-                );
-            }
-        }
-        // Here the user wants to know the balance for a specific token across
-        // users
-        (Some(base_token), false) => {
-            let tokens = query_tokens(context, Some(&base_token), None).await;
-            for (token_alias, token) in tokens {
-                // Compute the unique asset identifier from the token address
-                let token = token;
-                let _asset_type = AssetType::new(
-                    (token.clone(), epoch.0).serialize_to_vec().as_ref(),
-                )
-                .unwrap();
-                let mut found_any = false;
-                display_line!(context.io(), "Shielded Token {}:", token_alias);
-                for fvk in &viewing_keys {
-                    // Query the multi-asset balance at the given spending key
-                    let viewing_key = ExtendedFullViewingKey::from(*fvk).fvk.vk;
-                    let balance = if no_conversions {
-                        context
-                            .shielded_mut()
-                            .await
-                            .compute_shielded_balance(
-                                context.client(),
-                                &viewing_key,
-                            )
-                            .await
-                            .unwrap()
-                            .expect("context should contain viewing key")
-                    } else {
-                        context
-                            .shielded_mut()
-                            .await
-                            .compute_exchanged_balance(
-                                context.client(),
-                                context.io(),
-                                &viewing_key,
-                                epoch,
-                            )
-                            .await
-                            .unwrap()
-                            .expect("context should contain viewing key")
-                    };
-
-                    for ((_, address), val) in balance.iter() {
-                        if !val.is_zero() {
-                            found_any = true;
-                        }
-                        let formatted =
-                            context.format_amount(address, (*val).into()).await;
-                        display_line!(
-                            context.io(),
-                            "  {}, owned by {}",
-                            formatted,
-                            fvk
-                        );
-                    }
-                }
-                if !found_any {
-                    display_line!(
-                        context.io(),
-                        "No shielded {} balance found for any wallet key",
-                        token_alias,
+        (None, None) => vec![ibc_denom_key_prefix(None)],
+    };
+
+    for prefix in prefixes {
+        let ibc_denoms = query_storage_prefix::<String>(context, &prefix).await;
+        if let Some(ibc_denoms) = ibc_denoms {
+            for (key, ibc_denom) in ibc_denoms {
+                if let Some((_, hash)) = is_ibc_denom_key(&key) {
+                    let ibc_denom_alias =
+                        get_ibc_denom_alias(context, ibc_denom).await;
+                    let hash: IbcTokenHash = hash.parse().expect(
+                        "Parsing an IBC token hash from storage shouldn't fail",
                     );
+                    let ibc_token =
+                        Address::Internal(InternalAddress::IbcToken(hash));
+                    tokens.insert(ibc_denom_alias, ibc_token);
                 }
             }
         }
-        // Here the user wants to know all possible token balances for a key
-        (None, true) => {
-            // Query the multi-asset balance at the given spending key
-            let viewing_key =
-                ExtendedFullViewingKey::from(viewing_keys[0]).fvk.vk;
-            if no_conversions {
-                let balance = context
-                    .shielded_mut()
-                    .await
-                    .compute_shielded_balance(context.client(), &viewing_key)
-                    .await
-                    .unwrap()
-                    .expect("context should contain viewing key");
-                // Print balances by human-readable token names
-                print_decoded_balance_with_epoch(context, balance).await;
+    }
+    tokens
+}
+
+async fn get_ibc_denom_alias(
+    context: &impl Namada,
+    ibc_denom: impl AsRef<str>,
+) -> String {
+    let wallet = context.wallet().await;
+    is_ibc_denom(&ibc_denom)
+        .map(|(trace_path, base_token)| {
+            let base_token_alias = match Address::decode(&base_token) {
+                Ok(base_token) => wallet.lookup_alias(&base_token),
+                Err(_) => base_token,
+            };
+            if trace_path.is_empty() {
+                base_token_alias
             } else {
-                let balance = context
-                    .shielded_mut()
-                    .await
-                    .compute_exchanged_balance(
-                        context.client(),
-                        context.io(),
-                        &viewing_key,
-                        epoch,
-                    )
-                    .await
-                    .unwrap()
-                    .expect("context should contain viewing key");
-                // Print balances by human-readable token names
-                print_decoded_balance(context, balance, epoch).await;
+                format!("{}/{}", trace_path, base_token_alias)
+            }
+        })
+        .unwrap_or(ibc_denom.as_ref().to_string())
+}
+
+/// Query Proposals
+pub async fn query_proposal(context: &impl Namada, args: args::QueryProposal) {
+    if args.query.output.is_json() {
+        let epoch = query_epoch(context.client()).await.unwrap();
+        let ids = match args.proposal_id {
+            Some(id) => vec![id],
+            None => {
+                let last_proposal_id: u64 = query_storage_value(
+                    context.client(),
+                    &governance_storage::get_counter_key(),
+                )
+                .await
+                .unwrap();
+                (last_proposal_id.saturating_sub(10)..last_proposal_id)
+                    .collect()
+            }
+        };
+        let mut proposals = vec![];
+        for id in ids {
+            if let Some(proposal) =
+                query_proposal_by_id(context.client(), id).await.unwrap()
+            {
+                proposals.push(output::ProposalOutput::new(&proposal, epoch));
             }
         }
+        output::print_json(
+            context.io(),
+            "query-proposal",
+            output::ProposalsOutput { epoch, proposals },
+        );
+        return;
     }
-}
+    let current_epoch = query_and_print_epoch(context).await;
 
-pub async fn print_decoded_balance(
-    context: &impl Namada,
-    decoded_balance: MaspAmount,
-    epoch: Epoch,
-) {
-    if decoded_balance.is_empty() {
-        display_line!(context.io(), "No shielded balance found for given key");
-    } else {
-        for ((_, token_addr), amount) in decoded_balance
-            .iter()
-            .filter(|((token_epoch, _), _)| *token_epoch == epoch)
-        {
+    if let Some(id) = args.proposal_id {
+        let proposal =
+            query_proposal_by_id(context.client(), id).await.unwrap();
+        if let Some(proposal) = proposal {
             display_line!(
                 context.io(),
-                "{} : {}",
-                lookup_token_alias(context, token_addr, &MASP).await,
-                context.format_amount(token_addr, (*amount).into()).await,
+                "{}",
+                proposal.to_string_with_status(current_epoch)
             );
+        } else {
+            edisplay_line!(context.io(), "No proposal found with id: {}", id);
+        }
+    } else {
+        let last_proposal_id_key = governance_storage::get_counter_key();
+        let last_proposal_id: u64 =
+            query_storage_value(context.client(), &last_proposal_id_key)
+                .await
+                .unwrap();
+
+        let from_id = if last_proposal_id > 10 {
+            last_proposal_id - 10
+        } else {
+            0
+        };
+
+        display_line!(context.io(), "id: {}", last_proposal_id);
+
+        for id in from_id..last_proposal_id {
+            let proposal = query_proposal_by_id(context.client(), id)
+                .await
+                .unwrap()
+                .expect("Proposal should be written to storage.");
+            display_line!(context.io(), "{}", proposal);
         }
     }
 }
 
-pub async fn print_decoded_balance_with_epoch(
-    context: &impl Namada,
-    decoded_balance: MaspAmount,
-) {
-    let tokens = context
-        .wallet()
-        .await
-        .get_addresses_with_vp_type(AddressVpType::Token);
-    if decoded_balance.is_empty() {
-        display_line!(context.io(), "No shielded balance found for given key");
-    }
-    for ((epoch, token_addr), value) in decoded_balance.iter() {
-        let asset_value = (*value).into();
-        let alias = tokens
-            .get(token_addr)
-            .map(|a| a.to_string())
-            .unwrap_or_else(|| token_addr.to_string());
-        display_line!(
-            context.io(),
-            "{} | {} : {}",
-            alias,
-            epoch,
-            context.format_amount(token_addr, asset_value).await,
-        );
-    }
+/// Query proposal by Id
+pub async fn query_proposal_by_id<C: namada::ledger::queries::Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+) -> Result<Option<StorageProposal>, error::Error> {
+    namada_sdk::rpc::query_proposal_by_id(client, proposal_id).await
 }
 
 /// Query token amount of owner.
@@ -1156,6 +1286,19 @@ pub async fn query_proposal_result(
                 }
             };
 
+        if args.query.output.is_json() {
+            output::print_json(
+                context.io(),
+                "query-proposal-result",
+                output::ProposalResultOutput {
+                    proposal_id: Some(proposal_id),
+                    offline_proposal_hash: None,
+                    offline_votes: None,
+                    tally: output::TallyOutput::from(&proposal_result),
+                },
+            );
+            return;
+        }
         display_line!(context.io(), "Proposal Id: {} ", proposal_id);
         display_line!(context.io(), "{:4}{}", "", proposal_result);
     } else {
@@ -1234,6 +1377,21 @@ pub async fn query_proposal_result(
             TallyType::TwoThirds,
         );
 
+        if args.query.output.is_json() {
+            output::print_json(
+                context.io(),
+                "query-proposal-result",
+                output::ProposalResultOutput {
+                    proposal_id: None,
+                    offline_proposal_hash: Some(
+                        proposal.proposal.hash().to_string(),
+                    ),
+                    offline_votes: Some(votes.len()),
+                    tally: output::TallyOutput::from(&proposal_result),
+                },
+            );
+            return;
+        }
         display_line!(
             context.io(),
             "Proposal offline: {}",
@@ -1248,6 +1406,23 @@ pub async fn query_account(context: &impl Namada, args: args::QueryAccount) {
     let account = rpc::get_account_info(context.client(), &args.owner)
         .await
         .unwrap();
+    if args.query.output.is_json() {
+        let account = account.map(|account| output::AccountOutput {
+            address: account.address,
+            threshold: account.threshold,
+            // Ordered by the indexes of the keys
+            public_keys: account
+                .public_keys_map
+                .idx_to_pk
+                .into_iter()
+                .collect::<BTreeMap<_, _>>()
+                .into_values()
+                .map(|public_key| public_key.to_string())
+                .collect(),
+        });
+        output::print_json(context.io(), "query-account", account);
+        return;
+    }
     if let Some(account) = account {
         display_line!(context.io(), "Address: {}", account.address);
         display_line!(context.io(), "Threshold: {}", account.threshold);
@@ -1260,9 +1435,17 @@ pub async fn query_account(context: &impl Namada, args: args::QueryAccount) {
     }
 }
 
-pub async fn query_pgf(context: &impl Namada, _args: args::QueryPgf) {
+pub async fn query_pgf(context: &impl Namada, args: args::QueryPgf) {
     let stewards = query_pgf_stewards(context.client()).await;
     let fundings = query_pgf_fundings(context.client()).await;
+    if args.query.output.is_json() {
+        output::print_json(
+            context.io(),
+            "query-pgf",
+            output::PgfOutput::new(&stewards, &fundings),
+        );
+        return;
+    }
 
     match stewards.is_empty() {
         true => {
@@ -1313,8 +1496,18 @@ pub async fn query_pgf(context: &impl Namada, _args: args::QueryPgf) {
 
 pub async fn query_protocol_parameters(
     context: &impl Namada,
-    _args: args::QueryProtocolParameters,
+    args: args::QueryProtocolParameters,
 ) {
+    if args.query.output.is_json() {
+        let io = output::ProgressIo::new(context.io(), args.query.output);
+        let parameters = query_protocol_parameters_output(context, &io).await;
+        output::print_json(
+            context.io(),
+            "query-protocol-parameters",
+            parameters,
+        );
+        return;
+    }
     let governance_parameters =
         query_governance_parameters(context.client()).await;
     display_line!(context.io(), "Governance Parameters\n");
@@ -1509,6 +1702,112 @@ pub async fn query_protocol_parameters(
     );
 }
 
+/// Query all the parameters of the chain, for the JSON output, printing the
+/// diagnostics to the given [`Io`]
+async fn query_protocol_parameters_output(
+    context: &impl Namada,
+    io: &impl Io,
+) -> output::ProtocolParametersOutput {
+    let governance = query_governance_parameters(context.client()).await;
+    let pgf = query_pgf_parameters(context.client()).await;
+    let epoch_duration: EpochDuration = query_storage_value(
+        context.client(),
+        &param_storage::get_epoch_duration_storage_key(),
+    )
+    .await
+    .expect("Parameter should be defined.");
+    let max_block_duration: u64 = query_storage_value(
+        context.client(),
+        &param_storage::get_max_expected_time_per_block_key(),
+    )
+    .await
+    .expect("Parameter should be defined.");
+    let vp_whitelist: Vec<String> = query_storage_value(
+        context.client(),
+        &param_storage::get_vp_whitelist_storage_key(),
+    )
+    .await
+    .expect("Parameter should be defined.");
+    let tx_whitelist: Vec<String> = query_storage_value(
+        context.client(),
+        &param_storage::get_tx_whitelist_storage_key(),
+    )
+    .await
+    .expect("Parameter should be defined.");
+    let max_block_gas: u64 = query_storage_value(
+        context.client(),
+        &param_storage::get_max_block_gas_key(),
+    )
+    .await
+    .expect("Parameter should be defined.");
+    let fee_unshielding_gas_limit: u64 = query_storage_value(
+        context.client(),
+        &param_storage::get_fee_unshielding_gas_limit_key(),
+    )
+    .await
+    .expect("Parameter should be defined.");
+    let fee_unshielding_descriptions_limit: u64 = query_storage_value(
+        context.client(),
+        &param_storage::get_fee_unshielding_descriptions_limit_key(),
+    )
+    .await
+    .expect("Parameter should be defined.");
+    let gas_costs: BTreeMap<Address, token::Amount> = query_storage_value(
+        context.client(),
+        &param_storage::get_gas_cost_key(),
+    )
+    .await
+    .expect("Parameter should be defined.");
+    let mut gas_cost_table = BTreeMap::new();
+    for (token, gas_cost) in gas_costs {
+        let gas_cost =
+            rpc::denominate_amount(context.client(), io, &token, gas_cost)
+                .await;
+        gas_cost_table.insert(token, gas_cost);
+    }
+    let pos_params = query_pos_parameters(context.client()).await;
+
+    output::ProtocolParametersOutput {
+        governance: output::GovernanceParametersOutput {
+            min_proposal_fund: governance
+                .min_proposal_fund
+                .native_denominated(),
+            max_proposal_code_size: governance.max_proposal_code_size,
+            min_proposal_voting_period: governance.min_proposal_voting_period,
+            max_proposal_period: governance.max_proposal_period,
+            max_proposal_content_size: governance.max_proposal_content_size,
+            min_proposal_grace_epochs: governance.min_proposal_grace_epochs,
+        },
+        pgf: output::PgfParametersOutput {
+            pgf_inflation_rate: pgf.pgf_inflation_rate,
+            stewards_inflation_rate: pgf.stewards_inflation_rate,
+        },
+        protocol: output::ChainParametersOutput {
+            min_epoch_duration: epoch_duration.min_duration.0,
+            min_num_of_blocks: epoch_duration.min_num_of_blocks,
+            max_block_duration,
+            vp_whitelist,
+            tx_whitelist,
+            max_block_gas,
+            fee_unshielding_gas_limit,
+            fee_unshielding_descriptions_limit,
+            gas_cost_table,
+        },
+        pos: output::PosParametersOutput {
+            block_proposer_reward: pos_params.block_proposer_reward,
+            block_vote_reward: pos_params.block_vote_reward,
+            duplicate_vote_min_slash_rate: pos_params
+                .duplicate_vote_min_slash_rate,
+            light_client_attack_min_slash_rate: pos_params
+                .light_client_attack_min_slash_rate,
+            max_validator_slots: pos_params.max_validator_slots,
+            pipeline_len: pos_params.pipeline_len,
+            unbonding_len: pos_params.unbonding_len,
+            tm_votes_per_token: pos_params.tm_votes_per_token,
+        },
+    }
+}
+
 pub async fn query_bond<C: namada::ledger::queries::Client + Sync>(
     client: &C,
     source: &Address,
@@ -1629,7 +1928,7 @@ pub async fn query_bonds(
     context: &impl Namada,
     args: args::QueryBonds,
 ) -> std::io::Result<()> {
-    let epoch = query_and_print_epoch(context).await;
+    let epoch = query_and_maybe_print_epoch(context, args.query.output).await;
 
     let source = args.owner;
     let validator = args.validator;
@@ -1646,6 +1945,15 @@ pub async fn query_bonds(
     .await
     .unwrap();
 
+    if args.query.output.is_json() {
+        output::print_json(
+            context.io(),
+            "bonds",
+            output::BondsOutput::new(epoch, &bonds_and_unbonds),
+        );
+        return Ok(());
+    }
+
     for (bond_id, details) in &bonds_and_unbonds.data {
         let bond_type = if bond_id.source == bond_id.validator {
             format!("Self-bonds from {}", bond_id.validator)
@@ -1708,44 +2016,232 @@ pub async fn query_bonds(
         )?;
         display_line!(context.io(), &mut w; "")?;
     }
-    if bonds_and_unbonds.bonds_total != bonds_and_unbonds.bonds_total_slashed {
+    if bonds_and_unbonds.bonds_total != bonds_and_unbonds.bonds_total_slashed {
+        display_line!(
+            context.io(),
+            &mut w;
+            "All bonds total active: {}",
+            bonds_and_unbonds.bonds_total_active().to_string_native()
+        )?;
+    }
+    display_line!(
+        context.io(),
+        &mut w;
+        "All bonds total: {}",
+        bonds_and_unbonds.bonds_total.to_string_native()
+    )?;
+
+    if bonds_and_unbonds.unbonds_total
+        != bonds_and_unbonds.unbonds_total_slashed
+    {
+        display_line!(
+            context.io(),
+            &mut w;
+            "All unbonds total active: {}",
+            bonds_and_unbonds.unbonds_total_active().to_string_native()
+        )?;
+    }
+    display_line!(
+        context.io(),
+        &mut w;
+        "All unbonds total: {}",
+        bonds_and_unbonds.unbonds_total.to_string_native()
+    )?;
+    display_line!(
+        context.io(),
+        &mut w;
+        "All unbonds total withdrawable: {}",
+        bonds_and_unbonds.total_withdrawable.to_string_native()
+    )?;
+    Ok(())
+}
+
+/// Query the balances and bonds of the watch-only accounts of the wallet
+pub async fn query_watch_only_accounts(
+    context: &impl Namada,
+    format: args::OutputFormat,
+) {
+    let accounts = context
+        .wallet()
+        .await
+        .get_address_book()
+        .watch_only_accounts()
+        .clone();
+    if format.is_json() {
+        let epoch = query_epoch(context.client()).await.unwrap();
+        let mut watched = vec![];
+        for (alias, account) in accounts {
+            let owner = account.address;
+            let mut balances = vec![];
+            let tokens = query_tokens(context, None, Some(&owner)).await;
+            for (token_alias, token) in tokens {
+                let balance =
+                    get_token_balance(context.client(), &token, &owner).await;
+                if !balance.is_zero() {
+                    balances.push(
+                        token_balance(context, token, token_alias, balance)
+                            .await,
+                    );
+                }
+            }
+            // The totals are missing if the bonds couldn't be queried
+            let bonds = enriched_bonds_and_unbonds(
+                context.client(),
+                epoch,
+                &Some(owner.clone()),
+                &None,
+            )
+            .await
+            .ok();
+            watched.push(output::WatchedAccountOutput {
+                alias: alias.to_string(),
+                label: account.label,
+                address: owner,
+                balances,
+                bonds_total: bonds
+                    .as_ref()
+                    .map(|bonds| bonds.bonds_total.native_denominated()),
+                unbonds_total: bonds
+                    .as_ref()
+                    .map(|bonds| bonds.unbonds_total.native_denominated()),
+                total_withdrawable: bonds
+                    .as_ref()
+                    .map(|bonds| bonds.total_withdrawable.native_denominated()),
+            });
+        }
+        output::print_json(
+            context.io(),
+            "watched",
+            output::WatchedOutput {
+                epoch,
+                accounts: watched,
+            },
+        );
+        return;
+    }
+    if accounts.is_empty() {
+        display_line!(
+            context.io(),
+            "No watch-only accounts. Try `namadaw book watch --alias \
+             my-account --address ...` to watch an account."
+        );
+        return;
+    }
+    let epoch = query_and_print_epoch(context).await;
+    for (alias, account) in accounts {
+        let owner = account.address;
+        match account.label {
+            Some(label) => {
+                display_line!(context.io(), "{} ({}): {}", alias, label, owner)
+            }
+            None => display_line!(context.io(), "{}: {}", alias, owner),
+        }
+        let tokens = query_tokens(context, None, Some(&owner)).await;
+        for (token_alias, token) in tokens {
+            let balance =
+                get_token_balance(context.client(), &token, &owner).await;
+            if !balance.is_zero() {
+                let balance = context.format_amount(&token, balance).await;
+                display_line!(context.io(), "  {}: {}", token_alias, balance);
+            }
+        }
+        match enriched_bonds_and_unbonds(
+            context.client(),
+            epoch,
+            &Some(owner.clone()),
+            &None,
+        )
+        .await
+        {
+            Ok(details) => {
+                display_line!(
+                    context.io(),
+                    "  Bonds total: {}",
+                    details.bonds_total.to_string_native()
+                );
+                display_line!(
+                    context.io(),
+                    "  Unbonds total: {}",
+                    details.unbonds_total.to_string_native()
+                );
+                display_line!(
+                    context.io(),
+                    "  Unbonds total withdrawable: {}",
+                    details.total_withdrawable.to_string_native()
+                );
+            }
+            Err(err) => {
+                edisplay_line!(
+                    context.io(),
+                    "  Failed to query the bonds of {}: {}",
+                    alias,
+                    err
+                );
+            }
+        }
+    }
+}
+
+/// Restore the used accounts of a mnemonic code read from stdin
+pub async fn discover_accounts<N: Namada>(
+    context: &N,
+    args: args::DiscoverAccounts,
+) {
+    let mnemonic = N::WalletUtils::read_mnemonic_code().unwrap_or_else(|err| {
+        edisplay_line!(context.io(), "{}", err);
+        cli::safe_exit(1)
+    });
+    let passphrase = N::WalletUtils::read_mnemonic_passphrase(false);
+    let seed = namada_sdk::bip39::Seed::new(&mnemonic, &passphrase);
+    let password = read_and_confirm_encryption_password(
+        args.unsafe_dont_encrypt || context.wallet().await.is_store_encrypted(),
+    );
+    let mut kinds = args
+        .schemes
+        .into_iter()
+        .map(AccountKind::Transparent)
+        .collect::<Vec<_>>();
+    if args.shielded {
+        kinds.push(AccountKind::Shielded);
+    }
+    let accounts = namada_sdk::discovery::discover_accounts(
+        context,
+        &seed,
+        &kinds,
+        args.gap_limit,
+        &args.alias_prefix,
+        args.alias_force,
+        password,
+    )
+    .await
+    .unwrap_or_else(|err| {
+        edisplay_line!(context.io(), "{}", err);
+        display_line!(context.io(), "No changes are persisted. Exiting.");
+        cli::safe_exit(1)
+    });
+    if accounts.is_empty() {
         display_line!(
             context.io(),
-            &mut w;
-            "All bonds total active: {}",
-            bonds_and_unbonds.bonds_total_active().to_string_native()
-        )?;
+            "No used accounts found within the gap limit of {}.",
+            args.gap_limit
+        );
+        return;
     }
-    display_line!(
-        context.io(),
-        &mut w;
-        "All bonds total: {}",
-        bonds_and_unbonds.bonds_total.to_string_native()
-    )?;
-
-    if bonds_and_unbonds.unbonds_total
-        != bonds_and_unbonds.unbonds_total_slashed
-    {
+    context.wallet().await.save().unwrap_or_else(|err| {
+        edisplay_line!(context.io(), "{}", err);
+        cli::safe_exit(1)
+    });
+    display_line!(context.io(), "Restored {} accounts:", accounts.len());
+    for account in accounts {
         display_line!(
             context.io(),
-            &mut w;
-            "All unbonds total active: {}",
-            bonds_and_unbonds.unbonds_total_active().to_string_native()
-        )?;
+            "  \"{}\": {} account {} at {}",
+            account.alias,
+            account.kind,
+            account.index,
+            account.derivation_path
+        );
     }
-    display_line!(
-        context.io(),
-        &mut w;
-        "All unbonds total: {}",
-        bonds_and_unbonds.unbonds_total.to_string_native()
-    )?;
-    display_line!(
-        context.io(),
-        &mut w;
-        "All unbonds total withdrawable: {}",
-        bonds_and_unbonds.total_withdrawable.to_string_native()
-    )?;
-    Ok(())
 }
 
 /// Query PoS bonded stake
@@ -1755,9 +2251,54 @@ pub async fn query_bonded_stake<N: Namada>(
 ) {
     let epoch = match args.epoch {
         Some(epoch) => epoch,
-        None => query_and_print_epoch(context).await,
+        None => query_and_maybe_print_epoch(context, args.query.output).await,
     };
 
+    if args.query.output.is_json() {
+        let validators = match args.validator {
+            Some(validator) => {
+                let stake =
+                    get_validator_stake(context.client(), epoch, &validator)
+                        .await;
+                vec![output::ValidatorStakeOutput {
+                    validator,
+                    set: None,
+                    stake: stake.map(token::Amount::native_denominated),
+                }]
+            }
+            None => {
+                let (consensus, below_capacity) =
+                    query_validator_sets(context, epoch).await;
+                let consensus =
+                    consensus.into_iter().rev().map(|val| (val, "consensus"));
+                let below_capacity = below_capacity
+                    .into_iter()
+                    .rev()
+                    .map(|val| (val, "below_capacity"));
+                consensus
+                    .chain(below_capacity)
+                    .map(|(val, set)| output::ValidatorStakeOutput {
+                        validator: val.address,
+                        set: Some(set),
+                        stake: Some(val.bonded_stake.native_denominated()),
+                    })
+                    .collect()
+            }
+        };
+        let total_bonded_stake =
+            get_total_staked_tokens(context.client(), epoch).await;
+        output::print_json(
+            context.io(),
+            "bonded-stake",
+            output::BondedStakeOutput {
+                epoch,
+                validators,
+                total_bonded_stake: total_bonded_stake.native_denominated(),
+            },
+        );
+        return;
+    }
+
     match args.validator {
         Some(validator) => {
             let validator = validator;
@@ -1783,23 +2324,8 @@ pub async fn query_bonded_stake<N: Namada>(
             }
         }
         None => {
-            let consensus: BTreeSet<WeightedValidator> =
-                unwrap_client_response::<N::Client, _>(
-                    RPC.vp()
-                        .pos()
-                        .consensus_validator_set(context.client(), &Some(epoch))
-                        .await,
-                );
-            let below_capacity: BTreeSet<WeightedValidator> =
-                unwrap_client_response::<N::Client, _>(
-                    RPC.vp()
-                        .pos()
-                        .below_capacity_validator_set(
-                            context.client(),
-                            &Some(epoch),
-                        )
-                        .await,
-                );
+            let (consensus, below_capacity) =
+                query_validator_sets(context, epoch).await;
 
             // Iterate all validators
             let stdout = io::stdout();
@@ -1843,6 +2369,26 @@ pub async fn query_bonded_stake<N: Namada>(
     );
 }
 
+/// Query the consensus and below-capacity validator sets at the given epoch
+async fn query_validator_sets<N: Namada>(
+    context: &N,
+    epoch: Epoch,
+) -> (BTreeSet<WeightedValidator>, BTreeSet<WeightedValidator>) {
+    let consensus = unwrap_client_response::<N::Client, _>(
+        RPC.vp()
+            .pos()
+            .consensus_validator_set(context.client(), &Some(epoch))
+            .await,
+    );
+    let below_capacity = unwrap_client_response::<N::Client, _>(
+        RPC.vp()
+            .pos()
+            .below_capacity_validator_set(context.client(), &Some(epoch))
+            .await,
+    );
+    (consensus, below_capacity)
+}
+
 /// Query and return validator's commission rate and max commission rate change
 /// per epoch
 pub async fn query_commission_rate<
@@ -1906,6 +2452,19 @@ pub async fn query_and_print_validator_state(
     let state: Option<ValidatorState> =
         query_validator_state(context.client(), &validator, args.epoch).await;
 
+    if args.query.output.is_json() {
+        output::print_json(
+            context.io(),
+            "validator-state",
+            output::ValidatorStateOutput {
+                validator,
+                epoch: args.epoch,
+                state: state.as_ref().map(output::validator_state_name),
+            },
+        );
+        return;
+    }
+
     match state {
         Some(state) => match state {
             ValidatorState::Consensus => {
@@ -1951,6 +2510,20 @@ pub async fn query_and_print_commission_rate(
 
     let info: Option<CommissionPair> =
         query_commission_rate(context.client(), &validator, args.epoch).await;
+    if args.query.output.is_json() {
+        output::print_json(
+            context.io(),
+            "commission-rate",
+            output::CommissionRateOutput {
+                validator,
+                epoch: args.epoch,
+                commission_rate: info.as_ref().map(|info| info.commission_rate),
+                max_commission_change_per_epoch: info
+                    .map(|info| info.max_commission_change_per_epoch),
+            },
+        );
+        return;
+    }
     match info {
         Some(CommissionPair {
             commission_rate: rate,
@@ -1985,6 +2558,23 @@ pub async fn query_and_print_metadata(
     let metadata: Option<ValidatorMetaData> =
         query_metadata(context.client(), &validator).await;
 
+    if args.query.output.is_json() {
+        let info: Option<CommissionPair> =
+            query_commission_rate(context.client(), &validator, None).await;
+        output::print_json(
+            context.io(),
+            "validator-metadata",
+            output::ValidatorMetadataOutput {
+                validator,
+                metadata,
+                commission_rate: info.as_ref().map(|info| info.commission_rate),
+                max_commission_change_per_epoch: info
+                    .map(|info| info.max_commission_change_per_epoch),
+            },
+        );
+        return;
+    }
+
     match metadata {
         Some(ValidatorMetaData {
             email,
@@ -2054,6 +2644,35 @@ pub async fn query_and_print_metadata(
 
 /// Query PoS slashes
 pub async fn query_slashes<N: Namada>(context: &N, args: args::QuerySlashes) {
+    if args.query.output.is_json() {
+        let processed: HashMap<Address, Vec<Slash>> = match &args.validator {
+            Some(validator) => HashMap::from([(
+                validator.clone(),
+                unwrap_client_response::<N::Client, _>(
+                    RPC.vp()
+                        .pos()
+                        .validator_slashes(context.client(), validator)
+                        .await,
+                ),
+            )]),
+            None => unwrap_client_response::<N::Client, _>(
+                RPC.vp().pos().slashes(context.client()).await,
+            ),
+        };
+        let mut enqueued: HashMap<Address, BTreeMap<Epoch, Vec<Slash>>> =
+            unwrap_client_response::<N::Client, _>(
+                RPC.vp().pos().enqueued_slashes(context.client()).await,
+            );
+        if let Some(validator) = &args.validator {
+            enqueued.retain(|address, _| address == validator);
+        }
+        output::print_json(
+            context.io(),
+            "slashes",
+            output::SlashesOutput::new(&processed, &enqueued),
+        );
+        return;
+    }
     match args.validator {
         Some(validator) => {
             let validator = validator;
@@ -2212,6 +2831,18 @@ pub async fn query_and_print_rewards<N: Namada>(
     let (source, validator) = (args.source, args.validator);
 
     let rewards = query_rewards(context.client(), &source, &validator).await;
+    if args.query.output.is_json() {
+        output::print_json(
+            context.io(),
+            "rewards",
+            output::RewardsOutput {
+                source,
+                validator,
+                rewards: rewards.native_denominated(),
+            },
+        );
+        return;
+    }
     display_line!(
         context.io(),
         "Current rewards available for claim: {} NAM",
@@ -2230,6 +2861,16 @@ pub async fn query_delegations<N: Namada>(
             .delegation_validators(context.client(), &owner)
             .await,
     );
+    if args.query.output.is_json() {
+        let mut validators = delegations.into_iter().collect::<Vec<_>>();
+        validators.sort();
+        output::print_json(
+            context.io(),
+            "delegations",
+            output::DelegationsOutput { owner, validators },
+        );
+        return;
+    }
     if delegations.is_empty() {
         display_line!(context.io(), "No delegations found");
     } else {
@@ -2244,7 +2885,7 @@ pub async fn query_find_validator<N: Namada>(
     context: &N,
     args: args::QueryFindValidator,
 ) {
-    let args::QueryFindValidator { query: _, tm_addr } = args;
+    let args::QueryFindValidator { query, tm_addr } = args;
     if tm_addr.len() != 40 {
         edisplay_line!(
             context.io(),
@@ -2260,6 +2901,17 @@ pub async fn query_find_validator<N: Namada>(
             .validator_by_tm_addr(context.client(), &tm_addr)
             .await,
     );
+    if query.output.is_json() {
+        output::print_json(
+            context.io(),
+            "find-validator",
+            output::FindValidatorOutput {
+                tm_address: tm_addr,
+                validator,
+            },
+        );
+        return;
+    }
     match validator {
         Some(address) => {
             display_line!(
@@ -2351,6 +3003,283 @@ pub async fn known_address<C: namada::ledger::queries::Client + Sync>(
         .unwrap()
 }
 
+/// Sync the shielded context with the ledger, or with a MASP indexer if one
+/// is given, and save it
+pub async fn shielded_sync(context: &impl Namada, args: args::ShieldedSync) {
+    let fvks: Vec<_> = match args.viewing_key {
+        Some(viewing_key) => vec![viewing_key],
+        None => context
+            .wallet()
+            .await
+            .get_viewing_keys()
+            .values()
+            .copied()
+            .collect(),
+    }
+    .into_iter()
+    .map(|fvk| ExtendedFullViewingKey::from(fvk).fvk.vk)
+    .collect();
+    let mut shielded = context.shielded_mut().await;
+    load_shielded(context, &mut shielded).await;
+    let fetched = match &args.masp_indexer {
+        Some(url) => {
+            let source = IndexerClient::new(url);
+            shielded
+                .fetch_from(context.client(), context.io(), &source, &[], &fvks)
+                .await
+        }
+        None => {
+            shielded
+                .fetch(context.client(), context.io(), &[], &fvks)
+                .await
+        }
+    };
+    if let Err(err) = fetched {
+        edisplay_line!(
+            context.io(),
+            "Failed to sync the shielded context: {}",
+            err
+        );
+        cli::safe_exit(1)
+    }
+    if let Err(err) = shielded.save().await {
+        edisplay_line!(
+            context.io(),
+            "Failed to save the shielded context: {}",
+            err
+        );
+        cli::safe_exit(1)
+    }
+    display_line!(
+        context.io(),
+        "The shielded context is synced up to shielded transaction {}",
+        shielded.last_txidx
+    );
+}
+
+/// Query the notes received by the given viewing key, or by all the viewing
+/// keys in the wallet, and print them along with their memos.
+pub async fn query_shielded_notes(
+    context: &impl Namada,
+    args: args::QueryShieldedNotes,
+) {
+    let viewing_keys: Vec<_> = match args.viewing_key {
+        Some(viewing_key) => vec![viewing_key],
+        None => context
+            .wallet()
+            .await
+            .get_viewing_keys()
+            .values()
+            .copied()
+            .collect(),
+    };
+    let fvks: Vec<_> = viewing_keys
+        .iter()
+        .map(|fvk| ExtendedFullViewingKey::from(*fvk).fvk.vk)
+        .collect();
+    let io = output::ProgressIo::new(context.io(), args.query.output);
+    let mut shielded = context.shielded_mut().await;
+    load_shielded(context, &mut shielded).await;
+    shielded
+        .fetch(context.client(), &io, &[], &fvks)
+        .await
+        .unwrap();
+    // Save the update state so that future fetches can be short-circuited
+    let _ = shielded.save().await;
+
+    let mut notes_output = vec![];
+    for (viewing_key, vk) in viewing_keys.iter().zip(fvks.iter()) {
+        let notes = shielded
+            .compute_received_notes(context.client(), vk)
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        if args.query.output.is_json() {
+            let mut viewing_key_notes = output::ViewingKeyNotes {
+                viewing_key: viewing_key.to_string(),
+                notes: vec![],
+            };
+            for note in notes {
+                let token_alias =
+                    context.wallet().await.lookup_alias(&note.token);
+                viewing_key_notes.notes.push(output::NoteOutput {
+                    pos: note.pos,
+                    token_alias,
+                    epoch: note.epoch,
+                    amount: rpc::denominate_amount(
+                        context.client(),
+                        &io,
+                        &note.token,
+                        note.amount,
+                    )
+                    .await,
+                    spent: note.spent,
+                    memo: decode_memo(&note.memo),
+                    token: note.token,
+                });
+            }
+            notes_output.push(viewing_key_notes);
+            continue;
+        }
+        display_line!(context.io(), "Notes received by {}:", viewing_key);
+        if notes.is_empty() {
+            display_line!(context.io(), "  No notes found");
+        }
+        for note in notes {
+            let token_alias = context.wallet().await.lookup_alias(&note.token);
+            display_line!(
+                context.io(),
+                "  #{} {}: {} (epoch {}){}",
+                note.pos,
+                token_alias,
+                context.format_amount(&note.token, note.amount).await,
+                note.epoch,
+                if note.spent { ", spent" } else { "" },
+            );
+            if let Some(memo) = decode_memo(&note.memo) {
+                display_line!(context.io(), "    memo: {}", memo);
+            }
+        }
+    }
+    if args.query.output.is_json() {
+        output::print_json(context.io(), "shielded-notes", notes_output);
+    }
+}
+
+/// Query the notes received and spent by the given viewing key, or by all the
+/// viewing keys in the wallet, and either print them or export them to a file
+pub async fn query_shielded_history(
+    context: &impl Namada,
+    args: args::QueryShieldedHistory,
+) {
+    let viewing_keys: Vec<_> = match args.viewing_key {
+        Some(viewing_key) => vec![viewing_key],
+        None => context
+            .wallet()
+            .await
+            .get_viewing_keys()
+            .values()
+            .copied()
+            .collect(),
+    };
+    let fvks: Vec<_> = viewing_keys
+        .iter()
+        .map(|fvk| ExtendedFullViewingKey::from(*fvk).fvk.vk)
+        .collect();
+    let io = output::ProgressIo::new(context.io(), args.query.output);
+    let mut shielded = context.shielded_mut().await;
+    load_shielded(context, &mut shielded).await;
+    shielded
+        .fetch(context.client(), &io, &[], &fvks)
+        .await
+        .unwrap();
+    // Save the update state so that future fetches can be short-circuited
+    let _ = shielded.save().await;
+
+    let mut histories = BTreeMap::new();
+    for (viewing_key, vk) in viewing_keys.iter().zip(fvks.iter()) {
+        let history = shielded
+            .compute_history(context.client(), vk)
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        histories.insert(viewing_key.to_string(), history);
+    }
+
+    let Some(path) = args.export else {
+        if args.query.output.is_json() {
+            output::print_json(context.io(), "shielded-history", histories);
+            return;
+        }
+        for (viewing_key, history) in histories {
+            display_line!(context.io(), "History of {}:", viewing_key);
+            if history.is_empty() {
+                display_line!(context.io(), "  No shielded transactions found");
+            }
+            for entry in history {
+                let token_alias =
+                    context.wallet().await.lookup_alias(&entry.token);
+                display_line!(
+                    context.io(),
+                    "  Height {}, tx {}, epoch {}: {} {} {} (note #{}, asset \
+                     epoch {})",
+                    entry.height,
+                    entry.index.0,
+                    entry.epoch,
+                    entry.direction,
+                    context.format_amount(&entry.token, entry.amount).await,
+                    token_alias,
+                    entry.note_pos,
+                    entry.asset_epoch,
+                );
+                if let Some(hash) = entry.tx_hash {
+                    display_line!(context.io(), "    tx hash: {}", hash);
+                }
+                if let Some(counterpart) = &entry.counterpart {
+                    let (direction, preposition) = match counterpart.kind {
+                        TransparentKind::Shielding => ("shielded", "from"),
+                        TransparentKind::Unshielding => ("unshielded", "to"),
+                    };
+                    display_line!(
+                        context.io(),
+                        "    {} {} {} {}",
+                        direction,
+                        counterpart.amount,
+                        preposition,
+                        context
+                            .wallet()
+                            .await
+                            .lookup_alias(&counterpart.address),
+                    );
+                }
+                for conversion in &entry.conversions {
+                    display_line!(
+                        context.io(),
+                        "    converted {} from epoch {} to epoch {}",
+                        context.wallet().await.lookup_alias(&conversion.token),
+                        conversion.from_epoch,
+                        conversion.to_epoch,
+                    );
+                }
+                if let Some(memo) = &entry.memo {
+                    display_line!(context.io(), "    memo: {}", memo);
+                }
+            }
+        }
+        return;
+    };
+
+    let contents = match args.format {
+        args::HistoryFormat::Json => {
+            serde_json::to_string_pretty(&histories).unwrap()
+        }
+        args::HistoryFormat::Csv => {
+            let mut contents = format!("viewing_key,{HISTORY_CSV_HEADER}\n");
+            for (viewing_key, history) in &histories {
+                for entry in history {
+                    contents +=
+                        &format!("{},{}\n", viewing_key, entry.to_csv_record());
+                }
+            }
+            contents
+        }
+    };
+    if let Err(err) = fs::write(&path, contents) {
+        edisplay_line!(
+            context.io(),
+            "Failed to write the history to {}: {}",
+            path.to_string_lossy(),
+            err
+        );
+        cli::safe_exit(1);
+    }
+    display_line!(
+        context.io(),
+        "Exported the shielded history to {}",
+        path.to_string_lossy()
+    );
+}
+
 /// Query for all conversions.
 pub async fn query_conversions(
     context: &impl Namada,
@@ -2366,6 +3295,37 @@ pub async fn query_conversions(
     let conversions = rpc::query_conversions(context.client())
         .await
         .expect("Conversions should be defined");
+    if args.query.output.is_json() {
+        let wallet = context.wallet().await;
+        let asset = |addr: &Address, epoch: &Epoch| output::ConversionAsset {
+            token: addr.clone(),
+            token_alias: wallet.lookup_alias(addr),
+            epoch: *epoch,
+        };
+        let conversions = conversions
+            .values()
+            .filter(|(addr, epoch, amt)| {
+                !matches!(&target_token, Some(target) if target != addr)
+                    && !matches!(&args.epoch, Some(target) if target != epoch)
+                    && !amt.is_zero()
+            })
+            .map(|(addr, epoch, amt)| output::ConversionOutput {
+                asset: asset(addr, epoch),
+                components: amt
+                    .components()
+                    .map(|(asset_type, val)| {
+                        let (addr, epoch, _) = &conversions[asset_type];
+                        output::ConversionComponent {
+                            asset: asset(addr, epoch),
+                            value: val.to_string(),
+                        }
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+        output::print_json(context.io(), "conversions", conversions);
+        return;
+    }
     // Track whether any non-sentinel conversions are found
     let mut conversions_found = false;
     for (addr, epoch, amt) in conversions.values() {
@@ -2517,6 +3477,14 @@ pub async fn query_result(context: &impl Namada, args: args::QueryResult) {
     )
     .await;
     match tx_response {
+        Ok(response) if args.query.output.is_json() => output::print_json(
+            context.io(),
+            "tx-result",
+            output::TxResultOutput {
+                status: "applied",
+                response,
+            },
+        ),
         Ok(result) => {
             display_line!(
                 context.io(),
@@ -2532,6 +3500,16 @@ pub async fn query_result(context: &impl Namada, args: args::QueryResult) {
             )
             .await;
             match tx_response {
+                Ok(response) if args.query.output.is_json() => {
+                    output::print_json(
+                        context.io(),
+                        "tx-result",
+                        output::TxResultOutput {
+                            status: "accepted",
+                            response,
+                        },
+                    )
+                }
                 Ok(result) => display_line!(
                     context.io(),
                     "Transaction was accepted with result: {}",
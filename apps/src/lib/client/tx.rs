@@ -4,7 +4,9 @@ use std::io::Write;
 
 use borsh::BorshDeserialize;
 use borsh_ext::BorshSerializeExt;
+use itertools::Itertools;
 use ledger_namada_rs::{BIP44Path, NamadaApp};
+use ledger_transport::Exchange;
 use ledger_transport_hid::hidapi::HidApi;
 use ledger_transport_hid::TransportNativeHID;
 use namada::core::ledger::governance::cli::offline::{
@@ -19,16 +21,24 @@ use namada::types::address::{Address, ImplicitAddress};
 use namada::types::dec::Dec;
 use namada::types::io::Io;
 use namada::types::key::{self, *};
+use namada::types::masp::ExtendedViewingKey;
+use namada::types::storage::Epoch;
 use namada::types::transaction::pos::{BecomeValidator, ConsensusKeyChange};
+use namada_sdk::bundle::TxBundle;
 use namada_sdk::rpc::{TxBroadcastData, TxResponse};
+#[cfg(unix)]
+use namada_sdk::signer::{with_signer, RemoteSigner};
 use namada_sdk::wallet::alias::validator_consensus_key;
 use namada_sdk::wallet::{Wallet, WalletIo};
-use namada_sdk::{display_line, edisplay_line, error, signing, tx, Namada};
+use namada_sdk::{
+    display, display_line, edisplay_line, error, signing, tx, Namada,
+};
 use rand::rngs::OsRng;
 use tokio::sync::RwLock;
 
 use super::rpc;
 use crate::cli::{args, safe_exit};
+use crate::client::masp_device::{self, MaspDevice};
 use crate::client::rpc::query_wasm_code_hash;
 use crate::client::tx::signing::{
     default_sign, init_validator_signing_data, SigningTxData,
@@ -153,13 +163,67 @@ pub async fn with_hardware_wallet<'a, U: WalletIo + Clone>(
     Ok(tx)
 }
 
-// Sign the given transaction using a hardware wallet as a backup
+/// Env. var to sign transactions without asking for confirmation, the same
+/// as passing `--no-confirm`
+pub const ENV_VAR_SIGN_WITHOUT_CONFIRMATION: &str =
+    "NAMADA_SIGN_WITHOUT_CONFIRMATION";
+
+fn sign_without_confirmation() -> bool {
+    match std::env::var(ENV_VAR_SIGN_WITHOUT_CONFIRMATION) {
+        Ok(val) => val.to_ascii_lowercase() != "false",
+        _ => false,
+    }
+}
+
+/// Ask the user whether the described transaction should be signed
+async fn confirm_signing<N: Namada>(context: &N) -> Result<(), error::Error> {
+    let io = context.io();
+    display!(io, "\nDo you want to sign this transaction? (y/n): ");
+    io.flush();
+    loop {
+        let resp = io.read().await.map_err(|e| {
+            error::Error::Other(format!(
+                "Encountered error reading from STDIN: {e:?}"
+            ))
+        })?;
+        match resp.trim() {
+            "y" => return Ok(()),
+            "n" => {
+                return Err(error::Error::Other(
+                    "Aborted signing the transaction".into(),
+                ));
+            }
+            _ => {
+                display!(io, "Expected 'y' or 'n'. Please try again: ");
+                io.flush();
+            }
+        }
+    }
+}
+
+// Sign the given transaction using a hardware wallet or a remote signer as a
+// backup
 pub async fn sign<N: Namada>(
     context: &N,
     tx: &mut Tx,
     args: &args::Tx,
     signing_data: SigningTxData,
 ) -> Result<(), error::Error> {
+    // Show the user what they are about to sign
+    match signing::description::describe_tx(context, tx).await {
+        Ok(description) => {
+            display_line!(context.io(), "Signing transaction:\n{}", description)
+        }
+        Err(err) => edisplay_line!(
+            context.io(),
+            "Unable to describe the transaction to sign: {}",
+            err
+        ),
+    }
+    // The hardware wallet asks for its own confirmation
+    if !args.no_confirm && !args.use_device && !sign_without_confirmation() {
+        confirm_signing(context).await?;
+    }
     // Setup a reusable context for signing transactions using the Ledger
     if args.use_device {
         // Setup a reusable context for signing transactions using the Ledger
@@ -185,6 +249,26 @@ pub async fn sign<N: Namada>(
                 with_hw_data,
             )
             .await?;
+    } else if let Some(socket) = &args.remote_signer {
+        // Sign with the keys of the signing daemon that are not in the wallet
+        #[cfg(unix)]
+        {
+            let remote_signer = RemoteSigner::new(socket);
+            context
+                .sign(
+                    tx,
+                    args,
+                    signing_data,
+                    with_signer::<RemoteSigner>,
+                    &remote_signer,
+                )
+                .await?;
+        }
+        #[cfg(not(unix))]
+        return Err(error::Error::Other(format!(
+            "Signing daemons are only supported on Unix, unable to reach {}",
+            socket.display()
+        )));
     } else {
         // Otherwise sign without a backup procedure
         context
@@ -258,6 +342,28 @@ pub async fn submit_bridge_pool_tx<N: Namada>(
     Ok(())
 }
 
+pub async fn submit_bump_bridge_pool_fee<N: Namada>(
+    namada: &N,
+    args: args::BumpBridgePoolFee,
+) -> Result<(), error::Error> {
+    let tx_args = args.tx.clone();
+    let (mut tx, signing_data, _epoch) = args.build(namada).await?;
+
+    signing::generate_test_vector(namada, &tx).await?;
+
+    if tx_args.dump_tx {
+        tx::dump_tx(namada.io(), &tx_args, tx);
+    } else {
+        sign(namada, &mut tx, &tx_args, signing_data).await?;
+
+        signing::generate_test_vector(namada, &tx).await?;
+
+        namada.submit(tx, &tx_args).await?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_custom<N: Namada>(
     namada: &N,
     args: args::TxCustom,
@@ -435,14 +541,7 @@ pub async fn submit_change_consensus_key(
     .add_data(data);
     let signing_data = aux_signing_data(namada, &tx_args, None, None).await?;
 
-    tx::prepare_tx(
-        namada,
-        &tx_args,
-        &mut tx,
-        signing_data.fee_payer.clone(),
-        None,
-    )
-    .await?;
+    tx::prepare_tx(namada, &tx_args, &mut tx, &signing_data, None).await?;
 
     signing::generate_test_vector(namada, &tx).await?;
 
@@ -695,7 +794,9 @@ pub async fn submit_become_validator(
         scheme,
     )
     .unwrap();
-    let protocol_sk = validator_keys.get_protocol_keypair();
+    let protocol_sk = validator_keys
+        .get_protocol_keypair()
+        .expect("Generated validator keys always hold a protocol key");
     let protocol_key = protocol_sk.to_public();
 
     // Store the protocol key in the wallet so that we can sign the tx with it
@@ -764,14 +865,7 @@ pub async fn submit_become_validator(
     let signing_data =
         init_validator_signing_data(namada, &tx_args, all_pks).await?;
 
-    tx::prepare_tx(
-        namada,
-        &tx_args,
-        &mut tx,
-        signing_data.fee_payer.clone(),
-        None,
-    )
-    .await?;
+    tx::prepare_tx(namada, &tx_args, &mut tx, &signing_data, None).await?;
 
     signing::generate_test_vector(namada, &tx).await?;
 
@@ -932,6 +1026,36 @@ pub async fn submit_transfer(
     namada: &impl Namada,
     args: args::TxTransfer,
 ) -> Result<(), error::Error> {
+    if args.device_source.is_none() {
+        return submit_transfer_with_device(
+            namada,
+            args,
+            None::<&MaspDevice<TransportNativeHID>>,
+        )
+        .await;
+    }
+    // The spends of the shielded source are authorized by the hardware wallet
+    let hidapi = HidApi::new().map_err(|err| {
+        error::Error::Other(format!("Failed to create Hidapi: {}", err))
+    })?;
+    let transport = TransportNativeHID::new(&hidapi).map_err(|err| {
+        error::Error::Other(format!("Unable to connect to Ledger: {}", err))
+    })?;
+    submit_transfer_with_device(namada, args, Some(&MaspDevice::new(transport)))
+        .await
+}
+
+/// Submit a transfer, having the given device authorize the spends of its
+/// shielded source if the source is held by a hardware wallet
+pub async fn submit_transfer_with_device<E>(
+    namada: &impl Namada,
+    args: args::TxTransfer,
+    device: Option<&MaspDevice<E>>,
+) -> Result<(), error::Error>
+where
+    E: Exchange + Send + Sync,
+    E::Error: std::fmt::Display,
+{
     for _ in 0..2 {
         submit_reveal_aux(
             namada,
@@ -941,7 +1065,18 @@ pub async fn submit_transfer(
         .await?;
 
         let (mut tx, signing_data, tx_epoch) =
-            args.clone().build(namada).await?;
+            match (&args.device_source, device) {
+                (Some(viewing_key), Some(device)) => {
+                    build_device_transfer(
+                        namada,
+                        args.clone(),
+                        viewing_key.clone(),
+                        device,
+                    )
+                    .await?
+                }
+                _ => args.clone().build(namada).await?,
+            };
         signing::generate_test_vector(namada, &tx).await?;
 
         if args.tx.dump_tx {
@@ -982,6 +1117,42 @@ pub async fn submit_transfer(
     Ok(())
 }
 
+/// Build a transfer spending the notes of the given viewing key, whose spends
+/// are authorized by the device
+async fn build_device_transfer<E>(
+    namada: &impl Namada,
+    mut args: args::TxTransfer,
+    viewing_key: ExtendedViewingKey,
+    device: &MaspDevice<E>,
+) -> Result<(Tx, SigningTxData, Option<Epoch>), error::Error>
+where
+    E: Exchange + Send + Sync,
+    E::Error: std::fmt::Display,
+{
+    let amount = namada_sdk::rpc::validate_amount(
+        namada,
+        args.amount,
+        &args.token,
+        args.tx.force,
+    )
+    .await?;
+    args.amount = args::InputAmount::Validated(amount);
+    let shielded_parts = device
+        .gen_shielded_transfer(
+            namada,
+            masp_device::DEFAULT_ACCOUNT,
+            viewing_key.into(),
+            &args.target,
+            &args.token,
+            amount,
+            args.memo.clone(),
+        )
+        .await
+        .map_err(|err| error::Error::Other(err.to_string()))?;
+    tx::build_external_shielded_transfer(namada, &mut args, shielded_parts)
+        .await
+}
+
 pub async fn submit_ibc_transfer<N: Namada>(
     namada: &N,
     args: args::TxIbcTransfer,
@@ -1308,6 +1479,182 @@ where
     Ok(())
 }
 
+/// Create a partially signed bundle of a dumped transaction, with the public
+/// keys and threshold of its owner.
+pub async fn bundle_create<N: Namada>(
+    namada: &N,
+    args::BundleCreate {
+        tx: tx_args,
+        tx_data,
+        owner,
+    }: args::BundleCreate,
+) -> Result<(), error::Error> {
+    let tx = Tx::deserialize(tx_data.as_ref()).unwrap_or_else(|_| {
+        edisplay_line!(namada.io(), "Couldn't decode the transaction.");
+        safe_exit(1)
+    });
+    // The fees are paid by the key the transaction was wrapped with
+    let fee_payer = tx
+        .header()
+        .wrapper()
+        .ok_or_else(|| {
+            error::Error::Other(
+                "The transaction is not wrapped for fee payment.".to_string(),
+            )
+        })?
+        .pk;
+    let tx_args = args::Tx {
+        wrapper_fee_payer: Some(fee_payer),
+        disposable_signing_key: false,
+        ..tx_args
+    };
+    let signing_data = signing::aux_signing_data(
+        namada,
+        &tx_args,
+        Some(owner.clone()),
+        Some(owner),
+    )
+    .await?;
+    let bundle = TxBundle::new(&tx, &signing_data)
+        .map_err(|err| error::Error::Other(err.to_string()))?;
+    let filename = format!("{}.bundle", tx.header_hash());
+    let output_path = match &tx_args.output_folder {
+        Some(path) => path.join(filename),
+        None => filename.into(),
+    };
+    write_bundle(namada.io(), &output_path, &bundle);
+    display_line!(
+        namada.io(),
+        "Transaction bundle requiring {} of {} signatures written to {}",
+        bundle.threshold,
+        bundle.public_keys.len(),
+        output_path.display()
+    );
+    Ok(())
+}
+
+/// Sign a transaction bundle with the keys of the wallet. This doesn't need
+/// a connection to a node.
+pub fn bundle_sign<U: WalletIo>(
+    io: &impl Io,
+    wallet: &mut Wallet<U>,
+    args::BundleSign { bundle: path }: args::BundleSign,
+) {
+    let mut bundle = read_bundle(io, &path);
+    let signers = bundle.sign_with_wallet(wallet).unwrap_or_else(|err| {
+        edisplay_line!(io, "{}", err);
+        safe_exit(1)
+    });
+    if signers.is_empty() && !bundle.sealed {
+        edisplay_line!(
+            io,
+            "The wallet holds none of the keys missing from the bundle."
+        );
+        safe_exit(1)
+    }
+    write_bundle(io, &path, &bundle);
+    for signer in signers {
+        display_line!(io, "Signed the transaction with {}", signer);
+    }
+    display_bundle_status(io, &bundle);
+}
+
+/// Merge the signatures of bundles of the same transaction.
+pub fn bundle_merge(
+    io: &impl Io,
+    args::BundleMerge { bundles, output }: args::BundleMerge,
+) {
+    let mut paths = bundles.into_iter();
+    let mut bundle = match paths.next() {
+        Some(path) => read_bundle(io, &path),
+        None => {
+            edisplay_line!(io, "No bundles to merge.");
+            safe_exit(1)
+        }
+    };
+    for path in paths {
+        bundle.merge(read_bundle(io, &path)).unwrap_or_else(|err| {
+            edisplay_line!(io, "{}: {}", path.display(), err);
+            safe_exit(1)
+        });
+    }
+    write_bundle(io, &output, &bundle);
+    display_line!(io, "Merged bundle written to {}", output.display());
+    display_bundle_status(io, &bundle);
+}
+
+/// Submit the transaction of a bundle once its threshold is met. The bundle
+/// is sealed with the fee payer key of the wallet if it isn't yet.
+pub async fn bundle_submit<N: Namada>(
+    namada: &N,
+    args: args::BundleSubmit,
+) -> Result<(), error::Error> {
+    let mut bundle = read_bundle(namada.io(), &args.bundle);
+    if !bundle.is_threshold_met() {
+        display_bundle_status(namada.io(), &bundle);
+        return Err(error::Error::Other(format!(
+            "The bundle has {} signatures out of the {} required",
+            bundle.signatures.len(),
+            bundle.threshold
+        )));
+    }
+    if !bundle.sealed {
+        let fee_payer_key = signing::find_key_by_pk(
+            &mut *namada.wallet_mut().await,
+            &args.tx,
+            &bundle.fee_payer,
+        )?;
+        bundle
+            .seal(fee_payer_key)
+            .map_err(|err| error::Error::Other(err.to_string()))?;
+    }
+    let tx = bundle
+        .tx()
+        .map_err(|err| error::Error::Other(err.to_string()))?;
+    namada.submit(tx, &args.tx).await?;
+    Ok(())
+}
+
+fn read_bundle(io: &impl Io, path: &std::path::Path) -> TxBundle {
+    let json = std::fs::read_to_string(path).unwrap_or_else(|err| {
+        edisplay_line!(io, "Couldn't read {}: {}", path.display(), err);
+        safe_exit(1)
+    });
+    TxBundle::from_json(&json).unwrap_or_else(|err| {
+        edisplay_line!(io, "{}: {}", path.display(), err);
+        safe_exit(1)
+    })
+}
+
+fn write_bundle(io: &impl Io, path: &std::path::Path, bundle: &TxBundle) {
+    std::fs::write(path, bundle.to_json()).unwrap_or_else(|err| {
+        edisplay_line!(io, "Couldn't write {}: {}", path.display(), err);
+        safe_exit(1)
+    });
+}
+
+fn display_bundle_status(io: &impl Io, bundle: &TxBundle) {
+    if bundle.sealed {
+        display_line!(io, "The bundle is sealed and ready for submission.");
+    } else if bundle.is_threshold_met() {
+        display_line!(
+            io,
+            "The threshold is met. The bundle needs the signature of the fee \
+             payer {}.",
+            bundle.fee_payer
+        );
+    } else {
+        display_line!(
+            io,
+            "The bundle has {} signatures out of the {} required. Missing \
+             signers: {}",
+            bundle.signatures.len(),
+            bundle.threshold,
+            bundle.missing_signers().iter().join(", ")
+        );
+    }
+}
+
 pub async fn submit_reveal_pk<N: Namada>(
     namada: &N,
     args: args::RevealPk,
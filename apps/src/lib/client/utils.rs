@@ -263,14 +263,22 @@ pub async fn join_network(
 
     // Try to load pre-genesis wallet, if any
     let pre_genesis_wallet_path = base_dir.join(PRE_GENESIS_DIR);
-    let pre_genesis_wallet =
-        if let Some(wallet) = crate::wallet::load(&pre_genesis_wallet_path) {
-            Some(wallet)
-        } else {
+    let load_pre_genesis_wallet = |path: &Path| {
+        crate::wallet::load_if_exists(path).unwrap_or_else(|err| {
+            eprintln!(
+                "Unable to load the pre-genesis wallet at {}: {}",
+                path.to_string_lossy(),
+                err
+            );
+            safe_exit(1)
+        })
+    };
+    let pre_genesis_wallet = load_pre_genesis_wallet(&pre_genesis_wallet_path)
+        .or_else(|| {
             validator_alias_and_dir
                 .as_ref()
-                .and_then(|(_, path)| crate::wallet::load(path))
-        };
+                .and_then(|(_, path)| load_pre_genesis_wallet(path))
+        });
 
     // Derive wallet from genesis
     let wallet = genesis.derive_wallet(
@@ -900,10 +908,13 @@ pub fn try_load_pre_genesis_wallet(
 ) -> Option<(Wallet<CliWalletUtils>, PathBuf)> {
     let pre_genesis_dir = base_dir.join(PRE_GENESIS_DIR);
 
-    crate::wallet::load(&pre_genesis_dir).map(|wallet| {
-        let wallet_file = crate::wallet::wallet_file(&pre_genesis_dir);
-        (wallet, wallet_file)
-    })
+    let wallet = crate::wallet::load_if_exists(&pre_genesis_dir)
+        .unwrap_or_else(|err| {
+            eprintln!("Unable to load the pre-genesis wallet: {}", err);
+            safe_exit(1)
+        })?;
+    let wallet_file = crate::wallet::wallet_file(&pre_genesis_dir);
+    Some((wallet, wallet_file))
 }
 
 /// Try to load a pre-genesis wallet or terminate if it cannot be found.
@@ -10,6 +10,10 @@ pub const DEFAULT_ORACLE_RPC_ENDPOINT: &str = "http://127.0.0.1:8545";
 /// the oracle and the shell can hold.
 pub const ORACLE_CHANNEL_BUFFER_SIZE: usize = 1000;
 
+/// The default number of Ethereum JSON-RPC endpoints that must agree on
+/// an event before the oracle forwards it to the ledger.
+pub const DEFAULT_ORACLE_RPC_QUORUM: usize = 1;
+
 /// The mode in which to run the Ethereum bridge.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Mode {
@@ -33,6 +37,16 @@ pub struct Config {
     /// The Ethereum JSON-RPC endpoint that the Ethereum event oracle will use
     /// to listen for events from the Ethereum bridge smart contracts
     pub oracle_rpc_endpoint: String,
+    /// Additional Ethereum JSON-RPC endpoints, queried alongside
+    /// `oracle_rpc_endpoint`, preferably served by other providers
+    #[serde(default)]
+    pub oracle_rpc_extra_endpoints: Vec<String>,
+    /// The number of Ethereum JSON-RPC endpoints that must report an
+    /// event at the required number of confirmations before the oracle
+    /// forwards it to the ledger. It must be between 1 and the number of
+    /// endpoints, otherwise the oracle refuses to start. The default is 1.
+    #[serde(default = "default_oracle_rpc_quorum")]
+    pub oracle_rpc_quorum: usize,
     /// The size of bounded channel between the Ethereum oracle and main
     /// ledger subprocesses. This is the number of Ethereum events that
     /// can be held in the channel. The default is 1000.
@@ -44,7 +58,22 @@ impl Default for Config {
         Self {
             mode: Mode::RemoteEndpoint,
             oracle_rpc_endpoint: DEFAULT_ORACLE_RPC_ENDPOINT.to_owned(),
+            oracle_rpc_extra_endpoints: vec![],
+            oracle_rpc_quorum: DEFAULT_ORACLE_RPC_QUORUM,
             channel_buffer_size: ORACLE_CHANNEL_BUFFER_SIZE,
         }
     }
 }
+
+impl Config {
+    /// All the Ethereum JSON-RPC endpoints the oracle will listen to.
+    pub fn oracle_rpc_endpoints(&self) -> Vec<String> {
+        std::iter::once(self.oracle_rpc_endpoint.clone())
+            .chain(self.oracle_rpc_extra_endpoints.iter().cloned())
+            .collect()
+    }
+}
+
+fn default_oracle_rpc_quorum() -> usize {
+    DEFAULT_ORACLE_RPC_QUORUM
+}
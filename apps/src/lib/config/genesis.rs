@@ -349,6 +349,7 @@ pub fn make_dev_genesis(
     // Add Ethereum bridge params.
     genesis.parameters.eth_bridge_params = Some(templates::EthBridgeParams {
         eth_start_height: Default::default(),
+        bridge_pool_expiry: None,
         min_confirmations: Default::default(),
         contracts: Contracts {
             native_erc20: wnam(),
@@ -406,6 +406,7 @@ impl Finalized {
     ) -> Option<namada::ledger::eth_bridge::EthereumBridgeParams> {
         if let Some(templates::EthBridgeParams {
             eth_start_height,
+            bridge_pool_expiry,
             min_confirmations,
             contracts,
             erc20_whitelist,
@@ -413,6 +414,7 @@ impl Finalized {
         {
             Some(namada::ledger::eth_bridge::EthereumBridgeParams {
                 eth_start_height,
+                bridge_pool_expiry,
                 min_confirmations,
                 erc20_whitelist,
                 contracts,
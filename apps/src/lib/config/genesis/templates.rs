@@ -2,6 +2,7 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::marker::PhantomData;
+use std::num::NonZeroU64;
 use std::path::Path;
 
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -477,6 +478,16 @@ pub struct PgfParams<T: TemplateValidation> {
 pub struct EthBridgeParams {
     /// Initial Ethereum block height when events will first be extracted from.
     pub eth_start_height: ethereum_structs::BlockHeight,
+    /// Number of epochs after which transfers sitting in the Bridge
+    /// pool expire. If unset, transfers never expire.
+    ///
+    /// Expired transfers are only refunded once the Bridge pool nonce
+    /// is incremented, as they may still be relayed until then. Since
+    /// the nonce only increases when a batch of transfers is relayed to
+    /// Ethereum, expired transfers are not refunded while the bridge
+    /// is idle.
+    #[serde(default)]
+    pub bridge_pool_expiry: Option<NonZeroU64>,
     /// Minimum number of confirmations needed to trust an Ethereum branch.
     /// This must be at least one.
     pub min_confirmations: MinimumConfirmations,
@@ -70,6 +70,7 @@ fn get_tx_args(use_device: bool) -> TxArgs {
         dump_tx: false,
         output_folder: None,
         force: false,
+        no_confirm: false,
         broadcast_only: false,
         ledger_address: (),
         initialized_account_alias: None,
@@ -79,6 +80,7 @@ fn get_tx_args(use_device: bool) -> TxArgs {
         fee_token: nam(),
         fee_unshield: None,
         gas_limit: Default::default(),
+        estimate_gas: false,
         expiration: None,
         disposable_signing_key: false,
         chain_id: None,
@@ -87,6 +89,7 @@ fn get_tx_args(use_device: bool) -> TxArgs {
         tx_reveal_code_path: Default::default(),
         password: None,
         use_device,
+        remote_signer: None,
     }
 }
 
@@ -278,11 +281,7 @@ pub fn init_validator(
             validator_wallet.consensus_key.ref_to(),
         ),
         protocol_key: StringEncoded::new(
-            validator_wallet
-                .store
-                .validator_keys
-                .protocol_keypair
-                .ref_to(),
+            validator_wallet.protocol_key.ref_to(),
         ),
         tendermint_node_key: StringEncoded::new(
             validator_wallet.tendermint_node_key.ref_to(),
@@ -355,10 +354,8 @@ pub async fn sign_validator_account_tx(
             // Sign the tx with every validator key to authorize their usage
             let consensus_key_sig =
                 sign_key(&unsigned_tx, &validator_wallet.consensus_key);
-            let protocol_key_sig = sign_key(
-                &unsigned_tx,
-                &validator_wallet.store.validator_keys.protocol_keypair,
-            );
+            let protocol_key_sig =
+                sign_key(&unsigned_tx, &validator_wallet.protocol_key);
             let eth_hot_key_sig =
                 sign_key(&unsigned_tx, &validator_wallet.eth_hot_key);
             let eth_cold_key_sig =
@@ -46,6 +46,19 @@ pub struct Config {
 pub struct ValidatorLocalConfig {
     pub accepted_gas_tokens:
         HashMap<namada::types::address::Address, namada::types::token::Amount>,
+    /// The socket of a signing daemon holding the validator's protocol key.
+    /// When set, the protocol key of the wallet is not used, and it may be
+    /// absent from the wallet.
+    #[serde(default)]
+    pub protocol_key_signer: Option<PathBuf>,
+    /// How long to wait for the signing daemon, in milliseconds, before
+    /// leaving a vote extension or protocol tx unsigned
+    #[serde(default = "default_protocol_key_signer_timeout_ms")]
+    pub protocol_key_signer_timeout_ms: u64,
+}
+
+fn default_protocol_key_signer_timeout_ms() -> u64 {
+    1000
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -0,0 +1,450 @@
+//! The MASP indexer tails a ledger node and extracts its shielded
+//! transactions into a stream of [`CompactBlock`]s, which it serves over HTTP.
+//! Light clients can then sync their shielded context from the indexer with
+//! [`IndexerClient`], e.g. via `namadac shielded-sync --masp-indexer <url>`,
+//! instead of querying the node for every transaction.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
+use namada::tendermint_rpc::HttpClient;
+use namada::types::io::StdIo;
+use namada_sdk::error::Error;
+use namada_sdk::masp::{
+    CompactBlock, LedgerTxSource, ShieldedTxSource, SYNC_CHECKPOINT_INTERVAL,
+};
+use namada_sdk::rpc::wait_until_node_is_synched;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use warp::Filter;
+
+use crate::cli::api::CliClient;
+use crate::cli::args;
+
+/// The sub-directory of the base directory in which the indexer stores its data
+const INDEXER_DIR: &str = "masp-indexer";
+/// The file to which the indexed compact blocks are appended, each one
+/// prefixed with the little-endian `u32` length of its encoding
+const FILE_NAME: &str = "compact_blocks.log";
+
+/// The endpoint from which compact blocks can be requested, followed by the
+/// index of the first shielded transaction to return
+pub const BLOCKS_ENDPOINT: &str = "compact_blocks";
+/// The endpoint reporting the progress of the indexer
+pub const STATUS_ENDPOINT: &str = "status";
+/// The maximum number of shielded transactions returned by a single request
+/// to [`BLOCKS_ENDPOINT`]
+pub const MAX_BLOCKS_LIMIT: u64 = SYNC_CHECKPOINT_INTERVAL;
+
+/// The compact blocks indexed so far
+#[derive(Debug, Default)]
+pub struct IndexedBlocks {
+    /// The index of the next shielded transaction to fetch from the ledger
+    pub next_txidx: u64,
+    /// The indexed compact blocks, ordered by height
+    pub blocks: Vec<CompactBlock>,
+}
+
+impl IndexedBlocks {
+    /// Index the given compact blocks, which must contain the shielded
+    /// transactions from [`IndexedBlocks::next_txidx`] onwards
+    pub fn extend(&mut self, blocks: Vec<CompactBlock>) {
+        for block in blocks {
+            if let Some(tx) = block.txs.last() {
+                self.next_txidx = tx.txidx + 1;
+            }
+            match self.blocks.last_mut() {
+                Some(last) if last.height == block.height => {
+                    last.txs.extend(block.txs)
+                }
+                _ => self.blocks.push(block),
+            }
+        }
+    }
+
+    /// Get the compact blocks containing at most `limit` shielded
+    /// transactions from the given index onwards, trimmed to exactly those
+    /// transactions
+    pub fn blocks_from(&self, txidx: u64, limit: u64) -> Vec<CompactBlock> {
+        let start = self.blocks.partition_point(|block| {
+            block.txs.last().map_or(true, |tx| tx.txidx < txidx)
+        });
+        let mut count = 0;
+        let end = start
+            + self.blocks[start..]
+                .iter()
+                .take_while(|block| {
+                    let fits = count < limit;
+                    let txs = block.txs.iter().filter(|tx| tx.txidx >= txidx);
+                    count += txs.count() as u64;
+                    fits
+                })
+                .count();
+        CompactBlock::select(self.blocks[start..end].to_vec(), txidx, limit)
+    }
+
+    /// Load the indexed blocks from the given directory. If nothing was saved
+    /// yet, start from an empty index. A trailing block whose write was
+    /// interrupted is truncated from the file.
+    pub fn load(dir: &Path) -> std::io::Result<Self> {
+        let path = dir.join(FILE_NAME);
+        let mut bytes = Vec::new();
+        match File::open(&path) {
+            Ok(mut file) => file.read_to_end(&mut bytes)?,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                return Ok(Self::default());
+            }
+            Err(err) => return Err(err),
+        };
+        let mut index = Self::default();
+        let mut offset = 0;
+        while let Some(len) = bytes.get(offset..offset + 4).map(|len| {
+            u32::from_le_bytes(
+                len.try_into().expect("The length prefix has 4 bytes"),
+            ) as usize
+        }) {
+            let Some(block) = bytes.get(offset + 4..offset + 4 + len) else {
+                break;
+            };
+            index.extend(vec![CompactBlock::try_from_slice(block)?]);
+            offset += 4 + len;
+        }
+        if offset < bytes.len() {
+            tracing::warn!(
+                len = bytes.len() - offset,
+                "Truncating an incomplete block from the MASP index"
+            );
+            OpenOptions::new()
+                .write(true)
+                .open(&path)?
+                .set_len(offset as u64)?;
+        }
+        Ok(index)
+    }
+
+    /// Append the given compact blocks to the index saved in the given
+    /// directory
+    pub fn append(dir: &Path, blocks: &[CompactBlock]) -> std::io::Result<()> {
+        fs::create_dir_all(dir)?;
+        let mut bytes = Vec::new();
+        for block in blocks {
+            let block = block.serialize_to_vec();
+            let len = u32::try_from(block.len()).map_err(|_| {
+                std::io::Error::new(
+                    ErrorKind::InvalidInput,
+                    "compact block is too large",
+                )
+            })?;
+            bytes.extend(len.to_le_bytes());
+            bytes.extend(block);
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(FILE_NAME))?;
+        file.write_all(&bytes)?;
+        file.sync_data()
+    }
+}
+
+/// The progress of the indexer, as reported by [`STATUS_ENDPOINT`]
+#[derive(Debug, Serialize, Deserialize)]
+struct Status {
+    /// The index of the next shielded transaction to be indexed
+    next_txidx: u64,
+    /// The height of the last block containing shielded transactions
+    last_height: Option<u64>,
+}
+
+/// The query parameters of a request to [`BLOCKS_ENDPOINT`]
+#[derive(Debug, Deserialize)]
+struct BlocksQuery {
+    /// The maximum number of shielded transactions to return, capped at
+    /// [`MAX_BLOCKS_LIMIT`]
+    limit: Option<u64>,
+}
+
+/// Run the MASP indexer until it is interrupted
+pub async fn run(mut args: args::MaspIndexer) -> std::io::Result<()> {
+    let data_dir = args.base_dir.join(INDEXER_DIR);
+    let state = Arc::new(RwLock::new(IndexedBlocks::load(&data_dir)?));
+    let client = HttpClient::from_tendermint_address(&mut args.ledger_address);
+    wait_until_node_is_synched(&client, &StdIo)
+        .await
+        .map_err(|e| std::io::Error::new(ErrorKind::Other, e))?;
+    tracing::info!(
+        listen_addr = ?args.listen_address,
+        next_txidx = state.read().await.next_txidx,
+        "MASP indexer is starting"
+    );
+
+    let blocks_state = state.clone();
+    let blocks = warp::get()
+        .and(warp::path(BLOCKS_ENDPOINT))
+        .and(warp::path::param::<u64>())
+        .and(warp::path::end())
+        .and(warp::query::<BlocksQuery>())
+        .then(move |txidx: u64, query: BlocksQuery| {
+            let state = blocks_state.clone();
+            async move {
+                let limit = query
+                    .limit
+                    .map_or(MAX_BLOCKS_LIMIT, |l| l.min(MAX_BLOCKS_LIMIT));
+                let state = state.read().await;
+                state.blocks_from(txidx, limit).serialize_to_vec()
+            }
+        });
+    let status_state = state.clone();
+    let status = warp::get()
+        .and(warp::path(STATUS_ENDPOINT))
+        .and(warp::path::end())
+        .then(move || {
+            let state = status_state.clone();
+            async move {
+                let state = state.read().await;
+                warp::reply::json(&Status {
+                    next_txidx: state.next_txidx,
+                    last_height: state
+                        .blocks
+                        .last()
+                        .map(|block| block.height.0),
+                })
+            }
+        });
+    let server = warp::serve(blocks.or(status)).run(args.listen_address);
+
+    tokio::select! {
+        () = server => {},
+        () = tail(&client, state, &data_dir, args.poll_interval.0) => {},
+    }
+    Ok(())
+}
+
+/// Keep indexing the shielded transactions accepted by the ledger. Only the
+/// newly indexed blocks are appended to the index file.
+async fn tail(
+    client: &HttpClient,
+    state: Arc<RwLock<IndexedBlocks>>,
+    data_dir: &Path,
+    poll_interval: std::time::Duration,
+) {
+    loop {
+        let next_txidx = state.read().await.next_txidx;
+        match LedgerTxSource(client)
+            .compact_blocks(next_txidx, SYNC_CHECKPOINT_INTERVAL)
+            .await
+        {
+            Ok(blocks) if !blocks.is_empty() => {
+                // Persist the blocks before serving them, so that a restart
+                // cannot lose blocks that clients already synced
+                if let Err(error) = IndexedBlocks::append(data_dir, &blocks) {
+                    tracing::error!(?error, "Failed to save the MASP index");
+                } else {
+                    let mut state = state.write().await;
+                    state.extend(blocks);
+                    tracing::info!(
+                        next_txidx = state.next_txidx,
+                        "Indexed new shielded transactions"
+                    );
+                }
+            }
+            Ok(_) => {}
+            Err(error) => tracing::warn!(
+                ?error,
+                "Failed to fetch shielded transactions from the ledger"
+            ),
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// Obtains shielded transactions from a MASP indexer
+pub struct IndexerClient {
+    /// The base URL of the indexer
+    url: String,
+    /// The HTTP client
+    client: reqwest::Client,
+}
+
+impl IndexerClient {
+    /// Create a client for the MASP indexer at the given base URL
+    pub fn new(url: impl AsRef<str>) -> Self {
+        Self {
+            url: url.as_ref().trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Get the body of the response of the indexer to a request of the
+    /// given path
+    async fn get(&self, path: &str) -> Result<bytes::Bytes, Error> {
+        self.client
+            .get(format!("{}/{path}", self.url))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| {
+                Error::Other(format!("Failed to query the MASP indexer: {e}"))
+            })?
+            .bytes()
+            .await
+            .map_err(|e| {
+                Error::Other(format!(
+                    "Failed to read the MASP indexer response: {e}"
+                ))
+            })
+    }
+}
+
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+impl ShieldedTxSource for IndexerClient {
+    async fn head_txidx(&self) -> Result<u64, Error> {
+        let bytes = self.get(STATUS_ENDPOINT).await?;
+        let status: Status = serde_json::from_slice(&bytes).map_err(|e| {
+            Error::Other(format!("Failed to decode the indexer status: {e}"))
+        })?;
+        Ok(status.next_txidx)
+    }
+
+    async fn compact_blocks(
+        &self,
+        last_txidx: u64,
+        limit: u64,
+    ) -> Result<Vec<CompactBlock>, Error> {
+        let limit = limit.min(MAX_BLOCKS_LIMIT);
+        let bytes = self
+            .get(&format!("{BLOCKS_ENDPOINT}/{last_txidx}?limit={limit}"))
+            .await?;
+        let blocks =
+            Vec::<CompactBlock>::try_from_slice(&bytes).map_err(|e| {
+                Error::Other(format!("Failed to decode compact blocks: {e}"))
+            })?;
+        Ok(CompactBlock::select(blocks, last_txidx, limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada::types::address::{nam, MASP};
+    use namada::types::storage::{BlockHeight, Epoch, TxIndex};
+    use namada::types::token::{Amount, DenominatedAmount};
+    use namada_sdk::masp::{CompactTx, TransparentTransfer};
+
+    use super::*;
+
+    /// Make a compact block at the given height holding empty shielded
+    /// transactions with the given indices
+    fn block(
+        height: u64,
+        txidxs: impl IntoIterator<Item = u64>,
+    ) -> CompactBlock {
+        CompactBlock {
+            height: BlockHeight(height),
+            txs: txidxs
+                .into_iter()
+                .enumerate()
+                .map(|(index, txidx)| CompactTx {
+                    txidx,
+                    index: TxIndex(index as u32),
+                    epoch: Epoch(0),
+                    transparent: TransparentTransfer {
+                        source: MASP,
+                        target: MASP,
+                        token: nam(),
+                        amount: DenominatedAmount::native(Amount::zero()),
+                    },
+                    nullifiers: vec![],
+                    outputs: vec![],
+                    conversions: 0,
+                })
+                .collect(),
+        }
+    }
+
+    /// Get the heights and transaction indices of the given blocks
+    fn layout(blocks: &[CompactBlock]) -> Vec<(u64, Vec<u64>)> {
+        blocks
+            .iter()
+            .map(|block| {
+                (
+                    block.height.0,
+                    block.txs.iter().map(|tx| tx.txidx).collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Test that the served blocks hold exactly the requested page of
+    /// shielded transactions
+    #[test]
+    fn test_blocks_pagination() {
+        let mut index = IndexedBlocks::default();
+        index.extend(vec![block(1, 0..3), block(4, 3..4)]);
+        // A block split across two fetches is merged back together
+        index.extend(vec![block(4, 4..5), block(7, 5..8)]);
+        assert_eq!(index.next_txidx, 8);
+        assert_eq!(
+            layout(&index.blocks),
+            vec![(1, vec![0, 1, 2]), (4, vec![3, 4]), (7, vec![5, 6, 7])]
+        );
+
+        assert_eq!(layout(&index.blocks_from(0, 2)), vec![(1, vec![0, 1])]);
+        assert_eq!(
+            layout(&index.blocks_from(2, 3)),
+            vec![(1, vec![2]), (4, vec![3, 4])]
+        );
+        assert_eq!(
+            layout(&index.blocks_from(4, 100)),
+            vec![(4, vec![4]), (7, vec![5, 6, 7])]
+        );
+        assert!(index.blocks_from(8, 100).is_empty());
+        assert!(index.blocks_from(0, 0).is_empty());
+    }
+
+    /// Test that appended blocks are loaded back and that an interrupted
+    /// append is truncated away
+    #[test]
+    fn test_append_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(IndexedBlocks::load(dir.path()).unwrap().next_txidx, 0);
+
+        IndexedBlocks::append(dir.path(), &[block(1, 0..2), block(4, 2..3)])
+            .unwrap();
+        IndexedBlocks::append(dir.path(), &[block(4, 3..4)]).unwrap();
+        let index = IndexedBlocks::load(dir.path()).unwrap();
+        assert_eq!(index.next_txidx, 4);
+        assert_eq!(
+            layout(&index.blocks),
+            vec![(1, vec![0, 1]), (4, vec![2, 3])]
+        );
+
+        // Simulate a crash in the middle of an append
+        let path = dir.path().join(FILE_NAME);
+        let len = fs::metadata(&path).unwrap().len();
+        IndexedBlocks::append(dir.path(), &[block(9, 4..6)]).unwrap();
+        let full_len = fs::metadata(&path).unwrap().len();
+        OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(full_len - 1)
+            .unwrap();
+        let index = IndexedBlocks::load(dir.path()).unwrap();
+        assert_eq!(index.next_txidx, 4);
+        assert_eq!(fs::metadata(&path).unwrap().len(), len);
+
+        // Appending after the truncation yields a consistent index
+        IndexedBlocks::append(dir.path(), &[block(9, 4..6)]).unwrap();
+        let index = IndexedBlocks::load(dir.path()).unwrap();
+        assert_eq!(index.next_txidx, 6);
+        assert_eq!(
+            layout(&index.blocks),
+            vec![(1, vec![0, 1]), (4, vec![2, 3]), (9, vec![4, 5])]
+        );
+    }
+}
@@ -11,7 +11,9 @@ pub mod cli;
 pub mod client;
 pub mod config;
 pub mod logging;
+pub mod masp_indexer;
 pub mod node;
+pub mod query_gateway;
 pub mod wallet;
 pub mod wasm_loader;
 
@@ -1,17 +1,24 @@
 //! The oracle is controlled by sending commands over a channel.
 
 use namada::eth_bridge::oracle::config::Config;
-use tokio::sync::mpsc;
 use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, watch};
 
 /// Used by an oracle to receive commands.
 pub type Receiver = mpsc::Receiver<Command>;
 
+/// Used by an oracle to publish the health of its Ethereum RPC endpoints.
+pub type HealthSender = watch::Sender<Vec<EndpointHealth>>;
+
+/// Used to observe the health of an oracle's Ethereum RPC endpoints.
+pub type HealthReceiver = watch::Receiver<Vec<EndpointHealth>>;
+
 /// Used to send commands to an oracle.
 #[derive(Debug)]
 pub struct Sender {
     last_command: Option<Command>,
     inner_sender: mpsc::Sender<Command>,
+    health: Option<HealthReceiver>,
 }
 
 impl Sender {
@@ -29,6 +36,22 @@ impl Sender {
             Ok(())
         }
     }
+
+    /// Create a channel over which the oracle publishes the health of its
+    /// Ethereum RPC endpoints, returning the oracle's side of it.
+    pub fn health_channel(&mut self) -> HealthSender {
+        let (sender, receiver) = watch::channel(vec![]);
+        self.health = Some(receiver);
+        sender
+    }
+
+    /// The last published health of the oracle's Ethereum RPC endpoints.
+    pub fn endpoint_health(&self) -> Vec<EndpointHealth> {
+        self.health
+            .as_ref()
+            .map(|health| health.borrow().clone())
+            .unwrap_or_default()
+    }
 }
 
 /// Returns two sides of a [`mpsc`] channel that can be used for controlling an
@@ -38,6 +61,7 @@ pub fn channel() -> (Sender, Receiver) {
     let sender = Sender {
         last_command: None,
         inner_sender,
+        health: None,
     };
     (sender, receiver)
 }
@@ -50,3 +74,31 @@ pub enum Command {
     /// The oracle will not do anything until this command has been sent.
     UpdateConfig(Config),
 }
+
+/// The health of an Ethereum RPC endpoint, as observed by an oracle.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EndpointHealth {
+    /// The url of the endpoint.
+    pub url: String,
+    /// The number of requests to the endpoint that failed since the last
+    /// successful one.
+    pub consecutive_failures: u64,
+    /// The error returned by the last failed request, if any.
+    pub last_error: Option<String>,
+}
+
+impl EndpointHealth {
+    /// The health of an endpoint that has not been queried yet.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            consecutive_failures: 0,
+            last_error: None,
+        }
+    }
+
+    /// Check whether the last request to the endpoint succeeded.
+    pub fn is_healthy(&self) -> bool {
+        self.consecutive_failures == 0
+    }
+}
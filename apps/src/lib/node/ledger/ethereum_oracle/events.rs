@@ -1,16 +1,23 @@
 pub mod eth_events {
+    use std::borrow::Cow;
     use std::fmt::Debug;
+    use std::iter;
     use std::str::FromStr;
 
+    use ethabi::{EventParam, ParamType, Token};
     use ethbridge_bridge_events::{
         BridgeEvents, TransferToChainFilter, TransferToErcFilter,
         ValidatorSetUpdateFilter,
     };
-    use ethbridge_events::{DynEventCodec, Events as RawEvents};
+    use ethbridge_events::{
+        event_codecs, DynEventCodec, EventKind, Events as RawEvents,
+    };
     use namada::core::types::ethereum_structs;
+    use namada::eth_bridge::oracle::config::Config;
     use namada::types::address::Address;
     use namada::types::ethereum_events::{
-        EthAddress, EthereumEvent, TransferToEthereum, TransferToNamada, Uint,
+        ContractMessage, EthAddress, EthereumEvent, TransferToEthereum,
+        TransferToNamada, Uint,
     };
     use namada::types::hash::Hash;
     use namada::types::keccak::KeccakHash;
@@ -28,6 +35,98 @@ pub mod eth_events {
 
     pub type Result<T> = std::result::Result<T, Error>;
 
+    /// ABI signature of the event emitted by the bridge contract to
+    /// send arbitrary messages from Ethereum contracts to Namada
+    /// accounts.
+    pub const CONTRACT_MESSAGES_SIGNATURE: &str =
+        "ContractMessages(uint256,(address,string,bytes)[])";
+
+    /// Return the ABI of the [`CONTRACT_MESSAGES_SIGNATURE`] event.
+    fn contract_messages_abi() -> ethabi::Event {
+        ethabi::Event {
+            name: "ContractMessages".into(),
+            inputs: vec![
+                EventParam {
+                    name: "nonce".into(),
+                    kind: ParamType::Uint(256),
+                    indexed: false,
+                },
+                EventParam {
+                    name: "messages".into(),
+                    kind: ParamType::Array(Box::new(ParamType::Tuple(vec![
+                        ParamType::Address,
+                        ParamType::String,
+                        ParamType::Bytes,
+                    ]))),
+                    indexed: false,
+                },
+            ],
+            anonymous: false,
+        }
+    }
+
+    /// A kind of Ethereum event the oracle looks for.
+    #[derive(Copy, Clone)]
+    pub(in super::super) enum EventSource {
+        /// An event decoded by one of the [`ethbridge_events`] codecs.
+        Codec(DynEventCodec),
+        /// An event carrying [`EthereumEvent::ContractMessages`].
+        ContractMessages,
+    }
+
+    impl EventSource {
+        /// Iterate over all the kinds of Ethereum events the
+        /// oracle looks for.
+        pub fn all() -> impl Iterator<Item = Self> {
+            event_codecs()
+                .into_iter()
+                .map(Self::Codec)
+                .chain(iter::once(Self::ContractMessages))
+        }
+
+        /// Return the ABI signature of the event.
+        pub fn event_signature(&self) -> Cow<'static, str> {
+            match self {
+                Self::Codec(codec) => codec.event_signature().into(),
+                Self::ContractMessages => CONTRACT_MESSAGES_SIGNATURE.into(),
+            }
+        }
+
+        /// Return the address of the contract emitting the event.
+        pub fn contract_address(&self, config: &Config) -> ethabi::Address {
+            match self {
+                Self::Codec(codec) => match codec.kind() {
+                    EventKind::Bridge => config.bridge_contract.into(),
+                },
+                Self::ContractMessages => config.bridge_contract.into(),
+            }
+        }
+
+        /// Decode an event log into a [`PendingEvent`].
+        pub fn decode(
+            &self,
+            block_height: Uint256,
+            log: &ethabi::RawLog,
+            confirmations: Uint256,
+        ) -> Result<PendingEvent> {
+            match self {
+                Self::Codec(codec) => PendingEvent::decode(
+                    *codec,
+                    block_height,
+                    log,
+                    confirmations,
+                ),
+                Self::ContractMessages => {
+                    PendingEvent::decode_contract_messages(
+                        block_height,
+                        log,
+                        confirmations,
+                    )
+                }
+            }
+        }
+    }
+
     #[derive(Clone, Debug, PartialEq)]
     /// An event waiting for a certain number of confirmations
     /// before being sent to the ledger
@@ -112,6 +211,39 @@ pub mod eth_events {
             })
         }
 
+        /// Decodes an event log carrying [`EthereumEvent::ContractMessages`],
+        /// which is not covered by the [`ethbridge_events`] codecs.
+        pub fn decode_contract_messages(
+            block_height: Uint256,
+            log: &ethabi::RawLog,
+            confirmations: Uint256,
+        ) -> Result<Self> {
+            let log = contract_messages_abi()
+                .parse_log(log.clone())
+                .map_err(|e| Error::Decode(e.to_string()))?;
+            let mut params = log.params.into_iter().map(|param| param.value);
+            let event = match (params.next(), params.next(), params.next()) {
+                (
+                    Some(Token::Uint(nonce)),
+                    Some(Token::Array(messages)),
+                    None,
+                ) => EthereumEvent::ContractMessages {
+                    nonce: nonce.parse_uint256()?,
+                    messages: messages.parse_contract_message_array()?,
+                },
+                _ => {
+                    return Err(Error::Decode(
+                        "Malformed contract messages event".into(),
+                    ));
+                }
+            };
+            Ok(PendingEvent {
+                confirmations,
+                block_height,
+                event,
+            })
+        }
+
         /// Check if the minimum number of confirmations has been
         /// reached at the input block height.
         pub fn is_confirmed(&self, height: &Uint256) -> bool {
@@ -142,6 +274,8 @@ pub mod eth_events {
         parse_amount -> Amount;
         parse_amount_array -> Vec<Amount>;
         parse_bool -> bool;
+        parse_contract_message -> ContractMessage;
+        parse_contract_message_array -> Vec<ContractMessage>;
         parse_eth_address -> EthAddress;
         parse_eth_address_array -> Vec<EthAddress>;
         parse_hash -> Hash;
@@ -277,6 +411,44 @@ pub mod eth_events {
         }
     }
 
+    impl Parse for Token {
+        fn parse_contract_message(self) -> Result<ContractMessage> {
+            let fields = match self {
+                Token::Tuple(fields) => fields,
+                token => {
+                    return Err(Error::Decode(format!(
+                        "Expected a contract message, but got: {token:?}"
+                    )));
+                }
+            };
+            let mut fields = fields.into_iter();
+            match (fields.next(), fields.next(), fields.next(), fields.next()) {
+                (
+                    Some(Token::Address(source)),
+                    Some(Token::String(target)),
+                    Some(Token::Bytes(payload)),
+                    None,
+                ) => Ok(ContractMessage {
+                    source: source.parse_eth_address()?,
+                    target: target.parse_address()?,
+                    payload,
+                }),
+                _ => Err(Error::Decode(
+                    "Malformed contract message fields".into(),
+                )),
+            }
+        }
+    }
+
+    impl Parse for Vec<Token> {
+        fn parse_contract_message_array(self) -> Result<Vec<ContractMessage>> {
+            self.into_iter().try_fold(Vec::new(), |mut acc, msg| {
+                acc.push(msg.parse_contract_message()?);
+                Ok(acc)
+            })
+        }
+    }
+
     impl Parse for Vec<String> {
         fn parse_address_array(self) -> Result<Vec<Address>> {
             self.into_iter().try_fold(Vec::new(), |mut acc, addr| {
@@ -537,6 +709,47 @@ pub mod eth_events {
                 update
             );
         }
+
+        /// Test decoding a contract messages Ethereum event, and that the
+        /// ABI signature used to look for it matches its decoder.
+        #[test]
+        fn test_contract_messages_decode() {
+            let abi = contract_messages_abi();
+            assert_eq!(
+                abi.signature().0,
+                namada::eth_bridge::ethers::utils::keccak256(
+                    CONTRACT_MESSAGES_SIGNATURE
+                ),
+            );
+
+            let target = "tnam1q9uh06dh2crk5fzk8acgu9ncaq7kpceppsspuya2";
+            let log = ethabi::RawLog {
+                topics: vec![abi.signature()],
+                data: ethabi::encode(&[
+                    Token::Uint(7u64.into()),
+                    Token::Array(vec![Token::Tuple(vec![
+                        Token::Address(H160([1; 20])),
+                        Token::String(target.into()),
+                        Token::Bytes(b"payload".to_vec()),
+                    ])]),
+                ]),
+            };
+            let pending_event = EventSource::ContractMessages
+                .decode(123u64.into(), &log, 100u64.into())
+                .expect("Test failed");
+
+            assert_eq!(
+                pending_event.event,
+                EthereumEvent::ContractMessages {
+                    nonce: 7u64.into(),
+                    messages: vec![ContractMessage {
+                        source: EthAddress([1; 20]),
+                        target: Address::from_str(target).unwrap(),
+                        payload: b"payload".to_vec(),
+                    }],
+                }
+            );
+        }
     }
 }
 
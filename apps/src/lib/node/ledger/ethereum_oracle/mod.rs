@@ -1,12 +1,12 @@
 pub mod control;
 pub mod events;
+pub mod quorum;
 pub mod test_tools;
 
 use std::ops::ControlFlow;
 
 use async_trait::async_trait;
 use ethabi::Address;
-use ethbridge_events::{event_codecs, EventKind};
 use itertools::Either;
 use namada::core::hints;
 use namada::core::types::ethereum_structs;
@@ -22,7 +22,7 @@ use tokio::sync::mpsc::error::TryRecvError;
 use tokio::sync::mpsc::Sender as BoundedSender;
 use tokio::task::LocalSet;
 
-use self::events::PendingEvent;
+use self::events::{EventSource, PendingEvent};
 use super::abortable::AbortableSpawner;
 use crate::node::ledger::oracle::control::Command;
 
@@ -46,6 +46,10 @@ pub enum Error {
     MoreConfirmations,
     #[error("The Ethereum oracle timed out")]
     Timeout,
+    #[error("The Ethereum RPC endpoints did not reach a quorum: {0}")]
+    NoQuorum(String),
+    #[error("Invalid Ethereum oracle configuration: {0}")]
+    Config(String),
 }
 
 /// Convert values to [`ethabi`] Ethereum event logs.
@@ -279,24 +283,46 @@ async fn await_initial_configuration(
 }
 
 /// Set up an Oracle and run the process where the Oracle
-/// processes and forwards Ethereum events to the ledger
+/// processes and forwards Ethereum events to the ledger.
+///
+/// The oracle queries every given RPC endpoint, and only trusts
+/// the events and block heights that `quorum` of them agree on.
 pub fn run_oracle<C: RpcClient>(
-    url: impl AsRef<str>,
+    urls: Vec<String>,
+    quorum: usize,
     sender: BoundedSender<EthereumEvent>,
     control: control::Receiver,
+    health: control::HealthSender,
     last_processed_block: last_processed_block::Sender,
     spawner: &mut AbortableSpawner,
 ) -> tokio::task::JoinHandle<()> {
-    let url = url.as_ref().to_owned();
     let blocking_handle = tokio::task::spawn_blocking(move || {
         let rt = tokio::runtime::Handle::current();
         rt.block_on(async move {
             LocalSet::new()
                 .run_until(async move {
-                    tracing::info!(?url, "Ethereum event oracle is starting");
+                    tracing::info!(
+                        ?urls,
+                        quorum,
+                        "Ethereum event oracle is starting"
+                    );
 
-                    let oracle = Oracle::<C>::new(
-                        Either::Right(&url),
+                    let client = match quorum::QuorumClient::<C>::new(
+                        &urls, quorum, health,
+                    ) {
+                        Ok(client) => client,
+                        Err(error) => {
+                            // returning stops the oracle, and with it the
+                            // ledger
+                            tracing::error!(
+                                %error,
+                                "Ethereum event oracle could not be started"
+                            );
+                            return;
+                        }
+                    };
+                    let oracle = Oracle::new(
+                        Either::Left(client),
                         sender,
                         last_processed_block,
                         DEFAULT_BACKOFF,
@@ -306,7 +332,7 @@ pub fn run_oracle<C: RpcClient>(
                     run_oracle_aux(oracle).await;
 
                     tracing::info!(
-                        ?url,
+                        ?urls,
                         "Ethereum event oracle is no longer running"
                     );
                 })
@@ -499,11 +525,9 @@ async fn process_events_in_block<C: RpcClient>(
     );
     // check for events in Ethereum blocks that have reached the minimum number
     // of confirmations
-    for codec in event_codecs() {
-        let sig = codec.event_signature();
-        let addr: Address = match codec.kind() {
-            EventKind::Bridge => config.bridge_contract.into(),
-        };
+    for source in EventSource::all() {
+        let sig = source.event_signature();
+        let addr: Address = source.contract_address(config);
         tracing::debug!(
             ?block_to_process,
             ?addr,
@@ -528,8 +552,7 @@ async fn process_events_in_block<C: RpcClient>(
             logs.into_iter()
                 .map(IntoEthAbiLog::into_ethabi_log)
                 .filter_map(|log| {
-                    match PendingEvent::decode(
-                        codec,
+                    match source.decode(
                         block_to_process.clone().into(),
                         &log,
                         u64::from(config.min_confirmations).into(),
@@ -0,0 +1,351 @@
+//! An [`RpcClient`] that spreads every request over several Ethereum RPC
+//! endpoints, and only trusts the answers that a quorum of them agree on.
+
+use std::cell::RefCell;
+
+use async_trait::async_trait;
+use ethabi::Address;
+use futures::future::join_all;
+use namada::core::types::ethereum_structs;
+use namada::types::control_flow::time::{Duration, Instant};
+use namada_sdk::eth_bridge::SyncStatus;
+use tokio::sync::watch;
+
+use super::control::{EndpointHealth, HealthSender};
+use super::{Error, IntoEthAbiLog, RpcClient};
+
+/// A client for a set of Ethereum RPC endpoints, of which at least `quorum`
+/// must agree on an answer for it to be accepted.
+pub struct QuorumClient<C> {
+    /// The clients of each endpoint.
+    clients: Vec<C>,
+    /// The number of endpoints that must agree on an answer.
+    quorum: usize,
+    /// The health of each endpoint, in the same order as `clients`.
+    health: RefCell<Vec<EndpointHealth>>,
+    /// Where the health of the endpoints is published.
+    health_sender: HealthSender,
+}
+
+impl<C: RpcClient> QuorumClient<C> {
+    /// Create a client for the given endpoints.
+    ///
+    /// Fails if no endpoint was given, or if the quorum is zero or larger
+    /// than the number of endpoints.
+    pub fn new(
+        urls: &[String],
+        quorum: usize,
+        health_sender: HealthSender,
+    ) -> Result<Self, Error> {
+        Self::from_clients(
+            urls.iter()
+                .map(|url| (url.clone(), C::new_client(url)))
+                .collect(),
+            quorum,
+            health_sender,
+        )
+    }
+
+    /// Create a client from already instantiated endpoint clients, along
+    /// with their urls.
+    pub fn from_clients(
+        clients: Vec<(String, C)>,
+        quorum: usize,
+        health_sender: HealthSender,
+    ) -> Result<Self, Error> {
+        if clients.is_empty() {
+            return Err(Error::Config(
+                "No Ethereum RPC endpoint was provided".into(),
+            ));
+        }
+        if quorum == 0 || quorum > clients.len() {
+            return Err(Error::Config(format!(
+                "The RPC quorum must be between 1 and the number of endpoints \
+                 ({}), but it is {quorum}",
+                clients.len()
+            )));
+        }
+        let (health, clients): (Vec<_>, Vec<_>) = clients
+            .into_iter()
+            .map(|(url, client)| (EndpointHealth::new(url), client))
+            .unzip();
+        health_sender.send_replace(health.clone());
+        Ok(Self {
+            clients,
+            quorum,
+            health: RefCell::new(health),
+            health_sender,
+        })
+    }
+
+    /// Record the outcome of a request to each endpoint, and publish the
+    /// resulting health of the endpoints.
+    fn record_health<T>(&self, results: &[Result<T, Error>]) {
+        let mut health = self.health.borrow_mut();
+        for (endpoint, result) in health.iter_mut().zip(results) {
+            match result {
+                Ok(_) => {
+                    endpoint.consecutive_failures = 0;
+                    endpoint.last_error = None;
+                }
+                Err(error) => {
+                    if endpoint.is_healthy() {
+                        tracing::warn!(
+                            url = %endpoint.url,
+                            %error,
+                            "Ethereum RPC endpoint has become unhealthy"
+                        );
+                    }
+                    endpoint.consecutive_failures += 1;
+                    endpoint.last_error = Some(error.to_string());
+                }
+            }
+        }
+        self.health_sender.send_replace(health.clone());
+    }
+
+    /// Split the results of a request into the successful answers and the
+    /// first error, if there are not enough answers to reach a quorum.
+    fn successes<T>(
+        &self,
+        results: Vec<Result<T, Error>>,
+    ) -> Result<Vec<T>, Error> {
+        self.record_health(&results);
+        let mut first_error = None;
+        let mut answers = Vec::with_capacity(results.len());
+        for result in results {
+            match result {
+                Ok(answer) => answers.push(answer),
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+        match first_error {
+            // When every endpoint failed, behave like a single endpoint would
+            Some(error) if answers.is_empty() => Err(error),
+            _ if answers.len() < self.quorum => Err(Error::NoQuorum(format!(
+                "only {} out of {} endpoints answered, but {} are required",
+                answers.len(),
+                self.clients.len(),
+                self.quorum
+            ))),
+            _ => Ok(answers),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: RpcClient> RpcClient for QuorumClient<C> {
+    type Log = ethabi::RawLog;
+
+    fn new_client(rpc_url: &str) -> Self
+    where
+        Self: Sized,
+    {
+        let (health_sender, _) = watch::channel(vec![]);
+        Self::new(&[rpc_url.to_owned()], 1, health_sender)
+            .expect("A single endpoint with a quorum of 1 is always valid")
+    }
+
+    async fn check_events_in_block(
+        &self,
+        block: ethereum_structs::BlockHeight,
+        address: Address,
+        abi_signature: &str,
+    ) -> Result<Vec<Self::Log>, Error> {
+        let results = join_all(self.clients.iter().map(|client| async {
+            client
+                .check_events_in_block(block.clone(), address, abi_signature)
+                .await
+                .map(|logs| {
+                    logs.into_iter()
+                        .map(IntoEthAbiLog::into_ethabi_log)
+                        .collect::<Vec<_>>()
+                })
+        }))
+        .await;
+        let answers = self.successes(results)?;
+        Ok(agreed_logs(&answers, self.quorum))
+    }
+
+    async fn syncing(
+        &self,
+        last_processed_block: Option<&ethereum_structs::BlockHeight>,
+        backoff: Duration,
+        deadline: Instant,
+    ) -> Result<SyncStatus, Error> {
+        let results = join_all(self.clients.iter().map(|client| {
+            client.syncing(last_processed_block, backoff, deadline)
+        }))
+        .await;
+        let answers = self.successes(results)?;
+        let mut heights: Vec<_> = answers
+            .into_iter()
+            .filter_map(|status| match status {
+                SyncStatus::AtHeight(height) => Some(height),
+                SyncStatus::Syncing => None,
+            })
+            .collect();
+        if heights.len() < self.quorum {
+            return Ok(SyncStatus::Syncing);
+        }
+        // The highest block that a quorum of endpoints has reached
+        heights.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(SyncStatus::AtHeight(heights.swap_remove(self.quorum - 1)))
+    }
+
+    fn may_recover(&self, error: &Error) -> bool {
+        match error {
+            // The endpoints may still converge
+            Error::NoQuorum(_) => true,
+            // Every endpoint failed with this error
+            error => self.clients[0].may_recover(error),
+        }
+    }
+}
+
+/// Get the logs that at least `quorum` of the given answers contain, in the
+/// order in which they first appear. A log that occurs several times in the
+/// same block is returned as many times as a quorum of answers agree on.
+fn agreed_logs(
+    answers: &[Vec<ethabi::RawLog>],
+    quorum: usize,
+) -> Vec<ethabi::RawLog> {
+    let mut agreed: Vec<ethabi::RawLog> = vec![];
+    for log in answers.iter().flatten() {
+        if agreed.contains(log) {
+            continue;
+        }
+        let mut occurrences: Vec<_> = answers
+            .iter()
+            .map(|logs| logs.iter().filter(|other| *other == log).count())
+            .collect();
+        occurrences.sort_unstable_by(|a, b| b.cmp(a));
+        let count = occurrences.get(quorum - 1).copied().unwrap_or_default();
+        agreed.extend(std::iter::repeat(log.clone()).take(count));
+    }
+    agreed
+}
+
+#[cfg(test)]
+mod tests {
+    use num256::Uint256;
+
+    use super::*;
+    use crate::node::ledger::ethereum_oracle::test_tools::mock_web3_client::{
+        TestCmd, Web3Client,
+    };
+
+    fn log(byte: u8) -> ethabi::RawLog {
+        ethabi::RawLog {
+            topics: vec![],
+            data: vec![byte],
+        }
+    }
+
+    /// Test that only the logs reported by a quorum of endpoints are
+    /// accepted, as many times as the quorum agrees on.
+    #[test]
+    fn test_agreed_logs() {
+        let answers = vec![
+            vec![log(1), log(2), log(2)],
+            vec![log(2), log(1), log(3)],
+            vec![log(2), log(2), log(4)],
+        ];
+        assert_eq!(agreed_logs(&answers, 2), vec![log(1), log(2), log(2)]);
+        assert_eq!(agreed_logs(&answers, 3), vec![log(2)]);
+        assert_eq!(
+            agreed_logs(&answers, 1),
+            vec![log(1), log(2), log(2), log(3), log(4)]
+        );
+    }
+
+    /// Test that a quorum that no set of endpoints could ever reach is
+    /// rejected, rather than silently adjusted.
+    #[test]
+    fn test_invalid_quorum() {
+        let clients = || -> Vec<_> {
+            (0..2)
+                .map(|i| {
+                    let (_, client) = Web3Client::setup();
+                    (format!("http://endpoint-{i}"), client)
+                })
+                .collect()
+        };
+        let (health_sender, _) = watch::channel(vec![]);
+        for quorum in [0, 3] {
+            let result = QuorumClient::from_clients(
+                clients(),
+                quorum,
+                health_sender.clone(),
+            );
+            assert!(matches!(result, Err(Error::Config(_))));
+        }
+        let result = QuorumClient::<Web3Client>::from_clients(
+            vec![],
+            1,
+            health_sender.clone(),
+        );
+        assert!(matches!(result, Err(Error::Config(_))));
+        assert!(QuorumClient::from_clients(clients(), 2, health_sender).is_ok());
+    }
+
+    /// Test that the reported height is the highest one that a quorum of
+    /// endpoints has reached, and that unresponsive endpoints are reported
+    /// as unhealthy without halting the client.
+    #[tokio::test]
+    async fn test_quorum_failover() {
+        let clients: Vec<_> = (0..3)
+            .map(|i| {
+                let (_, client) = Web3Client::setup();
+                (format!("http://endpoint-{i}"), client)
+            })
+            .collect();
+        let controllers: Vec<_> = clients
+            .iter()
+            .map(|(_, client)| client.controller())
+            .collect();
+        for (controller, height) in controllers.iter().zip([10u64, 7, 5]) {
+            controller.apply_cmd(TestCmd::NewHeight(Uint256::from(height)));
+        }
+        let (health_sender, health_receiver) = watch::channel(vec![]);
+        let client =
+            QuorumClient::from_clients(clients, 2, health_sender).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(1);
+
+        let status = client
+            .syncing(None, Duration::from_millis(1), deadline)
+            .await
+            .unwrap();
+        assert!(
+            matches!(status, SyncStatus::AtHeight(h) if h == Uint256::from(7u64))
+        );
+
+        controllers[0].apply_cmd(TestCmd::Unresponsive);
+        let logs = client
+            .check_events_in_block(
+                Uint256::from(1u64).into(),
+                Address::zero(),
+                "event",
+            )
+            .await
+            .unwrap();
+        assert!(logs.is_empty());
+        let health = health_receiver.borrow().clone();
+        assert!(!health[0].is_healthy());
+        assert!(health[1].is_healthy() && health[2].is_healthy());
+
+        controllers[1].apply_cmd(TestCmd::Unresponsive);
+        let error = client
+            .check_events_in_block(
+                Uint256::from(1u64).into(),
+                Address::zero(),
+                "event",
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::NoQuorum(_)));
+        assert!(client.may_recover(&error));
+    }
+}
@@ -666,14 +666,16 @@ async fn maybe_start_ethereum_oracle(
         mpsc::channel(config.ethereum_bridge.channel_buffer_size);
     let (last_processed_block_sender, last_processed_block_receiver) =
         last_processed_block::channel();
-    let (control_sender, control_receiver) = oracle::control::channel();
+    let (mut control_sender, control_receiver) = oracle::control::channel();
 
     match config.ethereum_bridge.mode {
         ethereum_bridge::ledger::Mode::RemoteEndpoint => {
             let handle = oracle::run_oracle::<Provider<Http>>(
-                ethereum_url,
+                config.ethereum_bridge.oracle_rpc_endpoints(),
+                config.ethereum_bridge.oracle_rpc_quorum,
                 eth_sender,
                 control_receiver,
+                control_sender.health_channel(),
                 last_processed_block_sender,
                 spawner,
             );
@@ -1,14 +1,19 @@
 //! Implementation of the `FinalizeBlock` ABCI++ method for the Shell
 
+use std::collections::HashMap;
+
 use data_encoding::HEXUPPER;
+use namada::core::ledger::eth_bridge::storage::contract_messages;
 use namada::core::ledger::inflation;
 use namada::core::ledger::masp_conversions::update_allowed_conversions;
 use namada::core::ledger::pgf::ADDRESS as pgf_address;
-use namada::ledger::events::EventType;
-use namada::ledger::gas::{GasMetering, TxGasMeter};
+use namada::eth_bridge::protocol::transactions::ethereum_events;
+use namada::ledger::events::{EventLevel, EventType};
+use namada::ledger::gas::{get_max_block_gas, GasMetering, TxGasMeter};
 use namada::ledger::parameters::storage as params_storage;
 use namada::ledger::pos::{namada_proof_of_stake, staking_token_address};
 use namada::ledger::protocol;
+use namada::ledger::protocol::parallel::{BatchTx, SpeculativeTx};
 use namada::ledger::storage::wl_storage::WriteLogAndStorage;
 use namada::ledger::storage::EPOCH_SWITCH_BLOCKS_DELAY;
 use namada::ledger::storage_api::token::credit_tokens;
@@ -17,12 +22,16 @@ use namada::proof_of_stake::{
     find_validator_by_raw_hash, read_last_block_proposer_address,
     read_pos_params, read_total_stake, write_last_block_proposer_address,
 };
+use namada::proto::{Code, Commitment, Data};
 use namada::types::dec::Dec;
+use namada::types::eth_abi::Encode;
+use namada::types::eth_bridge_pool::ExpiredTransfer;
 use namada::types::key::tm_raw_hash_to_string;
 use namada::types::storage::{BlockHash, BlockResults, Epoch, Header};
 use namada::types::transaction::protocol::{
     ethereum_tx_data_variants, ProtocolTxType,
 };
+use namada::types::transaction::GasLimit;
 use namada::types::vote_extensions::ethereum_events::MultiSignedEthEvent;
 
 use super::governance::execute_governance_proposals;
@@ -97,6 +106,8 @@ where
 
             execute_governance_proposals(self, &mut response)?;
 
+            self.expire_bridge_pool_transfers(&mut response)?;
+
             // Copy the new_epoch + pipeline_len - 1 validator set into
             // new_epoch + pipeline_len
             namada_proof_of_stake::copy_validator_sets_and_positions(
@@ -199,6 +210,11 @@ where
         // Tracks the accepted transactions
         self.wl_storage.storage.block.results = BlockResults::default();
         let mut changed_keys = BTreeSet::new();
+        // The decrypted txs at the start of the block are executed in
+        // parallel, ahead of the loop below. Their results are only used if
+        // no conflicting changes were committed in the meantime
+        let mut speculations = self.speculate_decrypted_txs(&req.txs);
+        let mut committed_keys = BTreeSet::new();
         for (tx_index, processed_tx) in req.txs.iter().enumerate() {
             let tx = if let Ok(tx) = Tx::try_from(processed_tx.tx.as_ref()) {
                 tx
@@ -396,22 +412,33 @@ where
                     },
                 };
 
-            match protocol::dispatch_tx(
-                tx,
-                processed_tx.tx.as_ref(),
-                TxIndex(
-                    tx_index
-                        .try_into()
-                        .expect("transaction index out of bounds"),
-                ),
-                &mut tx_gas_meter,
-                &mut self.wl_storage,
-                &mut self.vp_wasm_cache,
-                &mut self.tx_wasm_cache,
-                Some(&native_block_proposer_address),
-            )
-            .map_err(Error::TxApply)
-            {
+            let speculation =
+                speculations.remove(&tx_index).filter(|speculation| {
+                    speculation
+                        .is_valid(&self.wl_storage.write_log, &committed_keys)
+                });
+            let dispatch_result = if let Some(speculation) = speculation {
+                let (result, gas_meter) =
+                    speculation.apply(&mut self.wl_storage.write_log);
+                tx_gas_meter = gas_meter;
+                result
+            } else {
+                protocol::dispatch_tx(
+                    tx,
+                    processed_tx.tx.as_ref(),
+                    TxIndex(
+                        tx_index
+                            .try_into()
+                            .expect("transaction index out of bounds"),
+                    ),
+                    &mut tx_gas_meter,
+                    &mut self.wl_storage,
+                    &mut self.vp_wasm_cache,
+                    &mut self.tx_wasm_cache,
+                    Some(&native_block_proposer_address),
+                )
+            };
+            match dispatch_result.map_err(Error::TxApply) {
                 Ok(ref mut result) => {
                     if result.is_accepted() {
                         if let EventType::Accepted = tx_event.event_type {
@@ -438,6 +465,13 @@ where
                                 self.commit_inner_tx_hash(wrapper);
                             }
                         }
+                        if !speculations.is_empty() {
+                            committed_keys.extend(
+                                self.wl_storage
+                                    .write_log
+                                    .get_keys_with_precommit(),
+                            );
+                        }
                         self.wl_storage.commit_tx();
                         if !tx_event.contains_key("code") {
                             tx_event["code"] = ErrorCodes::Ok.into();
@@ -489,6 +523,32 @@ where
                         stats.increment_rejected_txs();
                         self.wl_storage.drop_tx();
                         tx_event["code"] = ErrorCodes::InvalidTx.into();
+                        // Report which VPs rejected the tx and why to the
+                        // clients tracking it
+                        match serde_json::to_string(
+                            &result.vps_result.rejected_vps,
+                        ) {
+                            Ok(rejected_vps) => {
+                                tx_event["rejected_vps"] = rejected_vps;
+                            }
+                            Err(err) => {
+                                tracing::error!(
+                                    "Failed to serialize the rejected VPs: {}",
+                                    err
+                                );
+                            }
+                        }
+                        match serde_json::to_string(&result.vps_result.errors) {
+                            Ok(vp_errors) => {
+                                tx_event["vp_errors"] = vp_errors;
+                            }
+                            Err(err) => {
+                                tracing::error!(
+                                    "Failed to serialize the VP errors: {}",
+                                    err
+                                );
+                            }
+                        }
                     }
                     tx_event["gas_used"] = result.gas_used.to_string();
                     tx_event["info"] = result.to_string();
@@ -548,6 +608,11 @@ where
             response.events.push(tx_event);
         }
 
+        self.dispatch_eth_contract_messages(
+            &native_block_proposer_address,
+            &mut response,
+        )?;
+
         stats.set_tx_cache_size(
             self.tx_wasm_cache.get_size(),
             self.tx_wasm_cache.get_cache_size(),
@@ -630,6 +695,261 @@ where
             .expect("Must be able to update validator set");
     }
 
+    /// Remove the expired transfers from the Ethereum bridge pool, and
+    /// emit an event for each transfer that expired or got refunded.
+    ///
+    /// Expired transfers are only refunded after the Bridge pool nonce
+    /// they expired at, as recorded in the `nonce` attribute of their
+    /// events, is incremented.
+    fn expire_bridge_pool_transfers(
+        &mut self,
+        response: &mut shim::response::FinalizeBlock,
+    ) -> Result<()> {
+        let (_, transfers) =
+            ethereum_events::expire_transfers_to_eth(&mut self.wl_storage)
+                .map_err(|err| {
+                    storage_api::Error::wrap(
+                        "Failed to expire Bridge pool transfers",
+                        err,
+                    )
+                })?;
+        let expired = transfers.expired.into_iter().map(|expired| {
+            tracing::info!(
+                ?expired,
+                "A transfer in the Ethereum bridge pool expired, and will be \
+                 refunded once the Bridge pool nonce is incremented"
+            );
+            (EventType::EthBridgePoolExpiry, expired)
+        });
+        let refunded = transfers.refunded.into_iter().map(|refunded| {
+            tracing::info!(
+                ?refunded,
+                "Refunded an expired transfer in the Ethereum bridge pool"
+            );
+            (EventType::EthBridgePoolRefund, refunded)
+        });
+        response.events.extend(expired.chain(refunded).map(
+            |(event_type, ExpiredTransfer { transfer, nonce })| Event {
+                event_type,
+                level: EventLevel::Block,
+                attributes: HashMap::from([
+                    (
+                        "transfer_hash".to_string(),
+                        transfer.keccak256().to_string(),
+                    ),
+                    ("nonce".to_string(), nonce.to_string()),
+                    (
+                        "sender".to_string(),
+                        transfer.transfer.sender.to_string(),
+                    ),
+                    ("asset".to_string(), transfer.transfer.asset.to_string()),
+                    (
+                        "amount".to_string(),
+                        transfer.transfer.amount.to_string_native(),
+                    ),
+                    (
+                        "gas_payer".to_string(),
+                        transfer.gas_fee.payer.to_string(),
+                    ),
+                    (
+                        "gas_token".to_string(),
+                        transfer.gas_fee.token.to_string(),
+                    ),
+                    (
+                        "gas_amount".to_string(),
+                        transfer.gas_fee.amount.to_string_native(),
+                    ),
+                ]),
+            },
+        ));
+        Ok(())
+    }
+
+    /// Dispatch the messages from Ethereum contracts confirmed in this
+    /// block to the wasm handlers of their target accounts, and emit an
+    /// event with the outcome of each dispatch.
+    ///
+    /// Each handler is executed as a separate transaction, with the
+    /// message as its data, and may consume up to
+    /// [`contract_messages::HANDLER_GAS_LIMIT`]. The target account pays
+    /// for this gas upfront, in the native token, at the minimum gas price
+    /// to the block proposer. The fee is kept even if the handler fails,
+    /// while the handler's storage modifications are only committed if all
+    /// the triggered VPs accept them. Messages addressed to accounts
+    /// without a handler, or that cannot pay for it, are dropped.
+    ///
+    /// The handlers dispatched in a block may not consume more than the
+    /// maximum block gas in total. The remaining messages are dispatched
+    /// in the following blocks.
+    fn dispatch_eth_contract_messages(
+        &mut self,
+        block_proposer: &Address,
+        response: &mut shim::response::FinalizeBlock,
+    ) -> Result<()> {
+        let messages = contract_messages::take_pending(&mut self.wl_storage)?;
+        if messages.is_empty() {
+            return Ok(());
+        }
+        self.wl_storage.commit_tx();
+        let mut messages = messages.into_iter();
+
+        let gas_limit = GasLimit::from(contract_messages::HANDLER_GAS_LIMIT);
+        let native_token = self.wl_storage.storage.native_token.clone();
+        let fee = namada::ledger::parameters::read_gas_cost(
+            &self.wl_storage,
+            &native_token,
+        )?
+        .expect("The gas cost of the native token must be set")
+        .checked_mul(gas_limit.into())
+        .expect("The fee of a message handler must not overflow");
+        let mut block_gas_left = get_max_block_gas(&self.wl_storage)?;
+        let block_time = self
+            .wl_storage
+            .storage
+            .header
+            .as_ref()
+            .expect("The header of the block being finalized must be set")
+            .time;
+        while block_gas_left >= u64::from(gas_limit) {
+            let Some(message) = messages.next() else {
+                break;
+            };
+            let mut event = Event {
+                event_type: EventType::EthContractMessage,
+                level: EventLevel::Block,
+                attributes: HashMap::from([
+                    ("source".to_string(), message.source.to_string()),
+                    ("target".to_string(), message.target.to_string()),
+                ]),
+            };
+            let Some(handler) = contract_messages::read_handler(
+                &self.wl_storage,
+                &message.target,
+            )?
+            else {
+                tracing::info!(
+                    ?message,
+                    "Dropping an Ethereum contract message addressed to an \
+                     account without a handler"
+                );
+                event["code"] = ErrorCodes::InvalidTx.into();
+                event["info"] = "The target has no message handler".to_string();
+                response.events.push(event);
+                continue;
+            };
+            event["handler"] = handler.to_string();
+
+            // the target pays for the handler's gas, whatever the outcome
+            let balance = storage_api::token::read_balance(
+                &self.wl_storage,
+                &native_token,
+                &message.target,
+            )?;
+            if balance < fee {
+                tracing::info!(
+                    ?message,
+                    "Dropping an Ethereum contract message whose target \
+                     cannot pay for its handler"
+                );
+                event["code"] = ErrorCodes::FeeError.into();
+                event["info"] = format!(
+                    "The target cannot pay the fee of {} for its message \
+                     handler",
+                    fee.to_string_native()
+                );
+                response.events.push(event);
+                continue;
+            }
+            storage_api::token::transfer(
+                &mut self.wl_storage,
+                &native_token,
+                &message.target,
+                block_proposer,
+                fee,
+            )?;
+            self.wl_storage.commit_tx();
+            block_gas_left -= u64::from(gas_limit);
+            event["fee"] = fee.to_string_native();
+
+            // NB: the tx must be constructed deterministically, as it is
+            // executed by every node
+            let mut tx =
+                Tx::from_type(TxType::Decrypted(DecryptedTx::Decrypted));
+            tx.header.chain_id = self.chain_id.clone();
+            tx.header.timestamp = block_time;
+            tx.set_code(Code {
+                salt: [0; 8],
+                code: Commitment::Hash(handler),
+                tag: None,
+            });
+            tx.set_data(Data {
+                salt: [0; 8],
+                data: message.serialize_to_vec(),
+            });
+
+            let mut tx_gas_meter = TxGasMeter::new(gas_limit);
+            match protocol::apply_wasm_tx(
+                tx,
+                &TxIndex::default(),
+                ShellParams::new(
+                    &mut tx_gas_meter,
+                    &mut self.wl_storage,
+                    &mut self.vp_wasm_cache,
+                    &mut self.tx_wasm_cache,
+                ),
+            ) {
+                Ok(result) if result.is_accepted() => {
+                    tracing::info!(
+                        ?message,
+                        "Dispatched an Ethereum contract message"
+                    );
+                    self.wl_storage.commit_tx();
+                    event["code"] = ErrorCodes::Ok.into();
+                    event["gas_used"] = result.gas_used.to_string();
+                    event["info"] = result.to_string();
+                }
+                Ok(result) => {
+                    tracing::info!(
+                        ?message,
+                        rejected_vps = ?result.vps_result.rejected_vps,
+                        "The handler of an Ethereum contract message was \
+                         rejected"
+                    );
+                    self.wl_storage.drop_tx();
+                    event["code"] = ErrorCodes::InvalidTx.into();
+                    event["gas_used"] = result.gas_used.to_string();
+                    event["info"] = result.to_string();
+                }
+                Err(err) => {
+                    tracing::info!(
+                        ?message,
+                        "The handler of an Ethereum contract message failed \
+                         with: {err}"
+                    );
+                    self.wl_storage.drop_tx();
+                    event["code"] = ErrorCodes::WasmRuntimeError.into();
+                    event["gas_used"] =
+                        tx_gas_meter.get_tx_consumed_gas().to_string();
+                    event["info"] = err.to_string();
+                }
+            }
+            response.events.push(event);
+        }
+
+        // the messages left over are dispatched in the next blocks
+        let remaining: Vec<_> = messages.collect();
+        if !remaining.is_empty() {
+            tracing::info!(
+                remaining = remaining.len(),
+                "Deferring the dispatch of Ethereum contract messages to the \
+                 next block, as the block gas limit was reached"
+            );
+            contract_messages::enqueue(&mut self.wl_storage, remaining)?;
+            self.wl_storage.commit_tx();
+        }
+        Ok(())
+    }
+
     /// Calculate the new inflation rate, mint the new tokens to the PoS
     /// account, then update the reward products of the validators. This is
     /// executed while finalizing the first block of a new epoch and is applied
@@ -855,6 +1175,75 @@ where
         Ok(())
     }
 
+    /// Speculatively execute, in parallel, the decrypted txs at the start of
+    /// the block, before any of them is applied. The results are indexed by
+    /// the position of the txs in the block.
+    fn speculate_decrypted_txs(
+        &self,
+        txs: &[shim::request::ProcessedTx],
+    ) -> HashMap<usize, SpeculativeTx> {
+        // Speculations are made against the block write log only
+        if !self
+            .wl_storage
+            .write_log
+            .get_keys_with_precommit()
+            .is_empty()
+        {
+            return HashMap::new();
+        }
+        let mut batch = vec![];
+        let mut indices = vec![];
+        // The position in the queue of the wrapper of the next decrypted tx,
+        // following the order in which `finalize_block` pops them
+        let mut queue_index = 0;
+        for (tx_index, processed_tx) in txs.iter().enumerate() {
+            let tx = match Tx::try_from(processed_tx.tx.as_ref()) {
+                Ok(tx) => tx,
+                Err(_) => break,
+            };
+            let code = ErrorCodes::from_u32(processed_tx.result.code).unwrap();
+            if code == ErrorCodes::InvalidSig || tx.validate_tx().is_err() {
+                break;
+            }
+            let decrypted = match tx.header().tx_type {
+                TxType::Decrypted(inner) => {
+                    matches!(inner, DecryptedTx::Decrypted)
+                }
+                _ => break,
+            };
+            let gas_limit =
+                match self.wl_storage.storage.tx_queue.get(queue_index) {
+                    Some(tx_in_queue) => tx_in_queue.gas,
+                    None => break,
+                };
+            queue_index += 1;
+            if decrypted && code == ErrorCodes::Ok {
+                batch.push(BatchTx {
+                    tx,
+                    tx_index: TxIndex(
+                        tx_index
+                            .try_into()
+                            .expect("transaction index out of bounds"),
+                    ),
+                    gas_limit,
+                });
+                indices.push(tx_index);
+            }
+        }
+        // There is nothing to gain from a single tx
+        if batch.len() < 2 {
+            return HashMap::new();
+        }
+        let speculations = protocol::parallel::execute_speculatively(
+            &batch,
+            &self.wl_storage.storage,
+            &self.wl_storage.write_log,
+            &self.vp_wasm_cache,
+            &self.tx_wasm_cache,
+        );
+        indices.into_iter().zip(speculations).collect()
+    }
+
     // Write the inner tx hash to storage and remove the corresponding wrapper
     // hash since it's redundant (we check the inner tx hash too when validating
     // the wrapper). Requires the wrapper transaction as argument to recover
@@ -977,7 +1366,9 @@ mod test_finalize_block {
     };
     use namada::proto::{Code, Data, Section, Signature};
     use namada::types::dec::POS_DECIMAL_PRECISION;
-    use namada::types::ethereum_events::{EthAddress, Uint as ethUint};
+    use namada::types::ethereum_events::{
+        ContractMessage, EthAddress, Uint as ethUint,
+    };
     use namada::types::hash::Hash;
     use namada::types::keccak::KeccakHash;
     use namada::types::key::testing::common_sk_from_simple_seed;
@@ -3547,6 +3938,136 @@ mod test_finalize_block {
         )
     }
 
+    /// Test that pending Ethereum contract messages are dispatched to the
+    /// handlers of their targets, which pay the block proposer for the
+    /// handler's gas
+    #[test]
+    fn test_eth_contract_messages_dispatch() {
+        let (mut shell, _, _, _) = setup();
+
+        let validator = shell.mode.get_validator_address().unwrap().to_owned();
+        let pos_params =
+            namada_proof_of_stake::read_pos_params(&shell.wl_storage).unwrap();
+        let consensus_key =
+            namada_proof_of_stake::validator_consensus_key_handle(&validator)
+                .get(&shell.wl_storage, Epoch::default(), &pos_params)
+                .unwrap()
+                .unwrap();
+        let proposer_address = HEXUPPER
+            .decode(consensus_key.tm_raw_hash().as_bytes())
+            .unwrap();
+        let native_token = shell.wl_storage.storage.native_token.clone();
+
+        // store the code of the handler
+        let handler_code = TestWasms::TxNoOp.read_bytes();
+        let handler = Hash::sha256(&handler_code);
+        let handler_len = u64::try_from(handler_code.len()).unwrap();
+        shell
+            .wl_storage
+            .storage
+            .write(&Key::wasm_code(&handler), handler_code)
+            .unwrap();
+        shell
+            .wl_storage
+            .storage
+            .write(
+                &Key::wasm_code_len(&handler),
+                handler_len.serialize_to_vec(),
+            )
+            .unwrap();
+
+        // the funded target, the target without a handler and the target
+        // unable to pay for its handler
+        let funded = address::testing::established_address_1();
+        let unhandled = address::testing::established_address_2();
+        let unfunded = address::testing::established_address_3();
+        for target in [&funded, &unfunded] {
+            shell
+                .wl_storage
+                .write(&contract_messages::handler_key(target), handler)
+                .unwrap();
+        }
+        shell
+            .wl_storage
+            .write(
+                &token::balance_key(&native_token, &funded),
+                Amount::native_whole(10),
+            )
+            .unwrap();
+        let fee = namada::ledger::parameters::read_gas_cost(
+            &shell.wl_storage,
+            &native_token,
+        )
+        .unwrap()
+        .unwrap()
+        .checked_mul(contract_messages::HANDLER_GAS_LIMIT.into())
+        .unwrap();
+        let proposer_balance = storage_api::token::read_balance(
+            &shell.wl_storage,
+            &native_token,
+            &validator,
+        )
+        .unwrap();
+
+        let messages =
+            [&funded, &unhandled, &unfunded].map(|target| ContractMessage {
+                source: EthAddress([1; 20]),
+                target: target.clone(),
+                payload: b"payload".to_vec(),
+            });
+        contract_messages::enqueue(&mut shell.wl_storage, messages).unwrap();
+        shell.wl_storage.commit_tx();
+
+        let events = shell
+            .finalize_block(FinalizeBlock {
+                proposer_address,
+                ..Default::default()
+            })
+            .expect("Test failed");
+        let codes: Vec<_> = events
+            .iter()
+            .filter(|event| event.event_type == EventType::EthContractMessage)
+            .map(|event| event["code"].clone())
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                String::from(ErrorCodes::Ok),
+                String::from(ErrorCodes::InvalidTx),
+                String::from(ErrorCodes::FeeError),
+            ]
+        );
+
+        // only the funded target paid for its handler
+        let funded_balance = storage_api::token::read_balance(
+            &shell.wl_storage,
+            &native_token,
+            &funded,
+        )
+        .unwrap();
+        assert_eq!(
+            funded_balance,
+            Amount::native_whole(10).checked_sub(fee).unwrap()
+        );
+        let new_proposer_balance = storage_api::token::read_balance(
+            &shell.wl_storage,
+            &native_token,
+            &validator,
+        )
+        .unwrap();
+        assert_eq!(
+            new_proposer_balance,
+            proposer_balance.checked_add(fee).unwrap()
+        );
+
+        // no messages are left pending
+        assert!(
+            contract_messages::take_pending(&mut shell.wl_storage)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_ledger_slashing() -> storage_api::Result<()> {
         let num_validators = 7_u64;
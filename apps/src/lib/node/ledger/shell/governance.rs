@@ -1,5 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
+use namada::core::ledger::eth_bridge::storage::flow_control::{
+    self, FlowParams,
+};
 use namada::core::ledger::governance::storage::keys as gov_storage;
 use namada::core::ledger::governance::storage::proposal::{
     AddRemove, PGFAction, ProposalType, StoragePgfFunding,
@@ -13,6 +16,7 @@ use namada::core::ledger::pgf::storage::keys as pgf_storage;
 use namada::core::ledger::pgf::storage::steward::StewardDetail;
 use namada::core::ledger::pgf::ADDRESS;
 use namada::core::ledger::storage_api::governance as gov_api;
+use namada::ledger::events::{Event, EventLevel, EventType};
 use namada::ledger::governance::utils::ProposalEvent;
 use namada::ledger::pos::BondId;
 use namada::ledger::protocol;
@@ -87,11 +91,20 @@ where
                             gov_storage::get_proposal_code_key(id);
                         let proposal_code =
                             shell.wl_storage.read_bytes(&proposal_code_key)?;
+                        let flow_params_before =
+                            flow_control::read_params(&shell.wl_storage)?;
                         let result = execute_default_proposal(
                             shell,
                             id,
                             proposal_code.clone(),
                         )?;
+                        let flow_params_after =
+                            flow_control::read_params(&shell.wl_storage)?;
+                        response.events.extend(bridge_flow_events(
+                            id,
+                            &flow_params_before,
+                            &flow_params_after,
+                        ));
                         tracing::info!(
                             "Governance proposal (default) {} has been \
                              executed ({}) and passed.",
@@ -199,6 +212,70 @@ where
     Ok(proposals_result)
 }
 
+/// Build an event for each direction of the Ethereum bridge that was
+/// paused or resumed by a governance proposal, and for each flow limit
+/// that it set, changed or removed.
+fn bridge_flow_events(
+    proposal_id: u64,
+    before: &FlowParams,
+    after: &FlowParams,
+) -> Vec<Event> {
+    let mut events = vec![];
+    for direction in before.paused.symmetric_difference(&after.paused) {
+        let paused = after.paused.contains(direction);
+        tracing::info!(
+            %direction,
+            paused,
+            "Governance proposal {} paused or resumed the transfers across \
+             the Ethereum bridge",
+            proposal_id
+        );
+        events.push(Event {
+            event_type: EventType::EthBridgeFlow,
+            level: EventLevel::Block,
+            attributes: HashMap::from([
+                ("kind".to_string(), "pause".to_string()),
+                ("direction".to_string(), direction.to_string()),
+                ("paused".to_string(), paused.to_string()),
+                ("proposal_id".to_string(), proposal_id.to_string()),
+            ]),
+        });
+    }
+    let changed_limits: BTreeSet<_> = before
+        .limits
+        .keys()
+        .chain(after.limits.keys())
+        .filter(|key| before.limits.get(key) != after.limits.get(key))
+        .collect();
+    for (asset, direction) in changed_limits {
+        let limit = after
+            .limits
+            .get(&(*asset, *direction))
+            .map(|limit| limit.to_string_native())
+            .unwrap_or_else(|| "none".to_string());
+        tracing::info!(
+            %asset,
+            %direction,
+            %limit,
+            "Governance proposal {} changed a flow limit of the Ethereum \
+             bridge",
+            proposal_id
+        );
+        events.push(Event {
+            event_type: EventType::EthBridgeFlow,
+            level: EventLevel::Block,
+            attributes: HashMap::from([
+                ("kind".to_string(), "limit".to_string()),
+                ("direction".to_string(), direction.to_string()),
+                ("asset".to_string(), asset.to_string()),
+                ("limit".to_string(), limit),
+                ("proposal_id".to_string(), proposal_id.to_string()),
+            ]),
+        });
+    }
+    events
+}
+
 fn compute_proposal_votes<S>(
     storage: &S,
     params: &PosParams,
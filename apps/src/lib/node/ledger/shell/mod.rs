@@ -45,8 +45,8 @@ use namada::ledger::protocol::{
 use namada::ledger::storage::wl_storage::WriteLogAndStorage;
 use namada::ledger::storage::write_log::WriteLog;
 use namada::ledger::storage::{
-    DBIter, Sha256Hasher, Storage, StorageHasher, TempWlStorage, WlStorage, DB,
-    EPOCH_SWITCH_BLOCKS_DELAY,
+    DBIter, EthOracleProgress, Sha256Hasher, Storage, StorageHasher,
+    TempWlStorage, WlStorage, DB, EPOCH_SWITCH_BLOCKS_DELAY,
 };
 use namada::ledger::storage_api::tx::validate_tx_bytes;
 use namada::ledger::storage_api::{self, StorageRead};
@@ -288,6 +288,26 @@ impl EthereumReceiver {
         self.queue.iter().cloned().collect()
     }
 
+    /// Queue Ethereum events that were received from the oracle
+    /// before the ledger restarted, skipping those filtered out by
+    /// the provided predicate.
+    pub fn replay_events<F>(
+        &mut self,
+        events: Vec<EthereumEvent>,
+        mut keep_event: F,
+    ) -> usize
+    where
+        F: FnMut(&EthereumEvent) -> bool,
+    {
+        let mut replayed_events = 0;
+        for event in events {
+            if keep_event(&event) && self.queue.insert(event) {
+                replayed_events += 1;
+            }
+        }
+        replayed_events
+    }
+
     /// Remove the given [`EthereumEvent`] from the queue, if present.
     ///
     /// **INVARIANT:** This method preserves the sorting and de-duplication
@@ -321,6 +341,7 @@ impl ShellMode {
     }
 
     /// Get the protocol keypair for this validator.
+    #[cfg_attr(not(test), allow(dead_code))]
     pub fn get_protocol_key(&self) -> Option<&common::SecretKey> {
         match self {
             ShellMode::Validator {
@@ -333,7 +354,7 @@ impl ShellMode {
                         ..
                     },
                 ..
-            } => Some(protocol_keypair),
+            } => protocol_keypair.as_ref(),
             _ => None,
         }
     }
@@ -506,7 +527,14 @@ where
                         wallet_path.to_string_lossy()
                     );
                     let mut wallet = crate::wallet::load(wallet_path)
-                        .expect("Validator node must have a wallet");
+                        .unwrap_or_else(|err| {
+                            panic!(
+                                "Unable to load the wallet of the validator \
+                                 node from {}: {}",
+                                wallet_path.to_string_lossy(),
+                                err
+                            )
+                        });
                     let validator_local_config_path =
                         wallet_path.join("validator_local_config.toml");
 
@@ -514,8 +542,10 @@ where
                         if Path::is_file(&validator_local_config_path) {
                             Some(
                                 toml::from_slice(
-                                    &std::fs::read(validator_local_config_path)
-                                        .unwrap(),
+                                    &std::fs::read(
+                                        &validator_local_config_path,
+                                    )
+                                    .unwrap(),
                                 )
                                 .unwrap(),
                             )
@@ -523,18 +553,30 @@ where
                             None
                         };
 
-                    wallet
-                        .take_validator_data()
-                        .map(|data| ShellMode::Validator {
-                            data,
-                            broadcast_sender,
-                            eth_oracle,
-                            local_config: validator_local_config,
-                        })
-                        .expect(
-                            "Validator data should have been stored in the \
-                             wallet",
-                        )
+                    let data = wallet.take_validator_data().expect(
+                        "Validator data should have been stored in the wallet",
+                    );
+                    let has_protocol_key_signer = validator_local_config
+                        .as_ref()
+                        .map_or(false, |config| {
+                            config.protocol_key_signer.is_some()
+                        });
+                    if data.keys.protocol_keypair.is_none()
+                        && !has_protocol_key_signer
+                    {
+                        panic!(
+                            "The validator's protocol key must be either \
+                             stored in the wallet or held by the signing \
+                             daemon configured in {}",
+                            validator_local_config_path.to_string_lossy()
+                        );
+                    }
+                    ShellMode::Validator {
+                        data,
+                        broadcast_sender,
+                        eth_oracle,
+                        local_config: validator_local_config,
+                    }
                 }
                 #[cfg(test)]
                 {
@@ -545,7 +587,7 @@ where
                             address: crate::wallet::defaults::validator_address(
                             ),
                             keys: ValidatorKeys {
-                                protocol_keypair,
+                                protocol_keypair: Some(protocol_keypair),
                                 eth_bridge_keypair,
                             },
                         },
@@ -583,6 +625,7 @@ where
             // TODO: config event log params
             event_log: EventLog::default(),
         };
+        shell.restore_eth_oracle_progress();
         shell.update_eth_oracle(&Default::default());
         shell
     }
@@ -854,6 +897,7 @@ where
         response.data = root.0.to_vec().into();
 
         self.bump_last_processed_eth_block();
+        self.persist_eth_oracle_progress();
         self.broadcast_queued_txs();
 
         response
@@ -887,9 +931,94 @@ where
                      blocks"
                 ),
             }
+            for endpoint in eth_oracle.control_sender.endpoint_health() {
+                if !endpoint.is_healthy() {
+                    tracing::warn!(
+                        url = %endpoint.url,
+                        consecutive_failures = endpoint.consecutive_failures,
+                        last_error = ?endpoint.last_error,
+                        "Ethereum oracle's RPC endpoint is unhealthy"
+                    );
+                }
+            }
         }
     }
 
+    /// Persist the events sent by the Ethereum oracle whose vote extensions
+    /// have not been included on chain yet, such that they can be replayed
+    /// after a restart. The oracle's last processed block is not persisted
+    /// here, since it was already committed with the block's state.
+    fn persist_eth_oracle_progress(&mut self) {
+        let queries = self.wl_storage.ethbridge_queries();
+        let ShellMode::Validator {
+            eth_oracle:
+                Some(EthereumOracleChannels {
+                    ethereum_receiver, ..
+                }),
+            ..
+        } = &mut self.mode
+        else {
+            return;
+        };
+        ethereum_receiver
+            .fill_queue(|event| queries.validate_eth_event_nonce(event));
+        let progress = EthOracleProgress {
+            pending_events: ethereum_receiver.get_events(),
+        };
+        if let Err(error) = self
+            .wl_storage
+            .storage
+            .db
+            .write_eth_oracle_progress(&progress)
+        {
+            tracing::error!(
+                %error,
+                "Failed to persist the progress of the Ethereum oracle"
+            );
+        }
+    }
+
+    /// Restore the progress of the Ethereum oracle persisted before the
+    /// ledger was restarted. Events whose vote extensions made it on chain
+    /// in the meantime, as recorded in the vote tallies, are not replayed.
+    fn restore_eth_oracle_progress(&mut self) {
+        let ShellMode::Validator {
+            data,
+            eth_oracle:
+                Some(EthereumOracleChannels {
+                    ethereum_receiver, ..
+                }),
+            ..
+        } = &mut self.mode
+        else {
+            return;
+        };
+        let progress =
+            match self.wl_storage.storage.db.read_eth_oracle_progress() {
+                Ok(Some(progress)) => progress,
+                Ok(None) => return,
+                Err(error) => {
+                    tracing::error!(
+                        %error,
+                        "Failed to read the progress of the Ethereum oracle"
+                    );
+                    return;
+                }
+            };
+        let queries = self.wl_storage.ethbridge_queries();
+        let pending_events = progress.pending_events.len();
+        let replayed_events =
+            ethereum_receiver.replay_events(progress.pending_events, |event| {
+                queries.validate_eth_event_nonce(event)
+                    && !queries.has_voted_on_eth_event(&data.address, event)
+            });
+        tracing::info!(
+            replayed_events,
+            skipped_events = pending_events - replayed_events,
+            "Replayed Ethereum events received before the ledger restarted"
+        );
+    }
+
     /// Empties all the ledger's queues of transactions to be broadcasted
     /// via CometBFT's P2P network.
     #[inline]
@@ -906,16 +1035,10 @@ where
 
         let ext = self.craft_extension();
 
-        let protocol_key = self
-            .mode
-            .get_protocol_key()
-            .expect("Validators should have protocol keys");
-
-        let protocol_txs = iter_protocol_txs(ext).map(|protocol_tx| {
-            protocol_tx
-                .sign(protocol_key, self.chain_id.clone())
-                .to_bytes()
-        });
+        let protocol_txs: Vec<_> = iter_protocol_txs(ext)
+            .filter_map(|protocol_tx| self.sign_protocol_tx(&protocol_tx))
+            .map(|tx| tx.to_bytes())
+            .collect();
 
         for tx in protocol_txs {
             self.mode.broadcast(tx);
@@ -943,16 +1066,10 @@ where
             return;
         }
         if let Some(vote_extension) = self.sign_ethereum_events(eth_events) {
-            let protocol_key = self
-                .mode
-                .get_protocol_key()
-                .expect("Validators should have protocol keys");
-
-            let signed_tx = EthereumTxData::EthEventsVext(vote_extension)
-                .sign(protocol_key, self.chain_id.clone())
-                .to_bytes();
-
-            self.mode.broadcast(signed_tx);
+            let protocol_tx = EthereumTxData::EthEventsVext(vote_extension);
+            if let Some(signed_tx) = self.sign_protocol_tx(&protocol_tx) {
+                self.mode.broadcast(signed_tx.to_bytes());
+            }
         }
     }
 
@@ -1590,7 +1707,7 @@ mod test_utils {
     use namada::ledger::storage_api::StorageWrite;
     use namada::proof_of_stake::parameters::PosParams;
     use namada::proof_of_stake::validator_consensus_key_handle;
-    use namada::proto::{Code, Data};
+    use namada::proto::{Code, Data, SignableEthMessage, Signed};
     use namada::tendermint::abci::types::VoteInfo;
     use namada::types::address;
     use namada::types::chain::ChainId;
@@ -1601,6 +1718,7 @@ mod test_utils {
     use namada::types::storage::{BlockHash, Epoch, Header};
     use namada::types::time::{DateTimeUtc, DurationSecs};
     use namada::types::transaction::{Fee, TxType, WrapperTx};
+    use namada::types::vote_extensions::bridge_pool_roots;
     use tempfile::tempdir;
     use tokio::sync::mpsc::{Sender, UnboundedReceiver};
 
@@ -1707,6 +1825,25 @@ mod test_utils {
         KeccakHash(output)
     }
 
+    /// Sign Namada's state root at the given height, as a
+    /// validator would in its Bridge pool root vote extension.
+    pub fn sign_state_root(
+        shell: &TestShell,
+        hot_key: &common::SecretKey,
+        height: BlockHeight,
+    ) -> common::Signature {
+        let root = shell
+            .wl_storage
+            .ethbridge_queries()
+            .get_state_root_at_height(height)
+            .unwrap_or_default();
+        Signed::<_, SignableEthMessage>::new(
+            hot_key,
+            bridge_pool_roots::state_root_msg(&root, height),
+        )
+        .sig
+    }
+
     /// A wrapper around the shell that implements
     /// Drop so as to clean up the files that it
     /// generates. Also allows illegal state
@@ -2394,6 +2531,45 @@ mod shell_tests {
         );
     }
 
+    /// Test that the Ethereum events received from the oracle are
+    /// persisted along with its progress, and replayed after a restart
+    /// unless the validator's vote on them has already been tallied.
+    #[test]
+    fn test_eth_oracle_progress_replay() {
+        use std::collections::BTreeMap;
+
+        use namada::eth_bridge::storage::vote_tallies;
+        use namada::ledger::storage_api::StorageWrite;
+
+        let (mut shell, _, oracle, _) = test_utils::setup_at_height(3);
+        let event = EthereumEvent::TransfersToNamada {
+            nonce: 0u64.into(),
+            transfers: vec![],
+        };
+        oracle.blocking_send(event.clone()).expect("Test failed");
+        shell.persist_eth_oracle_progress();
+
+        // simulate a restart, losing the in-memory state of the oracle
+        shell.mode.dequeue_eth_event(&event);
+        shell.restore_eth_oracle_progress();
+        assert_eq!(shell.new_ethereum_events(), vec![event.clone()]);
+
+        // once the validator's vote is on chain, the event is not replayed
+        let validator = shell
+            .mode
+            .get_validator_address()
+            .expect("Test failed")
+            .clone();
+        let seen_by = BTreeMap::from([(validator, BlockHeight(3))]);
+        shell
+            .wl_storage
+            .write(&vote_tallies::Keys::from(&event).seen_by(), seen_by)
+            .expect("Test failed");
+        shell.mode.dequeue_eth_event(&event);
+        shell.restore_eth_oracle_progress();
+        assert!(shell.new_ethereum_events().is_empty());
+    }
+
     /// Test that Ethereum events with outdated nonces are
     /// not validated by `CheckTx`.
     #[test]
@@ -2505,6 +2681,11 @@ mod shell_tests {
                 block_height: shell.wl_storage.storage.get_last_block_height(),
                 validator_addr: address,
                 sig,
+                state_root_sig: test_utils::sign_state_root(
+                    &shell,
+                    hot_key,
+                    shell.wl_storage.storage.get_last_block_height(),
+                ),
             }
             .sign(protocol_key),
         )
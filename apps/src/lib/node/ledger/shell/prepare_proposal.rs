@@ -1132,6 +1132,8 @@ mod test_prepare_proposal {
                     namada::core::types::address::nam(),
                     Amount::from(1),
                 )]),
+                protocol_key_signer: None,
+                protocol_key_signer_timeout_ms: 1000,
             });
         }
 
@@ -1224,6 +1226,8 @@ mod test_prepare_proposal {
                     namada::core::types::address::nam(),
                     Amount::from(100),
                 )]),
+                protocol_key_signer: None,
+                protocol_key_signer_timeout_ms: 1000,
             });
         }
 
@@ -708,7 +708,7 @@ mod test_process_proposal {
     use super::*;
     use crate::node::ledger::shell::test_utils::{
         self, deactivate_bridge, gen_keypair, get_bp_bytes_to_sign,
-        ProcessProposal, TestError, TestShell,
+        sign_state_root, ProcessProposal, TestError, TestShell,
     };
     use crate::node::ledger::shims::abcipp_shim_types::shim::request::ProcessedTx;
     use crate::wallet;
@@ -781,6 +781,11 @@ mod test_process_proposal {
             block_height: shell.wl_storage.storage.get_last_block_height(),
             validator_addr: addr.clone(),
             sig,
+            state_root_sig: sign_state_root(
+                &shell,
+                shell.mode.get_eth_bridge_keypair().expect("Test failed"),
+                shell.wl_storage.storage.get_last_block_height(),
+            ),
         }
         .sign(shell.mode.get_protocol_key().expect("Test failed"));
         let tx = EthereumTxData::BridgePoolVext(vote_ext)
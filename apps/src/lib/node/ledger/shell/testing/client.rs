@@ -1,5 +1,6 @@
 use clap::Command as App;
 use eyre::Report;
+use namada::tendermint_rpc::WebSocketClientUrl;
 use namada::types::io::Io;
 use namada_sdk::error::Error as SdkError;
 use tendermint_config::net::Address as TendermintAddress;
@@ -10,20 +11,28 @@ use crate::cli::args::Global;
 use crate::cli::{args, cmds, Cmd, Context, NamadaClient, NamadaRelayer};
 use crate::node::ledger::shell::testing::utils::{Bin, TestingIo};
 
+/// The global arguments of the client of the given node
+fn global(node: &MockNode) -> Global {
+    let locked = node.shell.lock().unwrap();
+    Global {
+        is_pre_genesis: false,
+        chain_id: Some(locked.chain_id.clone()),
+        base_dir: locked.base_dir.clone(),
+        wasm_dir: Some(locked.wasm_dir.clone()),
+    }
+}
+
+/// Make the context of a client of the given node
+pub fn context(node: &MockNode) -> Result<Context, Report> {
+    Context::new::<TestingIo>(global(node))
+}
+
 pub fn run(
     node: &MockNode,
     who: Bin,
     mut args: Vec<&str>,
 ) -> Result<(), Report> {
-    let global = {
-        let locked = node.shell.lock().unwrap();
-        Global {
-            is_pre_genesis: false,
-            chain_id: Some(locked.chain_id.clone()),
-            base_dir: locked.base_dir.clone(),
-            wasm_dir: Some(locked.wasm_dir.clone()),
-        }
-    };
+    let global = global(node);
     let ctx = Context::new::<TestingIo>(global.clone())?;
 
     let rt = tokio::runtime::Runtime::new().unwrap();
@@ -81,6 +90,9 @@ pub fn run(
                 cmds::NamadaRelayer::ValidatorSet(sub_cmd) => {
                     NamadaRelayer::ValidatorSet(sub_cmd)
                 }
+                cmds::NamadaRelayer::StateProof(sub_cmd) => {
+                    NamadaRelayer::StateProof(sub_cmd)
+                }
             };
             rt.block_on(CliApi::handle_relayer_command(
                 Some(node),
@@ -97,6 +109,10 @@ impl<'a> CliClient for &'a MockNode {
         unreachable!("MockNode should always be instantiated at test start.")
     }
 
+    fn events_url(_: &TendermintAddress) -> Option<WebSocketClientUrl> {
+        None
+    }
+
     async fn wait_until_node_is_synced(
         &self,
         _io: &impl Io,
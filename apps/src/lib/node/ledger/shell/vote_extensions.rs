@@ -4,13 +4,18 @@ pub mod bridge_pool_vext;
 pub mod eth_events;
 pub mod val_set_update;
 
+use borsh::BorshSerialize;
 use namada::proto::{SignableEthMessage, Signed};
+use namada::types::hash::Hash;
 use namada::types::keccak::keccak_hash;
 use namada::types::transaction::protocol::EthereumTxData;
 use namada::types::vote_extensions::{
     bridge_pool_roots, ethereum_events, validator_set_update, VoteExtension,
 };
 use namada_sdk::eth_bridge::{EthBridgeQueries, SendValsetUpd};
+#[cfg(unix)]
+use namada_sdk::signer::RemoteSigner;
+use namada_sdk::signer::SignPayload;
 
 use super::*;
 use crate::node::ledger::shims::abcipp_shim_types::shim::TxBytes;
@@ -56,6 +61,8 @@ pub enum VoteExtensionError {
     DivergesFromStorage,
     #[error("The signature of the Bridge pool root is invalid")]
     InvalidBPRootSig,
+    #[error("The signature of Namada's state root is invalid")]
+    InvalidStateRootSig,
     #[error(
         "Received a vote extension for the Ethereum bridge which is currently \
          not active"
@@ -118,12 +125,7 @@ where
             tracing::debug!("New Ethereum events - {:#?}", ext.ethereum_events);
         }
 
-        let protocol_key = match &self.mode {
-            ShellMode::Validator { data, .. } => &data.keys.protocol_keypair,
-            _ => unreachable!("{VALIDATOR_EXPECT_MSG}"),
-        };
-
-        Some(ext.sign(protocol_key))
+        self.sign_with_protocol_key(ext)
     }
 
     /// Extend PreCommit votes with [`bridge_pool_roots::Vext`] instances.
@@ -152,14 +154,19 @@ where
             .get_eth_bridge_keypair()
             .expect(VALIDATOR_EXPECT_MSG);
         let signed = Signed::<_, SignableEthMessage>::new(eth_key, to_sign);
+        let block_height = self.wl_storage.storage.get_last_block_height();
+        let state_root = Hash(self.wl_storage.storage.merkle_root().0);
+        let state_root_sig = Signed::<_, SignableEthMessage>::new(
+            eth_key,
+            bridge_pool_roots::state_root_msg(&state_root, block_height),
+        );
         let ext = bridge_pool_roots::Vext {
-            block_height: self.wl_storage.storage.get_last_block_height(),
+            block_height,
             validator_addr,
             sig: signed.sig,
+            state_root_sig: state_root_sig.sig,
         };
-        let protocol_key =
-            self.mode.get_protocol_key().expect(VALIDATOR_EXPECT_MSG);
-        Some(ext.sign(protocol_key))
+        self.sign_with_protocol_key(ext)
     }
 
     /// Extend PreCommit votes with [`validator_set_update::Vext`]
@@ -208,6 +215,103 @@ where
             })
     }
 
+    /// Get the signer of this validator's protocol key: either the
+    /// configured signing daemon, with the protocol key registered on chain,
+    /// or the wallet.
+    fn protocol_key_signer(&self) -> Result<ProtocolKeySigner<'_>, String> {
+        let ShellMode::Validator {
+            data, local_config, ..
+        } = &self.mode
+        else {
+            unreachable!("{VALIDATOR_EXPECT_MSG}");
+        };
+        #[cfg_attr(not(unix), allow(unused_variables))]
+        let Some(ValidatorLocalConfig {
+            protocol_key_signer: Some(socket),
+            protocol_key_signer_timeout_ms: timeout_ms,
+            ..
+        }) = local_config
+        else {
+            return data
+                .keys
+                .get_protocol_keypair()
+                .map(ProtocolKeySigner::Wallet)
+                .ok_or_else(|| {
+                    "The wallet holds no protocol key and no signing daemon is \
+                     configured"
+                        .to_string()
+                });
+        };
+        #[cfg(unix)]
+        {
+            let params = read_pos_params(&self.wl_storage)
+                .map_err(|err| err.to_string())?;
+            let epoch = self.wl_storage.storage.get_current_epoch().0;
+            let public_key =
+                proof_of_stake::validator_protocol_key_handle(&data.address)
+                    .get(&self.wl_storage, epoch, &params)
+                    .map_err(|err| err.to_string())?
+                    .ok_or_else(|| {
+                        "The validator has no protocol key on chain".to_string()
+                    })?;
+            let timeout = std::time::Duration::from_millis(*timeout_ms);
+            Ok(ProtocolKeySigner::Remote(
+                RemoteSigner::new(socket).with_timeout(timeout),
+                public_key,
+            ))
+        }
+        #[cfg(not(unix))]
+        Err(format!(
+            "Signing daemons are only supported on Unix, unable to reach {}",
+            socket.display()
+        ))
+    }
+
+    /// Sign the given data, e.g. a vote extension, with this validator's
+    /// protocol key. Returns `None` if the protocol key signer failed.
+    pub fn sign_with_protocol_key<T>(&self, data: T) -> Option<Signed<T>>
+    where
+        T: BorshSerialize,
+    {
+        let payload = SignPayload::Data(data.serialize_to_vec());
+        self.protocol_key_signer()
+            .and_then(|signer| signer.sign(&payload))
+            .map(|sig| Signed::new_from(data, sig))
+            .map_err(|err| {
+                tracing::error!(
+                    %err,
+                    "Failed to sign data with the protocol key"
+                );
+            })
+            .ok()
+    }
+
+    /// Sign the given protocol transaction with this validator's protocol
+    /// key. Returns `None` if the protocol key signer failed.
+    pub fn sign_protocol_tx(&self, data: &EthereumTxData) -> Option<Tx> {
+        let sign = || {
+            let signer = self.protocol_key_signer()?;
+            let public_key = signer.public_key();
+            let mut tx =
+                data.unsigned_tx(public_key.clone(), self.chain_id.clone());
+            let sig = signer.sign(&SignPayload::Sections(tx.clone()))?;
+            tx.add_section(Section::Signature(proto::Signature {
+                targets: tx.sechashes(),
+                signer: proto::Signer::PubKeys(vec![public_key]),
+                signatures: [(0, sig)].into(),
+            }));
+            Ok(tx)
+        };
+        sign()
+            .map_err(|err: String| {
+                tracing::error!(
+                    %err,
+                    "Failed to sign a protocol tx with the protocol key"
+                );
+            })
+            .ok()
+    }
+
     /// Given a slice of [`TxBytes`], return an iterator over the
     /// ones we could deserialize to vote extension protocol txs.
     pub fn deserialize_vote_extensions<'shell>(
@@ -263,6 +367,46 @@ where
     }
 }
 
+/// The signer of a validator's protocol key
+enum ProtocolKeySigner<'shell> {
+    /// The key is held by the wallet
+    Wallet(&'shell common::SecretKey),
+    /// The given key is held by a signing daemon
+    #[cfg(unix)]
+    Remote(RemoteSigner, common::PublicKey),
+}
+
+impl ProtocolKeySigner<'_> {
+    /// The protocol key
+    fn public_key(&self) -> common::PublicKey {
+        match self {
+            Self::Wallet(secret_key) => secret_key.ref_to(),
+            #[cfg(unix)]
+            Self::Remote(_, public_key) => public_key.clone(),
+        }
+    }
+
+    /// Sign the given payload with the protocol key
+    fn sign(&self, payload: &SignPayload) -> Result<common::Signature, String> {
+        match self {
+            Self::Wallet(secret_key) => Ok(common::SigScheme::sign(
+                *secret_key,
+                payload.signed_hash(&secret_key.ref_to()),
+            )),
+            #[cfg(unix)]
+            Self::Remote(signer, public_key) => signer
+                .sign_blocking(public_key, payload)
+                .map_err(|err| err.to_string())?
+                .ok_or_else(|| {
+                    format!(
+                        "The signing daemon doesn't hold the protocol key {}",
+                        public_key
+                    )
+                }),
+        }
+    }
+}
+
 /// Yields an iterator over the protocol transactions
 /// in a [`VoteExtension`].
 pub fn iter_protocol_txs(
@@ -281,3 +425,152 @@ pub fn iter_protocol_txs(
     .into_iter()
     .flatten()
 }
+
+#[cfg(test)]
+mod test_protocol_key_signer {
+    use namada::types::key::*;
+    use namada::types::storage::BlockHeight;
+    use namada::types::transaction::protocol::EthereumTxData;
+    use namada::types::vote_extensions::ethereum_events;
+
+    use crate::config::ValidatorLocalConfig;
+    use crate::node::ledger::shell::test_utils::*;
+    use crate::node::ledger::shell::ShellMode;
+
+    fn vext(shell: &TestShell) -> ethereum_events::Vext {
+        ethereum_events::Vext {
+            block_height: BlockHeight(1),
+            ethereum_events: vec![],
+            validator_addr: shell
+                .mode
+                .get_validator_address()
+                .expect("Test failed")
+                .clone(),
+        }
+    }
+
+    /// Test that without a configured signer, the protocol key of the wallet
+    /// signs like it did before signers could be configured.
+    #[test]
+    fn test_wallet_protocol_key() {
+        let (shell, _, _, _) = setup();
+        let protocol_key =
+            shell.mode.get_protocol_key().expect("Test failed").clone();
+        let ext = vext(&shell);
+
+        let signed = shell.sign_with_protocol_key(ext.clone()).unwrap();
+        assert_eq!(signed.sig, ext.clone().sign(&protocol_key).sig);
+
+        let protocol_tx = EthereumTxData::EthEventsVext(signed);
+        assert_eq!(
+            shell.sign_protocol_tx(&protocol_tx).unwrap().to_bytes(),
+            protocol_tx
+                .sign(&protocol_key, shell.chain_id.clone())
+                .to_bytes()
+        );
+    }
+
+    /// Test that without a protocol key in the wallet nor a configured
+    /// signer, nothing gets signed.
+    #[test]
+    fn test_missing_protocol_key() {
+        let (mut shell, _, _, _) = setup();
+        if let ShellMode::Validator { data, .. } = &mut shell.mode {
+            data.keys.protocol_keypair = None;
+        }
+        let ext = vext(&shell);
+
+        assert!(shell.sign_with_protocol_key(ext.clone()).is_none());
+        let protocol_tx = EthereumTxData::EthEventsVext(
+            ext.sign(&namada::types::key::testing::keypair_1()),
+        );
+        assert!(shell.sign_protocol_tx(&protocol_tx).is_none());
+    }
+
+    /// Test signing with the protocol key held by a signing daemon, while
+    /// the wallet holds no protocol key.
+    #[cfg(unix)]
+    #[test]
+    fn test_remote_protocol_key() {
+        use namada_sdk::error::Error;
+        use namada_sdk::signer::{serve, SignPayload, Signer};
+
+        /// A signer holding a single secret key
+        struct KeySigner(common::SecretKey);
+
+        #[cfg_attr(feature = "async-send", async_trait::async_trait)]
+        #[cfg_attr(
+            not(feature = "async-send"),
+            async_trait::async_trait(?Send)
+        )]
+        impl Signer for KeySigner {
+            async fn find_public_key(
+                &self,
+                _pkh: &PublicKeyHash,
+            ) -> Result<Option<common::PublicKey>, Error> {
+                Ok(Some(self.0.ref_to()))
+            }
+
+            async fn sign(
+                &self,
+                public_key: &common::PublicKey,
+                payload: &SignPayload,
+            ) -> Result<Option<common::Signature>, Error> {
+                Ok((self.0.ref_to() == *public_key).then(|| {
+                    common::SigScheme::sign(
+                        &self.0,
+                        payload.signed_hash(public_key),
+                    )
+                }))
+            }
+        }
+
+        let (mut shell, _, _, _) = setup();
+        let protocol_key =
+            shell.mode.get_protocol_key().expect("Test failed").clone();
+        let protocol_pk = protocol_key.ref_to();
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("signer.sock");
+        if let ShellMode::Validator {
+            data, local_config, ..
+        } = &mut shell.mode
+        {
+            data.keys.protocol_keypair = None;
+            *local_config = Some(ValidatorLocalConfig {
+                accepted_gas_tokens: Default::default(),
+                protocol_key_signer: Some(socket.clone()),
+                protocol_key_signer_timeout_ms: 1000,
+            });
+        }
+        let ext = vext(&shell);
+
+        // The daemon isn't running yet
+        assert!(shell.sign_with_protocol_key(ext.clone()).is_none());
+
+        // A daemon that doesn't answer in time leaves the data unsigned
+        let unresponsive =
+            std::os::unix::net::UnixListener::bind(&socket).unwrap();
+        assert!(shell.sign_with_protocol_key(ext.clone()).is_none());
+        drop(unresponsive);
+        std::fs::remove_file(&socket).unwrap();
+
+        let daemon_socket = socket.clone();
+        std::thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(serve(&daemon_socket, &KeySigner(protocol_key)))
+        });
+        while !socket.exists() {
+            std::thread::yield_now();
+        }
+
+        let signed = shell.sign_with_protocol_key(ext).unwrap();
+        assert!(signed.verify(&protocol_pk).is_ok());
+
+        let protocol_tx = EthereumTxData::EthEventsVext(signed);
+        let tx = shell.sign_protocol_tx(&protocol_tx).unwrap();
+        assert!(tx.verify_signature(&protocol_pk, &tx.sechashes()).is_ok());
+    }
+}
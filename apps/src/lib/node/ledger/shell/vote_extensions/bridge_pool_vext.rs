@@ -27,6 +27,7 @@ where
     ///  * The validator correctly signed the extension.
     ///  * The validator signed over the correct height inside of the extension.
     ///  * Check that the inner signature is valid.
+    ///  * Check that the signature over Namada's state root is valid.
     #[inline]
     #[allow(dead_code)]
     pub fn validate_bp_roots_vext(
@@ -125,7 +126,15 @@ where
             .wl_storage
             .ethbridge_queries()
             .get_bridge_pool_root_at_height(ext.data.block_height)
-            .expect("We asserted that the queried height is correct")
+            .ok_or_else(|| {
+                tracing::debug!(
+                    height = ?ext.data.block_height,
+                    "Could not find the Bridge pool root at the height of a \
+                     Bridge pool root's vote extension, which may have been \
+                     pruned"
+                );
+                VoteExtensionError::UnexpectedBlockHeight
+            })?
             .0;
         let nonce = self
             .wl_storage
@@ -141,6 +150,37 @@ where
             .pos_queries()
             .read_validator_eth_hot_key(validator, Some(ext_height_epoch))
             .expect("A validator should have an Ethereum hot key in storage.");
+        signed.verify(&pk).map_err(|err| {
+            tracing::debug!(
+                ?err,
+                ?signed.sig,
+                ?pk,
+                %validator,
+                "Failed to verify the signature of an Bridge pool root \
+                issued by some validator."
+            );
+            VoteExtensionError::InvalidBPRootSig
+        })?;
+
+        let state_root = self
+            .wl_storage
+            .ethbridge_queries()
+            .get_state_root_at_height(ext.data.block_height)
+            .ok_or_else(|| {
+                tracing::debug!(
+                    height = ?ext.data.block_height,
+                    "Could not find Namada's state root at the height of a \
+                     Bridge pool root's vote extension"
+                );
+                VoteExtensionError::UnexpectedBlockHeight
+            })?;
+        let signed = Signed::<_, SignableEthMessage>::new_from(
+            bridge_pool_roots::state_root_msg(
+                &state_root,
+                ext.data.block_height,
+            ),
+            ext.data.state_root_sig.clone(),
+        );
         signed
             .verify(&pk)
             .map_err(|err| {
@@ -149,10 +189,10 @@ where
                     ?signed.sig,
                     ?pk,
                     %validator,
-                    "Failed to verify the signature of an Bridge pool root \
+                    "Failed to verify the signature of Namada's state root \
                     issued by some validator."
                 );
-                VoteExtensionError::InvalidBPRootSig
+                VoteExtensionError::InvalidStateRootSig
             })
             .map(|_| (voting_power, ext))
     }
@@ -222,6 +262,7 @@ mod test_bp_vote_extensions {
     use namada_sdk::eth_bridge::EthBridgeQueries;
 
     use crate::node::ledger::shell::test_utils::*;
+    use crate::node::ledger::shell::vote_extensions::VoteExtensionError;
     use crate::node::ledger::shims::abcipp_shim_types::shim::request::FinalizeBlock;
     use crate::wallet::defaults::{bertha_address, bertha_keypair};
 
@@ -299,17 +340,22 @@ mod test_bp_vote_extensions {
         assert_eq!(shell.start_new_epoch(Some(req)).0, 1);
 
         // Check that Bertha's vote extensions pass validation.
+        shell.wl_storage.storage.block.height =
+            shell.wl_storage.storage.get_last_block_height();
+        shell.commit();
         let to_sign = get_bp_bytes_to_sign();
         let sig = Signed::<_, SignableEthMessage>::new(&hot_key, to_sign).sig;
         let vote_ext = bridge_pool_roots::Vext {
             block_height: shell.wl_storage.storage.get_last_block_height(),
             validator_addr: bertha_address(),
             sig,
+            state_root_sig: sign_state_root(
+                &shell,
+                &hot_key,
+                shell.wl_storage.storage.get_last_block_height(),
+            ),
         }
         .sign(&bertha_keypair());
-        shell.wl_storage.storage.block.height =
-            shell.wl_storage.storage.get_last_block_height();
-        shell.commit();
         assert!(shell.validate_bp_roots_vext(
             vote_ext,
             shell.wl_storage.storage.get_last_block_height()
@@ -341,6 +387,11 @@ mod test_bp_vote_extensions {
             block_height: shell.wl_storage.storage.get_last_block_height(),
             validator_addr: address,
             sig,
+            state_root_sig: sign_state_root(
+                &shell,
+                shell.mode.get_eth_bridge_keypair().expect("Test failed"),
+                shell.wl_storage.storage.get_last_block_height(),
+            ),
         }
         .sign(shell.mode.get_protocol_key().expect("Test failed"));
         assert_eq!(
@@ -377,6 +428,11 @@ mod test_bp_vote_extensions {
             block_height: shell.wl_storage.storage.get_last_block_height(),
             validator_addr: address,
             sig,
+            state_root_sig: sign_state_root(
+                &shell,
+                shell.mode.get_eth_bridge_keypair().expect("Test failed"),
+                shell.wl_storage.storage.get_last_block_height(),
+            ),
         }
         .sign(shell.mode.get_protocol_key().expect("Test failed"));
         let valid = shell
@@ -411,6 +467,11 @@ mod test_bp_vote_extensions {
             block_height: shell.wl_storage.storage.get_last_block_height(),
             validator_addr: address,
             sig,
+            state_root_sig: sign_state_root(
+                &shell,
+                &signing_key,
+                shell.wl_storage.storage.get_last_block_height(),
+            ),
         }
         .sign(shell.mode.get_protocol_key().expect("Test failed"));
         assert!(!shell.validate_bp_roots_vext(
@@ -441,6 +502,11 @@ mod test_bp_vote_extensions {
             block_height: shell.wl_storage.storage.get_last_block_height(),
             validator_addr: address,
             sig,
+            state_root_sig: sign_state_root(
+                &shell,
+                shell.mode.get_eth_bridge_keypair().expect("Test failed"),
+                shell.wl_storage.storage.get_last_block_height(),
+            ),
         }
         .sign(&bertha_keypair());
         assert!(!shell.validate_bp_roots_vext(
@@ -461,6 +527,11 @@ mod test_bp_vote_extensions {
             block_height: height,
             validator_addr: address,
             sig,
+            state_root_sig: sign_state_root(
+                &shell,
+                shell.mode.get_eth_bridge_keypair().expect("Test failed"),
+                height,
+            ),
         }
         .sign(shell.mode.get_protocol_key().expect("Test failed"));
 
@@ -505,6 +576,11 @@ mod test_bp_vote_extensions {
             block_height: shell.wl_storage.storage.get_last_block_height(),
             validator_addr: address,
             sig,
+            state_root_sig: sign_state_root(
+                &shell,
+                shell.mode.get_eth_bridge_keypair().expect("Test failed"),
+                shell.wl_storage.storage.get_last_block_height(),
+            ),
         }
         .sign(shell.mode.get_protocol_key().expect("Test failed"));
         assert!(!shell.validate_bp_roots_vext(
@@ -529,6 +605,11 @@ mod test_bp_vote_extensions {
             block_height: shell.wl_storage.storage.get_last_block_height(),
             validator_addr: address,
             sig,
+            state_root_sig: sign_state_root(
+                &shell,
+                shell.mode.get_eth_bridge_keypair().expect("Test failed"),
+                shell.wl_storage.storage.get_last_block_height(),
+            ),
         }
         .sign(shell.mode.get_protocol_key().expect("Test failed"));
         assert!(!shell.validate_bp_roots_vext(
@@ -537,6 +618,51 @@ mod test_bp_vote_extensions {
         ))
     }
 
+    /// Test that a bridge pool root vext is rejected
+    /// if the signature over Namada's state root is incorrect.
+    #[test]
+    fn test_incorrect_state_root_sig() {
+        let (mut shell, _broadcaster, _, _oracle_control_recv) =
+            setup_at_height(1u64);
+        let address = shell
+            .mode
+            .get_validator_address()
+            .expect("Test failed")
+            .clone();
+        shell.wl_storage.storage.block.height =
+            shell.wl_storage.storage.get_last_block_height();
+        shell.commit();
+        let hot_key = shell.mode.get_eth_bridge_keypair().expect("Test failed");
+        let last_height = shell.wl_storage.storage.get_last_block_height();
+        let sig = Signed::<_, SignableEthMessage>::new(
+            hot_key,
+            get_bp_bytes_to_sign(),
+        )
+        .sig;
+        // sign over the right root, at the wrong height
+        let root = shell
+            .wl_storage
+            .ethbridge_queries()
+            .get_state_root_at_height(last_height)
+            .expect("Test failed");
+        let state_root_sig = Signed::<_, SignableEthMessage>::new(
+            hot_key,
+            bridge_pool_roots::state_root_msg(&root, last_height + 1),
+        )
+        .sig;
+        let bp_root = bridge_pool_roots::Vext {
+            block_height: last_height,
+            validator_addr: address,
+            sig,
+            state_root_sig,
+        }
+        .sign(shell.mode.get_protocol_key().expect("Test failed"));
+        assert!(matches!(
+            shell.validate_bp_roots_vext_and_get_it_back(bp_root, last_height),
+            Err(VoteExtensionError::InvalidStateRootSig)
+        ));
+    }
+
     /// Test that we can verify vext from several block heights
     /// prior.
     #[test]
@@ -580,6 +706,11 @@ mod test_bp_vote_extensions {
             block_height: 2.into(),
             validator_addr: address.clone(),
             sig,
+            state_root_sig: sign_state_root(
+                &shell,
+                shell.mode.get_eth_bridge_keypair().expect("Test failed"),
+                2.into(),
+            ),
         }
         .sign(shell.mode.get_protocol_key().expect("Test failed"));
         assert!(shell.validate_bp_roots_vext(
@@ -596,6 +727,11 @@ mod test_bp_vote_extensions {
             block_height: 3.into(),
             validator_addr: address,
             sig,
+            state_root_sig: sign_state_root(
+                &shell,
+                shell.mode.get_eth_bridge_keypair().expect("Test failed"),
+                3.into(),
+            ),
         }
         .sign(shell.mode.get_protocol_key().expect("Test failed"));
         assert!(shell.validate_bp_roots_vext(
@@ -647,6 +783,11 @@ mod test_bp_vote_extensions {
             block_height: 3.into(),
             validator_addr: address,
             sig,
+            state_root_sig: sign_state_root(
+                &shell,
+                shell.mode.get_eth_bridge_keypair().expect("Test failed"),
+                3.into(),
+            ),
         }
         .sign(shell.mode.get_protocol_key().expect("Test failed"));
         assert!(!shell.validate_bp_roots_vext(
@@ -6,6 +6,8 @@
 //!     oracle
 //!   - `eth_events_queue`: a queue of confirmed ethereum events to be processed
 //!     in order
+//!   - `eth_oracle_progress`: the last eth block processed by this node's
+//!     oracle, and the events it sent that are not yet on chain
 //!   - `height`: the last committed block height
 //!   - `tx_queue`: txs to be decrypted in the next block
 //!   - `next_epoch_min_start_height`: minimum block height from which the next
@@ -58,7 +60,7 @@ use namada::ledger::storage::merkle_tree::{
 use namada::ledger::storage::types::PrefixIterator;
 use namada::ledger::storage::{
     types, BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, Error,
-    MerkleTreeStoresRead, Result, StoreType, DB,
+    EthOracleProgress, MerkleTreeStoresRead, Result, StoreType, DB,
 };
 use namada::types::ethereum_events::Uint;
 use namada::types::internal::TxQueue;
@@ -811,7 +813,7 @@ impl DB for RocksDB {
         // Restore subtrees of Merkle tree
         if let Some(epoch) = epoch {
             for st in StoreType::iter_subtrees() {
-                let key_prefix = subtree_key_prefix(st, epoch);
+                let key_prefix = subtree_key_prefix(&st, epoch);
                 let root_key =
                     key_prefix.clone().with_segment("root".to_owned());
                 if let Some(bytes) = self
@@ -820,7 +822,7 @@ impl DB for RocksDB {
                     .map_err(|e| Error::DBError(e.into_string()))?
                 {
                     merkle_tree_stores.set_root(
-                        st,
+                        &st,
                         types::decode(bytes).map_err(Error::CodingError)?,
                     );
                 }
@@ -994,24 +996,24 @@ impl DB for RocksDB {
         // Merkle tree
         {
             for st in StoreType::iter() {
-                if *st == StoreType::Base || is_full_commit {
-                    let key_prefix = if *st == StoreType::Base {
+                if st == StoreType::Base || is_full_commit {
+                    let key_prefix = if st == StoreType::Base {
                         base_tree_key_prefix(height)
                     } else {
-                        subtree_key_prefix(st, epoch)
+                        subtree_key_prefix(&st, epoch)
                     };
                     let root_key =
                         key_prefix.clone().with_segment("root".to_owned());
                     batch.0.put_cf(
                         block_cf,
                         root_key.to_string(),
-                        types::encode(merkle_tree_stores.root(st)),
+                        types::encode(merkle_tree_stores.root(&st)),
                     );
                     let store_key = key_prefix.with_segment("store".to_owned());
                     batch.0.put_cf(
                         block_cf,
                         store_key.to_string(),
-                        merkle_tree_stores.store(st).encode(),
+                        merkle_tree_stores.store(&st).encode(),
                     );
                 }
             }
@@ -1119,14 +1121,13 @@ impl DB for RocksDB {
         let block_cf = self.get_column_family(BLOCK_CF)?;
         let mut merkle_tree_stores = MerkleTreeStoresRead::default();
         let store_types = store_type
-            .as_ref()
             .map(|st| Either::Left(std::iter::once(st)))
             .unwrap_or_else(|| Either::Right(StoreType::iter()));
         for st in store_types {
-            let key_prefix = if *st == StoreType::Base {
+            let key_prefix = if st == StoreType::Base {
                 base_tree_key_prefix(base_height)
             } else {
-                subtree_key_prefix(st, epoch)
+                subtree_key_prefix(&st, epoch)
             };
             let root_key = key_prefix.clone().with_segment("root".to_owned());
             let bytes = self
@@ -1136,7 +1137,7 @@ impl DB for RocksDB {
             match bytes {
                 Some(b) => {
                     let root = types::decode(b).map_err(Error::CodingError)?;
-                    merkle_tree_stores.set_root(st, root);
+                    merkle_tree_stores.set_root(&st, root);
                 }
                 None => return Ok(None),
             }
@@ -1497,6 +1498,30 @@ impl DB for RocksDB {
 
         Ok(())
     }
+
+    fn read_eth_oracle_progress(&self) -> Result<Option<EthOracleProgress>> {
+        let state_cf = self.get_column_family(STATE_CF)?;
+        match self
+            .0
+            .get_cf(state_cf, "eth_oracle_progress")
+            .map_err(|e| Error::DBError(e.into_string()))?
+        {
+            Some(bytes) => {
+                types::decode(bytes).map(Some).map_err(Error::CodingError)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn write_eth_oracle_progress(
+        &mut self,
+        progress: &EthOracleProgress,
+    ) -> Result<()> {
+        let state_cf = self.get_column_family(STATE_CF)?;
+        self.0
+            .put_cf(state_cf, "eth_oracle_progress", types::encode(progress))
+            .map_err(|e| Error::DBError(e.into_string()))
+    }
 }
 
 impl<'iter> DBIter<'iter> for RocksDB {
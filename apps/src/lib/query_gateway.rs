@@ -0,0 +1,240 @@
+//! The query gateway exposes every route of the ledger's queries router
+//! ([`RPC`]) as a JSON API over HTTP. A route's path and its required
+//! arguments are the segments of the URL path, while its optional arguments
+//! are passed as query parameters. The data of the queries that read any are
+//! posted as JSON. The gateway encodes the data with Borsh, proxies each
+//! request to the ABCI query of a ledger node and decodes the Borsh-encoded
+//! response into JSON, following the encodings declared next to each router.
+//! The OpenAPI specification of the API, generated from the router, is served
+//! at [`OPENAPI_ENDPOINT`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use namada::tendermint_rpc::HttpClient;
+use namada::types::storage::BlockHeight;
+use namada_sdk::queries::{route_request, Client, RouteSpec, RPC};
+use schemars::gen::SchemaSettings;
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+use warp::path::FullPath;
+use warp::Filter;
+
+use crate::cli::api::CliClient;
+use crate::cli::{args, namada_version};
+
+/// The endpoint serving the OpenAPI specification of the gateway
+pub const OPENAPI_ENDPOINT: &str = "openapi.json";
+/// The query parameter of the block height at which to query the routes that
+/// support it
+pub const BLOCK_HEIGHT_PARAM: &str = "block_height";
+
+/// Run the query gateway until it is interrupted
+pub async fn run(mut args: args::QueryGateway) -> std::io::Result<()> {
+    let client = Arc::new(HttpClient::from_tendermint_address(
+        &mut args.ledger_address,
+    ));
+    let routes = Arc::new(RPC.routes());
+    tracing::info!(
+        listen_addr = ?args.listen_address,
+        routes = routes.len(),
+        "Query gateway is starting"
+    );
+
+    let openapi = openapi_spec(&routes);
+    let spec = warp::get()
+        .and(warp::path(OPENAPI_ENDPOINT))
+        .and(warp::path::end())
+        .map(move || warp::reply::json(&openapi));
+    let queries = warp::get()
+        .or(warp::post())
+        .unify()
+        .and(warp::path::full())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(warp::body::bytes())
+        .then(move |path: FullPath, params, body: Bytes| {
+            let client = client.clone();
+            let routes = routes.clone();
+            async move {
+                let (status, body) =
+                    query(&client, &routes, path.as_str(), params, body).await;
+                warp::reply::with_status(warp::reply::json(&body), status)
+            }
+        });
+    warp::serve(spec.or(queries)).run(args.listen_address).await;
+    Ok(())
+}
+
+/// Proxy a request to the route matching the given path to the ledger node
+async fn query(
+    client: &HttpClient,
+    routes: &[RouteSpec],
+    path: &str,
+    mut params: HashMap<String, String>,
+    body: Bytes,
+) -> (StatusCode, Value) {
+    let height = match params.remove(BLOCK_HEIGHT_PARAM).map(|h| h.parse()) {
+        Some(Ok(height)) => Some(BlockHeight(height)),
+        Some(Err(_)) => {
+            return error(StatusCode::BAD_REQUEST, "Invalid block height");
+        }
+        None => None,
+    };
+    let Some((route, abci_path)) = routes.iter().find_map(|route| {
+        route
+            .abci_path(path, &params)
+            .map(|abci_path| (route, abci_path))
+    }) else {
+        return error(
+            StatusCode::NOT_FOUND,
+            format!("No query matches the path {path}"),
+        );
+    };
+    if !route.with_options && height.is_some() {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "This query doesn't support a block height",
+        );
+    }
+    let data = match (route_request(route), body.is_empty()) {
+        (_, true) => None,
+        (Some(request), false) => {
+            match serde_json::from_slice(&body)
+                .map_err(|err| err.to_string())
+                .and_then(request.from_json)
+            {
+                Ok(data) => Some(data),
+                Err(err) => {
+                    return error(
+                        StatusCode::BAD_REQUEST,
+                        format!("Invalid request data: {err}"),
+                    );
+                }
+            }
+        }
+        (None, false) => {
+            return error(
+                StatusCode::BAD_REQUEST,
+                "This query doesn't support request data",
+            );
+        }
+    };
+    match client.request(abci_path, data, height, false).await {
+        Ok(response) => match (route.decode)(&response.data) {
+            Ok(value) => (StatusCode::OK, value),
+            Err(err) => error(
+                StatusCode::BAD_GATEWAY,
+                format!("Failed to decode the response of the node: {err}"),
+            ),
+        },
+        Err(err) => error(StatusCode::BAD_GATEWAY, err.to_string()),
+    }
+}
+
+/// Make an error response with the given message
+fn error(status: StatusCode, message: impl ToString) -> (StatusCode, Value) {
+    (status, json!({ "error": message.to_string() }))
+}
+
+/// Generate the OpenAPI specification of the given routes
+fn openapi_spec(routes: &[RouteSpec]) -> Value {
+    let mut gen = SchemaSettings::openapi3().into_generator();
+    let mut paths = serde_json::Map::new();
+    for route in routes {
+        let mut parameters = route
+            .args()
+            .map(|arg| {
+                json!({
+                    "name": arg.name,
+                    "in": if arg.optional { "query" } else { "path" },
+                    "required": !arg.optional,
+                    "description": format!("A value of type `{}`", arg.ty),
+                    "schema": arg_schema(arg.ty),
+                })
+            })
+            .collect::<Vec<_>>();
+        if route.with_options {
+            parameters.push(json!({
+                "name": BLOCK_HEIGHT_PARAM,
+                "in": "query",
+                "required": false,
+                "description": "The height of the block to query, the last \
+                                committed block by default",
+                "schema": { "type": "integer", "minimum": 0 },
+            }));
+        }
+        let mut operation = json!({
+            "summary": route.handler,
+            "parameters": parameters,
+            "responses": {
+                "200": {
+                    "description": format!(
+                        "A value of type `{}`",
+                        route.return_type
+                    ),
+                    "content": {
+                        "application/json": {
+                            "schema": (route.response_schema)(&mut gen),
+                        },
+                    },
+                },
+                "400": error_response("The request is invalid"),
+                "404": error_response("No query matches the path"),
+                "502": error_response("The ledger node failed to respond"),
+            },
+        });
+        let mut item = serde_json::Map::new();
+        item.insert("get".to_string(), operation.clone());
+        if let Some(request) = route_request(route) {
+            operation["requestBody"] = json!({
+                "description": format!(
+                    "The data attached to the query, a value of type `{}`",
+                    request.ty
+                ),
+                "required": true,
+                "content": {
+                    "application/json": {
+                        "schema": (request.schema)(&mut gen),
+                    },
+                },
+            });
+            item.insert("post".to_string(), operation);
+        }
+        paths.insert(route.rest_path(), Value::Object(item));
+    }
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Namada query gateway",
+            "version": namada_version(),
+        },
+        "paths": paths,
+        "components": { "schemas": gen.take_definitions() },
+    })
+}
+
+/// The schema of a path argument of the given type
+fn arg_schema(ty: &str) -> Value {
+    match ty {
+        "u64" | "Epoch" | "BlockHeight" => {
+            json!({ "type": "integer", "minimum": 0 })
+        }
+        _ => json!({ "type": "string" }),
+    }
+}
+
+/// The description of an error response
+fn error_response(description: &str) -> Value {
+    json!({
+        "description": description,
+        "content": {
+            "application/json": {
+                "schema": {
+                    "type": "object",
+                    "properties": { "error": { "type": "string" } },
+                },
+            },
+        },
+    })
+}
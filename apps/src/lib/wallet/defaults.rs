@@ -27,11 +27,7 @@ mod dev {
     /// Get protocol, eth_bridge, and dkg keys from the validator pre-genesis
     /// wallet
     pub fn validator_keys() -> (common::SecretKey, common::SecretKey) {
-        let protocol_key = VALIDATOR_WALLET
-            .store
-            .validator_keys
-            .protocol_keypair
-            .clone();
+        let protocol_key = VALIDATOR_WALLET.protocol_key.clone();
         let eth_bridge_key = VALIDATOR_WALLET.eth_hot_key.clone();
         (protocol_key, eth_bridge_key)
     }
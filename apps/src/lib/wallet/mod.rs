@@ -12,7 +12,8 @@ pub use namada_sdk::wallet::alias::Alias;
 use namada_sdk::wallet::fs::FsWalletStorage;
 use namada_sdk::wallet::store::Store;
 use namada_sdk::wallet::{
-    ConfirmationResponse, FindKeyError, GenRestoreKeyError, Wallet, WalletIo,
+    ConfirmationResponse, FindKeyError, GenRestoreKeyError, LoadStoreError,
+    Wallet, WalletIo,
 };
 pub use namada_sdk::wallet::{ValidatorData, ValidatorKeys};
 use rand_core::OsRng;
@@ -198,7 +199,7 @@ pub fn gen_validator_keys<U: WalletIo>(
         data.keys.protocol_keypair.clone()
     })?;
     let eth_bridge_keypair = find_secret_key(wallet, eth_bridge_pk, |data| {
-        data.keys.eth_bridge_keypair.clone()
+        Some(data.keys.eth_bridge_keypair.clone())
     })?;
     Ok(store::gen_validator_keys(
         eth_bridge_keypair,
@@ -218,7 +219,7 @@ fn find_secret_key<F, U>(
     extract_key: F,
 ) -> Result<Option<common::SecretKey>, FindKeyError>
 where
-    F: Fn(&ValidatorData) -> common::SecretKey,
+    F: Fn(&ValidatorData) -> Option<common::SecretKey>,
     U: WalletIo,
 {
     maybe_pk
@@ -228,7 +229,7 @@ where
                 // TODO: optionally encrypt validator keys
                 .find_key_by_pkh(&pkh, None)
                 .ok()
-                .or_else(|| wallet.get_validator_data().map(extract_key))
+                .or_else(|| wallet.get_validator_data().and_then(extract_key))
                 .ok_or_else(|| FindKeyError::KeyNotFound(pkh.to_string()))
         })
         .transpose()
@@ -242,24 +243,29 @@ pub fn save(wallet: &Wallet<CliWalletUtils>) -> std::io::Result<()> {
 }
 
 /// Load a wallet from the store file.
-pub fn load(store_dir: &Path) -> Option<Wallet<CliWalletUtils>> {
-    let mut wallet = CliWalletUtils::new(store_dir.to_path_buf());
-    if wallet.load().is_err() {
-        return None;
+pub fn load(
+    store_dir: &Path,
+) -> Result<Wallet<CliWalletUtils>, LoadStoreError> {
+    self::store::load(store_dir)
+}
+
+/// Load a wallet from the store file, if there is one.
+pub fn load_if_exists(
+    store_dir: &Path,
+) -> Result<Option<Wallet<CliWalletUtils>>, LoadStoreError> {
+    if !exists(store_dir) {
+        return Ok(None);
     }
-    Some(wallet)
+    load(store_dir).map(Some)
 }
 
 /// Load a wallet from the store file or create a new wallet without any
 /// keys or addresses.
 pub fn load_or_new(store_dir: &Path) -> Wallet<CliWalletUtils> {
-    let store = self::store::load_or_new(store_dir).unwrap_or_else(|err| {
+    self::store::load_or_new(store_dir).unwrap_or_else(|err| {
         eprintln!("Unable to load the wallet: {}", err);
         cli::safe_exit(1)
-    });
-    let mut wallet = CliWalletUtils::new(store_dir.to_path_buf());
-    *wallet.store_mut() = store;
-    wallet
+    })
 }
 
 /// Check if a wallet exists in the given store dir.
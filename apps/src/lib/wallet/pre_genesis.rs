@@ -85,6 +85,11 @@ pub fn load(store_dir: &Path) -> Result<ValidatorWallet, ReadError> {
         .eth_cold_key
         .get::<CliWalletUtils>(true, password.clone())?;
     let eth_hot_key = store.validator_keys.eth_bridge_keypair.clone();
+    let protocol_key = store
+        .validator_keys
+        .protocol_keypair
+        .clone()
+        .ok_or(ReadError::MissingProtocolKey)?;
     let tendermint_node_key = store
         .tendermint_node_key
         .get::<CliWalletUtils>(true, password)?;
@@ -94,6 +99,7 @@ pub fn load(store_dir: &Path) -> Result<ValidatorWallet, ReadError> {
         consensus_key,
         eth_cold_key,
         eth_hot_key,
+        protocol_key,
         tendermint_node_key,
     })
 }
@@ -120,6 +126,10 @@ fn gen(
     );
     let validator_keys = gen_validator_keys(None, None, scheme);
     let eth_hot_key = validator_keys.eth_bridge_keypair.clone();
+    let protocol_key = validator_keys
+        .protocol_keypair
+        .clone()
+        .expect("Generated validator keys always hold a protocol key");
     let store = ValidatorStore {
         consensus_key,
         eth_cold_key,
@@ -131,6 +141,7 @@ fn gen(
         consensus_key: consensus_sk,
         eth_cold_key: eth_cold_sk,
         eth_hot_key,
+        protocol_key,
         tendermint_node_key: tendermint_node_sk,
     }
 }
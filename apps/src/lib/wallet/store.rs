@@ -2,7 +2,7 @@ use std::path::{Path, PathBuf};
 
 use namada::types::key::*;
 use namada_sdk::wallet::{
-    gen_secret_key, LoadStoreError, Store, ValidatorKeys,
+    gen_secret_key, LoadStoreError, ValidatorKeys, Wallet,
 };
 use rand::rngs::OsRng;
 
@@ -17,19 +17,29 @@ pub fn wallet_file(store_dir: impl AsRef<Path>) -> PathBuf {
 }
 
 /// Load the store file or create a new one without any keys or addresses.
-pub fn load_or_new(store_dir: &Path) -> Result<Store, LoadStoreError> {
-    load(store_dir).or_else(|_| {
-        let wallet = CliWalletUtils::new(store_dir.to_path_buf());
-        wallet.save()?;
-        Ok(wallet.into())
+/// An encrypted store that cannot be opened is never replaced.
+pub fn load_or_new(
+    store_dir: &Path,
+) -> Result<Wallet<CliWalletUtils>, LoadStoreError> {
+    load(store_dir).or_else(|err| match err {
+        LoadStoreError::Decrypt | LoadStoreError::UnsupportedVersion(_) => {
+            Err(err)
+        }
+        _ => {
+            let wallet = CliWalletUtils::new(store_dir.to_path_buf());
+            wallet.save()?;
+            Ok(wallet)
+        }
     })
 }
 
 /// Attempt to load the store file.
-pub fn load(store_dir: &Path) -> Result<Store, LoadStoreError> {
+pub fn load(
+    store_dir: &Path,
+) -> Result<Wallet<CliWalletUtils>, LoadStoreError> {
     let mut wallet = CliWalletUtils::new(store_dir.to_path_buf());
     wallet.load()?;
-    Ok(wallet.into())
+    Ok(wallet)
 }
 
 /// Generate keypair for signing protocol txs and for the DKG
@@ -52,17 +62,100 @@ pub fn gen_validator_keys(
     let protocol_keypair = protocol_keypair
         .unwrap_or_else(|| gen_secret_key(protocol_keypair_scheme, &mut OsRng));
     ValidatorKeys {
-        protocol_keypair,
+        protocol_keypair: Some(protocol_keypair),
         eth_bridge_keypair,
     }
 }
 
 #[cfg(test)]
 mod test_wallet {
+    use namada::types::address::testing::established_address_1;
     use namada::types::address::Address;
+    use namada_sdk::wallet::encrypted_store::{
+        store_version, PLAINTEXT_STORE_VERSION,
+    };
+    use namada_sdk::wallet::Store;
+    use zeroize::Zeroizing;
 
     use super::*;
 
+    fn password(password: &str) -> Zeroizing<String> {
+        Zeroizing::new(password.to_string())
+    }
+
+    /// Load the wallet store in the given dir with the given master password
+    fn load_with_password(
+        store_dir: &Path,
+        master_password: &str,
+    ) -> Result<Wallet<CliWalletUtils>, LoadStoreError> {
+        let mut wallet = CliWalletUtils::new(store_dir.to_path_buf());
+        wallet.set_master_password(Some(password(master_password)));
+        wallet.load().map(|()| wallet)
+    }
+
+    /// Save a wallet store holding an address and a raw key in the given dir,
+    /// encrypted with the master password "old". Returns the public key of
+    /// the raw key.
+    fn save_encrypted_wallet(store_dir: &Path) -> common::PublicKey {
+        let mut wallet = CliWalletUtils::new(store_dir.to_path_buf());
+        wallet.insert_address("my-account", established_address_1(), false);
+        let sk = gen_secret_key(SchemeType::Ed25519, &mut OsRng);
+        wallet
+            .insert_keypair(
+                "my-key".to_string(),
+                false,
+                sk.clone(),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        assert!(wallet.encrypt_store(password("old")).is_empty());
+        wallet.save().unwrap();
+        sk.ref_to()
+    }
+
+    /// Test changing the master password of an encrypted wallet store.
+    #[test]
+    fn test_store_change_password() {
+        let dir = tempfile::tempdir().unwrap();
+        save_encrypted_wallet(dir.path());
+
+        let mut wallet = load_with_password(dir.path(), "old").unwrap();
+        assert!(wallet.is_store_encrypted());
+        assert!(wallet.encrypt_store(password("new")).is_empty());
+        wallet.save().unwrap();
+
+        assert!(matches!(
+            load_with_password(dir.path(), "old"),
+            Err(LoadStoreError::Decrypt)
+        ));
+        let wallet = load_with_password(dir.path(), "new").unwrap();
+        assert!(wallet.is_store_encrypted());
+        assert!(wallet.find_address("my-account").is_some());
+    }
+
+    /// Test that a decrypted wallet store is saved back in the plaintext
+    /// format, with its raw keys encrypted with the former master password.
+    #[test]
+    fn test_store_decrypt() {
+        let dir = tempfile::tempdir().unwrap();
+        let pk = save_encrypted_wallet(dir.path());
+
+        let mut wallet = load_with_password(dir.path(), "old").unwrap();
+        wallet.decrypt_store();
+        wallet.save().unwrap();
+
+        let data = std::fs::read(wallet_file(dir.path())).unwrap();
+        assert_eq!(store_version(&data).unwrap(), PLAINTEXT_STORE_VERSION);
+        let mut wallet = load(dir.path()).unwrap();
+        assert!(!wallet.is_store_encrypted());
+        assert!(wallet.find_address("my-account").is_some());
+        assert!(wallet.store().find_key_by_pk(&pk).unwrap().is_encrypted());
+        let sk = wallet.find_key_by_pk(&pk, Some(password("old"))).unwrap();
+        assert_eq!(sk.ref_to(), pk);
+    }
+
     #[test]
     fn test_toml_roundtrip_ed25519() {
         let mut store = Store::default();
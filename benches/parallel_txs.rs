@@ -0,0 +1,162 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use namada::core::types::key::{
+    common, SecretKey as SecretKeyInterface, SigScheme,
+};
+use namada::core::types::token::{Amount, Transfer};
+use namada::ledger::gas::TxGasMeter;
+use namada::ledger::protocol::parallel::{self, BatchTx};
+use namada::ledger::protocol::{self, ShellParams};
+use namada::ledger::storage::TempWlStorage;
+use namada::ledger::storage_api::{key, token};
+use namada::types::address::{self, Address};
+use namada::types::key::{ed25519, RefTo};
+use namada::types::storage::TxIndex;
+use namada_apps::bench_utils::{BenchShell, TX_TRANSFER_WASM};
+use namada_apps::wallet::defaults;
+
+/// The number of txs in a batch
+const BATCH_SIZE: usize = 64;
+
+fn gen_keypair() -> common::SecretKey {
+    let mut csprng = rand::rngs::OsRng {};
+    ed25519::SigScheme::generate(&mut csprng)
+        .try_to_sk()
+        .unwrap()
+}
+
+/// Generate a batch of transparent transfers. If `conflicting`, they all
+/// debit the same account, otherwise they are all between distinct accounts.
+fn generate_batch(shell: &mut BenchShell, conflicting: bool) -> Vec<BatchTx> {
+    let sources: Vec<common::SecretKey> = if conflicting {
+        vec![defaults::albert_keypair(); BATCH_SIZE]
+    } else {
+        (0..BATCH_SIZE).map(|_| gen_keypair()).collect()
+    };
+    if !conflicting {
+        // Fund the sources and reveal their public keys for their VPs
+        for keypair in &sources {
+            let pk = keypair.ref_to();
+            key::reveal_pk(&mut shell.wl_storage, &pk).unwrap();
+            token::credit_tokens(
+                &mut shell.wl_storage,
+                &address::nam(),
+                &Address::from(&pk),
+                Amount::native_whole(1_000),
+            )
+            .unwrap();
+        }
+        shell.wl_storage.commit_tx();
+        shell.commit();
+    }
+
+    sources
+        .iter()
+        .enumerate()
+        .map(|(index, keypair)| {
+            let tx = shell.generate_tx(
+                TX_TRANSFER_WASM,
+                Transfer {
+                    source: Address::from(&keypair.ref_to()),
+                    target: Address::from(&gen_keypair().ref_to()),
+                    token: address::nam(),
+                    amount: Amount::native_whole(1).native_denominated(),
+                    key: None,
+                    shielded: None,
+                },
+                None,
+                None,
+                vec![keypair],
+            );
+            BatchTx {
+                tx,
+                tx_index: TxIndex(index as u32),
+                gas_limit: u64::MAX.into(),
+            }
+        })
+        .collect()
+}
+
+/// Compare the sequential and optimistic parallel execution of a batch of
+/// independent transfers and of a batch of transfers that all conflict.
+fn parallel_txs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel_txs");
+
+    for bench_name in ["independent", "conflicting"] {
+        let mut shell = BenchShell::default();
+        let batch = generate_batch(&mut shell, bench_name == "conflicting");
+
+        group.bench_function(format!("{bench_name}_sequential"), |b| {
+            b.iter_batched(
+                || {
+                    (
+                        TempWlStorage::new(&shell.wl_storage.storage),
+                        shell.vp_wasm_cache.clone(),
+                        shell.tx_wasm_cache.clone(),
+                    )
+                },
+                |(
+                    mut temp_wl_storage,
+                    mut vp_wasm_cache,
+                    mut tx_wasm_cache,
+                )| {
+                    for batch_tx in &batch {
+                        let result = protocol::apply_wasm_tx(
+                            batch_tx.tx.clone(),
+                            &batch_tx.tx_index,
+                            ShellParams::new(
+                                &mut TxGasMeter::new_from_sub_limit(
+                                    batch_tx.gas_limit,
+                                ),
+                                &mut temp_wl_storage,
+                                &mut vp_wasm_cache,
+                                &mut tx_wasm_cache,
+                            ),
+                        )
+                        .unwrap();
+                        assert!(result.is_accepted());
+                        temp_wl_storage.write_log.commit_tx();
+                    }
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        });
+
+        group.bench_function(format!("{bench_name}_parallel"), |b| {
+            b.iter_batched(
+                || {
+                    (
+                        batch.clone(),
+                        TempWlStorage::new(&shell.wl_storage.storage),
+                        shell.vp_wasm_cache.clone(),
+                        shell.tx_wasm_cache.clone(),
+                    )
+                },
+                |(
+                    batch,
+                    mut temp_wl_storage,
+                    mut vp_wasm_cache,
+                    mut tx_wasm_cache,
+                )| {
+                    let results = parallel::execute_batch(
+                        batch,
+                        temp_wl_storage.storage,
+                        &mut temp_wl_storage.write_log,
+                        &mut vp_wasm_cache,
+                        &mut tx_wasm_cache,
+                    );
+                    assert!(
+                        results
+                            .into_iter()
+                            .all(|result| result.unwrap().is_accepted())
+                    );
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(parallel, parallel_txs);
+criterion_main!(parallel);
@@ -32,6 +32,9 @@ pub const BRIDGE_POOL_ADDRESS: Address =
 struct Segments {
     signed_root: &'static str,
     bridge_pool_nonce: &'static str,
+    replaced_transfers: &'static str,
+    expired_transfers: &'static str,
+    transfer_expiry: &'static str,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -76,6 +79,81 @@ pub fn get_nonce_key() -> Key {
     }
 }
 
+/// Get the storage key pointing from a transfer that was replaced
+/// by a fee bump to the hash of the transfer replacing it.
+///
+/// Replaced transfers may still be part of a signed Bridge pool root,
+/// and therefore be relayed, until the Bridge pool nonce is incremented.
+pub fn get_replaced_key(hash: &KeccakHash) -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(BRIDGE_POOL_ADDRESS),
+            DbKeySeg::StringSeg(Segments::VALUES.replaced_transfers.into()),
+            hash.to_db_key(),
+        ],
+    }
+}
+
+/// Check if a key points to the replacement of a transfer, returning
+/// the hash of the replaced transfer.
+pub fn is_replaced_key(key: &Key) -> Option<KeccakHash> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(hash),
+        ] if addr == &BRIDGE_POOL_ADDRESS
+            && prefix == Segments::VALUES.replaced_transfers =>
+        {
+            KeccakHash::try_from(hash.as_str()).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Get the storage key prefix of all replaced transfers.
+pub fn get_replaced_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(BRIDGE_POOL_ADDRESS),
+            DbKeySeg::StringSeg(Segments::VALUES.replaced_transfers.into()),
+        ],
+    }
+}
+
+/// Get the storage key of a transfer that expired and was removed
+/// from the Bridge pool, but has not yet been refunded.
+pub fn get_expired_key(hash: &KeccakHash) -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(BRIDGE_POOL_ADDRESS),
+            DbKeySeg::StringSeg(Segments::VALUES.expired_transfers.into()),
+            hash.to_db_key(),
+        ],
+    }
+}
+
+/// Get the storage key prefix of all expired transfers.
+pub fn get_expired_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(BRIDGE_POOL_ADDRESS),
+            DbKeySeg::StringSeg(Segments::VALUES.expired_transfers.into()),
+        ],
+    }
+}
+
+/// Get the storage key of the number of epochs after which
+/// pending transfers expire. If unset, transfers never expire.
+pub fn get_transfer_expiry_key() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(BRIDGE_POOL_ADDRESS),
+            DbKeySeg::StringSeg(Segments::VALUES.transfer_expiry.into()),
+        ],
+    }
+}
+
 /// Check if a key belongs to the bridge pools sub-storage
 pub fn is_bridge_pool_key(key: &Key) -> bool {
     matches!(&key.segments[0], DbKeySeg::AddressSeg(addr) if addr == &BRIDGE_POOL_ADDRESS)
@@ -691,6 +769,32 @@ mod test_bridge_pool_tree {
         assert!(BridgePoolTree::parse_key(&key).is_err());
     }
 
+    /// Test that the keys of replaced transfers can be parsed back
+    /// into their hashes, and are not mistaken for pending transfers
+    #[test]
+    fn test_replaced_key() {
+        let transfer = PendingTransfer {
+            transfer: TransferToEthereum {
+                kind: TransferToEthereumKind::Erc20,
+                asset: EthAddress([1; 20]),
+                sender: bertha_address(),
+                recipient: EthAddress([2; 20]),
+                amount: 1u64.into(),
+            },
+            gas_fee: GasFee {
+                token: nam(),
+                amount: 0.into(),
+                payer: bertha_address(),
+            },
+        };
+        let hash = transfer.keccak256();
+        let key = get_replaced_key(&hash);
+        assert_eq!(is_replaced_key(&key), Some(hash));
+        assert!(!is_pending_transfer_key(&key));
+        assert!(key.split_prefix(&get_replaced_prefix()).is_some());
+        assert_eq!(is_replaced_key(&get_pending_key(&transfer)), None);
+    }
+
     /// Test that [`contains_key`] works correctly
     #[test]
     fn test_contains_key() {
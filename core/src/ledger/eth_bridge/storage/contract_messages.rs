@@ -0,0 +1,140 @@
+//! Storage of the arbitrary messages sent by Ethereum contracts
+//! to Namada accounts.
+//!
+//! Confirmed messages are queued under the Ethereum bridge
+//! sub-space by the protocol, until they are dispatched to
+//! the handler of their target account. An account opts into
+//! receiving messages by writing the hash of some wasm code
+//! under its [`handler_key`], and pays for the gas of its handler.
+//!
+//! Only messages from Ethereum to Namada are supported. Messages
+//! from Namada to Ethereum would have to be relayed in Bridge pool
+//! roots, whose leaves the bridge contract only accepts as ERC20
+//! transfers, so they are left to a later extension of the bridge.
+
+use super::prefix as ethbridge_key_prefix;
+use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
+use crate::types::address::Address;
+use crate::types::ethereum_events::ContractMessage;
+use crate::types::hash::Hash;
+use crate::types::storage::{self, DbKeySeg, KeySeg};
+
+/// The name of the main storage segment.
+const MAIN_SEGMENT: &str = "contract_messages";
+/// The storage segment of the messages pending dispatch.
+const PENDING_SEGMENT: &str = "pending";
+/// The storage segment of an account's message handler.
+const HANDLER_SEGMENT: &str = "eth_message_handler";
+
+/// The maximum amount of gas that the handler of a single message may
+/// consume. The target account of the message pays for all of it upfront.
+pub const HANDLER_GAS_LIMIT: u64 = 1_000_000;
+
+/// Storage key of the [`ContractMessage`]s pending dispatch.
+pub fn pending_key() -> storage::Key {
+    ethbridge_key_prefix()
+        .push(&MAIN_SEGMENT.to_owned())
+        .expect("Should be able to push a storage key segment")
+        .push(&PENDING_SEGMENT.to_owned())
+        .expect("Should be able to push a storage key segment")
+}
+
+/// Storage key of the hash of the wasm code handling the
+/// [`ContractMessage`]s addressed to `owner`.
+pub fn handler_key(owner: &Address) -> storage::Key {
+    storage::Key::from(owner.to_db_key())
+        .push(&HANDLER_SEGMENT.to_owned())
+        .expect("Should be able to push a storage key segment")
+}
+
+/// Check if the given [`storage::Key`] holds the message handler
+/// of some account, returning the owner of the handler.
+pub fn is_handler_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(handler)]
+            if handler == HANDLER_SEGMENT =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+/// Read the hash of the wasm code handling the messages addressed
+/// to `owner`. Returns [`None`] if the account has no handler.
+pub fn read_handler<S>(
+    storage: &S,
+    owner: &Address,
+) -> storage_api::Result<Option<Hash>>
+where
+    S: StorageRead,
+{
+    storage.read(&handler_key(owner))
+}
+
+/// Queue up confirmed [`ContractMessage`]s, to be dispatched
+/// to the handlers of their target accounts.
+pub fn enqueue<S>(
+    storage: &mut S,
+    messages: impl IntoIterator<Item = ContractMessage>,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = pending_key();
+    let mut pending: Vec<ContractMessage> =
+        storage.read(&key)?.unwrap_or_default();
+    pending.extend(messages);
+    storage.write(&key, pending)
+}
+
+/// Remove all the [`ContractMessage`]s pending dispatch from
+/// storage, returning them in the order they were confirmed.
+pub fn take_pending<S>(
+    storage: &mut S,
+) -> storage_api::Result<Vec<ContractMessage>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = pending_key();
+    let Some(pending) = storage.read(&key)? else {
+        return Ok(vec![]);
+    };
+    storage.delete(&key)?;
+    Ok(pending)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::storage::testing::TestWlStorage;
+    use crate::types::address::testing::established_address_1;
+    use crate::types::ethereum_events::testing::arbitrary_eth_address;
+
+    #[test]
+    fn test_is_handler_key() {
+        let owner = established_address_1();
+        assert_eq!(is_handler_key(&handler_key(&owner)), Some(&owner));
+        assert_eq!(is_handler_key(&pending_key()), None);
+    }
+
+    #[test]
+    fn test_enqueue_and_take_pending() {
+        let mut wl_storage = TestWlStorage::default();
+        let message = |payload: &[u8]| ContractMessage {
+            source: arbitrary_eth_address(),
+            target: established_address_1(),
+            payload: payload.to_vec(),
+        };
+
+        assert!(take_pending(&mut wl_storage).unwrap().is_empty());
+
+        enqueue(&mut wl_storage, [message(b"a")]).unwrap();
+        enqueue(&mut wl_storage, [message(b"b"), message(b"c")]).unwrap();
+        assert_eq!(
+            take_pending(&mut wl_storage).unwrap(),
+            vec![message(b"a"), message(b"b"), message(b"c")],
+        );
+        assert!(!wl_storage.has_key(&pending_key()).unwrap());
+    }
+}
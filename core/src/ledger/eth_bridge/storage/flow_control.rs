@@ -0,0 +1,476 @@
+//! Flow control of the assets crossing the Ethereum bridge.
+//!
+//! The pause flags and the per-epoch flow limits should only ever
+//! be written to by governance, or `InitChain`. The flows of each
+//! asset are only tracked if a limit has been configured for it.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use super::super::ADDRESS as BRIDGE_ADDRESS;
+use super::prefix as ethbridge_key_prefix;
+use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
+use crate::types::ethereum_events::EthAddress;
+use crate::types::storage::{self, DbKeySeg, Epoch};
+use crate::types::token::Amount;
+
+/// The name of the main storage segment.
+const MAIN_SEGMENT: &str = "flow_control";
+/// The storage segment of the pause flags.
+const PAUSED_SEGMENT: &str = "paused";
+/// The storage segment of the per-epoch flow limits of an asset.
+const LIMIT_SEGMENT: &str = "limit";
+/// The storage segment of the flows of an asset in the last epoch.
+const FLOW_SEGMENT: &str = "flow";
+
+/// The direction in which assets cross the Ethereum bridge.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Transfers from Ethereum to Namada.
+    Inbound,
+    /// Transfers from Namada to Ethereum.
+    Outbound,
+}
+
+impl Direction {
+    /// The storage key segment of this [`Direction`].
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Inbound => "inbound",
+            Self::Outbound => "outbound",
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "inbound" => Ok(Self::Inbound),
+            "outbound" => Ok(Self::Outbound),
+            _ => Err(format!("Invalid Ethereum bridge flow direction: {s}")),
+        }
+    }
+}
+
+/// The amount of an asset that has crossed the Ethereum
+/// bridge in some direction, during a given epoch.
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize,
+)]
+pub struct EpochFlow {
+    /// The epoch in which the flow was recorded.
+    pub epoch: Epoch,
+    /// The amount that has crossed the bridge during `epoch`.
+    pub amount: Amount,
+}
+
+impl EpochFlow {
+    /// Return the amount that has crossed the bridge during `epoch`.
+    #[inline]
+    pub fn amount_at(&self, epoch: Epoch) -> Amount {
+        if self.epoch == epoch {
+            self.amount
+        } else {
+            Amount::zero()
+        }
+    }
+
+    /// Record that `amount` has crossed the bridge during `epoch`.
+    ///
+    /// Returns [`None`] on overflow.
+    pub fn record(&self, epoch: Epoch, amount: Amount) -> Option<Self> {
+        Some(Self {
+            epoch,
+            amount: self.amount_at(epoch).checked_add(amount)?,
+        })
+    }
+}
+
+/// The flow control parameters of the bridge, which may only
+/// be changed by governance.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FlowParams {
+    /// The directions in which transfers are paused.
+    pub paused: BTreeSet<Direction>,
+    /// The per-epoch flow limits of each asset, in each direction.
+    pub limits: BTreeMap<(EthAddress, Direction), Amount>,
+}
+
+/// Return the storage key prefix of the flow control sub-space.
+fn flow_control_prefix() -> storage::Key {
+    ethbridge_key_prefix()
+        .push(&MAIN_SEGMENT.to_owned())
+        .expect("Should be able to push a storage key segment")
+}
+
+/// Return the storage key prefix of the given asset.
+fn asset_prefix(asset: &EthAddress) -> storage::Key {
+    flow_control_prefix()
+        .push(&asset.to_canonical())
+        .expect("Should be able to push a storage key segment")
+}
+
+/// Storage key of the flag pausing transfers in the given [`Direction`].
+pub fn paused_key(direction: Direction) -> storage::Key {
+    flow_control_prefix()
+        .push(&PAUSED_SEGMENT.to_owned())
+        .expect("Should be able to push a storage key segment")
+        .push(&direction.as_str().to_owned())
+        .expect("Should be able to push a storage key segment")
+}
+
+/// Storage key of the per-epoch flow limit of an asset in the
+/// given [`Direction`].
+pub fn limit_key(asset: &EthAddress, direction: Direction) -> storage::Key {
+    asset_prefix(asset)
+        .push(&LIMIT_SEGMENT.to_owned())
+        .expect("Should be able to push a storage key segment")
+        .push(&direction.as_str().to_owned())
+        .expect("Should be able to push a storage key segment")
+}
+
+/// Storage key of the [`EpochFlow`] of an asset in the
+/// given [`Direction`].
+pub fn flow_key(asset: &EthAddress, direction: Direction) -> storage::Key {
+    asset_prefix(asset)
+        .push(&FLOW_SEGMENT.to_owned())
+        .expect("Should be able to push a storage key segment")
+        .push(&direction.as_str().to_owned())
+        .expect("Should be able to push a storage key segment")
+}
+
+/// Check if the given [`storage::Key`] is a pause flag or a flow
+/// limit, which may only be written to by governance.
+pub fn is_governance_key(key: &storage::Key) -> bool {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(main), DbKeySeg::StringSeg(paused), DbKeySeg::StringSeg(direction)] => {
+            addr == &BRIDGE_ADDRESS
+                && main == MAIN_SEGMENT
+                && paused == PAUSED_SEGMENT
+                && Direction::from_str(direction).is_ok()
+        }
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(main), DbKeySeg::StringSeg(asset), DbKeySeg::StringSeg(limit), DbKeySeg::StringSeg(direction)] => {
+            addr == &BRIDGE_ADDRESS
+                && main == MAIN_SEGMENT
+                && limit == LIMIT_SEGMENT
+                && EthAddress::from_str(asset).is_ok()
+                && Direction::from_str(direction).is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Check if the given [`storage::Key`] holds the [`EpochFlow`] of
+/// some asset, returning the asset and the [`Direction`] of the flow.
+pub fn is_flow_key(key: &storage::Key) -> Option<(EthAddress, Direction)> {
+    is_asset_key(key, FLOW_SEGMENT)
+}
+
+/// Check if the given [`storage::Key`] holds the per-epoch flow limit
+/// of some asset, returning the asset and the [`Direction`] of the limit.
+pub fn is_limit_key(key: &storage::Key) -> Option<(EthAddress, Direction)> {
+    is_asset_key(key, LIMIT_SEGMENT)
+}
+
+/// Check if the given [`storage::Key`] is under the given segment of
+/// some asset, returning the asset and the [`Direction`] of the key.
+fn is_asset_key(
+    key: &storage::Key,
+    segment: &str,
+) -> Option<(EthAddress, Direction)> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(main), DbKeySeg::StringSeg(asset), DbKeySeg::StringSeg(seg), DbKeySeg::StringSeg(direction)]
+            if addr == &BRIDGE_ADDRESS
+                && main == MAIN_SEGMENT
+                && seg == segment =>
+        {
+            Some((
+                EthAddress::from_str(asset).ok()?,
+                Direction::from_str(direction).ok()?,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Check if transfers in the given [`Direction`] are paused.
+pub fn is_paused<S>(
+    storage: &S,
+    direction: Direction,
+) -> storage_api::Result<bool>
+where
+    S: StorageRead,
+{
+    Ok(storage.read(&paused_key(direction))?.unwrap_or(false))
+}
+
+/// Pause or resume transfers in the given [`Direction`].
+pub fn set_paused<S>(
+    storage: &mut S,
+    direction: Direction,
+    paused: bool,
+) -> storage_api::Result<()>
+where
+    S: StorageWrite,
+{
+    storage.write(&paused_key(direction), paused)
+}
+
+/// Read the per-epoch flow limit of an asset in the given [`Direction`].
+///
+/// If no limit has been configured, return [`None`].
+pub fn read_limit<S>(
+    storage: &S,
+    asset: &EthAddress,
+    direction: Direction,
+) -> storage_api::Result<Option<Amount>>
+where
+    S: StorageRead,
+{
+    storage.read(&limit_key(asset, direction))
+}
+
+/// Configure the per-epoch flow limit of an asset in the given
+/// [`Direction`]. A limit of [`None`] removes any existing limit.
+pub fn write_limit<S>(
+    storage: &mut S,
+    asset: &EthAddress,
+    direction: Direction,
+    limit: Option<Amount>,
+) -> storage_api::Result<()>
+where
+    S: StorageWrite,
+{
+    let key = limit_key(asset, direction);
+    match limit {
+        Some(limit) => storage.write(&key, limit),
+        None => storage.delete(&key),
+    }
+}
+
+/// Read all the [`FlowParams`] of the bridge.
+pub fn read_params<S>(storage: &S) -> storage_api::Result<FlowParams>
+where
+    S: StorageRead,
+{
+    let mut paused = BTreeSet::new();
+    for direction in [Direction::Inbound, Direction::Outbound] {
+        if is_paused(storage, direction)? {
+            paused.insert(direction);
+        }
+    }
+    let limits = storage_api::iter_prefix_with_filter::<Amount, _>(
+        storage,
+        &flow_control_prefix(),
+        |key| is_limit_key(key).is_some(),
+    )?
+    .map(|result| {
+        let (key, limit) = result?;
+        let asset_and_direction =
+            is_limit_key(&key).expect("The key was filtered to be a limit");
+        Ok((asset_and_direction, limit))
+    })
+    .collect::<storage_api::Result<_>>()?;
+    Ok(FlowParams { paused, limits })
+}
+
+/// Read the last recorded [`EpochFlow`] of an asset in the given
+/// [`Direction`].
+pub fn read_flow<S>(
+    storage: &S,
+    asset: &EthAddress,
+    direction: Direction,
+) -> storage_api::Result<EpochFlow>
+where
+    S: StorageRead,
+{
+    Ok(storage
+        .read(&flow_key(asset, direction))?
+        .unwrap_or_default())
+}
+
+/// Return the amount of an asset that may still cross the bridge in
+/// the given [`Direction`], during the current epoch.
+///
+/// If transfers are paused, the allowance is nil. If no limit has been
+/// configured for the asset, return [`None`].
+pub fn read_allowance<S>(
+    storage: &S,
+    asset: &EthAddress,
+    direction: Direction,
+) -> storage_api::Result<Option<Amount>>
+where
+    S: StorageRead,
+{
+    if is_paused(storage, direction)? {
+        return Ok(Some(Amount::zero()));
+    }
+    let Some(limit) = read_limit(storage, asset, direction)? else {
+        return Ok(None);
+    };
+    let epoch = storage.get_block_epoch()?;
+    let flow = read_flow(storage, asset, direction)?.amount_at(epoch);
+    Ok(Some(limit.checked_sub(flow).unwrap_or_default()))
+}
+
+/// Record that `amount` of an asset has crossed the bridge in the given
+/// [`Direction`], during the current epoch.
+///
+/// Flows are only recorded for assets with a configured limit. Returns
+/// whether the flow was recorded.
+pub fn record_flow<S>(
+    storage: &mut S,
+    asset: &EthAddress,
+    direction: Direction,
+    amount: Amount,
+) -> storage_api::Result<bool>
+where
+    S: StorageRead + StorageWrite,
+{
+    if read_limit(storage, asset, direction)?.is_none() {
+        return Ok(false);
+    }
+    let epoch = storage.get_block_epoch()?;
+    let flow = read_flow(storage, asset, direction)?
+        .record(epoch, amount)
+        .ok_or_else(|| {
+            storage_api::Error::new_const(
+                "Overflowed the flow of an asset across the Ethereum bridge",
+            )
+        })?;
+    storage.write(&flow_key(asset, direction), flow)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ethereum_events::testing::DAI_ERC20_ETH_ADDRESS;
+
+    /// Test that storage key serialization yields the expected values.
+    #[test]
+    fn test_keys_to_string() {
+        assert_eq!(
+            paused_key(Direction::Inbound).to_string(),
+            "#tnam1quqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqfgdmms/flow_control/\
+             paused/inbound"
+        );
+        assert_eq!(
+            limit_key(&DAI_ERC20_ETH_ADDRESS, Direction::Outbound).to_string(),
+            "#tnam1quqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqfgdmms/flow_control/\
+             0x6b175474e89094c44da98b954eedeac495271d0f/limit/outbound"
+        );
+    }
+
+    /// Test that governance and flow keys are told apart.
+    #[test]
+    fn test_key_kinds() {
+        let paused = paused_key(Direction::Outbound);
+        let limit = limit_key(&DAI_ERC20_ETH_ADDRESS, Direction::Inbound);
+        let flow = flow_key(&DAI_ERC20_ETH_ADDRESS, Direction::Inbound);
+
+        assert!(is_governance_key(&paused));
+        assert!(is_governance_key(&limit));
+        assert!(!is_governance_key(&flow));
+
+        assert_eq!(is_flow_key(&paused), None);
+        assert_eq!(is_flow_key(&limit), None);
+        assert_eq!(
+            is_flow_key(&flow),
+            Some((DAI_ERC20_ETH_ADDRESS, Direction::Inbound))
+        );
+
+        assert_eq!(is_limit_key(&paused), None);
+        assert_eq!(is_limit_key(&flow), None);
+        assert_eq!(
+            is_limit_key(&limit),
+            Some((DAI_ERC20_ETH_ADDRESS, Direction::Inbound))
+        );
+    }
+
+    /// Test that all the pause flags and limits are read, and
+    /// none of the flows.
+    #[test]
+    fn test_read_params() {
+        use crate::ledger::storage::testing::TestWlStorage;
+
+        let mut storage = TestWlStorage::default();
+        set_paused(&mut storage, Direction::Outbound, true)
+            .expect("Test failed");
+        write_limit(
+            &mut storage,
+            &DAI_ERC20_ETH_ADDRESS,
+            Direction::Inbound,
+            Some(Amount::from(10)),
+        )
+        .expect("Test failed");
+        record_flow(
+            &mut storage,
+            &DAI_ERC20_ETH_ADDRESS,
+            Direction::Inbound,
+            Amount::from(5),
+        )
+        .expect("Test failed");
+
+        let params = read_params(&storage).expect("Test failed");
+        assert_eq!(
+            params,
+            FlowParams {
+                paused: BTreeSet::from([Direction::Outbound]),
+                limits: BTreeMap::from([(
+                    (DAI_ERC20_ETH_ADDRESS, Direction::Inbound),
+                    Amount::from(10)
+                )]),
+            }
+        );
+    }
+
+    /// Test that flows are reset at the start of each epoch.
+    #[test]
+    fn test_epoch_flow_record() {
+        let flow = EpochFlow::default()
+            .record(Epoch(1), Amount::from(10))
+            .expect("Test failed");
+        assert_eq!(flow.amount_at(Epoch(1)), Amount::from(10));
+        assert_eq!(flow.amount_at(Epoch(2)), Amount::zero());
+
+        let flow = flow.record(Epoch(1), Amount::from(5)).expect("Test failed");
+        assert_eq!(flow.amount_at(Epoch(1)), Amount::from(15));
+
+        let flow = flow.record(Epoch(2), Amount::from(3)).expect("Test failed");
+        assert_eq!(
+            flow,
+            EpochFlow {
+                epoch: Epoch(2),
+                amount: Amount::from(3),
+            }
+        );
+        assert_eq!(flow.record(Epoch(2), Amount::max()), None);
+    }
+}
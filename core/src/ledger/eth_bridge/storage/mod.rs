@@ -1,5 +1,8 @@
 //! Functionality for accessing the storage subspace
 pub mod bridge_pool;
+pub mod contract_messages;
+pub mod flow_control;
+pub mod state_root;
 pub mod whitelist;
 pub mod wrapped_erc20s;
 
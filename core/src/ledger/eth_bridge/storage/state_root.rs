@@ -0,0 +1,49 @@
+//! Storage of the roots of Namada's merkle tree signed by
+//! validators, which let Ethereum contracts verify proofs
+//! of Namada's state.
+//!
+//! Validators sign the state root alongside the Ethereum
+//! bridge pool root, in their vote extensions. Signatures are
+//! collected under [`sigs_key`], until the bridge pool root
+//! of the same height is confirmed by a quorum of validators,
+//! at which point they are moved to [`signed_root_key`]. The
+//! signatures over older state roots, which can no longer
+//! replace the signed root, are pruned at the same time.
+
+use super::prefix as ethbridge_key_prefix;
+use crate::types::storage::{self, BlockHeight, KeySeg};
+
+/// The name of the main storage segment.
+const MAIN_SEGMENT: &str = "state_root";
+/// The storage segment of the most recent signed state root.
+const SIGNED_SEGMENT: &str = "signed";
+/// The storage segment of the partial signatures over state roots.
+const SIGS_SEGMENT: &str = "sigs";
+
+/// Storage key of the most recent state root signed by a
+/// quorum of validators, along with its block height.
+pub fn signed_root_key() -> storage::Key {
+    ethbridge_key_prefix()
+        .push(&MAIN_SEGMENT.to_owned())
+        .expect("Should be able to push a storage key segment")
+        .push(&SIGNED_SEGMENT.to_owned())
+        .expect("Should be able to push a storage key segment")
+}
+
+/// Storage prefix of the signatures collected so far over
+/// state roots, at every block height.
+pub fn sigs_prefix() -> storage::Key {
+    ethbridge_key_prefix()
+        .push(&MAIN_SEGMENT.to_owned())
+        .expect("Should be able to push a storage key segment")
+        .push(&SIGS_SEGMENT.to_owned())
+        .expect("Should be able to push a storage key segment")
+}
+
+/// Storage key of the signatures collected so far over the
+/// state root at the given block height.
+pub fn sigs_key(height: BlockHeight) -> storage::Key {
+    sigs_prefix()
+        .push(&height)
+        .expect("Should be able to push a storage key segment")
+}
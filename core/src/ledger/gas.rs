@@ -107,7 +107,7 @@ impl Gas {
 
     /// Converts the sub gas units to whole ones. If the sub units are not a
     /// multiple of the `SCALE` than ceil the quotient
-    fn get_whole_gas_units(&self) -> u64 {
+    pub fn get_whole_gas_units(&self) -> u64 {
         let quotient = self.sub / SCALE;
         if self.sub % SCALE == 0 {
             quotient
@@ -279,7 +279,12 @@ impl TxGasMeter {
     ///    the network
     pub fn add_wrapper_gas(&mut self, tx_bytes: &[u8]) -> Result<()> {
         self.consume(WRAPPER_TX_VALIDATION_GAS)?;
+        self.add_tx_size_gas(tx_bytes)
+    }
 
+    /// Add the gas required by the space that the given transaction bytes
+    /// take in the block and by their transmission over the network
+    pub fn add_tx_size_gas(&mut self, tx_bytes: &[u8]) -> Result<()> {
         let bytes_len = tx_bytes.len() as u64;
         self.consume(
             bytes_len
@@ -22,6 +22,7 @@ use crate::types::dec::Dec;
     Serialize,
     Deserialize,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 /// Pgf parameter structure
 pub struct PgfParameters {
     /// The set of stewards
@@ -1,6 +1,8 @@
 //! The merkle tree in the storage
+use std::collections::BTreeMap;
 use std::fmt;
 use std::str::FromStr;
+use std::sync::OnceLock;
 
 use arse_merkle_tree::default_store::DefaultStore;
 use arse_merkle_tree::error::Error as MtError;
@@ -51,6 +53,8 @@ pub enum Error {
     Ics23MultiLeaf,
     #[error("A Tendermint proof can only be constructed from an ICS23 proof.")]
     TendermintProof,
+    #[error("Invalid native sub-tree registration: {0}")]
+    SubTreeRegistration(String),
 }
 
 /// Result for functions that may fail
@@ -72,18 +76,124 @@ pub type Smt<H> = ArseMerkleTree<H, SmtHash, Hash, SmtStore, 32>;
 pub type Amt<H> =
     ArseMerkleTree<H, StringKey, TreeBytes, AmtStore, IBC_KEY_LIMIT>;
 
+/// The kind of merkle tree backing a sub-tree declared by a native module
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubTreeKind {
+    /// A sparse merkle tree, committing to the hashes of values
+    Smt,
+    /// An arse merkle tree, committing to raw values. Unlike [`Self::Smt`],
+    /// it supports non-existence proofs.
+    Amt,
+}
+
+/// Declaration of a sub-tree of the merkle tree by a native module
+#[derive(Debug)]
+pub struct SubTreeSpec {
+    /// The name of the sub-tree. The base tree commits to the root of the
+    /// sub-tree under this name, and its stores are persisted under it.
+    pub name: &'static str,
+    /// The internal addresses whose storage keys belong to the sub-tree.
+    /// The address segment is stripped from the keys in the sub-tree.
+    pub owners: &'static [InternalAddress],
+    /// The kind of merkle tree backing the sub-tree
+    pub kind: SubTreeKind,
+    /// Whether the stores of the sub-tree are kept, such that proofs can be
+    /// constructed at past heights. The stores of non-provable sub-trees
+    /// are pruned every epoch.
+    pub provable: bool,
+}
+
+// Sub-trees are identified by their name
+impl PartialEq for SubTreeSpec {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for SubTreeSpec {}
+
+impl PartialOrd for SubTreeSpec {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.name.partial_cmp(other.name)
+    }
+}
+
+impl std::hash::Hash for SubTreeSpec {
+    fn hash<S: std::hash::Hasher>(&self, state: &mut S) {
+        self.name.hash(state)
+    }
+}
+
+/// The sub-tree holding the storage of the PoS and slash pool accounts. It
+/// is always registered, such that every embedder of the storage routes
+/// their keys the same way.
+pub static POS_SUB_TREE: SubTreeSpec = SubTreeSpec {
+    name: "pos",
+    owners: &[InternalAddress::PoS, InternalAddress::PosSlashPool],
+    kind: SubTreeKind::Smt,
+    provable: false,
+};
+
+/// The registry of sub-trees declared by native modules, starting with
+/// [`POS_SUB_TREE`]. It is frozen the first time it is read.
+static NATIVE_SUB_TREES: OnceLock<Vec<&'static SubTreeSpec>> = OnceLock::new();
+
+/// Register the sub-trees declared by native modules, in addition to
+/// [`POS_SUB_TREE`].
+///
+/// This must be called once, before any merkle tree is built, as the
+/// registry is frozen the first time it is read. Names and owners must
+/// be unique, and may not clash with the built-in store types. Since the
+/// base tree commits to the root of every registered sub-tree, all the
+/// nodes of a chain must register the same sub-trees. A persisted tree
+/// committing to other sub-trees fails to be restored.
+pub fn register_native_sub_trees(
+    specs: Vec<&'static SubTreeSpec>,
+) -> Result<()> {
+    let specs: Vec<_> = std::iter::once(&POS_SUB_TREE).chain(specs).collect();
+    validate_native_sub_trees(&specs)?;
+    NATIVE_SUB_TREES.set(specs).map_err(|_| {
+        Error::SubTreeRegistration(
+            "the native sub-trees were already registered or read".to_string(),
+        )
+    })
+}
+
+/// Get the sub-trees registered by native modules
+pub fn native_sub_trees() -> &'static [&'static SubTreeSpec] {
+    NATIVE_SUB_TREES.get_or_init(|| vec![&POS_SUB_TREE])
+}
+
+/// Check that the given sub-trees can be registered together
+fn validate_native_sub_trees(specs: &[&SubTreeSpec]) -> Result<()> {
+    const BUILT_IN_NAMES: [&str; 4] =
+        ["base", "account", "ibc", "eth_bridge_pool"];
+    let mut names = std::collections::BTreeSet::new();
+    let mut owners = std::collections::BTreeSet::new();
+    for spec in specs {
+        if BUILT_IN_NAMES.contains(&spec.name) || !names.insert(spec.name) {
+            return Err(Error::SubTreeRegistration(format!(
+                "the name {} is already taken",
+                spec.name
+            )));
+        }
+        for owner in spec.owners {
+            if matches!(
+                owner,
+                InternalAddress::Ibc | InternalAddress::EthBridgePool
+            ) || !owners.insert(owner)
+            {
+                return Err(Error::SubTreeRegistration(format!(
+                    "the keys of {owner} already belong to another sub-tree"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Store types for the merkle tree
-#[derive(
-    Clone,
-    Copy,
-    Debug,
-    Hash,
-    PartialEq,
-    Eq,
-    PartialOrd,
-    BorshSerialize,
-    BorshDeserialize,
-)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd)]
 pub enum StoreType {
     /// Base tree, which has roots of the subtrees
     Base,
@@ -91,10 +201,10 @@ pub enum StoreType {
     Account,
     /// For IBC-related data
     Ibc,
-    /// For PoS-related data
-    PoS,
     /// For the Ethereum bridge Pool transfers
     BridgePool,
+    /// For a sub-tree registered with [`register_native_sub_trees`]
+    Native(&'static SubTreeSpec),
 }
 
 /// Backing storage for merkle trees
@@ -105,10 +215,10 @@ pub enum Store {
     Account(SmtStore),
     /// For IBC-related data
     Ibc(AmtStore),
-    /// For PoS-related data
-    PoS(SmtStore),
     /// For the Ethereum bridge Pool transfers
     BridgePool(BridgePoolStore),
+    /// For a sub-tree registered by a native module
+    Native(&'static SubTreeSpec, NativeStore),
 }
 
 impl Store {
@@ -118,8 +228,10 @@ impl Store {
             Self::Base(store) => StoreRef::Base(store),
             Self::Account(store) => StoreRef::Account(store),
             Self::Ibc(store) => StoreRef::Ibc(store),
-            Self::PoS(store) => StoreRef::PoS(store),
             Self::BridgePool(store) => StoreRef::BridgePool(store),
+            Self::Native(spec, store) => {
+                StoreRef::Native(*spec, store.as_ref())
+            }
         }
     }
 }
@@ -132,10 +244,10 @@ pub enum StoreRef<'a> {
     Account(&'a SmtStore),
     /// For IBC-related data
     Ibc(&'a AmtStore),
-    /// For PoS-related data
-    PoS(&'a SmtStore),
     /// For the Ethereum bridge Pool transfers
     BridgePool(&'a BridgePoolStore),
+    /// For a sub-tree registered by a native module
+    Native(&'static SubTreeSpec, NativeStoreRef<'a>),
 }
 
 impl<'a> StoreRef<'a> {
@@ -145,8 +257,8 @@ impl<'a> StoreRef<'a> {
             Self::Base(store) => Store::Base(store.to_owned()),
             Self::Account(store) => Store::Account(store.to_owned()),
             Self::Ibc(store) => Store::Ibc(store.to_owned()),
-            Self::PoS(store) => Store::PoS(store.to_owned()),
             Self::BridgePool(store) => Store::BridgePool(store.to_owned()),
+            Self::Native(spec, store) => Store::Native(spec, store.to_owned()),
         }
     }
 
@@ -156,48 +268,106 @@ impl<'a> StoreRef<'a> {
             Self::Base(store) => store.serialize_to_vec(),
             Self::Account(store) => store.serialize_to_vec(),
             Self::Ibc(store) => store.serialize_to_vec(),
-            Self::PoS(store) => store.serialize_to_vec(),
             Self::BridgePool(store) => store.serialize_to_vec(),
+            Self::Native(_, store) => store.encode(),
+        }
+    }
+}
+
+/// Backing storage of a sub-tree registered by a native module
+pub enum NativeStore {
+    /// Store of a [`SubTreeKind::Smt`] sub-tree
+    Smt(SmtStore),
+    /// Store of a [`SubTreeKind::Amt`] sub-tree
+    Amt(AmtStore),
+}
+
+impl NativeStore {
+    /// Create an empty store of the given kind
+    pub fn new(kind: SubTreeKind) -> Self {
+        match kind {
+            SubTreeKind::Smt => Self::Smt(SmtStore::default()),
+            SubTreeKind::Amt => Self::Amt(AmtStore::default()),
+        }
+    }
+
+    /// Convert to a `NativeStoreRef` with borrowed store
+    pub fn as_ref(&self) -> NativeStoreRef {
+        match self {
+            Self::Smt(store) => NativeStoreRef::Smt(store),
+            Self::Amt(store) => NativeStoreRef::Amt(store),
+        }
+    }
+}
+
+/// Pointer to backing storage of a sub-tree registered by a native module
+#[derive(Clone, Copy)]
+pub enum NativeStoreRef<'a> {
+    /// Store of a [`SubTreeKind::Smt`] sub-tree
+    Smt(&'a SmtStore),
+    /// Store of a [`SubTreeKind::Amt`] sub-tree
+    Amt(&'a AmtStore),
+}
+
+impl<'a> NativeStoreRef<'a> {
+    /// Get an owned copy of the backing store
+    pub fn to_owned(&self) -> NativeStore {
+        match *self {
+            Self::Smt(store) => NativeStore::Smt(store.to_owned()),
+            Self::Amt(store) => NativeStore::Amt(store.to_owned()),
+        }
+    }
+
+    /// Borsh serialize the backing store
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::Smt(store) => store.serialize_to_vec(),
+            Self::Amt(store) => store.serialize_to_vec(),
         }
     }
 }
 
 impl StoreType {
     /// Get an iterator for the base tree and subtrees
-    pub fn iter() -> std::slice::Iter<'static, Self> {
-        static SUB_TREE_TYPES: [StoreType; 5] = [
-            StoreType::Base,
-            StoreType::Account,
-            StoreType::PoS,
-            StoreType::Ibc,
-            StoreType::BridgePool,
-        ];
-        SUB_TREE_TYPES.iter()
+    pub fn iter() -> impl Iterator<Item = Self> {
+        std::iter::once(StoreType::Base).chain(Self::iter_subtrees())
     }
 
     /// Get an iterator for subtrees
-    pub fn iter_subtrees() -> std::slice::Iter<'static, Self> {
-        static SUB_TREE_TYPES: [StoreType; 4] = [
-            StoreType::Account,
-            StoreType::PoS,
-            StoreType::Ibc,
-            StoreType::BridgePool,
-        ];
-        SUB_TREE_TYPES.iter()
+    pub fn iter_subtrees() -> impl Iterator<Item = Self> {
+        [StoreType::Account, StoreType::Ibc, StoreType::BridgePool]
+            .into_iter()
+            .chain(native_sub_trees().iter().copied().map(StoreType::Native))
     }
 
     /// Get an iterator for the provable subtrees
-    pub fn iter_provable() -> std::slice::Iter<'static, Self> {
-        static SUB_TREE_TYPES: [StoreType; 2] =
-            [StoreType::Ibc, StoreType::BridgePool];
-        SUB_TREE_TYPES.iter()
+    pub fn iter_provable() -> impl Iterator<Item = Self> {
+        Self::iter_subtrees().filter(|st| st.is_provable())
     }
 
     /// Get an iterator for the non-provable subtrees
-    pub fn iter_non_provable() -> std::slice::Iter<'static, Self> {
-        static SUB_TREE_TYPES: [StoreType; 2] =
-            [StoreType::Account, StoreType::PoS];
-        SUB_TREE_TYPES.iter()
+    pub fn iter_non_provable() -> impl Iterator<Item = Self> {
+        Self::iter_subtrees().filter(|st| !st.is_provable())
+    }
+
+    /// Check if the stores of this type are kept for proofs at past
+    /// heights
+    pub fn is_provable(&self) -> bool {
+        match self {
+            Self::Ibc | Self::BridgePool => true,
+            Self::Native(spec) => spec.provable,
+            Self::Base | Self::Account => false,
+        }
+    }
+
+    /// Find the registered native sub-tree owning the given address
+    fn native_owner(
+        internal: &InternalAddress,
+    ) -> Option<&'static SubTreeSpec> {
+        native_sub_trees()
+            .iter()
+            .copied()
+            .find(|spec| spec.owners.contains(internal))
     }
 
     /// Get the store type and the sub key
@@ -208,9 +378,6 @@ impl StoreType {
         match key.segments.get(0) {
             Some(DbKeySeg::AddressSeg(Address::Internal(internal))) => {
                 match internal {
-                    InternalAddress::PoS | InternalAddress::PosSlashPool => {
-                        Ok((StoreType::PoS, key.sub_key()?))
-                    }
                     InternalAddress::Ibc => {
                         Ok((StoreType::Ibc, key.sub_key()?))
                     }
@@ -223,8 +390,13 @@ impl StoreType {
                             Ok((StoreType::Account, key.clone()))
                         }
                     }
-                    // use the same key for Parameters
-                    _ => Ok((StoreType::Account, key.clone())),
+                    _ => match Self::native_owner(internal) {
+                        Some(spec) => {
+                            Ok((StoreType::Native(spec), key.sub_key()?))
+                        }
+                        // use the same key for Parameters
+                        None => Ok((StoreType::Account, key.clone())),
+                    },
                 }
             }
             // use the same key for Account
@@ -234,12 +406,20 @@ impl StoreType {
 
     /// Get the key prefix if the store type is for a provable subtree.
     /// Otherwise, returns None.
+    ///
+    /// Provable native sub-trees with more than one owner have no single
+    /// prefix, and also return None.
     pub fn provable_prefix(&self) -> Option<Key> {
         let addr = match self {
             Self::Ibc => Address::Internal(InternalAddress::Ibc),
             Self::BridgePool => {
                 Address::Internal(InternalAddress::EthBridgePool)
             }
+            Self::Native(SubTreeSpec {
+                owners: [owner],
+                provable: true,
+                ..
+            }) => Address::Internal(owner.clone()),
             _ => return None,
         };
         Some(addr.to_db_key().into())
@@ -261,12 +441,20 @@ impl StoreType {
             Self::Ibc => Ok(Store::Ibc(
                 types::decode(bytes).map_err(Error::CodingError)?,
             )),
-            Self::PoS => Ok(Store::PoS(
-                types::decode(bytes).map_err(Error::CodingError)?,
-            )),
             Self::BridgePool => Ok(Store::BridgePool(
                 types::decode(bytes).map_err(Error::CodingError)?,
             )),
+            Self::Native(spec) => {
+                let store = match spec.kind {
+                    SubTreeKind::Smt => NativeStore::Smt(
+                        types::decode(bytes).map_err(Error::CodingError)?,
+                    ),
+                    SubTreeKind::Amt => NativeStore::Amt(
+                        types::decode(bytes).map_err(Error::CodingError)?,
+                    ),
+                };
+                Ok(Store::Native(*spec, store))
+            }
         }
     }
 }
@@ -279,9 +467,13 @@ impl FromStr for StoreType {
             "base" => Ok(StoreType::Base),
             "account" => Ok(StoreType::Account),
             "ibc" => Ok(StoreType::Ibc),
-            "pos" => Ok(StoreType::PoS),
             "eth_bridge_pool" => Ok(StoreType::BridgePool),
-            _ => Err(Error::StoreType(s.to_string())),
+            _ => native_sub_trees()
+                .iter()
+                .copied()
+                .find(|spec| spec.name == s)
+                .map(StoreType::Native)
+                .ok_or_else(|| Error::StoreType(s.to_string())),
         }
     }
 }
@@ -292,8 +484,8 @@ impl fmt::Display for StoreType {
             StoreType::Base => write!(f, "base"),
             StoreType::Account => write!(f, "account"),
             StoreType::Ibc => write!(f, "ibc"),
-            StoreType::PoS => write!(f, "pos"),
             StoreType::BridgePool => write!(f, "eth_bridge_pool"),
+            StoreType::Native(spec) => write!(f, "{}", spec.name),
         }
     }
 }
@@ -314,14 +506,25 @@ pub fn subtree_key_prefix(st: &StoreType, epoch: Epoch) -> Key {
         .with_segment(st.to_string())
 }
 
+/// Merkle tree of a sub-tree registered by a native module
+enum NativeTree<H: StorageHasher + Default> {
+    Smt(Smt<H>),
+    Amt(Amt<H>),
+}
+
 /// Merkle tree storage
-#[derive(Default)]
 pub struct MerkleTree<H: StorageHasher + Default> {
     base: Smt<H>,
     account: Smt<H>,
     ibc: Amt<H>,
-    pos: Smt<H>,
     bridge_pool: BridgePoolTree,
+    native: BTreeMap<&'static str, NativeTree<H>>,
+}
+
+impl<H: StorageHasher + Default> Default for MerkleTree<H> {
+    fn default() -> Self {
+        Self::new_partial(MerkleTreeStoresRead::default())
+    }
 }
 
 impl<H: StorageHasher + Default> core::fmt::Debug for MerkleTree<H> {
@@ -336,39 +539,24 @@ impl<H: StorageHasher + Default> core::fmt::Debug for MerkleTree<H> {
 impl<H: StorageHasher + Default> MerkleTree<H> {
     /// Restore the full tree from the stores
     pub fn new(stores: MerkleTreeStoresRead) -> Result<Self> {
-        let base = Smt::new(stores.base.0.into(), stores.base.1);
-        let account = Smt::new(stores.account.0.into(), stores.account.1);
-        let ibc = Amt::new(stores.ibc.0.into(), stores.ibc.1);
-        let pos = Smt::new(stores.pos.0.into(), stores.pos.1);
-        let bridge_pool =
-            BridgePoolTree::new(stores.bridge_pool.0, stores.bridge_pool.1);
-        let tree = Self {
-            base,
-            account,
-            ibc,
-            pos,
-            bridge_pool,
-        };
+        let tree = Self::new_partial(stores);
 
         // validate
-        let account_key = H::hash(StoreType::Account.to_string());
-        let account_root = tree.base.get(&account_key.into())?;
-        let ibc_key = H::hash(StoreType::Ibc.to_string());
-        let ibc_root = tree.base.get(&ibc_key.into())?;
-        let pos_key = H::hash(StoreType::PoS.to_string());
-        let pos_root = tree.base.get(&pos_key.into())?;
-        let bp_key = H::hash(StoreType::BridgePool.to_string());
-        let bp_root = tree.base.get(&bp_key.into())?;
-        if tree.base.root().is_zero()
-            && tree.account.root().is_zero()
-            && tree.ibc.root().is_zero()
-            && tree.pos.root().is_zero()
-            && tree.bridge_pool.root().is_zero()
-            || (account_root == tree.account.root().into()
-                && ibc_root == tree.ibc.root().into()
-                && pos_root == tree.pos.root().into()
-                && bp_root == tree.bridge_pool.root().into())
-        {
+        let is_empty = tree.base.root().is_zero()
+            && StoreType::iter_subtrees()
+                .all(|st| tree.sub_root(&st).0 == [0u8; 32]);
+        // the base tree must commit to the roots of the registered
+        // sub-trees, and to nothing else, e.g. the root of a sub-tree that
+        // isn't registered in this process
+        let mut expected_base =
+            Smt::<H>::new(H256::zero(), SmtStore::default());
+        for st in StoreType::iter_subtrees() {
+            let base_key = H::hash(st.to_string());
+            expected_base
+                .update(base_key.into(), Hash::from(tree.sub_root(&st)))?;
+        }
+        let is_consistent = expected_base.root() == tree.base.root();
+        if is_empty || is_consistent {
             Ok(tree)
         } else {
             Err(Error::MerkleTree(
@@ -382,25 +570,48 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
         let base = Smt::new(stores.base.0.into(), stores.base.1);
         let account = Smt::new(stores.account.0.into(), stores.account.1);
         let ibc = Amt::new(stores.ibc.0.into(), stores.ibc.1);
-        let pos = Smt::new(stores.pos.0.into(), stores.pos.1);
         let bridge_pool =
             BridgePoolTree::new(stores.bridge_pool.0, stores.bridge_pool.1);
+        let native = stores
+            .native
+            .into_iter()
+            .map(|(name, (root, store))| {
+                let tree = match store {
+                    NativeStore::Smt(store) => {
+                        NativeTree::Smt(Smt::new(root.into(), store))
+                    }
+                    NativeStore::Amt(store) => {
+                        NativeTree::Amt(Amt::new(root.into(), store))
+                    }
+                };
+                (name, tree)
+            })
+            .collect();
         Self {
             base,
             account,
             ibc,
-            pos,
             bridge_pool,
+            native,
         }
     }
 
+    fn native_tree(&self, spec: &SubTreeSpec) -> &NativeTree<H> {
+        self.native
+            .get(spec.name)
+            .expect("Native sub-trees should be registered")
+    }
+
     fn tree(&self, store_type: &StoreType) -> Box<dyn SubTreeRead + '_> {
         match store_type {
             StoreType::Base => Box::new(&self.base),
             StoreType::Account => Box::new(&self.account),
             StoreType::Ibc => Box::new(&self.ibc),
-            StoreType::PoS => Box::new(&self.pos),
             StoreType::BridgePool => Box::new(&self.bridge_pool),
+            StoreType::Native(spec) => match self.native_tree(spec) {
+                NativeTree::Smt(tree) => Box::new(tree),
+                NativeTree::Amt(tree) => Box::new(tree),
+            },
         }
     }
 
@@ -412,8 +623,15 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
             StoreType::Base => Box::new(&mut self.base),
             StoreType::Account => Box::new(&mut self.account),
             StoreType::Ibc => Box::new(&mut self.ibc),
-            StoreType::PoS => Box::new(&mut self.pos),
             StoreType::BridgePool => Box::new(&mut self.bridge_pool),
+            StoreType::Native(spec) => match self
+                .native
+                .get_mut(spec.name)
+                .expect("Native sub-trees should be registered")
+            {
+                NativeTree::Smt(tree) => Box::new(tree),
+                NativeTree::Amt(tree) => Box::new(tree),
+            },
         }
     }
 
@@ -475,15 +693,30 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
 
     /// Get the stores of the base and sub trees
     pub fn stores(&self) -> MerkleTreeStoresWrite {
+        let native = self
+            .native
+            .iter()
+            .map(|(name, tree)| {
+                let root_and_store = match tree {
+                    NativeTree::Smt(tree) => {
+                        (tree.root().into(), NativeStoreRef::Smt(tree.store()))
+                    }
+                    NativeTree::Amt(tree) => {
+                        (tree.root().into(), NativeStoreRef::Amt(tree.store()))
+                    }
+                };
+                (*name, root_and_store)
+            })
+            .collect();
         MerkleTreeStoresWrite {
             base: (self.base.root().into(), self.base.store()),
             account: (self.account.root().into(), self.account.store()),
             ibc: (self.ibc.root().into(), self.ibc.store()),
-            pos: (self.pos.root().into(), self.pos.store()),
             bridge_pool: (
                 self.bridge_pool.root().into(),
                 self.bridge_pool.store(),
             ),
+            native,
         }
     }
 
@@ -519,13 +752,25 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
     /// Get the non-existence proof
     pub fn get_non_existence_proof(&self, key: &Key) -> Result<Proof> {
         let (store_type, sub_key) = StoreType::sub_key(key)?;
-        if store_type != StoreType::Ibc {
-            return Err(Error::NonExistenceProof(store_type.to_string()));
-        }
+        // only the trees committing to raw values support them
+        let tree = match store_type {
+            StoreType::Ibc => &self.ibc,
+            StoreType::Native(spec) => match self.native_tree(spec) {
+                NativeTree::Amt(tree) => tree,
+                NativeTree::Smt(_) => {
+                    return Err(Error::NonExistenceProof(
+                        store_type.to_string(),
+                    ));
+                }
+            },
+            _ => {
+                return Err(Error::NonExistenceProof(store_type.to_string()));
+            }
+        };
 
         let string_key =
             StringKey::try_from_bytes(sub_key.to_string().as_bytes())?;
-        let mut nep = self.ibc.non_membership_proof(&string_key)?;
+        let mut nep = tree.non_membership_proof(&string_key)?;
         // Replace the values and the leaf op for the verification
         if let Some(ref mut nep) = nep.proof {
             match nep {
@@ -624,13 +869,29 @@ impl fmt::Display for MerkleRoot {
 }
 
 /// The root and store pairs to restore the trees
-#[derive(Default)]
 pub struct MerkleTreeStoresRead {
     base: (Hash, SmtStore),
     account: (Hash, SmtStore),
     ibc: (Hash, AmtStore),
-    pos: (Hash, SmtStore),
     bridge_pool: (KeccakHash, BridgePoolStore),
+    native: BTreeMap<&'static str, (Hash, NativeStore)>,
+}
+
+impl Default for MerkleTreeStoresRead {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            account: Default::default(),
+            ibc: Default::default(),
+            bridge_pool: Default::default(),
+            native: native_sub_trees()
+                .iter()
+                .map(|spec| {
+                    (spec.name, (Hash::default(), NativeStore::new(spec.kind)))
+                })
+                .collect(),
+        }
+    }
 }
 
 impl MerkleTreeStoresRead {
@@ -640,8 +901,8 @@ impl MerkleTreeStoresRead {
             StoreType::Base => self.base.0 = root,
             StoreType::Account => self.account.0 = root,
             StoreType::Ibc => self.ibc.0 = root,
-            StoreType::PoS => self.pos.0 = root,
             StoreType::BridgePool => self.bridge_pool.0 = root.into(),
+            StoreType::Native(spec) => self.native_entry_mut(*spec).0 = root,
         }
     }
 
@@ -651,19 +912,35 @@ impl MerkleTreeStoresRead {
             Store::Base(store) => self.base.1 = store,
             Store::Account(store) => self.account.1 = store,
             Store::Ibc(store) => self.ibc.1 = store,
-            Store::PoS(store) => self.pos.1 = store,
             Store::BridgePool(store) => self.bridge_pool.1 = store,
+            Store::Native(spec, store) => self.native_entry_mut(spec).1 = store,
         }
     }
 
+    fn native_entry_mut(
+        &mut self,
+        spec: &'static SubTreeSpec,
+    ) -> &mut (Hash, NativeStore) {
+        self.native
+            .entry(spec.name)
+            .or_insert_with(|| (Hash::default(), NativeStore::new(spec.kind)))
+    }
+
     /// Read the backing store of the requested type
     pub fn get_store(&self, store_type: StoreType) -> StoreRef {
         match store_type {
             StoreType::Base => StoreRef::Base(&self.base.1),
             StoreType::Account => StoreRef::Account(&self.account.1),
             StoreType::Ibc => StoreRef::Ibc(&self.ibc.1),
-            StoreType::PoS => StoreRef::PoS(&self.pos.1),
             StoreType::BridgePool => StoreRef::BridgePool(&self.bridge_pool.1),
+            StoreType::Native(spec) => StoreRef::Native(
+                spec,
+                self.native
+                    .get(spec.name)
+                    .expect("Native sub-trees should be registered")
+                    .1
+                    .as_ref(),
+            ),
         }
     }
 
@@ -673,8 +950,12 @@ impl MerkleTreeStoresRead {
             StoreType::Base => self.base.0,
             StoreType::Account => self.account.0,
             StoreType::Ibc => self.ibc.0,
-            StoreType::PoS => self.pos.0,
             StoreType::BridgePool => Hash(self.bridge_pool.0.0),
+            StoreType::Native(spec) => self
+                .native
+                .get(spec.name)
+                .map(|(root, _)| *root)
+                .unwrap_or_default(),
         }
     }
 }
@@ -684,8 +965,8 @@ pub struct MerkleTreeStoresWrite<'a> {
     base: (Hash, &'a SmtStore),
     account: (Hash, &'a SmtStore),
     ibc: (Hash, &'a AmtStore),
-    pos: (Hash, &'a SmtStore),
     bridge_pool: (Hash, &'a BridgePoolStore),
+    native: BTreeMap<&'static str, (Hash, NativeStoreRef<'a>)>,
 }
 
 impl<'a> MerkleTreeStoresWrite<'a> {
@@ -695,8 +976,8 @@ impl<'a> MerkleTreeStoresWrite<'a> {
             StoreType::Base => &self.base.0,
             StoreType::Account => &self.account.0,
             StoreType::Ibc => &self.ibc.0,
-            StoreType::PoS => &self.pos.0,
             StoreType::BridgePool => &self.bridge_pool.0,
+            StoreType::Native(spec) => &self.native_entry(spec).0,
         }
     }
 
@@ -706,10 +987,18 @@ impl<'a> MerkleTreeStoresWrite<'a> {
             StoreType::Base => StoreRef::Base(self.base.1),
             StoreType::Account => StoreRef::Account(self.account.1),
             StoreType::Ibc => StoreRef::Ibc(self.ibc.1),
-            StoreType::PoS => StoreRef::PoS(self.pos.1),
             StoreType::BridgePool => StoreRef::BridgePool(self.bridge_pool.1),
+            StoreType::Native(spec) => {
+                StoreRef::Native(*spec, self.native_entry(spec).1)
+            }
         }
     }
+
+    fn native_entry(&self, spec: &SubTreeSpec) -> &(Hash, NativeStoreRef<'a>) {
+        self.native
+            .get(spec.name)
+            .expect("Native sub-trees should be registered")
+    }
 }
 
 impl From<StorageError> for Error {
@@ -885,8 +1174,8 @@ mod test {
         let stores_write = tree.stores();
         let mut stores_read = MerkleTreeStoresRead::default();
         for st in StoreType::iter() {
-            stores_read.set_root(st, *stores_write.root(st));
-            stores_read.set_store(stores_write.store(st).to_owned());
+            stores_read.set_root(&st, *stores_write.root(&st));
+            stores_read.set_store(stores_write.store(&st).to_owned());
         }
         let restored_tree =
             MerkleTree::<Sha256Hasher>::new(stores_read).unwrap();
@@ -894,6 +1183,128 @@ mod test {
         assert!(restored_tree.has_key(&pos_key).unwrap());
     }
 
+    /// Test that a persisted tree committing to other sub-trees than the
+    /// registered ones fails to be restored.
+    #[test]
+    fn test_restore_tree_with_other_sub_trees() {
+        let restore = |tree: &MerkleTree<Sha256Hasher>| {
+            let stores_write = tree.stores();
+            let mut stores_read = MerkleTreeStoresRead::default();
+            for st in StoreType::iter() {
+                stores_read.set_root(&st, *stores_write.root(&st));
+                stores_read.set_store(stores_write.store(&st).to_owned());
+            }
+            stores_read
+        };
+        let key_prefix: Key =
+            Address::Internal(InternalAddress::PoS).to_db_key().into();
+        let pos_key = key_prefix.push(&"test".to_string()).unwrap();
+        let mut tree = MerkleTree::<Sha256Hasher>::default();
+        tree.update(&pos_key, [2u8; 8]).unwrap();
+
+        // the tree was persisted by a node that registered another sub-tree
+        let mut other_tree =
+            MerkleTree::<Sha256Hasher>::new_partial(restore(&tree));
+        let base_key = Sha256Hasher::hash("other");
+        other_tree
+            .base
+            .update(base_key.into(), Hash([1u8; 32]))
+            .unwrap();
+        assert!(MerkleTree::<Sha256Hasher>::new(restore(&other_tree)).is_err());
+
+        // the tree commits to a registered sub-tree whose store is missing
+        let mut stores_read = restore(&tree);
+        let pos = StoreType::Native(&POS_SUB_TREE);
+        stores_read.set_root(&pos, Hash::default());
+        stores_read.set_store(Store::Native(
+            &POS_SUB_TREE,
+            NativeStore::new(POS_SUB_TREE.kind),
+        ));
+        assert!(MerkleTree::<Sha256Hasher>::new(stores_read).is_err());
+
+        assert!(MerkleTree::<Sha256Hasher>::new(restore(&tree)).is_ok());
+    }
+
+    #[test]
+    fn test_native_sub_tree_registry() {
+        // store types round-trip through their names, which are unique
+        let names: std::collections::HashSet<_> = StoreType::iter()
+            .map(|st| {
+                let name = st.to_string();
+                assert_eq!(StoreType::from_str(&name).unwrap(), st);
+                name
+            })
+            .collect();
+        assert_eq!(names.len(), StoreType::iter().count());
+
+        // keys of all the owners are routed to the native sub-tree
+        for owner in POS_SUB_TREE.owners {
+            let key: Key = Address::Internal(owner.clone()).to_db_key().into();
+            let key = key.push(&"test".to_string()).unwrap();
+            let (store_type, sub_key) = StoreType::sub_key(&key).unwrap();
+            assert_eq!(store_type, StoreType::Native(&POS_SUB_TREE));
+            assert_eq!(sub_key, Key::parse("test").unwrap());
+        }
+        assert!(!StoreType::Native(&POS_SUB_TREE).is_provable());
+        assert!(StoreType::Native(&POS_SUB_TREE).provable_prefix().is_none());
+
+        // the base tree commits to the root of the native sub-tree
+        let mut tree = MerkleTree::<Sha256Hasher>::default();
+        let key: Key = Address::Internal(InternalAddress::PosSlashPool)
+            .to_db_key()
+            .into();
+        let key = key.push(&"test".to_string()).unwrap();
+        tree.update(&key, [1u8; 8]).unwrap();
+        let base_key = Sha256Hasher::hash(POS_SUB_TREE.name);
+        assert_eq!(
+            tree.base.get(&base_key.into()).unwrap(),
+            Hash::from(tree.sub_root(&StoreType::Native(&POS_SUB_TREE)))
+        );
+        assert!(tree.get_non_existence_proof(&key).is_err());
+
+        // the registry is frozen once it has been read
+        assert!(register_native_sub_trees(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_native_sub_tree_validation() {
+        static OTHER_SUB_TREE: SubTreeSpec = SubTreeSpec {
+            name: "other",
+            owners: &[InternalAddress::Governance],
+            kind: SubTreeKind::Amt,
+            provable: true,
+        };
+        static CLASHING_NAME: SubTreeSpec = SubTreeSpec {
+            name: "ibc",
+            owners: &[InternalAddress::Pgf],
+            kind: SubTreeKind::Smt,
+            provable: false,
+        };
+        static CLASHING_OWNER: SubTreeSpec = SubTreeSpec {
+            name: "clashing",
+            owners: &[InternalAddress::PoS],
+            kind: SubTreeKind::Smt,
+            provable: false,
+        };
+        static BUILT_IN_OWNER: SubTreeSpec = SubTreeSpec {
+            name: "bridge",
+            owners: &[InternalAddress::EthBridgePool],
+            kind: SubTreeKind::Smt,
+            provable: false,
+        };
+
+        assert!(validate_native_sub_trees(&[&POS_SUB_TREE, &OTHER_SUB_TREE])
+            .is_ok());
+        for invalid in [
+            vec![&POS_SUB_TREE, &POS_SUB_TREE],
+            vec![&CLASHING_NAME],
+            vec![&POS_SUB_TREE, &CLASHING_OWNER],
+            vec![&BUILT_IN_OWNER],
+        ] {
+            assert!(validate_native_sub_trees(&invalid).is_err());
+        }
+    }
+
     #[test]
     fn test_ibc_existence_proof() {
         let mut tree = MerkleTree::<Sha256Hasher>::default();
@@ -14,7 +14,8 @@ use super::merkle_tree::{
     base_tree_key_prefix, subtree_key_prefix, MerkleTreeStoresRead, StoreType,
 };
 use super::{
-    BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, Error, Result, DB,
+    BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, Error,
+    EthOracleProgress, Result, DB,
 };
 use crate::ledger::masp_conversions::ConversionState;
 use crate::ledger::storage::types::{self, KVBytes, PrefixIterator};
@@ -195,13 +196,13 @@ impl DB for MockDB {
         // Restore subtrees of Merkle tree
         if let Some(epoch) = epoch {
             for st in StoreType::iter_subtrees() {
-                let prefix_key = subtree_key_prefix(st, epoch);
+                let prefix_key = subtree_key_prefix(&st, epoch);
                 let root_key =
                     prefix_key.clone().with_segment("root".to_owned());
                 if let Some(bytes) = self.0.borrow().get(&root_key.to_string())
                 {
                     merkle_tree_stores.set_root(
-                        st,
+                        &st,
                         types::decode(bytes).map_err(Error::CodingError)?,
                     );
                 }
@@ -299,22 +300,22 @@ impl DB for MockDB {
         // Merkle tree
         {
             for st in StoreType::iter() {
-                if *st == StoreType::Base || is_full_commit {
-                    let key_prefix = if *st == StoreType::Base {
+                if st == StoreType::Base || is_full_commit {
+                    let key_prefix = if st == StoreType::Base {
                         base_tree_key_prefix(height)
                     } else {
-                        subtree_key_prefix(st, epoch)
+                        subtree_key_prefix(&st, epoch)
                     };
                     let root_key =
                         key_prefix.clone().with_segment("root".to_owned());
                     self.0.borrow_mut().insert(
                         root_key.to_string(),
-                        types::encode(merkle_tree_stores.root(st)),
+                        types::encode(merkle_tree_stores.root(&st)),
                     );
                     let store_key = key_prefix.with_segment("store".to_owned());
                     self.0.borrow_mut().insert(
                         store_key.to_string(),
-                        merkle_tree_stores.store(st).encode(),
+                        merkle_tree_stores.store(&st).encode(),
                     );
                 }
             }
@@ -412,21 +413,20 @@ impl DB for MockDB {
     ) -> Result<Option<MerkleTreeStoresRead>> {
         let mut merkle_tree_stores = MerkleTreeStoresRead::default();
         let store_types = store_type
-            .as_ref()
             .map(|st| Either::Left(std::iter::once(st)))
             .unwrap_or_else(|| Either::Right(StoreType::iter()));
         for st in store_types {
-            let key_prefix = if *st == StoreType::Base {
+            let key_prefix = if st == StoreType::Base {
                 base_tree_key_prefix(base_height)
             } else {
-                subtree_key_prefix(st, epoch)
+                subtree_key_prefix(&st, epoch)
             };
             let root_key = key_prefix.clone().with_segment("root".to_owned());
             let bytes = self.0.borrow().get(&root_key.to_string()).cloned();
             match bytes {
                 Some(b) => {
                     let root = types::decode(b).map_err(Error::CodingError)?;
-                    merkle_tree_stores.set_root(st, root);
+                    merkle_tree_stores.set_root(&st, root);
                 }
                 None => return Ok(None),
             }
@@ -627,6 +627,25 @@ impl DB for MockDB {
 
         Ok(())
     }
+
+    fn read_eth_oracle_progress(&self) -> Result<Option<EthOracleProgress>> {
+        match self.0.borrow().get("eth_oracle_progress") {
+            Some(bytes) => {
+                types::decode(bytes).map(Some).map_err(Error::CodingError)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn write_eth_oracle_progress(
+        &mut self,
+        progress: &EthOracleProgress,
+    ) -> Result<()> {
+        self.0
+            .borrow_mut()
+            .insert("eth_oracle_progress".to_owned(), types::encode(progress));
+        Ok(())
+    }
 }
 
 impl<'iter> DBIter<'iter> for MockDB {
@@ -11,6 +11,7 @@ pub mod write_log;
 
 use core::fmt::Debug;
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::format;
 
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -39,7 +40,7 @@ use crate::ledger::storage::merkle_tree::{
 use crate::tendermint::merkle::proof::ProofOps;
 use crate::types::address::{Address, EstablishedAddressGen, InternalAddress};
 use crate::types::chain::{ChainId, CHAIN_ID_LENGTH};
-use crate::types::ethereum_events::Uint;
+use crate::types::ethereum_events::{EthereumEvent, Uint};
 use crate::types::ethereum_structs;
 use crate::types::hash::{Error as HashError, Hash};
 use crate::types::internal::{ExpiredTxsQueue, TxQueue};
@@ -57,6 +58,10 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// it has 2 blocks delay on validator set update.
 pub const EPOCH_SWITCH_BLOCKS_DELAY: u32 = 2;
 
+/// The number of recently committed blocks whose merkle roots are kept in
+/// [`Storage::past_merkle_roots`].
+pub const PAST_MERKLE_ROOTS_LEN: u64 = 64;
+
 /// The storage data
 #[derive(Debug)]
 pub struct Storage<D, H>
@@ -113,6 +118,10 @@ where
     pub eth_events_queue: EthEventsQueue,
     /// How many block heights in the past can the storage be queried
     pub storage_read_past_height_limit: Option<u64>,
+    /// The merkle roots of the most recently committed blocks, which spare
+    /// rebuilding their trees from the DB diffs when they are queried. These
+    /// are not persisted.
+    pub past_merkle_roots: BTreeMap<BlockHeight, Hash>,
 }
 
 /// Last committed block
@@ -246,6 +255,17 @@ pub struct BlockStateWrite<'a> {
     pub eth_events_queue: &'a EthEventsQueue,
 }
 
+/// The progress of a validator's Ethereum oracle, persisted such that the
+/// events it sent can be replayed after a restart. The last Ethereum block
+/// processed by the oracle is not part of it, as it is already committed
+/// with the block's state, as [`BlockStateWrite::ethereum_height`].
+#[derive(Debug, Clone, Default, BorshSerialize, BorshDeserialize)]
+pub struct EthOracleProgress {
+    /// The Ethereum events sent by the oracle whose vote extensions have
+    /// not been included on chain yet
+    pub pending_events: Vec<EthereumEvent>,
+}
+
 /// A database backend.
 pub trait DB: std::fmt::Debug {
     /// A DB's cache
@@ -378,6 +398,15 @@ pub trait DB: std::fmt::Debug {
         batch: &mut Self::WriteBatch,
         key: &Key,
     ) -> Result<()>;
+
+    /// Read the last persisted progress of the Ethereum oracle
+    fn read_eth_oracle_progress(&self) -> Result<Option<EthOracleProgress>>;
+
+    /// Persist the progress of the Ethereum oracle, replacing the previous one
+    fn write_eth_oracle_progress(
+        &mut self,
+        progress: &EthOracleProgress,
+    ) -> Result<()>;
 }
 
 /// A database prefix iterator.
@@ -459,6 +488,7 @@ where
             ethereum_height: None,
             eth_events_queue: EthEventsQueue::default(),
             storage_read_past_height_limit,
+            past_merkle_roots: BTreeMap::new(),
         }
     }
 
@@ -571,6 +601,11 @@ where
             time: header.time,
         });
         self.last_epoch = self.block.epoch;
+        self.past_merkle_roots
+            .insert(self.block.height, Hash(self.merkle_root().0));
+        while self.past_merkle_roots.len() as u64 > PAST_MERKLE_ROOTS_LEN {
+            self.past_merkle_roots.pop_first();
+        }
         if is_full_commit {
             // prune old merkle tree stores
             self.prune_merkle_tree_stores(&mut batch)?;
@@ -1159,7 +1194,7 @@ where
         for st in StoreType::iter_non_provable() {
             self.db.prune_merkle_tree_store(
                 batch,
-                st,
+                &st,
                 self.block.epoch.prev(),
             )?;
         }
@@ -1172,7 +1207,7 @@ where
             for st in StoreType::iter_provable() {
                 self.db.prune_merkle_tree_store(
                     batch,
-                    st,
+                    &st,
                     oldest_epoch.prev(),
                 )?;
             }
@@ -1351,6 +1386,7 @@ pub mod testing {
                 ethereum_height: None,
                 eth_events_queue: EthEventsQueue::default(),
                 storage_read_past_height_limit: Some(1000),
+                past_merkle_roots: BTreeMap::new(),
             }
         }
     }
@@ -2,6 +2,7 @@
 //! before they are committed to the ledger's storage.
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 
 use itertools::Itertools;
 use thiserror::Error;
@@ -82,14 +83,37 @@ enum ReProtStorageModification {
     Finalize,
 }
 
+/// The storage keys observed by a transaction, recorded to detect conflicts
+/// with other transactions of the same block executed in parallel
+#[derive(Debug, Default, Clone)]
+pub struct ReadSet {
+    /// Keys read from the write log or, if absent there, from the storage
+    pub keys: HashSet<storage::Key>,
+    /// Prefixes of the keys iterated over
+    pub prefixes: HashSet<storage::Key>,
+}
+
+impl ReadSet {
+    /// Check if a write to the given key could change what was read
+    pub fn is_affected_by(&self, key: &storage::Key) -> bool {
+        self.keys.contains(key)
+            || self
+                .prefixes
+                .iter()
+                .any(|prefix| key.split_prefix(prefix).is_some())
+    }
+}
+
 /// The write log storage
 #[derive(Debug, Clone)]
 pub struct WriteLog {
     /// The generator of established addresses
     address_gen: Option<EstablishedAddressGen>,
     /// All the storage modification accepted by validity predicates are stored
-    /// in block write-log, before being committed to the storage
-    block_write_log: HashMap<storage::Key, StorageModification>,
+    /// in block write-log, before being committed to the storage. It is
+    /// shared by the clones of the write log, and only copied when one of
+    /// them modifies it.
+    block_write_log: Arc<HashMap<storage::Key, StorageModification>>,
     /// The storage modifications for the current transaction
     tx_write_log: HashMap<storage::Key, StorageModification>,
     /// A precommit bucket for the `tx_write_log`. This is useful for
@@ -104,8 +128,12 @@ pub struct WriteLog {
     /// The IBC events for the current transaction
     ibc_events: BTreeSet<IbcEvent>,
     /// Storage modifications for the replay protection storage, always
-    /// committed regardless of the result of the transaction
-    replay_protection: HashMap<Hash, ReProtStorageModification>,
+    /// committed regardless of the result of the transaction. Shared like
+    /// the block write-log.
+    replay_protection: Arc<HashMap<Hash, ReProtStorageModification>>,
+    /// The keys read since [`WriteLog::track_reads`] was called, if any.
+    /// Shared, as validity predicates read the write log concurrently.
+    read_set: Option<Arc<Mutex<ReadSet>>>,
 }
 
 /// Write log prefix iterator
@@ -128,11 +156,12 @@ impl Default for WriteLog {
     fn default() -> Self {
         Self {
             address_gen: None,
-            block_write_log: HashMap::with_capacity(100_000),
+            block_write_log: Arc::new(HashMap::with_capacity(100_000)),
             tx_write_log: HashMap::with_capacity(100),
             tx_precommit_write_log: HashMap::with_capacity(100),
             ibc_events: BTreeSet::new(),
-            replay_protection: HashMap::with_capacity(1_000),
+            replay_protection: Arc::new(HashMap::with_capacity(1_000)),
+            read_set: None,
         }
     }
 }
@@ -144,6 +173,7 @@ impl WriteLog {
         &self,
         key: &storage::Key,
     ) -> (Option<&StorageModification>, u64) {
+        self.record_read(key);
         // try to read from tx write log first
         match self
             .tx_write_log
@@ -182,6 +212,7 @@ impl WriteLog {
         &self,
         key: &storage::Key,
     ) -> (Option<&StorageModification>, u64) {
+        self.record_read(key);
         // try to read from tx write log first
         match self.block_write_log.get(key) {
             Some(v) => {
@@ -248,8 +279,7 @@ impl WriteLog {
         key: &storage::Key,
         value: Vec<u8>,
     ) -> Result<()> {
-        if let Some(prev) = self
-            .block_write_log
+        if let Some(prev) = Arc::make_mut(&mut self.block_write_log)
             .insert(key.clone(), StorageModification::Write { value })
         {
             match prev {
@@ -340,8 +370,7 @@ impl WriteLog {
         if key.is_validity_predicate().is_some() {
             return Err(Error::DeleteVp);
         }
-        if let Some(prev) = self
-            .block_write_log
+        if let Some(prev) = Arc::make_mut(&mut self.block_write_log)
             .insert(key.clone(), StorageModification::Delete)
         {
             match prev {
@@ -477,7 +506,10 @@ impl WriteLog {
             HashMap::with_capacity(100),
         );
 
-        self.block_write_log.extend(tx_precommit_write_log);
+        if !tx_precommit_write_log.is_empty() {
+            Arc::make_mut(&mut self.block_write_log)
+                .extend(tx_precommit_write_log);
+        }
         self.take_ibc_events();
     }
 
@@ -570,8 +602,8 @@ impl WriteLog {
         if let Some(address_gen) = self.address_gen.take() {
             storage.address_gen = address_gen
         }
-        self.block_write_log.clear();
-        self.replay_protection.clear();
+        Arc::make_mut(&mut self.block_write_log).clear();
+        Arc::make_mut(&mut self.replay_protection).clear();
         Ok(())
     }
 
@@ -634,9 +666,10 @@ impl WriteLog {
     /// Iterate modifications prior to the current transaction, whose storage
     /// key matches the given prefix, sorted by their storage key.
     pub fn iter_prefix_pre(&self, prefix: &storage::Key) -> PrefixIter {
+        self.record_prefix(prefix);
         let mut matches = BTreeMap::new();
 
-        for (key, modification) in &self.block_write_log {
+        for (key, modification) in self.block_write_log.iter() {
             if key.split_prefix(prefix).is_some() {
                 matches.insert(key.to_string(), modification.clone());
             }
@@ -649,9 +682,10 @@ impl WriteLog {
     /// Iterate modifications posterior of the current tx, whose storage key
     /// matches the given prefix, sorted by their storage key.
     pub fn iter_prefix_post(&self, prefix: &storage::Key) -> PrefixIter {
+        self.record_prefix(prefix);
         let mut matches = BTreeMap::new();
 
-        for (key, modification) in &self.block_write_log {
+        for (key, modification) in self.block_write_log.iter() {
             if key.split_prefix(prefix).is_some() {
                 matches.insert(key.to_string(), modification.clone());
             }
@@ -666,6 +700,81 @@ impl WriteLog {
         PrefixIter { iter }
     }
 
+    /// Start recording the keys read from the write log, which also covers
+    /// the reads that fall through to the storage. Any previously recorded
+    /// keys are discarded.
+    pub fn track_reads(&mut self) {
+        self.read_set = Some(Default::default());
+    }
+
+    /// Stop recording reads and return the keys read since
+    /// [`WriteLog::track_reads`] was called, if it was.
+    pub fn take_read_set(&mut self) -> Option<ReadSet> {
+        self.read_set.take().map(|read_set| {
+            std::mem::take(
+                &mut *read_set.lock().expect("The read set lock is poisoned"),
+            )
+        })
+    }
+
+    fn record_read(&self, key: &storage::Key) {
+        if let Some(read_set) = &self.read_set {
+            read_set
+                .lock()
+                .expect("The read set lock is poisoned")
+                .keys
+                .insert(key.clone());
+        }
+    }
+
+    fn record_prefix(&self, prefix: &storage::Key) {
+        if let Some(read_set) = &self.read_set {
+            read_set
+                .lock()
+                .expect("The read set lock is poisoned")
+                .prefixes
+                .insert(prefix.clone());
+        }
+    }
+
+    /// Get the generator of established addresses, if any address was
+    /// generated in the current block
+    pub fn address_gen(&self) -> Option<&EstablishedAddressGen> {
+        self.address_gen.as_ref()
+    }
+
+    /// Stop sharing the state of the block with the clones of this write log,
+    /// keeping only the state of the current transaction, such that they
+    /// can keep modifying the block state without copying it.
+    pub fn release_block_state(&mut self) {
+        self.block_write_log = Default::default();
+        self.replay_protection = Default::default();
+    }
+
+    /// Replace the state of the current transaction with the one of the given
+    /// write log, in which the transaction was applied on top of the same
+    /// block write log as this one. Used to adopt the result of a speculative
+    /// execution.
+    ///
+    /// The generator of established addresses is only replaced if the
+    /// transaction generated addresses, as the one of the other write log
+    /// may be outdated otherwise.
+    pub fn adopt_tx(&mut self, other: WriteLog, generated_addresses: bool) {
+        let WriteLog {
+            address_gen,
+            tx_write_log,
+            tx_precommit_write_log,
+            ibc_events,
+            ..
+        } = other;
+        if generated_addresses {
+            self.address_gen = address_gen;
+        }
+        self.tx_write_log = tx_write_log;
+        self.tx_precommit_write_log = tx_precommit_write_log;
+        self.ibc_events = ibc_events;
+    }
+
     /// Check if the given tx hash has already been processed. Returns `None` if
     /// the key is not known.
     pub fn has_replay_protection_entry(&self, hash: &Hash) -> Option<bool> {
@@ -676,8 +785,7 @@ impl WriteLog {
 
     /// Write the transaction hash
     pub(crate) fn write_tx_hash(&mut self, hash: Hash) -> Result<()> {
-        if self
-            .replay_protection
+        if Arc::make_mut(&mut self.replay_protection)
             .insert(hash, ReProtStorageModification::Write)
             .is_some()
         {
@@ -693,8 +801,7 @@ impl WriteLog {
 
     /// Remove the transaction hash
     pub(crate) fn delete_tx_hash(&mut self, hash: Hash) -> Result<()> {
-        match self
-            .replay_protection
+        match Arc::make_mut(&mut self.replay_protection)
             .insert(hash, ReProtStorageModification::Delete)
         {
             None => Ok(()),
@@ -716,8 +823,7 @@ impl WriteLog {
     /// blocks. This functions should be called at the beginning of the block
     /// processing, before any other replay protection operation is done
     pub fn finalize_tx_hash(&mut self, hash: Hash) -> Result<()> {
-        if self
-            .replay_protection
+        if Arc::make_mut(&mut self.replay_protection)
             .insert(hash, ReProtStorageModification::Finalize)
             .is_some()
         {
@@ -959,6 +1065,69 @@ mod tests {
         assert_eq!(value, None);
     }
 
+    #[test]
+    fn test_read_tracking() {
+        let mut write_log = WriteLog::default();
+        let key1 =
+            storage::Key::parse("key1").expect("cannot parse the key string");
+        let key2 =
+            storage::Key::parse("key2").expect("cannot parse the key string");
+        let prefix =
+            storage::Key::parse("prefix").expect("cannot parse the key string");
+        let sub_key = prefix
+            .push(&"sub".to_string())
+            .expect("cannot push the key segment");
+
+        // reads are not recorded unless requested
+        let _ = write_log.read(&key1);
+        assert!(write_log.take_read_set().is_none());
+
+        write_log.track_reads();
+        let _ = write_log.read(&key1);
+        let _ = write_log.iter_prefix_post(&prefix);
+        // writes are not reads
+        write_log.write(&key2, vec![1]).unwrap();
+        let read_set = write_log.take_read_set().expect("reads are tracked");
+
+        assert_eq!(read_set.keys, HashSet::from([key1.clone()]));
+        assert!(read_set.is_affected_by(&key1));
+        assert!(read_set.is_affected_by(&sub_key));
+        assert!(!read_set.is_affected_by(&key2));
+
+        // tracking stops once the read set is taken
+        let _ = write_log.read(&key2);
+        assert!(write_log.take_read_set().is_none());
+    }
+
+    #[test]
+    fn test_shared_block_state() {
+        let mut write_log = WriteLog::default();
+        let key1 =
+            storage::Key::parse("key1").expect("cannot parse the key string");
+        let key2 =
+            storage::Key::parse("key2").expect("cannot parse the key string");
+        write_log.write(&key1, vec![1]).unwrap();
+        write_log.commit_tx();
+
+        // a view sees the block state, but not the later changes to it
+        let mut view = write_log.clone();
+        write_log.write(&key2, vec![2]).unwrap();
+        write_log.commit_tx();
+        assert!(view.read(&key1).0.is_some());
+        assert!(view.read(&key2).0.is_none());
+
+        // the changes of the view's tx are kept once it is released
+        view.write(&key2, vec![3]).unwrap();
+        view.release_block_state();
+        assert!(view.read(&key1).0.is_none());
+        write_log.adopt_tx(view, false);
+        assert_matches!(
+            write_log.read(&key2).0,
+            Some(StorageModification::Write { value }) if value == &[3]
+        );
+        assert!(write_log.read(&key1).0.is_some());
+    }
+
     #[test]
     fn test_replay_protection_commit() {
         let mut storage =
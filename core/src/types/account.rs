@@ -12,6 +12,7 @@ use crate::hints;
 #[derive(
     Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 /// Account data
 pub struct Account {
     /// The map between indexes and public keys for an account
@@ -49,6 +50,7 @@ impl Account {
     Deserialize,
     Default,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 /// Holds the public key map data as a bimap for efficient quering
 pub struct AccountPublicKeysMap {
     /// Hashmap from public key to index
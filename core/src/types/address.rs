@@ -323,6 +323,9 @@ impl string_encoding::Format for Address {
 
 impl_display_and_from_str_via_format!(Address);
 
+#[cfg(feature = "schemars")]
+crate::types::string_encoding::impl_json_schema_via_string!(Address);
+
 impl serde::Serialize for Address {
     fn serialize<S>(
         &self,
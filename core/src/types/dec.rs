@@ -51,6 +51,9 @@ pub type Result<T> = std::result::Result<T, Error>;
 #[serde(into = "String")]
 pub struct Dec(pub I256);
 
+#[cfg(feature = "schemars")]
+crate::types::string_encoding::impl_json_schema_via_string!(Dec);
+
 impl Dec {
     /// Division with truncation (TODO: better description)
     pub fn trunc_div(&self, rhs: &Self) -> Option<Self> {
@@ -12,7 +12,7 @@ use crate::ledger::eth_bridge::storage::wrapped_erc20s;
 use crate::types::address::Address;
 use crate::types::eth_abi::Encode;
 use crate::types::ethereum_events::{
-    EthAddress, TransferToEthereum as TransferToEthereumEvent,
+    EthAddress, TransferToEthereum as TransferToEthereumEvent, Uint,
 };
 use crate::types::hash::Hash as HashDigest;
 use crate::types::storage::{DbKeySeg, Key};
@@ -40,6 +40,7 @@ const NAMESPACE: &str = "transfer";
     Serialize,
     Deserialize,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum TransferToEthereumKind {
     /// Transfer ERC20 assets from Namada to Ethereum.
     ///
@@ -127,6 +128,7 @@ impl<'transfer> PendingTransferAppendix<'transfer> {
     BorshDeserialize,
     BorshSchema,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TransferToEthereum {
     /// The kind of transfer to Ethereum.
     pub kind: TransferToEthereumKind,
@@ -156,6 +158,7 @@ pub struct TransferToEthereum {
     BorshDeserialize,
     BorshSchema,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PendingTransfer {
     /// Transfer to Ethereum data.
     pub transfer: TransferToEthereum,
@@ -283,6 +286,7 @@ impl From<&PendingTransfer> for Key {
     BorshDeserialize,
     BorshSchema,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GasFee {
     /// The amount of fees.
     pub amount: Amount,
@@ -293,6 +297,67 @@ pub struct GasFee {
     pub token: Address,
 }
 
+/// A request to replace a [`PendingTransfer`] sitting in the
+/// bridge pool with an identical transfer paying a higher gas fee,
+/// to increase its odds of being relayed.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct GasFeeBump {
+    /// The transfer to be replaced.
+    pub transfer: PendingTransfer,
+    /// The new amount of gas fees to pay for the transfer.
+    pub new_fee: Amount,
+}
+
+impl GasFeeBump {
+    /// Return the [`PendingTransfer`] that replaces the bumped transfer.
+    pub fn bumped_transfer(&self) -> PendingTransfer {
+        let mut transfer = self.transfer.clone();
+        transfer.gas_fee.amount = self.new_fee;
+        transfer
+    }
+
+    /// Return the additional amount of gas fees to escrow, if the
+    /// new fee is higher than the fee of the bumped transfer.
+    pub fn fee_increase(&self) -> Option<Amount> {
+        (self.new_fee > self.transfer.gas_fee.amount)
+            .then(|| self.new_fee - self.transfer.gas_fee.amount)
+    }
+}
+
+/// A [`PendingTransfer`] that was removed from the bridge pool
+/// after expiring, and is awaiting a refund.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct ExpiredTransfer {
+    /// The expired transfer.
+    pub transfer: PendingTransfer,
+    /// The bridge pool nonce at the time the transfer expired.
+    ///
+    /// Bridge pool roots signed with this nonce may still contain
+    /// the transfer, so it can only be refunded once the nonce
+    /// has been incremented.
+    pub nonce: Uint,
+}
+
 #[cfg(test)]
 mod test_eth_bridge_pool_types {
     use super::*;
@@ -320,4 +385,39 @@ mod test_eth_bridge_pool_types {
         let event: TransferToEthereumEvent = (&pending).into();
         assert_eq!(pending.keccak256(), event.keccak256());
     }
+
+    /// Test that bumping the gas fees of a [`PendingTransfer`] only
+    /// changes its fee amount, and that fees can only be increased.
+    #[test]
+    fn test_gas_fee_bump() {
+        let transfer = PendingTransfer {
+            transfer: TransferToEthereum {
+                kind: TransferToEthereumKind::Erc20,
+                amount: 10u64.into(),
+                asset: EthAddress([0xaa; 20]),
+                recipient: EthAddress([0xbb; 20]),
+                sender: established_address_1(),
+            },
+            gas_fee: GasFee {
+                token: nam(),
+                amount: 10u64.into(),
+                payer: established_address_1(),
+            },
+        };
+        let bump = GasFeeBump {
+            transfer: transfer.clone(),
+            new_fee: 25u64.into(),
+        };
+        let bumped = bump.bumped_transfer();
+        assert_eq!(bumped.transfer, transfer.transfer);
+        assert_eq!(bumped.gas_fee.amount, 25u64.into());
+        assert_ne!(bumped.keccak256(), transfer.keccak256());
+        assert_eq!(bump.fee_increase(), Some(15u64.into()));
+
+        let bump = GasFeeBump {
+            transfer,
+            new_fee: 10u64.into(),
+        };
+        assert_eq!(bump.fee_increase(), None);
+    }
 }
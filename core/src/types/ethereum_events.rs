@@ -143,6 +143,9 @@ impl Sub<u64> for Uint {
 #[serde(into = "String")]
 pub struct EthAddress(pub [u8; 20]);
 
+#[cfg(feature = "schemars")]
+crate::types::string_encoding::impl_json_schema_via_string!(EthAddress);
+
 impl EthAddress {
     /// The canonical way we represent an [`EthAddress`] in storage keys. A
     /// 40-character lower case hexadecimal address prefixed by '0x'.
@@ -253,6 +256,42 @@ impl From<TransfersToNamada> for EthereumEvent {
     }
 }
 
+/// A batch of arbitrary messages emitted by Ethereum contracts,
+/// addressed to accounts on Namada
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Hash,
+    Ord,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct ContractMessages {
+    /// Monotonically increasing nonce
+    pub nonce: Uint,
+    /// The batch of messages
+    pub messages: Vec<ContractMessage>,
+}
+
+impl GetEventNonce for ContractMessages {
+    #[inline]
+    fn get_event_nonce(&self) -> Uint {
+        self.nonce
+    }
+}
+
+impl From<ContractMessages> for EthereumEvent {
+    #[inline]
+    fn from(event: ContractMessages) -> Self {
+        let ContractMessages { nonce, messages } = event;
+        Self::ContractMessages { nonce, messages }
+    }
+}
+
 /// An Ethereum event to be processed by the Namada ledger
 #[derive(
     PartialEq,
@@ -304,6 +343,16 @@ pub enum EthereumEvent {
         #[allow(dead_code)]
         governance_validator_hash: KeccakHash,
     },
+    /// Event carrying batches of arbitrary messages from
+    /// Ethereum contracts to accounts on Namada
+    ContractMessages {
+        /// Monotonically increasing nonce
+        #[allow(dead_code)]
+        nonce: Uint,
+        /// The batch of messages
+        #[allow(dead_code)]
+        messages: Vec<ContractMessage>,
+    },
 }
 
 impl EthereumEvent {
@@ -336,6 +385,29 @@ pub struct TransferToNamada {
     pub receiver: Address,
 }
 
+/// An arbitrary message emitted by an Ethereum contract, addressed
+/// to an account on Namada
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Hash,
+    Ord,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct ContractMessage {
+    /// Address of the smart contract that emitted the message
+    pub source: EthAddress,
+    /// The address receiving the message on Namada
+    pub target: Address,
+    /// The payload of the message, which is opaque to the protocol
+    pub payload: Vec<u8>,
+}
+
 /// An event transferring some kind of value from Namada to Ethereum
 #[derive(
     Clone,
@@ -7,7 +7,7 @@ use std::fmt::Display;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use data_encoding::HEXUPPER;
 use ethabi::Token;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
 use thiserror::Error;
 pub use tiny_keccak::{Hasher, Keccak};
 
@@ -39,7 +39,9 @@ pub enum TryFromError {
     BorshSerialize,
     BorshDeserialize,
     BorshSchema,
+    Deserialize,
 )]
+#[serde(try_from = "String")]
 pub struct KeccakHash(pub [u8; 32]);
 
 impl KeccakHash {
@@ -109,6 +111,9 @@ impl AsRef<[u8]> for KeccakHash {
     }
 }
 
+#[cfg(feature = "schemars")]
+crate::types::string_encoding::impl_json_schema_via_string!(KeccakHash);
+
 impl Serialize for KeccakHash {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -45,6 +45,9 @@ pub enum PublicKey {
 const ED25519_PK_PREFIX: &str = "ED25519_PK_PREFIX";
 const SECP256K1_PK_PREFIX: &str = "SECP256K1_PK_PREFIX";
 
+#[cfg(feature = "schemars")]
+crate::types::string_encoding::impl_json_schema_via_string!(PublicKey);
+
 impl Serialize for PublicKey {
     fn serialize<S>(
         &self,
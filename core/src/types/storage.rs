@@ -21,7 +21,9 @@ use crate::bytes::ByteBuf;
 use crate::hints;
 use crate::ledger::eth_bridge::storage::bridge_pool::BridgePoolProof;
 use crate::types::address::{self, Address};
-use crate::types::ethereum_events::{GetEventNonce, TransfersToNamada, Uint};
+use crate::types::ethereum_events::{
+    ContractMessages, GetEventNonce, TransfersToNamada, Uint,
+};
 use crate::types::hash::Hash;
 use crate::types::keccak::{KeccakHash, TryFromError};
 use crate::types::time::DateTimeUtc;
@@ -1027,6 +1029,7 @@ impl KeySeg for common::PublicKey {
     Serialize,
     Deserialize,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Epoch(pub u64);
 
 impl Display for Epoch {
@@ -1276,10 +1279,36 @@ pub struct PrefixValue {
 }
 
 /// Container of all Ethereum event queues.
-#[derive(Default, Debug, BorshSerialize, BorshDeserialize)]
+///
+/// The queue of contract messages was added after the queue of transfers
+/// to Namada, so queues persisted before it existed are decoded with an
+/// empty queue of contract messages. For this reason, an
+/// [`EthEventsQueue`] must always be decoded on its own, rather than as
+/// part of some larger value.
+#[derive(Default, Debug, BorshSerialize)]
 pub struct EthEventsQueue {
     /// Queue of transfer to Namada events.
     pub transfers_to_namada: InnerEthEventsQueue<TransfersToNamada>,
+    /// Queue of contract message events.
+    pub contract_messages: InnerEthEventsQueue<ContractMessages>,
+}
+
+impl BorshDeserialize for EthEventsQueue {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let transfers_to_namada = BorshDeserialize::deserialize_reader(reader)?;
+        let mut contract_messages = vec![];
+        reader.read_to_end(&mut contract_messages)?;
+        let contract_messages = if contract_messages.is_empty() {
+            // persisted before contract messages were queued
+            Default::default()
+        } else {
+            BorshDeserialize::try_from_slice(&contract_messages)?
+        };
+        Ok(Self {
+            transfers_to_namada,
+            contract_messages,
+        })
+    }
 }
 
 /// A queue of confirmed Ethereum events of type `E`.
@@ -1595,6 +1624,40 @@ mod tests {
         assert!(queue.transfers_to_namada.pop_event().is_none());
     }
 
+    /// Test that an [`EthEventsQueue`] persisted before contract messages
+    /// were queued is decoded with an empty queue of contract messages,
+    /// and that the current encoding round-trips.
+    #[test]
+    fn test_eth_events_queue_encoding_migration() {
+        let mut legacy =
+            InnerEthEventsQueue::<TransfersToNamada>::new_at(2u64.into());
+        legacy.inner.push_back(TransfersToNamada {
+            transfers: vec![],
+            nonce: 3u64.into(),
+        });
+        let queue = EthEventsQueue::try_from_slice(&legacy.serialize_to_vec())
+            .expect("Test failed");
+        assert_eq!(queue.transfers_to_namada.get_event_nonce(), 2u64.into());
+        assert_eq!(queue.transfers_to_namada.inner, legacy.inner);
+        assert_eq!(queue.contract_messages.get_event_nonce(), 0u64.into());
+        assert!(queue.contract_messages.inner.is_empty());
+
+        let mut queue = queue;
+        queue.contract_messages.next_nonce_to_process = 5u64.into();
+        queue.contract_messages.inner.push_back(ContractMessages {
+            nonce: 6u64.into(),
+            messages: vec![],
+        });
+        let decoded = EthEventsQueue::try_from_slice(&queue.serialize_to_vec())
+            .expect("Test failed");
+        assert_eq!(decoded.transfers_to_namada.inner, legacy.inner);
+        assert_eq!(decoded.contract_messages.get_event_nonce(), 5u64.into());
+        assert_eq!(
+            decoded.contract_messages.inner,
+            queue.contract_messages.inner
+        );
+    }
+
     #[test]
     fn test_key_parse_valid() {
         let addr = address::testing::established_address_1();
@@ -130,6 +130,28 @@ macro_rules! impl_display_and_from_str_via_format {
     };
 }
 
+/// Implement [`schemars::JsonSchema`] for a type that is serialized as a
+/// string.
+#[cfg(feature = "schemars")]
+macro_rules! impl_json_schema_via_string {
+    ($t:ident) => {
+        impl schemars::JsonSchema for $t {
+            fn schema_name() -> std::string::String {
+                stringify!($t).to_string()
+            }
+
+            fn json_schema(
+                gen: &mut schemars::gen::SchemaGenerator,
+            ) -> schemars::schema::Schema {
+                <std::string::String as schemars::JsonSchema>::json_schema(gen)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "schemars")]
+pub(crate) use impl_json_schema_via_string;
+
 /// Get the length of the human-readable part
 // Not in the `Format` trait, cause functions in traits cannot be const
 pub const fn hrp_len<T: Format>() -> usize {
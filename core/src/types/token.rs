@@ -291,6 +291,7 @@ impl Amount {
     Serialize,
     Deserialize,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(transparent)]
 pub struct Denomination(pub u8);
 
@@ -510,6 +511,9 @@ impl Ord for DenominatedAmount {
     }
 }
 
+#[cfg(feature = "schemars")]
+crate::types::string_encoding::impl_json_schema_via_string!(Amount);
+
 impl serde::Serialize for Amount {
     fn serialize<S>(
         &self,
@@ -153,20 +153,31 @@ impl TryFrom<&Tx> for EthereumTxData {
 }
 
 impl EthereumTxData {
-    /// Sign transaction Ethereum data and wrap it in a [`Tx`].
-    pub fn sign(
+    /// Wrap transaction Ethereum data in a [`Tx`], to be signed with the
+    /// given protocol key.
+    pub fn unsigned_tx(
         &self,
-        signing_key: &common::SecretKey,
+        protocol_key: common::PublicKey,
         chain_id: ChainId,
     ) -> Tx {
         let (tx_data, tx_type) = self.serialize();
         let mut outer_tx =
             Tx::from_type(TxType::Protocol(Box::new(ProtocolTx {
-                pk: signing_key.ref_to(),
+                pk: protocol_key,
                 tx: tx_type,
             })));
         outer_tx.header.chain_id = chain_id;
         outer_tx.set_data(Data::new(tx_data));
+        outer_tx
+    }
+
+    /// Sign transaction Ethereum data and wrap it in a [`Tx`].
+    pub fn sign(
+        &self,
+        signing_key: &common::SecretKey,
+        chain_id: ChainId,
+    ) -> Tx {
+        let mut outer_tx = self.unsigned_tx(signing_key.ref_to(), chain_id);
         outer_tx.add_section(Section::Signature(Signature::new(
             outer_tx.sechashes(),
             [(0, signing_key.clone())].into_iter().collect(),
@@ -9,6 +9,9 @@ use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 
 use crate::proto::Signed;
 use crate::types::address::Address;
+use crate::types::ethereum_events::Uint;
+use crate::types::hash::Hash;
+use crate::types::keccak::{keccak_hash, KeccakHash};
 use crate::types::key::common;
 use crate::types::key::common::Signature;
 use crate::types::storage::BlockHeight;
@@ -44,6 +47,25 @@ pub struct BridgePoolRootVext {
     /// The actual signature being submitted.
     /// This is a signature over `keccak(eth_header || keccak(root || nonce))`.
     pub sig: Signature,
+    /// A signature over the root of Namada's merkle tree at
+    /// `block_height`, computed over the message returned by
+    /// [`state_root_msg`].
+    pub state_root_sig: Signature,
+}
+
+/// Domain separation tag of the messages signed over Namada's
+/// state root, which keeps them apart from the signatures over
+/// the Ethereum bridge pool root.
+pub const STATE_ROOT_TAG: &[u8] = b"namada-state-root";
+
+/// Compute the message signed by validators over the root of
+/// Namada's merkle tree at the given block height.
+///
+/// This is `keccak(STATE_ROOT_TAG || root || height)`, where
+/// the height is encoded as a big-endian 256-bit integer.
+pub fn state_root_msg(root: &Hash, height: BlockHeight) -> KeccakHash {
+    let height = Uint::from(height.0).to_bytes();
+    keccak_hash([STATE_ROOT_TAG, root.0.as_slice(), height.as_slice()].concat())
 }
 
 /// Alias for [`BridgePoolRootVext`].
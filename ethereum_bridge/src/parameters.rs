@@ -3,7 +3,9 @@ use std::num::NonZeroU64;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use eyre::{eyre, Result};
-use namada_core::ledger::eth_bridge::storage::whitelist;
+use namada_core::ledger::eth_bridge::storage::{
+    bridge_pool, flow_control, whitelist,
+};
 use namada_core::ledger::storage;
 use namada_core::ledger::storage::types::encode;
 use namada_core::ledger::storage::WlStorage;
@@ -36,6 +38,14 @@ pub struct Erc20WhitelistEntry {
     pub token_address: EthAddress,
     /// The token cap of the whitelisted ERC20 token.
     pub token_cap: DenominatedAmount,
+    /// The maximum amount of the token that may be transferred to
+    /// Namada in a single epoch. If unset, inflows are not limited.
+    #[serde(default)]
+    pub inflow_limit: Option<DenominatedAmount>,
+    /// The maximum amount of the token that may be transferred to
+    /// Ethereum in a single epoch. If unset, outflows are not limited.
+    #[serde(default)]
+    pub outflow_limit: Option<DenominatedAmount>,
 }
 
 /// Represents a configuration value for the minimum number of
@@ -87,6 +97,7 @@ impl From<MinimumConfirmations> for NonZeroU64 {
     BorshSerialize,
     BorshDeserialize,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(transparent)]
 pub struct ContractVersion(NonZeroU64);
 
@@ -111,6 +122,7 @@ impl Default for ContractVersion {
     BorshSerialize,
     BorshDeserialize,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct UpgradeableContract {
     /// The Ethereum address of the contract.
     pub address: EthAddress,
@@ -153,6 +165,16 @@ pub struct Contracts {
 pub struct EthereumBridgeParams {
     /// Initial Ethereum block height when events will first be extracted from.
     pub eth_start_height: ethereum_structs::BlockHeight,
+    /// Number of epochs after which transfers sitting in the Bridge
+    /// pool expire. If unset, transfers never expire.
+    ///
+    /// Expired transfers are only refunded once the Bridge pool nonce
+    /// is incremented, as they may still be relayed until then. Since
+    /// the nonce only increases when a batch of transfers is relayed to
+    /// Ethereum, expired transfers are not refunded while the bridge
+    /// is idle.
+    #[serde(default)]
+    pub bridge_pool_expiry: Option<NonZeroU64>,
     /// Minimum number of confirmations needed to trust an Ethereum branch.
     /// This must be at least one.
     pub min_confirmations: MinimumConfirmations,
@@ -176,6 +198,7 @@ impl EthereumBridgeParams {
         let Self {
             erc20_whitelist,
             eth_start_height,
+            bridge_pool_expiry,
             min_confirmations,
             contracts:
                 Contracts {
@@ -209,6 +232,8 @@ impl EthereumBridgeParams {
         for Erc20WhitelistEntry {
             token_address: addr,
             token_cap: DenominatedAmount { amount: cap, denom },
+            inflow_limit,
+            outflow_limit,
         } in erc20_whitelist
         {
             if addr == native_erc20
@@ -240,6 +265,29 @@ impl EthereumBridgeParams {
             }
             .into();
             wl_storage.write_bytes(&key, encode(denom)).unwrap();
+
+            for (direction, limit) in [
+                (flow_control::Direction::Inbound, inflow_limit),
+                (flow_control::Direction::Outbound, outflow_limit),
+            ] {
+                if let Some(limit) = limit {
+                    flow_control::write_limit(
+                        wl_storage,
+                        addr,
+                        direction,
+                        Some(limit.amount),
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        if let Some(expiry) = bridge_pool_expiry {
+            wl_storage
+                .write_bytes(
+                    &bridge_pool::get_transfer_expiry_key(),
+                    encode(&expiry.get()),
+                )
+                .unwrap();
         }
         // Initialize the storage for the Ethereum Bridge VP.
         vp::init_storage(wl_storage);
@@ -386,6 +434,7 @@ mod tests {
         let config = EthereumBridgeParams {
             erc20_whitelist: vec![],
             eth_start_height: Default::default(),
+            bridge_pool_expiry: None,
             min_confirmations: MinimumConfirmations::default(),
             contracts: Contracts {
                 native_erc20: EthAddress([42; 20]),
@@ -408,6 +457,7 @@ mod tests {
         let config = EthereumBridgeParams {
             erc20_whitelist: vec![],
             eth_start_height: Default::default(),
+            bridge_pool_expiry: None,
             min_confirmations: MinimumConfirmations::default(),
             contracts: Contracts {
                 native_erc20: EthAddress([42; 20]),
@@ -440,6 +490,7 @@ mod tests {
         let config = EthereumBridgeParams {
             erc20_whitelist: vec![],
             eth_start_height: Default::default(),
+            bridge_pool_expiry: None,
             min_confirmations: MinimumConfirmations::default(),
             contracts: Contracts {
                 native_erc20: EthAddress([42; 20]),
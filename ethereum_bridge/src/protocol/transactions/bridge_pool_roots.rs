@@ -1,9 +1,10 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
-use eyre::Result;
+use eyre::{eyre, Result};
 use namada_core::ledger::eth_bridge::storage::bridge_pool::get_signed_root_key;
+use namada_core::ledger::eth_bridge::storage::state_root;
 use namada_core::ledger::storage::{DBIter, StorageHasher, WlStorage, DB};
-use namada_core::ledger::storage_api::{StorageRead, StorageWrite};
+use namada_core::ledger::storage_api::{self, StorageRead, StorageWrite};
 use namada_core::types::address::Address;
 use namada_core::types::storage::BlockHeight;
 use namada_core::types::token::Amount;
@@ -16,7 +17,7 @@ use crate::protocol::transactions::votes::update::NewVotes;
 use crate::protocol::transactions::votes::{calculate_new, Votes};
 use crate::protocol::transactions::{utils, votes, ChangedKeys};
 use crate::storage::eth_bridge_queries::EthBridgeQueries;
-use crate::storage::proof::BridgePoolRootProof;
+use crate::storage::proof::{BridgePoolRootProof, StateRootProof};
 use crate::storage::vote_tallies::{self, BridgePoolRoot};
 /// Applies a tally of signatures on over the Ethereum
 /// bridge pool root and nonce. Note that every signature
@@ -44,7 +45,8 @@ where
     );
     let voting_powers = utils::get_voting_powers(wl_storage, &vext)?;
     let root_height = vext.iter().next().unwrap().data.block_height;
-    let (partial_proof, seen_by) = parse_vexts(wl_storage, vext);
+    let (partial_proof, state_root_proof, seen_by) =
+        parse_vexts(wl_storage, vext)?;
 
     // return immediately if a complete proof has already been acquired
     let bp_key = vote_tallies::Keys::from((&partial_proof, root_height));
@@ -68,6 +70,15 @@ where
         &voting_powers,
     )?;
 
+    // the signatures over the state root are confirmed along
+    // with the bridge pool root of the same height
+    changed.extend(apply_state_root_sigs(
+        wl_storage,
+        root_height,
+        state_root_proof,
+        confirmed_update.is_some(),
+    )?);
+
     // if the root is confirmed, update storage and add
     // relevant key to changed.
     if let Some(proof) = confirmed_update {
@@ -125,11 +136,15 @@ impl GetVoters for &MultiSignedVext {
 }
 
 /// Convert a set of signatures over bridge pool roots and nonces (at a certain
-/// height) into a partial proof and a new set of votes.
+/// height) into a partial proof and a new set of votes. The signatures over
+/// the state root at the same height are returned as another partial proof.
+///
+/// An error is returned if the merkle tree at the given height has been
+/// pruned from storage, in which case the signatures are dropped.
 fn parse_vexts<D, H>(
     wl_storage: &WlStorage<D, H>,
     multisigned: MultiSignedVext,
-) -> (BridgePoolRoot, Votes)
+) -> Result<(BridgePoolRoot, StateRootProof, Votes)>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
@@ -139,28 +154,108 @@ where
     let root = wl_storage
         .ethbridge_queries()
         .get_bridge_pool_root_at_height(height)
-        .expect("A BP root should be available at the given height");
+        .ok_or_else(|| {
+            eyre!("The Bridge pool root at height {height} is not available")
+        })?;
     let nonce = wl_storage
         .ethbridge_queries()
         .get_bridge_pool_nonce_at_height(height);
+    let state_root = wl_storage
+        .ethbridge_queries()
+        .get_state_root_at_height(height)
+        .ok_or_else(|| {
+            eyre!("The state root at height {height} is not available")
+        })?;
     let mut partial_proof = BridgePoolRootProof::new((root, nonce));
-    partial_proof.attach_signature_batch(multisigned.clone().into_iter().map(
-        |signed| {
-            (
-                wl_storage
-                    .ethbridge_queries()
-                    .get_eth_addr_book(&signed.data.validator_addr, epoch)
-                    .unwrap(),
-                signed.data.sig,
-            )
-        },
-    ));
+    let mut state_root_proof = StateRootProof::new((state_root, height));
+    for signed in multisigned.iter() {
+        let addr_book = wl_storage
+            .ethbridge_queries()
+            .get_eth_addr_book(&signed.data.validator_addr, epoch)
+            .unwrap();
+        partial_proof
+            .attach_signature(addr_book.clone(), signed.data.sig.clone());
+        state_root_proof
+            .attach_signature(addr_book, signed.data.state_root_sig.clone());
+    }
 
     let seen_by: Votes = multisigned
         .into_iter()
         .map(|signed| (signed.data.validator_addr, signed.data.block_height))
         .collect();
-    (BridgePoolRoot(partial_proof), seen_by)
+    Ok((BridgePoolRoot(partial_proof), state_root_proof, seen_by))
+}
+
+/// Accumulate the signatures over the state root at `root_height`.
+///
+/// Once the bridge pool root of the same height is confirmed, the
+/// state root has been signed by a quorum of validators as well,
+/// since every valid vote extension carries both signatures. It
+/// then replaces the previously signed state root in storage, if
+/// it is more recent. The signatures collected over this and older
+/// state roots, some of which never reached a quorum, are pruned.
+fn apply_state_root_sigs<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    root_height: BlockHeight,
+    mut update: StateRootProof,
+    confirmed: bool,
+) -> Result<ChangedKeys>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let is_outdated = wl_storage
+        .ethbridge_queries()
+        .get_signed_state_root()
+        .map(|existing| existing.data.1 >= root_height)
+        .unwrap_or(false);
+    if !confirmed && is_outdated {
+        // these signatures can no longer replace the signed root
+        return Ok(BTreeSet::new());
+    }
+    let sigs_key = state_root::sigs_key(root_height);
+    if let Some(partial) = wl_storage.read::<StateRootProof>(&sigs_key)? {
+        update.attach_signature_batch(partial.signatures);
+    }
+    if !confirmed {
+        wl_storage.write(&sigs_key, update)?;
+        return Ok(BTreeSet::from([sigs_key]));
+    }
+    let mut changed = prune_state_root_sigs(wl_storage, root_height)?;
+    if !is_outdated {
+        let signed_root_key = state_root::signed_root_key();
+        tracing::debug!(?root_height, "New state root proof acquired");
+        wl_storage.write(&signed_root_key, update)?;
+        changed.insert(signed_root_key);
+    }
+    Ok(changed)
+}
+
+/// Delete the signatures collected over the state roots at
+/// `up_to_height` and below, which can no longer replace the
+/// signed state root.
+fn prune_state_root_sigs<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    up_to_height: BlockHeight,
+) -> Result<ChangedKeys>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let mut stale = BTreeSet::new();
+    for entry in storage_api::iter_prefix::<StateRootProof>(
+        wl_storage,
+        &state_root::sigs_prefix(),
+    )? {
+        let (key, partial) = entry?;
+        if partial.data.1 <= up_to_height {
+            stale.insert(key);
+        }
+    }
+    for key in &stale {
+        wl_storage.delete(key)?;
+    }
+    Ok(stale)
 }
 
 /// This vote updates the voting power backing a bridge pool root / nonce in
@@ -225,14 +320,18 @@ mod test_apply_bp_roots_to_storage {
     use namada_core::ledger::eth_bridge::storage::bridge_pool::{
         get_key_from_hash, get_nonce_key,
     };
+    use namada_core::ledger::storage::mockdb::MockDB;
     use namada_core::ledger::storage::testing::TestWlStorage;
+    use namada_core::ledger::storage::StoreType;
     use namada_core::ledger::storage_api::StorageRead;
     use namada_core::proto::{SignableEthMessage, Signed};
     use namada_core::types::address;
     use namada_core::types::ethereum_events::Uint;
     use namada_core::types::keccak::{keccak_hash, KeccakHash};
+    use namada_core::types::key::common;
     use namada_core::types::storage::Key;
     use namada_core::types::vote_extensions::bridge_pool_roots;
+    use namada_core::types::vote_extensions::bridge_pool_roots::state_root_msg;
     use namada_core::types::voting_power::FractionalVotingPower;
     use namada_proof_of_stake::parameters::OwnedPosParams;
     use namada_proof_of_stake::write_pos_params;
@@ -243,6 +342,24 @@ mod test_apply_bp_roots_to_storage {
     };
     use crate::{bridge_pool_vp, test_utils};
 
+    /// Sign the state root at the given height, as a validator
+    /// would in its vote extension.
+    fn sign_state_root(
+        wl_storage: &TestWlStorage,
+        hot_key: &common::SecretKey,
+        height: BlockHeight,
+    ) -> common::Signature {
+        let root = wl_storage
+            .ethbridge_queries()
+            .get_state_root_at_height(height)
+            .expect("Test failed");
+        Signed::<_, SignableEthMessage>::new(
+            hot_key,
+            state_root_msg(&root, height),
+        )
+        .sig
+    }
+
     /// The data needed to run a test.
     struct TestPackage {
         /// Two validators
@@ -318,6 +435,7 @@ mod test_apply_bp_roots_to_storage {
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
                 .sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         }
         .sign(&keys[&validators[0]].protocol);
         let TxResult { changed_keys, .. } =
@@ -327,7 +445,8 @@ mod test_apply_bp_roots_to_storage {
             &BridgePoolRoot(BridgePoolRootProof::new((root, nonce))),
             100.into(),
         ));
-        let expected: BTreeSet<Key> = bp_root_key.into_iter().collect();
+        let mut expected: BTreeSet<Key> = bp_root_key.into_iter().collect();
+        expected.insert(state_root::sigs_key(100.into()));
         assert_eq!(expected, changed_keys);
 
         let hot_key = &keys[&validators[2]].eth_bridge;
@@ -335,6 +454,7 @@ mod test_apply_bp_roots_to_storage {
             validator_addr: validators[2].clone(),
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         }
         .sign(&keys[&validators[2]].protocol);
 
@@ -342,10 +462,13 @@ mod test_apply_bp_roots_to_storage {
             apply_derived_tx(&mut wl_storage, vext.into())
                 .expect("Test failed");
 
-        let expected: BTreeSet<Key> =
-            [bp_root_key.seen_by(), bp_root_key.voting_power()]
-                .into_iter()
-                .collect();
+        let expected: BTreeSet<Key> = [
+            bp_root_key.seen_by(),
+            bp_root_key.voting_power(),
+            state_root::sigs_key(100.into()),
+        ]
+        .into_iter()
+        .collect();
         assert_eq!(expected, changed_keys);
     }
 
@@ -368,6 +491,7 @@ mod test_apply_bp_roots_to_storage {
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
                 .sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         }
         .sign(&keys[&validators[0]].protocol)
         .into();
@@ -376,6 +500,7 @@ mod test_apply_bp_roots_to_storage {
             validator_addr: validators[1].clone(),
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         }
         .sign(&keys[&validators[1]].protocol);
         vexts.insert(vext);
@@ -388,6 +513,7 @@ mod test_apply_bp_roots_to_storage {
 
         let mut expected: BTreeSet<Key> = bp_root_key.into_iter().collect();
         expected.insert(get_signed_root_key());
+        expected.insert(state_root::signed_root_key());
         assert_eq!(expected, changed_keys);
     }
 
@@ -410,6 +536,7 @@ mod test_apply_bp_roots_to_storage {
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
                 .sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         }
         .sign(&keys[&validators[0]].protocol);
         _ = apply_derived_tx(&mut wl_storage, vext.into())
@@ -420,6 +547,7 @@ mod test_apply_bp_roots_to_storage {
             validator_addr: validators[1].clone(),
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         }
         .sign(&keys[&validators[1]].protocol);
         let TxResult { changed_keys, .. } =
@@ -434,12 +562,76 @@ mod test_apply_bp_roots_to_storage {
             bp_root_key.seen_by(),
             bp_root_key.voting_power(),
             get_signed_root_key(),
+            state_root::sigs_key(100.into()),
+            state_root::signed_root_key(),
         ]
         .into_iter()
         .collect();
         assert_eq!(expected, changed_keys);
     }
 
+    #[test]
+    /// Test that the signatures over the state root are
+    /// accumulated until the bridge pool root of the same
+    /// height is confirmed.
+    fn test_state_root_signed_with_bp_root() {
+        let TestPackage {
+            validators,
+            keys,
+            mut wl_storage,
+        } = setup();
+        let root = wl_storage.ethbridge_queries().get_bridge_pool_root();
+        let nonce = wl_storage.ethbridge_queries().get_bridge_pool_nonce();
+        let to_sign = keccak_hash([root.0, nonce.to_bytes()].concat());
+        let state_root = wl_storage
+            .ethbridge_queries()
+            .get_state_root_at_height(100.into())
+            .expect("Test failed");
+        let sigs_key = state_root::sigs_key(100.into());
+
+        let hot_key = &keys[&validators[0]].eth_bridge;
+        let vext = bridge_pool_roots::Vext {
+            validator_addr: validators[0].clone(),
+            block_height: 100.into(),
+            sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
+                .sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
+        }
+        .sign(&keys[&validators[0]].protocol);
+        _ = apply_derived_tx(&mut wl_storage, vext.into())
+            .expect("Test failed");
+        let partial = wl_storage
+            .read::<StateRootProof>(&sigs_key)
+            .expect("Test failed")
+            .expect("Test failed");
+        assert_eq!(partial.data, (state_root, 100.into()));
+        assert_eq!(partial.signatures.len(), 1);
+        assert!(
+            wl_storage
+                .ethbridge_queries()
+                .get_signed_state_root()
+                .is_none()
+        );
+
+        let hot_key = &keys[&validators[1]].eth_bridge;
+        let vext = bridge_pool_roots::Vext {
+            validator_addr: validators[1].clone(),
+            block_height: 100.into(),
+            sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
+        }
+        .sign(&keys[&validators[1]].protocol);
+        _ = apply_derived_tx(&mut wl_storage, vext.into())
+            .expect("Test failed");
+        let signed = wl_storage
+            .ethbridge_queries()
+            .get_signed_state_root()
+            .expect("Test failed");
+        assert_eq!(signed.data, (state_root, 100.into()));
+        assert_eq!(signed.signatures.len(), 2);
+        assert!(!wl_storage.has_key(&sigs_key).expect("Test failed"));
+    }
+
     #[test]
     /// Test that the voting power key is updated correctly.
     fn test_voting_power() {
@@ -462,6 +654,7 @@ mod test_apply_bp_roots_to_storage {
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
                 .sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         }
         .sign(&keys[&validators[0]].protocol);
         _ = apply_derived_tx(&mut wl_storage, vext.into())
@@ -481,6 +674,7 @@ mod test_apply_bp_roots_to_storage {
             validator_addr: validators[1].clone(),
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         }
         .sign(&keys[&validators[1]].protocol);
         _ = apply_derived_tx(&mut wl_storage, vext.into())
@@ -516,6 +710,7 @@ mod test_apply_bp_roots_to_storage {
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
                 .sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         }
         .sign(&keys[&validators[0]].protocol);
         _ = apply_derived_tx(&mut wl_storage, vext.into())
@@ -536,6 +731,7 @@ mod test_apply_bp_roots_to_storage {
             validator_addr: validators[1].clone(),
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         }
         .sign(&keys[&validators[1]].protocol);
         _ = apply_derived_tx(&mut wl_storage, vext.into())
@@ -575,6 +771,7 @@ mod test_apply_bp_roots_to_storage {
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
                 .sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         }
         .sign(&keys[&validators[0]].protocol);
         _ = apply_derived_tx(&mut wl_storage, vext.into())
@@ -596,6 +793,7 @@ mod test_apply_bp_roots_to_storage {
             validator_addr: validators[1].clone(),
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         }
         .sign(&keys[&validators[1]].protocol);
         _ = apply_derived_tx(&mut wl_storage, vext.into())
@@ -636,6 +834,7 @@ mod test_apply_bp_roots_to_storage {
             validator_addr: validators[0].clone(),
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         };
         expected.0.attach_signature(
             wl_storage
@@ -689,6 +888,7 @@ mod test_apply_bp_roots_to_storage {
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
                 .sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         }
         .sign(&keys[&validators[0]].protocol)
         .into();
@@ -698,6 +898,7 @@ mod test_apply_bp_roots_to_storage {
             validator_addr: validators[1].clone(),
             block_height: 100.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 100.into()),
         }
         .sign(&keys[&validators[1]].protocol);
 
@@ -829,6 +1030,7 @@ mod test_apply_bp_roots_to_storage {
             validator_addr: validator_1.clone(),
             block_height: 3.into(),
             sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 3.into()),
         }
         .sign(&keys[&validator_1].protocol);
 
@@ -877,6 +1079,11 @@ mod test_apply_bp_roots_to_storage {
                         to_sign.clone(),
                     )
                     .sig,
+                    state_root_sig: sign_state_root(
+                        &wl_storage,
+                        hot_key,
+                        $block_height.into(),
+                    ),
                 }
                 .sign(&keys[&validators[0]].protocol);
                 _ = apply_derived_tx(&mut wl_storage, vext.into())
@@ -890,6 +1097,11 @@ mod test_apply_bp_roots_to_storage {
                         to_sign.clone(),
                     )
                     .sig,
+                    state_root_sig: sign_state_root(
+                        &wl_storage,
+                        hot_key,
+                        $block_height.into(),
+                    ),
                 }
                 .sign(&keys[&validators[1]].protocol);
                 _ = apply_derived_tx(&mut wl_storage, vext.into())
@@ -924,5 +1136,124 @@ mod test_apply_bp_roots_to_storage {
             (BridgePoolRoot(r), BlockHeight(100))
                 if r.data.0 == root && r.data.1 == nonce
         );
+
+        // check the signed state root in storage is unchanged
+        let state_root = wl_storage
+            .ethbridge_queries()
+            .get_signed_state_root()
+            .expect("Test failed - no signed state root in storage");
+        assert_eq!(state_root.data.1, BlockHeight(100));
+    }
+
+    #[test]
+    /// Test that the signatures over a state root which never
+    /// reached a quorum are pruned, once a more recent state
+    /// root is signed.
+    fn test_unconfirmed_state_root_sigs_pruned() {
+        let TestPackage {
+            validators,
+            keys,
+            mut wl_storage,
+        } = setup();
+        let root = wl_storage.ethbridge_queries().get_bridge_pool_root();
+        let nonce = wl_storage.ethbridge_queries().get_bridge_pool_nonce();
+        let to_sign = keccak_hash([root.0, nonce.to_bytes()].concat());
+
+        let hot_key = &keys[&validators[0]].eth_bridge;
+        let vext = bridge_pool_roots::Vext {
+            validator_addr: validators[0].clone(),
+            block_height: 99.into(),
+            sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign.clone())
+                .sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 99.into()),
+        }
+        .sign(&keys[&validators[0]].protocol);
+        _ = apply_derived_tx(&mut wl_storage, vext.into())
+            .expect("Test failed");
+        let stale_sigs_key = state_root::sigs_key(99.into());
+        assert!(wl_storage.has_key(&stale_sigs_key).expect("Test failed"));
+
+        let mut vexts = MultiSignedVext::default();
+        for validator in &validators[..2] {
+            let hot_key = &keys[validator].eth_bridge;
+            let vext = bridge_pool_roots::Vext {
+                validator_addr: validator.clone(),
+                block_height: 100.into(),
+                sig: Signed::<_, SignableEthMessage>::new(
+                    hot_key,
+                    to_sign.clone(),
+                )
+                .sig,
+                state_root_sig: sign_state_root(
+                    &wl_storage,
+                    hot_key,
+                    100.into(),
+                ),
+            }
+            .sign(&keys[validator].protocol);
+            vexts.insert(vext);
+        }
+        let TxResult { changed_keys, .. } =
+            apply_derived_tx(&mut wl_storage, vexts).expect("Test failed");
+
+        assert!(changed_keys.contains(&stale_sigs_key));
+        assert!(!wl_storage.has_key(&stale_sigs_key).expect("Test failed"));
+        let signed = wl_storage
+            .ethbridge_queries()
+            .get_signed_state_root()
+            .expect("Test failed");
+        assert_eq!(signed.data.1, BlockHeight(100));
+    }
+
+    #[test]
+    /// Test that vote extensions issued at a height whose merkle
+    /// tree has been pruned from storage are dropped, rather than
+    /// crashing the node.
+    fn test_vext_at_pruned_height_dropped() {
+        let TestPackage {
+            validators,
+            keys,
+            mut wl_storage,
+        } = setup();
+        let root = wl_storage.ethbridge_queries().get_bridge_pool_root();
+        let nonce = wl_storage.ethbridge_queries().get_bridge_pool_nonce();
+        let to_sign = keccak_hash([root.0, nonce.to_bytes()].concat());
+        let hot_key = &keys[&validators[0]].eth_bridge;
+        let vext = bridge_pool_roots::Vext {
+            validator_addr: validators[0].clone(),
+            block_height: 99.into(),
+            sig: Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig,
+            state_root_sig: sign_state_root(&wl_storage, hot_key, 99.into()),
+        }
+        .sign(&keys[&validators[0]].protocol);
+
+        // prune the merkle tree at height 99
+        wl_storage.storage.past_merkle_roots.clear();
+        let mut batch = MockDB::batch();
+        for st in StoreType::iter_non_provable() {
+            wl_storage
+                .storage
+                .db
+                .prune_merkle_tree_store(&mut batch, &st, 0.into())
+                .expect("Test failed");
+        }
+        wl_storage
+            .storage
+            .db
+            .exec_batch(batch)
+            .expect("Test failed");
+        assert!(
+            wl_storage
+                .ethbridge_queries()
+                .get_state_root_at_height(99.into())
+                .is_none()
+        );
+
+        assert!(apply_derived_tx(&mut wl_storage, vext.into()).is_err());
+        assert!(
+            !wl_storage
+                .has_key(&state_root::sigs_key(99.into()))
+                .expect("Test failed")
+        );
     }
 }
@@ -7,24 +7,28 @@ use borsh::BorshDeserialize;
 use eyre::{Result, WrapErr};
 use namada_core::hints;
 use namada_core::ledger::eth_bridge::storage::bridge_pool::{
-    get_nonce_key, is_pending_transfer_key, BRIDGE_POOL_ADDRESS,
+    get_expired_key, get_expired_prefix, get_key_from_hash, get_nonce_key,
+    get_replaced_prefix, is_pending_transfer_key, BRIDGE_POOL_ADDRESS,
 };
+use namada_core::ledger::eth_bridge::storage::flow_control::{self, Direction};
 use namada_core::ledger::eth_bridge::storage::{
-    self as bridge_storage, wrapped_erc20s,
+    self as bridge_storage, contract_messages, wrapped_erc20s,
 };
 use namada_core::ledger::eth_bridge::ADDRESS as BRIDGE_ADDRESS;
 use namada_core::ledger::parameters::read_epoch_duration_parameter;
+use namada_core::ledger::storage::merkle_tree::StoreRef;
 use namada_core::ledger::storage::traits::StorageHasher;
-use namada_core::ledger::storage::{DBIter, WlStorage, DB};
+use namada_core::ledger::storage::{DBIter, StoreType, WlStorage, DB};
 use namada_core::ledger::storage_api::{StorageRead, StorageWrite};
 use namada_core::types::address::Address;
 use namada_core::types::eth_bridge_pool::{
-    PendingTransfer, TransferToEthereumKind,
+    ExpiredTransfer, PendingTransfer, TransferToEthereumKind,
 };
 use namada_core::types::ethereum_events::{
-    EthAddress, EthereumEvent, TransferToEthereum, TransferToNamada,
-    TransfersToNamada,
+    ContractMessages, EthAddress, EthereumEvent, TransferToEthereum,
+    TransferToNamada, TransfersToNamada, Uint,
 };
+use namada_core::types::keccak::KeccakHash;
 use namada_core::types::storage::{BlockHeight, Key, KeySeg};
 use namada_core::types::token;
 use namada_core::types::token::{balance_key, minted_balance_key};
@@ -56,6 +60,12 @@ where
             ref relayer,
             ..
         } => act_on_transfers_to_eth(wl_storage, transfers, relayer),
+        EthereumEvent::ContractMessages { nonce, messages } => {
+            act_on_contract_messages(
+                wl_storage,
+                ContractMessages { nonce, messages },
+            )
+        }
         _ => {
             tracing::debug!(?event, "No actions taken for Ethereum event");
             Ok(BTreeSet::default())
@@ -91,6 +101,32 @@ where
     Ok(changed_keys)
 }
 
+/// Queue up the confirmed [`ContractMessages`] batches, in nonce order,
+/// to be dispatched to the handlers of their target accounts once the
+/// current block is finalized.
+fn act_on_contract_messages<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    messages_event: ContractMessages,
+) -> Result<BTreeSet<Key>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    tracing::debug!(?messages_event, "Acting on contract messages");
+    let confirmed_messages: Vec<_> = wl_storage
+        .storage
+        .eth_events_queue
+        .contract_messages
+        .push_and_iter(messages_event)
+        .flat_map(|ContractMessages { messages, .. }| messages)
+        .collect();
+    if confirmed_messages.is_empty() {
+        return Ok(BTreeSet::default());
+    }
+    contract_messages::enqueue(wl_storage, confirmed_messages)?;
+    Ok(BTreeSet::from([contract_messages::pending_key()]))
+}
+
 fn update_transfers_to_namada_state<'tx, D, H>(
     wl_storage: &mut WlStorage<D, H>,
     changed_keys: &mut BTreeSet<Key>,
@@ -227,8 +263,9 @@ where
 /// on Namada.
 ///
 /// Mints `amount` of a wrapped ERC20 `asset` for `receiver`.
-/// If the given asset is not whitelisted or has exceeded the
-/// token caps, mint NUTs, too.
+/// If the given asset is not whitelisted, has exceeded the
+/// token caps or its inflow limit, or inbound transfers are
+/// paused, mint NUTs, too.
 fn mint_eth_assets<D, H>(
     wl_storage: &mut WlStorage<D, H>,
     asset: &EthAddress,
@@ -260,6 +297,18 @@ where
     // minted from the iterator
     .flatten();
 
+    if asset_count.should_mint_erc20s()
+        && flow_control::record_flow(
+            wl_storage,
+            asset,
+            Direction::Inbound,
+            asset_count.erc20_amount,
+        )?
+    {
+        _ = changed_keys
+            .insert(flow_control::flow_key(asset, Direction::Inbound));
+    }
+
     for (token, ref amount) in assets_to_mint {
         let balance_key = balance_key(&token, receiver);
         update::amount(wl_storage, &balance_key, |balance| {
@@ -365,6 +414,10 @@ where
         _ = changed_keys.insert(relayer_rewards_key);
     }
 
+    // now that the nonce has been incremented, transfers replaced
+    // by fee bumps can no longer be relayed
+    changed_keys.append(&mut remove_replaced_transfers(wl_storage)?);
+
     if pending_keys.is_empty() {
         return Ok(changed_keys);
     }
@@ -392,6 +445,136 @@ where
     Ok(changed_keys)
 }
 
+/// Remove the records of all transfers replaced by fee bumps, since
+/// none of the Bridge pool roots they were part of can be relayed
+/// anymore.
+fn remove_replaced_transfers<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+) -> Result<BTreeSet<Key>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let replaced_keys: BTreeSet<Key> = wl_storage
+        .iter_prefix(&get_replaced_prefix())
+        .context("Failed to iterate over storage")?
+        .map(|(k, _, _)| {
+            Key::from_str(k.as_str()).expect("Key should be parsable")
+        })
+        .collect();
+    for key in replaced_keys.iter() {
+        wl_storage.delete(key)?;
+    }
+    Ok(replaced_keys)
+}
+
+/// The transfers that [`expire_transfers_to_eth`] removed from the
+/// Bridge pool, or refunded.
+#[derive(Debug, Default)]
+pub struct ExpiredTransfers {
+    /// The transfers that expired, and are awaiting a refund.
+    pub expired: Vec<ExpiredTransfer>,
+    /// The expired transfers that were refunded.
+    pub refunded: Vec<ExpiredTransfer>,
+}
+
+/// Remove the transfers that have been sitting in the Bridge pool for
+/// longer than the configured expiry, and refund the expired transfers
+/// that can no longer be relayed.
+///
+/// Expired transfers are removed from the Bridge pool right away, such
+/// that they are left out of any Bridge pool root signed afterwards.
+/// However, roots signed with the current nonce may still contain them,
+/// therefore they are only refunded once the Bridge pool nonce has been
+/// incremented. Until then, they may still be relayed. Note that the
+/// nonce is only incremented when a batch of transfers is relayed, so
+/// on an idle bridge, expired transfers wait for the next relayed batch
+/// to be refunded.
+///
+/// Returns the keys changed in storage, along with the expired and
+/// refunded transfers.
+pub fn expire_transfers_to_eth<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+) -> Result<(BTreeSet<Key>, ExpiredTransfers)>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let mut changed_keys = BTreeSet::default();
+    let mut transfers = ExpiredTransfers::default();
+    let Some(nonce) = wl_storage.read::<Uint>(&get_nonce_key())? else {
+        // the Bridge pool has not been initialized
+        return Ok((changed_keys, transfers));
+    };
+
+    // refund the transfers that expired before the nonce was incremented
+    let expired: Vec<(Key, ExpiredTransfer)> = wl_storage
+        .iter_prefix(&get_expired_prefix())
+        .context("Failed to iterate over storage")?
+        .map(|(k, v, _)| {
+            let key =
+                Key::from_str(k.as_str()).expect("Key should be parsable");
+            let expired = ExpiredTransfer::try_from_slice(&v[..])
+                .expect("Expired transfer should be decoded");
+            (key, expired)
+        })
+        .collect();
+    for (key, expired) in expired {
+        if expired.nonce >= nonce {
+            continue;
+        }
+        changed_keys
+            .append(&mut refund_transfer_fees(wl_storage, &expired.transfer)?);
+        changed_keys.append(&mut refund_transferred_assets(
+            wl_storage,
+            &expired.transfer,
+        )?);
+        wl_storage.delete(&key)?;
+        _ = changed_keys.insert(key);
+        transfers.refunded.push(expired);
+    }
+
+    let Some(expiry) = wl_storage.ethbridge_queries().get_bridge_pool_expiry()
+    else {
+        return Ok((changed_keys, transfers));
+    };
+    let current_epoch = wl_storage.storage.get_current_epoch().0;
+    let pred_epochs = &wl_storage.storage.block.pred_epochs;
+    let stores = wl_storage.storage.block.tree.stores();
+    let store = match stores.store(&StoreType::BridgePool) {
+        StoreRef::BridgePool(store) => store,
+        _ => unreachable!(),
+    };
+    let expired_hashes: Vec<KeccakHash> = store
+        .iter()
+        .filter(|(_, inserted_height)| {
+            pred_epochs
+                .get_epoch(**inserted_height)
+                .map(|inserted_epoch| inserted_epoch + expiry <= current_epoch)
+                .unwrap_or_default()
+        })
+        .map(|(hash, _)| hash.clone())
+        .collect();
+
+    // remove the expired transfers from the pool
+    for hash in expired_hashes {
+        let pending_key = get_key_from_hash(&hash);
+        let Some(transfer) = wl_storage.read(&pending_key)? else {
+            continue;
+        };
+        tracing::debug!(?transfer, "Bridge pool transfer has expired");
+        let expired_key = get_expired_key(&hash);
+        let expired = ExpiredTransfer { transfer, nonce };
+        wl_storage.write(&expired_key, &expired)?;
+        wl_storage.delete(&pending_key)?;
+        _ = changed_keys.insert(pending_key);
+        _ = changed_keys.insert(expired_key);
+        transfers.expired.push(expired);
+    }
+
+    Ok((changed_keys, transfers))
+}
+
 fn increment_bp_nonce<D, H>(
     nonce_key: &Key,
     wl_storage: &mut WlStorage<D, H>,
@@ -566,7 +749,9 @@ mod tests {
     use assert_matches::assert_matches;
     use borsh_ext::BorshSerializeExt;
     use eyre::Result;
-    use namada_core::ledger::eth_bridge::storage::bridge_pool::get_pending_key;
+    use namada_core::ledger::eth_bridge::storage::bridge_pool::{
+        get_pending_key, get_replaced_key, get_transfer_expiry_key,
+    };
     use namada_core::ledger::parameters::{
         update_epoch_parameter, EpochDuration,
     };
@@ -575,10 +760,13 @@ mod tests {
     use namada_core::ledger::storage::types::encode;
     use namada_core::types::address::testing::gen_implicit_address;
     use namada_core::types::address::{gen_established_address, nam, wnam};
+    use namada_core::types::eth_abi::Encode;
     use namada_core::types::eth_bridge_pool::GasFee;
     use namada_core::types::ethereum_events::testing::{
         arbitrary_keccak_hash, arbitrary_nonce, DAI_ERC20_ETH_ADDRESS,
     };
+    use namada_core::types::ethereum_events::ContractMessage;
+    use namada_core::types::storage::Epoch;
     use namada_core::types::time::DurationSecs;
     use namada_core::types::token::Amount;
     use namada_core::types::{address, eth_bridge_pool};
@@ -841,6 +1029,54 @@ mod tests {
         );
     }
 
+    #[test]
+    /// Test that contract messages are only queued up for dispatch
+    /// once all the batches preceding them have been confirmed
+    fn test_act_on_contract_messages_in_nonce_order() {
+        let mut wl_storage = TestWlStorage::default();
+        test_utils::bootstrap_ethereum_bridge(&mut wl_storage);
+        let message = |payload: &[u8]| ContractMessage {
+            source: DAI_ERC20_ETH_ADDRESS,
+            target: address::testing::established_address_1(),
+            payload: payload.to_vec(),
+        };
+
+        let changed = act_on(
+            &mut wl_storage,
+            EthereumEvent::ContractMessages {
+                nonce: 1.into(),
+                messages: vec![message(b"second")],
+            },
+        )
+        .unwrap();
+        assert!(changed.is_empty());
+        assert!(
+            contract_messages::take_pending(&mut wl_storage)
+                .unwrap()
+                .is_empty()
+        );
+
+        let changed = act_on(
+            &mut wl_storage,
+            EthereumEvent::ContractMessages {
+                nonce: 0.into(),
+                messages: vec![message(b"first")],
+            },
+        )
+        .unwrap();
+        assert_eq!(changed, BTreeSet::from([contract_messages::pending_key()]));
+        assert_eq!(
+            contract_messages::take_pending(&mut wl_storage).unwrap(),
+            vec![message(b"first"), message(b"second")],
+        );
+        assert_eq!(
+            wl_storage
+                .ethbridge_queries()
+                .get_next_contract_messages_nonce(),
+            2.into(),
+        );
+    }
+
     /// Parameters to test minting DAI in Namada.
     struct TestMintDai {
         /// The token cap of DAI.
@@ -951,6 +1187,112 @@ mod tests {
         .run_test();
     }
 
+    /// Mint DAI to a fresh receiver, and return the wDAI and DAI NUT
+    /// balances of the receiver.
+    fn mint_dai(
+        wl_storage: &mut TestWlStorage,
+        amount: token::Amount,
+    ) -> (token::Amount, token::Amount) {
+        let receiver = gen_established_address();
+        let transfers = vec![TransferToNamada {
+            amount,
+            asset: DAI_ERC20_ETH_ADDRESS,
+            receiver: receiver.clone(),
+        }];
+        update_transfers_to_namada_state(
+            wl_storage,
+            &mut BTreeSet::new(),
+            &transfers,
+        )
+        .unwrap();
+        let balance = |wrapped| -> token::Amount {
+            wl_storage
+                .read(&balance_key(&wrapped, &receiver))
+                .unwrap()
+                .unwrap_or_default()
+        };
+        (
+            balance(wrapped_erc20s::token(&DAI_ERC20_ETH_ADDRESS)),
+            balance(wrapped_erc20s::nut(&DAI_ERC20_ETH_ADDRESS)),
+        )
+    }
+
+    /// Test that exceeding the inflow limit of DAI in an epoch results
+    /// in minting DAI NUTs, and that the limit is reset in the next epoch.
+    #[test]
+    fn test_minting_dai_on_inflow_limit_overrun() {
+        let mut wl_storage = TestWlStorage::default();
+        test_utils::bootstrap_ethereum_bridge(&mut wl_storage);
+        test_utils::whitelist_tokens(
+            &mut wl_storage,
+            [(
+                DAI_ERC20_ETH_ADDRESS,
+                test_utils::WhitelistMeta {
+                    cap: Amount::max(),
+                    denom: 18,
+                },
+            )],
+        );
+        flow_control::write_limit(
+            &mut wl_storage,
+            &DAI_ERC20_ETH_ADDRESS,
+            Direction::Inbound,
+            Some(Amount::from(60)),
+        )
+        .expect("Test failed");
+
+        assert_eq!(
+            mint_dai(&mut wl_storage, Amount::from(40)),
+            (Amount::from(40), Amount::zero())
+        );
+        assert_eq!(
+            mint_dai(&mut wl_storage, Amount::from(40)),
+            (Amount::from(20), Amount::from(20))
+        );
+        assert_eq!(
+            mint_dai(&mut wl_storage, Amount::from(40)),
+            (Amount::zero(), Amount::from(40))
+        );
+
+        wl_storage.storage.block.epoch = wl_storage.storage.block.epoch.next();
+        assert_eq!(
+            mint_dai(&mut wl_storage, Amount::from(40)),
+            (Amount::from(40), Amount::zero())
+        );
+    }
+
+    /// Test that only DAI NUTs are minted while inbound transfers are
+    /// paused.
+    #[test]
+    fn test_minting_dai_when_inbound_paused() {
+        let mut wl_storage = TestWlStorage::default();
+        test_utils::bootstrap_ethereum_bridge(&mut wl_storage);
+        test_utils::whitelist_tokens(
+            &mut wl_storage,
+            [(
+                DAI_ERC20_ETH_ADDRESS,
+                test_utils::WhitelistMeta {
+                    cap: Amount::max(),
+                    denom: 18,
+                },
+            )],
+        );
+
+        flow_control::set_paused(&mut wl_storage, Direction::Inbound, true)
+            .expect("Test failed");
+        assert_eq!(
+            mint_dai(&mut wl_storage, Amount::from(100)),
+            (Amount::zero(), Amount::from(100))
+        );
+
+        flow_control::set_paused(&mut wl_storage, Direction::Inbound, false)
+            .expect("Test failed");
+        assert_eq!(
+            mint_dai(&mut wl_storage, Amount::from(100)),
+            (Amount::from(100), Amount::zero())
+        );
+    }
+
     #[test]
     /// When we act on an [`EthereumEvent::TransfersToEthereum`], test
     /// that pending transfers are deleted from the Bridge pool, the
@@ -1112,6 +1454,72 @@ mod tests {
         assert_eq!(bp_erc_balance_post, Amount::from(0));
     }
 
+    #[test]
+    /// When we act on an [`EthereumEvent::TransfersToEthereum`] for a
+    /// transfer that was replaced by a fee bump, test that the transfer
+    /// replacing it is completed, and that the relayer receives the
+    /// bumped fee.
+    fn test_act_on_replaced_transfers_to_eth() {
+        let mut wl_storage = TestWlStorage::default();
+        test_utils::bootstrap_ethereum_bridge(&mut wl_storage);
+        wl_storage.commit_block().expect("Test failed");
+        init_storage(&mut wl_storage);
+        let asset = EthAddress([0xff; 20]);
+        let pending_transfers = init_bridge_pool_transfers(
+            &mut wl_storage,
+            [(asset, TransferDataBuilder::new().kind_erc20().build())],
+        );
+        init_balance(&mut wl_storage, &pending_transfers);
+
+        // bump the fee of the transfer
+        let replaced = pending_transfers[0].clone();
+        let bumped = eth_bridge_pool::GasFeeBump {
+            transfer: replaced.clone(),
+            new_fee: Amount::from(2),
+        }
+        .bumped_transfer();
+        let replaced_key = get_replaced_key(&replaced.keccak256());
+        wl_storage
+            .storage
+            .delete(&get_pending_key(&replaced))
+            .expect("Test failed");
+        wl_storage
+            .storage
+            .write(&get_pending_key(&bumped), bumped.serialize_to_vec())
+            .expect("Test failed");
+        wl_storage
+            .write(&replaced_key, bumped.keccak256())
+            .expect("Test failed");
+        let pool_nam_balance_key = balance_key(&nam(), &BRIDGE_POOL_ADDRESS);
+        update::amount(&mut wl_storage, &pool_nam_balance_key, |balance| {
+            balance.receive(&Amount::from(1));
+        })
+        .expect("Test failed");
+
+        // relay the replaced transfer
+        let relayer = gen_established_address("random");
+        let event = EthereumEvent::TransfersToEthereum {
+            nonce: arbitrary_nonce(),
+            transfers: vec![TransferToEthereum::from(&replaced)],
+            relayer: relayer.clone(),
+        };
+        let changed_keys = act_on(&mut wl_storage, event).unwrap();
+
+        assert!(changed_keys.contains(&get_pending_key(&bumped)));
+        assert!(changed_keys.contains(&replaced_key));
+        assert!(
+            !wl_storage
+                .has_key(&get_pending_key(&bumped))
+                .expect("Test failed")
+        );
+        assert!(!wl_storage.has_key(&replaced_key).expect("Test failed"));
+        let relayer_nam_balance: Amount = wl_storage
+            .read(&balance_key(&nam(), &relayer))
+            .expect("Test failed")
+            .expect("Test failed");
+        assert_eq!(relayer_nam_balance, Amount::from(2));
+    }
+
     #[test]
     /// Test that the transfers time out in the bridge pool then the refund when
     /// we act on a TransfersToEthereum
@@ -1229,6 +1637,214 @@ mod tests {
         }
     }
 
+    /// Test that transfers are removed from the bridge pool once they
+    /// expire, and only refunded after the bridge pool nonce is
+    /// incremented.
+    #[test]
+    fn test_expire_transfers_to_eth() {
+        let mut wl_storage = TestWlStorage::default();
+        test_utils::bootstrap_ethereum_bridge(&mut wl_storage);
+        wl_storage.commit_block().expect("Test failed");
+        init_storage(&mut wl_storage);
+        // Height 0, epoch 0
+        let pending_transfers = init_bridge_pool(&mut wl_storage);
+        init_balance(&mut wl_storage, &pending_transfers);
+        wl_storage
+            .write(&get_transfer_expiry_key(), 2u64)
+            .expect("Test failed");
+        wl_storage.commit_block().expect("Test failed");
+        wl_storage
+            .storage
+            .block
+            .pred_epochs
+            .new_epoch(BlockHeight(0));
+
+        // the transfers have not expired yet in epoch 1
+        wl_storage.storage.block.height = BlockHeight(11);
+        wl_storage
+            .storage
+            .block
+            .pred_epochs
+            .new_epoch(BlockHeight(10));
+        wl_storage.storage.block.epoch = Epoch(1);
+        let (changed_keys, transfers) =
+            expire_transfers_to_eth(&mut wl_storage).expect("Test failed");
+        assert!(changed_keys.is_empty());
+        assert!(transfers.expired.is_empty());
+        assert!(transfers.refunded.is_empty());
+
+        // the transfers expire in epoch 2, but are not refunded yet
+        wl_storage.storage.block.height = BlockHeight(21);
+        wl_storage
+            .storage
+            .block
+            .pred_epochs
+            .new_epoch(BlockHeight(20));
+        wl_storage.storage.block.epoch = Epoch(2);
+        let (changed_keys, transfers) =
+            expire_transfers_to_eth(&mut wl_storage).expect("Test failed");
+        assert!(transfers.refunded.is_empty());
+        assert_eq!(
+            transfers
+                .expired
+                .into_iter()
+                .map(|expired| expired.transfer)
+                .collect::<HashSet<_>>(),
+            pending_transfers.iter().cloned().collect::<HashSet<_>>(),
+        );
+        for transfer in pending_transfers.iter() {
+            let pending_key = get_pending_key(transfer);
+            let expired_key = get_expired_key(&transfer.keccak256());
+            assert!(changed_keys.contains(&pending_key));
+            assert!(changed_keys.contains(&expired_key));
+            assert!(!wl_storage.has_key(&pending_key).expect("Test failed"));
+            // expired transfers may still be relayed
+            let event: TransferToEthereum = transfer.into();
+            assert_eq!(
+                wl_storage
+                    .ethbridge_queries()
+                    .lookup_transfer_to_eth(&event),
+                Some((transfer.clone(), expired_key)),
+            );
+        }
+        wl_storage.commit_block().expect("Test failed");
+        for transfer in pending_transfers.iter() {
+            // the expired transfers were removed from the merkle tree
+            assert!(
+                !wl_storage
+                    .storage
+                    .block
+                    .tree
+                    .has_key(&get_pending_key(transfer))
+                    .expect("Test failed")
+            );
+        }
+        let (_, transfers) =
+            expire_transfers_to_eth(&mut wl_storage).expect("Test failed");
+        assert!(transfers.expired.is_empty());
+        assert!(transfers.refunded.is_empty());
+
+        // once the nonce is incremented, the transfers are refunded
+        increment_bp_nonce(&get_nonce_key(), &mut wl_storage)
+            .expect("Test failed");
+        let (_, transfers) =
+            expire_transfers_to_eth(&mut wl_storage).expect("Test failed");
+        assert_eq!(
+            transfers
+                .refunded
+                .into_iter()
+                .map(|refunded| refunded.transfer)
+                .collect::<HashSet<_>>(),
+            pending_transfers.iter().cloned().collect::<HashSet<_>>(),
+        );
+        assert_eq!(
+            wl_storage
+                .iter_prefix(&get_expired_prefix())
+                .expect("Test failed")
+                .count(),
+            0
+        );
+        let payer = address::testing::established_address_2();
+        let payer_balance: Amount = wl_storage
+            .read(&balance_key(&nam(), &payer))
+            .expect("Test failed")
+            .expect("Test failed");
+        let expected = pending_transfers
+            .iter()
+            .fold(Amount::from(0), |acc, t| acc + t.gas_fee.amount);
+        assert_eq!(payer_balance, expected);
+        for transfer in pending_transfers.iter() {
+            let token = if transfer.transfer.asset == wnam() {
+                nam()
+            } else {
+                transfer.token_address()
+            };
+            let sender_balance: Amount = wl_storage
+                .read(&balance_key(&token, &transfer.transfer.sender))
+                .expect("Test failed")
+                .expect("Test failed");
+            assert_eq!(sender_balance, transfer.transfer.amount);
+        }
+    }
+
+    /// Test that on an idle bridge, where the Bridge pool nonce is never
+    /// incremented, expired transfers stay escrowed and relayable for as
+    /// long as no batch of transfers is relayed, no matter how many epochs
+    /// go by.
+    #[test]
+    fn test_expired_transfers_on_idle_bridge() {
+        let mut wl_storage = TestWlStorage::default();
+        test_utils::bootstrap_ethereum_bridge(&mut wl_storage);
+        wl_storage.commit_block().expect("Test failed");
+        init_storage(&mut wl_storage);
+        let pending_transfers = init_bridge_pool(&mut wl_storage);
+        init_balance(&mut wl_storage, &pending_transfers);
+        wl_storage
+            .write(&get_transfer_expiry_key(), 1u64)
+            .expect("Test failed");
+        wl_storage.commit_block().expect("Test failed");
+        wl_storage
+            .storage
+            .block
+            .pred_epochs
+            .new_epoch(BlockHeight(0));
+        let nonce: Uint = wl_storage
+            .read(&get_nonce_key())
+            .expect("Test failed")
+            .expect("Test failed");
+
+        let mut expired = HashSet::new();
+        for epoch in 1..=10u64 {
+            let height = BlockHeight(epoch * 10);
+            wl_storage.storage.block.height = height + 1;
+            wl_storage.storage.block.pred_epochs.new_epoch(height);
+            wl_storage.storage.block.epoch = Epoch(epoch);
+            let (_, transfers) =
+                expire_transfers_to_eth(&mut wl_storage).expect("Test failed");
+            assert!(transfers.refunded.is_empty());
+            for transfer in transfers.expired {
+                assert_eq!(transfer.nonce, nonce);
+                expired.insert(transfer.transfer);
+            }
+            wl_storage.commit_block().expect("Test failed");
+        }
+        assert_eq!(
+            expired,
+            pending_transfers.iter().cloned().collect::<HashSet<_>>(),
+        );
+
+        for transfer in pending_transfers.iter() {
+            // the transfers may still be relayed
+            let event: TransferToEthereum = transfer.into();
+            assert_eq!(
+                wl_storage
+                    .ethbridge_queries()
+                    .lookup_transfer_to_eth(&event),
+                Some((
+                    transfer.clone(),
+                    get_expired_key(&transfer.keccak256())
+                )),
+            );
+            // and nothing was refunded to their senders
+            let token = if transfer.transfer.asset == wnam() {
+                nam()
+            } else {
+                transfer.token_address()
+            };
+            let sender_balance: Amount = wl_storage
+                .read(&balance_key(&token, &transfer.transfer.sender))
+                .expect("Test failed")
+                .expect("Test failed");
+            assert_eq!(sender_balance, Amount::zero());
+        }
+        let payer = address::testing::established_address_2();
+        let payer_balance: Amount = wl_storage
+            .read(&balance_key(&nam(), &payer))
+            .expect("Test failed")
+            .expect("Test failed");
+        assert_eq!(payer_balance, Amount::zero());
+    }
+
     #[test]
     fn test_redeem_native_token() -> Result<()> {
         let mut wl_storage = TestWlStorage::default();
@@ -1,17 +1,21 @@
+use std::collections::BTreeMap;
+
 use borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::hints;
+use namada_core::ledger::eth_bridge::storage::flow_control::{self, Direction};
 use namada_core::ledger::eth_bridge::storage::{
-    active_key, bridge_pool, whitelist,
+    active_key, bridge_pool, state_root, whitelist,
 };
 use namada_core::ledger::storage;
 use namada_core::ledger::storage::{StoreType, WlStorage};
 use namada_core::ledger::storage_api::StorageRead;
 use namada_core::types::address::Address;
 use namada_core::types::eth_abi::Encode;
-use namada_core::types::eth_bridge_pool::PendingTransfer;
+use namada_core::types::eth_bridge_pool::{ExpiredTransfer, PendingTransfer};
 use namada_core::types::ethereum_events::{
     EthAddress, EthereumEvent, GetEventNonce, TransferToEthereum, Uint,
 };
+use namada_core::types::hash::Hash;
 use namada_core::types::keccak::KeccakHash;
 use namada_core::types::storage::{BlockHeight, Epoch, Key as StorageKey};
 use namada_core::types::token;
@@ -26,7 +30,7 @@ use namada_proof_of_stake::{
     validator_eth_cold_key_handle, validator_eth_hot_key_handle,
 };
 
-use crate::storage::proof::BridgePoolRootProof;
+use crate::storage::proof::{BridgePoolRootProof, StateRootProof};
 use crate::storage::vote_tallies;
 
 /// This enum is used as a parameter to
@@ -191,6 +195,15 @@ where
             .get_event_nonce()
     }
 
+    /// Get the nonce of the next contract messages event to be processed.
+    pub fn get_next_contract_messages_nonce(self) -> Uint {
+        self.wl_storage
+            .storage
+            .eth_events_queue
+            .contract_messages
+            .get_event_nonce()
+    }
+
     /// Get the latest nonce for the Ethereum bridge
     /// pool.
     pub fn get_bridge_pool_nonce(self) -> Uint {
@@ -206,6 +219,16 @@ where
         .expect("Deserializing the nonce from storage should not fail.")
     }
 
+    /// Get the number of epochs after which transfers in the
+    /// Ethereum bridge pool expire.
+    ///
+    /// If no expiry has been configured, return [`None`].
+    pub fn get_bridge_pool_expiry(self) -> Option<u64> {
+        self.wl_storage
+            .read(&bridge_pool::get_transfer_expiry_key())
+            .expect("Reading the Bridge pool expiry shouldn't fail.")
+    }
+
     /// Get the nonce at a particular block height.
     pub fn get_bridge_pool_nonce_at_height(self, height: BlockHeight) -> Uint {
         Uint::try_from_slice(
@@ -272,6 +295,31 @@ where
         Some(base_tree.sub_root(&StoreType::BridgePool).into())
     }
 
+    /// Get the root of Namada's merkle tree at a given height.
+    ///
+    /// The roots of recently committed blocks are looked up in
+    /// memory. Older trees are rebuilt from storage, if they have
+    /// not been pruned yet.
+    pub fn get_state_root_at_height(self, height: BlockHeight) -> Option<Hash> {
+        let storage = &self.wl_storage.storage;
+        if height == storage.get_last_block_height() {
+            return Some(Hash(storage.merkle_root().0));
+        }
+        if let Some(root) = storage.past_merkle_roots.get(&height) {
+            return Some(*root);
+        }
+        let root = storage.get_merkle_tree(height, None).ok()?.root();
+        Some(Hash(root.0))
+    }
+
+    /// Get the latest root of Namada's merkle tree signed by
+    /// a quorum of validators, along with its signatures.
+    pub fn get_signed_state_root(self) -> Option<StateRootProof> {
+        self.wl_storage
+            .read(&state_root::signed_root_key())
+            .expect("Reading a signed state root shouldn't fail.")
+    }
+
     /// Determines if it is possible to send a validator set update vote
     /// extension at the provided [`BlockHeight`] in [`SendValsetUpd`].
     #[inline]
@@ -489,6 +537,8 @@ where
     /// 1. `token` is not whitelisted.
     /// 2. `token` has exceeded the configured token caps,
     ///    after minting `amount_to_mint`.
+    /// 3. Inbound transfers are paused, or `token` has exceeded its inflow
+    ///    limit for the current epoch, after minting `amount_to_mint`.
     pub fn get_eth_assets_to_mint(
         self,
         token: &EthAddress,
@@ -512,33 +562,91 @@ where
             );
         }
 
-        if amount_to_mint + supply > cap {
-            let erc20_amount = cap - supply;
-            let nut_amount = amount_to_mint - erc20_amount;
-
-            return EthAssetMint {
-                nut_amount,
-                erc20_amount,
-            };
+        let mut erc20_amount = if amount_to_mint + supply > cap {
+            cap - supply
+        } else {
+            amount_to_mint
+        };
+        if let Some(allowance) =
+            self.get_flow_allowance(token, Direction::Inbound)
+        {
+            erc20_amount = std::cmp::min(erc20_amount, allowance);
         }
 
         EthAssetMint {
-            erc20_amount: amount_to_mint,
-            nut_amount: token::Amount::zero(),
+            nut_amount: amount_to_mint - erc20_amount,
+            erc20_amount,
         }
     }
 
+    /// Check if transfers across the bridge in the given [`Direction`]
+    /// have been paused by governance.
+    pub fn is_flow_paused(self, direction: Direction) -> bool {
+        flow_control::is_paused(self.wl_storage, direction)
+            .expect("Reading from storage should not fail")
+    }
+
+    /// Fetch the per-epoch flow limit of the asset associated with the
+    /// given [`EthAddress`], in the given [`Direction`].
+    ///
+    /// If no limit has been configured, return [`None`].
+    pub fn get_flow_limit(
+        self,
+        token: &EthAddress,
+        direction: Direction,
+    ) -> Option<token::Amount> {
+        flow_control::read_limit(self.wl_storage, token, direction)
+            .expect("Reading from storage should not fail")
+    }
+
+    /// Return the amount of the asset associated with the given
+    /// [`EthAddress`] that may still cross the bridge in the given
+    /// [`Direction`], during the current epoch.
+    ///
+    /// If the flow of the asset is unlimited, return [`None`].
+    pub fn get_flow_allowance(
+        self,
+        token: &EthAddress,
+        direction: Direction,
+    ) -> Option<token::Amount> {
+        flow_control::read_allowance(self.wl_storage, token, direction)
+            .expect("Reading from storage should not fail")
+    }
+
     /// Given a [`TransferToEthereum`] event, look-up the corresponding
     /// [`PendingTransfer`].
+    ///
+    /// If the transfer has been replaced by a fee bump, the transfer
+    /// that replaced it is returned instead.
+    /// Transfers that expired, but were not refunded yet, are also
+    /// looked up.
     pub fn lookup_transfer_to_eth(
         self,
         transfer: &TransferToEthereum,
     ) -> Option<(PendingTransfer, StorageKey)> {
-        let pending_key = bridge_pool::get_key_from_hash(&transfer.keccak256());
-        self.wl_storage
-            .read(&pending_key)
-            .expect("Reading from storage should not fail")
-            .zip(Some(pending_key))
+        let mut hash = transfer.keccak256();
+        loop {
+            let pending_key = bridge_pool::get_key_from_hash(&hash);
+            let pending = self
+                .wl_storage
+                .read(&pending_key)
+                .expect("Reading from storage should not fail");
+            if pending.is_some() {
+                return pending.zip(Some(pending_key));
+            }
+            let expired_key = bridge_pool::get_expired_key(&hash);
+            let expired: Option<ExpiredTransfer> = self
+                .wl_storage
+                .read(&expired_key)
+                .expect("Reading from storage should not fail");
+            if let Some(ExpiredTransfer { transfer, .. }) = expired {
+                return Some((transfer, expired_key));
+            }
+            hash = self
+                .wl_storage
+                .read(&bridge_pool::get_replaced_key(&hash))
+                .expect("Reading from storage should not fail")?;
+        }
     }
 
     /// Valdidate an [`EthereumEvent`]'s nonce against the current
@@ -563,6 +671,11 @@ where
     /// For a transfers to Namada event to be considered valid,
     /// the nonce of this kind of event must not be lower than
     /// the one stored in Namada.
+    ///
+    /// ## Contract messages
+    ///
+    /// Contract messages are ordered by their own nonce, which is
+    /// validated in the same way as the nonce of transfers to Namada.
     pub fn validate_eth_event_nonce(&self, event: &EthereumEvent) -> bool {
         match event {
             EthereumEvent::TransfersToEthereum {
@@ -582,11 +695,46 @@ where
                     return false;
                 }
             }
+            EthereumEvent::ContractMessages {
+                nonce: ext_nonce, ..
+            } => {
+                let next_contract_messages_nonce =
+                    self.get_next_contract_messages_nonce();
+                if &next_contract_messages_nonce > ext_nonce {
+                    return false;
+                }
+            }
             // consider other ethereum event kinds valid
             _ => {}
         }
         true
     }
+
+    /// Check if the given [`EthereumEvent`] has already been dealt with
+    /// on chain, as far as the given validator is concerned. This is the
+    /// case if a quorum of validators has seen the event, or if the
+    /// validator's vote on it has already been tallied in
+    /// [`vote_tallies`].
+    pub fn has_voted_on_eth_event(
+        self,
+        validator: &Address,
+        event: &EthereumEvent,
+    ) -> bool {
+        let keys = vote_tallies::Keys::from(event);
+        let seen: bool = self
+            .wl_storage
+            .read(&keys.seen())
+            .expect("Reading a value from storage should not fail")
+            .unwrap_or(false);
+        if seen {
+            return true;
+        }
+        self.wl_storage
+            .read::<BTreeMap<Address, BlockHeight>>(&keys.seen_by())
+            .expect("Reading a value from storage should not fail")
+            .map(|seen_by| seen_by.contains_key(validator))
+            .unwrap_or(false)
+    }
 }
 
 /// Number of tokens to mint after receiving a "transfer
@@ -6,9 +6,10 @@ use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use ethers::abi::Tokenizable;
 use namada_core::types::eth_abi::Encode;
 use namada_core::types::ethereum_events::Uint;
+use namada_core::types::hash::Hash;
 use namada_core::types::keccak::KeccakHash;
 use namada_core::types::key::{common, secp256k1};
-use namada_core::types::storage::Epoch;
+use namada_core::types::storage::{BlockHeight, Epoch};
 use namada_core::types::vote_extensions::validator_set_update::{
     valset_upd_toks_to_hashes, EthAddrBook, VotingPowersMap, VotingPowersMapExt,
 };
@@ -30,6 +31,10 @@ pub struct EthereumProof<T> {
 
 pub type BridgePoolRootProof = EthereumProof<(KeccakHash, Uint)>;
 
+/// Proof that the root of Namada's merkle tree at some block
+/// height was signed by a quorum of validators.
+pub type StateRootProof = EthereumProof<(Hash, BlockHeight)>;
+
 impl<T> EthereumProof<T> {
     /// Return an incomplete [`EthereumProof`].
     pub fn new(data: T) -> Self {
@@ -105,6 +105,7 @@ pub fn bootstrap_ethereum_bridge(
         // start with empty erc20 whitelist
         erc20_whitelist: vec![],
         eth_start_height: Default::default(),
+        bridge_pool_expiry: None,
         min_confirmations: MinimumConfirmations::from(unsafe {
             // SAFETY: The only way the API contract of `NonZeroU64` can
             // be violated is if we construct values
@@ -353,6 +353,7 @@ pub struct GenesisValidator {
     PartialOrd,
     PartialEq,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ValidatorMetaData {
     /// Validator's email
     pub email: String,
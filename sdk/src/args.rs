@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration as StdDuration;
 
+use masp_primitives::memo::MemoBytes;
 use namada_core::ledger::governance::cli::onchain::{
     DefaultProposal, PgfFundingProposal, PgfStewardProposal,
 };
@@ -13,8 +14,8 @@ use namada_core::types::dec::Dec;
 use namada_core::types::ethereum_events::EthAddress;
 use namada_core::types::keccak::KeccakHash;
 use namada_core::types::key::{common, SchemeType};
-use namada_core::types::masp::MaspValue;
-use namada_core::types::storage::Epoch;
+use namada_core::types::masp::{MaspValue, PaymentAddress};
+use namada_core::types::storage::{BlockHeight, Epoch};
 use namada_core::types::time::DateTimeUtc;
 use namada_core::types::transaction::GasLimit;
 use namada_core::types::{storage, token};
@@ -104,6 +105,39 @@ impl NamadaTypes for SdkTypes {
 pub struct Query<C: NamadaTypes = SdkTypes> {
     /// The address of the ledger node as host:port
     pub ledger_address: C::TendermintAddress,
+    /// The format in which the result of the query is printed
+    pub output: OutputFormat,
+}
+
+/// The format in which the result of a query is printed
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// A versioned JSON document
+    Json,
+}
+
+impl OutputFormat {
+    /// Check if the result of the query is printed as JSON
+    pub fn is_json(&self) -> bool {
+        matches!(self, Self::Json)
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => {
+                Err(format!("Unknown output format {s}, expected text or json"))
+            }
+        }
+    }
 }
 
 /// Transaction associated results arguments
@@ -225,6 +259,11 @@ pub struct TxTransfer<C: NamadaTypes = SdkTypes> {
     pub amount: InputAmount,
     /// Native token address
     pub native_token: C::NativeAddress,
+    /// Memo attached to the shielded output of the transfer
+    pub memo: Option<MemoBytes>,
+    /// Viewing key of a shielded source whose spending key is held by a
+    /// hardware wallet. The transfer source is then the MASP address.
+    pub device_source: Option<C::ViewingKey>,
     /// Path to the TX WASM code file
     pub tx_code_path: PathBuf,
 }
@@ -270,6 +309,23 @@ impl<C: NamadaTypes> TxTransfer<C> {
         }
     }
 
+    /// Memo attached to the shielded output of the transfer
+    pub fn memo(self, memo: MemoBytes) -> Self {
+        Self {
+            memo: Some(memo),
+            ..self
+        }
+    }
+
+    /// Viewing key of a shielded source whose spending key is held by a
+    /// hardware wallet
+    pub fn device_source(self, device_source: C::ViewingKey) -> Self {
+        Self {
+            device_source: Some(device_source),
+            ..self
+        }
+    }
+
     /// Path to the TX WASM code file
     pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
         Self {
@@ -1269,6 +1325,26 @@ pub struct QueryConversions<C: NamadaTypes = SdkTypes> {
     pub epoch: Option<Epoch>,
 }
 
+/// Sync the shielded context
+#[derive(Clone, Debug)]
+pub struct ShieldedSync<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Viewing key whose notes to sync, all known keys if omitted
+    pub viewing_key: Option<C::ViewingKey>,
+    /// URL of a MASP indexer to sync from instead of the ledger
+    pub masp_indexer: Option<String>,
+}
+
+/// Query the notes received by shielded viewing key(s)
+#[derive(Clone, Debug)]
+pub struct QueryShieldedNotes<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Viewing key whose notes to query, all known keys if omitted
+    pub viewing_key: Option<C::ViewingKey>,
+}
+
 /// Query token balance(s)
 #[derive(Clone, Debug)]
 pub struct QueryAccount<C: NamadaTypes = SdkTypes> {
@@ -1302,6 +1378,43 @@ pub struct QueryTransfers<C: NamadaTypes = SdkTypes> {
     pub token: Option<C::Address>,
 }
 
+/// The format in which a shielded history is exported
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum HistoryFormat {
+    /// Comma-separated values, one note per line
+    #[default]
+    Csv,
+    /// A JSON array of history entries
+    Json,
+}
+
+impl std::str::FromStr for HistoryFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => {
+                Err(format!("Unknown history format {s}, expected csv or json"))
+            }
+        }
+    }
+}
+
+/// Query the history of shielded viewing key(s)
+#[derive(Clone, Debug)]
+pub struct QueryShieldedHistory<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Viewing key whose history to query, all known keys if omitted
+    pub viewing_key: Option<C::ViewingKey>,
+    /// File to which the history is exported, instead of being printed
+    pub export: Option<PathBuf>,
+    /// The format of the exported history
+    pub format: HistoryFormat,
+}
+
 /// Query PoS bond(s)
 #[derive(Clone, Debug)]
 pub struct QueryBonds<C: NamadaTypes = SdkTypes> {
@@ -1772,6 +1885,42 @@ pub struct SignTx<C: NamadaTypes = SdkTypes> {
     pub owner: C::Address,
 }
 
+/// Create a partially signed transaction bundle arguments
+#[derive(Clone, Debug)]
+pub struct BundleCreate<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Transaction data
+    pub tx_data: C::Data,
+    /// The account address
+    pub owner: C::Address,
+}
+
+/// Sign a transaction bundle arguments
+#[derive(Clone, Debug)]
+pub struct BundleSign {
+    /// The path to the bundle, which is updated in place
+    pub bundle: PathBuf,
+}
+
+/// Merge transaction bundles arguments
+#[derive(Clone, Debug)]
+pub struct BundleMerge {
+    /// The paths to the bundles of the same transaction
+    pub bundles: Vec<PathBuf>,
+    /// The path to write the merged bundle to
+    pub output: PathBuf,
+}
+
+/// Submit a transaction bundle arguments
+#[derive(Clone, Debug)]
+pub struct BundleSubmit<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The path to the bundle
+    pub bundle: PathBuf,
+}
+
 /// Query PoS commission rate
 #[derive(Clone, Debug)]
 pub struct QueryCommissionRate<C: NamadaTypes = SdkTypes> {
@@ -1830,6 +1979,25 @@ pub struct QueryFindValidator<C: NamadaTypes = SdkTypes> {
     pub tm_addr: String,
 }
 
+/// Account discovery arguments
+#[derive(Clone, Debug)]
+pub struct DiscoverAccounts<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The signature schemes of the transparent accounts to discover
+    pub schemes: Vec<SchemeType>,
+    /// Whether to discover shielded accounts too
+    pub shielded: bool,
+    /// The number of consecutive unused accounts after which to stop
+    pub gap_limit: u32,
+    /// The prefix of the aliases of the discovered accounts
+    pub alias_prefix: String,
+    /// Override the existing aliases without confirmation
+    pub alias_force: bool,
+    /// Don't encrypt the keys
+    pub unsafe_dont_encrypt: bool,
+}
+
 /// Query the raw bytes of given storage key
 #[derive(Clone, Debug)]
 pub struct QueryRawBytes<C: NamadaTypes = SdkTypes> {
@@ -1852,6 +2020,8 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub output_folder: Option<PathBuf>,
     /// Submit the transaction even if it doesn't pass client checks
     pub force: bool,
+    /// Sign the transaction without asking for confirmation
+    pub no_confirm: bool,
     /// Do not wait for the transaction to be added to the blockchain
     pub broadcast_only: bool,
     /// The address of the ledger node as host:port
@@ -1872,6 +2042,9 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub fee_unshield: Option<C::TransferSource>,
     /// The max amount of gas used to process tx
     pub gas_limit: GasLimit,
+    /// Estimate the gas limit by dry running the tx, instead of using the
+    /// given one
+    pub estimate_gas: bool,
     /// The optional expiration of the transaction
     pub expiration: Option<DateTimeUtc>,
     /// Generate an ephimeral signing key to be used only once to sign a
@@ -1889,6 +2062,9 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub password: Option<Zeroizing<String>>,
     /// Use device to sign the transaction
     pub use_device: bool,
+    /// Sign the transaction with the keys of the signing daemon listening on
+    /// the given socket, if they are not in the wallet
+    pub remote_signer: Option<PathBuf>,
 }
 
 /// Builder functions for Tx
@@ -1923,6 +2099,10 @@ pub trait TxBuilder<C: NamadaTypes>: Sized {
     fn force(self, force: bool) -> Self {
         self.tx(|x| Tx { force, ..x })
     }
+    /// Sign the transaction without asking for confirmation
+    fn no_confirm(self, no_confirm: bool) -> Self {
+        self.tx(|x| Tx { no_confirm, ..x })
+    }
     /// Do not wait for the transaction to be added to the blockchain
     fn broadcast_only(self, broadcast_only: bool) -> Self {
         self.tx(|x| Tx {
@@ -1985,6 +2165,11 @@ pub trait TxBuilder<C: NamadaTypes>: Sized {
     fn gas_limit(self, gas_limit: GasLimit) -> Self {
         self.tx(|x| Tx { gas_limit, ..x })
     }
+    /// Estimate the gas limit by dry running the tx, instead of using the
+    /// given one
+    fn estimate_gas(self, estimate_gas: bool) -> Self {
+        self.tx(|x| Tx { estimate_gas, ..x })
+    }
     /// The optional expiration of the transaction
     fn expiration(self, expiration: DateTimeUtc) -> Self {
         self.tx(|x| Tx {
@@ -2029,6 +2214,13 @@ pub trait TxBuilder<C: NamadaTypes>: Sized {
             ..x
         })
     }
+    /// Sign with the keys of the signing daemon listening on the given socket
+    fn remote_signer(self, socket: PathBuf) -> Self {
+        self.tx(|x| Tx {
+            remote_signer: Some(socket),
+            ..x
+        })
+    }
 }
 
 impl<C: NamadaTypes> TxBuilder<C> for Tx<C> {
@@ -2049,6 +2241,8 @@ pub struct MaspAddrKeyAdd {
     pub alias_force: bool,
     /// Any MASP value
     pub value: MaspValue,
+    /// The height of the first block that can contain notes for the key
+    pub birthday: Option<BlockHeight>,
     /// Don't encrypt the keypair
     pub unsafe_dont_encrypt: bool,
 }
@@ -2156,6 +2350,77 @@ pub struct KeyExport {
     pub alias: String,
 }
 
+/// Wallet address book contact insertion arguments
+#[derive(Clone, Debug)]
+pub struct ContactAdd {
+    /// Contact alias
+    pub alias: String,
+    /// Whether to force overwrite the alias
+    pub alias_force: bool,
+    /// Transparent address of the contact
+    pub address: Option<Address>,
+    /// Payment address of the contact
+    pub payment_address: Option<PaymentAddress>,
+    /// Human-readable label
+    pub label: Option<String>,
+    /// Tags of the contact
+    pub tags: Vec<String>,
+    /// Memo to attach to the transfers to the contact
+    pub memo_template: Option<String>,
+}
+
+/// Wallet watch-only account insertion arguments
+#[derive(Clone, Debug)]
+pub struct WatchOnlyAdd {
+    /// Account alias
+    pub alias: String,
+    /// Whether to force overwrite the alias
+    pub alias_force: bool,
+    /// Address of the account
+    pub address: Address,
+    /// Human-readable label
+    pub label: Option<String>,
+    /// Tags of the account
+    pub tags: Vec<String>,
+}
+
+/// Wallet address book listing arguments
+#[derive(Clone, Debug)]
+pub struct AddressBookList {
+    /// Only list the entries with this tag
+    pub tag: Option<String>,
+}
+
+/// Wallet address book entry removal arguments
+#[derive(Clone, Debug)]
+pub struct AddressBookRemove {
+    /// Alias of the entry
+    pub alias: String,
+}
+
+/// Wallet address book export arguments
+#[derive(Clone, Debug)]
+pub struct AddressBookExport {
+    /// Path of the JSON file to write
+    pub path: PathBuf,
+}
+
+/// Wallet address book import arguments
+#[derive(Clone, Debug)]
+pub struct AddressBookImport {
+    /// Path of the JSON file to read
+    pub path: PathBuf,
+    /// Whether to force overwrite the aliases
+    pub alias_force: bool,
+}
+
+/// Wallet signing daemon arguments
+#[derive(Clone, Debug)]
+pub struct KeyServe {
+    /// The path of the socket to listen on
+    pub socket: PathBuf,
+}
+
 /// Wallet address lookup arguments
 #[derive(Clone, Debug)]
 pub struct AddressOrAliasFind {
@@ -2216,6 +2481,9 @@ pub struct EthereumBridgePool<C: NamadaTypes = SdkTypes> {
     pub fee_payer: Option<C::Address>,
     /// The token in which the gas is being paid
     pub fee_token: C::Address,
+    /// Pay at least the recommended gas fee, derived from the
+    /// fees paid by the transfers currently in the Bridge pool.
+    pub auto_fee: bool,
     /// Path to the tx WASM code file
     pub code_path: PathBuf,
 }
@@ -2281,6 +2549,12 @@ impl<C: NamadaTypes> EthereumBridgePool<C> {
         Self { fee_token, ..self }
     }
 
+    /// Pay at least the recommended gas fee, derived from the
+    /// fees paid by the transfers currently in the Bridge pool.
+    pub fn auto_fee(self, auto_fee: bool) -> Self {
+        Self { auto_fee, ..self }
+    }
+
     /// Path to the tx WASM code file
     pub fn code_path(self, code_path: PathBuf) -> Self {
         Self { code_path, ..self }
@@ -2298,6 +2572,63 @@ impl EthereumBridgePool {
     }
 }
 
+/// Bridge pool fee bump arguments.
+#[derive(Debug, Clone)]
+pub struct BumpBridgePoolFee<C: NamadaTypes = SdkTypes> {
+    /// The args for building a tx to the bridge pool
+    pub tx: Tx<C>,
+    /// The keccak hash of the transfer to replace
+    pub transfer: KeccakHash,
+    /// The new amount of gas fees. If unset, the recommended
+    /// gas fee of the Bridge pool is paid.
+    pub fee_amount: Option<InputAmount>,
+    /// Path to the tx WASM code file
+    pub code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for BumpBridgePoolFee<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        BumpBridgePoolFee {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> BumpBridgePoolFee<C> {
+    /// The keccak hash of the transfer to replace
+    pub fn transfer(self, transfer: KeccakHash) -> Self {
+        Self { transfer, ..self }
+    }
+
+    /// The new amount of gas fees
+    pub fn fee_amount(self, fee_amount: InputAmount) -> Self {
+        Self {
+            fee_amount: Some(fee_amount),
+            ..self
+        }
+    }
+
+    /// Path to the tx WASM code file
+    pub fn code_path(self, code_path: PathBuf) -> Self {
+        Self { code_path, ..self }
+    }
+}
+
+impl BumpBridgePoolFee {
+    /// Build a transaction from this builder
+    pub async fn build(
+        self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(crate::proto::Tx, SigningTxData, Option<Epoch>)>
+    {
+        bridge_pool::build_bump_bridge_pool_fee_tx(context, self).await
+    }
+}
+
 /// Bridge pool proof arguments.
 #[derive(Debug, Clone)]
 pub struct BridgePoolProof<C: NamadaTypes = SdkTypes> {
@@ -2345,6 +2676,49 @@ pub struct RelayBridgePoolProof<C: NamadaTypes = SdkTypes> {
     pub safe_mode: bool,
 }
 
+/// Arguments to the Ethereum Bridge pool relayer daemon.
+#[derive(Debug, Clone)]
+pub struct BridgePoolRelayDaemon<C: NamadaTypes = SdkTypes> {
+    /// The batch recommendation parameters.
+    pub recommend: RecommendBatch<C>,
+    /// The minimum net profit, in gwei, that a batch must yield
+    /// to be relayed. Ignored if a net gas amount the relayer is
+    /// willing to pay has been provided.
+    pub min_profit: Option<u64>,
+    /// Only relay transfers of these assets. If empty, transfers
+    /// of any asset may be relayed.
+    pub allowed_tokens: Vec<EthAddress>,
+    /// The Namada address for receiving fees for relaying
+    pub relayer: Address,
+    /// Path to the file where the progress of the daemon
+    /// is persisted across restarts.
+    pub state_file: PathBuf,
+    /// Also relay validator set updates, in the same process.
+    pub relay_valset: bool,
+    /// The number of confirmations to wait for on Ethereum
+    pub confirmations: u64,
+    /// The Ethereum RPC endpoint.
+    pub eth_rpc_endpoint: C::EthereumAddress,
+    /// The price of Ethereum gas, during the
+    /// relay call.
+    pub gas_price: Option<u64>,
+    /// The address of the Ethereum wallet to pay the gas fees.
+    /// If unset, the default wallet is used.
+    pub eth_addr: Option<EthAddress>,
+    /// Synchronize with the network, or exit immediately,
+    /// if the Ethereum node has fallen behind.
+    pub sync: bool,
+    /// The amount of time to sleep between failed
+    /// daemon mode relays.
+    pub retry_dur: Option<StdDuration>,
+    /// The amount of time to sleep between successful
+    /// daemon mode relays.
+    pub success_dur: Option<StdDuration>,
+    /// Safe mode overrides keyboard interrupt signals, to ensure
+    /// Ethereum transfers aren't canceled midway through.
+    pub safe_mode: bool,
+}
+
 /// Bridge validator set arguments.
 #[derive(Debug, Clone)]
 pub struct BridgeValidatorSet<C: NamadaTypes = SdkTypes> {
@@ -2409,6 +2783,45 @@ pub struct ValidatorSetUpdateRelay<C: NamadaTypes = SdkTypes> {
     pub safe_mode: bool,
 }
 
+/// Namada state proof arguments.
+#[derive(Debug, Clone)]
+pub struct StateProof<C: NamadaTypes = SdkTypes> {
+    /// The query parameters.
+    pub query: Query<C>,
+    /// The storage key whose value should be proven.
+    pub key: storage::Key,
+}
+
+/// Namada state proof relayer arguments.
+#[derive(Debug, Clone)]
+pub struct RelayStateProof<C: NamadaTypes = SdkTypes> {
+    /// The query parameters.
+    pub query: Query<C>,
+    /// The storage key whose value should be proven.
+    pub key: storage::Key,
+    /// The address of the Ethereum contract verifying the proof.
+    pub verifier: EthAddress,
+    /// The number of block confirmations on Ethereum.
+    pub confirmations: u64,
+    /// The Ethereum RPC endpoint.
+    pub eth_rpc_endpoint: C::EthereumAddress,
+    /// The Ethereum gas that can be spent during
+    /// the relay call.
+    pub gas: Option<u64>,
+    /// The price of Ethereum gas, during the
+    /// relay call.
+    pub gas_price: Option<u64>,
+    /// The address of the Ethereum wallet to pay the gas fees.
+    /// If unset, the default wallet is used.
+    pub eth_addr: Option<EthAddress>,
+    /// Synchronize with the network, or exit immediately,
+    /// if the Ethereum node has fallen behind.
+    pub sync: bool,
+    /// Safe mode overrides keyboard interrupt signals, to ensure
+    /// Ethereum transactions aren't canceled midway through.
+    pub safe_mode: bool,
+}
+
 /// IBC shielded transfer generation arguments
 #[derive(Clone, Debug)]
 pub struct GenIbcShieldedTransafer<C: NamadaTypes = SdkTypes> {
@@ -0,0 +1,515 @@
+//! Partially signed transaction bundles, to build, sign and submit a
+//! transaction on separate machines.
+//!
+//! A [`TxBundle`] is created online from a dumped transaction, with the
+//! signing data of its owner: the public keys of the account and its
+//! threshold, and the fee payer. It can then be signed without access to a
+//! node, by every holder of some of the keys, and the bundles signed
+//! separately can be merged. Once the threshold is met, the bundle is sealed
+//! with the signature of the fee payer and the transaction can be submitted.
+
+use std::collections::BTreeMap;
+
+use namada_core::proto::{Signature, SignatureIndex, Signer, Tx};
+use namada_core::types::account::AccountPublicKeysMap;
+use namada_core::types::address::{masp_tx_key, Address};
+use namada_core::types::key::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::signing::SigningTxData;
+use crate::wallet::{Wallet, WalletIo};
+
+/// The version of the JSON format of transaction bundles
+pub const TX_BUNDLE_VERSION: u32 = 1;
+
+/// Errors of transaction bundles
+#[derive(Error, Debug)]
+pub enum BundleError {
+    /// Bundle decoding error
+    #[error("Failed decoding the transaction bundle: {0}")]
+    Decode(String),
+    /// Bundle of an unknown format version
+    #[error("Unsupported transaction bundle format version {0}")]
+    UnsupportedVersion(u32),
+    /// The bundles to merge are for different transactions
+    #[error("The transaction bundles are for different transactions")]
+    Mismatch,
+    /// A signature is not from a key of the account or is invalid
+    #[error("Invalid signature from {0}")]
+    InvalidSignature(common::PublicKey),
+    /// Not enough signatures were collected
+    #[error("The bundle has {0} signatures out of the {1} required")]
+    ThresholdNotMet(usize, u8),
+    /// The bundle is already sealed
+    #[error("The bundle is already sealed with the fee payer signature")]
+    Sealed,
+    /// The key does not belong to the fee payer
+    #[error("The fee payer of the transaction is {0}")]
+    WrongFeePayer(common::PublicKey),
+    /// The transaction has no wrapper header to pay its fees
+    #[error("The transaction is not wrapped for fee payment")]
+    NotWrapped,
+    /// A signature is indexed for another account or key
+    #[error("Signature from {0} has a wrong account index")]
+    WrongSignatureIndex(common::PublicKey),
+}
+
+/// A transaction with the signatures collected for it so far
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TxBundle {
+    /// The format version of the bundle
+    version: u32,
+    /// The hex encoded transaction, in the format of dumped transactions
+    tx: String,
+    /// The owner of the transaction, if any
+    pub owner: Option<Address>,
+    /// The public keys of the account, ordered by their index
+    pub public_keys: Vec<common::PublicKey>,
+    /// The number of signatures required by the account
+    pub threshold: u8,
+    /// The public key of the fee payer
+    pub fee_payer: common::PublicKey,
+    /// The signatures collected for the transaction
+    pub signatures: Vec<SignatureIndex>,
+    /// Whether the transaction is signed by the fee payer, after which no
+    /// more signatures can be added
+    pub sealed: bool,
+}
+
+impl TxBundle {
+    /// Create a bundle of the given unsigned transaction. Its fee payer is
+    /// the one of its wrapper header, which must be the one of the signing
+    /// data.
+    pub fn new(
+        tx: &Tx,
+        signing_data: &SigningTxData,
+    ) -> Result<Self, BundleError> {
+        let fee_payer = wrapper_fee_payer(tx)?;
+        if fee_payer != signing_data.fee_payer {
+            return Err(BundleError::WrongFeePayer(fee_payer));
+        }
+        let public_keys = signing_data
+            .account_public_keys_map
+            .as_ref()
+            .map(|map| {
+                let idx_to_pk: BTreeMap<_, _> = map.idx_to_pk.iter().collect();
+                idx_to_pk.into_values().cloned().collect()
+            })
+            .unwrap_or_default();
+        Ok(Self {
+            version: TX_BUNDLE_VERSION,
+            tx: tx.serialize(),
+            owner: signing_data.owner.clone(),
+            public_keys,
+            threshold: signing_data.threshold,
+            fee_payer,
+            signatures: vec![],
+            sealed: false,
+        })
+    }
+
+    /// Decode a bundle from its JSON format
+    pub fn from_json(json: &str) -> Result<Self, BundleError> {
+        let bundle: Self = serde_json::from_str(json)
+            .map_err(|err| BundleError::Decode(err.to_string()))?;
+        if bundle.version != TX_BUNDLE_VERSION {
+            return Err(BundleError::UnsupportedVersion(bundle.version));
+        }
+        // Check that the transaction can be decoded and that its fee payer
+        // is the one of the bundle
+        let fee_payer = wrapper_fee_payer(&bundle.tx()?)?;
+        if fee_payer != bundle.fee_payer {
+            return Err(BundleError::WrongFeePayer(fee_payer));
+        }
+        Ok(bundle)
+    }
+
+    /// Encode the bundle into its JSON format
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self)
+            .expect("Serializing of transaction bundle shouldn't fail")
+    }
+
+    /// The transaction of the bundle. Once sealed, it holds all the
+    /// signatures and is ready for submission.
+    pub fn tx(&self) -> Result<Tx, BundleError> {
+        let json = serde_json::to_vec(&self.tx)
+            .expect("Serializing of a string shouldn't fail");
+        Tx::deserialize(&json)
+            .map_err(|err| BundleError::Decode(err.to_string()))
+    }
+
+    /// The public keys of the account indexed for signing
+    pub fn account_public_keys_map(&self) -> AccountPublicKeysMap {
+        self.public_keys.iter().cloned().collect()
+    }
+
+    /// The public keys of the account that did not sign yet
+    pub fn missing_signers(&self) -> Vec<&common::PublicKey> {
+        self.public_keys
+            .iter()
+            .filter(|pk| !self.has_signed(pk))
+            .collect()
+    }
+
+    /// Check if the given key already signed the transaction
+    pub fn has_signed(&self, public_key: &common::PublicKey) -> bool {
+        self.signatures.iter().any(|sig| &sig.pubkey == public_key)
+    }
+
+    /// Check if enough signatures were collected to submit the transaction
+    pub fn is_threshold_met(&self) -> bool {
+        self.signatures.len() >= self.threshold as usize
+    }
+
+    /// Sign the transaction with the given keys of the account. The keys that
+    /// are not of the account or that already signed are ignored. Returns the
+    /// public keys of the new signatures.
+    pub fn sign(
+        &mut self,
+        secret_keys: &[common::SecretKey],
+    ) -> Result<Vec<common::PublicKey>, BundleError> {
+        if self.sealed {
+            return Err(BundleError::Sealed);
+        }
+        let secret_keys = secret_keys
+            .iter()
+            .filter(|sk| !self.has_signed(&sk.ref_to()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let tx = self.tx()?;
+        let signatures = if self.owner.is_some() {
+            tx.compute_section_signature(
+                &secret_keys,
+                &self.account_public_keys_map(),
+                self.owner.clone(),
+            )
+        } else {
+            // Without an owner, the signatures are not indexed by account, so
+            // each key signs on its own
+            secret_keys
+                .iter()
+                .filter(|sk| self.public_keys.contains(&sk.ref_to()))
+                .flat_map(|sk| {
+                    tx.compute_section_signature(
+                        std::slice::from_ref(sk),
+                        &std::iter::once(sk.ref_to()).collect(),
+                        None,
+                    )
+                })
+                .collect()
+        };
+        let signers = signatures.iter().map(|sig| sig.pubkey.clone()).collect();
+        self.signatures.extend(signatures);
+        Ok(signers)
+    }
+
+    /// Sign the transaction with all the keys of the account held by the
+    /// wallet, and seal it if the threshold is met and the wallet holds the
+    /// key of the fee payer. Returns the public keys of the new signatures.
+    pub fn sign_with_wallet<U: WalletIo>(
+        &mut self,
+        wallet: &mut Wallet<U>,
+    ) -> Result<Vec<common::PublicKey>, BundleError> {
+        let secret_keys = self
+            .missing_signers()
+            .into_iter()
+            .filter_map(|pk| find_key(wallet, pk))
+            .collect::<Vec<_>>();
+        let signers = self.sign(&secret_keys)?;
+        if self.is_threshold_met() {
+            if let Some(fee_payer_key) = find_key(wallet, &self.fee_payer) {
+                self.seal(fee_payer_key)?;
+            }
+        }
+        Ok(signers)
+    }
+
+    /// Add the signatures of another bundle of the same transaction
+    pub fn merge(&mut self, other: TxBundle) -> Result<(), BundleError> {
+        if self.sealed || other.sealed {
+            return Err(BundleError::Sealed);
+        }
+        if self.tx()?.sechashes() != other.tx()?.sechashes() {
+            return Err(BundleError::Mismatch);
+        }
+        let signing_hash = self.signing_hash()?;
+        for signature in other.signatures {
+            if self.has_signed(&signature.pubkey) {
+                continue;
+            }
+            if !self.is_index_valid(&signature) {
+                return Err(BundleError::WrongSignatureIndex(signature.pubkey));
+            }
+            if !self.public_keys.contains(&signature.pubkey)
+                || common::SigScheme::verify_signature(
+                    &signature.pubkey,
+                    &signing_hash,
+                    &signature.signature,
+                )
+                .is_err()
+            {
+                return Err(BundleError::InvalidSignature(signature.pubkey));
+            }
+            self.signatures.push(signature);
+        }
+        Ok(())
+    }
+
+    /// Add the collected signatures to the transaction and sign it with the
+    /// key of the fee payer
+    pub fn seal(
+        &mut self,
+        fee_payer_key: common::SecretKey,
+    ) -> Result<(), BundleError> {
+        if self.sealed {
+            return Err(BundleError::Sealed);
+        }
+        if !self.is_threshold_met() {
+            return Err(BundleError::ThresholdNotMet(
+                self.signatures.len(),
+                self.threshold,
+            ));
+        }
+        if fee_payer_key.ref_to() != self.fee_payer {
+            return Err(BundleError::WrongFeePayer(self.fee_payer.clone()));
+        }
+        let mut tx = self.tx()?;
+        if !self.signatures.is_empty() {
+            tx.add_signatures(self.signatures.clone());
+        }
+        tx.sign_wrapper(fee_payer_key);
+        self.tx = tx.serialize();
+        self.sealed = true;
+        Ok(())
+    }
+
+    /// Check that the given signature is indexed like the signatures made by
+    /// this bundle: by the position of its key in the account of the owner,
+    /// if any
+    fn is_index_valid(&self, signature: &SignatureIndex) -> bool {
+        match (&signature.index, &self.owner) {
+            (Some((address, index)), Some(owner)) => {
+                address == owner
+                    && self.public_keys.get(*index as usize)
+                        == Some(&signature.pubkey)
+            }
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// The hash that the account keys sign
+    fn signing_hash(
+        &self,
+    ) -> Result<namada_core::types::hash::Hash, BundleError> {
+        let section = Signature {
+            targets: vec![self.tx()?.raw_header_hash()],
+            signer: Signer::PubKeys(vec![]),
+            signatures: BTreeMap::new(),
+        };
+        Ok(section.get_raw_hash())
+    }
+}
+
+/// The fee payer in the wrapper header of the given transaction
+fn wrapper_fee_payer(tx: &Tx) -> Result<common::PublicKey, BundleError> {
+    tx.header()
+        .wrapper()
+        .map(|wrapper| wrapper.pk)
+        .ok_or(BundleError::NotWrapped)
+}
+
+/// Find the secret key of the given public key in the wallet. The password of
+/// encrypted keys is prompted for.
+fn find_key<U: WalletIo>(
+    wallet: &mut Wallet<U>,
+    public_key: &common::PublicKey,
+) -> Option<common::SecretKey> {
+    if *public_key == masp_tx_key().ref_to() {
+        Some(masp_tx_key())
+    } else {
+        wallet.find_key_by_pk(public_key, None).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::proto::{Code, Data};
+    use namada_core::types::address::nam;
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada_core::types::key::testing::gen_keypair;
+    use namada_core::types::storage::Epoch;
+    use namada_core::types::token::Amount;
+    use namada_core::types::transaction::{Fee, GasLimit, TxType};
+
+    use super::*;
+
+    fn tx(fee_payer: common::PublicKey) -> Tx {
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.set_code(Code::new(vec![], None));
+        tx.set_data(Data::new(vec![1, 2, 3]));
+        tx.add_wrapper(
+            Fee {
+                amount_per_gas_unit: Amount::zero(),
+                token: nam(),
+            },
+            fee_payer,
+            Epoch(0),
+            GasLimit::from(1),
+            None,
+        );
+        tx
+    }
+
+    fn signing_data(
+        owner: Option<Address>,
+        public_keys: Vec<common::PublicKey>,
+        threshold: u8,
+        fee_payer: common::PublicKey,
+    ) -> SigningTxData {
+        SigningTxData {
+            owner,
+            public_keys: public_keys.clone(),
+            threshold,
+            account_public_keys_map: Some(public_keys.into_iter().collect()),
+            fee_payer,
+        }
+    }
+
+    fn bundle(
+        public_keys: Vec<common::PublicKey>,
+        threshold: u8,
+        fee_payer: common::PublicKey,
+    ) -> TxBundle {
+        TxBundle::new(
+            &tx(fee_payer.clone()),
+            &signing_data(None, public_keys, threshold, fee_payer),
+        )
+        .unwrap()
+    }
+
+    /// Test that signatures collected separately are merged, and that the
+    /// bundle can only be sealed once the threshold is met.
+    #[test]
+    fn test_bundle_merge_and_seal() {
+        let keys = [
+            gen_keypair::<common::SigScheme>(),
+            gen_keypair::<common::SigScheme>(),
+            gen_keypair::<common::SigScheme>(),
+        ];
+        let fee_payer = gen_keypair::<common::SigScheme>();
+        let public_keys = keys.iter().map(RefTo::ref_to).collect::<Vec<_>>();
+        let mut first = bundle(public_keys.clone(), 2, fee_payer.ref_to());
+        let mut second = TxBundle::from_json(&first.to_json()).unwrap();
+
+        assert_eq!(
+            first.sign(&[keys[0].clone()]).unwrap(),
+            vec![public_keys[0].clone()]
+        );
+        assert!(matches!(
+            first.seal(fee_payer.clone()),
+            Err(BundleError::ThresholdNotMet(1, 2))
+        ));
+
+        second.sign(&[keys[1].clone()]).unwrap();
+        first.merge(second).unwrap();
+        assert!(first.is_threshold_met());
+        assert_eq!(first.missing_signers(), vec![&public_keys[2]]);
+
+        assert!(matches!(
+            first.seal(keys[2].clone()),
+            Err(BundleError::WrongFeePayer(_))
+        ));
+        first.seal(fee_payer).unwrap();
+        assert!(first.sealed);
+        assert!(matches!(
+            first.sign(&[keys[2].clone()]),
+            Err(BundleError::Sealed)
+        ));
+    }
+
+    /// Test that signatures of other transactions are rejected on merge.
+    #[test]
+    fn test_bundle_merge_rejects_foreign_signatures() {
+        let key = gen_keypair::<common::SigScheme>();
+        let fee_payer = gen_keypair::<common::SigScheme>().ref_to();
+        let mut bundle = bundle(vec![key.ref_to()], 1, fee_payer);
+        let mut forged = bundle.clone();
+        forged.sign(&[key.clone()]).unwrap();
+        forged.signatures[0].signature =
+            common::SigScheme::sign(&key, b"another transaction");
+        assert!(matches!(
+            bundle.merge(forged),
+            Err(BundleError::InvalidSignature(_))
+        ));
+    }
+
+    /// Test that the fee payer of a bundle is the one of the wrapper header
+    /// of its transaction.
+    #[test]
+    fn test_bundle_fee_payer_of_wrapper() {
+        let key = gen_keypair::<common::SigScheme>().ref_to();
+        let fee_payer = gen_keypair::<common::SigScheme>().ref_to();
+        let other = gen_keypair::<common::SigScheme>().ref_to();
+        let data = signing_data(None, vec![key.clone()], 1, other.clone());
+        assert!(matches!(
+            TxBundle::new(&tx(fee_payer.clone()), &data),
+            Err(BundleError::WrongFeePayer(pk)) if pk == fee_payer
+        ));
+
+        let mut unwrapped = Tx::from_type(TxType::Raw);
+        unwrapped.set_data(Data::new(vec![1, 2, 3]));
+        assert!(matches!(
+            TxBundle::new(&unwrapped, &data),
+            Err(BundleError::NotWrapped)
+        ));
+
+        let mut tampered = bundle(vec![key], 1, fee_payer);
+        tampered.fee_payer = other;
+        assert!(matches!(
+            TxBundle::from_json(&tampered.to_json()),
+            Err(BundleError::WrongFeePayer(_))
+        ));
+    }
+
+    /// Test that the account indexes of merged signatures must match the
+    /// keys that made them.
+    #[test]
+    fn test_bundle_merge_checks_indexes() {
+        let keys = [
+            gen_keypair::<common::SigScheme>(),
+            gen_keypair::<common::SigScheme>(),
+        ];
+        let public_keys = keys.iter().map(RefTo::ref_to).collect::<Vec<_>>();
+        let owner = established_address_1();
+        let fee_payer = gen_keypair::<common::SigScheme>().ref_to();
+        let mut bundle = TxBundle::new(
+            &tx(fee_payer.clone()),
+            &signing_data(Some(owner.clone()), public_keys, 2, fee_payer),
+        )
+        .unwrap();
+
+        let mut signed = bundle.clone();
+        signed.sign(&[keys[1].clone()]).unwrap();
+        assert_eq!(signed.signatures[0].index, Some((owner, 1)));
+        let mut wrong_index = signed.clone();
+        wrong_index.signatures[0].index.as_mut().unwrap().1 = 0;
+        assert!(matches!(
+            bundle.merge(wrong_index),
+            Err(BundleError::WrongSignatureIndex(_))
+        ));
+        let mut wrong_owner = signed.clone();
+        wrong_owner.signatures[0].index.as_mut().unwrap().0 =
+            established_address_2();
+        assert!(matches!(
+            bundle.merge(wrong_owner),
+            Err(BundleError::WrongSignatureIndex(_))
+        ));
+        bundle.merge(signed).unwrap();
+        assert_eq!(bundle.signatures.len(), 1);
+    }
+}
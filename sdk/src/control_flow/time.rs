@@ -328,6 +328,17 @@ pub async fn sleep(dur: Duration) {
     internal_sleep(dur).await;
 }
 
+/// Run a future until the given deadline.
+#[inline]
+pub async fn timeout_at<F: Future>(
+    deadline: Instant,
+    future: F,
+) -> Result<F::Output, Error> {
+    internal_timeout_at(deadline, future)
+        .await
+        .map_err(|_| Error::Elapsed)
+}
+
 #[cfg(target_family = "wasm")]
 #[allow(missing_docs)]
 mod internal {
@@ -0,0 +1,313 @@
+//! Discovery of the accounts derived from a mnemonic code.
+//!
+//! Following BIP-44 account discovery, the accounts of a seed are derived one
+//! index after the other, and the scan of a kind of account stops once
+//! `gap_limit` consecutive indexes are found unused on chain. A transparent
+//! account is used if its public key is revealed, if it has bonds or if it
+//! holds any known token. A shielded account is used if any note was ever sent
+//! to it.
+
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::future::Future;
+use std::ops::Range;
+
+use bip39::Seed;
+use masp_primitives::zip32::ExtendedFullViewingKey;
+use namada_core::types::address::Address;
+use namada_core::types::key::*;
+use zeroize::Zeroizing;
+
+use crate::error::Error;
+use crate::masp::{
+    trial_decrypt, LedgerTxSource, ShieldedTxSource, SYNC_CHECKPOINT_INTERVAL,
+};
+use crate::wallet::store::{derive_hd_secret_key, derive_hd_spending_key};
+use crate::wallet::{AddressVpType, DerivationPath};
+use crate::{rpc, Namada};
+
+/// The default number of consecutive unused accounts after which the
+/// discovery stops
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+/// A kind of accounts derived from a seed
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AccountKind {
+    /// Transparent accounts of the given signature scheme, on BIP-44 paths
+    Transparent(SchemeType),
+    /// Shielded accounts, on ZIP-32 paths
+    Shielded,
+}
+
+impl AccountKind {
+    /// The derivation path of the account with the given index
+    pub fn derivation_path(&self, index: u32) -> DerivationPath {
+        match self {
+            Self::Transparent(scheme) => {
+                DerivationPath::bip44_account(*scheme, index)
+            }
+            Self::Shielded => DerivationPath::zip32_account(index),
+        }
+    }
+}
+
+impl Display for AccountKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Transparent(SchemeType::Ed25519) => write!(f, "ed25519"),
+            Self::Transparent(SchemeType::Secp256k1) => write!(f, "secp256k1"),
+            Self::Transparent(SchemeType::Common) => write!(f, "common"),
+            Self::Shielded => write!(f, "shielded"),
+        }
+    }
+}
+
+/// An account of the seed found to be used on chain
+#[derive(Clone, Debug)]
+pub struct DiscoveredAccount {
+    /// The kind of the account
+    pub kind: AccountKind,
+    /// The index of the account
+    pub index: u32,
+    /// The path the key of the account is derived on
+    pub derivation_path: DerivationPath,
+    /// The alias the account was inserted into the wallet with
+    pub alias: String,
+}
+
+/// Discover the used accounts of the given kinds derived from the seed, and
+/// insert their keys into the wallet under the aliases
+/// `{alias_prefix}-{kind}-{index}`. The keys are encrypted with the given
+/// password, if any. The wallet is not saved.
+pub async fn discover_accounts<N: Namada>(
+    context: &N,
+    seed: &Seed,
+    kinds: &[AccountKind],
+    gap_limit: u32,
+    alias_prefix: &str,
+    alias_force: bool,
+    password: Option<Zeroizing<String>>,
+) -> Result<Vec<DiscoveredAccount>, Error> {
+    if gap_limit == 0 {
+        return Err(Error::Other(
+            "The gap limit of account discovery must be positive".to_string(),
+        ));
+    }
+    let mut tokens = context
+        .wallet()
+        .await
+        .get_addresses_with_vp_type(AddressVpType::Token);
+    tokens.insert(context.native_token());
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+
+    let mut discovered = vec![];
+    for kind in kinds {
+        let indexes = match kind {
+            AccountKind::Transparent(scheme) => {
+                scan_accounts(gap_limit, |indexes| {
+                    transparent_usage(context, *scheme, seed, &tokens, indexes)
+                })
+                .await?
+            }
+            AccountKind::Shielded => {
+                scan_accounts(gap_limit, |indexes| {
+                    shielded_usage(context, seed, indexes)
+                })
+                .await?
+            }
+        };
+        let mut wallet = context.wallet_mut().await;
+        for index in indexes {
+            let derivation_path = kind.derivation_path(index);
+            let alias = format!("{}-{}-{}", alias_prefix, kind, index);
+            let alias = match kind {
+                AccountKind::Transparent(scheme) => {
+                    wallet
+                        .derive_store_hd_secret_key(
+                            *scheme,
+                            Some(alias),
+                            alias_force,
+                            seed.clone(),
+                            derivation_path.clone(),
+                            password.clone(),
+                        )
+                        .map_err(|err| Error::Other(err.to_string()))?
+                        .0
+                }
+                AccountKind::Shielded => {
+                    wallet
+                        .derive_store_hd_spending_key(
+                            alias,
+                            alias_force,
+                            seed,
+                            derivation_path.clone(),
+                            password.clone(),
+                        )
+                        .ok_or_else(|| {
+                            Error::Other(
+                                "Action cancelled, no changes persisted."
+                                    .to_string(),
+                            )
+                        })?
+                        .0
+                }
+            };
+            discovered.push(DiscoveredAccount {
+                kind: *kind,
+                index,
+                derivation_path,
+                alias,
+            });
+        }
+    }
+    Ok(discovered)
+}
+
+/// Scan the account indexes from zero until `gap_limit` consecutive ones are
+/// unused, and return the used ones. The usage of the accounts is queried in
+/// ranges of indexes, which are never past the end of the scan.
+async fn scan_accounts<F, Fut>(
+    gap_limit: u32,
+    mut usage: F,
+) -> Result<Vec<u32>, Error>
+where
+    F: FnMut(Range<u32>) -> Fut,
+    Fut: Future<Output = Result<Vec<bool>, Error>>,
+{
+    let mut used = vec![];
+    let mut start = 0u32;
+    let mut gap = 0;
+    while gap < gap_limit {
+        let end = start.checked_add(gap_limit - gap).ok_or_else(|| {
+            Error::Other("Ran out of account indexes".to_string())
+        })?;
+        let range_usage = usage(start..end).await?;
+        for (index, is_used) in (start..end).zip(range_usage) {
+            if is_used {
+                used.push(index);
+                gap = 0;
+            } else {
+                gap += 1;
+            }
+        }
+        start = end;
+    }
+    Ok(used)
+}
+
+/// Check which of the transparent accounts with the given indexes are used
+async fn transparent_usage<N: Namada>(
+    context: &N,
+    scheme: SchemeType,
+    seed: &Seed,
+    tokens: &[Address],
+    indexes: Range<u32>,
+) -> Result<Vec<bool>, Error> {
+    let mut usage = vec![];
+    for index in indexes {
+        let sk = derive_hd_secret_key(
+            scheme,
+            seed.as_bytes(),
+            DerivationPath::bip44_account(scheme, index),
+        );
+        let address = Address::from(&sk.ref_to());
+        usage.push(is_address_used(context, &address, tokens).await?);
+    }
+    Ok(usage)
+}
+
+/// Check if the given transparent address was ever used on chain
+async fn is_address_used<N: Namada>(
+    context: &N,
+    address: &Address,
+    tokens: &[Address],
+) -> Result<bool, Error> {
+    let client = context.client();
+    if rpc::is_public_key_revealed(client, address).await?
+        || rpc::has_bonds(client, address).await?
+    {
+        return Ok(true);
+    }
+    for token in tokens {
+        if !rpc::get_token_balance(client, token, address)
+            .await?
+            .is_zero()
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Check which of the shielded accounts with the given indexes received
+/// notes. Every shielded output accepted by the ledger is trial-decrypted
+/// with the viewing keys of the accounts, without touching the shielded
+/// context: only the keys of the used accounts are kept, and their notes are
+/// fetched like those of any other new key.
+async fn shielded_usage<N: Namada>(
+    context: &N,
+    seed: &Seed,
+    indexes: Range<u32>,
+) -> Result<Vec<bool>, Error> {
+    let vks = indexes
+        .map(|index| {
+            let spend_key = derive_hd_spending_key(
+                seed.as_bytes(),
+                DerivationPath::zip32_account(index),
+            );
+            ExtendedFullViewingKey::from(
+                &masp_primitives::zip32::ExtendedSpendingKey::from(spend_key),
+            )
+            .fvk
+            .vk
+        })
+        .collect::<Vec<_>>();
+    let source = LedgerTxSource(context.client());
+    let head_txidx = source.head_txidx().await?;
+    let mut used = BTreeSet::new();
+    let mut txidx = 0;
+    while txidx < head_txidx {
+        let limit = SYNC_CHECKPOINT_INTERVAL.min(head_txidx - txidx);
+        let txs = source
+            .compact_blocks(txidx, limit)
+            .await?
+            .into_iter()
+            .flat_map(|block| block.txs)
+            .collect::<Vec<_>>();
+        if txs.is_empty() {
+            // The source is behind its head
+            break;
+        }
+        txidx += txs.len() as u64;
+        used.extend(txs.iter().flat_map(|tx| {
+            trial_decrypt(&tx.outputs, &vks)
+                .into_values()
+                .map(|note| note.vk)
+        }));
+    }
+    Ok(vks.iter().map(|vk| used.contains(vk)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that the scan stops after the gap limit and only queries the
+    /// indexes it needs.
+    #[tokio::test]
+    async fn test_scan_accounts_gap_limit() {
+        let used_indexes = [0, 2, 5, 9];
+        let mut queried = vec![];
+        let used = scan_accounts(3, |indexes| {
+            queried.push(indexes.clone());
+            async move {
+                Ok(indexes.map(|index| used_indexes.contains(&index)).collect())
+            }
+        })
+        .await
+        .unwrap();
+        // Index 9 is past the three unused indexes following 5
+        assert_eq!(used, vec![0, 2, 5]);
+        assert_eq!(queried, vec![0..3, 3..6, 6..9]);
+    }
+}
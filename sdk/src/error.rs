@@ -4,6 +4,7 @@ use namada_core::proto::Tx;
 use namada_core::types::address::Address;
 use namada_core::types::dec::Dec;
 use namada_core::types::ethereum_events::EthAddress;
+use namada_core::types::keccak::KeccakHash;
 use namada_core::types::storage;
 use namada_core::types::storage::Epoch;
 use prost::EncodeError;
@@ -75,6 +76,9 @@ pub enum EventError {
     /// Missing value in attributes.
     #[error("Attributes missing value: {0}")]
     MissingValue(String),
+    /// Invalid value in attributes.
+    #[error("Attributes invalid value of {0}: {1}")]
+    InvalidValue(String, String),
 }
 
 /// Errors that deal with querying some kind of data
@@ -364,6 +368,9 @@ pub enum EthereumBridgeError {
     /// Error generating Bridge pool proof.
     #[error("Failed to generate Bridge pool proof: {0}")]
     GenBridgePoolProof(String),
+    /// Error generating a proof of Namada's state.
+    #[error("Failed to generate a proof of Namada's state: {0}")]
+    GenStateProof(String),
     /// Error retrieving contract address.
     #[error("Failed to retrieve contract address: {0}")]
     RetrieveContract(String),
@@ -385,6 +392,23 @@ pub enum EthereumBridgeError {
     /// Transfer already in pool error.
     #[error("An identical transfer is already present in the Bridge pool")]
     TransferAlreadyInPool,
+    /// Error reading or writing the state of the relayer daemon.
+    #[error("Failed to access the relayer state: {0}")]
+    RelayerState(String),
+    /// Transfer not in pool error.
+    #[error("No transfer with hash {0} is present in the Bridge pool")]
+    TransferNotInPool(KeccakHash),
+    /// Gas fee not increased error.
+    #[error(
+        "The new gas fee {new_fee} must be higher than the current gas fee \
+         {current_fee} of the transfer"
+    )]
+    FeeNotIncreased {
+        /// The gas fee currently paid by the transfer.
+        current_fee: String,
+        /// The new gas fee of the transfer.
+        new_fee: String,
+    },
 }
 
 /// Checks if the given error is an invalid viewing key
@@ -3,42 +3,57 @@
 use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::future::Future;
+use std::ops::ControlFlow;
+use std::path::Path;
 use std::sync::Arc;
+use std::task::Poll;
 
 use borsh_ext::BorshSerializeExt;
 use ethbridge_bridge_contract::Bridge;
 use ethers::providers::Middleware;
-use futures::future::FutureExt;
+use futures::future::{self, Either, FutureExt};
 use namada_core::ledger::eth_bridge::storage::bridge_pool::get_pending_key;
 use namada_core::ledger::eth_bridge::storage::wrapped_erc20s;
 use namada_core::types::address::{Address, InternalAddress};
 use namada_core::types::eth_abi::Encode;
 use namada_core::types::eth_bridge_pool::{
-    GasFee, PendingTransfer, TransferToEthereum, TransferToEthereumKind,
+    GasFee, GasFeeBump, PendingTransfer, TransferToEthereum,
+    TransferToEthereumKind,
 };
-use namada_core::types::ethereum_events::EthAddress;
+use namada_core::types::ethereum_events::{EthAddress, Uint as EthUint};
 use namada_core::types::keccak::KeccakHash;
 use namada_core::types::storage::Epoch;
 use namada_core::types::token::{balance_key, Amount, DenominatedAmount};
+use namada_core::types::uint::I256;
 use namada_core::types::voting_power::FractionalVotingPower;
 use owo_colors::OwoColorize;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use super::{block_on_eth_sync, eth_sync_or_exit, BlockOnEthSync};
+use super::{
+    block_on_eth_sync, eth_sync_or, eth_sync_or_exit, validator_set,
+    BlockOnEthSync,
+};
 use crate::control_flow::install_shutdown_signal;
-use crate::control_flow::time::{Duration, Instant};
+use crate::control_flow::time::{
+    self, Duration, Instant, LinearBackoff, Sleep,
+};
 use crate::error::{
     EncodingError, Error, EthereumBridgeError, QueryError, TxError,
 };
 use crate::eth_bridge::ethers::abi::AbiDecode;
+use crate::eth_bridge::ethers::core::types::TransactionReceipt;
 use crate::internal_macros::echo_error;
 use crate::io::Io;
 use crate::proto::Tx;
 use crate::queries::{
-    Client, GenBridgePoolProofReq, GenBridgePoolProofRsp, TransferToErcArgs,
-    RPC,
+    BridgePoolFeeStats, Client, GenBridgePoolProofReq, GenBridgePoolProofRsp,
+    TransferToErcArgs, RPC,
+};
+use crate::rpc::{
+    denominate_amount, format_denominated_amount, query_storage_value,
+    query_wasm_code_hash, validate_amount,
 };
-use crate::rpc::{query_storage_value, query_wasm_code_hash, validate_amount};
 use crate::signing::aux_signing_data;
 use crate::tx::prepare_tx;
 use crate::{
@@ -59,9 +74,15 @@ pub async fn build_bridge_pool_tx(
         fee_amount,
         fee_payer,
         fee_token,
+        auto_fee,
         code_path,
     }: args::EthereumBridgePool,
 ) -> Result<(Tx, SigningTxData, Option<Epoch>), Error> {
+    let fee_amount = if auto_fee {
+        auto_fee_amount(context, &fee_token, fee_amount, tx_args.force).await?
+    } else {
+        fee_amount
+    };
     let sender_ = sender.clone();
     let (transfer, tx_code_hash, signing_data) = futures::try_join!(
         validate_bridge_pool_tx(
@@ -99,18 +120,168 @@ pub async fn build_bridge_pool_tx(
     )
     .add_data(transfer);
 
-    let epoch = prepare_tx(
-        context,
-        &tx_args,
-        &mut tx,
-        signing_data.fee_payer.clone(),
-        None,
+    let epoch =
+        prepare_tx(context, &tx_args, &mut tx, &signing_data, None).await?;
+
+    Ok((tx, signing_data, epoch))
+}
+
+/// Craft a transaction that replaces a transfer in the Ethereum bridge
+/// pool with an identical transfer paying a higher gas fee.
+pub async fn build_bump_bridge_pool_fee_tx(
+    context: &impl Namada,
+    args::BumpBridgePoolFee {
+        tx: tx_args,
+        transfer: transfer_hash,
+        fee_amount,
+        code_path,
+    }: args::BumpBridgePoolFee,
+) -> Result<(Tx, SigningTxData, Option<Epoch>), Error> {
+    let transfer = RPC
+        .shell()
+        .eth_bridge()
+        .read_ethereum_bridge_pool(context.client())
+        .await
+        .map_err(|e| {
+            Error::EthereumBridge(EthereumBridgeError::ReadBridgePool(
+                e.to_string(),
+            ))
+        })?
+        .into_iter()
+        .find(|transfer| transfer.keccak256() == transfer_hash)
+        .ok_or(Error::EthereumBridge(
+            EthereumBridgeError::TransferNotInPool(transfer_hash),
+        ))?;
+
+    let fee_token = &transfer.gas_fee.token;
+    let new_fee = match fee_amount {
+        Some(fee_amount) => {
+            validate_amount(context, fee_amount, fee_token, tx_args.force)
+                .await
+                .map_err(|e| {
+                    Error::Other(format!(
+                        "Failed to validate Bridge pool fee amount: {e}",
+                    ))
+                })?
+                .amount
+        }
+        None => query_fee_stats_of(context, fee_token)
+            .await?
+            .map(|stats| stats.recommended_fee())
+            .unwrap_or_default(),
+    };
+    let bump = GasFeeBump { transfer, new_fee };
+    if bump.fee_increase().is_none() && !tx_args.force {
+        let (current_fee, new_fee) = futures::join!(
+            format_denominated_amount(
+                context.client(),
+                context.io(),
+                fee_token,
+                bump.transfer.gas_fee.amount,
+            ),
+            format_denominated_amount(
+                context.client(),
+                context.io(),
+                fee_token,
+                bump.new_fee,
+            ),
+        );
+        return Err(Error::EthereumBridge(
+            EthereumBridgeError::FeeNotIncreased {
+                current_fee,
+                new_fee,
+            },
+        ));
+    }
+
+    let payer = bump.transfer.gas_fee.payer.clone();
+    let (tx_code_hash, signing_data) = futures::try_join!(
+        query_wasm_code_hash(context, code_path.to_string_lossy()),
+        aux_signing_data(
+            context,
+            &tx_args,
+            // fee payer
+            Some(payer.clone()),
+            // tx signer
+            Some(payer),
+        ),
+    )?;
+
+    let chain_id = tx_args
+        .chain_id
+        .clone()
+        .ok_or_else(|| Error::Other("No chain id available".into()))?;
+
+    let mut tx = Tx::new(chain_id, tx_args.expiration);
+    tx.add_code_from_hash(
+        tx_code_hash,
+        Some(code_path.to_string_lossy().into_owned()),
     )
-    .await?;
+    .add_data(bump);
+
+    let epoch =
+        prepare_tx(context, &tx_args, &mut tx, &signing_data, None).await?;
 
     Ok((tx, signing_data, epoch))
 }
 
+/// Query the statistics of the gas fees paid in the given token
+/// by the transfers in the Ethereum bridge pool.
+async fn query_fee_stats_of(
+    context: &impl Namada,
+    fee_token: &Address,
+) -> Result<Option<BridgePoolFeeStats>, Error> {
+    let mut stats = RPC
+        .shell()
+        .eth_bridge()
+        .read_bridge_pool_fee_stats(context.client())
+        .await
+        .map_err(|e| {
+            Error::EthereumBridge(EthereumBridgeError::ReadBridgePool(
+                e.to_string(),
+            ))
+        })?;
+    Ok(stats.remove(fee_token))
+}
+
+/// Raise the given gas fee to the recommended gas fee of the
+/// Ethereum bridge pool, if it is lower.
+async fn auto_fee_amount(
+    context: &impl Namada,
+    fee_token: &Address,
+    fee_amount: args::InputAmount,
+    force: bool,
+) -> Result<args::InputAmount, Error> {
+    let (fee_amount, stats) = futures::try_join!(
+        validate_amount(context, fee_amount, fee_token, force).map(|result| {
+            result.map_err(|e| {
+                Error::Other(format!(
+                    "Failed to validate Bridge pool fee amount: {e}",
+                ))
+            })
+        }),
+        query_fee_stats_of(context, fee_token),
+    )?;
+    let recommended_fee = stats
+        .map(|stats| stats.recommended_fee())
+        .unwrap_or_default();
+    if recommended_fee <= fee_amount.amount {
+        return Ok(args::InputAmount::Validated(fee_amount));
+    }
+    let recommended_fee = denominate_amount(
+        context.client(),
+        context.io(),
+        fee_token,
+        recommended_fee,
+    )
+    .await;
+    display_line!(
+        context.io(),
+        "Paying the recommended Bridge pool gas fee of {recommended_fee}."
+    );
+    Ok(args::InputAmount::Validated(recommended_fee))
+}
+
 /// Perform client validation checks on a Bridge pool transfer.
 #[allow(clippy::too_many_arguments)]
 async fn validate_bridge_pool_tx(
@@ -351,27 +522,44 @@ pub async fn query_bridge_pool(
     Ok(pool_contents)
 }
 
-/// Query the contents of the Ethereum bridge pool that
-/// is covered by the latest signed root.
+/// Query statistics about the gas fees paid by the transfers
+/// in the Ethereum bridge pool, per fee token.
 /// Prints out a json payload.
-pub async fn query_signed_bridge_pool(
+pub async fn query_bridge_pool_fee_stats(
     client: &(impl Client + Sync),
     io: &impl Io,
-) -> Result<HashMap<String, PendingTransfer>, Error> {
-    let response: Vec<PendingTransfer> = RPC
+) -> Result<HashMap<Address, BridgePoolFeeStats>, Error> {
+    let stats = RPC
         .shell()
         .eth_bridge()
-        .read_signed_ethereum_bridge_pool(client)
+        .read_bridge_pool_fee_stats(client)
         .await
         .map_err(|e| {
-            Error::EthereumBridge(EthereumBridgeError::ReadSignedBridgePool(
+            Error::EthereumBridge(EthereumBridgeError::ReadBridgePool(
                 e.to_string(),
             ))
         })?;
-    let pool_contents: HashMap<String, PendingTransfer> = response
-        .into_iter()
-        .map(|transfer| (transfer.keccak256().to_string(), transfer))
-        .collect();
+    if stats.is_empty() {
+        display_line!(io, "Bridge pool is empty.");
+        return Ok(stats);
+    }
+    display_line!(
+        io,
+        "{}",
+        serde_json::to_string_pretty(&stats)
+            .map_err(|e| EncodingError::Serde(e.to_string()))?
+    );
+    Ok(stats)
+}
+
+/// Query the contents of the Ethereum bridge pool that
+/// is covered by the latest signed root.
+/// Prints out a json payload.
+pub async fn query_signed_bridge_pool(
+    client: &(impl Client + Sync),
+    io: &impl Io,
+) -> Result<HashMap<String, PendingTransfer>, Error> {
+    let pool_contents = read_signed_bridge_pool(client).await?;
     if pool_contents.is_empty() {
         display_line!(io, "Bridge pool is empty.");
         return Ok(pool_contents);
@@ -388,6 +576,28 @@ pub async fn query_signed_bridge_pool(
     Ok(pool_contents)
 }
 
+/// Read the contents of the Ethereum bridge pool that
+/// is covered by the latest signed root, indexed by the
+/// hashes of the transfers.
+async fn read_signed_bridge_pool(
+    client: &(impl Client + Sync),
+) -> Result<HashMap<String, PendingTransfer>, Error> {
+    let response: Vec<PendingTransfer> = RPC
+        .shell()
+        .eth_bridge()
+        .read_signed_ethereum_bridge_pool(client)
+        .await
+        .map_err(|e| {
+            Error::EthereumBridge(EthereumBridgeError::ReadSignedBridgePool(
+                e.to_string(),
+            ))
+        })?;
+    Ok(response
+        .into_iter()
+        .map(|transfer| (transfer.keccak256().to_string(), transfer))
+        .collect())
+}
+
 /// Iterates over all ethereum events
 /// and returns the amount of voting power
 /// backing each `TransferToEthereum` event.
@@ -482,6 +692,16 @@ async fn construct_bridge_pool_proof(
         }
     }
 
+    generate_bridge_pool_proof(client, io, args).await
+}
+
+/// Request a proof that a set of transfers are in the bridge pool
+/// from a Namada node.
+async fn generate_bridge_pool_proof(
+    client: &(impl Client + Sync),
+    io: &impl Io,
+    args: GenBridgePoolProofReq<'_, '_>,
+) -> Result<GenBridgePoolProofRsp, Error> {
     let data = args.serialize_to_vec();
     let response = RPC
         .shell()
@@ -595,12 +815,33 @@ where
         client,
         io,
         GenBridgePoolProofReq {
-            transfers: Cow::Owned(args.transfers),
-            relayer: Cow::Owned(args.relayer),
+            transfers: Cow::Borrowed(&args.transfers),
+            relayer: Cow::Borrowed(&args.relayer),
             with_appendix: false,
         },
     )
     .await?;
+    let transf_result =
+        relay_proof_once(eth_client, client, io, &args, abi_encoded_args)
+            .await?;
+
+    display_line!(io, "{transf_result:?}");
+    Ok(())
+}
+
+/// Relay an ABI encoded Bridge pool proof to the Bridge contract,
+/// after checking that its nonce matches the one in the contract.
+async fn relay_proof_once<E>(
+    eth_client: Arc<E>,
+    client: &(impl Client + Sync),
+    io: &impl Io,
+    args: &args::RelayBridgePoolProof,
+    abi_encoded_args: Vec<u8>,
+) -> Result<Option<TransactionReceipt>, Error>
+where
+    E: Middleware,
+    E::Error: std::fmt::Debug + std::fmt::Display,
+{
     let bridge =
         match RPC.shell().eth_bridge().read_bridge_contract(client).await {
             Ok(address) => Bridge::new(address.address, eth_client),
@@ -689,15 +930,347 @@ where
     let pending_tx = relay_op.send().await.map_err(|e| {
         Error::EthereumBridge(EthereumBridgeError::ContractCall(e.to_string()))
     })?;
-    let transf_result = pending_tx
+    pending_tx
         .confirmations(args.confirmations as usize)
         .await
         .map_err(|e| {
             Error::EthereumBridge(EthereumBridgeError::Rpc(e.to_string()))
-        })?;
+        })
+}
 
-    display_line!(io, "{transf_result:?}");
-    Ok(())
+/// The progress of the Bridge pool relayer daemon, persisted
+/// across restarts.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+struct RelayerState {
+    /// The nonce of the last Bridge pool root relayed by the daemon.
+    last_relayed_nonce: Option<EthUint>,
+}
+
+impl RelayerState {
+    /// Load the state of the relayer from the given path, or start
+    /// afresh if none has been persisted yet.
+    fn load(path: &Path) -> Result<Self, Error> {
+        match std::fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                Error::EthereumBridge(EthereumBridgeError::RelayerState(
+                    e.to_string(),
+                ))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Ok(Self::default())
+            }
+            Err(e) => Err(Error::EthereumBridge(
+                EthereumBridgeError::RelayerState(e.to_string()),
+            )),
+        }
+    }
+
+    /// Persist the state of the relayer to the given path.
+    fn save(&self, path: &Path) -> Result<(), Error> {
+        let write = || -> std::io::Result<()> {
+            let tmp_path = path.with_extension("tmp");
+            std::fs::write(&tmp_path, serde_json::to_vec_pretty(self)?)?;
+            std::fs::rename(tmp_path, path)
+        };
+        write().map_err(|e| {
+            Error::EthereumBridge(EthereumBridgeError::RelayerState(
+                e.to_string(),
+            ))
+        })
+    }
+}
+
+/// Only keep the transfers of the given assets. If no assets
+/// are given, all transfers are kept.
+fn filter_allowed_tokens(
+    signed_pool: HashMap<String, PendingTransfer>,
+    allowed_tokens: &[EthAddress],
+) -> HashMap<String, PendingTransfer> {
+    if allowed_tokens.is_empty() {
+        return signed_pool;
+    }
+    signed_pool
+        .into_iter()
+        .filter(|(_, pending)| allowed_tokens.contains(&pending.transfer.asset))
+        .collect()
+}
+
+/// Continuously relay the most economical batches of transfers in
+/// the Bridge pool, and optionally validator set updates, until a
+/// shutdown signal is received.
+pub async fn relay_bridge_pool_daemon<E>(
+    eth_client: Arc<E>,
+    context: &impl Namada,
+    args: args::BridgePoolRelayDaemon,
+) -> Result<(), Error>
+where
+    E: Middleware,
+    E::Error: std::fmt::Debug + std::fmt::Display,
+{
+    let mut signal_receiver = args.safe_mode.then(install_shutdown_signal);
+
+    if args.sync {
+        block_on_eth_sync(
+            &*eth_client,
+            context.io(),
+            BlockOnEthSync {
+                deadline: Instant::now() + Duration::from_secs(60),
+                delta_sleep: Duration::from_secs(1),
+            },
+        )
+        .await?;
+    } else {
+        eth_sync_or_exit(&*eth_client, context.io()).await?;
+    }
+
+    let batches = relay_batches_daemon(
+        Arc::clone(&eth_client),
+        context,
+        &args,
+        &mut signal_receiver,
+    );
+    if !args.relay_valset {
+        return batches.await;
+    }
+
+    // the validator set relayer runs until an error occurs, so
+    // it must be stopped once the batch relayer has shut down
+    let valset = validator_set::relay_validator_set_update(
+        eth_client,
+        context.client(),
+        context.io(),
+        args::ValidatorSetUpdateRelay {
+            daemon: true,
+            query: args.recommend.query.clone(),
+            confirmations: args.confirmations,
+            eth_rpc_endpoint: (),
+            epoch: None,
+            gas: args.recommend.max_gas,
+            gas_price: args.gas_price,
+            eth_addr: args.eth_addr,
+            sync: false,
+            retry_dur: args.retry_dur,
+            success_dur: args.success_dur,
+            safe_mode: false,
+        },
+    );
+    futures::pin_mut!(batches, valset);
+    match future::select(batches, valset).await {
+        Either::Left((result, _)) | Either::Right((result, _)) => result,
+    }
+}
+
+/// Periodically relay the most economical batch of transfers
+/// in the Bridge pool, whose nonce has not been relayed yet.
+async fn relay_batches_daemon<E, F>(
+    eth_client: Arc<E>,
+    context: &impl Namada,
+    args: &args::BridgePoolRelayDaemon,
+    shutdown_receiver: &mut Option<F>,
+) -> Result<(), Error>
+where
+    E: Middleware,
+    E::Error: std::fmt::Debug + std::fmt::Display,
+    F: Future<Output = ()> + Unpin,
+{
+    const DEFAULT_RETRY_DURATION: Duration = Duration::from_secs(1);
+    const DEFAULT_SUCCESS_DURATION: Duration = Duration::from_secs(10);
+    const MAX_RELAY_ATTEMPTS: usize = 3;
+
+    let retry_duration = args.retry_dur.unwrap_or(DEFAULT_RETRY_DURATION);
+    let success_duration = args.success_dur.unwrap_or(DEFAULT_SUCCESS_DURATION);
+
+    // a negative cost is a minimum profit
+    let max_cost = match (args.recommend.gas, args.min_profit) {
+        (Some(gas), _) => I256::from(gas),
+        (None, Some(min_profit)) => -I256::from(min_profit),
+        (None, None) => I256::default(),
+    };
+
+    let mut state = RelayerState::load(&args.state_file)?;
+    let mut last_call_succeeded = true;
+
+    tracing::info!(
+        last_relayed_nonce = ?state.last_relayed_nonce,
+        "The Bridge pool relayer daemon has started"
+    );
+
+    loop {
+        let should_exit = if let Some(fut) = shutdown_receiver.as_mut() {
+            let fut = future::poll_fn(|cx| match fut.poll_unpin(cx) {
+                Poll::Pending => Poll::Ready(false),
+                Poll::Ready(_) => Poll::Ready(true),
+            });
+            futures::pin_mut!(fut);
+            fut.as_mut().await
+        } else {
+            false
+        };
+
+        if should_exit {
+            return Ok(());
+        }
+
+        let sleep_for = if last_call_succeeded {
+            success_duration
+        } else {
+            retry_duration
+        };
+
+        tracing::debug!(?sleep_for, "Sleeping");
+        time::sleep(sleep_for).await;
+
+        let is_synchronizing = eth_sync_or(&*eth_client, context.io(), || ())
+            .await
+            .is_err();
+        if is_synchronizing {
+            tracing::debug!("The Ethereum node is synchronizing");
+            last_call_succeeded = false;
+            continue;
+        }
+
+        let signed_pool = match read_signed_bridge_pool(context.client()).await
+        {
+            Ok(pool) => filter_allowed_tokens(pool, &args.allowed_tokens),
+            Err(error) => {
+                tracing::warn!(%error, "Failed to read the signed Bridge pool");
+                last_call_succeeded = false;
+                continue;
+            }
+        };
+        let (nonce, batch) = match compute_recommended_batch(
+            context,
+            &args.recommend,
+            signed_pool,
+            max_cost,
+        )
+        .await
+        {
+            Ok(recommendation) => recommendation,
+            Err(error) => {
+                tracing::warn!(%error, "Failed to recommend a batch");
+                last_call_succeeded = false;
+                continue;
+            }
+        };
+
+        if matches!(&state.last_relayed_nonce, Some(last) if *last >= nonce) {
+            tracing::debug!(
+                ?nonce,
+                "The latest signed Bridge pool root has already been relayed"
+            );
+            last_call_succeeded = false;
+            continue;
+        }
+        let Some(batch) = batch else {
+            tracing::debug!(
+                ?nonce,
+                "No batch of transfers satisfies the relaying parameters"
+            );
+            last_call_succeeded = false;
+            continue;
+        };
+
+        let relay_args = args::RelayBridgePoolProof {
+            query: args.recommend.query.clone(),
+            transfers: batch
+                .transfer_hashes
+                .iter()
+                .map(|hash| {
+                    KeccakHash::try_from(hash.as_str())
+                        .expect("Bridge pool transfer hashes should be valid")
+                })
+                .collect(),
+            relayer: args.relayer.clone(),
+            confirmations: args.confirmations,
+            eth_rpc_endpoint: (),
+            gas: args.recommend.max_gas,
+            gas_price: args.gas_price,
+            eth_addr: args.eth_addr,
+            sync: false,
+            safe_mode: false,
+        };
+        let relay_args = &relay_args;
+        let eth_client = &eth_client;
+
+        let relay_result = Sleep {
+            strategy: LinearBackoff {
+                delta: retry_duration,
+            },
+        }
+        .retry(MAX_RELAY_ATTEMPTS, || async move {
+            let proof = generate_bridge_pool_proof(
+                context.client(),
+                context.io(),
+                GenBridgePoolProofReq {
+                    transfers: Cow::Borrowed(&relay_args.transfers),
+                    relayer: Cow::Borrowed(&relay_args.relayer),
+                    with_appendix: false,
+                },
+            )
+            .await;
+            let abi_encoded_args = match proof {
+                Ok(GenBridgePoolProofRsp {
+                    abi_encoded_args, ..
+                }) => abi_encoded_args,
+                Err(error) => {
+                    tracing::warn!(%error, "Failed to generate a proof");
+                    return ControlFlow::Continue(());
+                }
+            };
+            match relay_proof_once(
+                Arc::clone(eth_client),
+                context.client(),
+                context.io(),
+                relay_args,
+                abi_encoded_args,
+            )
+            .await
+            {
+                // the batch was relayed by someone else, or the
+                // nonce in Namada is ahead of the contract's
+                Err(Error::EthereumBridge(
+                    EthereumBridgeError::InvalidBpNonce,
+                )) => ControlFlow::Break(None),
+                Err(error) => {
+                    tracing::warn!(%error, "Failed to relay the batch");
+                    ControlFlow::Continue(())
+                }
+                Ok(receipt) => ControlFlow::Break(Some(receipt)),
+            }
+        })
+        .await;
+
+        match relay_result {
+            Ok(Some(receipt)) => {
+                tracing::info!(
+                    ?nonce,
+                    transfers = batch.transfer_hashes.len(),
+                    net_profit = %batch.net_profit,
+                    ?receipt,
+                    "Relayed a batch of Bridge pool transfers"
+                );
+                state.last_relayed_nonce = Some(nonce);
+                state.save(&args.state_file)?;
+                last_call_succeeded = true;
+            }
+            Ok(None) => {
+                tracing::debug!(
+                    ?nonce,
+                    "The Bridge pool nonce is out of date, skipping the batch"
+                );
+                last_call_succeeded = false;
+            }
+            Err(_) => {
+                tracing::error!(
+                    ?nonce,
+                    "Giving up on relaying the batch, after \
+                     {MAX_RELAY_ATTEMPTS} attempts"
+                );
+                last_call_succeeded = false;
+            }
+        }
+    }
 }
 
 mod recommendations {
@@ -778,18 +1351,18 @@ mod recommendations {
     /// Batch of recommended transfers to Ethereum that generate
     /// a profit after a relay operation.
     #[derive(Debug, Eq, PartialEq)]
-    struct RecommendedBatch {
+    pub(super) struct RecommendedBatch {
         /// Hashes of the recommended transfers to be relayed.
-        transfer_hashes: Vec<String>,
+        pub(super) transfer_hashes: Vec<String>,
         /// Estimate of the total fees, measured in gwei, that will be paid
         /// on Ethereum.
-        ethereum_gas_fees: Uint,
+        pub(super) ethereum_gas_fees: Uint,
         /// Net profitt in gwei, based on the conversion rates provided
         /// to the algorithm.
-        net_profit: I256,
+        pub(super) net_profit: I256,
         /// Gas fees paid by the transfers considered for relaying,
         /// paid in various token types.
-        bridge_pool_gas_fees: HashMap<String, Uint>,
+        pub(super) bridge_pool_gas_fees: HashMap<String, Uint>,
     }
 
     /// Recommend the most economical batch of transfers to relay based
@@ -799,6 +1372,63 @@ mod recommendations {
         context: &impl Namada,
         args: args::RecommendBatch,
     ) -> Result<(), Error> {
+        let signed_pool =
+            query_signed_bridge_pool(context.client(), context.io()).await?;
+        let max_cost = args.gas.map(I256::from).unwrap_or_default();
+
+        compute_recommended_batch(context, &args, signed_pool, max_cost)
+            .await?
+            .1
+            .map(
+                |RecommendedBatch {
+                     transfer_hashes,
+                     ethereum_gas_fees,
+                     net_profit,
+                     bridge_pool_gas_fees,
+                 }| {
+                    display_line!(
+                        context.io(),
+                        "Recommended batch: {transfer_hashes:#?}"
+                    );
+                    display_line!(
+                        context.io(),
+                        "Estimated Ethereum transaction gas (in gwei): \
+                         {ethereum_gas_fees}",
+                    );
+                    display_line!(
+                        context.io(),
+                        "Estimated net profit (in gwei): {net_profit}"
+                    );
+                    display_line!(
+                        context.io(),
+                        "Total fees: {bridge_pool_gas_fees:#?}"
+                    );
+                },
+            )
+            .unwrap_or_else(|| {
+                display_line!(
+                    context.io(),
+                    "Unable to find a recommendation satisfying the input \
+                     parameters."
+                );
+            });
+
+        Ok(())
+    }
+
+    /// Compute the most economical batch of transfers from the given
+    /// signed Bridge pool contents, whose total relaying cost (in gwei)
+    /// does not exceed `max_cost`. A negative `max_cost` thus requires
+    /// a minimum net profit.
+    ///
+    /// Returns the nonce of the signed Bridge pool root, along with
+    /// the recommended batch, if any.
+    pub(super) async fn compute_recommended_batch(
+        context: &impl Namada,
+        args: &args::RecommendBatch,
+        signed_pool: HashMap<String, PendingTransfer>,
+        max_cost: I256,
+    ) -> Result<(EthUint, Option<RecommendedBatch>), Error> {
         // get transfers that can already been relayed but are awaiting a quorum
         // of backing votes.
         let in_progress = RPC
@@ -905,56 +1535,21 @@ mod recommendations {
             context.io(),
             &args.conversion_table,
             &in_progress,
-            query_signed_bridge_pool(context.client(), context.io()).await?,
+            signed_pool,
         )?;
 
         let max_gas =
             args.max_gas.map(Uint::from_u64).unwrap_or(uint::MAX_VALUE);
-        let max_cost = args.gas.map(I256::from).unwrap_or_default();
 
-        generate_recommendations(
+        let recommendation = generate_recommendations(
             context.io(),
             eligible,
             &args.conversion_table,
             validator_gas,
             max_gas,
             max_cost,
-        )?
-        .map(
-            |RecommendedBatch {
-                 transfer_hashes,
-                 ethereum_gas_fees,
-                 net_profit,
-                 bridge_pool_gas_fees,
-             }| {
-                display_line!(
-                    context.io(),
-                    "Recommended batch: {transfer_hashes:#?}"
-                );
-                display_line!(
-                    context.io(),
-                    "Estimated Ethereum transaction gas (in gwei): \
-                     {ethereum_gas_fees}",
-                );
-                display_line!(
-                    context.io(),
-                    "Estimated net profit (in gwei): {net_profit}"
-                );
-                display_line!(
-                    context.io(),
-                    "Total fees: {bridge_pool_gas_fees:#?}"
-                );
-            },
-        )
-        .unwrap_or_else(|| {
-            display_line!(
-                context.io(),
-                "Unable to find a recommendation satisfying the input \
-                 parameters."
-            );
-        });
-
-        Ok(())
+        )?;
+        Ok((latest_bp_nonce, recommendation))
     }
 
     /// Given an ordered list of signatures, figure out the size of the first
@@ -1581,7 +2176,53 @@ mod recommendations {
                     - transfer_fee() * I256::from(2_u64)
             );
         }
+
+        /// Test that the relayer daemon only considers transfers of
+        /// the allowed assets, if any are given.
+        #[test]
+        fn test_filter_allowed_tokens() {
+            let mut other = transfer(2);
+            other.transfer.asset = EthAddress([3; 20]);
+            let signed_pool: HashMap<_, _> = [transfer(1), other]
+                .into_iter()
+                .map(|t| (t.keccak256().to_string(), t))
+                .collect();
+
+            let filtered = filter_allowed_tokens(
+                signed_pool.clone(),
+                &[EthAddress([3; 20])],
+            );
+            assert_eq!(filtered.len(), 1);
+            assert!(filtered
+                .values()
+                .all(|t| t.transfer.asset == EthAddress([3; 20])));
+            assert_eq!(
+                filter_allowed_tokens(signed_pool.clone(), &[]),
+                signed_pool
+            );
+            assert!(filter_allowed_tokens(signed_pool, &[EthAddress([4; 20])])
+                .is_empty());
+        }
+
+        /// Test that the state of the relayer daemon survives restarts,
+        /// and that a fresh state is used if none was persisted.
+        #[test]
+        fn test_relayer_state_roundtrip() {
+            let dir = tempfile::tempdir().expect("Test failed");
+            let path = dir.path().join("relayer-state.json");
+            assert_eq!(
+                RelayerState::load(&path).expect("Test failed"),
+                RelayerState::default()
+            );
+
+            let state = RelayerState {
+                last_relayed_nonce: Some(EthUint::from(42_u64)),
+            };
+            state.save(&path).expect("Test failed");
+            assert_eq!(RelayerState::load(&path).expect("Test failed"), state);
+        }
     }
 }
 
 pub use recommendations::recommend_batch;
+use recommendations::{compute_recommended_batch, RecommendedBatch};
@@ -0,0 +1,174 @@
+//! Proofs of Namada's state, verifiable by Ethereum contracts.
+
+use std::sync::Arc;
+
+use ethers::abi::{self, AbiType, Tokenizable};
+use ethers::providers::Middleware;
+use ethers::types::TransactionRequest;
+use namada_core::types::ethereum_structs;
+use namada_core::types::storage::Key;
+use serde::Serialize;
+
+use super::{block_on_eth_sync, eth_sync_or_exit, BlockOnEthSync};
+use crate::control_flow::install_shutdown_signal;
+use crate::control_flow::time::{Duration, Instant};
+use crate::error::{EncodingError, Error, EthereumBridgeError};
+use crate::internal_macros::echo_error;
+use crate::io::Io;
+use crate::queries::{Client, GenStateProofRsp, StateProof, RPC};
+use crate::{args, display_line, edisplay_line, MaybeSync};
+
+/// The name of the function of a verifier contract that
+/// state proofs are relayed to.
+///
+/// Its Solidity signature is `verifyStateProof(ValidatorSetArgs,
+/// Signature[], StateProof)`, where the latter is a tuple of the
+/// form `(bytes32, uint256, bytes, bytes, bytes[])`.
+pub const VERIFY_STATE_PROOF: &str = "verifyStateProof";
+
+/// Compute the selector of [`VERIFY_STATE_PROOF`].
+pub fn verify_state_proof_selector() -> [u8; 4] {
+    abi::short_signature(
+        VERIFY_STATE_PROOF,
+        &[
+            ethereum_structs::ValidatorSetArgs::param_type(),
+            Vec::<ethereum_structs::Signature>::param_type(),
+            StateProof::param_type(),
+        ],
+    )
+}
+
+/// Request a proof of the value of `key` from a Namada node.
+async fn generate_state_proof(
+    client: &(impl Client + Sync),
+    io: &impl Io,
+    key: &Key,
+) -> Result<GenStateProofRsp, Error> {
+    RPC.shell()
+        .eth_bridge()
+        .generate_state_proof(client, key)
+        .await
+        .map_err(|e| {
+            edisplay_line!(
+                io,
+                "Encountered error constructing proof:\n{:?}",
+                e
+            );
+            Error::EthereumBridge(EthereumBridgeError::GenStateProof(
+                e.to_string(),
+            ))
+        })
+}
+
+/// A response from constructing a state proof.
+#[derive(Serialize)]
+struct StateProofResponse {
+    key: String,
+    height: u64,
+    abi_encoded_args: Vec<u8>,
+}
+
+/// Construct a proof of the value of some storage key, against
+/// the latest state root signed by Namada's validators, and
+/// return it to the user (as opposed to relaying it to Ethereum).
+pub async fn construct_state_proof(
+    client: &(impl Client + Sync),
+    io: &(impl Io + MaybeSync),
+    args: args::StateProof,
+) -> Result<(), Error> {
+    let GenStateProofRsp {
+        height,
+        abi_encoded_args,
+    } = generate_state_proof(client, io, &args.key).await?;
+    let resp = StateProofResponse {
+        key: args.key.to_string(),
+        height: height.0,
+        abi_encoded_args,
+    };
+    display_line!(
+        io,
+        "{}",
+        serde_json::to_string_pretty(&resp)
+            .map_err(|e| EncodingError::Serde(e.to_string()))?
+    );
+    Ok(())
+}
+
+/// Relay a proof of the value of some storage key to a verifier
+/// contract on Ethereum, by calling its [`VERIFY_STATE_PROOF`]
+/// function.
+pub async fn relay_state_proof<E>(
+    eth_client: Arc<E>,
+    client: &(impl Client + Sync),
+    io: &(impl Io + MaybeSync),
+    args: args::RelayStateProof,
+) -> Result<(), Error>
+where
+    E: Middleware,
+    E::Error: std::fmt::Debug + std::fmt::Display,
+{
+    let _signal_receiver = args.safe_mode.then(install_shutdown_signal);
+
+    if args.sync {
+        block_on_eth_sync(
+            &*eth_client,
+            io,
+            BlockOnEthSync {
+                deadline: Instant::now() + Duration::from_secs(60),
+                delta_sleep: Duration::from_secs(1),
+            },
+        )
+        .await?;
+    } else {
+        eth_sync_or_exit(&*eth_client, io).await?;
+    }
+
+    let rsp = generate_state_proof(client, io, &args.key).await?;
+    let (validator_set, signatures, state_proof) =
+        rsp.verify_state_proof_args().ok_or_else(|| {
+            EncodingError::Decoding(echo_error!(
+                io,
+                "Unable to decode the generated proof"
+            ))
+        })?;
+    let calldata = [
+        verify_state_proof_selector().as_slice(),
+        &abi::encode(&[
+            validator_set.into_token(),
+            signatures.into_token(),
+            state_proof.into_token(),
+        ]),
+    ]
+    .concat();
+
+    let mut relay_tx = TransactionRequest::new()
+        .to(ethers::types::Address::from(args.verifier))
+        .data(calldata);
+    if let Some(gas) = args.gas {
+        relay_tx = relay_tx.gas(gas);
+    }
+    if let Some(gas_price) = args.gas_price {
+        relay_tx = relay_tx.gas_price(gas_price);
+    }
+    if let Some(eth_addr) = args.eth_addr {
+        relay_tx = relay_tx.from(ethers::types::Address::from(eth_addr));
+    }
+
+    let pending_tx = eth_client
+        .send_transaction(relay_tx, None)
+        .await
+        .map_err(|e| {
+            Error::EthereumBridge(EthereumBridgeError::ContractCall(
+                e.to_string(),
+            ))
+        })?;
+    let receipt = pending_tx
+        .confirmations(args.confirmations as usize)
+        .await
+        .map_err(|e| {
+            Error::EthereumBridge(EthereumBridgeError::Rpc(e.to_string()))
+        })?;
+
+    display_line!(io, "{receipt:?}");
+    Ok(())
+}
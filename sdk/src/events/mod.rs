@@ -52,6 +52,17 @@ pub enum EventType {
     Proposal,
     /// The pgf payment
     PgfPayment,
+    /// The flow control parameters of the Ethereum bridge were changed
+    /// by governance
+    EthBridgeFlow,
+    /// A transfer in the Ethereum Bridge pool expired. It is refunded
+    /// once the Bridge pool nonce exceeds the `nonce` attribute of the
+    /// event, i.e. after the next batch of transfers is relayed
+    EthBridgePoolExpiry,
+    /// An expired transfer in the Ethereum Bridge pool was refunded
+    EthBridgePoolRefund,
+    /// A message from an Ethereum contract was dispatched to its handler
+    EthContractMessage,
 }
 
 impl Display for EventType {
@@ -62,6 +73,16 @@ impl Display for EventType {
             EventType::Ibc(t) => write!(f, "{}", t),
             EventType::Proposal => write!(f, "proposal"),
             EventType::PgfPayment => write!(f, "pgf_payment"),
+            EventType::EthBridgeFlow => write!(f, "eth_bridge_flow"),
+            EventType::EthBridgePoolExpiry => {
+                write!(f, "eth_bridge_pool_expiry")
+            }
+            EventType::EthBridgePoolRefund => {
+                write!(f, "eth_bridge_pool_refund")
+            }
+            EventType::EthContractMessage => {
+                write!(f, "eth_contract_message")
+            }
         }?;
         Ok(())
     }
@@ -76,6 +97,10 @@ impl FromStr for EventType {
             "applied" => Ok(EventType::Applied),
             "proposal" => Ok(EventType::Proposal),
             "pgf_payments" => Ok(EventType::PgfPayment),
+            "eth_bridge_flow" => Ok(EventType::EthBridgeFlow),
+            "eth_bridge_pool_expiry" => Ok(EventType::EthBridgePoolExpiry),
+            "eth_bridge_pool_refund" => Ok(EventType::EthBridgePoolRefund),
+            "eth_contract_message" => Ok(EventType::EthContractMessage),
             // IBC
             "update_client" => Ok(EventType::Ibc("update_client".to_string())),
             "send_packet" => Ok(EventType::Ibc("send_packet".to_string())),
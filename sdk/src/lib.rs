@@ -13,12 +13,16 @@ pub mod eth_bridge;
 pub mod rpc;
 
 pub mod args;
+pub mod bundle;
 pub mod masp;
+pub mod signer;
 pub mod signing;
+pub mod tracker;
 #[allow(clippy::result_large_err)]
 pub mod tx;
 
 pub mod control_flow;
+pub mod discovery;
 pub mod error;
 pub mod events;
 pub(crate) mod internal_macros;
@@ -39,6 +43,7 @@ use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
 use namada_core::types::address::Address;
 use namada_core::types::dec::Dec;
 use namada_core::types::ethereum_events::EthAddress;
+use namada_core::types::keccak::KeccakHash;
 use namada_core::types::key::*;
 use namada_core::types::masp::{TransferSource, TransferTarget};
 use namada_core::types::token;
@@ -56,12 +61,13 @@ use crate::signing::SigningTxData;
 use crate::token::DenominatedAmount;
 use crate::tx::{
     ProcessTxResponse, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
-    TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
-    TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
-    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
-    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
-    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
-    TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
+    TX_BRIDGE_POOL_BUMP_FEE_WASM, TX_BRIDGE_POOL_WASM,
+    TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
+    TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
+    TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
+    TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
+    TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+    TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
     TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
     VP_USER_WASM,
 };
@@ -118,6 +124,13 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
     /// Return the native token
     fn native_token(&self) -> Address;
 
+    /// The websocket URL of the ledger node, to subscribe to the events of
+    /// the submitted transactions instead of polling for them, if any
+    #[cfg(feature = "websocket-client")]
+    fn events_url(&self) -> Option<&tendermint_rpc::WebSocketClientUrl> {
+        None
+    }
+
     /// Make a tx builder using no arguments
     fn tx_builder(&self) -> args::Tx {
         args::Tx {
@@ -126,6 +139,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             dump_tx: false,
             output_folder: None,
             force: false,
+            no_confirm: false,
             broadcast_only: false,
             ledger_address: (),
             initialized_account_alias: None,
@@ -135,6 +149,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             fee_token: self.native_token(),
             fee_unshield: None,
             gas_limit: GasLimit::from(20_000),
+            estimate_gas: false,
             expiration: None,
             disposable_signing_key: false,
             chain_id: None,
@@ -143,6 +158,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             tx_reveal_code_path: PathBuf::from(TX_REVEAL_PK),
             password: None,
             use_device: false,
+            remote_signer: None,
         }
     }
 
@@ -162,6 +178,8 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             tx_code_path: PathBuf::from(TX_TRANSFER_WASM),
             tx: self.tx_builder(),
             native_token: self.native_token(),
+            memo: None,
+            device_source: None,
         }
     }
 
@@ -495,12 +513,27 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             }),
             fee_payer: None,
             fee_token: self.native_token(),
+            auto_fee: false,
             nut: false,
             code_path: PathBuf::from(TX_BRIDGE_POOL_WASM),
             tx: self.tx_builder(),
         }
     }
 
+    /// Make a BumpBridgePoolFee builder from the given minimum set of
+    /// arguments
+    fn new_bump_bridge_pool_fee(
+        &self,
+        transfer: KeccakHash,
+    ) -> args::BumpBridgePoolFee {
+        args::BumpBridgePoolFee {
+            transfer,
+            fee_amount: None,
+            code_path: PathBuf::from(TX_BRIDGE_POOL_BUMP_FEE_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a ResignSteward builder from the given minimum set of arguments
     fn new_resign_steward(&self, steward: Address) -> args::ResignSteward {
         args::ResignSteward {
@@ -564,6 +597,16 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         .await
     }
 
+    /// Estimate the gas and fee of the given transaction by dry running it
+    async fn estimate_fee(
+        &self,
+        tx: &Tx,
+        args: &args::Tx,
+        signing_data: &SigningTxData,
+    ) -> crate::error::Result<tx::FeeQuote> {
+        tx::estimate_fee(self, args, tx, signing_data).await
+    }
+
     /// Process the given transaction using the given flags
     async fn submit(
         &self,
@@ -614,6 +657,9 @@ where
     native_token: Address,
     /// The default builder for a Tx
     prototype: args::Tx,
+    /// The websocket URL of the ledger node, if any
+    #[cfg(feature = "websocket-client")]
+    events_url: Option<tendermint_rpc::WebSocketClientUrl>,
 }
 
 impl<C, U, V, I> NamadaImpl<C, U, V, I>
@@ -643,6 +689,7 @@ where
                 dump_tx: false,
                 output_folder: None,
                 force: false,
+                no_confirm: false,
                 broadcast_only: false,
                 ledger_address: (),
                 initialized_account_alias: None,
@@ -652,6 +699,7 @@ where
                 fee_token: native_token,
                 fee_unshield: None,
                 gas_limit: GasLimit::from(20_000),
+                estimate_gas: false,
                 expiration: None,
                 disposable_signing_key: false,
                 chain_id: None,
@@ -660,10 +708,23 @@ where
                 tx_reveal_code_path: PathBuf::from(TX_REVEAL_PK),
                 password: None,
                 use_device: false,
+                remote_signer: None,
             },
+            #[cfg(feature = "websocket-client")]
+            events_url: None,
         }
     }
 
+    /// Subscribe to the events of the submitted transactions at the given
+    /// websocket URL of the ledger node, if any, instead of polling for them
+    #[cfg(feature = "websocket-client")]
+    pub fn with_events_url(
+        self,
+        events_url: Option<tendermint_rpc::WebSocketClientUrl>,
+    ) -> Self {
+        Self { events_url, ..self }
+    }
+
     /// Construct a new Namada context looking up the native token address
     pub async fn new(
         client: C,
@@ -705,6 +766,11 @@ where
         self.native_token.clone()
     }
 
+    #[cfg(feature = "websocket-client")]
+    fn events_url(&self) -> Option<&tendermint_rpc::WebSocketClientUrl> {
+        self.events_url.as_ref()
+    }
+
     fn io(&self) -> &Self::Io {
         &self.io
     }
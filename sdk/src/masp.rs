@@ -2,7 +2,7 @@
 
 use std::collections::{btree_map, BTreeMap, BTreeSet, HashMap, HashSet};
 use std::env;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::ops::Deref;
 use std::path::PathBuf;
 
@@ -10,32 +10,39 @@ use std::path::PathBuf;
 // use async_std::io::{self};
 use borsh::{BorshDeserialize, BorshSerialize};
 use borsh_ext::BorshSerializeExt;
+use data_encoding::HEXLOWER;
 use itertools::Either;
 use lazy_static::lazy_static;
+use masp_note_encryption::{
+    EphemeralKeyBytes, ShieldedOutput, ENC_CIPHERTEXT_SIZE,
+};
 use masp_primitives::asset_type::AssetType;
 #[cfg(feature = "mainnet")]
 use masp_primitives::consensus::MainNetwork;
 #[cfg(not(feature = "mainnet"))]
 use masp_primitives::consensus::TestNetwork;
+use masp_primitives::constants::SPENDING_KEY_GENERATOR;
 use masp_primitives::convert::AllowedConversion;
-use masp_primitives::ff::PrimeField;
+use masp_primitives::ff::{Field, PrimeField};
 use masp_primitives::group::GroupEncoding;
-use masp_primitives::memo::MemoBytes;
+use masp_primitives::jubjub;
+use masp_primitives::memo::{Memo, MemoBytes};
 use masp_primitives::merkle_tree::{
     CommitmentTree, IncrementalWitness, MerklePath,
 };
-use masp_primitives::sapling::keys::FullViewingKey;
+use masp_primitives::sapling::keys::{ExpandedSpendingKey, FullViewingKey};
 use masp_primitives::sapling::note_encryption::*;
-use masp_primitives::sapling::redjubjub::PublicKey;
+use masp_primitives::sapling::prover::TxProver;
+use masp_primitives::sapling::redjubjub::{PublicKey, Signature};
 use masp_primitives::sapling::{
-    Diversifier, Node, Note, Nullifier, ViewingKey,
+    Diversifier, Node, Note, Nullifier, ProofGenerationKey, Rseed, ViewingKey,
 };
 use masp_primitives::transaction::builder::{self, *};
 use masp_primitives::transaction::components::sapling::builder::SaplingMetadata;
 use masp_primitives::transaction::components::transparent::builder::TransparentBuilder;
 use masp_primitives::transaction::components::{
     ConvertDescription, I128Sum, OutputDescription, SpendDescription, TxOut,
-    U64Sum,
+    U64Sum, GROTH_PROOF_SIZE,
 };
 use masp_primitives::transaction::fees::fixed::FeeRule;
 use masp_primitives::transaction::sighash::{signature_hash, SignableInput};
@@ -46,10 +53,11 @@ use masp_primitives::transaction::{
 };
 use masp_primitives::zip32::{ExtendedFullViewingKey, ExtendedSpendingKey};
 use masp_proofs::bellman::groth16::PreparedVerifyingKey;
-use masp_proofs::bls12_381::Bls12;
+use masp_proofs::bls12_381::{self, Bls12};
 use masp_proofs::prover::LocalTxProver;
 use masp_proofs::sapling::SaplingVerificationContext;
 use namada_core::types::address::{Address, MASP};
+use namada_core::types::hash::Hash;
 use namada_core::types::masp::{
     BalanceOwner, ExtendedViewingKey, PaymentAddress, TransferSource,
     TransferTarget,
@@ -61,7 +69,10 @@ use namada_core::types::token::{
 };
 use namada_core::types::transaction::WrapperTx;
 use rand_core::{CryptoRng, OsRng, RngCore};
+#[cfg(feature = "multicore")]
+use rayon::prelude::*;
 use ripemd::Digest as RipemdDigest;
+use serde::{Deserialize, Serialize};
 use sha2::Digest;
 use thiserror::Error;
 
@@ -92,6 +103,16 @@ pub const ENV_VAR_MASP_TEST_SEED: &str = "NAMADA_MASP_TEST_SEED";
 /// A directory to save serialized proofs for tests.
 pub const MASP_TEST_PROOFS_DIR: &str = "test_fixtures/masp_proofs";
 
+/// The number of shielded transactions that are downloaded and scanned
+/// between two saves of the shielded context while syncing
+pub const SYNC_CHECKPOINT_INTERVAL: u64 = 1000;
+
+/// The prefix of an encoded [`ShieldedContext`], followed by the version of
+/// its encoding. Contexts saved before the encoding was versioned lack it.
+const CONTEXT_MAGIC: &[u8] = b"namada-shielded-context";
+/// The version of the encoding of the saved shielded contexts
+pub const CONTEXT_VERSION: u8 = 2;
+
 /// The network to use for MASP
 #[cfg(feature = "mainnet")]
 const NETWORK: MainNetwork = MainNetwork;
@@ -119,6 +140,40 @@ pub struct ShieldedTransfer {
     pub epoch: Epoch,
 }
 
+impl ShieldedTransfer {
+    /// Authorize the spends of this transfer with signatures produced by an
+    /// external signer, such as a hardware wallet. The signatures must be
+    /// given in the order of the builder's sapling inputs.
+    pub fn authorize_spends(
+        &mut self,
+        signatures: Vec<Signature>,
+    ) -> Result<(), Error> {
+        let mut ordered = vec![None; signatures.len()];
+        for (input_idx, signature) in signatures.into_iter().enumerate() {
+            let slot = self
+                .metadata
+                .spend_index(input_idx)
+                .and_then(|spend_idx| ordered.get_mut(spend_idx))
+                .ok_or_else(|| {
+                    Error::Other(format!(
+                        "No spend description for sapling input {input_idx}"
+                    ))
+                })?;
+            *slot = Some(signature);
+        }
+        let ordered = ordered
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                Error::Other(
+                    "Missing spend authorization signature".to_string(),
+                )
+            })?;
+        self.masp_tx = apply_spend_signatures(&self.masp_tx, ordered)?;
+        Ok(())
+    }
+}
+
 #[cfg(feature = "testing")]
 #[derive(Clone, Copy, Debug)]
 enum LoadOrSaveProofs {
@@ -298,6 +353,63 @@ fn partial_deauthorize(
     ))
 }
 
+/// Compute the digest that the spend authorization signatures and the binding
+/// signature of the given shielded transaction commit to
+pub fn shielded_sighash(transaction: &Transaction) -> Option<[u8; 32]> {
+    // Partially deauthorize the transparent bundle
+    let unauth_tx_data = partial_deauthorize(transaction.deref())?;
+
+    let txid_parts = unauth_tx_data.digest(TxIdDigester);
+    // the commitment being signed is shared across all Sapling inputs; once
+    // V4 transactions are deprecated this should just be the txid, but
+    // for now we need to continue to compute it here.
+    let sighash =
+        signature_hash(&unauth_tx_data, &SignableInput::Shielded, &txid_parts);
+    Some(*sighash.as_ref())
+}
+
+/// Replace the spend authorization signatures of the given shielded
+/// transaction with those produced by an external signer, such as a hardware
+/// wallet, in the order of the transaction's spend descriptions. The
+/// resulting transaction is verified before being returned.
+pub fn apply_spend_signatures(
+    transaction: &Transaction,
+    signatures: Vec<Signature>,
+) -> Result<Transaction, Error> {
+    let mut sapling_bundle =
+        transaction.sapling_bundle().cloned().ok_or_else(|| {
+            Error::Other("The transaction has no shielded part".to_string())
+        })?;
+    if sapling_bundle.shielded_spends.len() != signatures.len() {
+        return Err(Error::Other(format!(
+            "Expected {} spend authorization signatures, but got {}",
+            sapling_bundle.shielded_spends.len(),
+            signatures.len()
+        )));
+    }
+    for (spend, signature) in
+        sapling_bundle.shielded_spends.iter_mut().zip(signatures)
+    {
+        spend.spend_auth_sig = signature;
+    }
+    let transaction = TransactionData::from_parts(
+        transaction.version(),
+        transaction.consensus_branch_id(),
+        transaction.lock_time(),
+        transaction.expiry_height(),
+        transaction.transparent_bundle().cloned(),
+        Some(sapling_bundle),
+    )
+    .freeze()
+    .map_err(|e| Error::Other(e.to_string()))?;
+    if !verify_shielded_tx(&transaction) {
+        return Err(Error::Other(
+            "The spend authorization signatures do not verify".to_string(),
+        ));
+    }
+    Ok(transaction)
+}
+
 /// Verify a shielded transaction.
 pub fn verify_shielded_tx(transaction: &Transaction) -> bool {
     tracing::info!("entered verify_shielded_tx()");
@@ -307,21 +419,12 @@ pub fn verify_shielded_tx(transaction: &Transaction) -> bool {
     } else {
         return false;
     };
-    let tx_data = transaction.deref();
 
-    // Partially deauthorize the transparent bundle
-    let unauth_tx_data = match partial_deauthorize(tx_data) {
-        Some(tx_data) => tx_data,
+    let sighash = match shielded_sighash(transaction) {
+        Some(sighash) => sighash,
         None => return false,
     };
 
-    let txid_parts = unauth_tx_data.digest(TxIdDigester);
-    // the commitment being signed is shared across all Sapling inputs; once
-    // V4 transactions are deprecated this should just be the txid, but
-    // for now we need to continue to compute it here.
-    let sighash =
-        signature_hash(&unauth_tx_data, &SignableInput::Shielded, &txid_parts);
-
     tracing::info!("sighash computed");
 
     let PVKs {
@@ -334,7 +437,7 @@ pub fn verify_shielded_tx(transaction: &Transaction) -> bool {
     let spends_valid = sapling_bundle
         .shielded_spends
         .iter()
-        .all(|spend| check_spend(spend, sighash.as_ref(), &mut ctx, spend_vk));
+        .all(|spend| check_spend(spend, &sighash, &mut ctx, spend_vk));
     let converts_valid = sapling_bundle
         .shielded_converts
         .iter()
@@ -359,7 +462,7 @@ pub fn verify_shielded_tx(transaction: &Transaction) -> bool {
 
     let result = ctx.final_check(
         assets_and_values,
-        sighash.as_ref(),
+        &sighash,
         sapling_bundle.authorization.binding_sig,
     );
     tracing::info!("final check result {result}");
@@ -379,7 +482,9 @@ pub fn get_params_dir() -> PathBuf {
 
 /// Freeze a Builder into the format necessary for inclusion in a Tx. This is
 /// the format used by hardware wallets to validate a MASP Transaction.
-struct WalletMap;
+/// Maps the spending keys of a builder to their viewing keys, or to the
+/// given viewing key if the spends are authorized by an external signer
+struct WalletMap(Option<ExtendedFullViewingKey>);
 
 impl<P1>
     masp_primitives::transaction::components::sapling::builder::MapBuilder<
@@ -392,7 +497,7 @@ impl<P1>
     fn map_params(&self, _s: P1) {}
 
     fn map_key(&self, s: ExtendedSpendingKey) -> ExtendedFullViewingKey {
-        (&s).into()
+        self.0.unwrap_or_else(|| (&s).into())
     }
 }
 
@@ -413,6 +518,200 @@ impl<P1, R1, N1>
     fn map_notifier(&self, _s: N1) {}
 }
 
+/// The owner of the notes spent by a shielded transfer
+struct Spender {
+    /// The key with which the builder makes the spends. For an external
+    /// signer, only its nullifier deriving and outgoing viewing keys are
+    /// genuine, and the spend authorization signatures made with it must be
+    /// replaced with the signer's.
+    sk: ExtendedSpendingKey,
+    /// The viewing key of the spent notes
+    xfvk: ExtendedFullViewingKey,
+    /// The proof generation key exported by an external signer
+    proof_generation_key: Option<ProofGenerationKey>,
+}
+
+impl Spender {
+    /// Spend notes with a spending key that we possess
+    fn local(sk: ExtendedSpendingKey) -> Self {
+        Self {
+            sk,
+            xfvk: (&sk).into(),
+            proof_generation_key: None,
+        }
+    }
+
+    /// Spend the notes of the given viewing key, whose spend authorizing key
+    /// is held by an external signer that exported the given proof
+    /// generation key
+    fn external(
+        xfvk: ExtendedFullViewingKey,
+        proof_generation_key: ProofGenerationKey,
+    ) -> Self {
+        Self {
+            sk: external_spending_key(&xfvk, &proof_generation_key),
+            xfvk,
+            proof_generation_key: Some(proof_generation_key),
+        }
+    }
+}
+
+/// Make a spending key with which a builder can make the spends of notes
+/// whose spend authorizing key is held by an external signer. It derives the
+/// same nullifiers and outgoing ciphertexts as the signer's key, but its
+/// spend authorizing key is random.
+fn external_spending_key(
+    xfvk: &ExtendedFullViewingKey,
+    proof_generation_key: &ProofGenerationKey,
+) -> ExtendedSpendingKey {
+    let mut sk = ExtendedSpendingKey::master(&[]);
+    sk.expsk = ExpandedSpendingKey {
+        ask: jubjub::Fr::random(OsRng),
+        nsk: proof_generation_key.nsk,
+        ovk: xfvk.fvk.ovk,
+    };
+    sk
+}
+
+/// A prover that records the randomizers of the spends that it proves. If
+/// given the proof generation key of an external signer, it proves the
+/// spends with it instead of with the builder's spending key.
+struct SpendRecordingProver<'a> {
+    /// The prover doing the actual work
+    inner: &'a LocalTxProver,
+    /// The proof generation key of an external signer
+    proof_generation_key: Option<ProofGenerationKey>,
+    /// The randomizers of the proven spends
+    alphas: std::sync::Mutex<Vec<jubjub::Fr>>,
+}
+
+impl<'a> SpendRecordingProver<'a> {
+    fn new(
+        inner: &'a LocalTxProver,
+        proof_generation_key: Option<ProofGenerationKey>,
+    ) -> Self {
+        Self {
+            inner,
+            proof_generation_key,
+            alphas: Default::default(),
+        }
+    }
+
+    /// Get the randomizers of the spends of the given transaction, in the
+    /// order of its spend descriptions. Each spend is matched to its
+    /// randomizer through its randomized verification key, derived from the
+    /// given spend validating key.
+    fn spend_alphas(
+        self,
+        transaction: &Transaction,
+        ak: jubjub::SubgroupPoint,
+    ) -> Vec<jubjub::Fr> {
+        let alphas = self
+            .alphas
+            .into_inner()
+            .expect("the prover is not used concurrently");
+        transaction
+            .sapling_bundle()
+            .map_or(&[][..], |x| &x.shielded_spends[..])
+            .iter()
+            .filter_map(|spend| {
+                alphas.iter().copied().find(|alpha| {
+                    PublicKey(ak.into())
+                        .randomize(*alpha, SPENDING_KEY_GENERATOR)
+                        .0
+                        == spend.rk.0
+                })
+            })
+            .collect()
+    }
+}
+
+impl TxProver for SpendRecordingProver<'_> {
+    type SaplingProvingContext =
+        <LocalTxProver as TxProver>::SaplingProvingContext;
+
+    fn new_sapling_proving_context(&self) -> Self::SaplingProvingContext {
+        self.inner.new_sapling_proving_context()
+    }
+
+    fn spend_proof(
+        &self,
+        ctx: &mut Self::SaplingProvingContext,
+        proof_generation_key: ProofGenerationKey,
+        diversifier: Diversifier,
+        rseed: Rseed,
+        ar: jubjub::Fr,
+        asset_type: AssetType,
+        value: u64,
+        anchor: bls12_381::Scalar,
+        merkle_path: MerklePath<Node>,
+    ) -> Result<([u8; GROTH_PROOF_SIZE], jubjub::ExtendedPoint, PublicKey), ()>
+    {
+        self.alphas
+            .lock()
+            .expect("the prover is not used concurrently")
+            .push(ar);
+        self.inner.spend_proof(
+            ctx,
+            self.proof_generation_key
+                .clone()
+                .unwrap_or(proof_generation_key),
+            diversifier,
+            rseed,
+            ar,
+            asset_type,
+            value,
+            anchor,
+            merkle_path,
+        )
+    }
+
+    fn output_proof(
+        &self,
+        ctx: &mut Self::SaplingProvingContext,
+        esk: jubjub::Fr,
+        payment_address: masp_primitives::sapling::PaymentAddress,
+        rcm: jubjub::Fr,
+        asset_type: AssetType,
+        value: u64,
+    ) -> ([u8; GROTH_PROOF_SIZE], jubjub::ExtendedPoint) {
+        self.inner.output_proof(
+            ctx,
+            esk,
+            payment_address,
+            rcm,
+            asset_type,
+            value,
+        )
+    }
+
+    fn convert_proof(
+        &self,
+        ctx: &mut Self::SaplingProvingContext,
+        allowed_conversion: AllowedConversion,
+        value: u64,
+        anchor: bls12_381::Scalar,
+        merkle_path: MerklePath<Node>,
+    ) -> Result<([u8; GROTH_PROOF_SIZE], jubjub::ExtendedPoint), ()> {
+        self.inner.convert_proof(
+            ctx,
+            allowed_conversion,
+            value,
+            anchor,
+            merkle_path,
+        )
+    }
+
+    fn binding_sig(
+        &self,
+        ctx: &mut Self::SaplingProvingContext,
+        assets_and_values: &I128Sum,
+        sighash: &[u8; 32],
+    ) -> Result<Signature, ()> {
+        self.inner.binding_sig(ctx, assets_and_values, sighash)
+    }
+}
+
 /// Abstracts platform specific details away from the logic of shielded pool
 /// operations.
 #[cfg_attr(feature = "async-send", async_trait::async_trait)]
@@ -473,6 +772,255 @@ pub fn is_amount_required(src: I128Sum, dest: I128Sum, delta: I128Sum) -> bool {
     false
 }
 
+/// Encode the given text into a memo that can be attached to the outputs of
+/// a shielded transfer. The text must not be longer than 512 bytes.
+pub fn encode_memo(text: &str) -> Result<MemoBytes, Error> {
+    MemoBytes::from_bytes(text.as_bytes()).map_err(|_| {
+        Error::Other(format!(
+            "The memo must not be longer than 512 bytes, but it has {} bytes",
+            text.len()
+        ))
+    })
+}
+
+/// Decode the given memo into a human-readable string. Text memos are
+/// returned as is, any other non-empty memo is returned hex-encoded.
+pub fn decode_memo(memo: &MemoBytes) -> Option<String> {
+    match Memo::try_from(memo.clone()) {
+        Ok(Memo::Empty) => None,
+        Ok(Memo::Text(text)) => Some(text.to_string()),
+        _ => {
+            let bytes = memo.as_slice();
+            let len = bytes.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+            Some(HEXLOWER.encode(&bytes[..len]))
+        }
+    }
+}
+
+/// A note received by one of our viewing keys
+#[derive(Debug, Clone)]
+pub struct ReceivedNote {
+    /// The position of the note in the commitment tree
+    pub pos: usize,
+    /// The token held by the note
+    pub token: Address,
+    /// The epoch of the note's asset type
+    pub epoch: Epoch,
+    /// The amount of the token held by the note
+    pub amount: token::Amount,
+    /// The memo attached to the note
+    pub memo: MemoBytes,
+    /// Whether the note has been spent
+    pub spent: bool,
+}
+
+/// A conversion of the asset type of a token from one epoch to a later one,
+/// as applied by a shielded transaction to the notes that it spent
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub struct ConversionRecord {
+    /// The converted token
+    pub token: Address,
+    /// The epoch of the asset type that was converted
+    pub from_epoch: Epoch,
+    /// The epoch of the asset type that it was converted into
+    pub to_epoch: Epoch,
+}
+
+/// The notes of our viewing keys that were involved in a shielded transaction
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct ShieldedTxRecord {
+    /// The epoch in which the transaction was applied
+    pub epoch: Epoch,
+    /// The hash of the transaction, i.e. of the inner transaction for
+    /// wrapped transactions. Unknown for records saved by clients that
+    /// predate it.
+    pub tx_hash: Option<Hash>,
+    /// The transparent part of the transaction
+    pub transparent: TransparentTransfer,
+    /// The positions of the notes that our viewing keys received
+    pub received: BTreeSet<usize>,
+    /// The positions of our notes that the transaction spent
+    pub spent: BTreeSet<usize>,
+    /// The conversions that the transaction applied to the asset types of
+    /// our spent notes. Their amounts are hidden, but they account for the
+    /// difference in asset epochs between the spent and received notes,
+    /// including any rewards.
+    pub conversions: Vec<ConversionRecord>,
+}
+
+impl ShieldedTxRecord {
+    /// Get the transparent side of the transaction, if it shields or
+    /// unshields tokens
+    pub fn counterpart(&self) -> Option<TransparentCounterpart> {
+        let transfer = &self.transparent;
+        let (kind, address) = if transfer.source != MASP {
+            (TransparentKind::Shielding, &transfer.source)
+        } else if transfer.target != MASP {
+            (TransparentKind::Unshielding, &transfer.target)
+        } else {
+            return None;
+        };
+        Some(TransparentCounterpart {
+            kind,
+            address: address.clone(),
+            token: transfer.token.clone(),
+            amount: transfer.amount,
+        })
+    }
+}
+
+/// Whether a transparent address sent tokens into or received tokens from
+/// the shielded pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransparentKind {
+    /// Tokens were moved from a transparent address into the shielded pool
+    Shielding,
+    /// Tokens were moved from the shielded pool to a transparent address
+    Unshielding,
+}
+
+/// The transparent side of a shielding or unshielding transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransparentCounterpart {
+    /// The direction in which the tokens moved
+    pub kind: TransparentKind,
+    /// The transparent source or target
+    pub address: Address,
+    /// The transferred token
+    pub token: Address,
+    /// The transferred amount
+    pub amount: token::DenominatedAmount,
+}
+
+/// Whether a note was received or spent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteDirection {
+    /// The note was created by the transaction
+    Received,
+    /// The note was consumed by the transaction
+    Spent,
+}
+
+impl Display for NoteDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Received => write!(f, "received"),
+            Self::Spent => write!(f, "spent"),
+        }
+    }
+}
+
+/// An entry in the shielded history of a viewing key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// The height of the block containing the transaction
+    pub height: BlockHeight,
+    /// The index of the transaction in its block
+    pub index: TxIndex,
+    /// The epoch in which the transaction was applied
+    pub epoch: Epoch,
+    /// The hash of the transaction, if known
+    pub tx_hash: Option<Hash>,
+    /// Whether the note was received or spent
+    pub direction: NoteDirection,
+    /// The position of the note in the commitment tree
+    pub note_pos: usize,
+    /// The token held by the note
+    pub token: Address,
+    /// The epoch of the note's asset type
+    pub asset_epoch: Epoch,
+    /// The amount of the token held by the note
+    pub amount: token::Amount,
+    /// The decoded memo attached to the note, if any
+    pub memo: Option<String>,
+    /// The conversions applied by the transaction to our spent notes
+    pub conversions: Vec<ConversionRecord>,
+    /// The transparent side of the transaction, if any
+    pub counterpart: Option<TransparentCounterpart>,
+}
+
+/// The header of the CSV export of a shielded history
+pub const HISTORY_CSV_HEADER: &str =
+    "height,index,epoch,tx_hash,direction,note_pos,token,asset_epoch,amount,\
+     memo,conversions,counterpart_kind,counterpart_address,counterpart_token,\
+     counterpart_amount";
+
+/// Quote the given CSV field if it contains special characters
+fn csv_field(field: String) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+impl HistoryEntry {
+    /// Render this entry as a CSV record with the columns of
+    /// [`HISTORY_CSV_HEADER`]
+    pub fn to_csv_record(&self) -> String {
+        let (kind, address, token, amount) = match &self.counterpart {
+            Some(counterpart) => (
+                match counterpart.kind {
+                    TransparentKind::Shielding => "shielding",
+                    TransparentKind::Unshielding => "unshielding",
+                }
+                .to_string(),
+                counterpart.address.to_string(),
+                counterpart.token.to_string(),
+                counterpart.amount.to_string(),
+            ),
+            None => Default::default(),
+        };
+        [
+            self.height.to_string(),
+            self.index.0.to_string(),
+            self.epoch.to_string(),
+            self.tx_hash
+                .map(|hash| hash.to_string())
+                .unwrap_or_default(),
+            self.direction.to_string(),
+            self.note_pos.to_string(),
+            self.token.to_string(),
+            self.asset_epoch.to_string(),
+            self.amount.to_string_native(),
+            self.memo.clone().unwrap_or_default(),
+            self.conversions
+                .iter()
+                .map(|conversion| {
+                    format!(
+                        "{}:{}->{}",
+                        conversion.token,
+                        conversion.from_epoch,
+                        conversion.to_epoch
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(";"),
+            kind,
+            address,
+            token,
+            amount,
+        ]
+        .into_iter()
+        .map(csv_field)
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
 // #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 // pub struct MaspAmount {
 //     pub asset: Address,
@@ -591,6 +1139,349 @@ pub type TransferDelta = HashMap<Address, MaspChange>;
 /// Represents the changes that were made to a list of shielded accounts
 pub type TransactionDelta = HashMap<ViewingKey, MaspAmount>;
 
+/// The type of the outputs of an authorized shielded transaction
+type AuthorizedOutput = OutputDescription<<<Authorized as Authorization>::SaplingAuth as masp_primitives::transaction::components::sapling::Authorization>::Proof>;
+
+/// A note that one of our viewing keys managed to decrypt
+#[derive(Debug, Clone)]
+pub struct DecryptedNote {
+    /// The viewing key that decrypted the note
+    pub vk: ViewingKey,
+    /// The decrypted note
+    pub note: Note,
+    /// The diversifier of the payment address the note was sent to
+    pub diversifier: Diversifier,
+    /// The memo attached to the note
+    pub memo: MemoBytes,
+}
+
+/// Maps the indices of the outputs of a shielded transaction to the notes
+/// that were decrypted from them
+pub type DecryptedNotes = BTreeMap<usize, DecryptedNote>;
+
+/// Try to decrypt the given shielded output with each of the given viewing
+/// keys, stopping at the first one that succeeds
+fn decrypt_output(
+    so: &CompactOutput,
+    vks: &[ViewingKey],
+) -> Option<DecryptedNote> {
+    vks.iter().find_map(|vk| {
+        try_sapling_note_decryption::<_, CompactOutput>(
+            &NETWORK,
+            1.into(),
+            &PreparedIncomingViewingKey::new(&vk.ivk()),
+            so,
+        )
+        .map(|(note, pa, memo)| DecryptedNote {
+            vk: *vk,
+            note,
+            diversifier: *pa.diversifier(),
+            memo,
+        })
+    })
+}
+
+/// Trial-decrypt every given output of a shielded transaction with the
+/// given viewing keys. The outputs are processed in parallel when the
+/// `multicore` feature is enabled.
+pub fn trial_decrypt(
+    outputs: &[CompactOutput],
+    vks: &[ViewingKey],
+) -> DecryptedNotes {
+    if vks.is_empty() {
+        return DecryptedNotes::new();
+    }
+    #[cfg(feature = "multicore")]
+    let outputs = outputs.par_iter();
+    #[cfg(not(feature = "multicore"))]
+    let outputs = outputs.iter();
+    outputs
+        .enumerate()
+        .filter_map(|(idx, so)| decrypt_output(so, vks).map(|note| (idx, note)))
+        .collect()
+}
+
+/// Make an error out of the failure to load the saved shielded context
+pub fn load_error(err: std::io::Error) -> Error {
+    Error::Other(format!("Failed to load the saved shielded context: {err}"))
+}
+
+/// Select the viewing keys whose birthday is not later than the given height
+fn active_viewing_keys(
+    vks: &[(ViewingKey, Option<BlockHeight>)],
+    height: BlockHeight,
+) -> Vec<ViewingKey> {
+    vks.iter()
+        .filter(|(_, birthday)| birthday.map_or(true, |b| b <= height))
+        .map(|(vk, _)| *vk)
+        .collect()
+}
+
+/// Shielded transactions keyed by the height of the block and the index
+/// within that block at which they were applied
+pub type ShieldedTxs =
+    BTreeMap<(BlockHeight, TxIndex), (Epoch, Transfer, Transaction)>;
+
+/// Query the number of shielded transactions accepted by the ledger, i.e.
+/// the index that the next accepted shielded transaction will have
+pub async fn query_shielded_head_txidx<C: Client + Sync>(
+    client: &C,
+) -> Result<u64, Error> {
+    // Construct the key where last transaction pointer is stored
+    let head_tx_key = Key::from(MASP.to_db_key())
+        .push(&HEAD_TX_KEY.to_owned())
+        .map_err(|k| {
+            Error::Other(format!("Cannot obtain a storage key: {}", k))
+        })?;
+    // Query for the index of the last accepted transaction
+    Ok(query_storage_value::<C, u64>(client, &head_tx_key)
+        .await
+        .unwrap_or(0))
+}
+
+/// Obtain a chronologically-ordered list of at most `limit` accepted
+/// shielded transactions from the ledger, starting from the given index. The
+/// ledger conceptually stores transactions as a vector. More concretely, the
+/// HEAD_TX_KEY location stores the index of the last accepted transaction
+/// and each transaction is stored at a key derived from its index.
+pub async fn query_shielded_txs<C: Client + Sync>(
+    client: &C,
+    last_txidx: u64,
+    limit: u64,
+) -> Result<ShieldedTxs, Error> {
+    // The address of the MASP account
+    let masp_addr = MASP;
+    let head_txidx = query_shielded_head_txidx(client)
+        .await?
+        .min(last_txidx.saturating_add(limit));
+    let mut shielded_txs = BTreeMap::new();
+    // Fetch the requested transactions that we do not have yet
+    for i in last_txidx..head_txidx {
+        // Construct the key for where the current transaction is stored
+        let current_tx_key = Key::from(masp_addr.to_db_key())
+            .push(&(TX_KEY_PREFIX.to_owned() + &i.to_string()))
+            .map_err(|e| {
+                Error::Other(format!("Cannot obtain a storage key {}", e))
+            })?;
+        // Obtain the current transaction
+        let (tx_epoch, tx_height, tx_index, current_tx, current_stx) =
+            query_storage_value::<
+                C,
+                (Epoch, BlockHeight, TxIndex, Transfer, Transaction),
+            >(client, &current_tx_key)
+            .await?;
+        // Collect the current transaction
+        shielded_txs
+            .insert((tx_height, tx_index), (tx_epoch, current_tx, current_stx));
+    }
+    Ok(shielded_txs)
+}
+
+/// A source of the shielded transactions accepted by the ledger, from which a
+/// [`ShieldedContext`] can be synced
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+pub trait ShieldedTxSource {
+    /// Get the number of shielded transactions available from this source,
+    /// i.e. the index in the ledger's list of shielded transactions that the
+    /// next one will have
+    async fn head_txidx(&self) -> Result<u64, Error>;
+
+    /// Obtain the compact blocks containing at most `limit` accepted
+    /// shielded transactions, starting from the given index in the ledger's
+    /// list of shielded transactions. The returned blocks contain exactly
+    /// the transactions from that index onwards, in chronological order.
+    async fn compact_blocks(
+        &self,
+        last_txidx: u64,
+        limit: u64,
+    ) -> Result<Vec<CompactBlock>, Error>;
+}
+
+/// Obtains shielded transactions by querying the storage of a ledger node
+pub struct LedgerTxSource<'a, C>(pub &'a C);
+
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+impl<'a, C: Client + Sync> ShieldedTxSource for LedgerTxSource<'a, C> {
+    async fn head_txidx(&self) -> Result<u64, Error> {
+        query_shielded_head_txidx(self.0).await
+    }
+
+    async fn compact_blocks(
+        &self,
+        last_txidx: u64,
+        limit: u64,
+    ) -> Result<Vec<CompactBlock>, Error> {
+        let txs = query_shielded_txs(self.0, last_txidx, limit).await?;
+        Ok(CompactBlock::from_shielded_txs(txs, last_txidx))
+    }
+}
+
+/// An output of a shielded transaction, stripped down to the data that is
+/// needed to trial-decrypt it and to add its note to the commitment tree
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct CompactOutput {
+    /// The commitment to the note
+    pub cmu: [u8; 32],
+    /// The ephemeral public key with which the note was encrypted
+    pub ephemeral_key: [u8; 32],
+    /// The encrypted note, along with its memo
+    pub enc_ciphertext: [u8; ENC_CIPHERTEXT_SIZE],
+}
+
+impl CompactOutput {
+    /// Strip down the given output of a shielded transaction
+    pub fn new(output: &AuthorizedOutput) -> Self {
+        Self {
+            cmu: output.cmu.to_repr(),
+            ephemeral_key: output.ephemeral_key.0,
+            enc_ciphertext: output.enc_ciphertext,
+        }
+    }
+}
+
+impl<P: masp_primitives::consensus::Parameters>
+    ShieldedOutput<SaplingDomain<P>, ENC_CIPHERTEXT_SIZE> for CompactOutput
+{
+    fn ephemeral_key(&self) -> EphemeralKeyBytes {
+        EphemeralKeyBytes(self.ephemeral_key)
+    }
+
+    fn cmstar_bytes(&self) -> [u8; 32] {
+        self.cmu
+    }
+
+    fn enc_ciphertext(&self) -> &[u8; ENC_CIPHERTEXT_SIZE] {
+        &self.enc_ciphertext
+    }
+}
+
+/// The transparent part of the transfer carrying a shielded transaction
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct TransparentTransfer {
+    /// The address that spent the tokens
+    pub source: Address,
+    /// The address that received the tokens
+    pub target: Address,
+    /// The address of the transferred token
+    pub token: Address,
+    /// The transferred amount
+    pub amount: token::DenominatedAmount,
+}
+
+impl From<&Transfer> for TransparentTransfer {
+    fn from(transfer: &Transfer) -> Self {
+        Self {
+            source: transfer.source.clone(),
+            target: transfer.target.clone(),
+            token: transfer.token.clone(),
+            amount: transfer.amount,
+        }
+    }
+}
+
+/// A shielded transaction stripped down to the data that is needed to scan
+/// it: the nullifiers of the notes it spends, the commitments and
+/// ciphertexts of the notes it creates and a summary of its transparent part
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct CompactTx {
+    /// The index of this transaction in the ledger's list of shielded
+    /// transactions
+    pub txidx: u64,
+    /// The index of this transaction within its block
+    pub index: TxIndex,
+    /// The epoch in which this transaction was applied
+    pub epoch: Epoch,
+    /// The transparent part of the transfer carrying this transaction
+    pub transparent: TransparentTransfer,
+    /// The nullifiers of the notes spent by this transaction
+    pub nullifiers: Vec<Nullifier>,
+    /// The outputs of this transaction
+    pub outputs: Vec<CompactOutput>,
+    /// The number of conversions applied by this transaction
+    pub conversions: usize,
+}
+
+impl CompactTx {
+    /// Strip down the given shielded transaction, the `txidx`-th one accepted
+    /// by the ledger
+    pub fn new(
+        txidx: u64,
+        index: TxIndex,
+        epoch: Epoch,
+        transfer: &Transfer,
+        shielded: &Transaction,
+    ) -> Self {
+        let bundle = shielded.sapling_bundle();
+        Self {
+            txidx,
+            index,
+            epoch,
+            transparent: transfer.into(),
+            nullifiers: bundle.map_or(vec![], |x| {
+                x.shielded_spends.iter().map(|ss| ss.nullifier).collect()
+            }),
+            outputs: bundle.map_or(vec![], |x| {
+                x.shielded_outputs.iter().map(CompactOutput::new).collect()
+            }),
+            conversions: bundle.map_or(0, |x| x.shielded_converts.len()),
+        }
+    }
+}
+
+/// The shielded transactions of a block, in the order in which they were
+/// applied
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct CompactBlock {
+    /// The height of the block
+    pub height: BlockHeight,
+    /// The shielded transactions of the block
+    pub txs: Vec<CompactTx>,
+}
+
+impl CompactBlock {
+    /// Group the given shielded transactions by block. The first transaction
+    /// is expected to be at the given index in the ledger's list of shielded
+    /// transactions.
+    pub fn from_shielded_txs(txs: ShieldedTxs, first_txidx: u64) -> Vec<Self> {
+        let mut blocks: Vec<Self> = vec![];
+        for (txidx, ((height, index), (epoch, transfer, shielded))) in
+            (first_txidx..).zip(txs)
+        {
+            let tx = CompactTx::new(txidx, index, epoch, &transfer, &shielded);
+            match blocks.last_mut() {
+                Some(block) if block.height == height => block.txs.push(tx),
+                _ => blocks.push(Self {
+                    height,
+                    txs: vec![tx],
+                }),
+            }
+        }
+        blocks
+    }
+
+    /// Restrict the given compact blocks to the shielded transactions from
+    /// the given index in the ledger's list of shielded transactions
+    /// onwards, up to `limit` of them. Blocks left empty are dropped.
+    pub fn select(
+        blocks: impl IntoIterator<Item = Self>,
+        last_txidx: u64,
+        limit: u64,
+    ) -> Vec<Self> {
+        let end_txidx = last_txidx.saturating_add(limit);
+        blocks
+            .into_iter()
+            .filter_map(|mut block| {
+                block
+                    .txs
+                    .retain(|tx| (last_txidx..end_txidx).contains(&tx.txidx));
+                (!block.txs.is_empty()).then_some(block)
+            })
+            .collect()
+    }
+}
+
 /// Represents the current state of the shielded pool from the perspective of
 /// the chosen viewing keys.
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
@@ -625,6 +1516,12 @@ pub struct ShieldedContext<U: ShieldedUtils> {
     pub asset_types: HashMap<AssetType, (Address, MaspDenom, Epoch)>,
     /// Maps note positions to their corresponding viewing keys
     pub vk_map: HashMap<usize, ViewingKey>,
+    /// Maps viewing keys to the height of the first block that can contain
+    /// notes for them. Earlier blocks are not trial-decrypted with these keys.
+    pub birthdays: HashMap<ViewingKey, BlockHeight>,
+    /// Records the notes received and spent by each transaction that
+    /// involves our viewing keys
+    pub tx_records: BTreeMap<(BlockHeight, TxIndex), ShieldedTxRecord>,
 }
 
 /// Default implementation to ease construction of TxContexts. Derive cannot be
@@ -645,13 +1542,155 @@ impl<U: ShieldedUtils + Default> Default for ShieldedContext<U> {
             delta_map: BTreeMap::default(),
             asset_types: HashMap::default(),
             vk_map: HashMap::default(),
+            birthdays: HashMap::default(),
+            tx_records: BTreeMap::default(),
+        }
+    }
+}
+
+/// A shielded context as saved before the encoding of the saved contexts was
+/// versioned
+#[derive(BorshDeserialize)]
+struct LegacyShieldedContext {
+    last_txidx: u64,
+    tree: CommitmentTree<Node>,
+    pos_map: HashMap<ViewingKey, BTreeSet<usize>>,
+    nf_map: HashMap<Nullifier, usize>,
+    note_map: HashMap<usize, Note>,
+    memo_map: HashMap<usize, MemoBytes>,
+    div_map: HashMap<usize, Diversifier>,
+    witness_map: HashMap<usize, IncrementalWitness<Node>>,
+    delta_map: BTreeMap<
+        (BlockHeight, TxIndex),
+        (Epoch, TransferDelta, TransactionDelta),
+    >,
+    spents: HashSet<usize>,
+    asset_types: HashMap<AssetType, (Address, MaspDenom, Epoch)>,
+    vk_map: HashMap<usize, ViewingKey>,
+}
+
+/// A shielded context as saved with version 1 of the encoding, whose
+/// transaction records lacked the transaction hashes and conversions
+#[derive(BorshDeserialize)]
+struct ShieldedContextV1 {
+    legacy: LegacyShieldedContext,
+    birthdays: HashMap<ViewingKey, BlockHeight>,
+    tx_records: BTreeMap<(BlockHeight, TxIndex), ShieldedTxRecordV1>,
+}
+
+/// A transaction record as saved with version 1 of the encoding
+#[derive(BorshDeserialize)]
+struct ShieldedTxRecordV1 {
+    epoch: Epoch,
+    transfer: Transfer,
+    received: BTreeSet<usize>,
+    spent: BTreeSet<usize>,
+    _conversions: usize,
+}
+
+impl From<ShieldedTxRecordV1> for ShieldedTxRecord {
+    fn from(record: ShieldedTxRecordV1) -> Self {
+        Self {
+            epoch: record.epoch,
+            tx_hash: None,
+            transparent: (&record.transfer).into(),
+            received: record.received,
+            spent: record.spent,
+            conversions: vec![],
+        }
+    }
+}
+
+impl<U: ShieldedUtils> ShieldedContext<U> {
+    /// Make a context out of the fields of a context saved with an earlier
+    /// version of the encoding
+    fn from_legacy(
+        utils: U,
+        legacy: LegacyShieldedContext,
+        birthdays: HashMap<ViewingKey, BlockHeight>,
+        tx_records: BTreeMap<(BlockHeight, TxIndex), ShieldedTxRecord>,
+    ) -> Self {
+        Self {
+            utils,
+            last_txidx: legacy.last_txidx,
+            tree: legacy.tree,
+            pos_map: legacy.pos_map,
+            nf_map: legacy.nf_map,
+            note_map: legacy.note_map,
+            memo_map: legacy.memo_map,
+            div_map: legacy.div_map,
+            witness_map: legacy.witness_map,
+            delta_map: legacy.delta_map,
+            spents: legacy.spents,
+            asset_types: legacy.asset_types,
+            vk_map: legacy.vk_map,
+            birthdays,
+            tx_records,
+        }
+    }
+
+    /// Encode this context for saving, prefixed with the version of the
+    /// encoding
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = CONTEXT_MAGIC.to_vec();
+        bytes.push(CONTEXT_VERSION);
+        self.serialize(&mut bytes)
+            .expect("cannot serialize shielded context");
+        bytes
+    }
+
+    /// Decode a context saved with [`ShieldedContext::encode`] by this or an
+    /// earlier version of the client, and attach the given utils to it
+    pub fn decode(utils: U, bytes: &[u8]) -> std::io::Result<Self> {
+        let Some(versioned) = bytes.strip_prefix(CONTEXT_MAGIC) else {
+            // The context was saved before versioning, and thus has neither
+            // birthdays nor transaction records
+            let legacy = LegacyShieldedContext::deserialize(&mut &bytes[..])?;
+            return Ok(Self::from_legacy(
+                utils,
+                legacy,
+                HashMap::default(),
+                BTreeMap::default(),
+            ));
+        };
+        match versioned.split_first() {
+            Some((&CONTEXT_VERSION, mut encoded)) => Ok(Self {
+                utils,
+                ..Self::deserialize(&mut encoded)?
+            }),
+            // The transaction records of version 1 lack the transaction
+            // hashes and the conversions, which cannot be recovered without
+            // rescanning
+            Some((1, mut encoded)) => {
+                let v1 = ShieldedContextV1::deserialize(&mut encoded)?;
+                let tx_records = v1
+                    .tx_records
+                    .into_iter()
+                    .map(|(key, record)| (key, record.into()))
+                    .collect();
+                Ok(Self::from_legacy(
+                    utils,
+                    v1.legacy,
+                    v1.birthdays,
+                    tx_records,
+                ))
+            }
+            Some((version, _)) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "The shielded context was saved with version {version} of \
+                     its encoding, which this client does not support"
+                ),
+            )),
+            None => Err(std::io::ErrorKind::UnexpectedEof.into()),
         }
     }
 }
 
 impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
     /// Try to load the last saved shielded context from the given context
-    /// directory. If this fails, then leave the current context unchanged.
+    /// directory. If no context was saved yet, or if this fails, then leave
+    /// the current context unchanged.
     pub async fn load(&mut self) -> std::io::Result<()> {
         self.utils.clone().load(self).await
     }
@@ -677,6 +1716,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         self.spents.extend(new_ctx.spents);
         self.asset_types.extend(new_ctx.asset_types);
         self.vk_map.extend(new_ctx.vk_map);
+        self.birthdays.extend(new_ctx.birthdays);
         // The deltas are the exception because different keys can reveal
         // different parts of the same transaction. Hence each delta needs to be
         // merged separately.
@@ -688,13 +1728,53 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             tfer_delta.extend(ntfer_delta);
             tx_delta.extend(ntx_delta);
         }
+        // Likewise, different keys can own different notes of the same
+        // transaction
+        for (key, nrecord) in new_ctx.tx_records {
+            match self.tx_records.entry(key) {
+                btree_map::Entry::Occupied(mut entry) => {
+                    let record = entry.get_mut();
+                    record.received.extend(nrecord.received);
+                    record.spent.extend(nrecord.spent);
+                }
+                btree_map::Entry::Vacant(entry) => {
+                    entry.insert(nrecord);
+                }
+            }
+        }
+    }
+
+    /// Set the height of the first block that can contain notes for the
+    /// given viewing key. Shielded transactions in earlier blocks will not be
+    /// trial-decrypted with this key.
+    pub fn set_birthday(&mut self, vk: ViewingKey, height: BlockHeight) {
+        self.birthdays.insert(vk, height);
     }
 
     /// Fetch the current state of the multi-asset shielded pool into a
-    /// ShieldedContext
+    /// ShieldedContext. The transactions are downloaded and scanned in
+    /// batches of [`SYNC_CHECKPOINT_INTERVAL`], after each of which the
+    /// context is saved so that an interrupted fetch can resume from the last
+    /// saved batch.
     pub async fn fetch<C: Client + Sync>(
         &mut self,
         client: &C,
+        io: &impl Io,
+        sks: &[ExtendedSpendingKey],
+        fvks: &[ViewingKey],
+    ) -> Result<(), Error> {
+        self.fetch_from(client, io, &LedgerTxSource(client), sks, fvks)
+            .await
+    }
+
+    /// Like [`ShieldedContext::fetch`], but obtains the shielded transactions
+    /// from the given source, e.g. a MASP indexer, instead of from the
+    /// ledger. The client is still used to decode asset types.
+    pub async fn fetch_from<C: Client + Sync, S: ShieldedTxSource + Sync>(
+        &mut self,
+        client: &C,
+        io: &impl Io,
+        source: &S,
         sks: &[ExtendedSpendingKey],
         fvks: &[ViewingKey],
     ) -> Result<(), Error> {
@@ -716,94 +1796,125 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
 
         // If unknown keys are being used, we need to scan older transactions
         // for any unspent notes
-        let (txs, mut tx_iter);
         if !unknown_keys.is_empty() {
-            // Load all transactions accepted until this point
-            txs = Self::fetch_shielded_transfers(client, 0).await?;
-            tx_iter = txs.iter();
             // Do this by constructing a shielding context only for unknown keys
             let mut tx_ctx = Self {
                 utils: self.utils.clone(),
+                birthdays: self.birthdays.clone(),
                 ..Default::default()
             };
             for vk in unknown_keys {
                 tx_ctx.pos_map.entry(vk).or_insert_with(BTreeSet::new);
             }
-            // Update this unknown shielded context until it is level with self
-            while tx_ctx.last_txidx != self.last_txidx {
-                if let Some(((height, idx), (epoch, tx, stx))) = tx_iter.next()
-                {
-                    tx_ctx
-                        .scan_tx(client, *height, *idx, *epoch, tx, stx)
-                        .await?;
-                } else {
-                    break;
-                }
-            }
+            // Update this unknown shielded context until it is level with
+            // self. This context must not be saved as it would overwrite
+            // the current one.
+            tx_ctx
+                .sync(client, io, source, self.last_txidx, false)
+                .await?;
             // Merge the context data originating from the unknown keys into the
             // current context
             self.merge(tx_ctx);
-        } else {
-            // Load only transactions accepted from last_txid until this point
-            txs =
-                Self::fetch_shielded_transfers(client, self.last_txidx).await?;
-            tx_iter = txs.iter();
         }
         // Now that we possess the unspent notes corresponding to both old and
         // new keys up until tx_pos, proceed to scan the new transactions.
-        for ((height, idx), (epoch, tx, stx)) in &mut tx_iter {
-            self.scan_tx(client, *height, *idx, *epoch, tx, stx).await?;
+        let head_txidx = source.head_txidx().await?;
+        self.sync(client, io, source, head_txidx, true).await
+    }
+
+    /// Download and scan the shielded transactions from the last one scanned
+    /// by this context up to the given index, in batches of
+    /// [`SYNC_CHECKPOINT_INTERVAL`]. If `checkpoint` is set, the context is
+    /// saved after each batch.
+    async fn sync<C: Client + Sync, S: ShieldedTxSource + Sync>(
+        &mut self,
+        client: &C,
+        io: &impl Io,
+        source: &S,
+        target_txidx: u64,
+        checkpoint: bool,
+    ) -> Result<(), Error> {
+        while self.last_txidx < target_txidx {
+            let limit =
+                SYNC_CHECKPOINT_INTERVAL.min(target_txidx - self.last_txidx);
+            let blocks = source.compact_blocks(self.last_txidx, limit).await?;
+            if blocks.is_empty() {
+                // The source is behind the given index
+                break;
+            }
+            self.scan_blocks(client, &blocks).await?;
+            if checkpoint {
+                // Save the progress so that an interrupted fetch does not
+                // need to start over. Failing to save is unproblematic, as
+                // the progress can always be re-fetched.
+                let _ = self.save().await;
+            }
+            display_line!(
+                io,
+                "Scanned {}/{} shielded transactions",
+                self.last_txidx,
+                target_txidx
+            );
+        }
+        Ok(())
+    }
+
+    /// Scan the shielded transactions of the given chronologically-ordered
+    /// compact blocks. The trial-decryption of the transactions is done up
+    /// front, in parallel when the `multicore` feature is enabled, before
+    /// they are applied in order.
+    async fn scan_blocks<C: Client + Sync>(
+        &mut self,
+        client: &C,
+        blocks: &[CompactBlock],
+    ) -> Result<(), Error> {
+        let txs: Vec<_> = blocks
+            .iter()
+            .flat_map(|block| block.txs.iter().map(|tx| (block.height, tx)))
+            .collect();
+        let decrypted = self.trial_decrypt_txs(&txs);
+        for ((height, tx), notes) in txs.into_iter().zip(decrypted) {
+            self.apply_scanned_tx(client, height, tx, notes).await?;
         }
         Ok(())
     }
 
+    /// Trial-decrypt the outputs of the given shielded transactions with the
+    /// viewing keys of this context whose birthday has been reached. The
+    /// transactions are processed in parallel when the `multicore` feature is
+    /// enabled.
+    fn trial_decrypt_txs(
+        &self,
+        txs: &[(BlockHeight, &CompactTx)],
+    ) -> Vec<DecryptedNotes> {
+        let vks = self.viewing_keys_with_birthdays();
+        #[cfg(feature = "multicore")]
+        let txs = txs.par_iter();
+        #[cfg(not(feature = "multicore"))]
+        let txs = txs.iter();
+        txs.map(|(height, tx)| {
+            trial_decrypt(&tx.outputs, &active_viewing_keys(&vks, *height))
+        })
+        .collect()
+    }
+
+    /// Get the viewing keys of this context along with their birthdays
+    fn viewing_keys_with_birthdays(
+        &self,
+    ) -> Vec<(ViewingKey, Option<BlockHeight>)> {
+        self.pos_map
+            .keys()
+            .map(|vk| (*vk, self.birthdays.get(vk).copied()))
+            .collect()
+    }
+
     /// Obtain a chronologically-ordered list of all accepted shielded
-    /// transactions from the ledger. The ledger conceptually stores
-    /// transactions as a vector. More concretely, the HEAD_TX_KEY location
-    /// stores the index of the last accepted transaction and each transaction
-    /// is stored at a key derived from its index.
+    /// transactions from the ledger. See [`query_shielded_txs`].
     pub async fn fetch_shielded_transfers<C: Client + Sync>(
         client: &C,
         last_txidx: u64,
-    ) -> Result<
-        BTreeMap<(BlockHeight, TxIndex), (Epoch, Transfer, Transaction)>,
-        Error,
-    > {
-        // The address of the MASP account
-        let masp_addr = MASP;
-        // Construct the key where last transaction pointer is stored
-        let head_tx_key = Key::from(masp_addr.to_db_key())
-            .push(&HEAD_TX_KEY.to_owned())
-            .map_err(|k| {
-                Error::Other(format!("Cannot obtain a storage key: {}", k))
-            })?;
-        // Query for the index of the last accepted transaction
-        let head_txidx = query_storage_value::<C, u64>(client, &head_tx_key)
-            .await
-            .unwrap_or(0);
-        let mut shielded_txs = BTreeMap::new();
-        // Fetch all the transactions we do not have yet
-        for i in last_txidx..head_txidx {
-            // Construct the key for where the current transaction is stored
-            let current_tx_key = Key::from(masp_addr.to_db_key())
-                .push(&(TX_KEY_PREFIX.to_owned() + &i.to_string()))
-                .map_err(|e| {
-                    Error::Other(format!("Cannot obtain a storage key {}", e))
-                })?;
-            // Obtain the current transaction
-            let (tx_epoch, tx_height, tx_index, current_tx, current_stx) =
-                query_storage_value::<
-                    C,
-                    (Epoch, BlockHeight, TxIndex, Transfer, Transaction),
-                >(client, &current_tx_key)
-                .await?;
-            // Collect the current transaction
-            shielded_txs.insert(
-                (tx_height, tx_index),
-                (tx_epoch, current_tx, current_stx),
-            );
-        }
-        Ok(shielded_txs)
+    ) -> Result<ShieldedTxs, Error> {
+        query_shielded_txs(client, last_txidx, u64::MAX).await
     }
 
     /// Applies the given transaction to the supplied context. More precisely,
@@ -823,15 +1934,33 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         tx: &Transfer,
         shielded: &Transaction,
     ) -> Result<(), Error> {
+        let tx = CompactTx::new(self.last_txidx, index, epoch, tx, shielded);
+        let vks =
+            active_viewing_keys(&self.viewing_keys_with_birthdays(), height);
+        let decrypted = trial_decrypt(&tx.outputs, &vks);
+        self.apply_scanned_tx(client, height, &tx, decrypted).await
+    }
+
+    /// Applies the given transaction to the supplied context, given the notes
+    /// that were already decrypted from its outputs. See
+    /// [`ShieldedContext::scan_tx`].
+    async fn apply_scanned_tx<C: Client + Sync>(
+        &mut self,
+        client: &C,
+        height: BlockHeight,
+        tx: &CompactTx,
+        mut decrypted: DecryptedNotes,
+    ) -> Result<(), Error> {
+        let (index, epoch) = (tx.index, tx.epoch);
         // For tracking the account changes caused by this Transaction
         let mut transaction_delta = TransactionDelta::new();
+        // For tracking which of our notes are involved in this Transaction
+        let mut received = BTreeSet::new();
+        let mut spent = BTreeSet::new();
         // Listen for notes sent to our viewing keys
-        for so in shielded
-            .sapling_bundle()
-            .map_or(&vec![], |x| &x.shielded_outputs)
-        {
+        for (output_idx, so) in tx.outputs.iter().enumerate() {
             // Create merkle tree leaf node from note commitment
-            let node = Node::new(so.cmu.to_repr());
+            let node = Node::new(so.cmu);
             // Update each merkle tree in the witness map with the latest
             // addition
             for (_, witness) in self.witness_map.iter_mut() {
@@ -847,71 +1976,61 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             // note
             let witness = IncrementalWitness::<Node>::from_tree(&self.tree);
             self.witness_map.insert(note_pos, witness);
-            // Let's try to see if any of our viewing keys can decrypt latest
-            // note
-            let mut pos_map = HashMap::new();
-            std::mem::swap(&mut pos_map, &mut self.pos_map);
-            for (vk, notes) in pos_map.iter_mut() {
-                let decres = try_sapling_note_decryption::<_, OutputDescription<<<Authorized as Authorization>::SaplingAuth as masp_primitives::transaction::components::sapling::Authorization>::Proof>>(
-                    &NETWORK,
-                    1.into(),
-                    &PreparedIncomingViewingKey::new(&vk.ivk()),
-                    so,
+            // So one of our viewing keys does decrypt this current note...
+            if let Some(DecryptedNote {
+                vk,
+                note,
+                diversifier,
+                memo,
+            }) = decrypted.remove(&output_idx)
+            {
+                // Add this note to list of notes decrypted by this viewing
+                // key
+                self.pos_map.entry(vk).or_default().insert(note_pos);
+                // Compute the nullifier now to quickly recognize when spent
+                let nf = note.nf(
+                    &vk.nk,
+                    note_pos.try_into().map_err(|_| {
+                        Error::Other("Can not get nullifier".to_string())
+                    })?,
                 );
-                // So this current viewing key does decrypt this current note...
-                if let Some((note, pa, memo)) = decres {
-                    // Add this note to list of notes decrypted by this viewing
-                    // key
-                    notes.insert(note_pos);
-                    // Compute the nullifier now to quickly recognize when spent
-                    let nf = note.nf(
-                        &vk.nk,
-                        note_pos.try_into().map_err(|_| {
-                            Error::Other("Can not get nullifier".to_string())
-                        })?,
-                    );
-                    self.note_map.insert(note_pos, note);
-                    self.memo_map.insert(note_pos, memo);
-                    // The payment address' diversifier is required to spend
-                    // note
-                    self.div_map.insert(note_pos, *pa.diversifier());
-                    self.nf_map.insert(nf, note_pos);
-                    // Note the account changes
-                    let balance = transaction_delta
-                        .entry(*vk)
-                        .or_insert_with(MaspAmount::default);
-                    *balance += self
-                        .decode_all_amounts(
-                            client,
-                            I128Sum::from_nonnegative(
-                                note.asset_type,
-                                note.value as i128,
-                            )
-                            .map_err(|()| {
-                                Error::Other(
-                                    "found note with invalid value or asset \
-                                     type"
-                                        .to_string(),
-                                )
-                            })?,
+                self.note_map.insert(note_pos, note);
+                self.memo_map.insert(note_pos, memo);
+                // The payment address' diversifier is required to spend
+                // note
+                self.div_map.insert(note_pos, diversifier);
+                self.nf_map.insert(nf, note_pos);
+                // Note the account changes
+                let balance = transaction_delta
+                    .entry(vk)
+                    .or_insert_with(MaspAmount::default);
+                *balance += self
+                    .decode_all_amounts(
+                        client,
+                        I128Sum::from_nonnegative(
+                            note.asset_type,
+                            note.value as i128,
                         )
-                        .await;
+                        .map_err(|()| {
+                            Error::Other(
+                                "found note with invalid value or asset type"
+                                    .to_string(),
+                            )
+                        })?,
+                    )
+                    .await;
 
-                    self.vk_map.insert(note_pos, *vk);
-                    break;
-                }
+                self.vk_map.insert(note_pos, vk);
+                received.insert(note_pos);
             }
-            std::mem::swap(&mut pos_map, &mut self.pos_map);
         }
         // Cancel out those of our notes that have been spent
-        for ss in shielded
-            .sapling_bundle()
-            .map_or(&vec![], |x| &x.shielded_spends)
-        {
+        for nullifier in &tx.nullifiers {
             // If the shielded spend's nullifier is in our map, then target note
             // is rendered unusable
-            if let Some(note_pos) = self.nf_map.get(&ss.nullifier) {
+            if let Some(note_pos) = self.nf_map.get(nullifier) {
                 self.spents.insert(*note_pos);
+                spent.insert(*note_pos);
                 // Note the account changes
                 let balance = transaction_delta
                     .entry(self.vk_map[note_pos])
@@ -936,16 +2055,35 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         }
         // Record the changes to the transparent accounts
         let mut transfer_delta = TransferDelta::new();
-        let token_addr = tx.token.clone();
+        let transparent = &tx.transparent;
         transfer_delta.insert(
-            tx.source.clone(),
+            transparent.source.clone(),
             MaspChange {
-                asset: token_addr,
-                change: -tx.amount.amount.change(),
+                asset: transparent.token.clone(),
+                change: -transparent.amount.amount.change(),
             },
         );
         self.last_txidx += 1;
 
+        if !received.is_empty() || !spent.is_empty() {
+            let tx_hash = rpc::query_tx_hash(client, height, index).await?;
+            let conversions = if tx.conversions > 0 {
+                self.spent_conversions(client, epoch, &spent).await
+            } else {
+                vec![]
+            };
+            self.tx_records.insert(
+                (height, index),
+                ShieldedTxRecord {
+                    epoch,
+                    tx_hash: Some(tx_hash),
+                    transparent: transparent.clone(),
+                    received,
+                    spent,
+                    conversions,
+                },
+            );
+        }
         self.delta_map.insert(
             (height, index),
             (epoch, transfer_delta, transaction_delta),
@@ -953,6 +2091,33 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         Ok(())
     }
 
+    /// Determine the conversions that a transaction applied in the given
+    /// epoch must have applied to the given spent notes of ours, i.e. those
+    /// from the asset types of earlier epochs to the current ones
+    async fn spent_conversions<C: Client + Sync>(
+        &mut self,
+        client: &C,
+        epoch: Epoch,
+        spent: &BTreeSet<usize>,
+    ) -> Vec<ConversionRecord> {
+        let mut conversions = BTreeSet::new();
+        for pos in spent {
+            let note = self.note_map[pos];
+            if let Some((token, _, from_epoch)) =
+                self.decode_asset_type(client, note.asset_type).await
+            {
+                if from_epoch < epoch {
+                    conversions.insert(ConversionRecord {
+                        token,
+                        from_epoch,
+                        to_epoch: epoch,
+                    });
+                }
+            }
+        }
+        conversions.into_iter().collect()
+    }
+
     /// Summarize the effects on shielded and transparent accounts of each
     /// Transfer in this context
     pub fn get_tx_deltas(
@@ -964,6 +2129,104 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         &self.delta_map
     }
 
+    /// Collect the notes received by the given viewing key, along with their
+    /// memos, ordered by their position in the commitment tree. If the key
+    /// is not in the context, then we do not know its notes and hence we
+    /// return None.
+    pub async fn compute_received_notes<C: Client + Sync>(
+        &mut self,
+        client: &C,
+        vk: &ViewingKey,
+    ) -> Result<Option<Vec<ReceivedNote>>, Error> {
+        let Some(positions) = self.pos_map.get(vk).cloned() else {
+            return Ok(None);
+        };
+        let mut notes = Vec::with_capacity(positions.len());
+        for pos in positions {
+            let note = *self.note_map.get(&pos).ok_or_else(|| {
+                Error::Other(format!("Unable to get note {pos}"))
+            })?;
+            let Some((token, denom, epoch)) =
+                self.decode_asset_type(client, note.asset_type).await
+            else {
+                // Notes of unknown asset types cannot be displayed
+                continue;
+            };
+            notes.push(ReceivedNote {
+                pos,
+                token,
+                epoch,
+                amount: token::Amount::from_masp_denominated(note.value, denom),
+                memo: self
+                    .memo_map
+                    .get(&pos)
+                    .cloned()
+                    .unwrap_or_else(MemoBytes::empty),
+                spent: self.spents.contains(&pos),
+            });
+        }
+        Ok(Some(notes))
+    }
+
+    /// Compute the history of the given viewing key: every note it received
+    /// or spent, along with the transaction that did so. If the key is not in
+    /// the context, then we do not know its history and hence we return None.
+    pub async fn compute_history<C: Client + Sync>(
+        &mut self,
+        client: &C,
+        vk: &ViewingKey,
+    ) -> Result<Option<Vec<HistoryEntry>>, Error> {
+        if !self.pos_map.contains_key(vk) {
+            return Ok(None);
+        }
+        let records: Vec<_> = self
+            .tx_records
+            .iter()
+            .map(|(key, record)| (*key, record.clone()))
+            .collect();
+        let mut history = Vec::new();
+        for ((height, index), record) in records {
+            let notes = record
+                .received
+                .iter()
+                .map(|pos| (NoteDirection::Received, *pos))
+                .chain(
+                    record.spent.iter().map(|pos| (NoteDirection::Spent, *pos)),
+                );
+            for (direction, pos) in notes {
+                if self.vk_map.get(&pos) != Some(vk) {
+                    continue;
+                }
+                let note = *self.note_map.get(&pos).ok_or_else(|| {
+                    Error::Other(format!("Unable to get note {pos}"))
+                })?;
+                let Some((token, denom, asset_epoch)) =
+                    self.decode_asset_type(client, note.asset_type).await
+                else {
+                    // Notes of unknown asset types cannot be displayed
+                    continue;
+                };
+                history.push(HistoryEntry {
+                    height,
+                    index,
+                    epoch: record.epoch,
+                    tx_hash: record.tx_hash,
+                    direction,
+                    note_pos: pos,
+                    token,
+                    asset_epoch,
+                    amount: token::Amount::from_masp_denominated(
+                        note.value, denom,
+                    ),
+                    memo: self.memo_map.get(&pos).and_then(decode_memo),
+                    conversions: record.conversions.clone(),
+                    counterpart: record.counterpart(),
+                });
+            }
+        }
+        Ok(Some(history))
+    }
+
     /// Compute the total unspent notes associated with the viewing key in the
     /// context. If the key is not in the context, then we do not know the
     /// balance and hence we return None.
@@ -1437,37 +2700,27 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
     /// the epoch of the transaction or even before, so exchange all these
     /// amounts to the epoch of the transaction in order to get the value that
     /// would have been displayed in the epoch of the transaction.
-    pub async fn compute_exchanged_pinned_balance(
+    pub async fn compute_exchanged_pinned_balance<C: Client + Sync>(
         &mut self,
-        context: &impl Namada,
+        client: &C,
+        io: &impl Io,
         owner: PaymentAddress,
         viewing_key: &ViewingKey,
     ) -> Result<(MaspAmount, Epoch), Error> {
         // Obtain the balance that will be exchanged
         let (amt, ep) =
-            Self::compute_pinned_balance(context.client(), owner, viewing_key)
-                .await?;
-        display_line!(context.io(), "Pinned balance: {:?}", amt);
+            Self::compute_pinned_balance(client, owner, viewing_key).await?;
+        display_line!(io, "Pinned balance: {:?}", amt);
         // Establish connection with which to do exchange rate queries
-        let amount = self.decode_all_amounts(context.client(), amt).await;
-        display_line!(context.io(), "Decoded pinned balance: {:?}", amount);
+        let amount = self.decode_all_amounts(client, amt).await;
+        display_line!(io, "Decoded pinned balance: {:?}", amount);
         // Finally, exchange the balance to the transaction's epoch
         let computed_amount = self
-            .compute_exchanged_amount(
-                context.client(),
-                context.io(),
-                amount,
-                ep,
-                BTreeMap::new(),
-            )
+            .compute_exchanged_amount(client, io, amount, ep, BTreeMap::new())
             .await?
             .0;
-        display_line!(context.io(), "Exchanged amount: {:?}", computed_amount);
-        Ok((
-            self.decode_all_amounts(context.client(), computed_amount)
-                .await,
-            ep,
-        ))
+        display_line!(io, "Exchanged amount: {:?}", computed_amount);
+        Ok((self.decode_all_amounts(client, computed_amount).await, ep))
     }
 
     /// Convert an amount whose units are AssetTypes to one whose units are
@@ -1533,32 +2786,88 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         target: &TransferTarget,
         token: &Address,
         amount: token::DenominatedAmount,
+        memo: Option<MemoBytes>,
     ) -> Result<Option<ShieldedTransfer>, TransferErr> {
-        // No shielded components are needed when neither source nor destination
-        // are shielded
+        let spender = source.spending_key().map(|sk| Spender::local(sk.into()));
+        let transfer = Self::build_shielded_transfer(
+            context,
+            spender,
+            source.address(),
+            target,
+            token,
+            amount,
+            memo,
+        )
+        .await?;
+        Ok(transfer.map(|(transfer, _alphas)| transfer))
+    }
+
+    /// Like [`ShieldedContext::gen_shielded_transfer`], but spends the notes
+    /// of the given viewing key, whose spend authorizing key is held by an
+    /// external signer such as a hardware wallet. The spends are proven with
+    /// the proof generation key exported by the signer. Along with the
+    /// transfer, the randomizers of its spends are returned in the order of
+    /// its spend descriptions. The signer must sign each spend with its
+    /// randomizer, and the signatures must then be applied with
+    /// [`apply_spend_signatures`], as the transfer is not authorized until
+    /// then.
+    pub async fn gen_external_shielded_transfer(
+        context: &impl Namada,
+        xfvk: ExtendedFullViewingKey,
+        proof_generation_key: ProofGenerationKey,
+        target: &TransferTarget,
+        token: &Address,
+        amount: token::DenominatedAmount,
+        memo: Option<MemoBytes>,
+    ) -> Result<(ShieldedTransfer, Vec<jubjub::Fr>), TransferErr> {
+        let spender = Spender::external(xfvk, proof_generation_key);
+        let transfer = Self::build_shielded_transfer(
+            context,
+            Some(spender),
+            None,
+            target,
+            token,
+            amount,
+            memo,
+        )
+        .await?;
+        Ok(transfer.expect("a shielded spender yields a shielded transfer"))
+    }
 
+    /// Make the shielded components of a transfer spending the notes of the
+    /// given spender, if any, or else the tokens of the given transparent
+    /// source. The randomizers of the spends are returned along with the
+    /// transfer, in the order of its spend descriptions.
+    async fn build_shielded_transfer(
+        context: &impl Namada,
+        spender: Option<Spender>,
+        transparent_source: Option<Address>,
+        target: &TransferTarget,
+        token: &Address,
+        amount: token::DenominatedAmount,
+        memo: Option<MemoBytes>,
+    ) -> Result<Option<(ShieldedTransfer, Vec<jubjub::Fr>)>, TransferErr> {
         use std::str::FromStr;
 
         use rand::rngs::StdRng;
         use rand_core::SeedableRng;
 
-        let spending_key = source.spending_key();
         let payment_address = target.payment_address();
         // No shielded components are needed when neither source nor
         // destination are shielded
-        if spending_key.is_none() && payment_address.is_none() {
+        if spender.is_none() && payment_address.is_none() {
             return Ok(None);
         }
-        // We want to fund our transaction solely from supplied spending key
-        let spending_key = spending_key.map(|x| x.into());
-        let spending_keys: Vec<_> = spending_key.into_iter().collect();
+        // We want to fund our transaction solely from the supplied spender
+        let spender_vks: Vec<_> =
+            spender.iter().map(|spender| spender.xfvk.fvk.vk).collect();
         {
             // Load the current shielded context given the spending key we
             // possess
             let mut shielded = context.shielded_mut().await;
-            let _ = shielded.load().await;
+            shielded.load().await.map_err(load_error)?;
             shielded
-                .fetch(context.client(), &spending_keys, &[])
+                .fetch(context.client(), context.io(), &[], &spender_vks)
                 .await?;
             // Save the update state so that future fetches can be
             // short-circuited
@@ -1566,9 +2875,8 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         }
         // Determine epoch in which to submit potential shielded transaction
         let epoch = rpc::query_epoch(context.client()).await?;
-        // Context required for storing which notes are in the source's
-        // possesion
-        let memo = MemoBytes::empty();
+        // The memo is only attached to the payment, not to the change
+        let memo = memo.unwrap_or_else(MemoBytes::empty);
 
         // Try to get a seed from env var, if any.
         let rng = if let Ok(seed) = env::var(ENV_VAR_MASP_TEST_SEED)
@@ -1598,14 +2906,14 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             convert_amount(epoch, token, amount.amount)?;
 
         // If there are shielded inputs
-        if let Some(sk) = spending_key {
+        if let Some(spender) = &spender {
             // Locate unspent notes that can help us meet the transaction amount
             let (_, unspent_notes, used_convs) = context
                 .shielded_mut()
                 .await
                 .collect_unspent_notes(
                     context,
-                    &to_viewing_key(&sk).vk,
+                    &spender.xfvk.fvk.vk,
                     I128Sum::from_sum(masp_amount),
                     epoch,
                 )
@@ -1613,7 +2921,12 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             // Commit the notes found to our transaction
             for (diversifier, note, merkle_path) in unspent_notes {
                 builder
-                    .add_sapling_spend(sk, diversifier, note, merkle_path)
+                    .add_sapling_spend(
+                        spender.sk,
+                        diversifier,
+                        note,
+                        merkle_path,
+                    )
                     .map_err(builder::Error::SaplingBuild)?;
             }
             // Commit the conversion notes used during summation
@@ -1632,8 +2945,8 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             // We add a dummy UTXO to our transaction, but only the source of
             // the parent Transfer object is used to validate fund
             // availability
-            let source_enc = source
-                .address()
+            let source_enc = transparent_source
+                .as_ref()
                 .ok_or_else(|| {
                     Error::Other(
                         "source address should be transparent".to_string(),
@@ -1660,7 +2973,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         // Now handle the outputs of this transaction
         // If there is a shielded output
         if let Some(pa) = payment_address {
-            let ovk_opt = spending_key.map(|x| x.expsk.ovk);
+            let ovk_opt = spender.as_ref().map(|x| x.xfvk.fvk.ovk);
             for (denom, asset_type) in MaspDenom::iter().zip(asset_types.iter())
             {
                 builder
@@ -1703,7 +3016,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         }
 
         // Now add outputs representing the change from this payment
-        if let Some(sk) = spending_key {
+        if let Some(spender) = &spender {
             // Represents the amount of inputs we are short by
             let mut additional = I128Sum::zero();
             for (asset_type, amt) in builder
@@ -1720,11 +3033,11 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
                     // Send the change in this asset type back to the sender
                     builder
                         .add_sapling_output(
-                            Some(sk.expsk.ovk),
-                            sk.default_address().1,
+                            Some(spender.xfvk.fvk.ovk),
+                            spender.xfvk.default_address().1,
                             *asset_type,
                             *amt as u64,
-                            memo.clone(),
+                            MemoBytes::empty(),
                         )
                         .map_err(builder::Error::SaplingBuild)?;
                 } else {
@@ -1772,24 +3085,41 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             LoadOrSaveProofs::Neither
         };
 
-        let builder_clone = builder.clone().map_builder(WalletMap);
+        let builder_clone = builder
+            .clone()
+            .map_builder(WalletMap(spender.as_ref().map(|x| x.xfvk)));
         #[cfg(feature = "testing")]
         let builder_bytes = borsh::to_vec(&builder_clone).map_err(|e| {
             Error::from(EncodingError::Conversion(e.to_string()))
         })?;
 
         let build_transfer = |prover: LocalTxProver| -> Result<
-            ShieldedTransfer,
+            (ShieldedTransfer, Vec<jubjub::Fr>),
             builder::Error<std::convert::Infallible>,
         > {
+            let prover = SpendRecordingProver::new(
+                &prover,
+                spender
+                    .as_ref()
+                    .and_then(|x| x.proof_generation_key.clone()),
+            );
             let (masp_tx, metadata) = builder
                 .build(&prover, &FeeRule::non_standard(U64Sum::zero()))?;
-            Ok(ShieldedTransfer {
-                builder: builder_clone,
-                masp_tx,
-                metadata,
-                epoch,
-            })
+            let alphas = match &spender {
+                Some(spender) => {
+                    prover.spend_alphas(&masp_tx, spender.xfvk.fvk.vk.ak)
+                }
+                None => vec![],
+            };
+            Ok((
+                ShieldedTransfer {
+                    builder: builder_clone,
+                    masp_tx,
+                    metadata,
+                    epoch,
+                },
+                alphas,
+            ))
         };
 
         #[cfg(feature = "testing")]
@@ -1830,15 +3160,15 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
                 let loaded: ShieldedTransfer =
                     BorshDeserialize::try_from_slice(&loaded_bytes)
                         .map_err(|_e| Error::Other(exp_str))?;
-
-                Ok(Some(loaded))
+                // The randomizers of loaded proofs are not saved
+                Ok(Some((loaded, vec![])))
             } else {
                 // Build and return the constructed transaction
                 let built = build_transfer(
                     context.shielded().await.utils.local_tx_prover(),
                 )?;
                 if let LoadOrSaveProofs::Save = load_or_save {
-                    let built_bytes = borsh::to_vec(&built).map_err(|e| {
+                    let built_bytes = borsh::to_vec(&built.0).map_err(|e| {
                         Error::from(EncodingError::Conversion(e.to_string()))
                     })?;
                     tokio::fs::write(&saved_filepath, built_bytes)
@@ -1866,6 +3196,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
     pub async fn query_tx_deltas<C: Client + Sync>(
         &mut self,
         client: &C,
+        io: &impl Io,
         query_owner: &Either<BalanceOwner, Vec<Address>>,
         query_token: &Option<Address>,
         viewing_keys: &HashMap<String, ExtendedViewingKey>,
@@ -1877,13 +3208,13 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         Error,
     > {
         const TXS_PER_PAGE: u8 = 100;
-        let _ = self.load().await;
+        self.load().await.map_err(load_error)?;
         let vks = viewing_keys;
         let fvks: Vec<_> = vks
             .values()
             .map(|fvk| ExtendedFullViewingKey::from(*fvk).fvk.vk)
             .collect();
-        self.fetch(client, &[], &fvks).await?;
+        self.fetch(client, io, &[], &fvks).await?;
         // Save the update state so that future fetches can be short-circuited
         let _ = self.save().await;
         // Required for filtering out rejected transactions from Tendermint
@@ -2146,6 +3477,177 @@ mod tests {
             &fake_params_paths[2].0,
         );
     }
+
+    /// Check that viewing keys are only used for trial-decryption from their
+    /// birthday onwards.
+    #[test]
+    fn test_active_viewing_keys() {
+        use masp_primitives::zip32::ExtendedSpendingKey;
+        use namada_core::types::storage::BlockHeight;
+
+        use super::{active_viewing_keys, to_viewing_key};
+
+        let vk =
+            |seed: u8| to_viewing_key(&ExtendedSpendingKey::master(&[seed])).vk;
+        let (vk_a, vk_b, vk_c) = (vk(0), vk(1), vk(2));
+        let vks = [
+            (vk_a, None),
+            (vk_b, Some(BlockHeight(10))),
+            (vk_c, Some(BlockHeight(20))),
+        ];
+        assert_eq!(active_viewing_keys(&vks, BlockHeight(1)), vec![vk_a]);
+        assert_eq!(
+            active_viewing_keys(&vks, BlockHeight(10)),
+            vec![vk_a, vk_b]
+        );
+        assert_eq!(
+            active_viewing_keys(&vks, BlockHeight(25)),
+            vec![vk_a, vk_b, vk_c]
+        );
+    }
+
+    /// Check that text memos survive an encoding roundtrip and that overlong
+    /// memos are rejected.
+    #[test]
+    fn test_memo_roundtrip() {
+        use masp_primitives::memo::MemoBytes;
+
+        use super::{decode_memo, encode_memo};
+
+        let memo = encode_memo("invoice #42").expect("Memo should be valid");
+        assert_eq!(decode_memo(&memo), Some("invoice #42".to_string()));
+        assert_eq!(decode_memo(&MemoBytes::empty()), None);
+        assert!(encode_memo(&"a".repeat(513)).is_err());
+    }
+
+    /// Check that exported CSV fields are quoted only when needed
+    #[test]
+    fn test_csv_field() {
+        use super::csv_field;
+
+        assert_eq!(csv_field("plain".to_string()), "plain");
+        assert_eq!(csv_field("a,b".to_string()), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\"".to_string()), "\"say \"\"hi\"\"\"");
+    }
+
+    /// Check that the spending key standing in for an external signer's
+    /// derives the same nullifiers and outgoing ciphertexts, without
+    /// knowing the signer's spend authorizing key
+    #[test]
+    fn test_external_spending_key() {
+        use masp_primitives::zip32::{
+            ExtendedFullViewingKey, ExtendedSpendingKey,
+        };
+
+        let sk = ExtendedSpendingKey::master(&[1; 32]);
+        let xfvk = ExtendedFullViewingKey::from(&sk);
+        let external = super::external_spending_key(
+            &xfvk,
+            &sk.expsk.proof_generation_key(),
+        );
+        assert_eq!(
+            external.expsk.proof_generation_key().to_viewing_key().nk,
+            xfvk.fvk.vk.nk
+        );
+        assert_eq!(external.expsk.ovk, xfvk.fvk.ovk);
+        assert_ne!(external.expsk.ask, sk.expsk.ask);
+    }
+
+    /// Check that contexts saved before the encoding was versioned or with
+    /// an earlier version can still be loaded, that the current encoding
+    /// roundtrips and that unknown versions are rejected
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_context_versions() {
+        use std::collections::{BTreeMap, BTreeSet};
+
+        use borsh::BorshSerialize;
+        use masp_primitives::zip32::ExtendedSpendingKey;
+        use namada_core::types::address::{nam, MASP};
+        use namada_core::types::storage::{BlockHeight, Epoch, TxIndex};
+        use namada_core::types::token::{Amount, DenominatedAmount, Transfer};
+
+        use super::fs::FsShieldedUtils;
+        use super::{
+            to_viewing_key, ShieldedContext, CONTEXT_MAGIC, CONTEXT_VERSION,
+        };
+
+        let vk = to_viewing_key(&ExtendedSpendingKey::master(&[0])).vk;
+        let mut ctx = ShieldedContext::<FsShieldedUtils>::default();
+        ctx.last_txidx = 42;
+        ctx.pos_map.insert(vk, BTreeSet::from([3]));
+
+        // A context saved before versioning lacks the magic, the version and
+        // the fields added since
+        let mut legacy = Vec::new();
+        ctx.last_txidx.serialize(&mut legacy).unwrap();
+        ctx.tree.serialize(&mut legacy).unwrap();
+        ctx.pos_map.serialize(&mut legacy).unwrap();
+        ctx.nf_map.serialize(&mut legacy).unwrap();
+        ctx.note_map.serialize(&mut legacy).unwrap();
+        ctx.memo_map.serialize(&mut legacy).unwrap();
+        ctx.div_map.serialize(&mut legacy).unwrap();
+        ctx.witness_map.serialize(&mut legacy).unwrap();
+        ctx.delta_map.serialize(&mut legacy).unwrap();
+        ctx.spents.serialize(&mut legacy).unwrap();
+        ctx.asset_types.serialize(&mut legacy).unwrap();
+        ctx.vk_map.serialize(&mut legacy).unwrap();
+        let decoded =
+            ShieldedContext::decode(FsShieldedUtils::default(), &legacy)
+                .expect("Legacy contexts should be decodable");
+        assert_eq!(decoded.last_txidx, 42);
+        assert_eq!(decoded.pos_map, ctx.pos_map);
+        assert!(decoded.birthdays.is_empty());
+
+        // Version 1 recorded the transparent part of the transactions as a
+        // transfer and only counted their conversions
+        let transfer = Transfer {
+            source: nam(),
+            target: MASP,
+            token: nam(),
+            amount: DenominatedAmount::native(Amount::native_whole(1)),
+            key: None,
+            shielded: None,
+        };
+        let mut v1 = CONTEXT_MAGIC.to_vec();
+        v1.push(1);
+        v1.extend(legacy);
+        ctx.set_birthday(vk, BlockHeight(5));
+        ctx.birthdays.serialize(&mut v1).unwrap();
+        let record = (
+            Epoch(2),
+            transfer,
+            BTreeSet::from([3]),
+            BTreeSet::<usize>::new(),
+            1usize,
+        );
+        BTreeMap::from([((BlockHeight(7), TxIndex(1)), record)])
+            .serialize(&mut v1)
+            .unwrap();
+        let decoded = ShieldedContext::decode(FsShieldedUtils::default(), &v1)
+            .expect("Version 1 contexts should be decodable");
+        assert_eq!(decoded.last_txidx, 42);
+        assert_eq!(decoded.birthdays, ctx.birthdays);
+        let record = &decoded.tx_records[&(BlockHeight(7), TxIndex(1))];
+        assert_eq!(record.epoch, Epoch(2));
+        assert_eq!(record.tx_hash, None);
+        assert_eq!(record.transparent.source, nam());
+        assert_eq!(record.received, BTreeSet::from([3]));
+        assert!(record.conversions.is_empty());
+
+        let encoded = ctx.encode();
+        let decoded =
+            ShieldedContext::decode(FsShieldedUtils::default(), &encoded)
+                .expect("Current contexts should be decodable");
+        assert_eq!(decoded.last_txidx, 42);
+        assert_eq!(decoded.pos_map, ctx.pos_map);
+        assert_eq!(decoded.birthdays, ctx.birthdays);
+
+        let mut future = encoded;
+        future[CONTEXT_MAGIC.len()] = CONTEXT_VERSION + 1;
+        assert!(ShieldedContext::decode(FsShieldedUtils::default(), &future)
+            .is_err());
+    }
 }
 
 #[cfg(feature = "std")]
@@ -2222,20 +3724,25 @@ pub mod fs {
         }
 
         /// Try to load the last saved shielded context from the given context
-        /// directory. If this fails, then leave the current context unchanged.
+        /// directory. If no context was saved yet, or if this fails, then
+        /// leave the current context unchanged.
         async fn load<U: ShieldedUtils + MaybeSend>(
             &self,
             ctx: &mut ShieldedContext<U>,
         ) -> std::io::Result<()> {
             // Try to load shielded context from file
-            let mut ctx_file = File::open(self.context_dir.join(FILE_NAME))?;
+            let mut ctx_file =
+                match File::open(self.context_dir.join(FILE_NAME)) {
+                    Ok(file) => file,
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                        return Ok(());
+                    }
+                    Err(err) => return Err(err),
+                };
             let mut bytes = Vec::new();
             ctx_file.read_to_end(&mut bytes)?;
             // Fill the supplied context with the deserialized object
-            *ctx = ShieldedContext {
-                utils: ctx.utils.clone(),
-                ..ShieldedContext::<U>::deserialize(&mut &bytes[..])?
-            };
+            *ctx = ShieldedContext::decode(ctx.utils.clone(), &bytes)?;
             Ok(())
         }
 
@@ -2257,10 +3764,7 @@ pub mod fs {
                     .write(true)
                     .create_new(true)
                     .open(tmp_path.clone())?;
-                let mut bytes = Vec::new();
-                ctx.serialize(&mut bytes)
-                    .expect("cannot serialize shielded context");
-                ctx_file.write_all(&bytes[..])?;
+                ctx_file.write_all(&ctx.encode())?;
             }
             // Atomically update the old shielded context file with new data.
             // Atomicity is required to prevent other client instances from
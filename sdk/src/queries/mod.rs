@@ -6,6 +6,9 @@ use namada_core::ledger::storage::traits::StorageHasher;
 use namada_core::ledger::storage::{DBIter, DB};
 use namada_core::ledger::storage_api;
 use namada_core::types::storage::BlockHeight;
+pub use router::{
+    JsonRequest, JsonResponse, RequestSpec, RouteArg, RouteSegment, RouteSpec,
+};
 pub use shell::Shell;
 use shell::SHELL;
 pub use types::{
@@ -15,8 +18,9 @@ pub use types::{
 use vp::{Vp, VP};
 
 pub use self::shell::eth_bridge::{
-    Erc20FlowControl, GenBridgePoolProofReq, GenBridgePoolProofRsp,
-    TransferToErcArgs,
+    BridgePoolFeeStats, Erc20FlowControl, GenBridgePoolProofReq,
+    GenBridgePoolProofRsp, GenStateProofRsp, StateProof, TransferToErcArgs,
+    VerifyStateProofArgs,
 };
 use crate::{MaybeSend, MaybeSync};
 
@@ -35,6 +39,26 @@ router! {RPC,
     ( "vp" ) = (sub VP),
 }
 
+// The types returned by the routes of several routers
+json_encoding! {
+    response json: [bool, Option<namada_core::types::storage::Epoch>],
+}
+
+/// The specification of the data attached to the queries of the given route,
+/// if its handler reads any. The `with_options` handlers that are not listed
+/// here only accept a block height.
+pub fn route_request(route: &RouteSpec) -> Option<RequestSpec> {
+    match (route.prefix.as_str(), route.handler) {
+        ("/shell", "dry_run_tx") => {
+            Some(RequestSpec::of::<namada_core::proto::Tx>())
+        }
+        ("/shell/eth_bridge", "generate_bridge_pool_proof") => {
+            Some(RequestSpec::of::<GenBridgePoolProofReq<'static, 'static>>())
+        }
+        _ => None,
+    }
+}
+
 /// Handle RPC query request in the ledger. On success, returns response with
 /// borsh-encoded data.
 pub fn handle_path<D, H, V, T>(
@@ -97,7 +121,7 @@ pub fn require_no_data(request: &RequestQuery) -> storage_api::Result<()> {
 
 /// Queries testing helpers
 #[cfg(any(test, feature = "testing"))]
-mod testing {
+pub(crate) mod testing {
 
     use namada_core::ledger::storage::testing::TestWlStorage;
     use namada_core::types::storage::BlockHeight;
@@ -5,6 +5,15 @@
 //! Note that for debugging pattern matching issue, you can uncomment
 //! all the `println!`s in this module.
 
+use std::collections::HashMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use borsh_ext::BorshSerializeExt;
+use data_encoding::HEXLOWER;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SchemaObject, StringValidation};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use thiserror::Error;
 
 /// Router error.
@@ -30,6 +39,329 @@ pub fn find_next_slash_index(path: &str, start: usize) -> usize {
         .unwrap_or(path.len())
 }
 
+/// The specification of a route generated by the `router!` macro, which can
+/// be used to expose the route outside of ABCI queries.
+#[derive(Clone, Debug)]
+pub struct RouteSpec {
+    /// The prefix path of the router the route belongs to
+    pub prefix: String,
+    /// The segments of the route's pattern
+    pub segments: Vec<RouteSegment>,
+    /// The name of the route's handler
+    pub handler: &'static str,
+    /// The name of the type returned by the route
+    pub return_type: &'static str,
+    /// Whether the handler supports request data and a block height
+    pub with_options: bool,
+    /// Decode the data of a response of the route into JSON
+    pub decode: fn(&[u8]) -> Result<serde_json::Value, String>,
+    /// The schema of the JSON responses of the route
+    pub response_schema: fn(&mut SchemaGenerator) -> Schema,
+}
+
+/// The specification of the data attached to the queries of a route
+#[derive(Clone, Copy, Debug)]
+pub struct RequestSpec {
+    /// The name of the type of the data
+    pub ty: &'static str,
+    /// Encode the JSON data of a request with Borsh
+    pub from_json: fn(serde_json::Value) -> Result<Vec<u8>, String>,
+    /// The schema of the JSON data of a request
+    pub schema: fn(&mut SchemaGenerator) -> Schema,
+}
+
+impl RequestSpec {
+    /// The specification of request data of the given type
+    pub fn of<T: JsonRequest>() -> Self {
+        Self {
+            ty: std::any::type_name::<T>(),
+            from_json: T::from_json,
+            schema: T::json_schema,
+        }
+    }
+}
+
+/// A segment of the pattern of a route
+#[derive(Clone, Copy, Debug)]
+pub enum RouteSegment {
+    /// A literal segment
+    Literal(&'static str),
+    /// A dynamic argument
+    Arg(RouteArg),
+}
+
+/// A dynamic argument of the pattern of a route
+#[derive(Clone, Copy, Debug)]
+pub struct RouteArg {
+    /// The name of the argument
+    pub name: &'static str,
+    /// The name of the type of the argument
+    pub ty: &'static str,
+    /// Whether the argument may be omitted
+    pub optional: bool,
+    /// Check that the given string can be parsed into the argument's type
+    pub is_valid: fn(&str) -> bool,
+}
+
+impl RouteSpec {
+    /// The arguments of the route, in the order of its pattern
+    pub fn args(&self) -> impl Iterator<Item = &RouteArg> {
+        self.segments.iter().filter_map(|segment| match segment {
+            RouteSegment::Arg(arg) => Some(arg),
+            RouteSegment::Literal(_) => None,
+        })
+    }
+
+    /// The path of the route in a REST API, with its required arguments
+    /// replaced by `{name}` placeholders. The optional arguments are passed as
+    /// query parameters instead.
+    pub fn rest_path(&self) -> String {
+        let segments =
+            self.segments.iter().filter_map(|segment| match segment {
+                RouteSegment::Literal(literal) => Some(literal.to_string()),
+                RouteSegment::Arg(arg) if arg.optional => None,
+                RouteSegment::Arg(arg) => Some(format!("{{{}}}", arg.name)),
+            });
+        itertools::join(
+            std::iter::once(self.prefix.clone()).chain(segments),
+            "/",
+        )
+    }
+
+    /// If the given REST path and query parameters match this route, build
+    /// the path of the equivalent ABCI query. Like in the router, an argument
+    /// that ends the pattern matches the rest of the path, slashes included.
+    pub fn abci_path(
+        &self,
+        rest_path: &str,
+        query: &HashMap<String, String>,
+    ) -> Option<String> {
+        let rest_path = rest_path.trim_end_matches('/');
+        let mut rest = rest_path.strip_prefix(self.prefix.as_str())?;
+        let mut path = vec![self.prefix.as_str()];
+        for (index, segment) in self.segments.iter().enumerate() {
+            let is_last = index + 1 == self.segments.len();
+            match segment {
+                RouteSegment::Arg(arg) if arg.optional => {
+                    if let Some(value) = query.get(arg.name) {
+                        if !(arg.is_valid)(value) {
+                            return None;
+                        }
+                        path.push(value.as_str());
+                    }
+                }
+                RouteSegment::Arg(arg) => {
+                    let (value, tail) = if is_last {
+                        (rest.strip_prefix('/')?, "")
+                    } else {
+                        split_segment(rest)?
+                    };
+                    if value.is_empty() || !(arg.is_valid)(value) {
+                        return None;
+                    }
+                    path.push(value);
+                    rest = tail;
+                }
+                RouteSegment::Literal(literal) => {
+                    let (value, tail) = split_segment(rest)?;
+                    if value != *literal {
+                        return None;
+                    }
+                    path.push(literal);
+                    rest = tail;
+                }
+            }
+        }
+        rest.is_empty().then(|| path.join("/"))
+    }
+}
+
+/// Split the first segment off a path starting with `/`
+fn split_segment(path: &str) -> Option<(&str, &str)> {
+    let path = path.strip_prefix('/')?;
+    let end = path.find('/').unwrap_or(path.len());
+    Some(path.split_at(end))
+}
+
+/// The JSON encoding of the data returned by the routes of this type. Every
+/// type returned by a route must declare its encoding with `json_encoding!`,
+/// next to the router the route belongs to.
+pub trait JsonResponse: BorshDeserialize {
+    /// Decode the Borsh-encoded data of a response into JSON
+    fn to_json(bytes: &[u8]) -> Result<serde_json::Value, String>;
+
+    /// The schema of the JSON encoding
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema;
+}
+
+/// The JSON encoding of the data attached to the queries of a route, declared
+/// with `json_encoding!` like [`JsonResponse`].
+pub trait JsonRequest: BorshSerialize {
+    /// Encode the JSON data of a request with Borsh
+    fn from_json(value: serde_json::Value) -> Result<Vec<u8>, String>;
+
+    /// The schema of the JSON encoding
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema;
+}
+
+/// Decode Borsh-encoded data into the JSON serialization of its type
+#[doc(hidden)]
+pub fn serde_to_json<T: BorshDeserialize + Serialize>(
+    bytes: &[u8],
+) -> Result<serde_json::Value, String> {
+    let value = T::try_from_slice(bytes).map_err(|err| err.to_string())?;
+    serde_json::to_value(value).map_err(|err| err.to_string())
+}
+
+/// Encode the JSON serialization of a value with Borsh
+#[doc(hidden)]
+pub fn serde_from_json<T: DeserializeOwned + BorshSerialize>(
+    value: serde_json::Value,
+) -> Result<Vec<u8>, String> {
+    let value: T =
+        serde_json::from_value(value).map_err(|err| err.to_string())?;
+    Ok(value.serialize_to_vec())
+}
+
+/// Wrap Borsh-encoded data in JSON, for types that cannot be represented in
+/// JSON
+#[doc(hidden)]
+pub fn borsh_to_json<T: BorshDeserialize>(
+    bytes: &[u8],
+) -> Result<serde_json::Value, String> {
+    T::try_from_slice(bytes).map_err(|err| err.to_string())?;
+    Ok(serde_json::json!({ BORSH_FIELD: HEXLOWER.encode(bytes) }))
+}
+
+/// Unwrap Borsh-encoded data from JSON, for types that cannot be represented
+/// in JSON
+#[doc(hidden)]
+pub fn borsh_from_json<T: BorshDeserialize>(
+    value: serde_json::Value,
+) -> Result<Vec<u8>, String> {
+    let bytes = value
+        .get(BORSH_FIELD)
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| {
+            format!("Expected an object with a `{BORSH_FIELD}` string")
+        })?;
+    let bytes = HEXLOWER
+        .decode(bytes.as_bytes())
+        .map_err(|err| err.to_string())?;
+    T::try_from_slice(&bytes).map_err(|err| err.to_string())?;
+    Ok(bytes)
+}
+
+/// The schema of Borsh-encoded data of the given type wrapped in JSON
+#[doc(hidden)]
+pub fn borsh_json_schema(type_name: &str) -> Schema {
+    let mut schema = SchemaObject {
+        instance_type: Some(InstanceType::Object.into()),
+        ..Default::default()
+    };
+    schema.metadata().description = Some(format!(
+        "A value of type `{type_name}`, which cannot be represented in JSON, \
+         hex-encoded with Borsh"
+    ));
+    let object = schema.object();
+    object.required.insert(BORSH_FIELD.to_string());
+    object.properties.insert(
+        BORSH_FIELD.to_string(),
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                pattern: Some("^([0-9a-f]{2})*$".to_string()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into(),
+    );
+    schema.into()
+}
+
+/// The field of the JSON object wrapping Borsh-encoded data
+const BORSH_FIELD: &str = "borsh";
+
+/// Declare how the types returned by routes, and the types of the data
+/// attached to their queries, are encoded in JSON. The `json` types are
+/// serialized with serde and described by their `schemars::JsonSchema`. The
+/// `borsh` types cannot be represented in JSON and are hex-encoded with Borsh
+/// in a `{"borsh": "<hex>"}` object instead.
+macro_rules! json_encoding {
+    (
+        $( response json: [ $( $json:ty ),* $(,)? ], )?
+        $( response borsh: [ $( $borsh:ty ),* $(,)? ], )?
+        $( request json: [ $( $json_req:ty ),* $(,)? ], )?
+        $( request borsh: [ $( $borsh_req:ty ),* $(,)? ], )?
+    ) => {
+        $( $(
+            impl $crate::queries::router::JsonResponse for $json {
+                fn to_json(
+                    bytes: &[u8],
+                ) -> std::result::Result<serde_json::Value, String> {
+                    $crate::queries::router::serde_to_json::<Self>(bytes)
+                }
+
+                fn json_schema(
+                    gen: &mut schemars::gen::SchemaGenerator,
+                ) -> schemars::schema::Schema {
+                    gen.subschema_for::<Self>()
+                }
+            }
+        )* )?
+        $( $(
+            impl $crate::queries::router::JsonResponse for $borsh {
+                fn to_json(
+                    bytes: &[u8],
+                ) -> std::result::Result<serde_json::Value, String> {
+                    $crate::queries::router::borsh_to_json::<Self>(bytes)
+                }
+
+                fn json_schema(
+                    _gen: &mut schemars::gen::SchemaGenerator,
+                ) -> schemars::schema::Schema {
+                    $crate::queries::router::borsh_json_schema(
+                        stringify!($borsh),
+                    )
+                }
+            }
+        )* )?
+        $( $(
+            impl $crate::queries::router::JsonRequest for $json_req {
+                fn from_json(
+                    value: serde_json::Value,
+                ) -> std::result::Result<Vec<u8>, String> {
+                    $crate::queries::router::serde_from_json::<Self>(value)
+                }
+
+                fn json_schema(
+                    gen: &mut schemars::gen::SchemaGenerator,
+                ) -> schemars::schema::Schema {
+                    gen.subschema_for::<Self>()
+                }
+            }
+        )* )?
+        $( $(
+            impl $crate::queries::router::JsonRequest for $borsh_req {
+                fn from_json(
+                    value: serde_json::Value,
+                ) -> std::result::Result<Vec<u8>, String> {
+                    $crate::queries::router::borsh_from_json::<Self>(value)
+                }
+
+                fn json_schema(
+                    _gen: &mut schemars::gen::SchemaGenerator,
+                ) -> schemars::schema::Schema {
+                    $crate::queries::router::borsh_json_schema(
+                        stringify!($borsh_req),
+                    )
+                }
+            }
+        )* )?
+    };
+}
+
 /// Invoke the sub-handler or call the handler function with the matched
 /// arguments generated by `try_match_segments`.
 macro_rules! handle_match {
@@ -619,6 +951,232 @@ macro_rules! pattern_and_handler_to_method {
     };
 }
 
+/// Turn patterns and their handlers into [`RouteSpec`]s pushed into the
+/// `$routes` vector, where `$router` is the router they belong to.
+macro_rules! pattern_and_handler_to_routes {
+    // Special terminal rule for `storage_value` handle, whose data are the
+    // bytes read from storage, returned as is
+    (
+        ( $router:ident, $routes:ident )
+        [ $( { $segment:expr } ),* ]
+        $return_type:path,
+        (with_options storage_value),
+        ()
+    ) => {
+        $routes.push($crate::queries::router::RouteSpec {
+            prefix: $router.prefix.clone(),
+            segments: vec![ $( $segment ),* ],
+            handler: "storage_value",
+            return_type: stringify!($return_type),
+            with_options: true,
+            decode: |bytes| {
+                Ok(serde_json::Value::String(
+                    data_encoding::HEXLOWER.encode(bytes),
+                ))
+            },
+            response_schema: |_gen| {
+                schemars::schema::SchemaObject {
+                    instance_type: Some(
+                        schemars::schema::InstanceType::String.into(),
+                    ),
+                    metadata: Some(Box::new(schemars::schema::Metadata {
+                        description: Some(
+                            "The hex-encoded bytes read from storage"
+                                .to_string(),
+                        ),
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                }
+                .into()
+            },
+        });
+    };
+
+    // terminal rule for $handle that uses request (`with_options`)
+    (
+        ( $router:ident, $routes:ident )
+        [ $( { $segment:expr } ),* ]
+        $return_type:path,
+        (with_options $handle:tt),
+        ()
+    ) => {
+        $routes.push($crate::queries::router::RouteSpec {
+            prefix: $router.prefix.clone(),
+            segments: vec![ $( $segment ),* ],
+            handler: stringify!($handle),
+            return_type: stringify!($return_type),
+            with_options: true,
+            decode:
+                <$return_type as $crate::queries::router::JsonResponse>::to_json,
+            response_schema:
+                <$return_type as $crate::queries::router::JsonResponse>::json_schema,
+        });
+    };
+
+    // terminal rule that $handle that doesn't use request
+    (
+        ( $router:ident, $routes:ident )
+        [ $( { $segment:expr } ),* ]
+        $return_type:path,
+        $handle:tt,
+        ()
+    ) => {
+        $routes.push($crate::queries::router::RouteSpec {
+            prefix: $router.prefix.clone(),
+            segments: vec![ $( $segment ),* ],
+            handler: stringify!($handle),
+            return_type: stringify!($return_type),
+            with_options: false,
+            decode:
+                <$return_type as $crate::queries::router::JsonResponse>::to_json,
+            response_schema:
+                <$return_type as $crate::queries::router::JsonResponse>::json_schema,
+        });
+    };
+
+    // sub-pattern
+    (
+        $target:tt
+        $segments:tt
+        $( $_return_type:path )?,
+        { $( $sub_pattern:tt $( -> $sub_return_ty:path )? = $handle:tt, )* },
+        $pattern:tt
+    ) => {
+        $(
+            // join pattern with each sub-pattern
+            pattern_and_handler_to_routes!(
+                $target
+                $segments
+                $( $sub_return_ty )?, $handle, $pattern, $sub_pattern
+            );
+        )*
+    };
+
+    // literal string arg
+    (
+        $target:tt
+        [ $( { $segment:expr } ),* ]
+        $( $return_type:path )?,
+        $handle:tt,
+        ( $pattern:literal $( / $tail:tt )* )
+    ) => {
+        pattern_and_handler_to_routes!(
+            $target
+            [ $( { $segment }, )* { $crate::queries::router::RouteSegment::Literal($pattern) } ]
+            $( $return_type )?, $handle, ( $( $tail )/ * )
+        );
+    };
+
+    // untyped arg
+    (
+        $target:tt
+        [ $( { $segment:expr } ),* ]
+        $( $return_type:path )?,
+        $handle:tt,
+        ( [$name:tt] $( / $tail:tt )* )
+    ) => {
+        pattern_and_handler_to_routes!(
+            $target
+            [ $( { $segment }, )* { $crate::queries::router::RouteSegment::Arg(
+                $crate::queries::router::RouteArg {
+                    name: stringify!($name),
+                    ty: "str",
+                    optional: false,
+                    is_valid: |_| true,
+                }
+            ) } ]
+            $( $return_type )?, $handle, ( $( $tail )/ * )
+        );
+    };
+
+    // typed arg
+    (
+        $target:tt
+        [ $( { $segment:expr } ),* ]
+        $( $return_type:path )?,
+        $handle:tt,
+        ( [$name:tt: $type:ty] $( / $tail:tt )* )
+    ) => {
+        pattern_and_handler_to_routes!(
+            $target
+            [ $( { $segment }, )* { $crate::queries::router::RouteSegment::Arg(
+                $crate::queries::router::RouteArg {
+                    name: stringify!($name),
+                    ty: stringify!($type),
+                    optional: false,
+                    is_valid: |arg| arg.parse::<$type>().is_ok(),
+                }
+            ) } ]
+            $( $return_type )?, $handle, ( $( $tail )/ * )
+        );
+    };
+
+    // opt typed arg
+    (
+        $target:tt
+        [ $( { $segment:expr } ),* ]
+        $( $return_type:path )?,
+        $handle:tt,
+        ( [$name:tt: opt $type:ty] $( / $tail:tt )* )
+    ) => {
+        pattern_and_handler_to_routes!(
+            $target
+            [ $( { $segment }, )* { $crate::queries::router::RouteSegment::Arg(
+                $crate::queries::router::RouteArg {
+                    name: stringify!($name),
+                    ty: stringify!($type),
+                    optional: true,
+                    is_valid: |arg| arg.parse::<$type>().is_ok(),
+                }
+            ) } ]
+            $( $return_type )?, $handle, ( $( $tail )/ * )
+        );
+    };
+
+    // join pattern with sub-pattern
+    (
+        $target:tt
+        [ $( { $segment:expr } ),* ]
+        $( $return_type:path )?,
+        $handle:tt,
+        ( $( $pattern:tt )/ * ), ( $( $sub_pattern:tt )/ * )
+    ) => {
+        pattern_and_handler_to_routes!(
+            $target
+            [ $( { $segment }, )* ]
+            $( $return_type )?,
+            $handle, ( $( $pattern / )* $( $sub_pattern )/ * )
+        );
+    };
+}
+
+/// Push the [`RouteSpec`]s of a top-level pattern of the `$router` into the
+/// `$routes` vector.
+macro_rules! pattern_to_routes {
+    // a sub router - collect its own routes
+    ( $router:ident, $routes:ident, $pattern:tt = (sub $sub_router:ident) ) => {
+        paste::paste! {
+            $routes.extend($router.[<$sub_router:camel:snake>]().routes());
+        }
+    };
+
+    // a sub-pattern - add a route for each handle inside it
+    (
+        $router:ident, $routes:ident,
+        $pattern:tt = { $( $sub_pattern:tt $( -> $sub_return_ty:path )? = $handle:tt, )* }
+    ) => {
+        pattern_and_handler_to_routes!( ($router, $routes) [],
+            { $( $sub_pattern $( -> $sub_return_ty )? = $handle, )* }, $pattern
+        );
+    };
+
+    // pattern with a handle - add a route for the handle
+    ( $router:ident, $routes:ident, $pattern:tt -> $return_type:path = $handle:tt ) => {
+        pattern_and_handler_to_routes!( ($router, $routes) [] $return_type, $handle, $pattern );
+    };
+}
+
 /// TT muncher macro that generates a `struct $name` with methods for all its
 /// handlers.
 macro_rules! router_type {
@@ -821,6 +1379,20 @@ macro_rules! router {
 			}
 		}
 
+		impl [<$name:camel>] {
+            #[doc = "Get the specifications of all the routes of this router, \
+                including the routes of its sub-routers."]
+            pub fn routes(&self) -> Vec<$crate::queries::router::RouteSpec> {
+                #[allow(unused_mut)]
+                let mut routes = vec![];
+                $(
+                    pattern_to_routes!(self, routes,
+                        $pattern $( -> $return_type )? = $handle);
+                )*
+                routes
+            }
+        }
+
 		#[doc = "`" $name "` path router"]
 		pub const $name: [<$name:camel>] = [<$name:camel>]::new();
 	}
@@ -992,6 +1564,10 @@ mod test_rpc {
         ( "c" ) -> String = (with_options c),
     }
 
+    json_encoding! {
+        response json: [String],
+    }
+
     router! {TEST_SUB_RPC,
         ( "x" ) -> String = x,
         ( "y" / [untyped_arg] ) -> String = y,
@@ -1001,6 +1577,9 @@ mod test_rpc {
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashMap;
+
+    use borsh_ext::BorshSerializeExt;
     use namada_core::ledger::storage_api;
     use namada_core::tendermint::block;
     use namada_core::types::storage::Epoch;
@@ -1008,6 +1587,7 @@ mod test {
     use namada_core::types::token::NATIVE_MAX_DECIMAL_PLACES;
 
     use super::test_rpc::TEST_RPC;
+    use super::{borsh_from_json, borsh_to_json, RouteSpec};
     use crate::queries::testing::TestClient;
     use crate::queries::{RequestCtx, RequestQuery, Router};
 
@@ -1117,4 +1697,79 @@ mod test {
 
         Ok(())
     }
+
+    /// Test the specifications of the routes generated for `TEST_RPC`.
+    #[test]
+    fn test_router_routes() {
+        let routes = TEST_RPC.routes();
+        let paths = routes.iter().map(RouteSpec::rest_path).collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "/sub/x",
+                "/sub/y/{untyped_arg}",
+                "/sub/z/{untyped_arg}",
+                "/a",
+                "/b/0/i",
+                "/b/0/ii",
+                "/b/1",
+                "/b/2/i/{balance}",
+                "/b/3/{a1}/{a2}/i/{a3}",
+                "/b/3/{a1}/{a2}/{a3}",
+                "/b/3/{a1}/{a2}/{a3}/ii",
+                "/b/3/{a1}/{a2}/iii",
+                "/b/3/{a1}/{a2}/iiii/xyz",
+                "/c",
+            ]
+        );
+
+        let route = |handler: &str| {
+            routes
+                .iter()
+                .find(|route| route.handler == handler)
+                .unwrap()
+        };
+        let no_query = HashMap::new();
+
+        // The argument ending a pattern matches the rest of the path
+        assert_eq!(
+            route("y").abci_path("/sub/y/a/b", &no_query).as_deref(),
+            Some("/sub/y/a/b")
+        );
+        // Arguments must be parseable into their type
+        assert_eq!(route("b3").abci_path("/b/3/1/2/3/ii", &no_query), None);
+        assert_eq!(
+            route("b3ii")
+                .abci_path("/b/3/1/2/3/ii", &no_query)
+                .as_deref(),
+            Some("/b/3/1/2/3/ii")
+        );
+        // Optional arguments are taken from the query parameters
+        let query = HashMap::from([("a4".to_string(), "10".to_string())]);
+        assert_eq!(
+            route("b3iiii")
+                .abci_path("/b/3/1/2/iiii/xyz", &query)
+                .as_deref(),
+            Some("/b/3/1/2/iiii/xyz/10")
+        );
+        let query = HashMap::from([("a4".to_string(), "x".to_string())]);
+        assert_eq!(
+            route("b3iiii").abci_path("/b/3/1/2/iiii/xyz", &query),
+            None
+        );
+
+        // Responses are decoded into JSON
+        let data = "a".serialize_to_vec();
+        assert_eq!((route("a").decode)(&data), Ok(serde_json::json!("a")));
+        assert!((route("a").decode)(&[1, 2]).is_err());
+        assert!(route("c").with_options);
+
+        // Types that cannot be represented in JSON are wrapped as Borsh
+        let json = borsh_to_json::<String>(&data).unwrap();
+        assert_eq!(json, serde_json::json!({ "borsh": "0100000061" }));
+        assert_eq!(borsh_from_json::<String>(json), Ok(data));
+        assert!(borsh_from_json::<String>(serde_json::json!("a")).is_err());
+        let json = serde_json::json!({ "borsh": "01" });
+        assert!(borsh_from_json::<String>(json).is_err());
+    }
 }
@@ -11,6 +11,7 @@ use namada_core::hints;
 use namada_core::ledger::storage::traits::StorageHasher;
 use namada_core::ledger::storage::{DBIter, LastBlock, DB};
 use namada_core::ledger::storage_api::{self, ResultExt, StorageRead};
+use namada_core::proto::Tx;
 use namada_core::types::account::{Account, AccountPublicKeysMap};
 use namada_core::types::address::Address;
 use namada_core::types::hash::Hash;
@@ -18,7 +19,6 @@ use namada_core::types::storage::{
     self, BlockHeight, BlockResults, Epoch, KeySeg, PrefixValue,
 };
 use namada_core::types::token::MaspDenom;
-#[cfg(any(test, feature = "async-client"))]
 use namada_core::types::transaction::TxResult;
 
 use self::eth_bridge::{EthBridge, ETH_BRIDGE};
@@ -106,6 +106,20 @@ router! {SHELL,
     ( "ibc_packet" / [event_type: EventType] / [source_port: PortId] / [source_channel: ChannelId] / [destination_port: PortId] / [destination_channel: ChannelId] / [sequence: Sequence]) -> Option<Event> = ibc_packet,
 }
 
+json_encoding! {
+    response json: [Epoch, Address, Option<Account>],
+    response borsh: [
+        Option<LastBlock>,
+        TxResult,
+        Vec<PrefixValue>,
+        Conversion,
+        BTreeMap<AssetType, ConversionWithoutPath>,
+        Vec<BlockResults>,
+        Option<Event>,
+    ],
+    request borsh: [Tx],
+}
+
 // Handlers:
 
 fn dry_run_tx<D, H, V, T>(
@@ -18,7 +18,7 @@ use namada_core::types::eth_bridge_pool::{
     PendingTransfer, PendingTransferAppendix,
 };
 use namada_core::types::ethereum_events::{
-    EthAddress, EthereumEvent, TransferToEthereum,
+    EthAddress, EthereumEvent, TransferToEthereum, Uint,
 };
 use namada_core::types::ethereum_structs;
 use namada_core::types::keccak::KeccakHash;
@@ -40,8 +40,11 @@ use namada_ethereum_bridge::storage::{
     bridge_contract_key, native_erc20_key, vote_tallies,
 };
 use namada_proof_of_stake::pos_queries::PosQueries;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use crate::eth_bridge::ethers::abi::AbiDecode;
+use crate::eth_bridge::ethers::types::{Bytes, U256 as ethUint};
 use crate::queries::{EncodedResponseQuery, RequestCtx, RequestQuery};
 
 /// Contains information about the flow control of some ERC20
@@ -67,8 +70,71 @@ impl Erc20FlowControl {
     }
 }
 
+/// Statistics about the gas fees paid by the transfers in the
+/// Ethereum bridge pool, in some fee token.
+#[derive(
+    Debug,
+    Clone,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    JsonSchema,
+)]
+pub struct BridgePoolFeeStats {
+    /// The number of transfers paying fees in the token.
+    pub transfers: u64,
+    /// The lowest fee paid by a transfer.
+    pub min: Amount,
+    /// The median fee paid by the transfers.
+    pub median: Amount,
+    /// The highest fee paid by a transfer.
+    pub max: Amount,
+}
+
+impl BridgePoolFeeStats {
+    /// Compute the fee statistics of a non-empty set of fees.
+    fn from_fees(mut fees: Vec<Amount>) -> Option<Self> {
+        fees.sort_unstable();
+        let min = *fees.first()?;
+        let max = *fees.last()?;
+        let mid = fees.len() / 2;
+        let median = if fees.len() % 2 == 0 {
+            fees[mid - 1].checked_add(fees[mid])? / 2
+        } else {
+            fees[mid]
+        };
+        Some(Self {
+            transfers: fees.len() as u64,
+            min,
+            median,
+            max,
+        })
+    }
+
+    /// Recommend a gas fee for a new transfer, such that it is at
+    /// least as attractive to relayers as half of the transfers in
+    /// the pool.
+    #[inline]
+    pub fn recommended_fee(&self) -> Amount {
+        self.median
+    }
+}
+
 /// Request data to pass to `generate_bridge_pool_proof`.
-#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
+#[derive(
+    Debug,
+    Clone,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    JsonSchema,
+)]
 pub struct GenBridgePoolProofReq<'transfers, 'relayer> {
     /// The hashes of the transfers to be relayed.
     pub transfers: Cow<'transfers, [KeccakHash]>,
@@ -108,6 +174,39 @@ impl GenBridgePoolProofRsp {
     }
 }
 
+/// Proof of the value of some storage key, against the root
+/// of Namada's merkle tree at some block height.
+///
+/// Its fields are, in order: the state root, the block height,
+/// the storage key, the storage value and the ICS23 commitment
+/// proofs of the key's sub-tree and of the sub-tree's root.
+pub type StateProof = ([u8; 32], ethUint, Bytes, Bytes, Vec<Bytes>);
+
+/// Arguments to pass to `verifyStateProof`.
+pub type VerifyStateProofArgs = (
+    ethereum_structs::ValidatorSetArgs,
+    Vec<ethereum_structs::Signature>,
+    StateProof,
+);
+
+/// Response data returned by `generate_state_proof`.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct GenStateProofRsp {
+    /// The block height of the signed state root the proof
+    /// was generated against.
+    pub height: BlockHeight,
+    /// Ethereum ABI encoded arguments to pass to `verifyStateProof`.
+    pub abi_encoded_args: Vec<u8>,
+}
+
+impl GenStateProofRsp {
+    /// Decode the arguments to pass to `verifyStateProof`.
+    #[inline]
+    pub fn verify_state_proof_args(&self) -> Option<VerifyStateProofArgs> {
+        VerifyStateProofArgs::decode(&self.abi_encoded_args).ok()
+    }
+}
+
 router! {ETH_BRIDGE,
     // Get the current contents of the Ethereum bridge pool
     ( "pool" / "contents" )
@@ -118,11 +217,22 @@ router! {ETH_BRIDGE,
     ( "pool" / "signed_contents" )
         -> Vec<PendingTransfer> = read_signed_ethereum_bridge_pool,
 
+    // Get statistics about the gas fees paid by the transfers
+    // in the Ethereum bridge pool, per fee token
+    ( "pool" / "fee_stats" )
+        -> HashMap<Address, BridgePoolFeeStats> = read_bridge_pool_fee_stats,
+
     // Generate a merkle proof for the inclusion of requested
     // transfers in the Ethereum bridge pool
     ( "pool" / "proof" )
         -> GenBridgePoolProofRsp = (with_options generate_bridge_pool_proof),
 
+    // Generate a proof of the value of a storage key, against the
+    // latest root of Namada's merkle tree signed by a quorum of
+    // validators. Keys of the Ethereum bridge pool are not supported.
+    ( "state" / "proof" / [key: Key] )
+        -> GenStateProofRsp = generate_state_proof,
+
     // Iterates over all ethereum events and returns the amount of
     // voting power backing each `TransferToEthereum` event.
     ( "pool" / "transfer_to_eth_progress" )
@@ -175,6 +285,25 @@ router! {ETH_BRIDGE,
         -> Erc20FlowControl = get_erc20_flow_control,
 }
 
+json_encoding! {
+    response json: [
+        Vec<PendingTransfer>,
+        HashMap<Address, BridgePoolFeeStats>,
+        UpgradeableContract,
+        EthAddress,
+    ],
+    response borsh: [
+        GenBridgePoolProofRsp,
+        GenStateProofRsp,
+        HashMap<PendingTransfer, FractionalVotingPower>,
+        EncodeCell<EthereumProof<(Epoch, VotingPowersMap)>>,
+        ValidatorSetArgs,
+        VotingPowersMap,
+        Erc20FlowControl,
+    ],
+    request json: [GenBridgePoolProofReq<'static, 'static>],
+}
+
 /// Read the total supply and respective cap of some wrapped
 /// ERC20 token in Namada.
 fn get_erc20_flow_control<D, H, V, T>(
@@ -260,6 +389,29 @@ where
     ))
 }
 
+/// Read statistics about the gas fees paid by the transfers
+/// in the Ethereum bridge pool, per fee token.
+fn read_bridge_pool_fee_stats<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> storage_api::Result<HashMap<Address, BridgePoolFeeStats>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let mut fees: HashMap<Address, Vec<Amount>> = HashMap::new();
+    for transfer in read_ethereum_bridge_pool(ctx)? {
+        fees.entry(transfer.gas_fee.token)
+            .or_default()
+            .push(transfer.gas_fee.amount);
+    }
+    Ok(fees
+        .into_iter()
+        .filter_map(|(token, fees)| {
+            BridgePoolFeeStats::from_fees(fees).map(|stats| (token, stats))
+        })
+        .collect())
+}
+
 /// Read the contents of the Ethereum bridge
 /// pool covered by the latest signed root.
 fn read_signed_ethereum_bridge_pool<D, H, V, T>(
@@ -451,6 +603,79 @@ where
     }
 }
 
+/// Generate a proof of the value of a storage key, against the
+/// latest root of Namada's merkle tree signed by a quorum of
+/// validators.
+fn generate_state_proof<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    key: Key,
+) -> storage_api::Result<GenStateProofRsp>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let signed_root = ctx
+        .wl_storage
+        .ethbridge_queries()
+        .get_signed_state_root()
+        .ok_or(storage_api::Error::SimpleMessage(
+            "No signed root of Namada's state exists in storage.",
+        ))
+        .into_storage_result()?;
+    let (state_root, height) = signed_root.data;
+
+    let value = match ctx
+        .wl_storage
+        .storage
+        .read_with_height(&key, height)
+        .into_storage_result()?
+    {
+        (Some(value), _gas) => value,
+        (None, _gas) => {
+            return Err(storage_api::Error::Custom(CustomError(
+                format!(
+                    "The storage key {key} has no value at the height of the \
+                     signed state root ({height})"
+                )
+                .into(),
+            )));
+        }
+    };
+    let proof = ctx
+        .wl_storage
+        .storage
+        .get_existence_proof(&key, &value, height)
+        .into_storage_result()?;
+
+    let epoch = ctx
+        .wl_storage
+        .pos_queries()
+        .get_epoch(height)
+        .expect("The epoch of the signed state root should be known");
+    let (validator_args, voting_powers) = ctx
+        .wl_storage
+        .ethbridge_queries()
+        .get_bridge_validator_set(Some(epoch));
+    let validator_set: ethereum_structs::ValidatorSetArgs =
+        validator_args.into();
+    let signatures = sort_sigs(&voting_powers, &signed_root.signatures);
+    let state_proof: StateProof = (
+        state_root.0,
+        Uint::from(height.0).into(),
+        key.to_string().into_bytes().into(),
+        value.into(),
+        proof.ops.into_iter().map(|op| op.data.into()).collect(),
+    );
+    Ok(GenStateProofRsp {
+        height,
+        abi_encoded_args: ethers::abi::AbiEncode::encode((
+            validator_set,
+            signatures,
+            state_proof,
+        )),
+    })
+}
+
 /// Iterates over all ethereum events
 /// and returns the amount of voting power
 /// backing each `TransferToEthereum` event.
@@ -684,7 +909,7 @@ mod test_ethbridge_router {
     use namada_core::ledger::eth_bridge::storage::bridge_pool::{
         get_pending_key, get_signed_root_key, BridgePoolTree,
     };
-    use namada_core::ledger::eth_bridge::storage::whitelist;
+    use namada_core::ledger::eth_bridge::storage::{state_root, whitelist};
     use namada_core::ledger::storage::mockdb::MockDBWriteBatch;
     use namada_core::ledger::storage_api::StorageWrite;
     use namada_core::types::address::nam;
@@ -694,7 +919,7 @@ mod test_ethbridge_router {
         GasFee, PendingTransfer, TransferToEthereum, TransferToEthereumKind,
     };
     use namada_core::types::ethereum_events::EthAddress;
-    use namada_core::types::storage::BlockHeight;
+    use namada_core::types::storage::{BlockHeight, KeySeg};
     use namada_core::types::vote_extensions::validator_set_update;
     use namada_core::types::vote_extensions::validator_set_update::{
         EthAddrBook, VotingPowersMapExt,
@@ -703,7 +928,9 @@ mod test_ethbridge_router {
         EthBridgeVotingPower, FractionalVotingPower,
     };
     use namada_ethereum_bridge::protocol::transactions::validator_set_update::aggregate_votes;
-    use namada_ethereum_bridge::storage::proof::BridgePoolRootProof;
+    use namada_ethereum_bridge::storage::proof::{
+        BridgePoolRootProof, StateRootProof,
+    };
     use namada_proof_of_stake::pos_queries::PosQueries;
 
     use super::test_utils::bertha_address;
@@ -943,6 +1170,58 @@ mod test_ethbridge_router {
         assert_eq!(pool, Vec::from([transfer]));
     }
 
+    /// Test that the fee statistics of the bridge pool are
+    /// computed per fee token.
+    #[tokio::test]
+    async fn test_read_bridge_pool_fee_stats() {
+        let mut client = TestClient::new(RPC);
+
+        // write transfers paying different fees into the bridge pool
+        client.wl_storage.storage.block.height = 1.into();
+        for (i, fee) in [1u64, 3, 4, 10].into_iter().enumerate() {
+            let transfer = PendingTransfer {
+                transfer: TransferToEthereum {
+                    kind: TransferToEthereumKind::Erc20,
+                    asset: EthAddress([0; 20]),
+                    recipient: EthAddress([i as u8; 20]),
+                    sender: bertha_address(),
+                    amount: 0.into(),
+                },
+                gas_fee: GasFee {
+                    token: nam(),
+                    amount: fee.into(),
+                    payer: bertha_address(),
+                },
+            };
+            client
+                .wl_storage
+                .write_bytes(
+                    &get_pending_key(&transfer),
+                    transfer.serialize_to_vec(),
+                )
+                .expect("Test failed");
+        }
+
+        // commit the changes and increase block height
+        client.wl_storage.commit_block().expect("Test failed");
+        client.wl_storage.storage.block.height += 1;
+
+        // check the response
+        let stats = RPC
+            .shell()
+            .eth_bridge()
+            .read_bridge_pool_fee_stats(&client)
+            .await
+            .unwrap();
+        let expected = BridgePoolFeeStats {
+            transfers: 4,
+            min: 1.into(),
+            median: 3.into(),
+            max: 10.into(),
+        };
+        assert_eq!(stats, HashMap::from([(nam(), expected)]));
+    }
+
     /// Test that reading the bridge pool always gets
     /// the latest pool
     #[tokio::test]
@@ -1122,6 +1401,93 @@ mod test_ethbridge_router {
         assert_eq!(encoded, resp.data.abi_encoded_args);
     }
 
+    /// Test that we can generate a proof of the value of a storage
+    /// key against the latest signed state root, even if the value
+    /// has changed since.
+    #[tokio::test]
+    async fn test_generate_state_proof() {
+        let mut client = TestClient::new(RPC);
+        test_utils::init_default_storage(&mut client.wl_storage);
+
+        // write a value to storage
+        let key = Key::from(bertha_address().to_db_key())
+            .push(&"state_proof_test".to_owned())
+            .expect("Test failed");
+        client
+            .wl_storage
+            .write_bytes(&key, vec![1, 2, 3])
+            .expect("Test failed");
+        let written_height = client.wl_storage.storage.block.height;
+
+        // commit the changes and increase block height
+        client.wl_storage.commit_block().expect("Test failed");
+        client.wl_storage.storage.block.height += 1;
+
+        // sign the state root at the previous height, and
+        // update the value
+        let state_root = client
+            .wl_storage
+            .ethbridge_queries()
+            .get_state_root_at_height(written_height)
+            .expect("Test failed");
+        client
+            .wl_storage
+            .write(
+                &state_root::signed_root_key(),
+                StateRootProof::new((state_root, written_height)),
+            )
+            .expect("Test failed");
+        client
+            .wl_storage
+            .write_bytes(&key, vec![4, 5, 6])
+            .expect("Test failed");
+
+        // commit the changes and increase block height
+        client.wl_storage.commit_block().expect("Test failed");
+        client.wl_storage.storage.block.height += 1;
+
+        let resp = RPC
+            .shell()
+            .eth_bridge()
+            .generate_state_proof(&client, &key)
+            .await
+            .unwrap();
+        assert_eq!(resp.height, written_height);
+        let (_, _, (root, height, proof_key, value, proof_ops)) =
+            resp.verify_state_proof_args().expect("Test failed");
+        assert_eq!(root, state_root.0);
+        assert_eq!(height, Uint::from(written_height.0).into());
+        assert_eq!(proof_key.to_vec(), key.to_string().into_bytes());
+        assert_eq!(value.to_vec(), vec![1, 2, 3]);
+        // one proof for the sub-tree, another for the base tree
+        assert_eq!(proof_ops.len(), 2);
+    }
+
+    /// Test that we cannot generate a proof of Namada's state
+    /// if no state root has been signed yet.
+    #[tokio::test]
+    async fn test_cannot_get_state_proof_without_signed_root() {
+        let mut client = TestClient::new(RPC);
+        test_utils::init_default_storage(&mut client.wl_storage);
+
+        let key = Key::from(bertha_address().to_db_key())
+            .push(&"state_proof_test".to_owned())
+            .expect("Test failed");
+        client
+            .wl_storage
+            .write_bytes(&key, vec![1, 2, 3])
+            .expect("Test failed");
+        client.wl_storage.commit_block().expect("Test failed");
+        client.wl_storage.storage.block.height += 1;
+
+        let resp = RPC
+            .shell()
+            .eth_bridge()
+            .generate_state_proof(&client, &key)
+            .await;
+        assert!(resp.is_err());
+    }
+
     /// Test if the merkle tree including a transfer has not had its
     /// root signed, then we cannot generate a proof.
     #[tokio::test]
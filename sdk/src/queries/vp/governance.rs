@@ -15,6 +15,10 @@ router! {GOV,
     ( "parameters" ) -> GovernanceParameters = parameters,
 }
 
+json_encoding! {
+    response borsh: [Option<StorageProposal>, Vec<Vote>, GovernanceParameters],
+}
+
 /// Find if the given address belongs to a validator account.
 fn proposal_id<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -15,6 +15,11 @@ router! {PGF,
     ( "parameters" ) -> PgfParameters = parameters,
 }
 
+json_encoding! {
+    response json: [PgfParameters],
+    response borsh: [Vec<StewardDetail>, Vec<StoragePgfFunding>],
+}
+
 /// Query the currect pgf steward set
 fn stewards<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -124,6 +124,30 @@ router! {POS,
 
 }
 
+json_encoding! {
+    response json: [
+        HashSet<Address>,
+        Option<token::Amount>,
+        Option<ValidatorMetaData>,
+        token::Amount,
+        HashMap<Address, token::Amount>,
+        Option<Address>,
+        BTreeSet<common::PublicKey>,
+    ],
+    response borsh: [
+        Vec<Slash>,
+        Option<CommissionPair>,
+        Option<ValidatorState>,
+        BTreeSet<WeightedValidator>,
+        PosParams,
+        HashMap<Epoch, token::Change>,
+        HashMap<(Epoch, Epoch), token::Amount>,
+        BondsAndUnbondsDetails,
+        HashMap<Address, BTreeMap<Epoch, Vec<Slash>>>,
+        HashMap<Address, Vec<Slash>>,
+    ],
+}
+
 /// Enriched bonds data with extra information calculated from the data queried
 /// from the node.
 #[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema)]
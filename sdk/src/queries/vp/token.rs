@@ -12,6 +12,10 @@ router! {TOKEN,
     ( "denomination" / [addr: Address] ) -> Option<token::Denomination> = denomination,
 }
 
+json_encoding! {
+    response json: [Option<token::Denomination>],
+}
+
 /// Get the number of decimal places (in base 10) for a
 /// token specified by `addr`.
 fn denomination<D, H, V, T>(
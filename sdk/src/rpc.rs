@@ -20,11 +20,12 @@ use namada_core::types::address::{Address, InternalAddress};
 use namada_core::types::hash::Hash;
 use namada_core::types::key::common;
 use namada_core::types::storage::{
-    BlockHeight, BlockResults, Epoch, Key, PrefixValue,
+    BlockHeight, BlockResults, Epoch, Key, PrefixValue, TxIndex,
 };
 use namada_core::types::token::{
     Amount, DenominatedAmount, Denomination, MaspDenom,
 };
+use namada_core::types::transaction::TxType;
 use namada_core::types::{storage, token};
 use namada_proof_of_stake::parameters::PosParams;
 use namada_proof_of_stake::types::{
@@ -135,6 +136,41 @@ pub async fn query_block<C: crate::queries::Client + Sync>(
     convert_response::<C, _>(RPC.shell().last_block(client).await)
 }
 
+/// Query the hash of the transaction at the given index of the block at the
+/// given height. For a decrypted transaction, this is the hash of its inner
+/// transaction, as reported by the event of its application.
+pub async fn query_tx_hash<C: crate::queries::Client + Sync>(
+    client: &C,
+    height: BlockHeight,
+    index: TxIndex,
+) -> Result<Hash, error::Error> {
+    let tm_height = Height::try_from(height.0).map_err(|e| {
+        Error::Other(format!("Invalid block height {height}: {e}"))
+    })?;
+    let block = client
+        .block(tm_height)
+        .await
+        .map_err(|e| Error::from(QueryError::General(e.to_string())))?
+        .block;
+    let bytes = block.data.get(index.0 as usize).ok_or_else(|| {
+        Error::from(QueryError::General(format!(
+            "The block at height {height} has no transaction at index {}",
+            index.0
+        )))
+    })?;
+    let tx = Tx::try_from(bytes.as_slice()).map_err(|e| {
+        Error::from(EncodingError::Decoding(format!(
+            "Failed to decode the transaction at height {height} and index \
+             {}: {e}",
+            index.0
+        )))
+    })?;
+    Ok(match tx.header().tx_type {
+        TxType::Decrypted(_) => tx.raw_header_hash(),
+        _ => tx.header_hash(),
+    })
+}
+
 /// A helper to unwrap client's response. Will shut down process on error.
 fn unwrap_client_response<C: crate::queries::Client, T>(
     response: Result<T, C::Error>,
@@ -476,17 +512,23 @@ pub async fn dry_run_tx<N: Namada>(
     context: &N,
     tx_bytes: Vec<u8>,
 ) -> Result<namada_core::types::transaction::TxResult, Error> {
-    let (data, height, prove) = (Some(tx_bytes), None, false);
-    let result = convert_response::<N::Client, _>(
-        RPC.shell()
-            .dry_run_tx(context.client(), data, height, prove)
-            .await,
-    )?
-    .data;
+    let result = simulate_tx(context.client(), tx_bytes).await?;
     display_line!(context.io(), "Dry-run result: {}", result);
     Ok(result)
 }
 
+/// Dry run a transaction without displaying its result
+pub async fn simulate_tx<C: crate::queries::Client + Sync>(
+    client: &C,
+    tx_bytes: Vec<u8>,
+) -> Result<namada_core::types::transaction::TxResult, Error> {
+    let (data, height, prove) = (Some(tx_bytes), None, false);
+    Ok(convert_response::<C, _>(
+        RPC.shell().dry_run_tx(client, data, height, prove).await,
+    )?
+    .data)
+}
+
 /// Data needed for broadcasting a tx and
 /// monitoring its progress on chain
 ///
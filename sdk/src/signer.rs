@@ -0,0 +1,794 @@
+//! Signers holding secret keys outside of the wallet, such as key management
+//! services, HSMs or threshold signing services.
+//!
+//! A [`Signer`] only has to look up public keys and sign [`SignPayload`]s.
+//! This is enough to sign every part of a transaction, as well as the data
+//! signed with validator protocol keys, e.g. vote extensions. Payloads carry
+//! what is being signed rather than its hash, so that a signer can check
+//! what it signs. A [`RemoteSigner`] talks to a signing daemon over a local
+//! socket, which can be served from any other [`Signer`] with [`serve`].
+
+use std::collections::{BTreeMap, HashSet};
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+#[cfg(unix)]
+use std::time::Duration;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use borsh_ext::BorshSerializeExt;
+use data_encoding::HEXLOWER;
+use namada_core::ledger::storage::Sha256Hasher;
+use namada_core::proto::{Section, SerializeWithBorsh, Signature, Signed};
+use namada_core::types::address::Address;
+#[cfg(unix)]
+use namada_core::types::address::ImplicitAddress;
+use namada_core::types::hash::Hash;
+use namada_core::types::key::*;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use zeroize::Zeroizing;
+
+use crate::error::Error;
+use crate::proto::{self, Tx};
+use crate::signing::Signable;
+use crate::wallet::{Wallet, WalletIo};
+use crate::{args, MaybeSend, MaybeSync};
+
+/// Something to be signed by a [`Signer`]
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub enum SignPayload {
+    /// The raw header of the given transaction, as signed by the signers of
+    /// its inner transaction
+    RawHeader(Tx),
+    /// All the sections of the given transaction, as signed by the fee
+    /// payer of a wrapper or the signer of a protocol transaction
+    Sections(Tx),
+    /// The given Borsh encoded data, as signed in a [`Signed`] value, e.g. a
+    /// vote extension
+    Data(Vec<u8>),
+}
+
+impl SignPayload {
+    /// The hashes of the transaction sections committed to by a signature
+    /// section over this payload
+    fn targets(&self) -> Vec<Hash> {
+        match self {
+            Self::RawHeader(tx) => vec![tx.raw_header_hash()],
+            Self::Sections(tx) => {
+                // Signatures made by the protocol are not signed over
+                let mut tx = tx.clone();
+                tx.protocol_filter();
+                tx.sechashes()
+            }
+            Self::Data(_) => vec![],
+        }
+    }
+
+    /// The hash that the given key has to sign for this payload
+    pub fn signed_hash(&self, public_key: &common::PublicKey) -> Hash {
+        if let Self::Data(data) = self {
+            return Hash(data.signable_hash::<Sha256Hasher>());
+        }
+        Signature {
+            targets: self.targets(),
+            signer: proto::Signer::PubKeys(vec![public_key.clone()]),
+            signatures: BTreeMap::new(),
+        }
+        .get_raw_hash()
+    }
+}
+
+/// A holder of secret keys, able to sign with them
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+pub trait Signer {
+    /// Look up the public key with the given hash, if its secret key is held
+    /// by this signer
+    async fn find_public_key(
+        &self,
+        pkh: &PublicKeyHash,
+    ) -> Result<Option<common::PublicKey>, Error>;
+
+    /// Sign the given payload with the secret key of the given public key,
+    /// if it is held by this signer. The signature is made over
+    /// [`SignPayload::signed_hash`].
+    async fn sign(
+        &self,
+        public_key: &common::PublicKey,
+        payload: &SignPayload,
+    ) -> Result<Option<common::Signature>, Error>;
+}
+
+/// A signer using the keys of a software wallet
+pub struct WalletSigner<'a, U> {
+    wallet: &'a RwLock<Wallet<U>>,
+    password: Option<Zeroizing<String>>,
+}
+
+impl<'a, U> WalletSigner<'a, U> {
+    /// Sign with the keys of the given wallet. The password is used to
+    /// decrypt the keys, otherwise it is prompted for if needed.
+    pub fn new(
+        wallet: &'a RwLock<Wallet<U>>,
+        password: Option<Zeroizing<String>>,
+    ) -> Self {
+        Self { wallet, password }
+    }
+}
+
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+impl<U> Signer for WalletSigner<'_, U>
+where
+    U: WalletIo + MaybeSend + MaybeSync,
+{
+    async fn find_public_key(
+        &self,
+        pkh: &PublicKeyHash,
+    ) -> Result<Option<common::PublicKey>, Error> {
+        Ok(self.wallet.read().await.find_public_key_by_pkh(pkh).ok())
+    }
+
+    async fn sign(
+        &self,
+        public_key: &common::PublicKey,
+        payload: &SignPayload,
+    ) -> Result<Option<common::Signature>, Error> {
+        Ok(self
+            .wallet
+            .write()
+            .await
+            .find_key_by_pk(public_key, self.password.clone())
+            .ok()
+            .map(|secret_key| {
+                common::SigScheme::sign(
+                    &secret_key,
+                    payload.signed_hash(public_key),
+                )
+            }))
+    }
+}
+
+fn missing_key(public_key: &common::PublicKey) -> Error {
+    Error::Other(format!(
+        "The signer doesn't hold the secret key of {}",
+        public_key
+    ))
+}
+
+/// Make a signature section over the given payload with the given key of
+/// the signer
+pub async fn sign_section(
+    signer: &(impl Signer + ?Sized),
+    public_key: &common::PublicKey,
+    payload: SignPayload,
+) -> Result<Signature, Error> {
+    if let SignPayload::Data(_) = payload {
+        return Err(Error::Other(
+            "Only transactions are signed in sections".to_string(),
+        ));
+    }
+    let targets = payload.targets();
+    let signature = signer
+        .sign(public_key, &payload)
+        .await?
+        .ok_or_else(|| missing_key(public_key))?;
+    Ok(Signature {
+        targets,
+        signer: proto::Signer::PubKeys(vec![public_key.clone()]),
+        signatures: [(0, signature)].into(),
+    })
+}
+
+/// Sign the requested parts of the given transaction with the given key of
+/// the signer. To be used as the fallback of [`crate::signing::sign_tx`], for
+/// the keys that are not in the wallet.
+pub async fn with_signer<S>(
+    mut tx: Tx,
+    pubkey: common::PublicKey,
+    parts: HashSet<Signable>,
+    signer: &S,
+) -> Result<Tx, Error>
+where
+    S: Signer + MaybeSync,
+{
+    // Sign the raw header if that is requested
+    if parts.contains(&Signable::RawHeader) {
+        let section =
+            sign_section(signer, &pubkey, SignPayload::RawHeader(tx.clone()))
+                .await?;
+        tx.protocol_filter();
+        tx.add_section(Section::Signature(section));
+    }
+    // Sign the fee header if that is requested
+    if parts.contains(&Signable::FeeHeader) {
+        tx.protocol_filter();
+        let section =
+            sign_section(signer, &pubkey, SignPayload::Sections(tx.clone()))
+                .await?;
+        tx.add_section(Section::Signature(section));
+    }
+    Ok(tx)
+}
+
+/// Sign the given data with the given key of the signer, e.g. a validator
+/// protocol key, with the same result as [`Signed::new`].
+pub async fn sign_data<T>(
+    signer: &(impl Signer + ?Sized),
+    public_key: &common::PublicKey,
+    data: T,
+) -> Result<Signed<T, SerializeWithBorsh>, Error>
+where
+    T: BorshSerialize,
+{
+    let payload = SignPayload::Data(data.serialize_to_vec());
+    let signature = signer
+        .sign(public_key, &payload)
+        .await?
+        .ok_or_else(|| missing_key(public_key))?;
+    Ok(Signed::new_from(data, signature))
+}
+
+/// Look up the public key of the given implicit address in the remote signer
+/// of the given transaction arguments, if any
+pub async fn find_remote_pk(
+    args: &args::Tx,
+    addr: &Address,
+) -> Result<Option<common::PublicKey>, Error> {
+    match (addr, &args.remote_signer) {
+        #[cfg(unix)]
+        (Address::Implicit(ImplicitAddress(pkh)), Some(socket)) => {
+            RemoteSigner::new(socket).find_public_key(pkh).await
+        }
+        _ => Ok(None),
+    }
+}
+
+/// A request to a signing daemon. Keys, key hashes and signatures are in
+/// their usual string encoding, payloads are hex encoded Borsh.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "method")]
+pub enum SignerRequest {
+    /// Look up a public key from its hash
+    FindPublicKey {
+        /// The hash of the public key
+        pkh: String,
+    },
+    /// Sign a payload
+    Sign {
+        /// The public key of the secret key to sign with
+        public_key: String,
+        /// The [`SignPayload`] to sign
+        payload: String,
+    },
+}
+
+impl SignerRequest {
+    /// A request to sign the given payload with the given key
+    pub fn sign(public_key: &common::PublicKey, payload: &SignPayload) -> Self {
+        Self::Sign {
+            public_key: public_key.to_string(),
+            payload: HEXLOWER.encode(&payload.serialize_to_vec()),
+        }
+    }
+}
+
+/// The response of a signing daemon to a [`SignerRequest`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerResponse {
+    /// The public key that was looked up, if found
+    PublicKey(Option<String>),
+    /// The requested signature, if the key was found
+    Signature(Option<String>),
+    /// The request failed
+    Error(String),
+}
+
+#[cfg(unix)]
+impl SignerResponse {
+    fn into_public_key(self) -> Result<Option<common::PublicKey>, Error> {
+        match self {
+            Self::PublicKey(pk) => pk
+                .map(|pk| {
+                    pk.parse().map_err(|err| {
+                        Error::Other(format!(
+                            "Invalid public key from the signer: {}",
+                            err
+                        ))
+                    })
+                })
+                .transpose(),
+            other => Err(other.unexpected()),
+        }
+    }
+
+    fn into_signature(self) -> Result<Option<common::Signature>, Error> {
+        match self {
+            Self::Signature(sig) => sig
+                .map(|sig| {
+                    sig.parse().map_err(|err| {
+                        Error::Other(format!(
+                            "Invalid signature from the signer: {}",
+                            err
+                        ))
+                    })
+                })
+                .transpose(),
+            other => Err(other.unexpected()),
+        }
+    }
+
+    fn unexpected(self) -> Error {
+        match self {
+            Self::Error(err) => {
+                Error::Other(format!("The signer failed with: {}", err))
+            }
+            other => Error::Other(format!(
+                "Unexpected response from the signer: {:?}",
+                other
+            )),
+        }
+    }
+}
+
+/// Answer a request to a signing daemon with the given signer
+pub async fn handle_request(
+    signer: &(impl Signer + ?Sized),
+    request: SignerRequest,
+) -> SignerResponse {
+    let response = match request {
+        SignerRequest::FindPublicKey { pkh } => match pkh.parse() {
+            Ok(pkh) => signer.find_public_key(&pkh).await.map(|pk| {
+                SignerResponse::PublicKey(pk.map(|pk| pk.to_string()))
+            }),
+            Err(err) => {
+                Err(Error::Other(format!("Invalid public key hash: {}", err)))
+            }
+        },
+        SignerRequest::Sign {
+            public_key,
+            payload,
+        } => {
+            let payload = HEXLOWER
+                .decode(payload.as_bytes())
+                .ok()
+                .and_then(|bytes| SignPayload::try_from_slice(&bytes).ok());
+            match (public_key.parse(), payload) {
+                (Ok(public_key), Some(payload)) => {
+                    signer.sign(&public_key, &payload).await.map(|sig| {
+                        SignerResponse::Signature(
+                            sig.map(|sig| sig.to_string()),
+                        )
+                    })
+                }
+                _ => Err(Error::Other(
+                    "Invalid public key or payload".to_string(),
+                )),
+            }
+        }
+    };
+    response.unwrap_or_else(|err| SignerResponse::Error(err.to_string()))
+}
+
+/// How long a signing daemon keeps a connection open without receiving a
+/// request
+#[cfg(unix)]
+const SIGNER_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A client of a signing daemon listening on a Unix socket. Requests and
+/// responses are JSON encoded [`SignerRequest`]s and [`SignerResponse`]s,
+/// one per line.
+#[cfg(unix)]
+#[derive(Clone, Debug)]
+pub struct RemoteSigner {
+    socket: PathBuf,
+    timeout: Option<Duration>,
+}
+
+#[cfg(unix)]
+impl RemoteSigner {
+    /// Connect to the daemon listening on the given socket
+    pub fn new(socket: impl Into<PathBuf>) -> Self {
+        Self {
+            socket: socket.into(),
+            timeout: None,
+        }
+    }
+
+    /// Give up on requests, and thus leave payloads unsigned, when the daemon
+    /// takes longer than the given duration to receive them or to respond.
+    /// There is no timeout by default, so that the daemon may prompt for
+    /// passwords.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    fn io_err(&self, err: std::io::Error) -> Error {
+        Error::Other(format!(
+            "Unable to reach the signer at {}: {}",
+            self.socket.to_string_lossy(),
+            err
+        ))
+    }
+
+    fn encode_request(request: &SignerRequest) -> Result<String, Error> {
+        let mut line = serde_json::to_string(request)
+            .map_err(|err| Error::Other(err.to_string()))?;
+        line.push('\n');
+        Ok(line)
+    }
+
+    fn decode_response(
+        response: Option<String>,
+    ) -> Result<SignerResponse, Error> {
+        let response = response.ok_or_else(|| {
+            Error::Other("The signer closed the connection".to_string())
+        })?;
+        serde_json::from_str(&response).map_err(|err| {
+            Error::Other(format!("Invalid response from the signer: {}", err))
+        })
+    }
+
+    async fn request(
+        &self,
+        request: &SignerRequest,
+    ) -> Result<SignerResponse, Error> {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let request = Self::encode_request(request)?;
+        let exchange = async {
+            let mut stream =
+                tokio::net::UnixStream::connect(&self.socket).await?;
+            stream.write_all(request.as_bytes()).await?;
+            BufReader::new(stream).lines().next_line().await
+        };
+        let response = match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, exchange)
+                .await
+                .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))
+                .and_then(|response| response),
+            None => exchange.await,
+        }
+        .map_err(|err| self.io_err(err))?;
+        Self::decode_response(response)
+    }
+
+    /// Sign the given payload outside of an async runtime, e.g. from the
+    /// ledger's shell thread
+    pub fn sign_blocking(
+        &self,
+        public_key: &common::PublicKey,
+        payload: &SignPayload,
+    ) -> Result<Option<common::Signature>, Error> {
+        use std::io::{BufRead, BufReader, Write};
+
+        let request = SignerRequest::sign(public_key, payload);
+        let mut stream = std::os::unix::net::UnixStream::connect(&self.socket)
+            .map_err(|err| self.io_err(err))?;
+        stream
+            .set_read_timeout(self.timeout)
+            .and_then(|()| stream.set_write_timeout(self.timeout))
+            .map_err(|err| self.io_err(err))?;
+        stream
+            .write_all(Self::encode_request(&request)?.as_bytes())
+            .map_err(|err| self.io_err(err))?;
+        let response = BufReader::new(stream)
+            .lines()
+            .next()
+            .transpose()
+            .map_err(|err| self.io_err(err))?;
+        Self::decode_response(response)?.into_signature()
+    }
+}
+
+#[cfg(unix)]
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+impl Signer for RemoteSigner {
+    async fn find_public_key(
+        &self,
+        pkh: &PublicKeyHash,
+    ) -> Result<Option<common::PublicKey>, Error> {
+        self.request(&SignerRequest::FindPublicKey {
+            pkh: pkh.to_string(),
+        })
+        .await?
+        .into_public_key()
+    }
+
+    async fn sign(
+        &self,
+        public_key: &common::PublicKey,
+        payload: &SignPayload,
+    ) -> Result<Option<common::Signature>, Error> {
+        self.request(&SignerRequest::sign(public_key, payload))
+            .await?
+            .into_signature()
+    }
+}
+
+/// Serve the keys of the given signer to [`RemoteSigner`]s connecting to the
+/// given Unix socket, until an I/O error occurs. Connections are handled
+/// concurrently, and are closed after a minute without requests.
+///
+/// The socket is only accessible to its owner, and connections from
+/// processes of other users are refused.
+#[cfg(unix)]
+pub async fn serve(
+    socket: &Path,
+    signer: &(impl Signer + ?Sized),
+) -> Result<(), Error> {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    use futures::stream::{FuturesUnordered, StreamExt};
+
+    let io_err = |err: std::io::Error| {
+        Error::Other(format!(
+            "Signer socket {} failed: {}",
+            socket.to_string_lossy(),
+            err
+        ))
+    };
+    let listener = tokio::net::UnixListener::bind(socket).map_err(io_err)?;
+    std::fs::set_permissions(socket, std::fs::Permissions::from_mode(0o600))
+        .map_err(io_err)?;
+    let owner = std::fs::metadata(socket).map_err(io_err)?.uid();
+    let mut connections = FuturesUnordered::new();
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted.map_err(io_err)?;
+                // Clients may have connected before the permissions were
+                // restricted
+                match stream.peer_cred() {
+                    Ok(cred) if cred.uid() == owner => {}
+                    _ => continue,
+                }
+                connections.push(serve_connection(stream, signer));
+            }
+            Some(()) = connections.next(), if !connections.is_empty() => {}
+        }
+    }
+}
+
+/// Answer the requests of a client of [`serve`] until it disconnects or stays
+/// idle for too long
+#[cfg(unix)]
+async fn serve_connection(
+    stream: tokio::net::UnixStream,
+    signer: &(impl Signer + ?Sized),
+) {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let (read, mut write) = stream.into_split();
+    let mut lines = BufReader::new(read).lines();
+    // A client that misbehaves only loses its own connection
+    while let Ok(Ok(Some(line))) =
+        tokio::time::timeout(SIGNER_IDLE_TIMEOUT, lines.next_line()).await
+    {
+        let response = match serde_json::from_str(&line) {
+            Ok(request) => handle_request(signer, request).await,
+            Err(err) => {
+                SignerResponse::Error(format!("Invalid request: {}", err))
+            }
+        };
+        let Ok(mut line) = serde_json::to_string(&response) else {
+            break;
+        };
+        line.push('\n');
+        if write.write_all(line.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::proto::Data;
+    use namada_core::types::account::AccountPublicKeysMap;
+    use namada_core::types::transaction::TxType;
+
+    use super::*;
+
+    /// A signer holding a single secret key
+    struct KeySigner(common::SecretKey);
+
+    #[cfg_attr(feature = "async-send", async_trait::async_trait)]
+    #[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+    impl Signer for KeySigner {
+        async fn find_public_key(
+            &self,
+            pkh: &PublicKeyHash,
+        ) -> Result<Option<common::PublicKey>, Error> {
+            let pk = self.0.ref_to();
+            Ok((PublicKeyHash::from(&pk) == *pkh).then_some(pk))
+        }
+
+        async fn sign(
+            &self,
+            public_key: &common::PublicKey,
+            payload: &SignPayload,
+        ) -> Result<Option<common::Signature>, Error> {
+            Ok((self.0.ref_to() == *public_key).then(|| {
+                common::SigScheme::sign(
+                    &self.0,
+                    payload.signed_hash(public_key),
+                )
+            }))
+        }
+    }
+
+    fn keypair() -> common::SecretKey {
+        namada_core::types::key::testing::keypair_1()
+    }
+
+    fn tx() -> Tx {
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.set_data(Data::new(b"data".to_vec()));
+        tx
+    }
+
+    /// Test that signing through a signer gives the same signature sections
+    /// as signing with the secret key directly.
+    #[tokio::test]
+    async fn test_with_signer_matches_local_signing() {
+        let keypair = keypair();
+        let signer = KeySigner(keypair.clone());
+        let parts = HashSet::from([Signable::RawHeader, Signable::FeeHeader]);
+        let tx = tx();
+
+        let signed = with_signer(tx.clone(), keypair.ref_to(), parts, &signer)
+            .await
+            .unwrap();
+        let mut expected = tx;
+        expected.sign_raw(
+            vec![keypair.clone()],
+            AccountPublicKeysMap::from_iter([keypair.ref_to()]),
+            None,
+        );
+        expected.sign_wrapper(keypair);
+        assert_eq!(signed.serialize_to_vec(), expected.serialize_to_vec());
+    }
+
+    /// Test that data signed through a signer verifies like [`Signed::new`].
+    #[tokio::test]
+    async fn test_sign_data() {
+        let keypair = keypair();
+        let signer = KeySigner(keypair.clone());
+        let signed: Signed<u64, SerializeWithBorsh> =
+            sign_data(&signer, &keypair.ref_to(), 42u64).await.unwrap();
+        assert!(signed.verify(&keypair.ref_to()).is_ok());
+        assert_eq!(
+            signed.sig,
+            Signed::<u64, SerializeWithBorsh>::new(&keypair, 42).sig
+        );
+
+        let other = namada_core::types::key::testing::keypair_2();
+        let result: Result<Signed<u64, SerializeWithBorsh>, _> =
+            sign_data(&signer, &other.ref_to(), 42).await;
+        assert!(result.is_err());
+    }
+
+    /// Test a round trip through a signing daemon.
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_remote_signer() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let keypair = keypair();
+        let signer = KeySigner(keypair.clone());
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("signer.sock");
+        let remote = RemoteSigner::new(&socket);
+        let payload = SignPayload::Sections(tx());
+        let expected = common::SigScheme::sign(
+            &keypair,
+            payload.signed_hash(&keypair.ref_to()),
+        );
+
+        let client = async {
+            // Wait for the daemon to bind its socket
+            while !socket.exists() {
+                tokio::task::yield_now().await;
+            }
+            let pkh = PublicKeyHash::from(&keypair.ref_to());
+            assert_eq!(
+                remote.find_public_key(&pkh).await.unwrap(),
+                Some(keypair.ref_to())
+            );
+            let other = namada_core::types::key::testing::keypair_2();
+            assert_eq!(
+                remote
+                    .find_public_key(&PublicKeyHash::from(&other.ref_to()))
+                    .await
+                    .unwrap(),
+                None
+            );
+            assert_eq!(
+                remote.sign(&keypair.ref_to(), &payload).await.unwrap(),
+                Some(expected.clone())
+            );
+            // The socket is only accessible to its owner
+            let mode = std::fs::metadata(&socket).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+            // Payloads that don't decode are not signed
+            let response = handle_request(
+                &signer,
+                SignerRequest::Sign {
+                    public_key: keypair.ref_to().to_string(),
+                    payload: "00".repeat(32),
+                },
+            )
+            .await;
+            assert!(matches!(response, SignerResponse::Error(_)));
+            // The blocking client gets the same signature
+            let remote = remote.clone();
+            let pk = keypair.ref_to();
+            let payload = payload.clone();
+            let blocking = tokio::task::spawn_blocking(move || {
+                remote.sign_blocking(&pk, &payload)
+            });
+            assert_eq!(blocking.await.unwrap().unwrap(), Some(expected));
+        };
+        tokio::select! {
+            result = serve(&socket, &signer) => panic!("{:?}", result),
+            () = client => {}
+        }
+    }
+
+    /// Test that a client holding its connection open doesn't keep the
+    /// daemon from answering other clients.
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_serve_concurrent_connections() {
+        let keypair = keypair();
+        let signer = KeySigner(keypair.clone());
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("signer.sock");
+        let remote = RemoteSigner::new(&socket);
+
+        let client = async {
+            while !socket.exists() {
+                tokio::task::yield_now().await;
+            }
+            let _idle = tokio::net::UnixStream::connect(&socket).await.unwrap();
+            let pkh = PublicKeyHash::from(&keypair.ref_to());
+            let found = tokio::time::timeout(
+                Duration::from_secs(10),
+                remote.find_public_key(&pkh),
+            )
+            .await
+            .expect("The daemon must answer while another client is idle");
+            assert_eq!(found.unwrap(), Some(keypair.ref_to()));
+        };
+        tokio::select! {
+            result = serve(&socket, &signer) => panic!("{:?}", result),
+            () = client => {}
+        }
+    }
+
+    /// Test that requests to an unresponsive daemon time out, leaving the
+    /// payload unsigned.
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_remote_signer_timeout() {
+        let keypair = keypair();
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("signer.sock");
+        // Connections are queued but never answered
+        let _listener =
+            std::os::unix::net::UnixListener::bind(&socket).unwrap();
+        let remote =
+            RemoteSigner::new(&socket).with_timeout(Duration::from_millis(100));
+        let payload = SignPayload::Data(vec![]);
+
+        assert!(remote.sign(&keypair.ref_to(), &payload).await.is_err());
+        let pk = keypair.ref_to();
+        let blocking = tokio::task::spawn_blocking(move || {
+            remote.sign_blocking(&pk, &payload)
+        });
+        assert!(blocking.await.unwrap().is_err());
+    }
+}
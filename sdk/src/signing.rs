@@ -60,6 +60,8 @@ pub use crate::wallet::store::AddressVpType;
 use crate::wallet::{Wallet, WalletIo};
 use crate::{args, display_line, rpc, MaybeSend, Namada};
 
+pub mod description;
+
 #[cfg(feature = "std")]
 /// Env. var specifying where to store signing test vectors
 const ENV_VAR_LEDGER_LOG_PATH: &str = "NAMADA_LEDGER_LOG_PATH";
@@ -168,7 +170,14 @@ pub async fn tx_signers(
     match signer {
         Some(signer) if signer == MASP => Ok(vec![masp_tx_key().ref_to()]),
 
-        Some(signer) => Ok(vec![find_pk(context, &signer).await?]),
+        Some(signer) => match find_pk(context, &signer).await {
+            Ok(pk) => Ok(vec![pk]),
+            // The key may be held by a remote signer instead
+            Err(err) => crate::signer::find_remote_pk(args, &signer)
+                .await?
+                .map(|pk| vec![pk])
+                .ok_or(err),
+        },
         None => other_err(
             "All transactions must be signed; please either specify the key \
              or the address from which to look up the signing key."
@@ -550,6 +559,7 @@ pub async fn wrap_tx<N: Namada>(
                         &target,
                         &args.fee_token,
                         fee_amount,
+                        None,
                     )
                     .await
                 {
@@ -817,6 +827,42 @@ fn format_outputs(output: &mut Vec<String>) {
     }
 }
 
+/// Find the MASP builder of the shielded part of the given transfer, if any,
+/// along with the decoded MASP AssetTypes it uses
+fn find_masp_builder<'a>(
+    tx: &'a Tx,
+    transfer: &Transfer,
+) -> (
+    Option<&'a MaspBuilder>,
+    HashMap<AssetType, (Address, MaspDenom, Epoch)>,
+) {
+    // To facilitate lookups of MASP AssetTypes
+    let mut asset_types = HashMap::new();
+    let builder = if let Some(shielded_hash) = transfer.shielded {
+        tx.sections.iter().find_map(|x| match x {
+            Section::MaspBuilder(builder)
+                if builder.target == shielded_hash =>
+            {
+                for (addr, denom, epoch) in &builder.asset_types {
+                    match make_asset_type(Some(*epoch), addr, *denom) {
+                        Err(_) => None,
+                        Ok(asset) => {
+                            asset_types
+                                .insert(asset, (addr.clone(), *denom, *epoch));
+                            Some(builder)
+                        }
+                    }?;
+                }
+                Some(builder)
+            }
+            _ => None,
+        })
+    } else {
+        None
+    };
+    (builder, asset_types)
+}
+
 /// Adds a Ledger output for the sender and destination for transparent and MASP
 /// transactions
 pub async fn make_ledger_masp_endpoints(
@@ -1321,32 +1367,7 @@ pub async fn to_ledger_vector(
         .map_err(|err| {
             Error::from(EncodingError::Conversion(err.to_string()))
         })?;
-        // To facilitate lookups of MASP AssetTypes
-        let mut asset_types = HashMap::new();
-        let builder = if let Some(shielded_hash) = transfer.shielded {
-            tx.sections.iter().find_map(|x| match x {
-                Section::MaspBuilder(builder)
-                    if builder.target == shielded_hash =>
-                {
-                    for (addr, denom, epoch) in &builder.asset_types {
-                        match make_asset_type(Some(*epoch), addr, *denom) {
-                            Err(_) => None,
-                            Ok(asset) => {
-                                asset_types.insert(
-                                    asset,
-                                    (addr.clone(), *denom, *epoch),
-                                );
-                                Some(builder)
-                            }
-                        }?;
-                    }
-                    Some(builder)
-                }
-                _ => None,
-            })
-        } else {
-            None
-        };
+        let (builder, asset_types) = find_masp_builder(tx, &transfer);
 
         tv.name = "Transfer_0".to_string();
 
@@ -0,0 +1,859 @@
+//! Human-readable descriptions of transactions.
+//!
+//! A description decodes the data of a transaction according to the tag of its
+//! code section into named fields, with the addresses replaced by their wallet
+//! aliases when known and the amounts formatted with the denomination of their
+//! token. It is meant to be shown to a user before they sign a transaction.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use borsh::BorshDeserialize;
+use data_encoding::HEXLOWER;
+use masp_primitives::asset_type::AssetType;
+use masp_primitives::transaction::components::sapling::fees::{
+    InputView, OutputView,
+};
+use namada_core::types::address::{Address, MASP};
+use namada_core::types::eth_bridge_pool::{
+    GasFeeBump, PendingTransfer, TransferToEthereumKind,
+};
+use namada_core::types::key::common;
+use namada_core::types::masp::{ExtendedViewingKey, PaymentAddress};
+use namada_core::types::storage::Epoch;
+use namada_core::types::token::{Amount, MaspDenom, Transfer};
+use namada_core::types::transaction::account::{InitAccount, UpdateAccount};
+use namada_core::types::transaction::governance::{
+    InitProposalData, VoteProposalData,
+};
+use namada_core::types::transaction::pgf::UpdateStewardCommission;
+use namada_core::types::transaction::pos;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use super::{find_masp_builder, LedgerProposalType};
+use crate::error::{EncodingError, Error};
+use crate::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
+use crate::ibc::primitives::proto::Any;
+use crate::proto::{Section, Tx};
+use crate::tx::{
+    TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_BUMP_FEE_WASM,
+    TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
+    TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
+    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
+    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
+    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
+    TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
+    TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
+    VP_USER_WASM,
+};
+use crate::Namada;
+
+/// A named field of a transaction description
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxField {
+    /// The name of the field
+    pub name: String,
+    /// The human-readable value of the field
+    pub value: String,
+}
+
+/// A human-readable description of a transaction
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxDescription {
+    /// The kind of the transaction, e.g. "Transfer" or "Bond"
+    pub kind: String,
+    /// The hash of the code of the transaction
+    pub code_hash: String,
+    /// The fields decoded from the data of the transaction
+    pub fields: Vec<TxField>,
+    /// The fields of the wrapper of the transaction, if it is wrapped
+    pub wrapper: Vec<TxField>,
+}
+
+impl TxDescription {
+    /// Get the value of the first field with the given name
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|field| field.name == name)
+            .map(|field| field.value.as_str())
+    }
+}
+
+impl Display for TxDescription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Type : {}", self.kind)?;
+        for field in &self.fields {
+            writeln!(f, "  {} : {}", field.name, field.value)?;
+        }
+        if !self.wrapper.is_empty() {
+            writeln!(f, "Fee :")?;
+            for field in &self.wrapper {
+                writeln!(f, "  {} : {}", field.name, field.value)?;
+            }
+        }
+        write!(f, "Code hash : {}", self.code_hash)
+    }
+}
+
+/// Accumulates the fields of a description, formatting addresses and amounts
+struct Describer<'a, N: Namada> {
+    context: &'a N,
+    aliases: HashMap<Address, String>,
+    fields: Vec<TxField>,
+}
+
+impl<'a, N: Namada> Describer<'a, N> {
+    /// Add a field with the given name and value
+    fn push(&mut self, name: &str, value: impl Display) {
+        self.fields.push(TxField {
+            name: name.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    /// Add a field with the given name and value, if any
+    fn push_opt(&mut self, name: &str, value: Option<impl Display>) {
+        if let Some(value) = value {
+            self.push(name, value);
+        }
+    }
+
+    /// Format an address along with its wallet alias, if known
+    fn address(&self, address: &Address) -> String {
+        match self.aliases.get(address) {
+            Some(alias) => format!("{} ({})", alias, address),
+            None => address.to_string(),
+        }
+    }
+
+    /// Add a field holding an address
+    fn push_address(&mut self, name: &str, address: &Address) {
+        let value = self.address(address);
+        self.push(name, value);
+    }
+
+    /// Format an amount of the given token with its denomination, followed by
+    /// the alias of the token, if known
+    async fn amount(&self, token: &Address, amount: Amount) -> String {
+        let amount = self.context.format_amount(token, amount).await;
+        match self.aliases.get(token) {
+            Some(alias) => format!("{} {}", amount, alias),
+            None => format!("{} {}", amount, token),
+        }
+    }
+
+    /// Add a field holding an amount of the given token
+    async fn push_amount(
+        &mut self,
+        name: &str,
+        token: &Address,
+        amount: Amount,
+    ) {
+        let value = self.amount(token, amount).await;
+        self.push(name, value);
+    }
+
+    /// Add a field holding an amount of a MASP asset type, decoded with the
+    /// given asset types when possible
+    async fn push_asset_amount(
+        &mut self,
+        name: &str,
+        asset_type: &AssetType,
+        amount: u64,
+        assets: &HashMap<AssetType, (Address, MaspDenom, Epoch)>,
+    ) {
+        match assets.get(asset_type) {
+            Some((token, denom, _epoch)) => {
+                let amount = Amount::from_masp_denominated(amount, *denom);
+                self.push_amount(name, token, amount).await
+            }
+            None => self.push(name, format!("{} {}", amount, asset_type)),
+        }
+    }
+}
+
+/// Decode the data section of the transaction
+fn decode_data<T: BorshDeserialize>(tx: &Tx) -> Result<T, Error> {
+    T::try_from_slice(
+        &tx.data()
+            .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+    )
+    .map_err(|err| Error::from(EncodingError::Conversion(err.to_string())))
+}
+
+/// Describe the given transaction in human-readable fields
+pub async fn describe_tx<N: Namada>(
+    context: &N,
+    tx: &Tx,
+) -> Result<TxDescription, Error> {
+    // To facilitate lookups of human-readable addresses
+    let aliases: HashMap<Address, String> = context
+        .wallet()
+        .await
+        .get_addresses()
+        .into_iter()
+        .map(|(alias, addr)| (addr, alias))
+        .collect();
+    let mut desc = Describer {
+        context,
+        aliases,
+        fields: vec![],
+    };
+
+    let code_sec = tx
+        .get_section(tx.code_sechash())
+        .ok_or_else(|| {
+            Error::Other("expected tx code section to be present".to_string())
+        })?
+        .code_sec()
+        .ok_or_else(|| {
+            Error::Other("expected section to have code tag".to_string())
+        })?;
+    let code_hash = HEXLOWER.encode(&code_sec.code.hash().0);
+    let native_token = context.native_token();
+
+    let kind = match code_sec.tag.as_deref() {
+        Some(TX_INIT_ACCOUNT_WASM) => {
+            let init_account: InitAccount = decode_data(tx)?;
+            for pk in &init_account.public_keys {
+                desc.push("Public key", pk);
+            }
+            desc.push("Threshold", init_account.threshold);
+            desc.push("VP type", vp_code(tx, &init_account.vp_code_hash)?);
+            "Init Account"
+        }
+        Some(TX_UPDATE_ACCOUNT_WASM) => {
+            let update_account: UpdateAccount = decode_data(tx)?;
+            desc.push_address("Address", &update_account.addr);
+            for pk in &update_account.public_keys {
+                desc.push("Public key", pk);
+            }
+            desc.push_opt("Threshold", update_account.threshold);
+            if let Some(vp_code_hash) = &update_account.vp_code_hash {
+                desc.push("VP type", vp_code(tx, vp_code_hash)?);
+            }
+            "Update Account"
+        }
+        Some(TX_REVEAL_PK) => {
+            let public_key: common::PublicKey = decode_data(tx)?;
+            desc.push("Public key", public_key);
+            "Reveal Pubkey"
+        }
+        Some(TX_TRANSFER_WASM) => {
+            let transfer: Transfer = decode_data(tx)?;
+            describe_transfer(&mut desc, tx, &transfer).await;
+            "Transfer"
+        }
+        Some(TX_IBC_WASM) => {
+            let any_msg = Any::decode(
+                tx.data()
+                    .ok_or_else(|| Error::Other("Invalid Data".to_string()))?
+                    .as_ref(),
+            )
+            .map_err(|x| {
+                Error::from(EncodingError::Conversion(x.to_string()))
+            })?;
+            match MsgTransfer::try_from(any_msg.clone()) {
+                Ok(transfer) => {
+                    desc.push("Source port", &transfer.port_id_on_a);
+                    desc.push("Source channel", &transfer.chan_id_on_a);
+                    desc.push(
+                        "Token",
+                        format!(
+                            "{} {}",
+                            transfer.packet_data.token.amount,
+                            transfer.packet_data.token.denom
+                        ),
+                    );
+                    desc.push("Sender", &transfer.packet_data.sender);
+                    desc.push("Receiver", &transfer.packet_data.receiver);
+                    desc.push("Timeout height", transfer.timeout_height_on_b);
+                    desc.push(
+                        "Timeout timestamp",
+                        transfer
+                            .timeout_timestamp_on_b
+                            .into_tm_time()
+                            .map_or("(none)".to_string(), |time| {
+                                time.to_rfc3339()
+                            }),
+                    );
+                }
+                Err(_) => desc.push("Message type", &any_msg.type_url),
+            }
+            "IBC"
+        }
+        Some(TX_BRIDGE_POOL_WASM) => {
+            let transfer: PendingTransfer = decode_data(tx)?;
+            describe_bridge_pool_transfer(&mut desc, &transfer).await;
+            "Bridge Pool Transfer"
+        }
+        Some(TX_BRIDGE_POOL_BUMP_FEE_WASM) => {
+            let bump: GasFeeBump = decode_data(tx)?;
+            describe_bridge_pool_transfer(&mut desc, &bump.transfer).await;
+            desc.push_amount(
+                "New gas fee",
+                &bump.transfer.gas_fee.token,
+                bump.new_fee,
+            )
+            .await;
+            "Bridge Pool Fee Bump"
+        }
+        Some(TX_BOND_WASM) => {
+            let bond: pos::Bond = decode_data(tx)?;
+            if let Some(source) = &bond.source {
+                desc.push_address("Source", source);
+            }
+            desc.push_address("Validator", &bond.validator);
+            desc.push_amount("Amount", &native_token, bond.amount).await;
+            "Bond"
+        }
+        Some(TX_UNBOND_WASM) => {
+            let unbond: pos::Unbond = decode_data(tx)?;
+            if let Some(source) = &unbond.source {
+                desc.push_address("Source", source);
+            }
+            desc.push_address("Validator", &unbond.validator);
+            desc.push_amount("Amount", &native_token, unbond.amount)
+                .await;
+            "Unbond"
+        }
+        Some(TX_REDELEGATE_WASM) => {
+            let redelegation: pos::Redelegation = decode_data(tx)?;
+            desc.push_address("Owner", &redelegation.owner);
+            desc.push_address("Source validator", &redelegation.src_validator);
+            desc.push_address(
+                "Destination validator",
+                &redelegation.dest_validator,
+            );
+            desc.push_amount("Amount", &native_token, redelegation.amount)
+                .await;
+            "Redelegate"
+        }
+        Some(TX_WITHDRAW_WASM) => {
+            let withdraw: pos::Withdraw = decode_data(tx)?;
+            if let Some(source) = &withdraw.source {
+                desc.push_address("Source", source);
+            }
+            desc.push_address("Validator", &withdraw.validator);
+            "Withdraw"
+        }
+        Some(TX_CLAIM_REWARDS_WASM) => {
+            let claim: pos::Withdraw = decode_data(tx)?;
+            if let Some(source) = &claim.source {
+                desc.push_address("Source", source);
+            }
+            desc.push_address("Validator", &claim.validator);
+            "Claim Rewards"
+        }
+        Some(TX_BECOME_VALIDATOR_WASM) => {
+            let validator: pos::BecomeValidator = decode_data(tx)?;
+            desc.push_address("Address", &validator.address);
+            desc.push("Consensus key", &validator.consensus_key);
+            desc.push("Ethereum cold key", &validator.eth_cold_key);
+            desc.push("Ethereum hot key", &validator.eth_hot_key);
+            desc.push("Protocol key", &validator.protocol_key);
+            desc.push("Commission rate", validator.commission_rate);
+            desc.push(
+                "Maximum commission rate change",
+                validator.max_commission_rate_change,
+            );
+            desc.push("Email", &validator.email);
+            desc.push_opt("Description", validator.description.as_ref());
+            desc.push_opt("Website", validator.website.as_ref());
+            desc.push_opt("Discord handle", validator.discord_handle.as_ref());
+            "Become Validator"
+        }
+        Some(TX_CHANGE_COMMISSION_WASM) => {
+            let change: pos::CommissionChange = decode_data(tx)?;
+            desc.push_address("Validator", &change.validator);
+            desc.push("New rate", change.new_rate);
+            "Change Commission"
+        }
+        Some(TX_CHANGE_METADATA_WASM) => {
+            let change: pos::MetaDataChange = decode_data(tx)?;
+            desc.push_address("Validator", &change.validator);
+            desc.push_opt("Email", change.email.as_ref());
+            desc.push_opt("Description", change.description.as_ref());
+            desc.push_opt("Website", change.website.as_ref());
+            desc.push_opt("Discord handle", change.discord_handle.as_ref());
+            desc.push_opt("Commission rate", change.commission_rate);
+            "Change Validator Metadata"
+        }
+        Some(TX_CHANGE_CONSENSUS_KEY_WASM) => {
+            let change: pos::ConsensusKeyChange = decode_data(tx)?;
+            desc.push_address("Validator", &change.validator);
+            desc.push("Consensus key", &change.consensus_key);
+            "Change Consensus Key"
+        }
+        Some(TX_UNJAIL_VALIDATOR_WASM) => {
+            let validator: Address = decode_data(tx)?;
+            desc.push_address("Validator", &validator);
+            "Unjail Validator"
+        }
+        Some(TX_DEACTIVATE_VALIDATOR_WASM) => {
+            let validator: Address = decode_data(tx)?;
+            desc.push_address("Validator", &validator);
+            "Deactivate Validator"
+        }
+        Some(TX_REACTIVATE_VALIDATOR_WASM) => {
+            let validator: Address = decode_data(tx)?;
+            desc.push_address("Validator", &validator);
+            "Reactivate Validator"
+        }
+        Some(TX_INIT_PROPOSAL) => {
+            let proposal: InitProposalData = decode_data(tx)?;
+            desc.push_opt("ID", proposal.id);
+            desc.push(
+                "Proposal type",
+                LedgerProposalType(&proposal.r#type, tx),
+            );
+            desc.push_address("Author", &proposal.author);
+            desc.push("Voting start epoch", proposal.voting_start_epoch);
+            desc.push("Voting end epoch", proposal.voting_end_epoch);
+            desc.push("Grace epoch", proposal.grace_epoch);
+            desc.push("Content", HEXLOWER.encode(&proposal.content.0));
+            "Init Proposal"
+        }
+        Some(TX_VOTE_PROPOSAL) => {
+            let vote: VoteProposalData = decode_data(tx)?;
+            desc.push("ID", vote.id);
+            desc.push("Vote", &vote.vote);
+            desc.push_address("Voter", &vote.voter);
+            for delegation in &vote.delegations {
+                desc.push_address("Delegation", delegation);
+            }
+            "Vote Proposal"
+        }
+        Some(TX_RESIGN_STEWARD) => {
+            let steward: Address = decode_data(tx)?;
+            desc.push_address("Steward", &steward);
+            "Resign Steward"
+        }
+        Some(TX_UPDATE_STEWARD_COMMISSION) => {
+            let update: UpdateStewardCommission = decode_data(tx)?;
+            desc.push_address("Steward", &update.steward);
+            let mut commission =
+                update.commission.into_iter().collect::<Vec<_>>();
+            commission.sort();
+            for (target, rate) in commission {
+                let target = desc.address(&target);
+                desc.push("Commission", format!("{} to {}", rate, target));
+            }
+            "Update Steward Commission"
+        }
+        _ => "Custom",
+    };
+
+    let mut wrapper = vec![];
+    if let Some(header) = tx.header.wrapper() {
+        let token = &header.fee.token;
+        let fields = [
+            ("Fee payer", desc.address(&Address::from(&header.pk))),
+            ("Public key", header.pk.to_string()),
+            ("Fee token", desc.address(token)),
+            (
+                "Fee per gas unit",
+                desc.amount(token, header.fee.amount_per_gas_unit).await,
+            ),
+            ("Gas limit", u64::from(header.gas_limit).to_string()),
+            ("Epoch", header.epoch.to_string()),
+        ];
+        wrapper.extend(fields.into_iter().map(|(name, value)| TxField {
+            name: name.to_string(),
+            value,
+        }));
+    }
+
+    Ok(TxDescription {
+        kind: kind.to_string(),
+        code_hash,
+        fields: desc.fields,
+        wrapper,
+    })
+}
+
+/// Describe the validity predicate code with the given hash, attached to the
+/// transaction in an extra data section
+fn vp_code(
+    tx: &Tx,
+    vp_code_hash: &namada_core::types::hash::Hash,
+) -> Result<String, Error> {
+    let extra = tx
+        .get_section(vp_code_hash)
+        .and_then(|x| Section::extra_data_sec(x.as_ref()))
+        .ok_or_else(|| Error::Other("unable to load vp code".to_string()))?;
+    Ok(if extra.tag == Some(VP_USER_WASM.to_string()) {
+        "User".to_string()
+    } else {
+        HEXLOWER.encode(&extra.code.hash().0)
+    })
+}
+
+/// Describe the endpoints and amounts of a transparent or MASP transfer
+async fn describe_transfer<N: Namada>(
+    desc: &mut Describer<'_, N>,
+    tx: &Tx,
+    transfer: &Transfer,
+) {
+    let (builder, assets) = find_masp_builder(tx, transfer);
+    if transfer.source != MASP {
+        desc.push_address("Source", &transfer.source);
+    } else if let Some(builder) = builder {
+        for input in builder.builder.sapling_inputs() {
+            let vk = ExtendedViewingKey::from(*input.key());
+            desc.push("Source", vk);
+            desc.push_asset_amount(
+                "Sending",
+                &input.asset_type(),
+                input.value(),
+                &assets,
+            )
+            .await;
+        }
+    } else {
+        desc.push_address("Source", &transfer.source);
+    }
+    if transfer.target != MASP {
+        desc.push_address("Target", &transfer.target);
+    } else if let Some(builder) = builder {
+        for output in builder.builder.sapling_outputs() {
+            let pa = PaymentAddress::from(output.address());
+            desc.push("Target", pa);
+            desc.push_asset_amount(
+                "Receiving",
+                &output.asset_type(),
+                output.value(),
+                &assets,
+            )
+            .await;
+        }
+    } else {
+        desc.push_address("Target", &transfer.target);
+    }
+    let token = desc.address(&transfer.token);
+    desc.push("Token", token);
+    desc.push("Amount", transfer.amount);
+    desc.push_opt("Memo key", transfer.key.as_ref());
+    if let Some(shielded) = &transfer.shielded {
+        desc.push("Shielded section", HEXLOWER.encode(&shielded.0));
+    }
+}
+
+/// Describe a transfer from the Ethereum bridge pool
+async fn describe_bridge_pool_transfer<N: Namada>(
+    desc: &mut Describer<'_, N>,
+    transfer: &PendingTransfer,
+) {
+    let kind = match transfer.transfer.kind {
+        TransferToEthereumKind::Erc20 => "ERC20",
+        TransferToEthereumKind::Nut => "NUT",
+    };
+    desc.push("Transfer kind", kind);
+    desc.push_address("Sender", &transfer.transfer.sender);
+    desc.push("Recipient", transfer.transfer.recipient);
+    desc.push("Asset", transfer.transfer.asset);
+    desc.push_amount(
+        "Amount",
+        &transfer.token_address(),
+        transfer.transfer.amount,
+    )
+    .await;
+    desc.push_address("Gas payer", &transfer.gas_fee.payer);
+    desc.push_amount(
+        "Gas fee",
+        &transfer.gas_fee.token,
+        transfer.gas_fee.amount,
+    )
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use borsh::BorshSerialize;
+    use borsh_ext::BorshSerializeExt;
+    use futures::executor::block_on;
+    use masp_proofs::prover::LocalTxProver;
+    use namada_core::ledger::storage::types::encode;
+    use namada_core::proto::{Code, Data};
+    use namada_core::types::address::nam;
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada_core::types::eth_bridge_pool::{GasFee, TransferToEthereum};
+    use namada_core::types::ethereum_events::EthAddress;
+    use namada_core::types::storage::BlockHeight;
+    use namada_core::types::token::{self, DenominatedAmount, Denomination};
+    use namada_core::types::transaction::TxType;
+
+    use super::*;
+    use crate::ibc::apps::transfer::types::packet::PacketData;
+    use crate::ibc::apps::transfer::types::PrefixedCoin;
+    use crate::ibc::core::channel::types::timeout::TimeoutHeight;
+    use crate::ibc::core::host::types::identifiers::{ChannelId, PortId};
+    use crate::ibc::primitives::{Msg, Timestamp};
+    use crate::io::NullIo;
+    use crate::masp::{ShieldedContext, ShieldedUtils};
+    use crate::queries::testing::TestClient;
+    use crate::queries::{Client, EncodedResponseQuery, Rpc, RPC};
+    use crate::tendermint_rpc::error::Error as RpcError;
+    use crate::tendermint_rpc::SimpleRequest;
+    use crate::wallet::store::Store;
+    use crate::wallet::{LoadStoreError, Wallet, WalletIo, WalletStorage};
+    use crate::{MaybeSend, MaybeSync, NamadaImpl};
+
+    #[derive(Clone)]
+    struct TestWalletUtils;
+
+    impl WalletIo for TestWalletUtils {
+        type Rng = rand_core::OsRng;
+    }
+
+    impl WalletStorage for TestWalletUtils {
+        fn save<U>(&self, _wallet: &Wallet<U>) -> Result<(), LoadStoreError> {
+            Ok(())
+        }
+
+        fn load<U>(
+            &self,
+            _wallet: &mut Wallet<U>,
+        ) -> Result<(), LoadStoreError> {
+            Ok(())
+        }
+    }
+
+    #[derive(Default, Clone, BorshSerialize, BorshDeserialize)]
+    struct TestShieldedUtils;
+
+    #[cfg_attr(feature = "async-send", async_trait::async_trait)]
+    #[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+    impl ShieldedUtils for TestShieldedUtils {
+        fn local_tx_prover(&self) -> LocalTxProver {
+            unimplemented!("descriptions do not build MASP transactions")
+        }
+
+        async fn load<U: ShieldedUtils + MaybeSend>(
+            &self,
+            _ctx: &mut ShieldedContext<U>,
+        ) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        async fn save<U: ShieldedUtils + MaybeSync>(
+            &self,
+            _ctx: &ShieldedContext<U>,
+        ) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A test client that can be shared with the context, which requires its
+    /// client to be `Sync` unlike the mock DB of [`TestClient`]
+    struct SyncTestClient(Mutex<TestClient<Rpc>>);
+
+    #[cfg_attr(feature = "async-send", async_trait::async_trait)]
+    #[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+    impl Client for SyncTestClient {
+        type Error = std::io::Error;
+
+        async fn request(
+            &self,
+            path: String,
+            data: Option<Vec<u8>>,
+            height: Option<BlockHeight>,
+            prove: bool,
+        ) -> Result<EncodedResponseQuery, Self::Error> {
+            // The test client answers synchronously from its storage
+            let client = self.0.lock().unwrap();
+            block_on(client.request(path, data, height, prove))
+        }
+
+        async fn perform<R>(&self, request: R) -> Result<R::Output, RpcError>
+        where
+            R: SimpleRequest,
+        {
+            let client = self.0.lock().unwrap();
+            block_on(client.perform(request))
+        }
+    }
+
+    type TestContext =
+        NamadaImpl<SyncTestClient, TestWalletUtils, TestShieldedUtils, NullIo>;
+
+    /// A context whose storage holds the denominations of the given tokens
+    /// and whose wallet knows the native token and the given addresses
+    fn context(
+        denoms: &[(Address, u8)],
+        aliases: &[(&str, Address)],
+    ) -> TestContext {
+        let mut client = TestClient::new(RPC);
+        for (token, denom) in denoms {
+            client
+                .wl_storage
+                .storage
+                .write(&token::denom_key(token), encode(&Denomination(*denom)))
+                .expect("Test failed");
+        }
+        let mut wallet = Wallet::new(TestWalletUtils, Store::default());
+        wallet.insert_address("nam", nam(), false);
+        for (alias, address) in aliases {
+            wallet.insert_address(alias, address.clone(), false);
+        }
+        NamadaImpl::native_new(
+            SyncTestClient(Mutex::new(client)),
+            wallet,
+            ShieldedContext::default(),
+            NullIo,
+            nam(),
+        )
+    }
+
+    /// A transaction with the given code tag and serialized data
+    fn tx(tag: &str, data: Vec<u8>) -> Tx {
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.set_code(Code::new(vec![], Some(tag.to_string())));
+        tx.set_data(Data::new(data));
+        tx
+    }
+
+    /// Test that a transparent transfer is described with the aliases of its
+    /// endpoints and token.
+    #[tokio::test]
+    async fn test_describe_transfer() {
+        let source = established_address_1();
+        let target = established_address_2();
+        let context = context(
+            &[(nam(), 6)],
+            &[("albert", source.clone()), ("bertha", target.clone())],
+        );
+        let transfer = Transfer {
+            source: source.clone(),
+            target: target.clone(),
+            token: nam(),
+            amount: DenominatedAmount {
+                amount: token::Amount::from_u64(1_500_000),
+                denom: Denomination(6),
+            },
+            key: None,
+            shielded: None,
+        };
+        let tx = tx(TX_TRANSFER_WASM, transfer.serialize_to_vec());
+
+        let desc = describe_tx(&context, &tx).await.expect("Test failed");
+        assert_eq!(desc.kind, "Transfer");
+        assert_eq!(
+            desc.field("Source"),
+            Some(format!("albert ({})", source).as_str())
+        );
+        assert_eq!(
+            desc.field("Target"),
+            Some(format!("bertha ({})", target).as_str())
+        );
+        assert_eq!(
+            desc.field("Token"),
+            Some(format!("nam ({})", nam()).as_str())
+        );
+        assert_eq!(desc.field("Amount"), Some("1.5"));
+        assert_eq!(desc.field("Memo key"), None);
+        assert!(desc.wrapper.is_empty());
+    }
+
+    /// Test that a bond is described with its amount in the native token.
+    #[tokio::test]
+    async fn test_describe_bond() {
+        let validator = established_address_1();
+        let context =
+            context(&[(nam(), 6)], &[("validator", validator.clone())]);
+        let bond = pos::Bond {
+            validator: validator.clone(),
+            amount: token::Amount::native_whole(10),
+            source: None,
+        };
+        let tx = tx(TX_BOND_WASM, bond.serialize_to_vec());
+
+        let desc = describe_tx(&context, &tx).await.expect("Test failed");
+        assert_eq!(desc.kind, "Bond");
+        assert_eq!(desc.field("Source"), None);
+        assert_eq!(
+            desc.field("Validator"),
+            Some(format!("validator ({})", validator).as_str())
+        );
+        assert_eq!(desc.field("Amount"), Some("10 nam"));
+    }
+
+    /// Test that an IBC transfer is decoded from its protobuf message.
+    #[tokio::test]
+    async fn test_describe_ibc_transfer() {
+        let sender = established_address_1();
+        let context = context(&[(nam(), 6)], &[]);
+        let msg = MsgTransfer {
+            port_id_on_a: PortId::transfer(),
+            chan_id_on_a: ChannelId::new(0),
+            packet_data: PacketData {
+                token: PrefixedCoin {
+                    denom: "nam".parse().expect("Test failed"),
+                    amount: "100".parse().expect("Test failed"),
+                },
+                sender: sender.to_string().into(),
+                receiver: "cosmos1receiver".to_string().into(),
+                memo: String::new().into(),
+            },
+            timeout_height_on_b: TimeoutHeight::Never,
+            timeout_timestamp_on_b: Timestamp::none(),
+        };
+        let tx = tx(TX_IBC_WASM, msg.to_any().encode_to_vec());
+
+        let desc = describe_tx(&context, &tx).await.expect("Test failed");
+        assert_eq!(desc.kind, "IBC");
+        assert_eq!(desc.field("Source port"), Some("transfer"));
+        assert_eq!(desc.field("Source channel"), Some("channel-0"));
+        assert_eq!(desc.field("Token"), Some("100 nam"));
+        assert_eq!(desc.field("Sender"), Some(sender.to_string().as_str()));
+        assert_eq!(desc.field("Receiver"), Some("cosmos1receiver"));
+        assert_eq!(desc.field("Timeout timestamp"), Some("(none)"));
+    }
+
+    /// Test that a Bridge pool transfer is described with the denominations
+    /// of both the transferred asset and the gas fee.
+    #[tokio::test]
+    async fn test_describe_bridge_pool_transfer() {
+        let sender = established_address_1();
+        let transfer = PendingTransfer {
+            transfer: TransferToEthereum {
+                kind: TransferToEthereumKind::Erc20,
+                asset: EthAddress([1; 20]),
+                recipient: EthAddress([2; 20]),
+                sender: sender.clone(),
+                amount: token::Amount::from_u64(2_000_000_000_000_000_000),
+            },
+            gas_fee: GasFee {
+                amount: token::Amount::native_whole(1),
+                payer: sender.clone(),
+                token: nam(),
+            },
+        };
+        let erc20 = transfer.token_address();
+        let context = context(
+            &[(nam(), 6), (erc20.clone(), 18)],
+            &[("albert", sender.clone())],
+        );
+        let tx = tx(TX_BRIDGE_POOL_WASM, transfer.serialize_to_vec());
+
+        let desc = describe_tx(&context, &tx).await.expect("Test failed");
+        assert_eq!(desc.kind, "Bridge Pool Transfer");
+        assert_eq!(desc.field("Transfer kind"), Some("ERC20"));
+        assert_eq!(
+            desc.field("Sender"),
+            Some(format!("albert ({})", sender).as_str())
+        );
+        assert_eq!(
+            desc.field("Recipient"),
+            Some(EthAddress([2; 20]).to_string().as_str())
+        );
+        assert_eq!(desc.field("Amount"), Some(format!("2 {}", erc20).as_str()));
+        assert_eq!(desc.field("Gas fee"), Some("1 nam"));
+    }
+}
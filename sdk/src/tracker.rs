@@ -0,0 +1,704 @@
+//! Tracking of the status of a transaction submitted to the ledger.
+//!
+//! A transaction goes through two stages on chain: its wrapper is first
+//! accepted in a block, which pays the fee, and its inner transaction is then
+//! applied in a later block. The [`TxTracker`] follows these stages with the
+//! state machine of [`TxStatus`]:
+//!
+//! `Pending → Accepted → Applied / Rejected / Expired`
+//!
+//! The tracker reads the events of the transaction from the event log of the
+//! ledger node. If given a CometBFT subscription client, e.g. a websocket
+//! client, it waits for the notifications of the events of the transaction
+//! instead of polling the node.
+
+use std::collections::BTreeMap;
+use std::pin::Pin;
+
+use futures::{Stream, StreamExt};
+use namada_core::types::address::Address;
+use namada_core::types::storage::BlockHeight;
+use serde::Serialize;
+
+use crate::control_flow::time;
+use crate::error::{Error, EventError};
+use crate::events::Event;
+use crate::proto::Tx;
+use crate::rpc::{self, TxEventQuery};
+use crate::tendermint_rpc::client::SubscriptionClient;
+use crate::tendermint_rpc::query::Query;
+use crate::tendermint_rpc::Error as RpcError;
+
+/// The interval at which the event log is polled when the events of the
+/// transaction aren't notified
+const POLL_INTERVAL: time::Duration = time::Duration::from_secs(1);
+/// The interval at which the event log is polled when waiting for the
+/// notifications of the events of the transaction, in case one is missed
+const SUBSCRIBED_POLL_INTERVAL: time::Duration = time::Duration::from_secs(5);
+
+/// The notifications of a subscription to an event of a transaction. Only
+/// their arrival matters, as the event is then read from the event log.
+type Notifications = Pin<Box<dyn Stream<Item = Result<(), RpcError>> + Send>>;
+
+/// The stage of a transaction
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxStage {
+    /// The wrapper transaction, paying the fee
+    Wrapper,
+    /// The inner transaction
+    Inner,
+}
+
+/// A stage of a transaction that was included in a block
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TxOutcome {
+    /// The height of the block
+    pub height: BlockHeight,
+    /// The gas used by the stage
+    pub gas_used: u64,
+    /// The information about the result given by the ledger
+    pub info: String,
+    /// The accounts initialized by the inner transaction
+    pub initialized_accounts: Vec<Address>,
+}
+
+/// The rejection of a transaction by a validity predicate
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct VpRejection {
+    /// The address whose validity predicate rejected the transaction
+    pub address: Address,
+    /// The errors that occurred in the validity predicate, if any
+    pub errors: Vec<String>,
+}
+
+/// A stage of a transaction that was rejected in a block
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TxRejection {
+    /// The stage that was rejected
+    pub stage: TxStage,
+    /// The height of the block
+    pub height: BlockHeight,
+    /// The result code given by the ledger
+    pub code: u32,
+    /// The gas used by the stage
+    pub gas_used: u64,
+    /// The information about the result given by the ledger
+    pub info: String,
+    /// The rejections of the validity predicates, empty if the transaction
+    /// failed for another reason
+    pub vp_rejections: Vec<VpRejection>,
+}
+
+/// The status of a transaction submitted to the ledger
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TxStatus {
+    /// The wrapper isn't in a block yet
+    Pending,
+    /// The wrapper was accepted and the inner transaction is waiting to be
+    /// applied
+    Accepted(TxOutcome),
+    /// The inner transaction was applied
+    Applied(TxOutcome),
+    /// The wrapper or the inner transaction was rejected
+    Rejected(TxRejection),
+    /// The transaction didn't reach a final status before the deadline of
+    /// the tracker
+    Expired,
+}
+
+impl TxStatus {
+    /// Whether the status of the transaction can't change anymore
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Applied(_) | Self::Rejected(_) | Self::Expired)
+    }
+
+    /// The status following the given event of the given stage. The result
+    /// code of the event is required, as it tells whether the stage was
+    /// rejected. Its other missing or malformed attributes take default
+    /// values.
+    pub fn from_event(
+        stage: TxStage,
+        event: &Event,
+    ) -> Result<Self, EventError> {
+        let attribute = |key: &str| event.get(key).map(String::as_str);
+        let height = BlockHeight(
+            attribute("height")
+                .and_then(|height| height.parse().ok())
+                .unwrap_or_default(),
+        );
+        let code: u32 = attribute("code")
+            .ok_or_else(|| EventError::MissingKey("code".to_string()))?
+            .parse()
+            .map_err(|err: std::num::ParseIntError| {
+                EventError::InvalidValue("code".to_string(), err.to_string())
+            })?;
+        let gas_used = attribute("gas_used")
+            .and_then(|gas| gas.parse().ok())
+            .unwrap_or_default();
+        let info = attribute("info").unwrap_or_default().to_string();
+        if code != 0 {
+            return Ok(Self::Rejected(TxRejection {
+                stage,
+                height,
+                code,
+                gas_used,
+                info,
+                vp_rejections: vp_rejections(event),
+            }));
+        }
+        let outcome = TxOutcome {
+            height,
+            gas_used,
+            info,
+            initialized_accounts: attribute("initialized_accounts")
+                .and_then(|accounts| serde_json::from_str(accounts).ok())
+                .unwrap_or_default(),
+        };
+        Ok(match stage {
+            TxStage::Wrapper => Self::Accepted(outcome),
+            TxStage::Inner => Self::Applied(outcome),
+        })
+    }
+}
+
+/// The rejections of the validity predicates reported in the given event
+fn vp_rejections(event: &Event) -> Vec<VpRejection> {
+    let rejected_vps: Vec<Address> = event
+        .get("rejected_vps")
+        .and_then(|vps| serde_json::from_str(vps).ok())
+        .unwrap_or_default();
+    let vp_errors: Vec<(Address, String)> = event
+        .get("vp_errors")
+        .and_then(|errors| serde_json::from_str(errors).ok())
+        .unwrap_or_default();
+    let mut rejections: BTreeMap<Address, Vec<String>> = rejected_vps
+        .into_iter()
+        .map(|address| (address, vec![]))
+        .collect();
+    for (address, error) in vp_errors {
+        rejections.entry(address).or_default().push(error);
+    }
+    rejections
+        .into_iter()
+        .map(|(address, errors)| VpRejection { address, errors })
+        .collect()
+}
+
+/// Tracks the status of a submitted transaction until it's final
+pub struct TxTracker<'a, C> {
+    client: &'a C,
+    wrapper_hash: String,
+    inner_hash: String,
+    deadline: time::Instant,
+    status: TxStatus,
+    event: Option<Event>,
+    accepted: Option<Notifications>,
+    applied: Option<Notifications>,
+}
+
+impl<'a, C: crate::queries::Client + Sync> TxTracker<'a, C> {
+    /// Track the given wrapped transaction until the given deadline. The
+    /// events of the transaction are read from the given client.
+    pub fn new(client: &'a C, tx: &Tx, deadline: time::Instant) -> Self {
+        Self::from_hashes(
+            client,
+            tx.header_hash().to_string(),
+            tx.raw_header_hash().to_string(),
+            deadline,
+        )
+    }
+
+    /// Track the transaction with the given wrapper and inner transaction
+    /// hashes until the given deadline
+    pub fn from_hashes(
+        client: &'a C,
+        wrapper_hash: String,
+        inner_hash: String,
+        deadline: time::Instant,
+    ) -> Self {
+        Self {
+            client,
+            wrapper_hash,
+            inner_hash,
+            deadline,
+            status: TxStatus::Pending,
+            event: None,
+            accepted: None,
+            applied: None,
+        }
+    }
+
+    /// Subscribe to the events of the transaction with the given CometBFT
+    /// subscription client, e.g. a websocket client. This should be done
+    /// before broadcasting the transaction.
+    pub async fn subscribe<S: SubscriptionClient + Sync>(
+        &mut self,
+        subscriptions: &S,
+    ) -> Result<(), Error> {
+        let accepted = TxEventQuery::Accepted(&self.wrapper_hash);
+        self.accepted = Some(subscribe(subscriptions, accepted).await?);
+        let applied = TxEventQuery::Applied(&self.inner_hash);
+        self.applied = Some(subscribe(subscriptions, applied).await?);
+        Ok(())
+    }
+
+    /// The current status of the transaction
+    pub fn status(&self) -> &TxStatus {
+        &self.status
+    }
+
+    /// The deadline of the tracking, after which the transaction is expired
+    pub fn deadline(&self) -> time::Instant {
+        self.deadline
+    }
+
+    /// The last event of the transaction found on chain, if any
+    pub fn event(&self) -> Option<&Event> {
+        self.event.as_ref()
+    }
+
+    /// Wait for the status of the transaction to change, and return the new
+    /// status. A final status is returned as is. Errors if the event of the
+    /// transaction is malformed, leaving the status unchanged.
+    pub async fn next(&mut self) -> Result<&TxStatus, Error> {
+        let (stage, query, subscription) = match self.status {
+            TxStatus::Pending => (
+                TxStage::Wrapper,
+                TxEventQuery::Accepted(&self.wrapper_hash),
+                &mut self.accepted,
+            ),
+            TxStatus::Accepted(_) => (
+                TxStage::Inner,
+                TxEventQuery::Applied(&self.inner_hash),
+                &mut self.applied,
+            ),
+            _ => return Ok(&self.status),
+        };
+        self.status = match wait_for_event(
+            self.client,
+            query,
+            subscription,
+            self.deadline,
+        )
+        .await
+        {
+            Some(event) => {
+                let status = TxStatus::from_event(stage, &event)?;
+                self.event = Some(event);
+                status
+            }
+            None => TxStatus::Expired,
+        };
+        Ok(&self.status)
+    }
+
+    /// Track the transaction until its status is final, calling the given
+    /// function on every new status, and return the final status
+    pub async fn track(
+        mut self,
+        mut on_status: impl FnMut(&TxStatus),
+    ) -> Result<TxStatus, Error> {
+        while !self.status.is_final() {
+            on_status(self.next().await?);
+        }
+        Ok(self.status)
+    }
+}
+
+/// Subscribe to the notifications of the event of the given query
+async fn subscribe<S: SubscriptionClient + Sync>(
+    subscriptions: &S,
+    query: TxEventQuery<'_>,
+) -> Result<Notifications, Error> {
+    subscriptions
+        .subscribe(Query::from(query))
+        .await
+        .map(|subscription| -> Notifications {
+            Box::pin(subscription.map(|notification| notification.map(drop)))
+        })
+        .map_err(|err| {
+            Error::Other(format!(
+                "Failed to subscribe to the events of the transaction: {err}"
+            ))
+        })
+}
+
+/// Wait for the event of the given query until the deadline. The event log
+/// is queried whenever the subscription notifies of the event, and at
+/// regular intervals in case a notification is missed or the subscription
+/// fails.
+async fn wait_for_event<C: crate::queries::Client + Sync>(
+    client: &C,
+    query: TxEventQuery<'_>,
+    subscription: &mut Option<Notifications>,
+    deadline: time::Instant,
+) -> Option<Event> {
+    time::timeout_at(deadline, async {
+        loop {
+            match rpc::query_tx_events(client, query).await {
+                Ok(Some(event)) => {
+                    tracing::debug!(event = ?event, "Found tx event");
+                    break event;
+                }
+                Ok(None) => {}
+                Err(err) => tracing::debug!(
+                    query = ?query,
+                    %err,
+                    "ABCI query failed, retrying tx status query",
+                ),
+            }
+            let Some(notifications) = subscription.as_mut() else {
+                time::sleep(POLL_INTERVAL).await;
+                continue;
+            };
+            let poll_at = time::Instant::now() + SUBSCRIBED_POLL_INTERVAL;
+            match time::timeout_at(poll_at, notifications.next()).await {
+                Ok(Some(Ok(_))) | Err(_) => {}
+                Ok(Some(Err(err))) => {
+                    tracing::debug!(
+                        query = ?query,
+                        %err,
+                        "Tx event subscription failed, polling the tx status",
+                    );
+                    *subscription = None;
+                }
+                Ok(None) => *subscription = None,
+            }
+        }
+    })
+    .await
+    .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use futures::channel::mpsc;
+    use futures::executor::block_on;
+    use futures::SinkExt;
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada_core::types::hash::Hash;
+
+    use super::*;
+    use crate::events::{EventLevel, EventType};
+    use crate::queries::testing::TestClient;
+    use crate::queries::{Client, EncodedResponseQuery, Rpc, RPC};
+    use crate::tendermint_rpc::SimpleRequest;
+
+    /// A test client that can be shared with the tracker, which requires its
+    /// client to be `Sync` unlike the mock DB of [`TestClient`]
+    struct SyncTestClient(Mutex<TestClient<Rpc>>);
+
+    #[cfg_attr(feature = "async-send", async_trait::async_trait)]
+    #[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+    impl Client for SyncTestClient {
+        type Error = std::io::Error;
+
+        async fn request(
+            &self,
+            path: String,
+            data: Option<Vec<u8>>,
+            height: Option<BlockHeight>,
+            prove: bool,
+        ) -> Result<EncodedResponseQuery, Self::Error> {
+            // The test client answers synchronously from its event log
+            let client = self.0.lock().unwrap();
+            block_on(client.request(path, data, height, prove))
+        }
+
+        async fn perform<R>(&self, request: R) -> Result<R::Output, RpcError>
+        where
+            R: SimpleRequest,
+        {
+            let client = self.0.lock().unwrap();
+            block_on(client.perform(request))
+        }
+    }
+
+    impl SyncTestClient {
+        fn new() -> Self {
+            Self(Mutex::new(TestClient::new(RPC)))
+        }
+
+        fn log_event(&self, event: Event) {
+            self.0.lock().unwrap().event_log.log_events([event]);
+        }
+    }
+
+    fn event(event_type: EventType, attributes: &[(&str, String)]) -> Event {
+        Event {
+            event_type,
+            level: EventLevel::Tx,
+            attributes: attributes
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    /// Test the status following an accepted wrapper and an applied inner
+    /// transaction.
+    #[test]
+    fn test_status_from_successful_events() {
+        let accepted = event(
+            EventType::Accepted,
+            &[
+                ("height", "10".to_string()),
+                ("code", "0".to_string()),
+                ("gas_used", "120".to_string()),
+                ("info", "Check inputs passed.".to_string()),
+            ],
+        );
+        let status = TxStatus::from_event(TxStage::Wrapper, &accepted).unwrap();
+        assert_eq!(
+            status,
+            TxStatus::Accepted(TxOutcome {
+                height: BlockHeight(10),
+                gas_used: 120,
+                info: "Check inputs passed.".to_string(),
+                initialized_accounts: vec![],
+            })
+        );
+        assert!(!status.is_final());
+
+        let account = established_address_1();
+        let applied = event(
+            EventType::Applied,
+            &[
+                ("height", "11".to_string()),
+                ("code", "0".to_string()),
+                ("gas_used", "5000".to_string()),
+                ("info", "Transaction is valid.".to_string()),
+                (
+                    "initialized_accounts",
+                    serde_json::to_string(&[&account]).unwrap(),
+                ),
+            ],
+        );
+        let status = TxStatus::from_event(TxStage::Inner, &applied).unwrap();
+        assert_eq!(
+            status,
+            TxStatus::Applied(TxOutcome {
+                height: BlockHeight(11),
+                gas_used: 5000,
+                info: "Transaction is valid.".to_string(),
+                initialized_accounts: vec![account],
+            })
+        );
+        assert!(status.is_final());
+    }
+
+    /// Test that the rejections of the VPs are reported per address.
+    #[test]
+    fn test_status_from_rejected_event() {
+        let (vp_1, vp_2) = (established_address_1(), established_address_2());
+        let applied = event(
+            EventType::Applied,
+            &[
+                ("height", "11".to_string()),
+                ("code", "2".to_string()),
+                ("gas_used", "4000".to_string()),
+                ("info", "Transaction is invalid.".to_string()),
+                (
+                    "rejected_vps",
+                    serde_json::to_string(&[&vp_1, &vp_2]).unwrap(),
+                ),
+                (
+                    "vp_errors",
+                    serde_json::to_string(&[(&vp_2, "Out of gas")]).unwrap(),
+                ),
+            ],
+        );
+        let status = TxStatus::from_event(TxStage::Inner, &applied).unwrap();
+        let mut vp_rejections = vec![
+            VpRejection {
+                address: vp_1,
+                errors: vec![],
+            },
+            VpRejection {
+                address: vp_2,
+                errors: vec!["Out of gas".to_string()],
+            },
+        ];
+        vp_rejections.sort_by(|a, b| a.address.cmp(&b.address));
+        assert_eq!(
+            status,
+            TxStatus::Rejected(TxRejection {
+                stage: TxStage::Inner,
+                height: BlockHeight(11),
+                code: 2,
+                gas_used: 4000,
+                info: "Transaction is invalid.".to_string(),
+                vp_rejections,
+            })
+        );
+        assert!(status.is_final());
+    }
+
+    /// Test that an event without a result code or with a malformed one is
+    /// an error, as it can't tell whether the transaction was rejected.
+    #[test]
+    fn test_status_from_event_without_code() {
+        let accepted =
+            event(EventType::Accepted, &[("height", "10".to_string())]);
+        assert!(matches!(
+            TxStatus::from_event(TxStage::Wrapper, &accepted),
+            Err(EventError::MissingKey(key)) if key == "code"
+        ));
+
+        let accepted = event(
+            EventType::Accepted,
+            &[("height", "10".to_string()), ("code", "ok".to_string())],
+        );
+        assert!(matches!(
+            TxStatus::from_event(TxStage::Wrapper, &accepted),
+            Err(EventError::InvalidValue(key, _)) if key == "code"
+        ));
+    }
+
+    /// Test that polling finds the event of the transaction in the event log,
+    /// and that the wait expires at the deadline if there is none.
+    #[tokio::test]
+    async fn test_wait_for_event_polling() {
+        let hash = Hash::sha256(b"wrapper").to_string();
+        let client = SyncTestClient::new();
+        let deadline = time::Instant::now() + time::Duration::from_millis(10);
+        let found = wait_for_event(
+            &client,
+            TxEventQuery::Accepted(&hash),
+            &mut None,
+            deadline,
+        )
+        .await;
+        assert_eq!(found, None);
+
+        let accepted = event(
+            EventType::Accepted,
+            &[("hash", hash.clone()), ("code", "0".to_string())],
+        );
+        client.log_event(accepted.clone());
+        // The event of another stage of the transaction isn't matched
+        let deadline = time::Instant::now() + time::Duration::from_millis(10);
+        let found = wait_for_event(
+            &client,
+            TxEventQuery::Applied(&hash),
+            &mut None,
+            deadline,
+        )
+        .await;
+        assert_eq!(found, None);
+
+        let deadline = time::Instant::now() + time::Duration::from_secs(1);
+        let found = wait_for_event(
+            &client,
+            TxEventQuery::Accepted(&hash),
+            &mut None,
+            deadline,
+        )
+        .await;
+        assert_eq!(found, Some(accepted));
+    }
+
+    /// Test that a notification of the subscription wakes up the wait for
+    /// the event, before the event log would be polled again.
+    #[tokio::test]
+    async fn test_wait_for_event_notified() {
+        let hash = Hash::sha256(b"inner").to_string();
+        let client = SyncTestClient::new();
+        let (mut sender, receiver) = mpsc::channel(1);
+        let mut subscription: Option<Notifications> = Some(Box::pin(receiver));
+        let applied = event(
+            EventType::Applied,
+            &[("hash", hash.clone()), ("code", "0".to_string())],
+        );
+        // The deadline is before the event log is polled again
+        let deadline = time::Instant::now() + time::Duration::from_secs(2);
+        let wait = wait_for_event(
+            &client,
+            TxEventQuery::Applied(&hash),
+            &mut subscription,
+            deadline,
+        );
+        let notify = async {
+            client.log_event(applied.clone());
+            sender.send(Ok(())).await.unwrap();
+        };
+        let (found, ()) = futures::join!(wait, notify);
+        assert_eq!(found, Some(applied));
+        assert!(subscription.is_some());
+    }
+
+    /// Test that the wait falls back to polling the event log when the
+    /// subscription ends.
+    #[tokio::test]
+    async fn test_wait_for_event_subscription_closed() {
+        let hash = Hash::sha256(b"inner").to_string();
+        let client = SyncTestClient::new();
+        let (sender, receiver) = mpsc::channel(1);
+        drop(sender);
+        let mut subscription: Option<Notifications> = Some(Box::pin(receiver));
+        let deadline = time::Instant::now() + time::Duration::from_millis(10);
+        let found = wait_for_event(
+            &client,
+            TxEventQuery::Applied(&hash),
+            &mut subscription,
+            deadline,
+        )
+        .await;
+        assert_eq!(found, None);
+        assert!(subscription.is_none());
+    }
+
+    /// Test that the tracker follows the stages of the transaction until its
+    /// inner transaction is applied.
+    #[tokio::test]
+    async fn test_tracker() {
+        let wrapper_hash = Hash::sha256(b"wrapper").to_string();
+        let inner_hash = Hash::sha256(b"inner").to_string();
+        let client = SyncTestClient::new();
+        client.log_event(event(
+            EventType::Accepted,
+            &[
+                ("hash", wrapper_hash.clone()),
+                ("height", "10".to_string()),
+                ("code", "0".to_string()),
+            ],
+        ));
+        client.log_event(event(
+            EventType::Applied,
+            &[
+                ("hash", inner_hash.clone()),
+                ("height", "11".to_string()),
+                ("code", "0".to_string()),
+            ],
+        ));
+        let deadline = time::Instant::now() + time::Duration::from_secs(1);
+        let tracker =
+            TxTracker::from_hashes(&client, wrapper_hash, inner_hash, deadline);
+        let mut statuses = vec![];
+        let status = tracker
+            .track(|status| statuses.push(status.clone()))
+            .await
+            .unwrap();
+        let outcome = |height| TxOutcome {
+            height: BlockHeight(height),
+            gas_used: 0,
+            info: String::new(),
+            initialized_accounts: vec![],
+        };
+        assert_eq!(
+            statuses,
+            vec![
+                TxStatus::Accepted(outcome(10)),
+                TxStatus::Applied(outcome(11)),
+            ]
+        );
+        assert_eq!(status, TxStatus::Applied(outcome(11)));
+    }
+}
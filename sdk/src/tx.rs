@@ -1,11 +1,13 @@
 //! SDK functions to construct different types of transactions
 use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Display;
 use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use borsh::BorshSerialize;
+use borsh_ext::BorshSerializeExt;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::transaction::builder;
 use masp_primitives::transaction::builder::Builder;
@@ -23,6 +25,7 @@ use namada_core::ibc::core::channel::types::timeout::TimeoutHeight;
 use namada_core::ibc::core::client::types::Height as IbcHeight;
 use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
 use namada_core::ibc::primitives::{Msg, Timestamp as IbcTimestamp};
+use namada_core::ledger::gas::{GasMetering, TxGasMeter, VERIFY_TX_SIG_GAS};
 use namada_core::ledger::governance::cli::onchain::{
     DefaultProposal, OnChainProposal, PgfFundingProposal, PgfStewardProposal,
     ProposalVote,
@@ -30,6 +33,7 @@ use namada_core::ledger::governance::cli::onchain::{
 use namada_core::ledger::governance::storage::proposal::ProposalType;
 use namada_core::ledger::governance::storage::vote::StorageProposalVote;
 use namada_core::ledger::ibc::storage::channel_key;
+use namada_core::ledger::parameters::storage as parameter_storage;
 use namada_core::ledger::pgf::cli::steward::Commission;
 use namada_core::types::address::{Address, InternalAddress, MASP};
 use namada_core::types::dec::Dec;
@@ -39,13 +43,13 @@ use namada_core::types::key::*;
 use namada_core::types::masp::{TransferSource, TransferTarget};
 use namada_core::types::storage::Epoch;
 use namada_core::types::time::DateTimeUtc;
-use namada_core::types::token::MaspDenom;
+use namada_core::types::token::{DenominatedAmount, MaspDenom};
 use namada_core::types::transaction::account::{InitAccount, UpdateAccount};
 use namada_core::types::transaction::governance::{
     InitProposalData, VoteProposalData,
 };
 use namada_core::types::transaction::pgf::UpdateStewardCommission;
-use namada_core::types::transaction::pos;
+use namada_core::types::transaction::{pos, Fee, GasLimit, TxResult, TxType};
 use namada_core::types::{storage, token};
 use namada_proof_of_stake::parameters::PosParams;
 use namada_proof_of_stake::types::{CommissionPair, ValidatorState};
@@ -56,7 +60,7 @@ use crate::error::{EncodingError, Error, QueryError, Result, TxError};
 use crate::io::Io;
 use crate::masp::TransferErr::Build;
 use crate::masp::{make_asset_type, ShieldedContext, ShieldedTransfer};
-use crate::proto::{MaspBuilder, Tx};
+use crate::proto::{MaspBuilder, Section, Signature, Tx};
 use crate::queries::Client;
 use crate::rpc::{
     self, query_wasm_code_hash, validate_amount, TxBroadcastData, TxResponse,
@@ -64,6 +68,7 @@ use crate::rpc::{
 use crate::signing::{self, SigningTxData, TxSourcePostBalance};
 use crate::tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use crate::tendermint_rpc::error::Error as RpcError;
+use crate::tracker::{TxStage, TxStatus, TxTracker};
 use crate::wallet::WalletIo;
 use crate::{display_line, edisplay_line, Namada};
 
@@ -101,6 +106,8 @@ pub const TX_WITHDRAW_WASM: &str = "tx_withdraw.wasm";
 pub const TX_CLAIM_REWARDS_WASM: &str = "tx_claim_rewards.wasm";
 /// Bridge pool WASM path
 pub const TX_BRIDGE_POOL_WASM: &str = "tx_bridge_pool.wasm";
+/// Bridge pool fee bump WASM path
+pub const TX_BRIDGE_POOL_BUMP_FEE_WASM: &str = "tx_bridge_pool_bump_fee.wasm";
 /// Change commission WASM path
 pub const TX_CHANGE_COMMISSION_WASM: &str =
     "tx_change_validator_commission.wasm";
@@ -167,21 +174,315 @@ pub fn dump_tx<IO: Io>(io: &IO, args: &args::Tx, tx: Tx) {
     }
 }
 
+/// The safety margin added to the gas used by the dry run of a transaction to
+/// estimate its gas limit, in percent
+pub const GAS_ESTIMATE_MARGIN_PERCENT: u64 = 20;
+
+/// The estimated gas and fee of a transaction, obtained by dry running it
+#[derive(Clone, Debug)]
+pub struct FeeQuote {
+    /// The gas used by the dry run in a wrapper, plus the gas of verifying
+    /// the signatures and of unshielding the fee that it can't account for
+    pub gas_used: u64,
+    /// The gas used with the safety margin added
+    pub gas_limit: GasLimit,
+    /// The token in which the fee is paid
+    pub fee_token: Address,
+    /// The price of a unit of gas
+    pub gas_price: DenominatedAmount,
+    /// The maximum fee, which is paid if the whole gas limit is used
+    pub fee: DenominatedAmount,
+}
+
+impl Display for FeeQuote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "gas used {}, gas limit {}, gas price {} {}, fee up to {} {}",
+            self.gas_used,
+            u64::from(self.gas_limit),
+            self.gas_price,
+            self.fee_token,
+            self.fee,
+            self.fee_token,
+        )
+    }
+}
+
+/// Estimate the gas and fee of a built transaction by dry running it in a
+/// wrapper. The gas price is the minimum one of the fee token, unless a higher
+/// fee amount is given in the arguments.
+///
+/// The dry run can't use the signatures of the transaction, which it may not
+/// have yet, so it's signed with placeholder keys instead, yielding a
+/// transaction of the same size as the signed one. The validity predicates
+/// checking these signatures reject them at the first one, so the gas of
+/// verifying the signatures up to the threshold of the signer is added to the
+/// gas used by the dry run. Since the node doesn't charge gas for unshielding
+/// the fee, the unshielding transaction only adds the gas of its size in the
+/// wrapper, when the fee payer's transparent balance can't cover the fee.
+///
+/// Only a failure to apply the transaction is an error. The validity
+/// predicates rejecting the dry run for another reason than the placeholder
+/// signatures are reported with a warning, as the estimate may then be too
+/// low.
+pub async fn estimate_fee<N: Namada>(
+    context: &N,
+    args: &args::Tx,
+    tx: &Tx,
+    signing_data: &SigningTxData,
+) -> Result<FeeQuote> {
+    let epoch = rpc::query_epoch(context.client()).await?;
+    let max_block_gas: u64 = rpc::query_storage_value(
+        context.client(),
+        &parameter_storage::get_max_block_gas_key(),
+    )
+    .await?;
+    let dry_run_tx = fee_estimation_tx(
+        tx,
+        signing_data,
+        &args.fee_token,
+        epoch,
+        max_block_gas.into(),
+    );
+    let result =
+        rpc::simulate_tx(context.client(), dry_run_tx.to_bytes()).await?;
+    let rejected_vps = unexpected_rejected_vps(&result, signing_data);
+    if !rejected_vps.is_empty() {
+        edisplay_line!(
+            context.io(),
+            "Warning: the dry run of the transaction was rejected by the \
+             validity predicates of {}, the estimated gas may be too low.",
+            rejected_vps
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    let mut extra_gas_meter = TxGasMeter::new_from_sub_limit(u64::MAX.into());
+    if !signing_data.public_keys.is_empty() {
+        extra_gas_meter
+            .consume(VERIFY_TX_SIG_GAS * u64::from(signing_data.threshold))
+            .map_err(|err| Error::Other(err.to_string()))?;
+    }
+    let mut gas_used = total_gas_used(&result, &extra_gas_meter)?;
+    let mut gas_limit = gas_limit_with_margin(gas_used)?;
+
+    let gas_costs: BTreeMap<Address, token::Amount> = rpc::query_storage_value(
+        context.client(),
+        &parameter_storage::get_gas_cost_key(),
+    )
+    .await?;
+    let minimum_gas_price =
+        gas_costs.get(&args.fee_token).copied().ok_or_else(|| {
+            Error::Other(format!(
+                "Could not retrieve from storage the gas cost for token {}",
+                args.fee_token
+            ))
+        })?;
+    let gas_price = match args.fee_amount {
+        Some(amount) => {
+            let amount =
+                validate_amount(context, amount, &args.fee_token, args.force)
+                    .await?
+                    .amount;
+            std::cmp::max(amount, minimum_gas_price)
+        }
+        None => minimum_gas_price,
+    };
+
+    if let Some(spending_key) = &args.fee_unshield {
+        let fee_payer = Address::from(&signing_data.fee_payer);
+        let balance: token::Amount = rpc::query_storage_value(
+            context.client(),
+            &token::balance_key(&args.fee_token, &fee_payer),
+        )
+        .await
+        .unwrap_or_default();
+        let fee = gas_price * u64::from(gas_limit);
+        if balance < fee {
+            let target = TransferTarget::Address(fee_payer);
+            let amount = DenominatedAmount {
+                amount: fee,
+                denom: 0.into(),
+            };
+            match ShieldedContext::<N::ShieldedUtils>::gen_shielded_transfer(
+                context,
+                spending_key,
+                &target,
+                &args.fee_token,
+                amount,
+                None,
+            )
+            .await
+            {
+                Ok(Some(ShieldedTransfer { masp_tx, .. })) => {
+                    extra_gas_meter
+                        .add_tx_size_gas(
+                            &Section::MaspTx(masp_tx).serialize_to_vec(),
+                        )
+                        .map_err(|err| Error::Other(err.to_string()))?;
+                    gas_used = total_gas_used(&result, &extra_gas_meter)?;
+                    gas_limit = gas_limit_with_margin(gas_used)?;
+                }
+                Ok(None) if !args.force => {
+                    return Err(Error::from(TxError::FeeUnshieldingError(
+                        "Missing unshielding transaction".to_string(),
+                    )));
+                }
+                Err(err) if !args.force => {
+                    return Err(Error::from(TxError::FeeUnshieldingError(
+                        err.to_string(),
+                    )));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let fee = gas_price * u64::from(gas_limit);
+    Ok(FeeQuote {
+        gas_used,
+        gas_limit,
+        fee_token: args.fee_token.clone(),
+        gas_price: context.denominate_amount(&args.fee_token, gas_price).await,
+        fee: context.denominate_amount(&args.fee_token, fee).await,
+    })
+}
+
+/// Build the transaction dry run to estimate the fee of the given one. The
+/// inner transaction is signed with a placeholder key for each public key of
+/// the signer and wrapped with the given gas limit. The wrapper is signed by a
+/// placeholder fee payer and pays no fee, so that the dry run doesn't depend on
+/// the balance of the actual fee payer.
+fn fee_estimation_tx(
+    tx: &Tx,
+    signing_data: &SigningTxData,
+    fee_token: &Address,
+    epoch: Epoch,
+    gas_limit: GasLimit,
+) -> Tx {
+    let mut tx = tx.clone();
+    // The MASP builders hold private data that mustn't leave the client
+    tx.protocol_filter();
+    tx.sections
+        .retain(|section| !matches!(section, Section::Signature(_)));
+    tx.update_header(TxType::Raw);
+    if !signing_data.public_keys.is_empty() {
+        let secret_keys = (0..)
+            .zip(signing_data.public_keys.iter().map(placeholder_secret_key))
+            .collect();
+        tx.add_section(Section::Signature(Signature::new(
+            vec![tx.raw_header_hash()],
+            secret_keys,
+            signing_data.owner.clone(),
+        )));
+    }
+    let fee_payer = placeholder_secret_key(&signing_data.fee_payer);
+    tx.add_wrapper(
+        Fee {
+            amount_per_gas_unit: token::Amount::zero(),
+            token: fee_token.clone(),
+        },
+        fee_payer.ref_to(),
+        epoch,
+        gas_limit,
+        None,
+    );
+    tx.sign_wrapper(fee_payer);
+    tx
+}
+
+/// A fixed secret key of the scheme of the given public key, producing
+/// signatures of the same size as the ones of its secret key
+fn placeholder_secret_key(public_key: &common::PublicKey) -> common::SecretKey {
+    match public_key {
+        common::PublicKey::Ed25519(_) => {
+            common::SecretKey::Ed25519(ed25519::SigScheme::from_bytes([1; 32]))
+        }
+        common::PublicKey::Secp256k1(_) => common::SecretKey::Secp256k1(
+            secp256k1::SigScheme::from_bytes([1; 32]),
+        ),
+    }
+}
+
+/// The addresses whose validity predicates rejected a fee estimation dry run,
+/// other than the ones of the signer, which reject the placeholder signatures
+fn unexpected_rejected_vps<'a>(
+    result: &'a TxResult,
+    signing_data: &SigningTxData,
+) -> Vec<&'a Address> {
+    result
+        .vps_result
+        .rejected_vps
+        .iter()
+        .filter(|address| {
+            signing_data.owner.as_ref() != Some(*address)
+                && !signing_data
+                    .public_keys
+                    .iter()
+                    .any(|public_key| Address::from(public_key) == **address)
+        })
+        .collect()
+}
+
+/// The gas used by a dry run, plus the extra gas that it couldn't account for,
+/// in whole gas units
+fn total_gas_used(
+    result: &TxResult,
+    extra_gas_meter: &TxGasMeter,
+) -> Result<u64> {
+    result
+        .gas_used
+        .checked_add(extra_gas_meter.get_tx_consumed_gas())
+        .map(|gas| gas.get_whole_gas_units())
+        .ok_or_else(|| Error::Other("Overflow in gas".to_string()))
+}
+
+/// The gas limit covering the given gas used with the safety margin
+fn gas_limit_with_margin(gas_used: u64) -> Result<GasLimit> {
+    gas_used
+        .checked_mul(100 + GAS_ESTIMATE_MARGIN_PERCENT)
+        .map(|gas| (gas + 99) / 100)
+        .map(GasLimit::from)
+        .ok_or_else(|| Error::Other("Overflow in gas".to_string()))
+}
+
 /// Prepare a transaction for signing and submission by adding a wrapper header
-/// to it.
+/// to it. If requested, the gas limit of the wrapper is estimated by dry
+/// running the transaction first.
 #[allow(clippy::too_many_arguments)]
 pub async fn prepare_tx(
     context: &impl Namada,
     args: &args::Tx,
     tx: &mut Tx,
-    fee_payer: common::PublicKey,
+    signing_data: &SigningTxData,
     tx_source_balance: Option<TxSourcePostBalance>,
 ) -> Result<Option<Epoch>> {
+    let args = if args.estimate_gas {
+        let quote = estimate_fee(context, args, tx, signing_data).await?;
+        display_line!(context.io(), "Estimated fee: {}", quote);
+        Cow::Owned(args::Tx {
+            gas_limit: quote.gas_limit,
+            ..args.clone()
+        })
+    } else {
+        Cow::Borrowed(args)
+    };
     if !args.dry_run {
         let epoch = rpc::query_epoch(context.client()).await?;
 
-        signing::wrap_tx(context, tx, args, tx_source_balance, epoch, fee_payer)
-            .await
+        signing::wrap_tx(
+            context,
+            tx,
+            &args,
+            tx_source_balance,
+            epoch,
+            signing_data.fee_payer.clone(),
+        )
+        .await
     } else {
         Ok(None)
     }
@@ -278,7 +579,7 @@ pub async fn build_reveal_pk(
         args.tx_reveal_code_path.clone(),
         public_key,
         do_nothing,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await
@@ -365,54 +666,143 @@ pub async fn submit_tx(
         TxBroadcastData::DryRun(tx) => Err(TxError::ExpectLiveRun(tx.clone())),
     }?;
 
-    // Broadcast the supplied transaction
-    broadcast_tx(context, &to_broadcast).await?;
-
     let deadline = time::Instant::now()
         + time::Duration::from_secs(
             DEFAULT_NAMADA_EVENTS_MAX_WAIT_TIME_SECONDS,
         );
+    let tracker = TxTracker::from_hashes(
+        context.client(),
+        wrapper_hash.clone(),
+        decrypted_hash.clone(),
+        deadline,
+    );
+
+    #[cfg(feature = "websocket-client")]
+    if let Some(events_url) = context.events_url() {
+        return submit_subscribed_tx(
+            context,
+            &to_broadcast,
+            tracker,
+            events_url.clone(),
+        )
+        .await;
+    }
+    broadcast_and_track_tx(context, &to_broadcast, tracker).await
+}
 
+/// Submit the transaction like [`submit_tx`], waiting for the notifications
+/// of its events from a websocket client connected to the given URL. If the
+/// client fails, the events are polled.
+#[cfg(feature = "websocket-client")]
+async fn submit_subscribed_tx<C: crate::queries::Client + Sync>(
+    context: &impl Namada,
+    to_broadcast: &TxBroadcastData,
+    mut tracker: TxTracker<'_, C>,
+    events_url: crate::tendermint_rpc::WebSocketClientUrl,
+) -> Result<TxResponse> {
+    use futures::future::{self, Either};
+
+    use crate::tendermint_rpc::WebSocketClient;
+
+    let (client, driver) = match WebSocketClient::new(events_url).await {
+        Ok(client) => client,
+        Err(err) => {
+            tracing::debug!(
+                %err,
+                "Failed to connect the websocket client, polling the tx \
+                 status",
+            );
+            return broadcast_and_track_tx(context, to_broadcast, tracker)
+                .await;
+        }
+    };
+    // The subscriptions must be made before broadcasting, to not miss the
+    // events of the transaction
+    let submission = Box::pin(async {
+        if let Err(err) = tracker.subscribe(&client).await {
+            tracing::debug!(%err, "Polling the tx status");
+        }
+        broadcast_and_track_tx(context, to_broadcast, tracker).await
+    });
+    // The driver runs the websocket connection while the transaction is
+    // tracked. If it stops, the subscriptions end and the tracker falls back
+    // to polling.
+    let response = match future::select(submission, Box::pin(driver.run()))
+        .await
+    {
+        Either::Left((response, _)) => response,
+        Either::Right((result, submission)) => {
+            if let Err(err) = result {
+                tracing::debug!(%err, "The websocket client failed");
+            }
+            submission.await
+        }
+    };
+    response
+}
+
+/// Broadcast the transaction and follow it with the given tracker until its
+/// wrapper is rejected or its inner transaction is applied
+async fn broadcast_and_track_tx<C: crate::queries::Client + Sync>(
+    context: &impl Namada,
+    to_broadcast: &TxBroadcastData,
+    mut tracker: TxTracker<'_, C>,
+) -> Result<TxResponse> {
+    // Broadcast the supplied transaction
+    broadcast_tx(context, to_broadcast).await?;
+
+    let deadline = tracker.deadline();
     tracing::debug!(
         transaction = ?to_broadcast,
         ?deadline,
         "Awaiting transaction approval",
     );
 
-    let parsed = {
-        let wrapper_query = rpc::TxEventQuery::Accepted(wrapper_hash.as_str());
-        let event =
-            rpc::query_tx_status(context, wrapper_query, deadline).await?;
-        let parsed = TxResponse::from_event(event);
-        let tx_to_str = |parsed| {
-            serde_json::to_string_pretty(parsed).map_err(|err| {
-                Error::from(EncodingError::Serde(err.to_string()))
-            })
+    let tx_to_str = |parsed: &TxResponse| {
+        serde_json::to_string_pretty(parsed)
+            .map_err(|err| Error::from(EncodingError::Serde(err.to_string())))
+    };
+    // Follow the transaction until its wrapper is rejected or its encrypted
+    // payload is decrypted and applied
+    let parsed = loop {
+        let was_accepted = matches!(tracker.status(), TxStatus::Accepted(_));
+        let stage = match tracker.next().await? {
+            TxStatus::Pending => continue,
+            TxStatus::Accepted(_) => TxStage::Wrapper,
+            TxStatus::Applied(_) => TxStage::Inner,
+            TxStatus::Rejected(rejection) => rejection.stage,
+            TxStatus::Expired => {
+                edisplay_line!(
+                    context.io(),
+                    "Transaction status query deadline of {deadline:?} \
+                     exceeded"
+                );
+                return Err(Error::Tx(if was_accepted {
+                    TxError::AppliedTimeout
+                } else {
+                    TxError::AcceptTimeout
+                }));
+            }
         };
-        display_line!(
-            context.io(),
-            "Transaction accepted with result: {}",
-            tx_to_str(&parsed)?
-        );
-        // The transaction is now on chain. We wait for it to be decrypted
-        // and applied
-        if parsed.code == 0.to_string() {
-            // We also listen to the event emitted when the encrypted
-            // payload makes its way onto the blockchain
-            let decrypted_query =
-                rpc::TxEventQuery::Applied(decrypted_hash.as_str());
-            let event =
-                rpc::query_tx_status(context, decrypted_query, deadline)
-                    .await?;
-            let parsed = TxResponse::from_event(event);
-            display_line!(
+        let event = tracker
+            .event()
+            .cloned()
+            .expect("A non-pending status must come from an event");
+        let parsed = TxResponse::from_event(event);
+        match stage {
+            TxStage::Wrapper => display_line!(
+                context.io(),
+                "Transaction accepted with result: {}",
+                tx_to_str(&parsed)?
+            ),
+            TxStage::Inner => display_line!(
                 context.io(),
                 "Transaction applied with result: {}",
                 tx_to_str(&parsed)?
-            );
-            Ok(parsed)
-        } else {
-            Ok(parsed)
+            ),
+        }
+        if tracker.status().is_final() {
+            break parsed;
         }
     };
 
@@ -421,7 +811,7 @@ pub async fn submit_tx(
         "Transaction approved",
     );
 
-    parsed
+    Ok(parsed)
 }
 
 /// decode components of a masp note
@@ -607,7 +997,7 @@ pub async fn build_validator_commission_change(
         tx_code_path.clone(),
         data,
         do_nothing,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await
@@ -736,7 +1126,7 @@ pub async fn build_validator_metadata_change(
         tx_code_path.clone(),
         data,
         do_nothing,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await
@@ -795,7 +1185,7 @@ pub async fn build_update_steward_commission(
         tx_code_path.clone(),
         data,
         do_nothing,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await
@@ -835,7 +1225,7 @@ pub async fn build_resign_steward(
         tx_code_path.clone(),
         steward.clone(),
         do_nothing,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await
@@ -937,7 +1327,7 @@ pub async fn build_unjail_validator(
         tx_code_path.clone(),
         validator.clone(),
         do_nothing,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await
@@ -1008,7 +1398,7 @@ pub async fn build_deactivate_validator(
         tx_code_path.clone(),
         validator.clone(),
         do_nothing,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await
@@ -1078,7 +1468,7 @@ pub async fn build_reactivate_validator(
         tx_code_path.clone(),
         validator.clone(),
         do_nothing,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await
@@ -1258,7 +1648,7 @@ pub async fn build_redelegation(
         tx_code_path.clone(),
         data,
         do_nothing,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await
@@ -1341,7 +1731,7 @@ pub async fn build_withdraw(
         tx_code_path.clone(),
         data,
         do_nothing,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await
@@ -1389,7 +1779,7 @@ pub async fn build_claim_rewards(
         tx_code_path.clone(),
         data,
         do_nothing,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await
@@ -1496,7 +1886,7 @@ pub async fn build_unbond(
         tx_code_path.clone(),
         data,
         do_nothing,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await?;
@@ -1681,7 +2071,7 @@ pub async fn build_bond(
         tx_code_path.clone(),
         data,
         do_nothing,
-        &signing_data.fee_payer,
+        &signing_data,
         tx_source_balance,
     )
     .await
@@ -1734,7 +2124,7 @@ pub async fn build_default_proposal(
         tx_code_path.clone(),
         init_proposal_data,
         push_data,
-        &signing_data.fee_payer,
+        &signing_data,
         None, // TODO: need to pay the fee to submit a proposal
     )
     .await
@@ -1821,7 +2211,7 @@ pub async fn build_vote_proposal(
         tx_code_path.clone(),
         data,
         do_nothing,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await
@@ -1866,7 +2256,7 @@ pub async fn build_pgf_funding_proposal(
         tx_code_path.clone(),
         init_proposal_data,
         add_section,
-        &signing_data.fee_payer,
+        &signing_data,
         None, // TODO: need to pay the fee to submit a proposal
     )
     .await
@@ -1912,7 +2302,7 @@ pub async fn build_pgf_stewards_proposal(
         tx_code_path.clone(),
         init_proposal_data,
         add_section,
-        &signing_data.fee_payer,
+        &signing_data,
         None, // TODO: need to pay the fee to submit a proposal
     )
     .await
@@ -2039,7 +2429,7 @@ pub async fn build_ibc_transfer(
         context,
         &args.tx,
         &mut tx,
-        signing_data.fee_payer.clone(),
+        &signing_data,
         tx_source_balance,
     )
     .await?;
@@ -2055,7 +2445,7 @@ pub async fn build<F, D>(
     path: PathBuf,
     data: D,
     on_tx: F,
-    gas_payer: &common::PublicKey,
+    signing_data: &SigningTxData,
     tx_source_balance: Option<TxSourcePostBalance>,
 ) -> Result<(Tx, Option<Epoch>)>
 where
@@ -2068,7 +2458,7 @@ where
         path,
         data,
         on_tx,
-        gas_payer,
+        signing_data,
         tx_source_balance,
     )
     .await
@@ -2081,7 +2471,7 @@ async fn build_pow_flag<F, D>(
     path: PathBuf,
     mut data: D,
     on_tx: F,
-    gas_payer: &common::PublicKey,
+    signing_data: &SigningTxData,
     tx_source_balance: Option<TxSourcePostBalance>,
 ) -> Result<(Tx, Option<Epoch>)>
 where
@@ -2109,7 +2499,7 @@ where
         context,
         tx_args,
         &mut tx_builder,
-        gas_payer.clone(),
+        signing_data,
         tx_source_balance,
     )
     .await?;
@@ -2175,6 +2565,33 @@ async fn used_asset_types<P, R, K, N>(
 pub async fn build_transfer<N: Namada>(
     context: &N,
     args: &mut args::TxTransfer,
+) -> Result<(Tx, SigningTxData, Option<Epoch>)> {
+    if args.device_source.is_some() {
+        return Err(Error::Other(
+            "The spends of a hardware wallet must be authorized by the device \
+             before building the transfer"
+                .to_string(),
+        ));
+    }
+    build_transfer_aux(context, args, None).await
+}
+
+/// Submit a transfer from a shielded source whose spending key is held by a
+/// hardware wallet, embedding the given shielded parts that the device
+/// authorized. The amount of the transfer must have been validated.
+pub async fn build_external_shielded_transfer<N: Namada>(
+    context: &N,
+    args: &mut args::TxTransfer,
+    shielded_parts: ShieldedTransfer,
+) -> Result<(Tx, SigningTxData, Option<Epoch>)> {
+    build_transfer_aux(context, args, Some(shielded_parts)).await
+}
+
+/// Submit a transfer, generating its shielded parts unless they are given
+async fn build_transfer_aux<N: Namada>(
+    context: &N,
+    args: &mut args::TxTransfer,
+    shielded_parts: Option<ShieldedTransfer>,
 ) -> Result<(Tx, SigningTxData, Option<Epoch>)> {
     let default_signer = Some(args.source.effective_address());
     let signing_data = signing::aux_signing_data(
@@ -2234,16 +2651,29 @@ pub async fn build_transfer<N: Namada>(
         _ => None,
     };
 
+    // A memo can only be delivered inside of a shielded output
+    if args.memo.is_some() && args.target.payment_address().is_none() {
+        return Err(Error::Other(
+            "A memo can only be attached to a transfer to a payment address"
+                .to_string(),
+        ));
+    }
+
     // Construct the shielded part of the transaction, if any
-    let stx_result =
-        ShieldedContext::<N::ShieldedUtils>::gen_shielded_transfer(
-            context,
-            &args.source,
-            &args.target,
-            &args.token,
-            validated_amount,
-        )
-        .await;
+    let stx_result = match shielded_parts {
+        Some(shielded_parts) => Ok(Some(shielded_parts)),
+        None => {
+            ShieldedContext::<N::ShieldedUtils>::gen_shielded_transfer(
+                context,
+                &args.source,
+                &args.target,
+                &args.token,
+                validated_amount,
+                args.memo.clone(),
+            )
+            .await
+        }
+    };
 
     let shielded_parts = match stx_result {
         Ok(stx) => Ok(stx),
@@ -2316,7 +2746,7 @@ pub async fn build_transfer<N: Namada>(
         args.tx_code_path.clone(),
         transfer,
         add_shielded,
-        &signing_data.fee_payer,
+        &signing_data,
         tx_source_balance,
     )
     .await?;
@@ -2390,7 +2820,7 @@ pub async fn build_init_account(
         tx_code_path.clone(),
         data,
         add_code_hash,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await
@@ -2472,7 +2902,7 @@ pub async fn build_update_account(
         tx_code_path.clone(),
         data,
         add_code_hash,
-        &signing_data.fee_payer,
+        &signing_data,
         None,
     )
     .await
@@ -2518,14 +2948,8 @@ pub async fn build_custom(
         tx
     };
 
-    let epoch = prepare_tx(
-        context,
-        tx_args,
-        &mut tx,
-        signing_data.fee_payer.clone(),
-        None,
-    )
-    .await?;
+    let epoch =
+        prepare_tx(context, tx_args, &mut tx, &signing_data, None).await?;
 
     Ok((tx, signing_data, epoch))
 }
@@ -2569,6 +2993,7 @@ pub async fn gen_ibc_shielded_transfer<N: Namada>(
             &args.target,
             &token,
             validated_amount,
+            None,
         )
         .await
         .map_err(|err| TxError::MaspError(err.to_string()))?;
@@ -2836,3 +3261,146 @@ fn proposal_to_vec(proposal: OnChainProposal) -> Result<Vec<u8>> {
     borsh::to_vec(&proposal.content)
         .map_err(|e| Error::from(EncodingError::Conversion(e.to_string())))
 }
+
+#[cfg(test)]
+mod tests {
+    use namada_core::types::account::AccountPublicKeysMap;
+    use namada_core::types::address::testing::established_address_1;
+    use namada_core::types::address::{nam, InternalAddress};
+    use namada_core::types::chain::ChainId;
+    use namada_core::types::key::testing::{keypair_1, keypair_2};
+
+    use super::*;
+
+    fn signing_data(
+        secret_keys: &[common::SecretKey],
+        fee_payer: &common::SecretKey,
+    ) -> SigningTxData {
+        let public_keys: Vec<_> =
+            secret_keys.iter().map(|sk| sk.ref_to()).collect();
+        SigningTxData {
+            owner: Some(established_address_1()),
+            threshold: 2,
+            account_public_keys_map: Some(AccountPublicKeysMap::from_iter(
+                public_keys.clone(),
+            )),
+            public_keys,
+            fee_payer: fee_payer.ref_to(),
+        }
+    }
+
+    fn inner_tx() -> Tx {
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![0; 32], None).add_data(vec![1_u8; 64]);
+        tx
+    }
+
+    /// Test that the dry run of a fee estimation is a valid wrapper of the
+    /// same size as the transaction signed with the actual keys
+    #[test]
+    fn test_fee_estimation_tx() {
+        let secret_keys = vec![
+            keypair_1(),
+            common::SecretKey::Secp256k1(secp256k1::SigScheme::from_bytes(
+                [7; 32],
+            )),
+        ];
+        let fee_payer = keypair_2();
+        let signing_data = signing_data(&secret_keys, &fee_payer);
+        let epoch = Epoch(3);
+        let gas_limit = GasLimit::from(50_000);
+
+        let mut signed = inner_tx();
+        signed.sign_raw(
+            secret_keys,
+            signing_data.account_public_keys_map.clone().unwrap(),
+            signing_data.owner.clone(),
+        );
+        let signed_inner = signed.clone();
+        signed.add_wrapper(
+            Fee {
+                amount_per_gas_unit: token::Amount::native_whole(1),
+                token: nam(),
+            },
+            fee_payer.ref_to(),
+            epoch,
+            gas_limit,
+            None,
+        );
+        signed.sign_wrapper(fee_payer);
+
+        let dry_run_tx = fee_estimation_tx(
+            &inner_tx(),
+            &signing_data,
+            &nam(),
+            epoch,
+            gas_limit,
+        );
+        assert!(dry_run_tx.validate_tx().is_ok());
+        match dry_run_tx.header().tx_type {
+            TxType::Wrapper(wrapper) => {
+                assert!(wrapper.fee.amount_per_gas_unit.is_zero());
+                assert_eq!(wrapper.fee.token, nam());
+                assert_eq!(wrapper.gas_limit, gas_limit);
+                assert_eq!(wrapper.epoch, epoch);
+            }
+            tx_type => panic!("Expected a wrapper, got {:?}", tx_type),
+        }
+        assert_eq!(dry_run_tx.to_bytes().len(), signed.to_bytes().len());
+
+        // The signatures of an already signed transaction are replaced
+        let dry_run_tx = fee_estimation_tx(
+            &signed_inner,
+            &signing_data,
+            &nam(),
+            epoch,
+            gas_limit,
+        );
+        assert_eq!(dry_run_tx.to_bytes().len(), signed.to_bytes().len());
+    }
+
+    /// Test that the rejections of the placeholder signatures by the VPs of
+    /// the signer are not reported
+    #[test]
+    fn test_unexpected_rejected_vps() {
+        let signing_data = signing_data(&[keypair_1()], &keypair_2());
+        let implicit = Address::from(&keypair_1().ref_to());
+        let pos = Address::Internal(InternalAddress::PoS);
+        let mut result = TxResult::default();
+        result.vps_result.rejected_vps.extend([
+            established_address_1(),
+            implicit,
+            pos.clone(),
+        ]);
+        assert_eq!(unexpected_rejected_vps(&result, &signing_data), vec![&pos]);
+
+        result.vps_result.rejected_vps.remove(&pos);
+        assert!(unexpected_rejected_vps(&result, &signing_data).is_empty());
+    }
+
+    /// Test the safety margin added to the estimated gas
+    #[test]
+    fn test_gas_limit_with_margin() {
+        assert_eq!(u64::from(gas_limit_with_margin(0).unwrap()), 0);
+        assert_eq!(u64::from(gas_limit_with_margin(100).unwrap()), 120);
+        // The margin is rounded up
+        assert_eq!(u64::from(gas_limit_with_margin(101).unwrap()), 122);
+        assert!(gas_limit_with_margin(u64::MAX).is_err());
+    }
+
+    /// Test the total gas of a dry run with the gas it couldn't account for
+    #[test]
+    fn test_total_gas_used() {
+        let result = TxResult {
+            gas_used: 10_000_000.into(),
+            ..Default::default()
+        };
+        let mut extra_gas_meter =
+            TxGasMeter::new_from_sub_limit(u64::MAX.into());
+        assert_eq!(total_gas_used(&result, &extra_gas_meter).unwrap(), 1_000);
+
+        // Partial gas units are rounded up
+        extra_gas_meter.consume(VERIFY_TX_SIG_GAS * 2).unwrap();
+        assert_eq!(total_gas_used(&result, &extra_gas_meter).unwrap(), 1_002);
+    }
+}
@@ -0,0 +1,341 @@
+//! Address book of the wallet.
+//!
+//! The address book describes the counterparties of the user, and the
+//! accounts that the wallet watches without holding their keys. Its entries
+//! share the alias namespace of the rest of the store, so their aliases can
+//! be used wherever an address is expected.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use namada_core::types::address::Address;
+use namada_core::types::masp::{PaymentAddress, TransferTarget};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::alias::Alias;
+
+/// The version of the JSON format of exported address books
+pub const ADDRESS_BOOK_VERSION: u32 = 1;
+
+/// A counterparty of the user
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Contact {
+    /// The transparent address of the counterparty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// The shielded payment address of the counterparty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment_address: Option<PaymentAddress>,
+    /// A human-readable label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Tags to group entries by
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub tags: BTreeSet<String>,
+    /// The memo to attach to the transfers to the counterparty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo_template: Option<String>,
+}
+
+/// An account that the wallet tracks without holding its keys
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WatchOnlyAccount {
+    /// The address of the account
+    pub address: Address,
+    /// A human-readable label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Tags to group entries by
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub tags: BTreeSet<String>,
+}
+
+/// Who controls an address known to the wallet
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AddressOwnership {
+    /// The wallet holds the key of the address
+    Owned,
+    /// The address is watched without its key
+    WatchOnly,
+    /// The address belongs to a counterparty of the address book
+    Contact,
+    /// Nothing is known about the owner of the address
+    Unknown,
+}
+
+impl std::fmt::Display for AddressOwnership {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Owned => write!(f, "owned"),
+            Self::WatchOnly => write!(f, "watch-only"),
+            Self::Contact => write!(f, "contact"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Errors of address book import
+#[derive(Error, Debug)]
+pub enum AddressBookError {
+    /// Address book decoding error
+    #[error("Failed decoding the address book: {0}")]
+    Decode(serde_json::Error),
+    /// Address book of an unknown format version
+    #[error("Unsupported address book format version {0}")]
+    UnsupportedVersion(u32),
+}
+
+/// The contacts and watch-only accounts of a wallet
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct AddressBook {
+    /// Counterparties of the user
+    #[serde(default)]
+    contacts: BTreeMap<Alias, Contact>,
+    /// Accounts tracked without their keys
+    #[serde(default)]
+    watch_only: BTreeMap<Alias, WatchOnlyAccount>,
+}
+
+/// The JSON format of exported address books
+#[derive(Serialize, Deserialize)]
+struct VersionedAddressBook {
+    version: u32,
+    #[serde(flatten)]
+    address_book: AddressBook,
+}
+
+impl AddressBook {
+    /// Check if the address book has no entries
+    pub fn is_empty(&self) -> bool {
+        self.contacts.is_empty() && self.watch_only.is_empty()
+    }
+
+    /// Get all the contacts by their alias
+    pub fn contacts(&self) -> &BTreeMap<Alias, Contact> {
+        &self.contacts
+    }
+
+    /// Get all the watch-only accounts by their alias
+    pub fn watch_only_accounts(&self) -> &BTreeMap<Alias, WatchOnlyAccount> {
+        &self.watch_only
+    }
+
+    /// Check if an entry has the given alias
+    pub fn contains_alias(&self, alias: &Alias) -> bool {
+        self.contacts.contains_key(alias) || self.watch_only.contains_key(alias)
+    }
+
+    /// Find the transparent address of the entry with the given alias
+    pub fn find_address(&self, alias: &Alias) -> Option<&Address> {
+        self.watch_only
+            .get(alias)
+            .map(|account| &account.address)
+            .or_else(|| self.contacts.get(alias)?.address.as_ref())
+    }
+
+    /// Find the payment address of the contact with the given alias
+    pub fn find_payment_addr(&self, alias: &Alias) -> Option<&PaymentAddress> {
+        self.contacts.get(alias)?.payment_address.as_ref()
+    }
+
+    /// Find the memo template of the contact with the given alias for a
+    /// transfer to the given target. Only transfers to the payment address
+    /// of the contact get a memo, as transparent transfers can't carry one.
+    pub fn find_memo_template(
+        &self,
+        alias: &Alias,
+        target: &TransferTarget,
+    ) -> Option<&str> {
+        let contact = self.contacts.get(alias)?;
+        match target {
+            TransferTarget::PaymentAddress(payment_address)
+                if contact.payment_address.as_ref()
+                    == Some(payment_address) =>
+            {
+                contact.memo_template.as_deref()
+            }
+            _ => None,
+        }
+    }
+
+    /// Find the alias of the entry with the given transparent address
+    pub fn find_alias(&self, address: &Address) -> Option<&Alias> {
+        self.watch_only
+            .iter()
+            .find(|(_, account)| &account.address == address)
+            .or_else(|| {
+                self.contacts.iter().find(|(_, contact)| {
+                    contact.address.as_ref() == Some(address)
+                })
+            })
+            .map(|(alias, _)| alias)
+    }
+
+    /// Insert a contact with the given alias, replacing any entry with it
+    pub fn insert_contact(&mut self, alias: Alias, contact: Contact) {
+        self.watch_only.remove(&alias);
+        self.contacts.insert(alias, contact);
+    }
+
+    /// Insert a watch-only account with the given alias, replacing any entry
+    /// with it
+    pub fn insert_watch_only(
+        &mut self,
+        alias: Alias,
+        account: WatchOnlyAccount,
+    ) {
+        self.contacts.remove(&alias);
+        self.watch_only.insert(alias, account);
+    }
+
+    /// Remove the entry with the given alias. Returns whether there was one.
+    pub fn remove(&mut self, alias: &Alias) -> bool {
+        self.contacts.remove(alias).is_some()
+            || self.watch_only.remove(alias).is_some()
+    }
+
+    /// Add all the entries of another address book, replacing the ones with
+    /// the same aliases
+    pub fn extend(&mut self, other: AddressBook) {
+        for (alias, contact) in other.contacts {
+            self.insert_contact(alias, contact);
+        }
+        for (alias, account) in other.watch_only {
+            self.insert_watch_only(alias, account);
+        }
+    }
+
+    /// Encode the address book into its versioned JSON format
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&VersionedAddressBook {
+            version: ADDRESS_BOOK_VERSION,
+            address_book: self.clone(),
+        })
+        .expect("Serializing of address book shouldn't fail")
+    }
+
+    /// Decode an address book from its versioned JSON format
+    pub fn from_json(json: &str) -> Result<Self, AddressBookError> {
+        let versioned: VersionedAddressBook =
+            serde_json::from_str(json).map_err(AddressBookError::Decode)?;
+        if versioned.version != ADDRESS_BOOK_VERSION {
+            return Err(AddressBookError::UnsupportedVersion(
+                versioned.version,
+            ));
+        }
+        Ok(versioned.address_book)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+
+    use super::*;
+
+    fn address_book() -> AddressBook {
+        let mut address_book = AddressBook::default();
+        address_book.insert_contact(
+            "alice".into(),
+            Contact {
+                address: Some(established_address_1()),
+                label: Some("Alice".to_string()),
+                tags: ["friends".to_string()].into(),
+                memo_template: Some("Invoice".to_string()),
+                ..Default::default()
+            },
+        );
+        address_book.insert_watch_only(
+            "treasury".into(),
+            WatchOnlyAccount {
+                address: established_address_2(),
+                label: None,
+                tags: BTreeSet::new(),
+            },
+        );
+        address_book
+    }
+
+    /// Test the lookups of addresses and aliases in the address book.
+    #[test]
+    fn test_address_book_lookups() {
+        let mut address_book = address_book();
+        assert_eq!(
+            address_book.find_address(&"alice".into()),
+            Some(&established_address_1())
+        );
+        assert_eq!(
+            address_book.find_alias(&established_address_2()),
+            Some(&"treasury".into())
+        );
+        assert!(address_book.find_payment_addr(&"alice".into()).is_none());
+
+        // An entry is replaced by another one with the same alias
+        address_book.insert_contact("treasury".into(), Contact::default());
+        assert!(address_book.watch_only_accounts().is_empty());
+        assert!(address_book.find_alias(&established_address_2()).is_none());
+
+        assert!(address_book.remove(&"alice".into()));
+        assert!(!address_book.remove(&"alice".into()));
+    }
+
+    /// Test that the memo template of a contact is only used for transfers
+    /// to its payment address.
+    #[test]
+    fn test_memo_template() {
+        let mut address_book = address_book();
+        let payment_address = PaymentAddress::from(
+            masp_primitives::zip32::ExtendedSpendingKey::master(&[7; 32])
+                .default_address()
+                .1,
+        );
+        address_book.insert_contact(
+            "bob".into(),
+            Contact {
+                payment_address: Some(payment_address),
+                memo_template: Some("Rent".to_string()),
+                ..Default::default()
+            },
+        );
+
+        // A contact with a transparent address
+        let transparent = TransferTarget::Address(established_address_1());
+        assert!(address_book
+            .find_memo_template(&"alice".into(), &transparent)
+            .is_none());
+        // A contact with a payment address
+        let shielded = TransferTarget::PaymentAddress(payment_address);
+        assert_eq!(
+            address_book.find_memo_template(&"bob".into(), &shielded),
+            Some("Rent")
+        );
+        assert!(address_book
+            .find_memo_template(&"alice".into(), &shielded)
+            .is_none());
+        assert!(address_book
+            .find_memo_template(&"carol".into(), &shielded)
+            .is_none());
+    }
+
+    /// Test the JSON roundtrip of an address book and the rejection of
+    /// unknown versions.
+    #[test]
+    fn test_address_book_json() {
+        let address_book = address_book();
+        let json = address_book.to_json();
+        let decoded = AddressBook::from_json(&json).unwrap();
+        assert_eq!(decoded.contacts(), address_book.contacts());
+        assert_eq!(
+            decoded.watch_only_accounts(),
+            address_book.watch_only_accounts()
+        );
+
+        let json = r#"{"version": 2, "contacts": {}}"#;
+        assert!(matches!(
+            AddressBook::from_json(json),
+            Err(AddressBookError::UnsupportedVersion(2))
+        ));
+    }
+}
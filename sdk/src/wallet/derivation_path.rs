@@ -13,6 +13,7 @@ use tiny_hderive::Error as HDeriveError;
 
 const ETH_COIN_TYPE: u32 = 60;
 const NAMADA_COIN_TYPE: u32 = 877;
+const ZIP32_PURPOSE: u32 = 32;
 
 #[derive(Error, Debug)]
 pub enum DerivationPathError {
@@ -81,10 +82,24 @@ impl DerivationPath {
     }
 
     pub fn default_for_scheme(scheme: SchemeType) -> Self {
-        let path = Self::bip44(scheme, 0, 0, 0);
+        Self::bip44_account(scheme, 0)
+    }
+
+    /// The BIP-44 path of the first key of the given account
+    pub fn bip44_account(scheme: SchemeType, account: u32) -> Self {
+        let path = Self::bip44(scheme, account, 0, 0);
         path.hardened(scheme)
     }
 
+    /// The ZIP-32 path of the spending key of the given shielded account
+    pub fn zip32_account(account: u32) -> Self {
+        Self::new(vec![
+            ChildIndex::Hardened(ZIP32_PURPOSE),
+            ChildIndex::Hardened(NAMADA_COIN_TYPE),
+            ChildIndex::Hardened(account),
+        ])
+    }
+
     pub fn from_path_str(
         scheme: SchemeType,
         path: &str,
@@ -180,4 +195,20 @@ mod tests {
         assert!(!path_nam.is_compatible(SchemeType::Secp256k1));
         assert!(path_nam.is_compatible(SchemeType::Common));
     }
+
+    #[test]
+    fn account_paths() {
+        assert_eq!(
+            DerivationPath::bip44_account(SchemeType::Ed25519, 3).to_string(),
+            "m/44'/877'/3'/0'/0'"
+        );
+        assert_eq!(
+            DerivationPath::bip44_account(SchemeType::Secp256k1, 3).to_string(),
+            "m/44'/60'/3'/0/0"
+        );
+        assert_eq!(
+            DerivationPath::zip32_account(3).to_string(),
+            "m/32'/877'/3'"
+        );
+    }
 }
@@ -0,0 +1,192 @@
+//! Wallet stores encrypted as a whole with a master password.
+//!
+//! An encrypted wallet file is a small TOML envelope holding the sealed TOML
+//! encoding of a [`Store`]. Unlike the encryption of individual keypairs, it
+//! also hides aliases, addresses, viewing keys and payment addresses, and the
+//! wallet is unlocked with a single password.
+//!
+//! Wallet files are versioned by the `version` field of the envelope. The
+//! plaintext [`Store`] files, which have no such field, are version `0`.
+
+use data_encoding::HEXLOWER;
+use orion::{aead, kdf};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use super::keys::{encryption_key, encryption_salt};
+use super::{LoadStoreError, Store};
+
+/// The version of the plaintext wallet store format
+pub const PLAINTEXT_STORE_VERSION: u32 = 0;
+
+/// The version of the current encrypted wallet store format
+pub const ENCRYPTED_STORE_VERSION: u32 = 1;
+
+/// The part of a wallet file common to all its format versions
+#[derive(Deserialize)]
+struct StoreHeader {
+    #[serde(default)]
+    version: u32,
+}
+
+/// A wallet store sealed with a key derived from a master password
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EncryptedStore {
+    /// The format version of the encrypted store
+    version: u32,
+    /// The hex encoded salt of the key derivation
+    salt: String,
+    /// The hex encoded sealed store
+    ciphertext: String,
+}
+
+impl EncryptedStore {
+    /// Seal the given store with a fresh key derived from the password.
+    pub fn seal(store: &Store, password: &Zeroizing<String>) -> Self {
+        let salt = encryption_salt();
+        let encryption_key = encryption_key(&salt, password);
+        let data = Zeroizing::new(store.encode());
+        let ciphertext = aead::seal(&encryption_key, &data)
+            .expect("Encryption of data shouldn't fail");
+        Self {
+            version: ENCRYPTED_STORE_VERSION,
+            salt: HEXLOWER.encode(salt.as_ref()),
+            ciphertext: HEXLOWER.encode(&ciphertext),
+        }
+    }
+
+    /// Open the sealed store with the given password.
+    pub fn open(
+        &self,
+        password: &Zeroizing<String>,
+    ) -> Result<Store, LoadStoreError> {
+        let raw_salt = HEXLOWER
+            .decode(self.salt.as_bytes())
+            .map_err(|_| LoadStoreError::Decrypt)?;
+        let salt = kdf::Salt::from_slice(&raw_salt)
+            .map_err(|_| LoadStoreError::Decrypt)?;
+        let ciphertext = HEXLOWER
+            .decode(self.ciphertext.as_bytes())
+            .map_err(|_| LoadStoreError::Decrypt)?;
+        let encryption_key = encryption_key(&salt, password);
+        let data = Zeroizing::new(
+            aead::open(&encryption_key, &ciphertext)
+                .map_err(|_| LoadStoreError::Decrypt)?,
+        );
+        Store::decode(data.to_vec()).map_err(LoadStoreError::Decode)
+    }
+
+    /// Decode an encrypted store from a string of bytes
+    pub fn decode(data: &[u8]) -> Result<Self, toml::de::Error> {
+        toml::from_slice(data)
+    }
+
+    /// Encode an encrypted store into a string of bytes
+    pub fn encode(&self) -> Vec<u8> {
+        toml::to_vec(self).expect("Serializing of store shouldn't fail")
+    }
+}
+
+/// Get the format version of the given wallet file.
+pub fn store_version(data: &[u8]) -> Result<u32, LoadStoreError> {
+    toml::from_slice::<StoreHeader>(data)
+        .map(|header| header.version)
+        .map_err(LoadStoreError::Decode)
+}
+
+/// Decode a wallet file of any supported format version into the current
+/// [`Store`]. The password is only requested if the file is encrypted, in
+/// which case it is returned alongside the store to encrypt it again on save.
+pub fn decode_store(
+    data: Vec<u8>,
+    password: impl FnOnce() -> Zeroizing<String>,
+) -> Result<(Store, Option<Zeroizing<String>>), LoadStoreError> {
+    match store_version(&data)? {
+        PLAINTEXT_STORE_VERSION => {
+            Ok((Store::decode(data).map_err(LoadStoreError::Decode)?, None))
+        }
+        ENCRYPTED_STORE_VERSION => {
+            let encrypted = EncryptedStore::decode(&data)
+                .map_err(LoadStoreError::Decode)?;
+            let password = password();
+            let store = encrypted.open(&password)?;
+            Ok((store, Some(password)))
+        }
+        version => Err(LoadStoreError::UnsupportedVersion(version)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::types::address::testing::established_address_1;
+    use rand_core::OsRng;
+
+    use super::*;
+    use crate::wallet::alias::Alias;
+    use crate::wallet::WalletIo;
+
+    #[derive(Clone)]
+    struct TestWalletIo;
+
+    impl WalletIo for TestWalletIo {
+        type Rng = OsRng;
+    }
+
+    fn password(password: &str) -> Zeroizing<String> {
+        Zeroizing::new(password.to_string())
+    }
+
+    fn store() -> Store {
+        let mut store = Store::default();
+        store.insert_address::<TestWalletIo>(
+            Alias::from("my-account"),
+            established_address_1(),
+            false,
+        );
+        store
+    }
+
+    /// Test that an encrypted store hides its contents and opens only with
+    /// the right password.
+    #[test]
+    fn test_encrypted_store_roundtrip() {
+        let store = store();
+        let data = EncryptedStore::seal(&store, &password("pwd")).encode();
+        assert!(
+            !String::from_utf8_lossy(&data).contains("my-account"),
+            "The aliases must be encrypted"
+        );
+        assert_eq!(store_version(&data).unwrap(), ENCRYPTED_STORE_VERSION);
+
+        let (decoded, decoded_password) =
+            decode_store(data.clone(), || password("pwd")).unwrap();
+        assert_eq!(decoded.encode(), store.encode());
+        assert_eq!(decoded_password, Some(password("pwd")));
+
+        assert!(matches!(
+            decode_store(data, || password("wrong")),
+            Err(LoadStoreError::Decrypt)
+        ));
+    }
+
+    /// Test that plaintext stores are decoded without a password and that
+    /// unknown versions are rejected.
+    #[test]
+    fn test_store_versions() {
+        let store = store();
+        let data = store.encode();
+        assert_eq!(store_version(&data).unwrap(), PLAINTEXT_STORE_VERSION);
+        let (decoded, decoded_password) = decode_store(data, || {
+            panic!("A plaintext store must not need a password")
+        })
+        .unwrap();
+        assert_eq!(decoded.encode(), store.encode());
+        assert!(decoded_password.is_none());
+
+        let data = b"version = 2\n".to_vec();
+        assert!(matches!(
+            decode_store(data, || password("pwd")),
+            Err(LoadStoreError::UnsupportedVersion(2))
+        ));
+    }
+}
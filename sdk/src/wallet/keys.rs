@@ -194,6 +194,29 @@ where
             StoredKeypair::Raw(_) => false,
         }
     }
+
+    /// Encrypt a raw keypair with the given password. Encrypted keypairs are
+    /// left as they are.
+    pub fn encrypt(&mut self, password: &Zeroizing<String>) {
+        if let StoredKeypair::Raw(keypair) = self {
+            *self = Self::Encrypted(EncryptedKeypair::new(
+                keypair,
+                password.clone(),
+            ));
+        }
+    }
+
+    /// Decrypt an encrypted keypair, if it was encrypted with the given
+    /// password. Returns whether the keypair is now raw.
+    pub fn decrypt(&mut self, password: &Zeroizing<String>) -> bool {
+        if let StoredKeypair::Encrypted(encrypted_keypair) = self {
+            match encrypted_keypair.decrypt(password.clone()) {
+                Ok(keypair) => *self = Self::Raw(keypair),
+                Err(_) => return false,
+            }
+        }
+        true
+    }
 }
 
 impl<T: BorshSerialize + BorshDeserialize> EncryptedKeypair<T> {
@@ -234,12 +257,15 @@ impl<T: BorshSerialize + BorshDeserialize> EncryptedKeypair<T> {
 }
 
 /// Keypair encryption salt
-fn encryption_salt() -> kdf::Salt {
+pub(super) fn encryption_salt() -> kdf::Salt {
     kdf::Salt::default()
 }
 
 /// Make encryption secret key from a password.
-fn encryption_key(salt: &kdf::Salt, password: &str) -> kdf::SecretKey {
+pub(super) fn encryption_key(
+    salt: &kdf::Salt,
+    password: &str,
+) -> kdf::SecretKey {
     kdf::Password::from_slice(password.as_bytes())
         .and_then(|password| kdf::derive_key(&password, salt, 3, 1 << 17, 32))
         .expect("Generation of encryption secret key shouldn't fail")
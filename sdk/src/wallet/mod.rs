@@ -1,6 +1,8 @@
 //! Provides functionality for managing keys and addresses for a user
+pub mod address_book;
 pub mod alias;
 mod derivation_path;
+pub mod encrypted_store;
 mod keys;
 pub mod pre_genesis;
 pub mod store;
@@ -24,10 +26,13 @@ pub use store::{AddressVpType, Store};
 use thiserror::Error;
 use zeroize::Zeroizing;
 
+pub use self::address_book::{
+    AddressBook, AddressBookError, AddressOwnership, Contact, WatchOnlyAccount,
+};
 pub use self::derivation_path::{DerivationPath, DerivationPathError};
 pub use self::keys::{DecryptionError, StoredKeypair};
 pub use self::store::{ConfirmationResponse, ValidatorData, ValidatorKeys};
-use crate::wallet::store::derive_hd_secret_key;
+use crate::wallet::store::{derive_hd_secret_key, derive_hd_spending_key};
 
 /// Errors of key generation / recovery
 #[derive(Error, Debug)]
@@ -112,6 +117,12 @@ pub enum LoadStoreError {
     /// Wallet store writing error
     #[error("Failed to write the wallet store: {0}")]
     StoreNewWallet(String),
+    /// Encrypted wallet store decryption error
+    #[error("Failed decrypting the wallet store. Is the password correct?")]
+    Decrypt,
+    /// Wallet store of an unknown format version
+    #[error("Unsupported wallet store format version {0}")]
+    UnsupportedVersion(u32),
 }
 
 /// Captures the permanent storage parts of the wallet's functioning
@@ -133,6 +144,7 @@ pub mod fs {
     use fd_lock::RwLock;
     use rand_core::OsRng;
 
+    use super::encrypted_store::{decode_store, EncryptedStore};
     use super::*;
 
     /// A trait for deriving WalletStorage for standard filesystems
@@ -144,9 +156,14 @@ pub mod fs {
     /// Wallet file name
     const FILE_NAME: &str = "wallet.toml";
 
-    impl<F: FsWalletStorage> WalletStorage for F {
+    impl<F: FsWalletStorage + WalletIo> WalletStorage for F {
         fn save<U>(&self, wallet: &Wallet<U>) -> Result<(), LoadStoreError> {
-            let data = wallet.store.encode();
+            let data = match &wallet.master_password {
+                Some(password) => {
+                    EncryptedStore::seal(&wallet.store, password).encode()
+                }
+                None => wallet.store.encode(),
+            };
             let wallet_path = self.store_dir().join(FILE_NAME);
             // Make sure the dir exists
             let wallet_dir = wallet_path.parent().unwrap();
@@ -195,8 +212,14 @@ pub mod fs {
                     err.to_string(),
                 )
             })?;
-            wallet.store =
-                Store::decode(store).map_err(LoadStoreError::Decode)?;
+            // An encrypted store is unlocked with the password it was last
+            // loaded with, if any, to only prompt for it once
+            let cached_password = wallet.master_password.take();
+            let (store, password) = decode_store(store, || {
+                cached_password.unwrap_or_else(|| F::read_password(false))
+            })?;
+            wallet.store = store;
+            wallet.master_password = password;
             Ok(())
         }
     }
@@ -269,6 +292,8 @@ pub struct Wallet<U> {
     store: Store,
     decrypted_key_cache: HashMap<Alias, common::SecretKey>,
     decrypted_spendkey_cache: HashMap<Alias, ExtendedSpendingKey>,
+    /// The password of the whole store, if it is saved encrypted
+    master_password: Option<Zeroizing<String>>,
 }
 
 impl<U> From<Wallet<U>> for Store {
@@ -285,6 +310,38 @@ impl<U> Wallet<U> {
             store,
             decrypted_key_cache: HashMap::default(),
             decrypted_spendkey_cache: HashMap::default(),
+            master_password: None,
+        }
+    }
+
+    /// Check if the store is saved encrypted with a master password
+    pub fn is_store_encrypted(&self) -> bool {
+        self.master_password.is_some()
+    }
+
+    /// Set the master password that unlocks the store on load and encrypts it
+    /// on save. Without one, the store is saved in plaintext.
+    pub fn set_master_password(&mut self, password: Option<Zeroizing<String>>) {
+        self.master_password = password;
+    }
+
+    /// Encrypt the whole store with the given master password from the next
+    /// save on, or change its master password if it is already encrypted.
+    /// The keys that are encrypted on their own with the same password are
+    /// decrypted, as the store encryption protects them. Returns the aliases
+    /// of the keys that remain encrypted with another password.
+    pub fn encrypt_store(&mut self, password: Zeroizing<String>) -> Vec<Alias> {
+        let encrypted_keys = self.store.decrypt_keypairs(&password);
+        self.master_password = Some(password);
+        encrypted_keys
+    }
+
+    /// Save the store in plaintext from the next save on. The raw keys are
+    /// then encrypted on their own with the former master password, so that
+    /// they are never written in the clear.
+    pub fn decrypt_store(&mut self) {
+        if let Some(password) = self.master_password.take() {
+            self.store.encrypt_keypairs(&password);
         }
     }
 
@@ -389,6 +446,22 @@ impl<U> Wallet<U> {
         self.store.find_alias(address)
     }
 
+    /// Get the contacts and watch-only accounts of the wallet
+    pub fn get_address_book(&self) -> &AddressBook {
+        self.store.get_address_book()
+    }
+
+    /// Tell who controls the given address
+    pub fn address_ownership(&self, address: &Address) -> AddressOwnership {
+        self.store.address_ownership(address)
+    }
+
+    /// Remove the contact or watch-only account with the given alias.
+    /// Returns whether there was one.
+    pub fn remove_book_entry(&mut self, alias: impl AsRef<str>) -> bool {
+        self.store.remove_book_entry(&alias.into())
+    }
+
     /// Try to find an alias for a given address from the wallet. If not found,
     /// formats the address into a string.
     pub fn lookup_alias(&self, addr: &Address) -> String {
@@ -640,6 +713,25 @@ impl<U: WalletIo> Wallet<U> {
         .map(|alias| (alias, sk))
     }
 
+    /// Derive a spending key from the given seed and ZIP-32 path, and insert
+    /// it into the store with the provided alias, converted to lower case. If
+    /// the alias already exists, optionally force overwrite the key for the
+    /// alias. Returns the alias of the key and the key itself, or `None` if
+    /// the insertion was cancelled.
+    pub fn derive_store_hd_spending_key(
+        &mut self,
+        alias: String,
+        alias_force: bool,
+        seed: &Seed,
+        derivation_path: DerivationPath,
+        password: Option<Zeroizing<String>>,
+    ) -> Option<(String, ExtendedSpendingKey)> {
+        let spend_key =
+            derive_hd_spending_key(seed.as_bytes(), derivation_path);
+        self.insert_spending_key(alias, spend_key, password, alias_force)
+            .map(|alias| (alias, spend_key))
+    }
+
     /// Generate a disposable signing key for fee payment and store it under the
     /// precomputed alias in the wallet. This is simply a wrapper around
     /// `gen_key` to manage the alias
@@ -945,6 +1037,55 @@ impl<U: WalletIo> Wallet<U> {
             .map(Into::into)
     }
 
+    /// Insert a contact into the wallet under the given alias
+    pub fn insert_contact(
+        &mut self,
+        alias: String,
+        contact: Contact,
+        force_alias: bool,
+    ) -> Option<String> {
+        self.store
+            .insert_contact::<U>(alias.into(), contact, force_alias)
+            .map(Into::into)
+    }
+
+    /// Insert a watch-only account into the wallet under the given alias
+    pub fn insert_watch_only(
+        &mut self,
+        alias: String,
+        account: WatchOnlyAccount,
+        force_alias: bool,
+    ) -> Option<String> {
+        self.store
+            .insert_watch_only::<U>(alias.into(), account, force_alias)
+            .map(Into::into)
+    }
+
+    /// Insert all the entries of an address book into the wallet. Returns
+    /// the aliases of the inserted entries.
+    pub fn import_address_book(
+        &mut self,
+        address_book: AddressBook,
+        force_alias: bool,
+    ) -> Vec<String> {
+        let mut aliases = vec![];
+        for (alias, contact) in address_book.contacts().clone() {
+            aliases.extend(self.insert_contact(
+                alias.into(),
+                contact,
+                force_alias,
+            ));
+        }
+        for (alias, account) in address_book.watch_only_accounts().clone() {
+            aliases.extend(self.insert_watch_only(
+                alias.into(),
+                account,
+                force_alias,
+            ));
+        }
+        aliases
+    }
+
     /// Insert a payment address into the wallet under the given alias
     pub fn insert_payment_addr(
         &mut self,
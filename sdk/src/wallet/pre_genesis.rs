@@ -23,6 +23,9 @@ pub enum ReadError {
     /// Failed to decode a key
     #[error("Failed to decode a key: {0}")]
     Decryption(wallet::keys::DecryptionError),
+    /// The protocol key is missing
+    #[error("The validator pre-genesis wallet has no protocol key")]
+    MissingProtocolKey,
 }
 
 /// Validator pre-genesis wallet includes all the required keys for genesis
@@ -36,6 +39,8 @@ pub struct ValidatorWallet {
     pub eth_cold_key: common::SecretKey,
     /// Cryptographic keypair for eth hot key
     pub eth_hot_key: common::SecretKey,
+    /// Cryptographic keypair for protocol key
+    pub protocol_key: common::SecretKey,
     /// Cryptographic keypair for Tendermint node key
     pub tendermint_node_key: common::SecretKey,
 }
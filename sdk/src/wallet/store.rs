@@ -16,6 +16,9 @@ use serde::{Deserialize, Serialize};
 use slip10_ed25519;
 use zeroize::Zeroizing;
 
+use super::address_book::{
+    AddressBook, AddressOwnership, Contact, WatchOnlyAccount,
+};
 use super::alias::{self, Alias};
 use super::derivation_path::DerivationPath;
 use super::pre_genesis;
@@ -34,16 +37,18 @@ pub enum ConfirmationResponse {
 /// Special keys for a validator
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ValidatorKeys {
-    /// Special keypair for signing protocol txs
-    pub protocol_keypair: common::SecretKey,
+    /// Special keypair for signing protocol txs. It is absent when the
+    /// protocol key is held by a signing daemon instead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol_keypair: Option<common::SecretKey>,
     /// Special hot keypair for signing Ethereum bridge txs
     pub eth_bridge_keypair: common::SecretKey,
 }
 
 impl ValidatorKeys {
-    /// Get the protocol keypair
-    pub fn get_protocol_keypair(&self) -> &common::SecretKey {
-        &self.protocol_keypair
+    /// Get the protocol keypair, if it is held by the wallet
+    pub fn get_protocol_keypair(&self) -> Option<&common::SecretKey> {
+        self.protocol_keypair.as_ref()
     }
 }
 
@@ -80,6 +85,9 @@ pub struct Store {
     pub(crate) validator_data: Option<ValidatorData>,
     /// Namada address vp type
     address_vp_types: BTreeMap<AddressVpType, HashSet<Address>>,
+    /// Contacts and watch-only accounts
+    #[serde(default, skip_serializing_if = "AddressBook::is_empty")]
+    address_book: AddressBook,
 }
 
 /// Grouping of addresses by validity predicate.
@@ -148,7 +156,10 @@ impl Store {
         &self,
         alias: impl AsRef<str>,
     ) -> Option<&PaymentAddress> {
-        self.payment_addrs.get(&alias.into())
+        let alias = alias.into();
+        self.payment_addrs
+            .get(&alias)
+            .or_else(|| self.address_book.find_payment_addr(&alias))
     }
 
     /// Find the stored key by a public key.
@@ -192,12 +203,45 @@ impl Store {
 
     /// Find the stored address by an alias.
     pub fn find_address(&self, alias: impl AsRef<str>) -> Option<&Address> {
-        self.addresses.get_by_left(&alias.into())
+        let alias = alias.into();
+        self.addresses
+            .get_by_left(&alias)
+            .or_else(|| self.address_book.find_address(&alias))
     }
 
     /// Find an alias by the address if it's in the wallet.
     pub fn find_alias(&self, address: &Address) -> Option<&Alias> {
-        self.addresses.get_by_right(address)
+        self.addresses
+            .get_by_right(address)
+            .or_else(|| self.address_book.find_alias(address))
+    }
+
+    /// Get the contacts and watch-only accounts.
+    pub fn get_address_book(&self) -> &AddressBook {
+        &self.address_book
+    }
+
+    /// Tell who controls the given address.
+    pub fn address_ownership(&self, address: &Address) -> AddressOwnership {
+        let watched = self
+            .address_book
+            .watch_only_accounts()
+            .values()
+            .any(|account| &account.address == address);
+        if watched {
+            return AddressOwnership::WatchOnly;
+        }
+        match address {
+            Address::Implicit(ImplicitAddress(pkh))
+                if self.find_key_by_pkh(pkh).is_some() =>
+            {
+                AddressOwnership::Owned
+            }
+            _ if self.address_book.find_alias(address).is_some() => {
+                AddressOwnership::Contact
+            }
+            _ => AddressOwnership::Unknown,
+        }
     }
 
     /// Get all known keys by their alias, paired with PKH, if known.
@@ -555,6 +599,75 @@ impl Store {
         Some(alias)
     }
 
+    /// Insert a contact with the given alias. If the alias is already used,
+    /// will prompt for overwrite/reselection confirmation, which when
+    /// declined, the contact won't be added. Return the selected alias if the
+    /// contact has been added.
+    pub fn insert_contact<U: WalletIo>(
+        &mut self,
+        alias: Alias,
+        contact: Contact,
+        force: bool,
+    ) -> Option<Alias> {
+        let alias = self.select_book_alias::<U>(alias, "a contact", force)?;
+        self.remove_alias(&alias);
+        self.address_book.insert_contact(alias.clone(), contact);
+        Some(alias)
+    }
+
+    /// Insert a watch-only account with the given alias. If the alias is
+    /// already used, will prompt for overwrite/reselection confirmation, which
+    /// when declined, the account won't be added. Return the selected alias if
+    /// the account has been added.
+    pub fn insert_watch_only<U: WalletIo>(
+        &mut self,
+        alias: Alias,
+        account: WatchOnlyAccount,
+        force: bool,
+    ) -> Option<Alias> {
+        let alias =
+            self.select_book_alias::<U>(alias, "a watch-only account", force)?;
+        self.remove_alias(&alias);
+        self.address_book.insert_watch_only(alias.clone(), account);
+        Some(alias)
+    }
+
+    /// Select the alias of a new address book entry, confirming the
+    /// overwrite of any existing alias unless forced.
+    fn select_book_alias<U: WalletIo>(
+        &self,
+        alias: Alias,
+        alias_for: &str,
+        force: bool,
+    ) -> Option<Alias> {
+        // abort if the alias is reserved or empty
+        if Alias::is_reserved(&alias).is_some() {
+            println!("The alias {} is reserved", alias);
+            return None;
+        }
+        if alias.is_empty() {
+            eprintln!("Empty alias given.");
+            return None;
+        }
+        if self.contains_alias(&alias) && !force {
+            match U::show_overwrite_confirmation(&alias, alias_for) {
+                ConfirmationResponse::Replace => {}
+                ConfirmationResponse::Reselect(new_alias) => {
+                    return self
+                        .select_book_alias::<U>(new_alias, alias_for, false);
+                }
+                ConfirmationResponse::Skip => return None,
+            }
+        }
+        Some(alias)
+    }
+
+    /// Remove the contact or watch-only account with the given alias.
+    /// Returns whether there was one.
+    pub fn remove_book_entry(&mut self, alias: &Alias) -> bool {
+        self.address_book.remove(alias)
+    }
+
     /// Check if any map of the wallet contains the given alias
     pub fn contains_alias(&self, alias: &Alias) -> bool {
         self.payment_addrs.contains_key(alias)
@@ -565,6 +678,7 @@ impl Store {
             || self.pkhs.values().contains(alias)
             || self.public_keys.contains_key(alias)
             || self.derivation_paths.contains_key(alias)
+            || self.address_book.contains_alias(alias)
     }
 
     /// Completely remove the given alias from all maps in the wallet
@@ -577,6 +691,7 @@ impl Store {
         self.pkhs.retain(|_key, val| val != alias);
         self.public_keys.remove(alias);
         self.derivation_paths.remove(alias);
+        self.address_book.remove(alias);
     }
 
     /// Extend this store from another store (typically pre-genesis).
@@ -593,6 +708,7 @@ impl Store {
             pkhs,
             validator_data: _,
             address_vp_types,
+            address_book,
         } = self;
         view_keys.extend(store.view_keys);
         spend_keys.extend(store.spend_keys);
@@ -603,6 +719,7 @@ impl Store {
         addresses.extend(store.addresses);
         pkhs.extend(store.pkhs);
         address_vp_types.extend(store.address_vp_types);
+        address_book.extend(store.address_book);
     }
 
     /// Extend this store from pre-genesis validator wallet.
@@ -685,6 +802,33 @@ impl Store {
     pub fn encode(&self) -> Vec<u8> {
         toml::to_vec(self).expect("Serializing of store shouldn't fail")
     }
+
+    /// Encrypt all the raw secret and spending keys with the given password.
+    pub fn encrypt_keypairs(&mut self, password: &Zeroizing<String>) {
+        for keypair in self.secret_keys.values_mut() {
+            keypair.encrypt(password);
+        }
+        for keypair in self.spend_keys.values_mut() {
+            keypair.encrypt(password);
+        }
+    }
+
+    /// Decrypt the secret and spending keys that are encrypted with the given
+    /// password. Returns the aliases of the keys that remain encrypted.
+    pub fn decrypt_keypairs(
+        &mut self,
+        password: &Zeroizing<String>,
+    ) -> Vec<Alias> {
+        let secret_keys =
+            self.secret_keys.iter_mut().filter_map(|(alias, keypair)| {
+                (!keypair.decrypt(password)).then(|| alias.clone())
+            });
+        let spend_keys =
+            self.spend_keys.iter_mut().filter_map(|(alias, keypair)| {
+                (!keypair.decrypt(password)).then(|| alias.clone())
+            });
+        secret_keys.chain(spend_keys).collect()
+    }
 }
 
 /// Generate a new secret key from the seed.
@@ -724,6 +868,28 @@ pub fn derive_hd_secret_key(
     }
 }
 
+/// Generate a new spending key from the seed, following the ZIP-32 derivation
+/// of shielded keys.
+pub fn derive_hd_spending_key(
+    seed: &[u8],
+    derivation_path: DerivationPath,
+) -> ExtendedSpendingKey {
+    let master = masp_primitives::zip32::ExtendedSpendingKey::master(seed);
+    let indexes = derivation_path
+        .path()
+        .iter()
+        .map(|idx| {
+            if idx.is_hardened() {
+                masp_primitives::zip32::ChildIndex::Hardened(idx.to_u32())
+            } else {
+                masp_primitives::zip32::ChildIndex::NonHardened(idx.to_u32())
+            }
+        })
+        .collect_vec();
+    masp_primitives::zip32::ExtendedSpendingKey::from_path(&master, &indexes)
+        .into()
+}
+
 impl Display for AddressVpType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
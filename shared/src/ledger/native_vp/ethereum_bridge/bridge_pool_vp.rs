@@ -16,23 +16,33 @@ use std::collections::BTreeSet;
 use std::marker::PhantomData;
 
 use borsh::BorshDeserialize;
-use eyre::eyre;
+use eyre::{eyre, WrapErr};
 use namada_core::hints;
 use namada_core::ledger::eth_bridge::storage::bridge_pool::{
-    get_pending_key, is_bridge_pool_key, BRIDGE_POOL_ADDRESS,
+    get_pending_key, get_replaced_key, is_bridge_pool_key, BRIDGE_POOL_ADDRESS,
+};
+use namada_core::ledger::eth_bridge::storage::flow_control::{
+    self, Direction, EpochFlow,
 };
 use namada_core::ledger::eth_bridge::storage::whitelist;
 use namada_core::ledger::eth_bridge::ADDRESS as BRIDGE_ADDRESS;
 use namada_ethereum_bridge::parameters::read_native_erc20_address;
 use namada_ethereum_bridge::storage::wrapped_erc20s;
+use namada_sdk::tx::TX_BRIDGE_POOL_BUMP_FEE_WASM;
 
 use crate::ledger::native_vp::{Ctx, NativeVp, StorageReader};
 use crate::ledger::storage::traits::StorageHasher;
 use crate::ledger::storage::{DBIter, DB};
-use crate::proto::Tx;
+use crate::ledger::storage_api::StorageRead;
+use crate::proto::{Section, Tx};
 use crate::types::address::{Address, InternalAddress};
-use crate::types::eth_bridge_pool::{PendingTransfer, TransferToEthereumKind};
+use crate::types::eth_abi::Encode;
+use crate::types::eth_bridge_pool::{
+    GasFeeBump, PendingTransfer, TransferToEthereumKind,
+};
 use crate::types::ethereum_events::EthAddress;
+use crate::types::hash::Hash;
+use crate::types::keccak::KeccakHash;
 use crate::types::storage::Key;
 use crate::types::token::{balance_key, Amount};
 use crate::vm::WasmCacheAccess;
@@ -311,6 +321,211 @@ where
         Ok(true)
     }
 
+    /// Check that outbound transfers have not been paused, and that
+    /// the transferred amount was recorded in the outflow of the asset
+    /// during the current epoch, without exceeding its outflow limit.
+    fn check_outflow(
+        &self,
+        transfer: &PendingTransfer,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool, Error> {
+        let asset = &transfer.transfer.asset;
+        let pre = self.ctx.pre();
+
+        let other_flows_changed = keys_changed
+            .iter()
+            .filter_map(flow_control::is_flow_key)
+            .any(|flow| flow != (*asset, Direction::Outbound));
+        if other_flows_changed {
+            tracing::debug!(
+                ?transfer,
+                "Only the outflow of the transferred asset may be changed"
+            );
+            return Ok(false);
+        }
+
+        if flow_control::is_paused(&pre, Direction::Outbound)
+            .wrap_err("Failed to read the outbound pause flag")?
+        {
+            tracing::debug!(
+                ?transfer,
+                "Transfers to Ethereum are currently paused"
+            );
+            return Ok(false);
+        }
+
+        let flow_key = flow_control::flow_key(asset, Direction::Outbound);
+        let limit = flow_control::read_limit(&pre, asset, Direction::Outbound)
+            .wrap_err("Failed to read the outflow limit")?;
+        let Some(limit) = limit else {
+            // NB: flows are only tracked for assets with a limit
+            return Ok(!keys_changed.contains(&flow_key));
+        };
+
+        let epoch = pre
+            .get_block_epoch()
+            .wrap_err("Failed to read the current epoch")?;
+        let expected_flow =
+            flow_control::read_flow(&pre, asset, Direction::Outbound)
+                .wrap_err("Failed to read the outflow")?
+                .record(epoch, transfer.transfer.amount)
+                .ok_or_else(|| eyre!("Overflowed the outflow of {asset}"))?;
+        if expected_flow.amount > limit {
+            tracing::debug!(
+                ?transfer,
+                outflow = %expected_flow.amount.to_string_native(),
+                limit = %limit.to_string_native(),
+                "The transfer exceeds the outflow limit of the asset in the \
+                 current epoch"
+            );
+            return Ok(false);
+        }
+
+        let recorded_flow: Option<EpochFlow> =
+            (&self.ctx).read_post_value(&flow_key)?;
+        if recorded_flow != Some(expected_flow) {
+            tracing::debug!(
+                ?transfer,
+                ?recorded_flow,
+                ?expected_flow,
+                "The outflow of the asset was not correctly recorded"
+            );
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Check if the code of the given tx is the fee bump wasm,
+    /// as whitelisted in storage.
+    fn is_fee_bump(&self, tx: &Tx) -> Result<bool, Error> {
+        let Some(code_hash) = tx
+            .get_section(tx.code_sechash())
+            .and_then(|section| Section::code_sec(section.as_ref()))
+            .map(|code| code.code.hash())
+        else {
+            return Ok(false);
+        };
+        let bump_code_hash: Option<Hash> = (&self.ctx)
+            .read_pre_value(&Key::wasm_hash(TX_BRIDGE_POOL_BUMP_FEE_WASM))?;
+        Ok(bump_code_hash == Some(code_hash))
+    }
+
+    /// Validate the replacement of a transfer in the pool with an
+    /// identical transfer paying a higher gas fee.
+    fn validate_fee_bump(
+        &self,
+        bump: &GasFeeBump,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool, Error> {
+        let Some(fee_increase) = bump.fee_increase() else {
+            tracing::debug!(
+                ?bump,
+                "The gas fee of a transfer in the Bridge pool can only be \
+                 increased"
+            );
+            return Ok(false);
+        };
+        let replaced = &bump.transfer;
+        let bumped = bump.bumped_transfer();
+        let replaced_key = get_pending_key(replaced);
+        let bumped_key = get_pending_key(&bumped);
+        let replacement_key = get_replaced_key(&replaced.keccak256());
+
+        // check that the replaced transfer is in the pool, and
+        // that the transfer replacing it is not
+        let replaced_pre: Option<PendingTransfer> =
+            (&self.ctx).read_pre_value(&replaced_key)?;
+        if replaced_pre.is_none() {
+            tracing::debug!(
+                ?bump,
+                "The transfer to be replaced is not in the Bridge pool"
+            );
+            return Ok(false);
+        }
+        let bumped_pre: Option<PendingTransfer> =
+            (&self.ctx).read_pre_value(&bumped_key)?;
+        if bumped_pre.is_some() {
+            tracing::debug!(
+                ?bump,
+                "The transfer with the bumped fee is already in the Bridge \
+                 pool"
+            );
+            return Ok(false);
+        }
+
+        // check that only the expected keys of the pool were changed
+        for key in keys_changed.iter().filter(|k| is_bridge_pool_key(k)) {
+            if *key != replaced_key
+                && *key != bumped_key
+                && *key != replacement_key
+            {
+                tracing::debug!(
+                    ?bump,
+                    %key,
+                    "Rejecting fee bump as it is attempting to change an \
+                     incorrect key in the Ethereum bridge pool"
+                );
+                return Ok(false);
+            }
+        }
+        if keys_changed
+            .iter()
+            .any(|key| flow_control::is_flow_key(key).is_some())
+        {
+            tracing::debug!(
+                ?bump,
+                "Fee bumps cannot change the flows of assets across the \
+                 Ethereum bridge"
+            );
+            return Ok(false);
+        }
+
+        // check that the replaced transfer was swapped for the bumped one
+        let replaced_post: Option<PendingTransfer> =
+            (&self.ctx).read_post_value(&replaced_key)?;
+        let bumped_post: Option<PendingTransfer> =
+            (&self.ctx).read_post_value(&bumped_key)?;
+        let replacement_post: Option<KeccakHash> =
+            (&self.ctx).read_post_value(&replacement_key)?;
+        if replaced_post.is_some()
+            || bumped_post.as_ref() != Some(&bumped)
+            || replacement_post != Some(bumped.keccak256())
+        {
+            tracing::debug!(
+                ?bump,
+                "The transfer was not correctly replaced in the Bridge pool"
+            );
+            return Ok(false);
+        }
+
+        // check that the fee increase was escrowed
+        let gas_check = EscrowDelta {
+            token: Cow::Borrowed(&bumped.gas_fee.token),
+            payer_account: &bumped.gas_fee.payer,
+            escrow_account: &BRIDGE_POOL_ADDRESS,
+            expected_debit: fee_increase,
+            expected_credit: fee_increase,
+            transferred_amount: &fee_increase,
+            _kind: PhantomData,
+        };
+        if !gas_check.validate(keys_changed) {
+            tracing::debug!(?bump, "Missing storage modifications in the pool");
+            return Ok(false);
+        }
+        let wnam_address = read_native_erc20_address(&self.ctx.pre())?;
+        self.check_gas_escrow(&wnam_address, &bumped, gas_check)
+            .map(|ok| {
+                if ok {
+                    tracing::info!(
+                        "The Ethereum bridge pool VP accepted the fee bump \
+                         {:?}.",
+                        bump
+                    );
+                }
+                ok
+            })
+    }
+
     /// Deteremine the debit and credit amounts that should be checked.
     fn determine_escrow_checks<'trans, 'this: 'trans>(
         &'this self,
@@ -540,6 +755,12 @@ where
         let Some(tx_data) = tx.data() else {
             return Err(eyre!("No transaction data found").into());
         };
+        if self.is_fee_bump(tx)? {
+            let bump: GasFeeBump =
+                BorshDeserialize::try_from_slice(&tx_data[..])
+                    .map_err(|e| Error(e.into()))?;
+            return self.validate_fee_bump(&bump, keys_changed);
+        }
         let transfer: PendingTransfer =
             BorshDeserialize::try_from_slice(&tx_data[..])
                 .map_err(|e| Error(e.into()))?;
@@ -601,6 +822,10 @@ where
             );
             return Ok(false);
         }
+        // check that the transfer is within the outflow limits
+        if !self.check_outflow(&transfer, keys_changed)? {
+            return Ok(false);
+        }
         // check that gas was correctly escrowed.
         if !self.check_gas_escrow(
             &wnam_address,
@@ -677,6 +902,8 @@ mod test_bridge_pool_vp {
     const ESCROWED_NUTS: u64 = 1_000;
     const GAS_FEE: u64 = 100;
     const TOKENS: u64 = 100;
+    /// Stand-in for the fee bump wasm code
+    const BUMP_FEE_CODE: &[u8] = b"tx_bridge_pool_bump_fee";
 
     /// A set of balances for an address
     struct Balance {
@@ -904,6 +1131,7 @@ mod test_bridge_pool_vp {
         let config = EthereumBridgeParams {
             erc20_whitelist: vec![],
             eth_start_height: Default::default(),
+            bridge_pool_expiry: None,
             min_confirmations: Default::default(),
             contracts: Contracts {
                 native_erc20: wnam(),
@@ -1066,6 +1294,68 @@ mod test_bridge_pool_vp {
         );
     }
 
+    /// Test that a transfer to the pool may not tamper with the
+    /// flow of any other asset, or with the inflow of the transferred
+    /// asset, alongside its own outflow.
+    #[test]
+    fn test_other_flows_changed_rejected() {
+        let tampered_flows = [
+            flow_control::flow_key(&EthAddress([1; 20]), Direction::Outbound),
+            flow_control::flow_key(&EthAddress([1; 20]), Direction::Inbound),
+            flow_control::flow_key(&ASSET, Direction::Inbound),
+        ];
+        for tampered_flow in tampered_flows {
+            assert_bridge_pool(
+                SignedAmount::Negative(GAS_FEE.into()),
+                SignedAmount::Positive(GAS_FEE.into()),
+                SignedAmount::Negative(TOKENS.into()),
+                SignedAmount::Positive(TOKENS.into()),
+                |transfer, log| {
+                    log.write(
+                        &get_pending_key(transfer),
+                        transfer.serialize_to_vec(),
+                    )
+                    .unwrap();
+                    // reset the flow of another asset, to dodge its limit
+                    log.write(
+                        &tampered_flow,
+                        EpochFlow::default().serialize_to_vec(),
+                    )
+                    .unwrap();
+                    BTreeSet::from([get_pending_key(transfer), tampered_flow])
+                },
+                Expect::False,
+            );
+        }
+    }
+
+    /// Test that the outflow of an asset without a limit may not be
+    /// recorded, since flows are only tracked for limited assets.
+    #[test]
+    fn test_unlimited_outflow_recorded_rejected() {
+        assert_bridge_pool(
+            SignedAmount::Negative(GAS_FEE.into()),
+            SignedAmount::Positive(GAS_FEE.into()),
+            SignedAmount::Negative(TOKENS.into()),
+            SignedAmount::Positive(TOKENS.into()),
+            |transfer, log| {
+                log.write(
+                    &get_pending_key(transfer),
+                    transfer.serialize_to_vec(),
+                )
+                .unwrap();
+                let flow_key =
+                    flow_control::flow_key(&ASSET, Direction::Outbound);
+                let flow = EpochFlow::default()
+                    .record(Default::default(), TOKENS.into())
+                    .expect("Test failed");
+                log.write(&flow_key, flow.serialize_to_vec()).unwrap();
+                BTreeSet::from([get_pending_key(transfer), flow_key])
+            },
+            Expect::False,
+        );
+    }
+
     /// Test that if the balance for the gas payer
     /// was not correctly adjusted, reject
     #[test]
@@ -1941,4 +2231,172 @@ mod test_bridge_pool_vp {
 
         assert!(!delta.validate(&some_changed_keys));
     }
+
+    /// Helper function that tests bumping the gas fee of the transfer
+    /// in the initial pool to `new_fee`, escrowing `fee_delta` from the
+    /// payer into the Bridge pool.
+    fn assert_fee_bump(
+        new_fee: Amount,
+        fee_delta: Amount,
+        record_replacement: bool,
+        bump_code: bool,
+        expect: Expect,
+    ) {
+        // setup
+        let mut wl_storage = setup_storage();
+        let tx = Tx::from_type(TxType::Raw);
+        wl_storage
+            .storage
+            .write(
+                &Key::wasm_hash(TX_BRIDGE_POOL_BUMP_FEE_WASM),
+                Hash::sha256(BUMP_FEE_CODE).serialize_to_vec(),
+            )
+            .expect("Test failed");
+
+        // replace the transfer in the pool
+        let bump = GasFeeBump {
+            transfer: initial_pool(),
+            new_fee,
+        };
+        let bumped = bump.bumped_transfer();
+        let mut keys_changed = BTreeSet::from([
+            get_pending_key(&bump.transfer),
+            get_pending_key(&bumped),
+        ]);
+        wl_storage
+            .write_log
+            .delete(&get_pending_key(&bump.transfer))
+            .expect("Test failed");
+        wl_storage
+            .write_log
+            .write(&get_pending_key(&bumped), bumped.serialize_to_vec())
+            .expect("Test failed");
+        if record_replacement {
+            let key = get_replaced_key(&bump.transfer.keccak256());
+            wl_storage
+                .write_log
+                .write(&key, bumped.keccak256().serialize_to_vec())
+                .expect("Test failed");
+            keys_changed.insert(key);
+        }
+
+        // escrow the fee increase
+        let mut new_keys_changed = update_balances(
+            &mut wl_storage.write_log,
+            Balance {
+                asset: ASSET,
+                kind: TransferToEthereumKind::Erc20,
+                owner: bertha_address(),
+                gas: BERTHA_WEALTH.into(),
+                token: BERTHA_TOKENS.into(),
+            },
+            SignedAmount::Negative(fee_delta),
+            SignedAmount::Negative(Amount::zero()),
+        );
+        keys_changed.append(&mut new_keys_changed);
+        let mut new_keys_changed = update_balances(
+            &mut wl_storage.write_log,
+            Balance {
+                asset: ASSET,
+                kind: TransferToEthereumKind::Erc20,
+                owner: BRIDGE_POOL_ADDRESS,
+                gas: ESCROWED_AMOUNT.into(),
+                token: ESCROWED_TOKENS.into(),
+            },
+            SignedAmount::Positive(fee_delta),
+            SignedAmount::Positive(Amount::zero()),
+        );
+        keys_changed.append(&mut new_keys_changed);
+        let verifiers = BTreeSet::default();
+
+        // create the data to be given to the vp
+        let vp = BridgePoolVp {
+            ctx: setup_ctx(
+                &tx,
+                &wl_storage.storage,
+                &wl_storage.write_log,
+                &keys_changed,
+                &verifiers,
+            ),
+        };
+
+        let mut tx = Tx::new(wl_storage.storage.chain_id.clone(), None);
+        let code: &[u8] = if bump_code {
+            BUMP_FEE_CODE
+        } else {
+            b"tx_bridge_pool"
+        };
+        tx.add_code(code.to_vec(), None);
+        tx.add_data(bump);
+
+        let res = vp.validate_tx(&tx, &keys_changed, &verifiers);
+        match expect {
+            Expect::True => assert!(res.expect("Test failed")),
+            Expect::False => assert!(!res.expect("Test failed")),
+            Expect::Error => assert!(res.is_err()),
+        }
+    }
+
+    /// Test that replacing a transfer in the pool with a higher fee,
+    /// and escrowing the fee increase, passes the vp
+    #[test]
+    fn test_fee_bump_happy_flow() {
+        assert_fee_bump(
+            GAS_FEE.into(),
+            GAS_FEE.into(),
+            true,
+            true,
+            Expect::True,
+        );
+    }
+
+    /// Test that the gas fees of a transfer cannot be decreased
+    #[test]
+    fn test_fee_bump_must_increase_fee() {
+        assert_fee_bump(
+            Amount::zero(),
+            Amount::zero(),
+            true,
+            true,
+            Expect::False,
+        );
+    }
+
+    /// Test that the whole fee increase must be escrowed
+    #[test]
+    fn test_fee_bump_incorrect_escrow() {
+        assert_fee_bump(
+            GAS_FEE.into(),
+            (GAS_FEE - 1).into(),
+            true,
+            true,
+            Expect::False,
+        );
+    }
+
+    /// Test that the replacement of a transfer must be recorded
+    #[test]
+    fn test_fee_bump_requires_replacement_record() {
+        assert_fee_bump(
+            GAS_FEE.into(),
+            GAS_FEE.into(),
+            false,
+            true,
+            Expect::False,
+        );
+    }
+
+    /// Test that fee bumps are only validated as such when the tx runs
+    /// the fee bump code, and are otherwise rejected as malformed
+    /// transfers to the pool
+    #[test]
+    fn test_fee_bump_requires_bump_code() {
+        assert_fee_bump(
+            GAS_FEE.into(),
+            GAS_FEE.into(),
+            true,
+            false,
+            Expect::Error,
+        );
+    }
 }
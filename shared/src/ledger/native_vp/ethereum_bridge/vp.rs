@@ -2,8 +2,11 @@
 use std::collections::{BTreeSet, HashSet};
 
 use eyre::{eyre, Result};
-use namada_core::ledger::eth_bridge::storage::{self, escrow_key};
+use namada_core::ledger::eth_bridge::storage::{
+    self, escrow_key, flow_control,
+};
 use namada_core::ledger::storage::traits::StorageHasher;
+use namada_core::ledger::storage_api::governance;
 use namada_core::ledger::{eth_bridge, storage as ledger_storage};
 use namada_core::types::address::Address;
 use namada_core::types::storage::Key;
@@ -79,6 +82,58 @@ where
             Ok(false)
         }
     }
+
+    /// Validate the changes to the flow control keys of the bridge.
+    ///
+    /// The pause flags and flow limits may only be changed by accepted
+    /// governance proposals, while the flows of assets to Ethereum may
+    /// only be changed alongside a Bridge pool transfer, which is
+    /// validated by the Bridge pool VP.
+    ///
+    /// Returns [`None`] if the changes are invalid, otherwise whether
+    /// any flow control key was changed.
+    fn check_flow_control_keys(
+        &self,
+        tx: &Tx,
+        keys_changed: &BTreeSet<Key>,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<Option<bool>, Error> {
+        let governance_changed =
+            keys_changed.iter().any(flow_control::is_governance_key);
+        if governance_changed {
+            let is_accepted = tx.data().map_or(false, |data| {
+                governance::is_proposal_accepted(&self.ctx.pre(), &data)
+                    .unwrap_or(false)
+            });
+            if !is_accepted {
+                tracing::debug!(
+                    "The flow control parameters of the Ethereum bridge can \
+                     only be changed by governance"
+                );
+                return Ok(None);
+            }
+        }
+        let flows_changed = keys_changed
+            .iter()
+            .any(|key| flow_control::is_flow_key(key).is_some());
+        if flows_changed
+            && !verifiers.contains(&storage::bridge_pool::BRIDGE_POOL_ADDRESS)
+        {
+            tracing::debug!(
+                "The flows of assets across the Ethereum bridge can only be \
+                 changed by Bridge pool transfers"
+            );
+            return Ok(None);
+        }
+        Ok(Some(governance_changed || flows_changed))
+    }
+}
+
+/// Check if the given key is a flow control key of the bridge.
+#[inline]
+fn is_flow_control_key(key: &Key) -> bool {
+    flow_control::is_governance_key(key)
+        || flow_control::is_flow_key(key).is_some()
 }
 
 impl<'a, DB, H, CA> NativeVp for EthBridge<'a, DB, H, CA>
@@ -101,7 +156,7 @@ where
     /// no wasm transactions should be able to modify those keys.
     fn validate_tx(
         &self,
-        _: &Tx,
+        tx: &Tx,
         keys_changed: &BTreeSet<Key>,
         verifiers: &BTreeSet<Address>,
     ) -> Result<bool, Self::Error> {
@@ -111,8 +166,29 @@ where
             "Ethereum Bridge VP triggered",
         );
 
-        if !validate_changed_keys(&self.ctx.storage.native_token, keys_changed)?
-        {
+        let flow_control_changed =
+            self.check_flow_control_keys(tx, keys_changed, verifiers)?;
+        let keys_changed: BTreeSet<_> = match flow_control_changed {
+            None => return Ok(false),
+            Some(false) => keys_changed.clone(),
+            Some(true) => {
+                let keys_changed: BTreeSet<_> = keys_changed
+                    .iter()
+                    .filter(|key| !is_flow_control_key(key))
+                    .cloned()
+                    .collect();
+                // only the flow control keys under our account changed
+                if !keys_changed.iter().any(storage::has_eth_addr_segment) {
+                    return Ok(true);
+                }
+                keys_changed
+            }
+        };
+
+        if !validate_changed_keys(
+            &self.ctx.storage.native_token,
+            &keys_changed,
+        )? {
             return Ok(false);
         }
 
@@ -228,6 +304,7 @@ mod tests {
         let config = EthereumBridgeParams {
             erc20_whitelist: vec![],
             eth_start_height: Default::default(),
+            bridge_pool_expiry: None,
             min_confirmations: Default::default(),
             contracts: Contracts {
                 native_erc20: wnam(),
@@ -1,4 +1,6 @@
 //! The ledger's protocol
+pub mod parallel;
+
 use std::collections::BTreeSet;
 
 use borsh_ext::BorshSerializeExt;
@@ -1108,7 +1110,9 @@ mod tests {
     use namada_core::types::keccak::keccak_hash;
     use namada_core::types::storage::BlockHeight;
     use namada_core::types::token::Amount;
-    use namada_core::types::vote_extensions::bridge_pool_roots::BridgePoolRootVext;
+    use namada_core::types::vote_extensions::bridge_pool_roots::{
+        state_root_msg, BridgePoolRootVext,
+    };
     use namada_core::types::vote_extensions::ethereum_events::EthereumEventsVext;
     use namada_core::types::voting_power::FractionalVotingPower;
     use namada_core::types::{address, key};
@@ -1221,10 +1225,20 @@ mod tests {
         let hot_key =
             &keys[&address::testing::established_address_2()].eth_bridge;
         let sig = Signed::<_, SignableEthMessage>::new(hot_key, to_sign).sig;
+        let state_root = wl_storage
+            .ethbridge_queries()
+            .get_state_root_at_height(100.into())
+            .unwrap();
+        let state_root_sig = Signed::<_, SignableEthMessage>::new(
+            hot_key,
+            state_root_msg(&state_root, 100.into()),
+        )
+        .sig;
         let vext = BridgePoolRootVext {
             block_height: BlockHeight(100),
             validator_addr: address::testing::established_address_2(),
             sig,
+            state_root_sig,
         }
         .sign(&signing_key);
         let tx = EthereumTxData::BridgePoolVext(vext);
@@ -0,0 +1,322 @@
+//! Optimistic parallel execution of the decrypted txs of a block.
+//!
+//! In the spirit of Block-STM, every tx of a batch is first executed
+//! speculatively, in parallel with the others, on a view of the write log of
+//! the block as it was before any of them was applied. The views share the
+//! state of the block rather than copying it. The keys read by each
+//! tx are recorded along the way. The txs are then committed in the order of
+//! the block. A speculative result is only kept if it observed the same state
+//! as a sequential execution would have, i.e. if none of the keys it read were
+//! changed by a tx committed before it, and if its replay protection entry and
+//! the generator of established addresses it used are unchanged. Otherwise,
+//! the tx is executed again on top of the current write log. The resulting
+//! state is thus identical to the one of a sequential execution.
+
+use std::collections::BTreeSet;
+
+use namada_core::ledger::gas::{Gas, TxGasMeter};
+use namada_core::types::address::EstablishedAddressGen;
+use namada_core::types::hash::Hash;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use super::{apply_wasm_tx, Result, ShellParams};
+use crate::ledger::storage::wl_storage::TempWlStorage;
+use crate::ledger::storage::write_log::{ReadSet, WriteLog};
+use crate::ledger::storage::{DBIter, Storage, StorageHasher, DB};
+use crate::proto::Tx;
+use crate::types::storage::{Key, TxIndex};
+use crate::types::transaction::TxResult;
+use crate::vm::wasm::{TxCache, VpCache};
+use crate::vm::WasmCacheAccess;
+
+/// A decrypted tx to be executed as part of a batch
+#[derive(Debug, Clone)]
+pub struct BatchTx {
+    /// The decrypted tx
+    pub tx: Tx,
+    /// The index of the tx in the block
+    pub tx_index: TxIndex,
+    /// The gas available to the tx, left over by its wrapper
+    pub gas_limit: Gas,
+}
+
+/// The outcome of the speculative execution of a tx
+#[derive(Debug)]
+pub struct SpeculativeTx {
+    result: Result<TxResult>,
+    gas_meter: TxGasMeter,
+    write_log: WriteLog,
+    read_set: ReadSet,
+    tx_hash: Hash,
+    replay_entry: Option<bool>,
+    base_address_gen: Option<EstablishedAddressGen>,
+}
+
+impl SpeculativeTx {
+    /// Check if the speculative execution observed the same state as a
+    /// sequential execution on top of the given write log would, where
+    /// `committed_keys` are the keys committed to the block since the
+    /// speculation started.
+    pub fn is_valid(
+        &self,
+        write_log: &WriteLog,
+        committed_keys: &BTreeSet<Key>,
+    ) -> bool {
+        if write_log.has_replay_protection_entry(&self.tx_hash)
+            != self.replay_entry
+        {
+            return false;
+        }
+        if self.generated_addresses()
+            && write_log.address_gen() != self.base_address_gen.as_ref()
+        {
+            return false;
+        }
+        !committed_keys
+            .iter()
+            .any(|key| self.read_set.is_affected_by(key))
+    }
+
+    /// Adopt the changes of the tx in the given write log, which must be in
+    /// between txs. Returns the result of the tx and its gas meter, as left
+    /// by [`super::apply_wasm_tx`]. The tx must then be either committed or
+    /// dropped, as usual.
+    pub fn apply(
+        self,
+        write_log: &mut WriteLog,
+    ) -> (Result<TxResult>, TxGasMeter) {
+        let generated_addresses = self.generated_addresses();
+        write_log.adopt_tx(self.write_log, generated_addresses);
+        (self.result, self.gas_meter)
+    }
+
+    /// Check if the tx generated established addresses
+    fn generated_addresses(&self) -> bool {
+        self.write_log.address_gen() != self.base_address_gen.as_ref()
+    }
+}
+
+/// Execute the given txs in parallel, each on its own view of the given write
+/// log. The speculative results are returned in the same order as the txs.
+pub fn execute_speculatively<D, H, CA>(
+    txs: &[BatchTx],
+    storage: &Storage<D, H>,
+    write_log: &WriteLog,
+    vp_wasm_cache: &VpCache<CA>,
+    tx_wasm_cache: &TxCache<CA>,
+) -> Vec<SpeculativeTx>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+    CA: 'static + WasmCacheAccess + Sync,
+{
+    txs.par_iter()
+        .map(|batch_tx| {
+            let tx_hash = batch_tx.tx.raw_header_hash();
+            let mut wl_storage = TempWlStorage {
+                write_log: write_log.clone(),
+                storage,
+            };
+            wl_storage.write_log.track_reads();
+            let mut gas_meter =
+                TxGasMeter::new_from_sub_limit(batch_tx.gas_limit);
+            let result = apply_wasm_tx(
+                batch_tx.tx.clone(),
+                &batch_tx.tx_index,
+                ShellParams::new(
+                    &mut gas_meter,
+                    &mut wl_storage,
+                    &mut vp_wasm_cache.clone(),
+                    &mut tx_wasm_cache.clone(),
+                ),
+            );
+            let mut tx_write_log = wl_storage.write_log;
+            let read_set = tx_write_log.take_read_set().unwrap_or_default();
+            // the block write log can then be modified without being copied
+            tx_write_log.release_block_state();
+            SpeculativeTx {
+                result,
+                gas_meter,
+                write_log: tx_write_log,
+                read_set,
+                tx_hash,
+                replay_entry: write_log.has_replay_protection_entry(&tx_hash),
+                base_address_gen: write_log.address_gen().cloned(),
+            }
+        })
+        .collect()
+}
+
+/// Apply the given txs on top of the given write log, with the same outcome
+/// as applying them one after the other with [`super::apply_wasm_tx`]. The
+/// accepted txs are committed to the block write log, the others are
+/// dropped. Unlike in `FinalizeBlock`, the hashes of the txs are not written
+/// for replay protection. Returns the result of every tx.
+pub fn execute_batch<D, H, CA>(
+    txs: Vec<BatchTx>,
+    storage: &Storage<D, H>,
+    write_log: &mut WriteLog,
+    vp_wasm_cache: &mut VpCache<CA>,
+    tx_wasm_cache: &mut TxCache<CA>,
+) -> Vec<Result<TxResult>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+    CA: 'static + WasmCacheAccess + Sync,
+{
+    let speculations = execute_speculatively(
+        &txs,
+        storage,
+        write_log,
+        vp_wasm_cache,
+        tx_wasm_cache,
+    );
+    let mut committed_keys = BTreeSet::new();
+    txs.into_iter()
+        .zip(speculations)
+        .map(|(batch_tx, speculation)| {
+            let result = if speculation.is_valid(write_log, &committed_keys) {
+                speculation.apply(write_log).0
+            } else {
+                let mut wl_storage = TempWlStorage {
+                    write_log: std::mem::take(write_log),
+                    storage,
+                };
+                let result = apply_wasm_tx(
+                    batch_tx.tx,
+                    &batch_tx.tx_index,
+                    ShellParams::new(
+                        &mut TxGasMeter::new_from_sub_limit(batch_tx.gas_limit),
+                        &mut wl_storage,
+                        vp_wasm_cache,
+                        tx_wasm_cache,
+                    ),
+                );
+                *write_log = wl_storage.write_log;
+                result
+            };
+            match &result {
+                Ok(tx_result) if tx_result.is_accepted() => {
+                    committed_keys.extend(write_log.get_keys_with_precommit());
+                    write_log.commit_tx();
+                }
+                _ => write_log.drop_tx(),
+            }
+            result
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn speculation(write_log: WriteLog, read_set: ReadSet) -> SpeculativeTx {
+        SpeculativeTx {
+            result: Ok(TxResult::default()),
+            gas_meter: TxGasMeter::new_from_sub_limit(0.into()),
+            write_log,
+            read_set,
+            tx_hash: Hash::sha256(b"tx"),
+            replay_entry: None,
+            base_address_gen: None,
+        }
+    }
+
+    fn key(key: &str) -> Key {
+        Key::parse(key).expect("Test failed")
+    }
+
+    /// Test that a speculation is invalidated by committed writes to the keys
+    /// that it read, or that fall under the prefixes it iterated over.
+    #[test]
+    fn test_read_write_conflicts() {
+        let read_set = ReadSet {
+            keys: [key("read")].into(),
+            prefixes: [key("prefix")].into(),
+        };
+        let spec = speculation(WriteLog::default(), read_set);
+        let write_log = WriteLog::default();
+
+        assert!(spec.is_valid(&write_log, &BTreeSet::new()));
+        assert!(spec.is_valid(&write_log, &[key("other")].into()));
+        assert!(!spec.is_valid(&write_log, &[key("read")].into()));
+        assert!(!spec.is_valid(&write_log, &[key("prefix/sub")].into()));
+    }
+
+    /// Test that a speculation is invalidated if the replay protection entry
+    /// of its tx changed since it was executed.
+    #[test]
+    fn test_replay_protection_conflicts() {
+        let spec = speculation(WriteLog::default(), ReadSet::default());
+        let mut write_log = WriteLog::default();
+        assert!(spec.is_valid(&write_log, &BTreeSet::new()));
+
+        write_log
+            .finalize_tx_hash(spec.tx_hash)
+            .expect("Test failed");
+        assert!(!spec.is_valid(&write_log, &BTreeSet::new()));
+    }
+
+    /// Test that a speculation is only invalidated by new established
+    /// addresses if its tx generated addresses too.
+    #[test]
+    fn test_address_generation_conflicts() {
+        let address_gen = EstablishedAddressGen::new("test");
+        let vp_code_hash = Hash::sha256(b"vp");
+        let mut write_log = WriteLog::default();
+        let _ = write_log.init_account(&address_gen, vp_code_hash);
+        write_log.commit_tx();
+
+        let spec = speculation(WriteLog::default(), ReadSet::default());
+        assert!(spec.is_valid(&write_log, &BTreeSet::new()));
+
+        let mut tx_write_log = WriteLog::default();
+        let _ = tx_write_log.init_account(&address_gen, vp_code_hash);
+        let spec = speculation(tx_write_log, ReadSet::default());
+        assert!(spec.is_valid(&WriteLog::default(), &BTreeSet::new()));
+        assert!(!spec.is_valid(&write_log, &BTreeSet::new()));
+    }
+
+    /// Test that adopting a speculation which did not generate addresses keeps
+    /// the generator of the block, when a previous tx of the block generated
+    /// addresses.
+    #[test]
+    fn test_apply_keeps_address_gen() {
+        let address_gen = EstablishedAddressGen::new("test");
+        let vp_code_hash = Hash::sha256(b"vp");
+        let mut write_log = WriteLog::default();
+
+        // a first tx initializes an account
+        let (first, _) = write_log.init_account(&address_gen, vp_code_hash);
+        write_log.commit_tx();
+
+        // a plain tx speculated before the first tx was applied
+        let mut tx_write_log = WriteLog::default();
+        tx_write_log
+            .write(&key("changed"), vec![1])
+            .expect("Test failed");
+        let spec = speculation(tx_write_log, ReadSet::default());
+        assert!(spec.is_valid(&write_log, &BTreeSet::new()));
+        let _ = spec.apply(&mut write_log);
+        write_log.commit_tx();
+
+        // a last tx initializes another account
+        let (last, _) = write_log.init_account(&address_gen, vp_code_hash);
+        assert_ne!(first, last);
+    }
+
+    /// Test that adopting a speculation carries over the changes of its tx.
+    #[test]
+    fn test_apply_speculation() {
+        let changed = key("changed");
+        let mut tx_write_log = WriteLog::default();
+        tx_write_log.write(&changed, vec![1]).expect("Test failed");
+        let spec = speculation(tx_write_log, ReadSet::default());
+
+        let mut write_log = WriteLog::default();
+        let (result, _) = spec.apply(&mut write_log);
+        assert!(result.expect("Test failed").is_accepted());
+        assert_eq!(write_log.get_keys(), [changed].into());
+    }
+}
@@ -297,6 +297,7 @@ async fn test_bridge_pool_e2e() {
         |mut genesis| {
             genesis.ethereum_bridge_params = Some(EthereumBridgeParams {
                 eth_start_height: Default::default(),
+                bridge_pool_expiry: None,
                 min_confirmations: Default::default(),
                 contracts: Contracts {
                     native_erc20: wnam(),
@@ -488,6 +489,7 @@ async fn test_bridge_pool_e2e() {
 async fn test_wnam_transfer() -> Result<()> {
     let ethereum_bridge_params = EthereumBridgeParams {
         eth_start_height: Default::default(),
+        bridge_pool_expiry: None,
         min_confirmations: MinimumConfirmations::from(unsafe {
             // SAFETY: The only way the API contract of `NonZeroU64` can
             // be violated is if we construct values
@@ -595,6 +597,7 @@ async fn test_wnam_transfer() -> Result<()> {
 fn test_configure_oracle_from_storage() -> Result<()> {
     let ethereum_bridge_params = EthereumBridgeParams {
         eth_start_height: Default::default(),
+        bridge_pool_expiry: None,
         min_confirmations: MinimumConfirmations::from(unsafe {
             // SAFETY: The only way the API contract of `NonZeroU64` can
             // be violated is if we construct values
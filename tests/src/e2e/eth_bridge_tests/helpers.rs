@@ -82,6 +82,7 @@ impl EventsEndpointClient {
 pub fn setup_single_validator_test() -> Result<(Test, NamadaBgCmd)> {
     let ethereum_bridge_params = EthereumBridgeParams {
         eth_start_height: Default::default(),
+        bridge_pool_expiry: None,
         min_confirmations: MinimumConfirmations::from(unsafe {
             // SAFETY: The only way the API contract of `NonZeroU64` can
             // be violated is if we construct values
@@ -20,6 +20,7 @@ use eyre::eyre;
 use itertools::{Either, Itertools};
 use namada::types::chain::ChainId;
 use namada_apps::cli::context::ENV_VAR_CHAIN_ID;
+use namada_apps::client::tx::ENV_VAR_SIGN_WITHOUT_CONFIRMATION;
 use namada_apps::client::utils::{
     self, validator_pre_genesis_dir, validator_pre_genesis_txs_file,
 };
@@ -1052,6 +1053,7 @@ where
         .env("NAMADA_CMT_STDOUT", "true")
         .env("CMT_LOG_LEVEL", "info")
         .env("NAMADA_LOG_COLOR", "false")
+        .env(ENV_VAR_SIGN_WITHOUT_CONFIRMATION, "true")
         .current_dir(working_dir)
         .args(["--base-dir", &base_dir.as_ref().to_string_lossy()]);
 
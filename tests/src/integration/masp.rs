@@ -1,10 +1,23 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use color_eyre::eyre::Result;
 use color_eyre::owo_colors::OwoColorize;
-use namada_apps::node::ledger::shell::testing::client::run;
-use namada_apps::node::ledger::shell::testing::utils::{Bin, CapturedOutput};
+use namada::types::masp::{
+    ExtendedSpendingKey, ExtendedViewingKey, TransferSource, TransferTarget,
+};
+use namada::types::token::DenominatedAmount;
+use namada_apps::client::masp_device::mock::MockMaspDevice;
+use namada_apps::client::masp_device::{MaspDevice, DEFAULT_ACCOUNT};
+use namada_apps::client::tx::submit_transfer_with_device;
+use namada_apps::node::ledger::shell::testing::client::{context, run};
+use namada_apps::node::ledger::shell::testing::utils::{
+    Bin, CapturedOutput, TestingIo,
+};
+use namada_sdk::args::{InputAmount, TxBuilder};
 use namada_sdk::masp::fs::FsShieldedUtils;
+use namada_sdk::Namada;
 use test_log::test;
 
 use super::setup;
@@ -1248,3 +1261,110 @@ fn wrapper_fee_unshielding() -> Result<()> {
     assert!(tx_run);
     Ok(())
 }
+
+/// In this test we verify that the notes of a spending key held by a hardware
+/// wallet can be spent, with the spends authorized by the device.
+#[test]
+fn masp_device_transfer() -> Result<()> {
+    // This address doesn't matter for tests. But an argument is required.
+    let validator_one_rpc = "127.0.0.1:26567";
+    // Download the shielded pool parameters before starting node
+    let _ = FsShieldedUtils::new(PathBuf::new());
+    let (mut node, _services) = setup::setup()?;
+    // Wait till epoch boundary
+    node.next_epoch();
+    // Send 20 BTC from Albert to PA(A)
+    run(
+        &node,
+        Bin::Client,
+        vec![
+            "transfer",
+            "--source",
+            ALBERT,
+            "--target",
+            AA_PAYMENT_ADDRESS,
+            "--token",
+            BTC,
+            "--amount",
+            "20",
+            "--node",
+            validator_one_rpc,
+        ],
+    )?;
+    node.assert_success();
+
+    // Send 7 BTC from VK(A) to Christel, with the spends authorized by a
+    // software device holding SK(A)
+    let spending_key = ExtendedSpendingKey::from_str(A_SPENDING_KEY).unwrap();
+    let device = MaspDevice::new(MockMaspDevice::new(HashMap::from([(
+        DEFAULT_ACCOUNT,
+        spending_key.into(),
+    )])));
+    let chain_id = node.shell.lock().unwrap().chain_id.clone();
+    let namada = context(&node)?.to_sdk(&node, TestingIo);
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let (masp, christel, btc, albert_key) = {
+            let wallet = namada.wallet().await;
+            (
+                wallet.find_address(MASP).unwrap().into_owned(),
+                wallet.find_address(CHRISTEL).unwrap().into_owned(),
+                wallet.find_address(BTC).unwrap().into_owned(),
+                wallet.find_public_key(ALBERT_KEY).unwrap(),
+            )
+        };
+        let args = namada
+            .new_transfer(
+                TransferSource::Address(masp),
+                TransferTarget::Address(christel),
+                btc,
+                InputAmount::Unvalidated(DenominatedAmount::from_str("7")?),
+            )
+            .device_source(ExtendedViewingKey::from_str(AA_VIEWING_KEY)?)
+            .chain_id(chain_id)
+            .signing_keys(vec![albert_key]);
+        submit_transfer_with_device(&namada, args, Some(&device)).await?;
+        Result::<()>::Ok(())
+    })?;
+    node.assert_success();
+
+    // Assert BTC balance at VK(A) is 13
+    let captured = CapturedOutput::of(|| {
+        run(
+            &node,
+            Bin::Client,
+            vec![
+                "balance",
+                "--owner",
+                AA_VIEWING_KEY,
+                "--token",
+                BTC,
+                "--node",
+                validator_one_rpc,
+            ],
+        )
+    });
+    assert!(captured.result.is_ok());
+    assert!(captured.contains("btc: 13"));
+
+    // Assert BTC balance at Christel is 7 more than at genesis
+    let captured = CapturedOutput::of(|| {
+        run(
+            &node,
+            Bin::Client,
+            vec![
+                "balance",
+                "--owner",
+                CHRISTEL,
+                "--token",
+                BTC,
+                "--node",
+                validator_one_rpc,
+            ],
+        )
+    });
+    assert!(captured.result.is_ok());
+    assert!(captured.contains("btc: 1000007"));
+
+    Ok(())
+}
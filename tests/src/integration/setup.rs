@@ -5,6 +5,7 @@ use std::sync::{Arc, Mutex};
 
 use color_eyre::eyre::{eyre, Result};
 use namada_apps::cli::args;
+use namada_apps::client::tx::ENV_VAR_SIGN_WITHOUT_CONFIRMATION;
 use namada_apps::client::utils::PRE_GENESIS_DIR;
 use namada_apps::config;
 use namada_apps::config::genesis::chain::Finalized;
@@ -40,6 +41,8 @@ pub fn initialize_genesis() -> Result<(MockNode, MockServicesController)> {
         Ok(val) => val.to_ascii_lowercase() != "false",
         _ => false,
     };
+    // The client runs in-process without anyone to confirm signing
+    std::env::set_var(ENV_VAR_SIGN_WITHOUT_CONFIRMATION, "true");
     let test_dir = TestDir::new();
     let template_dir = working_dir.join(SINGLE_NODE_NET_GENESIS);
 
@@ -133,7 +136,8 @@ fn finalize_wallet(
         });
 
     // Try to load pre-genesis wallet
-    let pre_genesis_wallet = namada_apps::wallet::load(&pre_genesis_path);
+    let pre_genesis_wallet =
+        namada_apps::wallet::load_if_exists(&pre_genesis_path).unwrap();
     let chain_dir = global_args
         .base_dir
         .join(global_args.chain_id.as_ref().unwrap().as_str());
@@ -68,8 +68,11 @@ mod test_bridge_pool_vp {
             erc20_whitelist: vec![Erc20WhitelistEntry {
                 token_address: wnam(),
                 token_cap: Amount::from_u64(TOKEN_CAP).native_denominated(),
+                inflow_limit: None,
+                outflow_limit: None,
             }],
             eth_start_height: Default::default(),
+            bridge_pool_expiry: None,
             min_confirmations: Default::default(),
             contracts: Contracts {
                 native_erc20: wnam(),
@@ -4,6 +4,8 @@ pub mod tx_become_validator;
 pub mod tx_bond;
 #[cfg(feature = "tx_bridge_pool")]
 pub mod tx_bridge_pool;
+#[cfg(feature = "tx_bridge_pool_bump_fee")]
+pub mod tx_bridge_pool_bump_fee;
 #[cfg(feature = "tx_change_consensus_key")]
 pub mod tx_change_consensus_key;
 #[cfg(feature = "tx_change_validator_commission")]
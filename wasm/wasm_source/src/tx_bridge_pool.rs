@@ -1,6 +1,7 @@
 //! A tx for adding a transfer request across the Ethereum bridge
 //! into the bridge pool.
 use borsh::BorshDeserialize;
+use eth_bridge::storage::flow_control::{self, Direction};
 use eth_bridge::storage::{bridge_pool, native_erc20_key};
 use eth_bridge_pool::{GasFee, PendingTransfer, TransferToEthereum};
 use namada_tx_prelude::borsh_ext::BorshSerializeExt;
@@ -57,6 +58,8 @@ fn apply_tx(ctx: &mut Ctx, signed: Tx) -> TxResult {
         )?;
     }
     log_string("Escrow succeeded");
+    // account for the outbound flow of the asset, if it is limited
+    flow_control::record_flow(ctx, &asset, Direction::Outbound, amount)?;
     // add transfer into the pool
     let pending_key = bridge_pool::get_pending_key(&transfer);
     ctx.write_bytes(&pending_key, transfer.serialize_to_vec())
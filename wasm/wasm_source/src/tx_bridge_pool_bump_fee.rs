@@ -0,0 +1,50 @@
+//! A tx for replacing a transfer in the bridge pool with
+//! an identical transfer paying a higher gas fee.
+use eth_abi::Encode;
+use eth_bridge::storage::bridge_pool;
+use eth_bridge_pool::{GasFee, GasFeeBump};
+use namada_tx_prelude::borsh_ext::BorshSerializeExt;
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1038546)]
+fn apply_tx(ctx: &mut Ctx, signed: Tx) -> TxResult {
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let bump = GasFeeBump::try_from_slice(&data[..])
+        .map_err(|e| Error::wrap("Error deserializing GasFeeBump", e))?;
+    log_string("Received fee bump of a transfer in the pool.");
+    let fee_increase = bump
+        .fee_increase()
+        .ok_or_err_msg("The gas fee of a transfer can only be increased")?;
+    let bumped = bump.bumped_transfer();
+    // escrow the additional gas fees
+    let GasFee {
+        token: ref fee_token_addr,
+        ref payer,
+        ..
+    } = bumped.gas_fee;
+    token::transfer(
+        ctx,
+        payer,
+        &bridge_pool::BRIDGE_POOL_ADDRESS,
+        fee_token_addr,
+        fee_increase.native_denominated(),
+    )?;
+    log_string("Token transfer succeeded.");
+    // replace the transfer in the pool
+    ctx.delete(&bridge_pool::get_pending_key(&bump.transfer))
+        .wrap_err("Could not remove the replaced transfer from the pool")?;
+    ctx.write_bytes(
+        &bridge_pool::get_pending_key(&bumped),
+        bumped.serialize_to_vec(),
+    )
+    .wrap_err("Could not write transfer to bridge pool")?;
+    ctx.write(
+        &bridge_pool::get_replaced_key(&bump.transfer.keccak256()),
+        bumped.keccak256(),
+    )
+    .wrap_err("Could not record the replacement of the transfer")?;
+    Ok(())
+}